@@ -17,8 +17,9 @@
 use cf_chains::{RefundParametersRpc, VaultSwapExtraParametersRpc};
 use cf_rpc_apis::{
 	broker::{
+		AffiliateAccount, AffiliateAction, AffiliateShortId, BlockNumber, BrokerChannelDefaults,
 		BrokerRpcApiServer, DcaParameters, GetOpenDepositChannelsQuery, SwapDepositAddress,
-		TransactionInId, WithdrawFeesDetail,
+		SwapDepositAddressRequest, TransactionInId, WithdrawFeesDetail,
 	},
 	RpcApiError, RpcResult,
 };
@@ -32,7 +33,7 @@ use chainflip_api::{
 		state_chain_runtime::runtime_apis::{ChainAccounts, VaultAddresses, VaultSwapDetails},
 		AccountRole, AffiliateDetails, Affiliates, Asset, BasisPoints, CcmChannelMetadata,
 	},
-	rpc_types::H256,
+	rpc_types::{Bytes, H256, U256},
 	settings::StateChain,
 	AccountId32, AddressString, BrokerApi, ChannelActionType, ChannelId, DepositMonitorApi,
 	EthereumAddress, OperatorApi, SignedExtrinsicApi, StateChainApi,
@@ -103,6 +104,61 @@ impl BrokerRpcApiServer for RpcServerImpl {
 			.await?)
 	}
 
+	async fn request_swap_deposit_address_batch(
+		&self,
+		requests: Vec<SwapDepositAddressRequest>,
+	) -> RpcResult<Vec<SwapDepositAddress>> {
+		Ok(self.api.broker_api().request_swap_deposit_address_batch(requests).await?)
+	}
+
+	async fn request_swap_deposit_address_with_defaults(
+		&self,
+		source_asset: Asset,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		refund_address: AddressString,
+		min_price: U256,
+		broker_commission: Option<BasisPoints>,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+		affiliate_fees: Option<Affiliates<AccountId32>>,
+		retry_duration: Option<BlockNumber>,
+		dca_parameters: Option<DcaParameters>,
+	) -> RpcResult<SwapDepositAddress> {
+		Ok(self
+			.api
+			.broker_api()
+			.request_swap_deposit_address_with_defaults(
+				source_asset,
+				destination_asset,
+				destination_address,
+				refund_address,
+				min_price,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				retry_duration,
+				dca_parameters,
+			)
+			.await?)
+	}
+
+	async fn set_channel_defaults(
+		&self,
+		defaults: Option<BrokerChannelDefaults>,
+	) -> RpcResult<H256> {
+		Ok(self.api.broker_api().set_broker_channel_defaults(defaults).await?)
+	}
+
+	async fn get_channel_defaults(&self) -> RpcResult<Option<BrokerChannelDefaults>> {
+		Ok(self
+			.api
+			.raw_client()
+			.cf_broker_channel_defaults(self.api.state_chain_client.account_id(), None)
+			.await?)
+	}
+
 	async fn withdraw_fees(
 		&self,
 		asset: Asset,
@@ -225,6 +281,14 @@ impl BrokerRpcApiServer for RpcServerImpl {
 			.await?)
 	}
 
+	async fn list_affiliates(&self) -> RpcResult<Vec<AffiliateAccount>> {
+		Ok(self
+			.api
+			.raw_client()
+			.cf_affiliates(self.api.state_chain_client.account_id(), None)
+			.await?)
+	}
+
 	async fn affiliate_withdrawal_request(
 		&self,
 		affiliate_account_id: AccountId32,
@@ -232,6 +296,37 @@ impl BrokerRpcApiServer for RpcServerImpl {
 		Ok(self.api.broker_api().affiliate_withdrawal_request(affiliate_account_id).await?)
 	}
 
+	async fn affiliate_signing_payload(
+		&self,
+		short_id: AffiliateShortId,
+		action: AffiliateAction,
+	) -> RpcResult<H256> {
+		Ok(self
+			.api
+			.raw_client()
+			.cf_affiliate_signing_payload(
+				self.api.state_chain_client.account_id(),
+				short_id,
+				action,
+				None,
+			)
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("No affiliate is registered with this short id"))?)
+	}
+
+	async fn update_affiliate_withdrawal_address(
+		&self,
+		short_id: AffiliateShortId,
+		withdrawal_address: EthereumAddress,
+		signature: Bytes,
+	) -> RpcResult<AccountId32> {
+		Ok(self
+			.api
+			.broker_api()
+			.update_affiliate_withdrawal_address(short_id, withdrawal_address, signature)
+			.await?)
+	}
+
 	async fn vault_addresses(&self) -> RpcResult<VaultAddresses> {
 		Ok(self.api.raw_client().cf_vault_addresses(None).await?)
 	}
@@ -245,8 +245,10 @@ fn account_role_parser(s: &str) -> Result<AccountRole, String> {
 		Ok(AccountRole::LiquidityProvider)
 	} else if lower_str == "b" || lower_str == "broker" {
 		Ok(AccountRole::Broker)
+	} else if lower_str == "o" || lower_str == "operator" {
+		Ok(AccountRole::Operator)
 	} else {
-		Err(format!("{s} is not a valid role. The valid roles (with their shorthand input) are: 'Validator' (v), 'Liquidity Provider' (lp), 'Broker' (b)"))
+		Err(format!("{s} is not a valid role. The valid roles (with their shorthand input) are: 'Validator' (v), 'Liquidity Provider' (lp), 'Broker' (b), 'Operator' (o)"))
 	}
 }
 
@@ -242,6 +242,7 @@ impl LpRpcApiServer for RpcServerImpl {
 		sell_amount: NumberOrHex,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: Option<WaitFor>,
+		expiry_block: Option<BlockNumber>,
 	) -> RpcResult<ApiWaitForResult<Vec<LimitOrder>>> {
 		Ok(self
 			.api
@@ -253,6 +254,7 @@ impl LpRpcApiServer for RpcServerImpl {
 				id.try_into()?,
 				tick,
 				try_parse_number_or_hex(sell_amount)?,
+				expiry_block,
 				dispatch_at,
 				wait_for.unwrap_or_default(),
 			)
@@ -17,7 +17,7 @@
 use crate::RpcResult;
 
 use cf_chains::{eth::Address as EthereumAddress, CcmChannelMetadata, VaultSwapExtraParametersRpc};
-use cf_rpc_types::{AccountId32, BlockUpdate, H256};
+use cf_rpc_types::{AccountId32, BlockUpdate, Bytes, H256, U256};
 use jsonrpsee::proc_macros::rpc;
 
 pub use cf_primitives::DcaParameters;
@@ -42,6 +42,44 @@ pub trait BrokerRpcApi {
 		dca_parameters: Option<DcaParameters>,
 	) -> RpcResult<SwapDepositAddress>;
 
+	/// Opens up to [MAX_BATCHED_DEPOSIT_CHANNELS] deposit channels in a single extrinsic. Either
+	/// all of the channels are opened or none of them are.
+	#[method(name = "request_swap_deposit_address_batch", aliases = ["broker_requestSwapDepositAddressBatch"])]
+	async fn request_swap_deposit_address_batch(
+		&self,
+		requests: Vec<SwapDepositAddressRequest>,
+	) -> RpcResult<Vec<SwapDepositAddress>>;
+
+	/// Like `request_swap_deposit_address`, but the broker commission, boost fee, refund retry
+	/// duration and DCA parameters fall back to the broker's channel defaults if they are omitted.
+	/// See `set_channel_defaults`.
+	#[method(name = "request_swap_deposit_address_with_defaults", aliases = ["broker_requestSwapDepositAddressWithDefaults"])]
+	async fn request_swap_deposit_address_with_defaults(
+		&self,
+		source_asset: Asset,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		refund_address: AddressString,
+		min_price: U256,
+		broker_commission: Option<BasisPoints>,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+		affiliate_fees: Option<Affiliates<AccountId32>>,
+		retry_duration: Option<BlockNumber>,
+		dca_parameters: Option<DcaParameters>,
+	) -> RpcResult<SwapDepositAddress>;
+
+	/// Registers (or, if `None`, removes) the parameters that channels opened through
+	/// `request_swap_deposit_address_with_defaults` fall back to.
+	#[method(name = "set_channel_defaults", aliases = ["broker_setChannelDefaults"])]
+	async fn set_channel_defaults(
+		&self,
+		defaults: Option<BrokerChannelDefaults>,
+	) -> RpcResult<H256>;
+
+	#[method(name = "get_channel_defaults", aliases = ["broker_getChannelDefaults"])]
+	async fn get_channel_defaults(&self) -> RpcResult<Option<BrokerChannelDefaults>>;
+
 	#[method(name = "withdraw_fees", aliases = ["broker_withdrawFees"])]
 	async fn withdraw_fees(
 		&self,
@@ -98,12 +136,37 @@ pub trait BrokerRpcApi {
 		affiliate: Option<AccountId32>,
 	) -> RpcResult<Vec<(AccountId32, AffiliateDetails)>>;
 
+	/// Lists the broker's affiliates, with their short ids and the fees they have earned but not
+	/// yet withdrawn.
+	#[method(name = "list_affiliates", aliases = ["broker_listAffiliates"])]
+	async fn list_affiliates(&self) -> RpcResult<Vec<AffiliateAccount>>;
+
 	#[method(name = "affiliate_withdrawal_request", aliases = ["broker_affiliateWithdrawalRequest"])]
 	async fn affiliate_withdrawal_request(
 		&self,
 		affiliate_account_id: AccountId32,
 	) -> RpcResult<WithdrawFeesDetail>;
 
+	/// Returns the hash that the affiliate has to sign, using `personal_sign` with the key of its
+	/// current withdrawal address, to authorise `action`.
+	#[method(name = "get_affiliate_signing_payload", aliases = ["broker_getAffiliateSigningPayload"])]
+	async fn affiliate_signing_payload(
+		&self,
+		short_id: AffiliateShortId,
+		action: AffiliateAction,
+	) -> RpcResult<H256>;
+
+	/// Updates the withdrawal address of one of the broker's affiliates. The update has to be
+	/// signed by the affiliate's current withdrawal address, see `get_affiliate_signing_payload`.
+	/// Returns the affiliate's account id.
+	#[method(name = "update_affiliate_withdrawal_address", aliases = ["broker_updateAffiliateWithdrawalAddress"])]
+	async fn update_affiliate_withdrawal_address(
+		&self,
+		short_id: AffiliateShortId,
+		withdrawal_address: EthereumAddress,
+		signature: Bytes,
+	) -> RpcResult<AccountId32>;
+
 	#[method(name = "get_vault_addresses", aliases = ["broker_getVaultAddresses"])]
 	async fn vault_addresses(&self) -> RpcResult<VaultAddresses>;
 
@@ -16,23 +16,41 @@
 
 use anyhow::bail;
 
-use crate::{H256, U256};
-use cf_chains::{Chain, ChainCrypto, ChannelRefundParameters, ForeignChain};
-use cf_primitives::AffiliateShortId;
+use crate::{AccountId32, EthereumAddress, H256, U256};
+use cf_chains::{CcmChannelMetadata, Chain, ChainCrypto, ChannelRefundParameters, ForeignChain};
+use cf_primitives::DcaParameters;
 use cf_utilities::rpc::NumberOrHex;
 use sp_core::serde::{Deserialize, Serialize};
 use std::fmt;
 
 pub use cf_chains::{address::AddressString, RefundParametersRpc};
-pub use cf_primitives::{AccountRole, Affiliates, Asset, BasisPoints, ChannelId, SemVer};
-pub use pallet_cf_swapping::AffiliateDetails;
+pub use cf_primitives::{
+	AccountRole, AffiliateShortId, Affiliates, Asset, BasisPoints, BlockNumber, ChannelId, SemVer,
+};
+pub use pallet_cf_swapping::{AffiliateAction, AffiliateDetails, BrokerChannelDefaults};
 pub use state_chain_runtime::runtime_apis::{
-	ChainAccounts, ChannelActionType, CustomRuntimeApi, TransactionScreeningEvents, VaultAddresses,
-	VaultSwapDetails,
+	AffiliateInfo, ChainAccounts, ChannelActionType, CustomRuntimeApi, TransactionScreeningEvents,
+	VaultAddresses, VaultSwapDetails,
 };
 
 pub type RefundParameters = ChannelRefundParameters<AddressString>;
 
+pub use state_chain_runtime::constants::common::MAX_BATCHED_DEPOSIT_CHANNELS;
+
+/// The parameters of a deposit channel opened as part of a batch.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapDepositAddressRequest {
+	pub source_asset: Asset,
+	pub destination_asset: Asset,
+	pub destination_address: AddressString,
+	pub broker_commission: BasisPoints,
+	pub channel_metadata: Option<CcmChannelMetadata>,
+	pub boost_fee: Option<BasisPoints>,
+	pub affiliate_fees: Option<Affiliates<AccountId32>>,
+	pub refund_parameters: RefundParametersRpc,
+	pub dca_parameters: Option<DcaParameters>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SwapDepositAddress {
 	pub address: AddressString,
@@ -43,6 +61,23 @@ pub struct SwapDepositAddress {
 	pub refund_parameters: RefundParametersRpc,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AffiliateAccount {
+	pub account_id: AccountId32,
+	pub short_id: AffiliateShortId,
+	pub withdrawal_address: EthereumAddress,
+	/// The USDC fees earned by the affiliate that have not been withdrawn yet.
+	pub earned_fees: NumberOrHex,
+}
+
+impl From<AffiliateInfo> for AffiliateAccount {
+	fn from(
+		AffiliateInfo { account_id, short_id, withdrawal_address, earned_fees }: AffiliateInfo,
+	) -> Self {
+		Self { account_id, short_id, withdrawal_address, earned_fees: earned_fees.into() }
+	}
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WithdrawFeesDetail {
 	pub tx_hash: H256,
@@ -24,7 +24,7 @@ use std::ops::Range;
 
 pub use cf_chains::eth::Address as EthereumAddress;
 pub use cf_utilities::rpc::NumberOrHex;
-pub use sp_core::{bounded::BoundedVec, crypto::AccountId32, ConstU32, H256, U256};
+pub use sp_core::{bounded::BoundedVec, crypto::AccountId32, Bytes, ConstU32, H256, U256};
 pub use state_chain_runtime::{chainflip::BlockUpdate, Hash};
 
 /// Defines all broker related RPC types
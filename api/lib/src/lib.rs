@@ -29,7 +29,7 @@ use serde::Serialize;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 pub use sp_core::crypto::AccountId32;
-use sp_core::{ed25519::Public as EdPublic, sr25519::Public as SrPublic, Bytes, Pair, H256};
+use sp_core::{ed25519::Public as EdPublic, sr25519::Public as SrPublic, Bytes, Pair, H256, U256};
 pub use state_chain_runtime::chainflip::BlockUpdate;
 use state_chain_runtime::{opaque::SessionKeys, RuntimeCall};
 use zeroize::Zeroize;
@@ -265,6 +265,8 @@ pub trait OperatorApi: SignedExtrinsicApi + RotateSessionKeysApi + AuctionPhaseA
 				RuntimeCall::from(pallet_cf_swapping::Call::register_as_broker {}),
 			AccountRole::LiquidityProvider =>
 				RuntimeCall::from(pallet_cf_lp::Call::register_lp_account {}),
+			AccountRole::Operator =>
+				RuntimeCall::from(pallet_cf_validator::Call::register_as_operator {}),
 			AccountRole::Unregistered => bail!("Cannot register account role {:?}", role),
 		};
 
@@ -401,6 +403,143 @@ pub trait BrokerApi: SignedExtrinsicApi + StorageApi + Sized + Send + Sync + 'st
 			}
 		)
 	}
+
+	async fn request_swap_deposit_address_with_defaults(
+		&self,
+		source_asset: Asset,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		refund_address: AddressString,
+		min_price: U256,
+		broker_commission: Option<BasisPoints>,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+		affiliate_fees: Option<Affiliates<AccountId32>>,
+		retry_duration: Option<BlockNumber>,
+		dca_parameters: Option<DcaParameters>,
+	) -> Result<SwapDepositAddress> {
+		let (_tx_hash, events, header, ..) = self
+			.submit_signed_extrinsic_with_dry_run(
+				pallet_cf_swapping::Call::request_swap_deposit_address_with_broker_defaults {
+					source_asset,
+					destination_asset,
+					destination_address: destination_address
+						.try_parse_to_encoded_address(destination_asset.into())?,
+					broker_commission,
+					channel_metadata,
+					boost_fee,
+					affiliate_fees: affiliate_fees.unwrap_or_default(),
+					refund_address: refund_address
+						.try_parse_to_encoded_address(source_asset.into())?,
+					min_price,
+					retry_duration,
+					dca_parameters,
+				},
+			)
+			.await?
+			.until_in_block()
+			.await?;
+
+		extract_event!(
+			events,
+			state_chain_runtime::RuntimeEvent::Swapping,
+			pallet_cf_swapping::Event::SwapDepositAddressReady,
+			{
+				deposit_address,
+				channel_id,
+				source_chain_expiry_block,
+				channel_opening_fee,
+				refund_parameters,
+				..
+			},
+			SwapDepositAddress {
+				address: AddressString::from_encoded_address(deposit_address),
+				issued_block: header.number,
+				channel_id: *channel_id,
+				source_chain_expiry_block: (*source_chain_expiry_block).into(),
+				channel_opening_fee: (*channel_opening_fee).into(),
+				refund_parameters: refund_parameters
+					.map_address(|refund_address| {
+						AddressString::from_encoded_address(&refund_address)
+					}),
+			}
+		)
+	}
+
+	async fn request_swap_deposit_address_batch(
+		&self,
+		requests: Vec<SwapDepositAddressRequest>,
+	) -> Result<Vec<SwapDepositAddress>> {
+		if requests.is_empty() || requests.len() > MAX_BATCHED_DEPOSIT_CHANNELS as usize {
+			bail!("The batch must contain between 1 and {MAX_BATCHED_DEPOSIT_CHANNELS} requests");
+		}
+
+		let calls = requests
+			.into_iter()
+			.map(
+				|SwapDepositAddressRequest {
+				     source_asset,
+				     destination_asset,
+				     destination_address,
+				     broker_commission,
+				     channel_metadata,
+				     boost_fee,
+				     affiliate_fees,
+				     refund_parameters,
+				     dca_parameters,
+				 }| {
+					Ok(RuntimeCall::from(
+						pallet_cf_swapping::Call::request_swap_deposit_address_with_affiliates {
+							source_asset,
+							destination_asset,
+							destination_address: destination_address
+								.try_parse_to_encoded_address(destination_asset.into())?,
+							broker_commission,
+							channel_metadata,
+							boost_fee: boost_fee.unwrap_or_default(),
+							affiliate_fees: affiliate_fees.unwrap_or_default(),
+							refund_parameters: refund_parameters.try_map_address(|addr| {
+								addr.try_parse_to_encoded_address(source_asset.into())
+							})?,
+							dca_parameters,
+						},
+					))
+				},
+			)
+			.collect::<Result<Vec<_>>>()?;
+
+		let (_tx_hash, events, header, ..) = self
+			.submit_signed_extrinsic_with_dry_run(pallet_utility::Call::batch_all { calls })
+			.await?
+			.until_in_block()
+			.await?;
+
+		Ok(events
+			.iter()
+			.filter_map(|event| match event {
+				state_chain_runtime::RuntimeEvent::Swapping(
+					pallet_cf_swapping::Event::SwapDepositAddressReady {
+						deposit_address,
+						channel_id,
+						source_chain_expiry_block,
+						channel_opening_fee,
+						refund_parameters,
+						..
+					},
+				) => Some(SwapDepositAddress {
+					address: AddressString::from_encoded_address(deposit_address),
+					issued_block: header.number,
+					channel_id: *channel_id,
+					source_chain_expiry_block: (*source_chain_expiry_block).into(),
+					channel_opening_fee: (*channel_opening_fee).into(),
+					refund_parameters: refund_parameters.map_address(|refund_address| {
+						AddressString::from_encoded_address(&refund_address)
+					}),
+				}),
+				_ => None,
+			})
+			.collect())
+	}
 	async fn withdraw_fees(
 		&self,
 		asset: Asset,
@@ -533,6 +672,37 @@ pub trait BrokerApi: SignedExtrinsicApi + StorageApi + Sized + Send + Sync + 'st
 		)
 	}
 
+	async fn update_affiliate_withdrawal_address(
+		&self,
+		short_id: AffiliateShortId,
+		withdrawal_address: EthereumAddress,
+		signature: Bytes,
+	) -> Result<AccountId32> {
+		let (_, events, ..) = self
+			.submit_signed_extrinsic_with_dry_run(
+				pallet_cf_swapping::Call::update_affiliate_withdrawal_address {
+					broker_id: self.account_id(),
+					short_id,
+					withdrawal_address,
+					signature: signature
+						.0
+						.try_into()
+						.map_err(|_| anyhow!("The signature must be 65 bytes long"))?,
+				},
+			)
+			.await?
+			.until_in_block()
+			.await?;
+
+		extract_event!(
+			&events,
+			state_chain_runtime::RuntimeEvent::Swapping,
+			pallet_cf_swapping::Event::AffiliateWithdrawalAddressUpdated,
+			{ affiliate_id, .. },
+			affiliate_id.clone()
+		)
+	}
+
 	async fn set_vault_swap_minimum_broker_fee(
 		&self,
 		minimum_fee_bps: BasisPoints,
@@ -553,6 +723,27 @@ pub trait BrokerApi: SignedExtrinsicApi + StorageApi + Sized + Send + Sync + 'st
 			tx_hash
 		)
 	}
+
+	async fn set_broker_channel_defaults(
+		&self,
+		defaults: Option<BrokerChannelDefaults>,
+	) -> Result<H256> {
+		let (tx_hash, events, ..) = self
+			.submit_signed_extrinsic_with_dry_run(
+				pallet_cf_swapping::Call::set_broker_channel_defaults { defaults },
+			)
+			.await?
+			.until_in_block()
+			.await?;
+
+		extract_event!(
+			events,
+			state_chain_runtime::RuntimeEvent::Swapping,
+			pallet_cf_swapping::Event::BrokerChannelDefaultsSet,
+			{ .. },
+			tx_hash
+		)
+	}
 }
 
 #[async_trait]
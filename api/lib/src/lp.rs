@@ -361,6 +361,7 @@ pub trait LpApi: SignedExtrinsicApi + Sized + Send + Sync + 'static {
 		id: OrderId,
 		option_tick: Option<Tick>,
 		sell_amount: AssetAmount,
+		expiry_block: Option<BlockNumber>,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: WaitFor,
 	) -> Result<ApiWaitForResult<Vec<LimitOrder>>> {
@@ -372,6 +373,7 @@ pub trait LpApi: SignedExtrinsicApi + Sized + Send + Sync + 'static {
 				id,
 				option_tick,
 				sell_amount,
+				expiry_block,
 			},
 			dispatch_at,
 			wait_for,
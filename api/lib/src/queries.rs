@@ -92,13 +92,21 @@ impl QueryApi {
 		Ok(channels
 			.into_iter()
 			.filter_map(|(_, DepositChannelDetails { action, deposit_channel, .. })| match action {
-				pallet_cf_ingress_egress::ChannelAction::Swap { destination_asset, .. } =>
+				pallet_cf_ingress_egress::ChannelAction::Swap { destination_asset, .. } |
+				pallet_cf_ingress_egress::ChannelAction::SplitSwap {
+					destination_asset, ..
+				} => Some(SwapChannelInfo {
+					deposit_address: deposit_channel.address.to_humanreadable(network_environment),
+					source_asset: deposit_channel.asset.into(),
+					destination_asset,
+				}),
+				pallet_cf_ingress_egress::ChannelAction::FundAccount { .. } =>
 					Some(SwapChannelInfo {
 						deposit_address: deposit_channel
 							.address
 							.to_humanreadable(network_environment),
 						source_asset: deposit_channel.asset.into(),
-						destination_asset,
+						destination_asset: Asset::Flip,
 					}),
 				_ => None,
 			})
@@ -40,8 +40,15 @@ interface SolVaultSwapDetails {
   program_id: string;
   accounts: RpcAccountMeta[];
   data: string;
+  address_lookup_tables: RpcAddressLookupTable[];
+  transaction_message: string;
 }
 
+type RpcAddressLookupTable = {
+  key: string;
+  addresses: string[];
+};
+
 type RpcAccountMeta = {
   pubkey: string;
   is_signer: boolean;
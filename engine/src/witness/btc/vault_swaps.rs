@@ -188,6 +188,7 @@ pub fn try_extract_vault_swap_witness(
 		boost_fee: data.parameters.boost_fee.into(),
 		channel_id: Some(channel_id),
 		deposit_address: Some(vault_address.script_pubkey()),
+		refund_ccm_metadata: None,
 	})
 }
 
@@ -357,6 +358,7 @@ mod tests {
 				boost_fee: MOCK_SWAP_PARAMS.parameters.boost_fee.into(),
 				deposit_address: Some(vault_deposit_address.script_pubkey()),
 				channel_id: Some(CHANNEL_ID),
+				refund_ccm_metadata: None,
 			})
 		);
 	}
@@ -267,6 +267,7 @@ macro_rules! vault_deposit_witness {
 			refund_params: $params.refund_params,
 			channel_id: None,
 			deposit_address: None,
+			refund_ccm_metadata: None,
 		}
 	}
 }
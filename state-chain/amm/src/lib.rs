@@ -366,6 +366,39 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		self.range_orders.collect_and_burn(lp, tick_range.start, tick_range.end, size)
 	}
 
+	pub fn transfer_range_order(
+		&mut self,
+		from: &LiquidityProvider,
+		to: LiquidityProvider,
+		tick_range: core::ops::Range<Tick>,
+	) -> Result<(), range_orders::PositionError<range_orders::TransferError>> {
+		self.range_orders.transfer(from, to, tick_range.start, tick_range.end)
+	}
+
+	pub fn transfer_limit_order(
+		&mut self,
+		from: &LiquidityProvider,
+		to: LiquidityProvider,
+		order: Side,
+		tick: Tick,
+	) -> Result<(), limit_orders::PositionError<limit_orders::TransferError>> {
+		match order.to_sold_pair() {
+			Pairs::Base => self.limit_orders.transfer::<QuoteToBase>(from, to, tick),
+			Pairs::Quote => self.limit_orders.transfer::<BaseToQuote>(from, to, tick),
+		}
+	}
+
+	pub fn range_order_mint_quote(
+		&self,
+		tick_range: core::ops::Range<Tick>,
+		size: range_orders::Size,
+	) -> Result<
+		(PoolPairsMap<Amount>, range_orders::Liquidity),
+		range_orders::PositionError<range_orders::MintError<Infallible>>,
+	> {
+		self.range_orders.mint_quote(tick_range.start, tick_range.end, size)
+	}
+
 	pub fn range_order_liquidity_value(
 		&self,
 		tick_range: core::ops::Range<Tick>,
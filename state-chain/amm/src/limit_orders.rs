@@ -303,6 +303,12 @@ impl<T> PositionError<T> {
 	}
 }
 
+#[derive(Debug)]
+pub enum TransferError {
+	/// The recipient already has a position with the same key
+	PositionAlreadyExists,
+}
+
 #[derive(Debug)]
 pub enum BurnError {}
 
@@ -870,6 +876,32 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		))
 	}
 
+	/// Moves a position to a different liquidity provider without modifying it. The position keeps
+	/// its amounts and its share of any swaps that have already been executed against it, so
+	/// fees are not collected by this operation.
+	///
+	/// This function never panics.
+	pub(super) fn transfer<SD: SwapDirection>(
+		&mut self,
+		from: &LiquidityProvider,
+		to: LiquidityProvider,
+		tick: Tick,
+	) -> Result<(), PositionError<TransferError>> {
+		let sqrt_price = Self::validate_tick(tick)?;
+
+		let positions = &mut self.positions[!SD::INPUT_SIDE];
+
+		if positions.contains_key(&(sqrt_price, to.clone())) {
+			return Err(PositionError::Other(TransferError::PositionAlreadyExists))
+		}
+		let position = positions
+			.remove(&(sqrt_price, from.clone()))
+			.ok_or(PositionError::NonExistent)?;
+		positions.insert((sqrt_price, to), position);
+
+		Ok(())
+	}
+
 	/// Returns all the assets associated with a position
 	///
 	/// This function never panics.
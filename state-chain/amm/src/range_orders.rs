@@ -404,6 +404,12 @@ pub enum PositionError<T> {
 	Other(T),
 }
 
+#[derive(Debug)]
+pub enum TransferError {
+	/// The recipient already has a position with the same key
+	PositionAlreadyExists,
+}
+
 #[derive(Debug)]
 pub enum BurnError {
 	/// The ratio of assets removed from the position must match the ratio of assets in the
@@ -576,25 +582,7 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		let [option_initial_lower_delta, option_initial_upper_delta] =
 			[lower_tick, upper_tick].map(|tick| self.liquidity_map.get(&tick));
 
-		let minted_liquidity = self
-			.size_as_liquidity(lower_tick, upper_tick, size)
-			.ok_or(PositionError::Other(MintError::AssetRatioUnachieveable))
-			.and_then(|liquidity| {
-				if liquidity >
-					MAX_TICK_GROSS_LIQUIDITY -
-						[option_initial_lower_delta, option_initial_upper_delta]
-							.into_iter()
-							.filter_map(|option_tick_delta| {
-								option_tick_delta.map(|tick_delta| tick_delta.liquidity_gross)
-							})
-							.max()
-							.unwrap_or(0)
-				{
-					Err(PositionError::Other(MintError::MaximumGrossLiquidity))
-				} else {
-					Ok(liquidity)
-				}
-			})?;
+		let minted_liquidity = self.liquidity_to_mint(lower_tick, upper_tick, size)?;
 
 		if option_position.is_some() || minted_liquidity != 0 {
 			let mut position = option_position.cloned().unwrap_or_else(|| Position {
@@ -662,6 +650,52 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		}
 	}
 
+	/// Returns the liquidity a range order of the given size would mint, and the amounts that would
+	/// be debited for it, without changing the pool. These are the same amounts `collect_and_mint`
+	/// would debit.
+	///
+	/// This function never panics
+	pub(super) fn mint_quote(
+		&self,
+		lower_tick: Tick,
+		upper_tick: Tick,
+		size: Size,
+	) -> Result<(PoolPairsMap<Amount>, Liquidity), PositionError<MintError<Infallible>>> {
+		Self::validate_position_range(lower_tick, upper_tick)?;
+		let minted_liquidity = self.liquidity_to_mint(lower_tick, upper_tick, size)?;
+		let (amounts_required, _) =
+			self.inner_liquidity_to_amounts::<true>(minted_liquidity, lower_tick, upper_tick);
+		Ok((amounts_required, minted_liquidity))
+	}
+
+	fn liquidity_to_mint<E>(
+		&self,
+		lower_tick: Tick,
+		upper_tick: Tick,
+		size: Size,
+	) -> Result<Liquidity, PositionError<MintError<E>>> {
+		self.size_as_liquidity(lower_tick, upper_tick, size)
+			.ok_or(PositionError::Other(MintError::AssetRatioUnachieveable))
+			.and_then(|liquidity| {
+				if liquidity >
+					MAX_TICK_GROSS_LIQUIDITY -
+						[lower_tick, upper_tick]
+							.into_iter()
+							.filter_map(|tick| {
+								self.liquidity_map
+									.get(&tick)
+									.map(|tick_delta| tick_delta.liquidity_gross)
+							})
+							.max()
+							.unwrap_or(0)
+				{
+					Err(PositionError::Other(MintError::MaximumGrossLiquidity))
+				} else {
+					Ok(liquidity)
+				}
+			})
+	}
+
 	/// Calculates the fees owed to the specified position, resets the fees owed for that
 	/// position to zero, removes liquidity from the specified range-order, and returns the value of
 	/// the burnt liquidity in `Amounts` with the calculated amount of owed fees. If all the
@@ -1114,6 +1148,31 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		})
 	}
 
+	/// Moves a position to a different liquidity provider without modifying it. The position keeps
+	/// its liquidity and its fee growth checkpoints, so any uncollected fees move with it.
+	///
+	/// This function never panics
+	pub(super) fn transfer(
+		&mut self,
+		from: &LiquidityProvider,
+		to: LiquidityProvider,
+		lower_tick: Tick,
+		upper_tick: Tick,
+	) -> Result<(), PositionError<TransferError>> {
+		Self::validate_position_range(lower_tick, upper_tick)?;
+
+		if self.positions.contains_key(&(to.clone(), lower_tick, upper_tick)) {
+			return Err(PositionError::Other(TransferError::PositionAlreadyExists))
+		}
+		let position = self
+			.positions
+			.remove(&(from.clone(), lower_tick, upper_tick))
+			.ok_or(PositionError::NonExistent)?;
+		self.positions.insert((to, lower_tick, upper_tick), position);
+
+		Ok(())
+	}
+
 	/// Returns the current value of a position i.e. the assets you would receive by burning the
 	/// position, and the fees earned by the position since the last time it was updated/collected.
 	///
@@ -2,12 +2,14 @@ use cf_primitives::{AccountRole, Asset, AuthorityCount, FLIPPERINOS_PER_FLIP};
 use cf_traits::IncreaseOrDecrease;
 use codec::Encode;
 use frame_support::pallet_prelude::TransactionValidityError;
-use pallet_cf_flip::{FeeScalingRate, FeeScalingRateConfig};
+use pallet_cf_flip::{FeeScaledCallClasses, FeeScalingRate, FeeScalingRateConfig};
 use pallet_cf_pools::RangeOrderSize;
 use sp_block_builder::runtime_decl_for_block_builder::BlockBuilderV6;
 use sp_keyring::test::AccountKeyring;
 use sp_runtime::{generic::Era, MultiSignature};
-use state_chain_runtime::{Balance, Flip, Runtime, RuntimeCall, SignedPayload, System};
+use state_chain_runtime::{
+	chainflip::FeeScalingCallIndex, Balance, Flip, Runtime, RuntimeCall, SignedPayload, System,
+};
 
 pub fn apply_extrinsic_and_calculate_gas_fee(
 	caller: AccountKeyring,
@@ -158,3 +160,58 @@ fn fee_scales_within_a_pool() {
 			);
 		});
 }
+
+#[test]
+fn fee_scaling_can_be_extended_to_other_calls() {
+	const EPOCH_BLOCKS: u32 = 100;
+	const MAX_AUTHORITIES: AuthorityCount = 10;
+	const DEREGISTER_AS_BROKER: RuntimeCall =
+		RuntimeCall::Swapping(pallet_cf_swapping::Call::deregister_as_broker {});
+	let lp = AccountKeyring::Alice;
+	super::genesis::with_test_defaults()
+		.epoch_duration(EPOCH_BLOCKS)
+		.max_authorities(MAX_AUTHORITIES)
+		.with_additional_accounts(&[(
+			lp.to_account_id(),
+			AccountRole::LiquidityProvider,
+			5 * FLIPPERINOS_PER_FLIP,
+		)])
+		.build()
+		.execute_with(|| {
+			let (mut testnet, _, _) =
+				crate::network::fund_authorities_and_join_auction(MAX_AUTHORITIES);
+
+			FeeScalingRate::<Runtime>::set(FeeScalingRateConfig::DelayedExponential {
+				threshold: 0,
+				exponent: 1,
+			});
+
+			// Calls other than pool orders are not scaled by default.
+			let (first_fee, _) =
+				apply_extrinsic_and_calculate_gas_fee(lp, DEREGISTER_AS_BROKER).unwrap();
+			let (second_fee, _) =
+				apply_extrinsic_and_calculate_gas_fee(lp, DEREGISTER_AS_BROKER).unwrap();
+			assert_eq!(first_fee, second_fee);
+
+			testnet.move_forward_blocks(1);
+
+			// Once listed, the call is scaled like any other.
+			FeeScaledCallClasses::<Runtime>::set(Some(
+				vec![DEREGISTER_AS_BROKER.using_encoded(|bytes| FeeScalingCallIndex::Call {
+					pallet_index: bytes[0],
+					call_index: bytes[1],
+				})]
+				.try_into()
+				.unwrap(),
+			));
+			let (first_fee, _) =
+				apply_extrinsic_and_calculate_gas_fee(lp, DEREGISTER_AS_BROKER).unwrap();
+			assert_eq!(
+				Flip::next_call_fee_multiplier(&lp.to_account_id(), &DEREGISTER_AS_BROKER),
+				Some(2)
+			);
+			let (second_fee, _) =
+				apply_extrinsic_and_calculate_gas_fee(lp, DEREGISTER_AS_BROKER).unwrap();
+			assert_eq!(second_fee, 2 * first_fee);
+		});
+}
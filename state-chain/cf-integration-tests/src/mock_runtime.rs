@@ -179,6 +179,8 @@ impl ExtBuilder {
 				redemption_tax: MIN_FUNDING / 2,
 				minimum_funding: MIN_FUNDING,
 				redemption_ttl: core::time::Duration::from_secs(REDEMPTION_TTL_SECS),
+				redeem_address_update_delay: pallet_cf_funding::DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY
+					.into(),
 			},
 			reputation: ReputationConfig {
 				accrual_ratio: ACCRUAL_RATIO,
@@ -225,6 +227,7 @@ impl ExtBuilder {
 				},
 				auction_bid_cutoff_percentage: Percent::from_percent(0),
 				max_authority_set_contraction_percentage: DEFAULT_MAX_AUTHORITY_SET_CONTRACTION,
+				..Default::default()
 			},
 			ethereum_vault: EthereumVaultConfig {
 				deployment_block: Some(0),
@@ -396,6 +396,7 @@ fn vault_swap_deposit_witness(
 		boost_fee: 0,
 		deposit_address: Some(SolAddress([2u8; 32])),
 		channel_id: Some(0),
+		refund_ccm_metadata: None,
 	}
 }
 
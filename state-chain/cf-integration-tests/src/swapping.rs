@@ -49,7 +49,7 @@ use cf_traits::{
 	SwapType,
 };
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok,
 	instances::Instance1,
 	traits::{OnFinalize, OnIdle, Time},
 };
@@ -61,6 +61,7 @@ use pallet_cf_ingress_egress::{DepositWitness, FailedForeignChainCall, VaultDepo
 use pallet_cf_pools::{HistoricalEarnedFees, RangeOrderSize};
 use pallet_cf_swapping::{SwapRequestIdCounter, SwapRetryDelay};
 use sp_core::{H160, U256};
+use sp_runtime::traits::Dispatchable;
 
 use state_chain_runtime::{
 	chainflip::{
@@ -325,6 +326,7 @@ fn set_limit_order(
 		id,
 		tick,
 		sell_amount,
+		None,
 	));
 	let new_sell_balance =
 		pallet_cf_asset_balances::FreeBalances::<Runtime>::get(account_id, sell_asset);
@@ -445,6 +447,67 @@ fn basic_pool_setup_provision_and_swap() {
 		});
 }
 
+#[test]
+fn can_open_deposit_channels_in_a_batch() {
+	super::genesis::with_test_defaults()
+		.with_additional_accounts(&[(ZION, AccountRole::Broker, 5 * FLIPPERINOS_PER_FLIP)])
+		.build()
+		.execute_with(|| {
+			let open_channel = |destination_asset| {
+				RuntimeCall::Swapping(
+					pallet_cf_swapping::Call::request_swap_deposit_address_with_affiliates {
+						source_asset: Asset::Eth,
+						destination_asset,
+						destination_address: EncodedAddress::Eth([1u8; 20]),
+						broker_commission: 0,
+						channel_metadata: None,
+						boost_fee: 0,
+						affiliate_fees: Default::default(),
+						refund_parameters: ETH_REFUND_PARAMS
+							.map_address(|addr| EncodedAddress::Eth(addr.0)),
+						dca_parameters: None,
+					},
+				)
+			};
+
+			assert_ok!(RuntimeCall::Utility(pallet_utility::Call::batch_all {
+				calls: vec![open_channel(Asset::Flip), open_channel(Asset::Usdc)],
+			})
+			.dispatch(RuntimeOrigin::signed(ZION)));
+			assert_eq!(
+				System::events()
+					.into_iter()
+					.filter(|record| matches!(
+						record.event,
+						RuntimeEvent::Swapping(
+							pallet_cf_swapping::Event::SwapDepositAddressReady { .. }
+						)
+					))
+					.count(),
+				2
+			);
+
+			// Only deposit channels can be opened in a batch.
+			assert_noop!(
+				RuntimeCall::Utility(pallet_utility::Call::batch_all {
+					calls: vec![
+						open_channel(Asset::Flip),
+						RuntimeCall::System(frame_system::Call::remark { remark: vec![] }),
+					],
+				})
+				.dispatch(RuntimeOrigin::signed(ZION)),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+			assert_noop!(
+				RuntimeCall::Utility(pallet_utility::Call::batch {
+					calls: vec![open_channel(Asset::Flip)],
+				})
+				.dispatch(RuntimeOrigin::signed(ZION)),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+		});
+}
+
 #[test]
 fn can_process_ccm_via_swap_deposit_address() {
 	const DECIMALS: u128 = 10u128.pow(18);
@@ -616,6 +679,7 @@ fn vault_swap_deposit_witness(
 		boost_fee: 0,
 		deposit_address: Some(H160::from([0x03; 20])),
 		channel_id: Some(0),
+		refund_ccm_metadata: None,
 	}
 }
 
@@ -94,6 +94,32 @@ impl Default for ArbitrumTrackedData {
 	}
 }
 
+/// The gas overhead of a CCM egress on Arbitrum, excluding the user's gas budget.
+///
+/// Arbitrum charges for both L2 execution and for posting the transaction's data to L1. The L1
+/// component is expressed in L2 gas units, so it fluctuates with the ratio of the L1 and L2 fees.
+#[derive(Copy, Clone, RuntimeDebug, Default, PartialEq, Eq)]
+pub struct ArbitrumCcmGasEstimate {
+	pub l2_execution_gas: GasAmount,
+	pub l1_data_gas: GasAmount,
+}
+
+impl ArbitrumCcmGasEstimate {
+	pub fn overhead(&self) -> GasAmount {
+		self.l2_execution_gas.saturating_add(self.l1_data_gas)
+	}
+
+	/// The gas limit for a CCM egress with the given gas budget, capped at the maximum gas limit.
+	pub fn gas_limit(&self, gas_budget: GasAmount) -> GasAmount {
+		self.overhead().saturating_add(gas_budget).min(fees::MAX_GAS_LIMIT)
+	}
+
+	/// The largest gas budget that can be fully honoured without hitting the gas limit cap.
+	pub fn max_gas_budget(&self) -> GasAmount {
+		fees::MAX_GAS_LIMIT.saturating_sub(self.overhead())
+	}
+}
+
 impl ArbitrumTrackedData {
 	pub fn max_fee_per_gas(
 		&self,
@@ -109,12 +135,11 @@ impl ArbitrumTrackedData {
 	// therefore covers the Arbitrum gas overhead so we just need to account for the
 	// extra gas from our Vault logic. That also covers the extra gas required that
 	// is dependant on the message's length.
-	pub fn calculate_ccm_gas_limit(
+	pub fn estimate_ccm_gas(
 		&self,
 		is_native_asset: bool,
-		gas_budget: GasAmount,
 		message_length: usize,
-	) -> GasAmount {
+	) -> ArbitrumCcmGasEstimate {
 		use crate::arb::fees::*;
 
 		let vault_gas_overhead = if is_native_asset {
@@ -128,8 +153,9 @@ impl ArbitrumTrackedData {
 		// should be included in the user's gas budget together with the receiving logic's gas
 		// required.
 		let l2g = vault_gas_overhead.saturating_add(message_length as u128);
-		let l1p = self.l1_base_fee_estimate * L1_GAS_PER_BYTES;
-		let p = self.base_fee;
+		let l1p = self.l1_base_fee_estimate.saturating_mul(L1_GAS_PER_BYTES);
+		// Guard against a zero base fee, which would otherwise make the L1 component undefined.
+		let p = self.base_fee.max(1);
 
 		// The user's estimation via `eth.estimate_gas` will already contain the fixed Arbitrum
 		// gas overhead of bytes according to the current Arbitrum documentation.
@@ -139,8 +165,22 @@ impl ArbitrumTrackedData {
 
 		let b = l1c.div_ceil(p);
 
-		let gas_limit = l2g.saturating_add(b);
-		gas_limit.saturating_add(gas_budget).min(MAX_GAS_LIMIT)
+		ArbitrumCcmGasEstimate { l2_execution_gas: l2g, l1_data_gas: b }
+	}
+
+	pub fn calculate_ccm_gas_limit(
+		&self,
+		is_native_asset: bool,
+		gas_budget: GasAmount,
+		message_length: usize,
+	) -> GasAmount {
+		self.estimate_ccm_gas(is_native_asset, message_length).gas_limit(gas_budget)
+	}
+
+	/// The largest gas budget a CCM egress of the given asset and message length can use, given
+	/// the current L1 and L2 fees.
+	pub fn max_ccm_gas_budget(&self, is_native_asset: bool, message_length: usize) -> GasAmount {
+		self.estimate_ccm_gas(is_native_asset, message_length).max_gas_budget()
 	}
 
 	pub fn calculate_transaction_fee(
@@ -288,6 +328,50 @@ mod test {
 		assert_eq!(gas_limit_token + gas_budget_extra, gas_limit_token_extra);
 	}
 
+	#[test]
+	fn ccm_gas_estimate_separates_l1_and_l2_gas() {
+		let arb_tracked_data = ArbitrumTrackedData {
+			base_fee: 100_000_000u128,
+			l1_base_fee_estimate: 26_920_712_879u128,
+		};
+
+		let estimate = arb_tracked_data.estimate_ccm_gas(true, 1);
+		assert_eq!(estimate.l2_execution_gas, CCM_VAULT_NATIVE_GAS_OVERHEAD + 1);
+		assert_eq!(estimate.overhead() + 80_000u128, 1858469u128);
+
+		// The L1 component scales with the L1 fee, the L2 component does not.
+		let doubled_l1_fee = ArbitrumTrackedData {
+			l1_base_fee_estimate: 2 * arb_tracked_data.l1_base_fee_estimate,
+			..arb_tracked_data
+		}
+		.estimate_ccm_gas(true, 1);
+		assert_eq!(doubled_l1_fee.l2_execution_gas, estimate.l2_execution_gas);
+		assert!(doubled_l1_fee.l1_data_gas >= 2 * estimate.l1_data_gas - 1);
+
+		// A zero base fee must not cause a division by zero.
+		let _ = ArbitrumTrackedData { base_fee: 0, ..arb_tracked_data }.estimate_ccm_gas(true, 1);
+	}
+
+	#[test]
+	fn max_ccm_gas_budget_fits_under_cap() {
+		let arb_tracked_data = ArbitrumTrackedData {
+			base_fee: 100_000_000u128,
+			l1_base_fee_estimate: 26_920_712_879u128,
+		};
+
+		for is_native_asset in [true, false] {
+			let max_gas_budget = arb_tracked_data.max_ccm_gas_budget(is_native_asset, 100);
+			assert_eq!(
+				arb_tracked_data.calculate_ccm_gas_limit(is_native_asset, max_gas_budget, 100),
+				MAX_GAS_LIMIT
+			);
+			assert_eq!(
+				arb_tracked_data.calculate_ccm_gas_limit(is_native_asset, max_gas_budget - 1, 100),
+				MAX_GAS_LIMIT - 1
+			);
+		}
+	}
+
 	#[test]
 	fn gas_limit_cap() {
 		const GAS_BUDGET: u128 = 80_000u128;
@@ -30,10 +30,11 @@ use crate::{
 use cf_primitives::{Asset, ForeignChain};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_runtime::DispatchError;
 use sp_std::{collections::btree_set::BTreeSet, vec, vec::Vec};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
 pub enum CcmValidityError {
 	CannotDecodeCcmAdditionalData,
 	CcmIsTooLong,
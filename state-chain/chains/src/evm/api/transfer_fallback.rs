@@ -25,7 +25,7 @@ use sp_std::{vec, vec::Vec};
 #[derive(Encode, Decode, TypeInfo, Clone, RuntimeDebug, PartialEq, Eq)]
 pub struct TransferFallback {
 	/// The failed transfer that needs to be addressed.
-	transfer_param: EncodableTransferAssetParams,
+	pub transfer_param: EncodableTransferAssetParams,
 }
 
 impl TransferFallback {
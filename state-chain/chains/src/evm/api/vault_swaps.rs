@@ -19,6 +19,89 @@ pub mod x_call_token;
 pub mod x_swap_native;
 pub mod x_swap_token;
 
+use crate::evm::api::{EvmAddress, EvmCall};
+use ethabi::Token;
+use frame_support::sp_runtime::RuntimeDebug;
+use sp_core::U256;
+use sp_std::vec::Vec;
+
+/// The arguments of any of the vault swap calls, decoded from their abi-encoded calldata.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct DecodedVaultSwapCall {
+	pub dst_chain: u32,
+	pub dst_address: Vec<u8>,
+	pub dst_token: u32,
+	/// The message and gas amount of a cross-chain message (xCall* only).
+	pub ccm: Option<(Vec<u8>, U256)>,
+	/// The source token and amount (x*Token only). Native swaps carry the amount as the value.
+	pub src_token: Option<(EvmAddress, U256)>,
+	pub cf_parameters: Vec<u8>,
+}
+
+/// Decodes the calldata of a `xSwapNative`, `xSwapToken`, `xCallNative` or `xCallToken` call.
+///
+/// Returns `None` if the function selector doesn't match any of these calls or if the arguments
+/// can't be decoded.
+pub fn decode_vault_swap_calldata(calldata: &[u8]) -> Option<DecodedVaultSwapCall> {
+	let selector = calldata.get(..4)?;
+	let decode_args = |function: ethabi::Function| {
+		if function.short_signature() == selector {
+			function.decode_input(&calldata[4..]).ok()
+		} else {
+			None
+		}
+	};
+	let to_u32 = |token: Token| token.into_uint().and_then(|value| value.try_into().ok());
+
+	if let Some(args) = decode_args(x_swap_native::XSwapNative::get_function(false)) {
+		let [dst_chain, dst_address, dst_token, cf_parameters] =
+			<[Token; 4]>::try_from(args).ok()?;
+		Some(DecodedVaultSwapCall {
+			dst_chain: to_u32(dst_chain)?,
+			dst_address: dst_address.into_bytes()?,
+			dst_token: to_u32(dst_token)?,
+			ccm: None,
+			src_token: None,
+			cf_parameters: cf_parameters.into_bytes()?,
+		})
+	} else if let Some(args) = decode_args(x_swap_token::XSwapToken::get_function(false)) {
+		let [dst_chain, dst_address, dst_token, src_token, amount, cf_parameters] =
+			<[Token; 6]>::try_from(args).ok()?;
+		Some(DecodedVaultSwapCall {
+			dst_chain: to_u32(dst_chain)?,
+			dst_address: dst_address.into_bytes()?,
+			dst_token: to_u32(dst_token)?,
+			ccm: None,
+			src_token: Some((src_token.into_address()?, amount.into_uint()?)),
+			cf_parameters: cf_parameters.into_bytes()?,
+		})
+	} else if let Some(args) = decode_args(x_call_native::XCallNative::get_function(false)) {
+		let [dst_chain, dst_address, dst_token, message, gas_amount, cf_parameters] =
+			<[Token; 6]>::try_from(args).ok()?;
+		Some(DecodedVaultSwapCall {
+			dst_chain: to_u32(dst_chain)?,
+			dst_address: dst_address.into_bytes()?,
+			dst_token: to_u32(dst_token)?,
+			ccm: Some((message.into_bytes()?, gas_amount.into_uint()?)),
+			src_token: None,
+			cf_parameters: cf_parameters.into_bytes()?,
+		})
+	} else if let Some(args) = decode_args(x_call_token::XCallToken::get_function(false)) {
+		let [dst_chain, dst_address, dst_token, message, gas_amount, src_token, amount, cf_parameters] =
+			<[Token; 8]>::try_from(args).ok()?;
+		Some(DecodedVaultSwapCall {
+			dst_chain: to_u32(dst_chain)?,
+			dst_address: dst_address.into_bytes()?,
+			dst_token: to_u32(dst_token)?,
+			ccm: Some((message.into_bytes()?, gas_amount.into_uint()?)),
+			src_token: Some((src_token.into_address()?, amount.into_uint()?)),
+			cf_parameters: cf_parameters.into_bytes()?,
+		})
+	} else {
+		None
+	}
+}
+
 /// Some test values and utility functions used within the Vault swap call module.
 #[cfg(test)]
 pub mod test_utils {
@@ -72,3 +155,61 @@ pub mod test_utils {
 		)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{test_utils::*, *};
+	use crate::address::EncodedAddress;
+	use cf_primitives::{Asset, ForeignChain};
+
+	#[test]
+	fn can_decode_vault_swap_calldata() {
+		let dest_address = EncodedAddress::Dot([0xff; 32]);
+		let message = channel_metadata().message.to_vec();
+
+		assert_eq!(
+			decode_vault_swap_calldata(
+				&x_swap_native::XSwapNative::new(
+					dest_address.clone(),
+					Asset::Dot,
+					dummy_cf_parameter(false),
+				)
+				.abi_encoded_payload()
+			),
+			Some(DecodedVaultSwapCall {
+				dst_chain: ForeignChain::Polkadot as u32,
+				dst_address: dest_address.inner_bytes().to_vec(),
+				dst_token: Asset::Dot as u32,
+				ccm: None,
+				src_token: None,
+				cf_parameters: dummy_cf_parameter(false),
+			})
+		);
+		assert_eq!(
+			decode_vault_swap_calldata(
+				&x_call_token::XCallToken::new(
+					dest_address.clone(),
+					Asset::Dot,
+					message.clone(),
+					1_000_000u128,
+					refund_address(),
+					10_000u128,
+					dummy_cf_parameter(true),
+				)
+				.abi_encoded_payload()
+			),
+			Some(DecodedVaultSwapCall {
+				dst_chain: ForeignChain::Polkadot as u32,
+				dst_address: dest_address.inner_bytes().to_vec(),
+				dst_token: Asset::Dot as u32,
+				ccm: Some((message, U256::from(1_000_000u128))),
+				src_token: Some((refund_address(), U256::from(10_000u128))),
+				cf_parameters: dummy_cf_parameter(true),
+			})
+		);
+
+		// Unknown selectors and truncated calldata are rejected.
+		assert_eq!(decode_vault_swap_calldata(&[0xde, 0xad, 0xbe, 0xef, 0x00]), None);
+		assert_eq!(decode_vault_swap_calldata(&[0x01]), None);
+	}
+}
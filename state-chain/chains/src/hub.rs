@@ -20,6 +20,7 @@ use crate::*;
 
 pub mod api;
 pub mod benchmarking;
+pub mod vault_swap_encoding;
 pub mod xcm_types;
 
 #[cfg(feature = "std")]
@@ -355,7 +356,14 @@ pub enum AssethubRuntimeCall {
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
-pub enum SystemCall {}
+pub enum SystemCall {
+	/// Make some on-chain remark and emit an event.
+	#[codec(index = 7u8)]
+	remark_with_event {
+		#[allow(missing_docs)]
+		remark: Vec<u8>,
+	},
+}
 
 #[allow(non_camel_case_types)]
 #[allow(clippy::large_enum_variant)]
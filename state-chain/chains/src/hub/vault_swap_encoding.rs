@@ -0,0 +1,155 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Vault swaps from Assethub are submitted by the user as a single `utility.batch_all` call that
+//! transfers the input asset to the Assethub vault and attaches the swap parameters as a
+//! `system.remark_with_event`.
+
+use super::{AssethubRuntimeCall, AssetsCall, BalancesCall, SystemCall, UtilityCall};
+use crate::{
+	address::EncodedAddress,
+	dot::{PolkadotAccountId, PolkadotAccountIdLookup, PolkadotBalance},
+};
+use cf_primitives::{chains::assets::hub, Asset, ASSETHUB_USDC_ASSET_ID, ASSETHUB_USDT_ASSET_ID};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::{vec, vec::Vec};
+
+/// Identifies a remark as a Chainflip vault swap.
+pub const VAULT_SWAP_REMARK_PREFIX: [u8; 4] = *b"CFVS";
+
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Debug)]
+pub struct AssethubVaultSwapData {
+	pub output_asset: Asset,
+	pub output_address: EncodedAddress,
+	/// Encoded `VersionedCfParameters`, as produced by `build_cf_parameters`.
+	pub cf_parameters: Vec<u8>,
+}
+
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Debug)]
+pub enum VersionedAssethubVaultSwapData {
+	V0(AssethubVaultSwapData),
+}
+
+pub fn encode_swap_params_in_remark(data: AssethubVaultSwapData) -> Vec<u8> {
+	[VAULT_SWAP_REMARK_PREFIX.to_vec(), VersionedAssethubVaultSwapData::V0(data).encode()].concat()
+}
+
+/// Returns `None` if the remark is not a (supported) Chainflip vault swap.
+pub fn decode_swap_params_from_remark(remark: &[u8]) -> Option<AssethubVaultSwapData> {
+	let mut payload = remark.strip_prefix(&VAULT_SWAP_REMARK_PREFIX[..])?;
+	match VersionedAssethubVaultSwapData::decode(&mut payload).ok()? {
+		VersionedAssethubVaultSwapData::V0(data) => Some(data),
+	}
+}
+
+/// Builds the call that the user needs to submit on Assethub to perform the vault swap.
+pub fn vault_swap_call(
+	source_asset: hub::Asset,
+	amount: PolkadotBalance,
+	vault_account: PolkadotAccountId,
+	remark: Vec<u8>,
+) -> AssethubRuntimeCall {
+	let dest = PolkadotAccountIdLookup::from(vault_account);
+	let transfer = match source_asset {
+		hub::Asset::HubDot => AssethubRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+			dest,
+			value: amount,
+		}),
+		hub::Asset::HubUsdt => AssethubRuntimeCall::Assets(AssetsCall::transfer {
+			id: ASSETHUB_USDT_ASSET_ID,
+			dest,
+			value: amount,
+		}),
+		hub::Asset::HubUsdc => AssethubRuntimeCall::Assets(AssetsCall::transfer {
+			id: ASSETHUB_USDC_ASSET_ID,
+			dest,
+			value: amount,
+		}),
+	};
+
+	AssethubRuntimeCall::Utility(UtilityCall::batch_all {
+		calls: vec![
+			transfer,
+			AssethubRuntimeCall::System(SystemCall::remark_with_event { remark }),
+		],
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const VAULT_ACCOUNT: PolkadotAccountId = PolkadotAccountId([7u8; 32]);
+
+	fn mock_swap_data() -> AssethubVaultSwapData {
+		AssethubVaultSwapData {
+			output_asset: Asset::Eth,
+			output_address: EncodedAddress::Eth([1u8; 20]),
+			cf_parameters: vec![2u8; 10],
+		}
+	}
+
+	#[test]
+	fn remark_encoding_round_trip() {
+		let remark = encode_swap_params_in_remark(mock_swap_data());
+
+		assert!(remark.starts_with(&VAULT_SWAP_REMARK_PREFIX));
+		assert_eq!(decode_swap_params_from_remark(&remark), Some(mock_swap_data()));
+
+		// Remarks that are not vault swaps are ignored:
+		assert_eq!(decode_swap_params_from_remark(&remark[1..]), None);
+		assert_eq!(decode_swap_params_from_remark(b"hello world"), None);
+	}
+
+	#[test]
+	fn vault_swap_call_transfers_to_vault_and_attaches_remark() {
+		let remark = encode_swap_params_in_remark(mock_swap_data());
+
+		for (asset, expected_transfer) in [
+			(
+				hub::Asset::HubDot,
+				AssethubRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+					dest: VAULT_ACCOUNT.into(),
+					value: 1_000,
+				}),
+			),
+			(
+				hub::Asset::HubUsdc,
+				AssethubRuntimeCall::Assets(AssetsCall::transfer {
+					id: ASSETHUB_USDC_ASSET_ID,
+					dest: VAULT_ACCOUNT.into(),
+					value: 1_000,
+				}),
+			),
+		] {
+			let call = vault_swap_call(asset, 1_000, VAULT_ACCOUNT, remark.clone());
+			assert_eq!(
+				call,
+				AssethubRuntimeCall::Utility(UtilityCall::batch_all {
+					calls: vec![
+						expected_transfer,
+						AssethubRuntimeCall::System(SystemCall::remark_with_event {
+							remark: remark.clone()
+						}),
+					],
+				})
+			);
+			// Utility pallet index followed by the `batch_all` call index.
+			assert_eq!(call.encode()[..2], [40u8, 2u8]);
+		}
+	}
+}
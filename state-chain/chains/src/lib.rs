@@ -1091,6 +1091,10 @@ pub enum VaultSwapExtraParameters<Address, Amount> {
 		refund_parameters: ChannelRefundParameters<Address>,
 		from_token_account: Option<Address>,
 	},
+	Assethub {
+		input_amount: Amount,
+		refund_parameters: ChannelRefundParameters<Address>,
+	},
 }
 
 impl<Address: Clone, Amount> VaultSwapExtraParameters<Address, Amount> {
@@ -1120,6 +1124,11 @@ impl<Address: Clone, Amount> VaultSwapExtraParameters<Address, Amount> {
 				refund_parameters: refund_parameters.try_map_address(&f)?,
 				from_token_account: from_token_account.map(&f).transpose()?,
 			},
+			VaultSwapExtraParameters::Assethub { input_amount, refund_parameters } =>
+				VaultSwapExtraParameters::Assethub {
+					input_amount,
+					refund_parameters: refund_parameters.try_map_address(f)?,
+				},
 		})
 	}
 
@@ -1152,6 +1161,11 @@ impl<Address: Clone, Amount> VaultSwapExtraParameters<Address, Amount> {
 				refund_parameters,
 				from_token_account,
 			},
+			VaultSwapExtraParameters::Assethub { input_amount, refund_parameters } =>
+				VaultSwapExtraParameters::Assethub {
+					input_amount: f(input_amount)?,
+					refund_parameters,
+				},
 		})
 	}
 }
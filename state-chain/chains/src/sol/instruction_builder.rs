@@ -26,14 +26,18 @@ use crate::{
 	sol::{
 		sol_tx_core::{
 			consts::{SOL_USDC_DECIMAL, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID},
-			program_instructions::swap_endpoints::{
-				SwapEndpointProgram, SwapNativeParams, SwapTokenParams,
+			program_instructions::{
+				swap_endpoints::{
+					SwapEndpointProgram, SwapNativeParams, SwapTokenParams, XSwapNative, XSwapToken,
+				},
+				ProgramInstruction,
 			},
 		},
 		SolAddress, SolAmount, SolApiEnvironment, SolInstruction, SolPubkey,
 	},
 	CcmChannelMetadata,
 };
+use borsh::BorshDeserialize;
 use cf_primitives::chains::assets::any::Asset;
 use sp_std::vec::Vec;
 
@@ -47,6 +51,13 @@ fn token_program_id() -> SolAddress {
 
 pub struct SolanaInstructionBuilder;
 
+/// The arguments of a vault swap instruction, decoded from the instruction data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedSolanaVaultSwap {
+	Native(SwapNativeParams),
+	Token(SwapTokenParams),
+}
+
 impl SolanaInstructionBuilder {
 	pub fn x_swap_native(
 		api_environment: SolApiEnvironment,
@@ -111,6 +122,19 @@ impl SolanaInstructionBuilder {
 			system_program_id(),
 		)
 	}
+
+	/// Decodes the data of a `x_swap_native` or `x_swap_token` instruction. Returns `None` if the
+	/// function discriminator doesn't match either instruction or the arguments can't be decoded.
+	pub fn decode_vault_swap_instruction_data(data: &[u8]) -> Option<DecodedSolanaVaultSwap> {
+		let (discriminator, args) = (data.get(..8)?, data.get(8..)?);
+		if discriminator == XSwapNative::function_discriminator() {
+			SwapNativeParams::try_from_slice(args).ok().map(DecodedSolanaVaultSwap::Native)
+		} else if discriminator == XSwapToken::function_discriminator() {
+			SwapTokenParams::try_from_slice(args).ok().map(DecodedSolanaVaultSwap::Token)
+		} else {
+			None
+		}
+	}
 }
 
 #[cfg(test)]
@@ -390,4 +414,59 @@ mod test {
 			BLOCKHASH.into(),
 		);
 	}
+
+	#[test]
+	fn can_decode_vault_swap_instruction_data() {
+		let native_instruction = SolanaInstructionBuilder::x_swap_native(
+			api_env(),
+			agg_key().into(),
+			Asset::Eth,
+			DESTINATION_ADDRESS_ETH,
+			FROM.into(),
+			EVENT_DATA_ACCOUNT.into(),
+			INPUT_AMOUNT,
+			cf_parameter(false),
+			None,
+		);
+		assert_eq!(
+			SolanaInstructionBuilder::decode_vault_swap_instruction_data(&native_instruction.data),
+			Some(DecodedSolanaVaultSwap::Native(SwapNativeParams {
+				amount: INPUT_AMOUNT,
+				dst_chain: DESTINATION_ADDRESS_ETH.chain() as u32,
+				dst_address: DESTINATION_ADDRESS_ETH.into_vec(),
+				dst_token: Asset::Eth as u32,
+				ccm_parameters: None,
+				cf_parameters: cf_parameter(false),
+			}))
+		);
+
+		let token_instruction = SolanaInstructionBuilder::x_swap_usdc(
+			api_env(),
+			Asset::Sol,
+			EncodedAddress::Sol(DESTINATION_ADDRESS_SOL.0),
+			FROM.into(),
+			FROM.into(),
+			EVENT_DATA_ACCOUNT.into(),
+			TOKEN_SUPPORTED_ACCOUNT.into(),
+			INPUT_AMOUNT,
+			cf_parameter(true),
+			Some(ccm_parameter().channel_metadata),
+		);
+		assert!(matches!(
+			SolanaInstructionBuilder::decode_vault_swap_instruction_data(&token_instruction.data),
+			Some(DecodedSolanaVaultSwap::Token(SwapTokenParams {
+				amount: INPUT_AMOUNT,
+				ccm_parameters: Some(_),
+				..
+			}))
+		));
+
+		// The account metas are not part of the instruction data.
+		assert_eq!(
+			SolanaInstructionBuilder::decode_vault_swap_instruction_data(
+				&native_instruction.data[8..]
+			),
+			None
+		);
+	}
 }
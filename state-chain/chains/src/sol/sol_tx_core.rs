@@ -72,6 +72,21 @@ pub mod rpc_types {
 			}
 		}
 	}
+
+	#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Encode, Decode, TypeInfo)]
+	pub struct SolAddressLookupTableRpc {
+		pub key: SolAddress,
+		pub addresses: Vec<SolAddress>,
+	}
+
+	impl From<AddressLookupTableAccount> for SolAddressLookupTableRpc {
+		fn from(value: AddressLookupTableAccount) -> Self {
+			SolAddressLookupTableRpc {
+				key: value.key.into(),
+				addresses: value.addresses.into_iter().map(|a| a.into()).collect(),
+			}
+		}
+	}
 }
 
 #[derive(Encode, Decode, TypeInfo, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
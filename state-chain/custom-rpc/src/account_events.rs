@@ -0,0 +1,213 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-memory index of the events touching each account in the most recent finalized blocks.
+//!
+//! The index is populated from finality notifications, so it only covers blocks finalized since
+//! the node was started. It is only maintained if the node is started with
+//! `--enable-account-events-index`.
+
+use super::*;
+
+use cf_rpc_apis::OrderFilled;
+use futures::StreamExt;
+use state_chain_runtime::runtime_apis::AccountEvent;
+use std::{
+	collections::VecDeque,
+	sync::{Arc, RwLock},
+};
+
+/// The number of finalized blocks kept in the index, roughly one hour of blocks.
+pub const MAX_INDEXED_BLOCKS: u32 = 600;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcAccountEventData {
+	Event(AccountEvent),
+	OrderFilled(OrderFilled),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcAccountEvent {
+	pub block_number: BlockNumber,
+	pub block_hash: Hash,
+	#[serde(flatten)]
+	pub data: RpcAccountEventData,
+}
+
+struct IndexedBlock {
+	block_number: BlockNumber,
+	block_hash: Hash,
+	events: Vec<(state_chain_runtime::AccountId, RpcAccountEventData)>,
+}
+
+#[derive(Clone, Default)]
+pub struct AccountEventsIndex {
+	blocks: Arc<RwLock<VecDeque<IndexedBlock>>>,
+}
+
+impl AccountEventsIndex {
+	/// Indexes every finalized block until the client shuts down.
+	pub async fn index_finalized_blocks<C, B, BE>(self, client: Arc<C>)
+	where
+		B: BlockT<Hash = Hash, Header = state_chain_runtime::Header>,
+		B::Header: Unpin,
+		BE: Send + Sync + 'static + Backend<B>,
+		C: sp_api::ProvideRuntimeApi<B>
+			+ Send
+			+ Sync
+			+ 'static
+			+ BlockBackend<B>
+			+ ExecutorProvider<B>
+			+ HeaderBackend<B>
+			+ HeaderMetadata<B, Error = sc_client_api::blockchain::Error>
+			+ BlockchainEvents<B>
+			+ CallApiAt<B>
+			+ StorageProvider<B, BE>,
+		C::Api: CustomRuntimeApi<B>,
+	{
+		let mut finality_notifications = client.finality_notification_stream();
+		while let Some(notification) = finality_notifications.next().await {
+			// Blocks that were finalized implicitly are not notified individually. Only the most
+			// recent of them are kept in the index, so there is no point indexing the others.
+			let finalized =
+				notification.tree_route.iter().chain(std::iter::once(&notification.hash));
+			let skipped =
+				(notification.tree_route.len() + 1).saturating_sub(MAX_INDEXED_BLOCKS as usize);
+			for hash in finalized.skip(skipped) {
+				if let Err(error) = self.index_block(client.as_ref(), *hash) {
+					log::warn!("Failed to index account events of block {hash}: {error:?}");
+				}
+			}
+		}
+	}
+
+	fn index_block<C, B, BE>(&self, client: &C, hash: Hash) -> RpcResult<()>
+	where
+		B: BlockT<Hash = Hash, Header = state_chain_runtime::Header>,
+		B::Header: Unpin,
+		BE: Send + Sync + 'static + Backend<B>,
+		C: sp_api::ProvideRuntimeApi<B>
+			+ Send
+			+ Sync
+			+ 'static
+			+ BlockBackend<B>
+			+ ExecutorProvider<B>
+			+ HeaderBackend<B>
+			+ HeaderMetadata<B, Error = sc_client_api::blockchain::Error>
+			+ BlockchainEvents<B>
+			+ CallApiAt<B>
+			+ StorageProvider<B, BE>,
+		C::Api: CustomRuntimeApi<B>,
+	{
+		let BlockUpdate { block_hash, block_number, data: OrderFills { fills } } =
+			order_fills::order_fills_for_block(client, hash)?;
+
+		let api = client.runtime_api();
+		// Runtimes predating version 5 of the runtime api don't provide account events.
+		let events = if ensure_api_version::<B, _>(&*api, hash, 5).is_ok() {
+			api.cf_account_events(hash).map_err(CfApiError::from)?
+		} else {
+			Vec::new()
+		}
+		.into_iter()
+		.map(|(account_id, event)| (account_id, RpcAccountEventData::Event(event)))
+		.chain(fills.into_iter().map(|fill| {
+			let lp = match &fill {
+				OrderFilled::LimitOrder { lp, .. } | OrderFilled::RangeOrder { lp, .. } =>
+					lp.clone(),
+			};
+			(lp, RpcAccountEventData::OrderFilled(fill))
+		}))
+		.collect();
+
+		let mut blocks = self.blocks.write().expect("Lock is never poisoned");
+		if blocks.len() >= MAX_INDEXED_BLOCKS as usize {
+			blocks.pop_front();
+		}
+		blocks.push_back(IndexedBlock { block_number, block_hash, events });
+
+		Ok(())
+	}
+
+	/// The events touching `account_id` in the last `last_blocks` indexed blocks, oldest first.
+	pub fn account_events(
+		&self,
+		account_id: &state_chain_runtime::AccountId,
+		last_blocks: u32,
+	) -> Vec<RpcAccountEvent> {
+		let blocks = self.blocks.read().expect("Lock is never poisoned");
+		blocks
+			.iter()
+			.skip(blocks.len().saturating_sub(last_blocks as usize))
+			.flat_map(|block| {
+				block
+					.events
+					.iter()
+					.filter(|(account, _)| account == account_id)
+					.map(|(_, data)| RpcAccountEvent {
+						block_number: block.block_number,
+						block_hash: block.block_hash,
+						data: data.clone(),
+					})
+			})
+			.collect()
+	}
+}
+
+#[rpc(server, client, namespace = "cf")]
+pub trait AccountEventsApi {
+	/// Returns the deposits, swaps, order fills and redemptions touching `account_id` in the last
+	/// `last_blocks` finalized blocks (by default, all of the indexed blocks). Only blocks
+	/// finalized since the node was started are indexed. Only available if the node was started
+	/// with `--enable-account-events-index`.
+	#[method(name = "account_events")]
+	fn cf_account_events(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		last_blocks: Option<u32>,
+	) -> RpcResult<Vec<RpcAccountEvent>>;
+}
+
+pub struct AccountEventsRpc {
+	pub index: AccountEventsIndex,
+}
+
+impl AccountEventsRpc {
+	pub fn new(index: AccountEventsIndex) -> Self {
+		Self { index }
+	}
+}
+
+impl AccountEventsApiServer for AccountEventsRpc {
+	fn cf_account_events(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		last_blocks: Option<u32>,
+	) -> RpcResult<Vec<RpcAccountEvent>> {
+		let last_blocks = last_blocks.unwrap_or(MAX_INDEXED_BLOCKS);
+		if last_blocks > MAX_INDEXED_BLOCKS {
+			return Err(call_error(
+				format!(
+					"Too many blocks requested: at most {MAX_INDEXED_BLOCKS} blocks are indexed"
+				),
+				CfErrorCode::OtherError,
+			)
+			.into());
+		}
+		Ok(self.index.account_events(&account_id, last_blocks))
+	}
+}
@@ -15,7 +15,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::CfApiError;
-use cf_rpc_apis::{internal_error, RpcApiError};
+use cf_rpc_apis::{call_error, internal_error, CfErrorCode, RpcApiError};
 use futures::{stream, stream::StreamExt, FutureExt};
 use jsonrpsee::{types::error::ErrorObjectOwned, PendingSubscriptionSink, RpcModule};
 
@@ -28,7 +28,7 @@ use sc_rpc_spec_v2::chain_head::{
 	api::ChainHeadApiServer, ChainHead, ChainHeadConfig, FollowEvent,
 };
 use serde::Serialize;
-use sp_api::CallApiAt;
+use sp_api::{ApiExt, CallApiAt};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use state_chain_runtime::{chainflip::BlockUpdate, runtime_apis::CustomRuntimeApi, Hash};
 use std::{fmt::Debug, marker::PhantomData, num::NonZero, sync::Arc};
@@ -77,6 +77,45 @@ where
 	{
 		Ok(f(&*self.client.runtime_api(), self.unwrap_or_best(at)).map_err(CfApiError::from)?)
 	}
+
+	/// Like [Self::with_runtime_api], but fails if the runtime at the given block implements a
+	/// version of the [CustomRuntimeApi] older than `version`, i.e. one that doesn't provide the
+	/// methods called by `f`.
+	pub fn with_runtime_api_since<E, R>(
+		&self,
+		at: Option<Hash>,
+		version: u32,
+		f: impl FnOnce(&C::Api, Hash) -> Result<R, E>,
+	) -> Result<R, RpcApiError>
+	where
+		CfApiError: From<E>,
+	{
+		let api = self.client.runtime_api();
+		let hash = self.unwrap_or_best(at);
+		ensure_api_version::<B, _>(&*api, hash, version)?;
+		Ok(f(&*api, hash).map_err(CfApiError::from)?)
+	}
+}
+
+/// Fails if the runtime at the given block implements a version of the [CustomRuntimeApi] older
+/// than `version`.
+pub fn ensure_api_version<B: BlockT, Api: ApiExt<B>>(
+	api: &Api,
+	hash: B::Hash,
+	version: u32,
+) -> Result<(), CfApiError> {
+	let api_version = api
+		.api_version::<dyn CustomRuntimeApi<state_chain_runtime::Block>>(hash)?
+		.unwrap_or_default();
+	if api_version < version {
+		return Err(CfApiError::ErrorObject(call_error(
+			format!(
+				"Not supported by the runtime at block {hash:?}, which implements version {api_version} of the runtime api (required: {version})."
+			),
+			CfErrorCode::RuntimeApiError,
+		)))
+	}
+	Ok(())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -217,6 +256,7 @@ where
 			end_on_error,
 			sink,
 			move |client, hash, _state| f(client, hash).map(|res| (res, ())),
+			|_| false,
 		)
 		.await
 	}
@@ -225,8 +265,8 @@ where
 	/// only when it changes, or every new block.
 	/// Note depending on the notification_behaviour blocks can be skipped. Also this
 	/// subscription can either filter out, or end the stream if the provided async closure returns
-	/// an error.
-	async fn new_subscription_with_state<
+	/// an error. The subscription ends after the first value for which `is_final` returns true.
+	pub async fn new_subscription_with_state<
 		T: Serialize + Send + Clone + Eq + 'static,
 		// State to carry forward between calls to the closure.
 		S: 'static + Clone + Send,
@@ -234,6 +274,7 @@ where
 			+ Send
 			+ Clone
 			+ 'static,
+		P: Fn(&T) -> bool + Send + 'static,
 	>(
 		&self,
 		notification_behaviour: NotificationBehaviour,
@@ -241,6 +282,7 @@ where
 		end_on_error: bool,
 		pending_sink: PendingSubscriptionSink,
 		f: F,
+		is_final: P,
 	) {
 		// Make sure to use the same chain head client for both subscription and cleaner
 		let chain_head_client = Arc::new(self.chain_head_api());
@@ -408,6 +450,12 @@ where
 			})
 			.take_while(|item| futures::future::ready(item.is_ok()))
 			.map(Result::unwrap)
+			.scan(false, move |ended, update| {
+				futures::future::ready((!*ended).then(|| {
+					*ended = is_final(&update.data);
+					update
+				}))
+			})
 			.boxed();
 
 		self.executor.spawn(
@@ -27,23 +27,24 @@ use cf_chains::{
 use cf_node_client::{
 	extract_from_first_matching_event, subxt_state_chain_config::cf_static_runtime, ExtrinsicData,
 };
-use cf_primitives::{Affiliates, Asset, BasisPoints, ChannelId};
+use cf_primitives::{AffiliateShortId, Affiliates, Asset, BasisPoints, BlockNumber, ChannelId};
 use cf_rpc_apis::{
 	broker::{
-		BrokerRpcApiServer, DcaParameters, GetOpenDepositChannelsQuery, SwapDepositAddress,
-		TransactionInId, WithdrawFeesDetail,
+		AffiliateAccount, BrokerChannelDefaults, BrokerRpcApiServer, DcaParameters,
+		GetOpenDepositChannelsQuery, SwapDepositAddress, SwapDepositAddressRequest,
+		TransactionInId, WithdrawFeesDetail, MAX_BATCHED_DEPOSIT_CHANNELS,
 	},
-	RpcResult, H256,
+	RpcResult, H256, U256,
 };
 use jsonrpsee::{core::async_trait, PendingSubscriptionSink};
-use pallet_cf_swapping::AffiliateDetails;
+use pallet_cf_swapping::{AffiliateAction, AffiliateDetails};
 use sc_client_api::{
 	blockchain::HeaderMetadata, Backend, BlockBackend, BlockchainEvents, ExecutorProvider,
 	HeaderBackend, StorageProvider,
 };
 use sc_transaction_pool::FullPool;
-use sp_api::CallApiAt;
-use sp_core::crypto::AccountId32;
+use sp_api::{ApiExt, CallApiAt};
+use sp_core::{crypto::AccountId32, Bytes};
 use sp_runtime::traits::Block as BlockT;
 use state_chain_runtime::{
 	runtime_apis::{
@@ -222,6 +223,180 @@ where
 		.map_err(CfApiError::from)?)
 	}
 
+	async fn request_swap_deposit_address_batch(
+		&self,
+		requests: Vec<SwapDepositAddressRequest>,
+	) -> RpcResult<Vec<SwapDepositAddress>> {
+		if requests.is_empty() || requests.len() > MAX_BATCHED_DEPOSIT_CHANNELS as usize {
+			return Err(anyhow::anyhow!(
+				"The batch must contain between 1 and {MAX_BATCHED_DEPOSIT_CHANNELS} requests"
+			)
+			.into());
+		}
+
+		let calls = requests
+			.into_iter()
+			.map(
+				|SwapDepositAddressRequest {
+				     source_asset,
+				     destination_asset,
+				     destination_address,
+				     broker_commission,
+				     channel_metadata,
+				     boost_fee,
+				     affiliate_fees,
+				     refund_parameters,
+				     dca_parameters,
+				 }| {
+					Ok(RuntimeCall::from(
+						pallet_cf_swapping::Call::request_swap_deposit_address_with_affiliates {
+							source_asset,
+							destination_asset,
+							destination_address: destination_address
+								.try_parse_to_encoded_address(destination_asset.into())?,
+							broker_commission,
+							channel_metadata,
+							boost_fee: boost_fee.unwrap_or_default(),
+							affiliate_fees: affiliate_fees.unwrap_or_default(),
+							refund_parameters: refund_parameters.try_map_address(|addr| {
+								addr.try_parse_to_encoded_address(source_asset.into())
+							})?,
+							dca_parameters,
+						},
+					))
+				},
+			)
+			.collect::<anyhow::Result<Vec<_>>>()?;
+
+		let ExtrinsicData { events, header, .. } = self
+			.signed_pool_client
+			.submit_watch_dynamic(
+				RuntimeCall::from(pallet_utility::Call::batch_all { calls }),
+				false,
+				true,
+			)
+			.await
+			.map_err(CfApiError::from)?;
+
+		Ok(events
+			.find_all_static_events::<cf_static_runtime::swapping::events::SwapDepositAddressReady>(
+				false,
+			)
+			.map_err(CfApiError::from)?
+			.into_iter()
+			.map(
+				|cf_static_runtime::swapping::events::SwapDepositAddressReady {
+				     deposit_address,
+				     channel_id,
+				     source_chain_expiry_block,
+				     channel_opening_fee,
+				     refund_parameters,
+				     ..
+				 }| SwapDepositAddress {
+					address: AddressString::from_encoded_address(deposit_address.0),
+					issued_block: header.number,
+					channel_id,
+					source_chain_expiry_block: source_chain_expiry_block.into(),
+					channel_opening_fee: channel_opening_fee.into(),
+					refund_parameters: ChannelRefundParameters::from(refund_parameters)
+						.map_address(|refund_address| {
+							AddressString::from_encoded_address(&refund_address.0)
+						}),
+				},
+			)
+			.collect())
+	}
+
+	async fn request_swap_deposit_address_with_defaults(
+		&self,
+		source_asset: Asset,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		refund_address: AddressString,
+		min_price: U256,
+		broker_commission: Option<BasisPoints>,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+		affiliate_fees: Option<Affiliates<AccountId32>>,
+		retry_duration: Option<BlockNumber>,
+		dca_parameters: Option<DcaParameters>,
+	) -> RpcResult<SwapDepositAddress> {
+		let ExtrinsicData { events, header, .. } = self
+			.signed_pool_client
+			.submit_watch_dynamic(
+				RuntimeCall::from(
+					pallet_cf_swapping::Call::request_swap_deposit_address_with_broker_defaults {
+						source_asset,
+						destination_asset,
+						destination_address: destination_address
+							.try_parse_to_encoded_address(destination_asset.into())?,
+						broker_commission,
+						channel_metadata,
+						boost_fee,
+						affiliate_fees: affiliate_fees.unwrap_or_default(),
+						refund_address: refund_address
+							.try_parse_to_encoded_address(source_asset.into())?,
+						min_price,
+						retry_duration,
+						dca_parameters,
+					},
+				),
+				false,
+				true,
+			)
+			.await
+			.map_err(CfApiError::from)?;
+
+		Ok(extract_from_first_matching_event!(
+			events,
+			cf_static_runtime::swapping::events::SwapDepositAddressReady,
+			{
+				deposit_address,
+				channel_id,
+				source_chain_expiry_block,
+				channel_opening_fee,
+				refund_parameters
+			},
+			SwapDepositAddress {
+				address: AddressString::from_encoded_address(deposit_address.0),
+				issued_block: header.number,
+				channel_id,
+				source_chain_expiry_block: source_chain_expiry_block.into(),
+				channel_opening_fee: channel_opening_fee.into(),
+				refund_parameters: ChannelRefundParameters::from(refund_parameters)
+				.map_address(|refund_address| {
+					AddressString::from_encoded_address(&refund_address.0)
+				}),
+			}
+		)
+		.map_err(CfApiError::from)?)
+	}
+
+	async fn set_channel_defaults(
+		&self,
+		defaults: Option<BrokerChannelDefaults>,
+	) -> RpcResult<H256> {
+		let ExtrinsicData { tx_hash, .. } = self
+			.signed_pool_client
+			.submit_watch_dynamic(
+				RuntimeCall::from(pallet_cf_swapping::Call::set_broker_channel_defaults {
+					defaults,
+				}),
+				false,
+				true,
+			)
+			.await
+			.map_err(CfApiError::from)?;
+
+		Ok(tx_hash)
+	}
+
+	async fn get_channel_defaults(&self) -> RpcResult<Option<BrokerChannelDefaults>> {
+		self.rpc_backend.with_runtime_api_since(None, 5, |api, hash| {
+			api.cf_broker_channel_defaults(hash, self.signed_pool_client.account_id())
+		})
+	}
+
 	async fn withdraw_fees(
 		&self,
 		asset: Asset,
@@ -275,26 +450,57 @@ where
 		affiliate_fees: Option<Affiliates<AccountId32>>,
 		dca_parameters: Option<DcaParameters>,
 	) -> RpcResult<VaultSwapDetails<AddressString>> {
-		Ok(self
-			.rpc_backend
-			.client
-			.runtime_api()
-			.cf_request_swap_parameter_encoding(
-				self.rpc_backend.client.info().best_hash,
-				self.signed_pool_client.account_id(),
+		let api = self.rpc_backend.client.runtime_api();
+		let hash = self.rpc_backend.client.info().best_hash;
+
+		let broker = self.signed_pool_client.account_id();
+		let destination_address =
+			destination_address.try_parse_to_encoded_address(destination_asset.into())?;
+		let extra_parameters = extra_parameters.try_into_encoded_params(source_asset.into())?;
+		let boost_fee = boost_fee.unwrap_or_default();
+		let affiliate_fees = affiliate_fees.unwrap_or_default();
+
+		let api_version = api
+			.api_version::<dyn CustomRuntimeApi<state_chain_runtime::Block>>(hash)
+			.map_err(CfApiError::from)?
+			.unwrap_or_default();
+
+		let details = if api_version < 5 {
+			api.cf_request_swap_parameter_encoding_before_version_5(
+				hash,
+				broker,
 				source_asset,
 				destination_asset,
-				destination_address.try_parse_to_encoded_address(destination_asset.into())?,
+				destination_address,
 				broker_commission,
-				extra_parameters.try_into_encoded_params(source_asset.into())?,
+				extra_parameters,
 				channel_metadata,
-				boost_fee.unwrap_or_default(),
-				affiliate_fees.unwrap_or_default(),
+				boost_fee,
+				affiliate_fees,
 				dca_parameters,
 			)
 			.map_err(CfApiError::from)?
 			.map_err(CfApiError::from)?
-			.map_btc_address(Into::into))
+			.into()
+		} else {
+			api.cf_request_swap_parameter_encoding(
+				hash,
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				extra_parameters,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				dca_parameters,
+			)
+			.map_err(CfApiError::from)?
+			.map_err(CfApiError::from)?
+		};
+
+		Ok(details.map_btc_address(Into::into))
 	}
 
 	async fn mark_transaction_for_rejection(&self, tx_id: TransactionInId) -> RpcResult<()> {
@@ -444,6 +650,14 @@ where
 			.map_err(CfApiError::from)?)
 	}
 
+	async fn list_affiliates(&self) -> RpcResult<Vec<AffiliateAccount>> {
+		self.rpc_backend
+			.with_runtime_api_since(None, 5, |api, hash| {
+				api.cf_affiliates(hash, self.signed_pool_client.account_id())
+			})
+			.map(|affiliates| affiliates.into_iter().map(Into::into).collect())
+	}
+
 	async fn affiliate_withdrawal_request(
 		&self,
 		affiliate_account_id: AccountId32,
@@ -475,6 +689,58 @@ where
 		.map_err(CfApiError::from)?)
 	}
 
+	async fn affiliate_signing_payload(
+		&self,
+		short_id: AffiliateShortId,
+		action: AffiliateAction,
+	) -> RpcResult<H256> {
+		Ok(self
+			.rpc_backend
+			.with_runtime_api_since(None, 5, |api, hash| {
+				api.cf_affiliate_signing_payload(
+					hash,
+					self.signed_pool_client.account_id(),
+					short_id,
+					action,
+				)
+			})?
+			.map(H256::from)
+			.ok_or_else(|| anyhow::anyhow!("No affiliate is registered with this short id"))?)
+	}
+
+	async fn update_affiliate_withdrawal_address(
+		&self,
+		short_id: AffiliateShortId,
+		withdrawal_address: EthereumAddress,
+		signature: Bytes,
+	) -> RpcResult<AccountId32> {
+		let ExtrinsicData { events, .. } = self
+			.signed_pool_client
+			.submit_watch_dynamic(
+				RuntimeCall::from(pallet_cf_swapping::Call::update_affiliate_withdrawal_address {
+					broker_id: self.signed_pool_client.account_id(),
+					short_id,
+					withdrawal_address,
+					signature: signature
+						.0
+						.try_into()
+						.map_err(|_| anyhow::anyhow!("The signature must be 65 bytes long"))?,
+				}),
+				false,
+				true,
+			)
+			.await
+			.map_err(CfApiError::from)?;
+
+		Ok(extract_from_first_matching_event!(
+			events,
+			cf_static_runtime::swapping::events::AffiliateWithdrawalAddressUpdated,
+			{ affiliate_id },
+			AccountId32::from(affiliate_id.0)
+		)
+		.map_err(CfApiError::from)?)
+	}
+
 	async fn vault_addresses(&self) -> RpcResult<VaultAddresses> {
 		Ok(self
 			.rpc_backend
@@ -15,27 +15,37 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-	backend::{CustomRpcBackend, NotificationBehaviour},
-	boost_pool_rpc::BoostPoolFeesRpc,
+	backend::{ensure_api_version, CustomRpcBackend, NotificationBehaviour},
+	boost_pool_rpc::{BoostPoolFeesRpc, BoostPoolPageRpc},
+	storage_batch::{RpcStorageQuery, RpcStorageQueryResult},
 };
 use boost_pool_rpc::BoostPoolDetailsRpc;
 use cf_amm::{
 	common::{PoolPairsMap, Side},
-	math::{Amount as AmmAmount, Tick},
+	math::{
+		mul_div_floor_checked, sqrt_price_to_price, Amount as AmmAmount, SqrtPriceQ64F96, Tick,
+	},
 	range_orders::Liquidity,
 };
 use cf_chains::{
-	address::{AddressString, ForeignChainAddressHumanreadable, ToHumanreadableAddress},
+	address::{
+		AddressString, EncodedAddress, ForeignChainAddressHumanreadable, ToHumanreadableAddress,
+	},
 	eth::Address as EthereumAddress,
 	CcmChannelMetadata, Chain, VaultSwapExtraParametersRpc, MAX_CCM_MSG_LENGTH,
 };
 use cf_node_client::events_decoder;
 use cf_primitives::{
 	chains::assets::any::{self, AssetMap},
-	AccountRole, Affiliates, Asset, AssetAmount, BasisPoints, BlockNumber, BroadcastId,
-	DcaParameters, EpochIndex, ForeignChain, NetworkEnvironment, SemVer, SwapId, SwapRequestId,
+	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, BlockNumber,
+	BoostPoolTier, BroadcastId, DcaParameters, EgressId, EpochIndex, ForeignChain,
+	NetworkEnvironment, SemVer, SwapId, SwapRequestId, SWAP_DELAY_BLOCKS,
 };
-use cf_rpc_apis::{call_error, internal_error, CfErrorCode, OrderFills, RpcApiError, RpcResult};
+use cf_rpc_apis::{
+	broker::AffiliateAccount, call_error, internal_error, CfErrorCode, OrderFills, RpcApiError,
+	RpcResult,
+};
+use cf_traits::SafeModeCode;
 use cf_utilities::rpc::NumberOrHex;
 use core::ops::Range;
 use jsonrpsee::{
@@ -49,17 +59,21 @@ use jsonrpsee::{
 };
 use pallet_cf_governance::GovCallHash;
 use pallet_cf_pools::{
-	AskBidMap, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders, PoolPriceV1,
-	UnidirectionalPoolDepth,
+	AskBidMap, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders, PoolOrdersCursor,
+	PoolOrdersPage, PoolOrdersValuation, PoolPriceV1, PoolStatistics, RangeOrderMintQuote,
+	RangeOrderSize, UnidirectionalPoolDepth,
+};
+use pallet_cf_swapping::{
+	AffiliateAction, AffiliateDetails, BrokerChannelDefaults, DcaUpdate, SwapLegInfo,
+	SwapRequestStatus, SwapStatus,
 };
-use pallet_cf_swapping::{AffiliateDetails, SwapLegInfo};
 use sc_client_api::{
 	blockchain::HeaderMetadata, Backend, BlockBackend, BlockchainEvents, ExecutorProvider,
 	HeaderBackend, StorageProvider,
 };
 use serde::{Deserialize, Serialize};
 use sp_api::{ApiError, ApiExt, CallApiAt};
-use sp_core::U256;
+use sp_core::{H256, U256};
 use sp_runtime::{
 	traits::{Block as BlockT, UniqueSaturatedInto},
 	AccountId32, Percent, Permill,
@@ -69,11 +83,17 @@ use state_chain_runtime::{
 	chainflip::{BlockUpdate, Offence},
 	constants::common::TX_FEE_MULTIPLIER,
 	runtime_apis::{
-		AuctionState, BoostPoolDepth, BoostPoolDetails, BrokerInfo, CcmData, ChainAccounts,
-		ChannelActionType, CustomRuntimeApi, DispatchErrorWithMessage, ElectoralRuntimeApi,
-		FailingWitnessValidators, FeeTypes, LiquidityProviderBoostPoolInfo, LiquidityProviderInfo,
-		RuntimeApiPenalty, SimulatedSwapInformation, TradingStrategyInfo, TradingStrategyLimits,
-		TransactionScreeningEvents, ValidatorInfo, VaultAddresses, VaultSwapDetails,
+		AccountSummary, AuctionState, BoostPoolDepth, BoostPoolDetails, BoostQuote,
+		BroadcastLifecycleEvent, BroadcastState, BrokerInfo, CallFeeQuote, CcmData, ChainAccounts,
+		ChannelActionType, CustomRuntimeApi, DecodedVaultSwap, DepositEvent,
+		DispatchErrorWithMessage, EgressBroadcastEvent, ElectoralRuntimeApi, EpochRewardStatement,
+		ExternalTransactionRef, FailedCallDetails, FailingWitnessValidators, FeeScalingInfo,
+		FeeTypes, LiquidityProviderBoostPoolInfo, LiquidityProviderInfo, LpEpochEarnedFees,
+		ProjectedAuctionOutcome, RotationBlocker, RotationStatus, RuntimeApiPenalty,
+		ScheduledEgress, SimulatedSwapInformation, SimulatedSwapInformationWithFees,
+		TradingStrategyInfo, TradingStrategyLimits, TransactionScreeningEvents, ValidatorInfo,
+		ValidatorPerformance, ValidatorWitnessReport, VaultAddresses, VaultSwapDetails,
+		MAX_ACCOUNTS_PAGE_SIZE, MAX_BOOST_POOL_DETAILS_PAGE_SIZE,
 	},
 	safe_mode::RuntimeSafeMode,
 	Hash, NetworkFee, SolanaInstance,
@@ -82,14 +102,102 @@ use std::{
 	collections::{BTreeMap, BTreeSet, HashMap},
 	marker::PhantomData,
 	sync::Arc,
+	time::{Duration, Instant},
 };
 
+pub mod account_events;
 pub mod backend;
 pub mod broker;
 pub mod lp;
 pub mod monitoring;
 pub mod order_fills;
 pub mod pool_client;
+pub mod storage_batch;
+
+/// The state of the broadcast that sends out the output of a swap request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum SwapBroadcastStatus {
+	Pending { chain: ForeignChain, broadcast_id: BroadcastId },
+	Succeeded { chain: ForeignChain, broadcast_id: BroadcastId },
+	Aborted { chain: ForeignChain, broadcast_id: BroadcastId },
+}
+
+/// A swap request's status together with the state of its egress broadcast, if any.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapStatusUpdate {
+	#[serde(flatten)]
+	pub swap_status: SwapStatus,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub broadcast: Option<SwapBroadcastStatus>,
+}
+
+impl SwapStatusUpdate {
+	/// Whether the swap request has reached a state it won't move on from.
+	pub fn is_final(&self) -> bool {
+		match self.swap_status.status {
+			SwapRequestStatus::Credited | SwapRequestStatus::Refunded => true,
+			SwapRequestStatus::AwaitingEgress { .. } => matches!(
+				self.broadcast,
+				Some(SwapBroadcastStatus::Succeeded { .. } | SwapBroadcastStatus::Aborted { .. })
+			),
+			SwapRequestStatus::Scheduled | SwapRequestStatus::Executing { .. } => false,
+		}
+	}
+
+	fn apply_broadcast_event(&mut self, egress_id: EgressId, event: EgressBroadcastEvent) {
+		self.broadcast = match (event, self.broadcast.take()) {
+			(EgressBroadcastEvent::BroadcastRequested { broadcast_id, egress_ids }, _)
+				if egress_ids.contains(&egress_id) =>
+				Some(SwapBroadcastStatus::Pending { chain: egress_id.0, broadcast_id }),
+			(
+				EgressBroadcastEvent::BroadcastSucceeded { chain, broadcast_id },
+				Some(SwapBroadcastStatus::Pending {
+					chain: pending_chain,
+					broadcast_id: pending_id,
+				}),
+			) if (chain, broadcast_id) == (pending_chain, pending_id) =>
+				Some(SwapBroadcastStatus::Succeeded { chain, broadcast_id }),
+			(
+				EgressBroadcastEvent::BroadcastAborted { chain, broadcast_id },
+				Some(SwapBroadcastStatus::Pending {
+					chain: pending_chain,
+					broadcast_id: pending_id,
+				}),
+			) if (chain, broadcast_id) == (pending_chain, pending_id) =>
+				Some(SwapBroadcastStatus::Aborted { chain, broadcast_id }),
+			(_, broadcast) => broadcast,
+		};
+	}
+}
+
+/// The state of a broadcast, as tracked by `cf_subscribe_broadcast_status`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum BroadcastStatus {
+	Pending,
+	/// The transaction reference is only known if the broadcast succeeded after subscribing.
+	Succeeded {
+		transaction_ref: Option<ExternalTransactionRef>,
+	},
+	Aborted,
+}
+
+/// A broadcast's status together with the steps it went through in the latest block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BroadcastStatusUpdate {
+	#[serde(flatten)]
+	pub status: BroadcastStatus,
+	/// The events of the broadcast in this block, eg. retries or re-signing of the transaction.
+	pub events: Vec<BroadcastLifecycleEvent>,
+}
+
+impl BroadcastStatusUpdate {
+	/// Whether the broadcast has succeeded or has been aborted.
+	pub fn is_final(&self) -> bool {
+		!matches!(self.status, BroadcastStatus::Pending)
+	}
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScheduledSwap {
@@ -223,6 +331,15 @@ pub enum RpcAccountInfo {
 		apy_bp: Option<u32>,
 		restricted_balances: BTreeMap<EthereumAddress, NumberOrHex>,
 	},
+	Operator {
+		flip_balance: NumberOrHex,
+		managed_validators: Vec<state_chain_runtime::AccountId>,
+	},
+	/// An account holding both the Broker and the LiquidityProvider roles.
+	BrokerAndLiquidityProvider {
+		broker: Box<RpcAccountInfo>,
+		liquidity_provider: Box<RpcAccountInfo>,
+	},
 }
 
 impl RpcAccountInfo {
@@ -272,6 +389,10 @@ impl RpcAccountInfo {
 		}
 	}
 
+	fn operator(balance: u128, managed_validators: Vec<state_chain_runtime::AccountId>) -> Self {
+		Self::Operator { flip_balance: balance.into(), managed_validators }
+	}
+
 	fn validator(info: ValidatorInfo) -> Self {
 		Self::Validator {
 			flip_balance: info.balance.into(),
@@ -330,6 +451,40 @@ pub struct RpcAuctionState {
 	min_active_bid: Option<NumberOrHex>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RpcFeeScalingInfo {
+	pub call_count: u16,
+	pub multiplier: u16,
+	pub next_call_fee: NumberOrHex,
+}
+
+impl From<FeeScalingInfo> for RpcFeeScalingInfo {
+	fn from(info: FeeScalingInfo) -> Self {
+		Self {
+			call_count: info.call_count,
+			multiplier: info.multiplier,
+			next_call_fee: info.next_call_fee.into(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RpcCallFeeQuote {
+	pub base_fee: NumberOrHex,
+	pub multiplier: u16,
+	pub fee: NumberOrHex,
+}
+
+impl From<CallFeeQuote> for RpcCallFeeQuote {
+	fn from(quote: CallFeeQuote) -> Self {
+		Self {
+			base_fee: quote.base_fee.into(),
+			multiplier: quote.multiplier,
+			fee: quote.fee.into(),
+		}
+	}
+}
+
 impl From<AuctionState> for RpcAuctionState {
 	fn from(auction_state: AuctionState) -> Self {
 		Self {
@@ -399,6 +554,99 @@ fn into_rpc_swap_output(
 	}
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RpcSwapOutputWithFees {
+	#[serde(flatten)]
+	pub swap_output: RpcSwapOutputV2,
+	pub affiliate_fees: Vec<(state_chain_runtime::AccountId, RpcFee)>,
+	pub boost_fee: RpcFee,
+}
+fn into_rpc_swap_output_with_fees(
+	simulated_swap_info: SimulatedSwapInformationWithFees,
+	from_asset: Asset,
+	to_asset: Asset,
+) -> RpcSwapOutputWithFees {
+	RpcSwapOutputWithFees {
+		swap_output: into_rpc_swap_output(
+			SimulatedSwapInformation {
+				intermediary: simulated_swap_info.intermediary,
+				output: simulated_swap_info.output,
+				network_fee: simulated_swap_info.network_fee,
+				ingress_fee: simulated_swap_info.ingress_fee,
+				egress_fee: simulated_swap_info.egress_fee,
+				broker_fee: simulated_swap_info.broker_fee,
+			},
+			from_asset,
+			to_asset,
+		),
+		affiliate_fees: simulated_swap_info
+			.affiliate_fees
+			.into_iter()
+			.map(|(account_id, amount)| {
+				(account_id, RpcFee { asset: cf_primitives::STABLE_ASSET, amount: amount.into() })
+			})
+			.collect(),
+		boost_fee: RpcFee { asset: from_asset, amount: simulated_swap_info.boost_fee.into() },
+	}
+}
+
+/// Validates the parameters common to all swap rate queries, converting them into the form
+/// expected by the runtime api.
+fn validate_swap_rate_params(
+	amount: U256,
+	ccm_data: Option<CcmData>,
+	additional_orders: Option<Vec<SwapRateV2AdditionalOrder>>,
+) -> RpcResult<(
+	AssetAmount,
+	Option<Vec<state_chain_runtime::runtime_apis::SimulateSwapAdditionalOrder>>,
+)> {
+	let amount =
+		amount
+			.try_into()
+			.map_err(|_| "Swap input amount too large.")
+			.and_then(|amount: u128| {
+				if amount == 0 {
+					Err("Swap input amount cannot be zero.")
+				} else {
+					Ok(amount)
+				}
+			})
+			.map_err(|s| ErrorObject::owned(ErrorCode::InvalidParams.code(), s, None::<()>))?;
+
+	if let Some(CcmData { message_length, .. }) = ccm_data {
+		if message_length > MAX_CCM_MSG_LENGTH {
+			return Err(RpcApiError::ErrorObject(ErrorObject::owned(
+				ErrorCode::InvalidParams.code(),
+				"CCM message size too large.",
+				None::<()>,
+			)));
+		}
+	}
+
+	let additional_orders = additional_orders.map(|additional_orders| {
+		additional_orders
+			.into_iter()
+			.map(|additional_order| match additional_order {
+				SwapRateV2AdditionalOrder::LimitOrder {
+					base_asset,
+					quote_asset,
+					side,
+					tick,
+					sell_amount,
+				} => state_chain_runtime::runtime_apis::SimulateSwapAdditionalOrder::LimitOrder {
+					base_asset,
+					quote_asset,
+					side,
+					tick,
+					sell_amount: sell_amount.unique_saturated_into(),
+				},
+			})
+			.collect()
+	});
+
+	Ok((amount, additional_orders))
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub enum SwapRateV2AdditionalOrder {
 	LimitOrder { base_asset: Asset, quote_asset: Asset, side: Side, tick: Tick, sell_amount: U256 },
@@ -442,6 +690,7 @@ pub struct FundingEnvironment {
 pub struct SwappingEnvironment {
 	maximum_swap_amounts: any::AssetMap<Option<NumberOrHex>>,
 	network_fee_hundredth_pips: Permill,
+	swap_delay_blocks: u32,
 	swap_retry_delay_blocks: u32,
 	max_swap_retry_duration_blocks: u32,
 	max_swap_request_duration_blocks: u32,
@@ -456,6 +705,48 @@ pub struct RpcEnvironment {
 	pools: PoolsEnvironment,
 }
 
+/// The version of the [RpcFullEnvironment] format. Incremented whenever its fields change.
+pub const FULL_ENVIRONMENT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RpcFullEnvironment {
+	pub version: u32,
+	pub block_hash: state_chain_runtime::Hash,
+	pub block_number: BlockNumber,
+	#[serde(flatten)]
+	pub environment: RpcEnvironment,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcAccountSummary {
+	pub account_id: state_chain_runtime::AccountId,
+	pub role: AccountRole,
+	pub vanity_name: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flip_balance: Option<NumberOrHex>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub asset_balances: Option<any::AssetMap<NumberOrHex>>,
+}
+
+impl From<AccountSummary> for RpcAccountSummary {
+	fn from(summary: AccountSummary) -> Self {
+		Self {
+			account_id: summary.account_id,
+			role: summary.role,
+			// The vanity name is guaranteed to be utf8 when it is saved on the chain.
+			vanity_name: String::from_utf8_lossy(&summary.vanity_name).into_owned(),
+			flip_balance: summary.flip_balance.map(Into::into),
+			asset_balances: summary.asset_balances.map(|balances| balances.map(Into::into)),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcAccountsPage {
+	pub accounts: Vec<RpcAccountSummary>,
+	pub next: Option<state_chain_runtime::AccountId>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct PoolPriceV2 {
 	pub base_asset: Asset,
@@ -464,6 +755,18 @@ pub struct PoolPriceV2 {
 	pub price: pallet_cf_pools::PoolPriceV2,
 }
 
+/// The relative change from one pool price to another, in basis points.
+fn pool_price_change_bps(from: SqrtPriceQ64F96, to: SqrtPriceQ64F96) -> BasisPoints {
+	let (from, to) = (sqrt_price_to_price(from), sqrt_price_to_price(to));
+	if from.is_zero() {
+		return if to.is_zero() { 0 } else { BasisPoints::MAX };
+	}
+	let change = if to > from { to - from } else { from - to };
+	mul_div_floor_checked(change, U256::from(10_000), from)
+		.and_then(|bps| bps.try_into().ok())
+		.unwrap_or(BasisPoints::MAX)
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct RpcPrewitnessedSwap {
 	pub base_asset: Asset,
@@ -477,6 +780,75 @@ pub struct SwapResponse {
 	swaps: Vec<ScheduledSwap>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcAssetCapabilities {
+	#[serde(flatten)]
+	pub asset: Asset,
+	pub decimals: u8,
+	pub minimum_deposit_amount: NumberOrHex,
+	/// The minimum amount of a vault swap, or `None` if the chain doesn't support vault swaps.
+	pub minimum_vault_swap_amount: Option<NumberOrHex>,
+	/// Whether deposits of the asset can currently be boosted.
+	pub boost_enabled: bool,
+	pub boost_tiers: Vec<BoostPoolTier>,
+	pub ccm_supported: bool,
+	pub deposits_enabled: bool,
+	pub egress_enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcBoostQuote {
+	/// Whether the full amount could currently be boosted. If not, the deposit won't be boosted.
+	pub fully_boostable: bool,
+	/// The amount boosted by each of the pools used, by tier.
+	pub boosted_amounts: BTreeMap<BoostPoolTier, NumberOrHex>,
+	pub boost_fee: NumberOrHex,
+	/// The boost fee relative to the deposit amount, blended across the pools used.
+	pub boost_fee_bps: BasisPoints,
+	/// The deposit amount after the boost fee, but before the ingress fee.
+	pub net_amount: NumberOrHex,
+}
+
+impl From<BoostQuote> for RpcBoostQuote {
+	fn from(quote: BoostQuote) -> Self {
+		Self {
+			fully_boostable: quote.fully_boostable,
+			boosted_amounts: quote
+				.boosted_amounts
+				.into_iter()
+				.map(|(tier, amount)| (tier, amount.into()))
+				.collect(),
+			boost_fee: quote.boost_fee.into(),
+			boost_fee_bps: quote.boost_fee_bps,
+			net_amount: quote.net_amount.into(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RpcSafeModeStatus {
+	/// The overall safe mode code of the runtime.
+	pub code: SafeModeCode,
+	/// The safe mode code of each pallet.
+	pub pallets: BTreeMap<String, SafeModeCode>,
+	/// The state of each of the individual features.
+	pub features: RuntimeSafeMode,
+}
+
+impl From<RuntimeSafeMode> for RpcSafeModeStatus {
+	fn from(safe_mode: RuntimeSafeMode) -> Self {
+		Self {
+			code: SafeModeCode::of(&safe_mode),
+			pallets: safe_mode
+				.pallet_codes()
+				.into_iter()
+				.map(|(pallet, code)| (pallet.to_string(), code))
+				.collect(),
+			features: safe_mode,
+		}
+	}
+}
+
 type TradingStrategyInfoHexAmounts = TradingStrategyInfo<NumberOrHex>;
 
 mod boost_pool_rpc {
@@ -593,6 +965,12 @@ mod boost_pool_rpc {
 			}
 		}
 	}
+	#[derive(Serialize, Deserialize, Clone)]
+	pub struct BoostPoolPageRpc<T> {
+		#[serde(flatten)]
+		pub pool: T,
+		pub next_offset: Option<u32>,
+	}
 }
 
 type BoostPoolDepthResponse = Vec<BoostPoolDepth>;
@@ -741,6 +1119,21 @@ pub trait CustomApi {
 		additional_orders: Option<Vec<SwapRateV2AdditionalOrder>>,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<RpcSwapOutputV2>;
+	#[method(name = "swap_rate_with_fees")]
+	fn cf_pool_swap_rate_with_fees(
+		&self,
+		from_asset: Asset,
+		to_asset: Asset,
+		amount: U256,
+		broker_commission: BasisPoints,
+		affiliate_fees: Option<Affiliates<state_chain_runtime::AccountId>>,
+		boost_fee: Option<BasisPoints>,
+		dca_parameters: Option<DcaParameters>,
+		ccm_data: Option<CcmData>,
+		exclude_fees: Option<BTreeSet<FeeTypes>>,
+		additional_orders: Option<Vec<SwapRateV2AdditionalOrder>>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcSwapOutputWithFees>;
 	#[method(name = "required_asset_ratio_for_range_order")]
 	fn cf_required_asset_ratio_for_range_order(
 		&self,
@@ -797,6 +1190,17 @@ pub trait CustomApi {
 		liquidity: Liquidity,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<PoolPairsMap<AmmAmount>>;
+	/// Returns the assets that would be debited for a new range order of the given size at the
+	/// pool's current price, and the liquidity the order would have.
+	#[method(name = "pool_range_order_mint_quote")]
+	fn cf_pool_range_order_mint_quote(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		tick_range: Range<Tick>,
+		size: RangeOrderSize,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RangeOrderMintQuote>;
 	#[method(name = "funding_environment")]
 	fn cf_funding_environment(
 		&self,
@@ -824,6 +1228,13 @@ pub trait CustomApi {
 	) -> RpcResult<Vec<PoolPairsMap<Asset>>>;
 	#[method(name = "environment")]
 	fn cf_environment(&self, at: Option<state_chain_runtime::Hash>) -> RpcResult<RpcEnvironment>;
+	/// Returns the same data as `cf_environment`, all read at a single block, together with the
+	/// block and the version of the response format.
+	#[method(name = "full_environment")]
+	fn cf_full_environment(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcFullEnvironment>;
 	#[deprecated(note = "Use direct storage access of `CurrentReleaseVersion` instead.")]
 	#[method(name = "current_compatibility_version")]
 	fn cf_current_compatibility_version(&self) -> RpcResult<SemVer>;
@@ -835,6 +1246,18 @@ pub trait CustomApi {
 	#[subscription(name = "subscribe_pool_price_v2", item = BlockUpdate<PoolPriceV2>)]
 	async fn cf_subscribe_pool_price_v2(&self, base_asset: Asset, quote_asset: Asset);
 
+	// Subscribe to the price of the base_asset/quote_asset pool. Unlike `subscribe_pool_price_v2`,
+	// an update is only produced once at least `min_interval_ms` have passed since the previous
+	// update and the pool price has moved by at least `min_change_bps` since then.
+	#[subscription(name = "subscribe_pool_price_throttled", item = BlockUpdate<PoolPriceV2>)]
+	async fn cf_subscribe_pool_price_throttled(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		min_interval_ms: Option<u64>,
+		min_change_bps: Option<BasisPoints>,
+	);
+
 	// Subscribe to a stream that on every block produces a list of all scheduled/pending
 	// swaps in the base_asset/quote_asset pool, including any "implicit" half-swaps (as a
 	// part of a swap involving two pools)
@@ -847,6 +1270,35 @@ pub trait CustomApi {
 	#[subscription(name = "subscribe_transaction_screening_events", item = BlockUpdate<TransactionScreeningEvents>)]
 	async fn cf_subscribe_transaction_screening_events(&self);
 
+	// Subscribe to the status of a swap request. An update is produced whenever the status changes
+	// in a finalized block, until the output has been credited or broadcast, or the request has
+	// been refunded.
+	#[subscription(name = "subscribe_swap_status", item = BlockUpdate<SwapStatusUpdate>)]
+	async fn cf_subscribe_swap_status(&self, swap_request_id: SwapRequestId);
+
+	// Subscribe to the deposits made to the given addresses, or to any deposit channel owned by the
+	// given broker. On every finalized block, produces the deposits that were pre-witnessed,
+	// boosted or finalised in that block.
+	#[subscription(name = "subscribe_deposits", item = BlockUpdate<Vec<DepositEvent>>)]
+	async fn cf_subscribe_deposits(
+		&self,
+		deposit_addresses: Option<Vec<AddressString>>,
+		broker: Option<state_chain_runtime::AccountId>,
+	);
+
+	// Subscribe to the progress of authority set rotations. An update is produced whenever the
+	// rotation phase, the candidate set, or the rotation progress of any chain's vault changes.
+	#[subscription(name = "subscribe_rotation_status", item = BlockUpdate<RotationStatus>)]
+	async fn cf_subscribe_rotation_status(&self);
+
+	#[subscription(name = "subscribe_safe_mode_status", item = BlockUpdate<RpcSafeModeStatus>)]
+	async fn cf_subscribe_safe_mode_status(&self);
+
+	// Subscribe to the status of a broadcast. An update is produced whenever the broadcast is
+	// retried or re-signed in a finalized block, until it has succeeded or has been aborted.
+	#[subscription(name = "subscribe_broadcast_status", item = BlockUpdate<BroadcastStatusUpdate>)]
+	async fn cf_subscribe_broadcast_status(&self, chain: ForeignChain, broadcast_id: BroadcastId);
+
 	#[method(name = "lp_get_order_fills")]
 	fn cf_lp_get_order_fills(&self, at: Option<Hash>) -> RpcResult<BlockUpdate<OrderFills>>;
 
@@ -861,6 +1313,25 @@ pub trait CustomApi {
 	#[method(name = "supported_assets")]
 	fn cf_supported_assets(&self) -> RpcResult<Vec<Asset>>;
 
+	/// Returns the supported assets together with their chain, decimals, deposit minimums, boost
+	/// tiers, CCM support, and whether their deposits and egresses are currently enabled.
+	#[method(name = "supported_assets_v2")]
+	fn cf_supported_assets_v2(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcAssetCapabilities>>;
+
+	/// Quotes the boost of a deposit of `amount`, using the boost pools with a fee of at most
+	/// `max_boost_fee` (by default, all of them) at their current liquidity.
+	#[method(name = "boost_quote")]
+	fn cf_boost_quote(
+		&self,
+		asset: Asset,
+		amount: NumberOrHex,
+		max_boost_fee: Option<BasisPoints>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcBoostQuote>;
+
 	#[method(name = "failed_call_ethereum")]
 	fn cf_failed_call_ethereum(
 		&self,
@@ -903,12 +1374,45 @@ pub trait CustomApi {
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<BoostPoolFeesResponse>;
 
+	/// Like `cf_boost_pool_details`, but for a single pool and at most `limit` of its boosters,
+	/// starting at `offset` (the `next_offset` of the previous page). If a `booster` is given, only
+	/// that booster's details are returned.
+	#[method(name = "boost_pool_details_page")]
+	fn cf_boost_pool_details_page(
+		&self,
+		asset: Asset,
+		fee_tier: BoostPoolTier,
+		booster: Option<state_chain_runtime::AccountId>,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BoostPoolPageRpc<BoostPoolDetailsRpc>>;
+
+	/// Like `cf_boost_pool_pending_fees`, but paginated in the same way as
+	/// `cf_boost_pool_details_page`.
+	#[method(name = "boost_pool_pending_fees_page")]
+	fn cf_boost_pool_pending_fees_page(
+		&self,
+		asset: Asset,
+		fee_tier: BoostPoolTier,
+		booster: Option<state_chain_runtime::AccountId>,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BoostPoolPageRpc<BoostPoolFeesRpc>>;
+
 	#[method(name = "safe_mode_statuses")]
 	fn cf_safe_mode_statuses(
 		&self,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<RuntimeSafeMode>;
 
+	#[method(name = "safe_mode_status")]
+	fn cf_safe_mode_status(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcSafeModeStatus>;
+
 	#[method(name = "solana_electoral_data")]
 	fn cf_solana_electoral_data(
 		&self,
@@ -939,6 +1443,15 @@ pub trait CustomApi {
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<()>;
 
+	#[method(name = "validate_ccm_channel_metadata")]
+	fn cf_validate_ccm_channel_metadata(
+		&self,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		channel_metadata: CcmChannelMetadata,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<()>;
+
 	#[method(name = "request_swap_parameter_encoding")]
 	fn cf_request_swap_parameter_encoding(
 		&self,
@@ -976,6 +1489,44 @@ pub trait CustomApi {
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<(state_chain_runtime::AccountId, AffiliateDetails)>>;
 
+	/// Returns the affiliates registered by `broker`, with the fees they have earned but not yet
+	/// withdrawn.
+	#[method(name = "affiliates")]
+	fn cf_affiliates(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<AffiliateAccount>>;
+
+	/// Returns the hash that an affiliate has to sign with the key of its withdrawal address to
+	/// authorise `action`, or `None` if `broker` has no affiliate with this short id.
+	#[method(name = "affiliate_signing_payload")]
+	fn cf_affiliate_signing_payload(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		short_id: AffiliateShortId,
+		action: AffiliateAction,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<H256>>;
+
+	/// Returns the channel parameters registered by `broker` as defaults, if any.
+	#[method(name = "broker_channel_defaults")]
+	fn cf_broker_channel_defaults(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<BrokerChannelDefaults>>;
+
+	/// Returns the hash that the EVM refund address of a DCA swap request has to sign to
+	/// authorise `update`, or `None` if the swap request does not refund to an EVM address.
+	#[method(name = "dca_update_signing_payload")]
+	fn cf_dca_update_signing_payload(
+		&self,
+		swap_request_id: SwapRequestId,
+		update: DcaUpdate,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<H256>>;
+
 	#[method(name = "get_vault_addresses")]
 	fn cf_vault_addresses(
 		&self,
@@ -1000,50 +1551,282 @@ pub trait CustomApi {
 		&self,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<TradingStrategyLimits>;
-}
 
-/// An RPC extension for the state chain node.
-pub struct CustomRpc<C, B, BE> {
-	pub rpc_backend: CustomRpcBackend<C, B, BE>,
-}
+	#[method(name = "get_private_evm_deposit_addresses")]
+	fn cf_private_evm_deposit_addresses(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<(ForeignChain, Vec<(Asset, EncodedAddress)>)>>;
 
-impl<C, B, BE> CustomRpc<C, B, BE>
-where
-	B: BlockT<Hash = state_chain_runtime::Hash>,
-	C: Send + Sync + 'static + HeaderBackend<B>,
-{
-	pub fn new(
-		client: Arc<C>,
-		backend: Arc<BE>,
-		executor: Arc<dyn sp_core::traits::SpawnNamed>,
-	) -> Self {
-		Self { rpc_backend: CustomRpcBackend::new(client, backend, executor) }
-	}
-}
+	#[method(name = "swap_status")]
+	fn cf_swap_status(
+		&self,
+		swap_request_id: SwapRequestId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<SwapStatus>>;
 
-pub struct StorageQueryApi<'a, C, B>(&'a C, PhantomData<B>);
+	#[method(name = "pool_twap")]
+	fn cf_pool_twap(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		window_blocks: u32,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<PoolPriceV1>>;
 
-impl<'a, C, B> StorageQueryApi<'a, C, B>
-where
-	B: BlockT<Hash = state_chain_runtime::Hash>,
-	C: Send + Sync + 'static + CallApiAt<B>,
-{
-	pub fn new(client: &'a C) -> Self {
-		Self(client, Default::default())
-	}
+	#[method(name = "pool_statistics")]
+	fn cf_pool_statistics(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<(EpochIndex, PoolStatistics)>>;
 
-	pub fn get_storage_value<
-		V: frame_support::storage::StorageValue<T> + 'static,
-		T: codec::FullCodec,
-	>(
+	#[method(name = "pool_protocol_fee")]
+	fn cf_pool_protocol_fee(
 		&self,
-		hash: <B as BlockT>::Hash,
-	) -> RpcResult<<V as frame_support::storage::StorageValue<T>>::Query> {
-		self.with_state_backend(hash, || V::get())
-	}
+		base_asset: Asset,
+		quote_asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Permill>;
 
-	pub fn collect_from_storage_map<
-		M: frame_support::storage::IterableStorageMap<K, V> + 'static,
+	/// The info of each additional fee tier pool of the asset pair.
+	#[method(name = "fee_tier_pools")]
+	fn cf_fee_tier_pools(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<PoolInfo>>;
+
+	/// Like `cf_pool_orders`, but returns at most `limit` orders, starting after the order
+	/// identified by `after` (the `next` cursor of the previous page).
+	#[method(name = "pool_orders_page")]
+	fn cf_pool_orders_page(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		lp: Option<state_chain_runtime::AccountId>,
+		filled_orders: Option<bool>,
+		after: Option<PoolOrdersCursor<state_chain_runtime::AccountId>>,
+		limit: Option<u32>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<PoolOrdersPage<state_chain_runtime::Runtime>>;
+
+	/// Returns the LP's orders in every pool in which it has orders.
+	#[method(name = "lp_orders")]
+	fn cf_lp_orders(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		filled_orders: Option<bool>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<(Asset, Asset, PoolOrders<state_chain_runtime::Runtime>)>>;
+
+	/// Returns the LP's open orders in every pool in which it has orders, each valued in USDC at
+	/// the pool's current range order price, along with the total value of the orders in each
+	/// pool. Fees earned by the orders are not included in their value.
+	#[method(name = "lp_orders_valuation")]
+	fn cf_lp_orders_valuation(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<PoolOrdersValuation<state_chain_runtime::Runtime>>>;
+
+	/// Returns the account's current fee scaling multiplier for order updates in the pool with
+	/// the given base asset, and the fee that would be charged for its next order update.
+	#[method(name = "fee_scaling_multiplier")]
+	fn cf_fee_scaling_multiplier(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		base_asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcFeeScalingInfo>;
+
+	/// Returns the fee the account would be charged for submitting the SCALE-encoded call next,
+	/// including any fee scaling. `len` is the length of the encoded extrinsic.
+	#[method(name = "call_fee_quote")]
+	fn cf_call_fee_quote(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		call: Vec<u8>,
+		len: u32,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcCallFeeQuote>;
+
+	/// Returns block authorship, witnessing, reputation and heartbeat statistics for each current
+	/// authority over the current epoch.
+	#[method(name = "validator_performance")]
+	fn cf_validator_performance(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<ValidatorPerformance>>;
+
+	/// Returns the authority set, bond and each bidder's status that would result if the auction
+	/// were resolved with the current bids. Returns `None` if the auction can't be resolved.
+	#[method(name = "projected_auction_outcome")]
+	fn cf_projected_auction_outcome(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<ProjectedAuctionOutcome>>;
+
+	/// Returns all accounts whose vanity name matches the given name exactly.
+	#[method(name = "accounts_by_vanity_name")]
+	fn cf_accounts_by_vanity_name(
+		&self,
+		name: String,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<state_chain_runtime::AccountId>>;
+
+	/// Like `cf_accounts`, but only returns accounts with one of the given `roles` (all roles if
+	/// none are given), at most `limit` at a time, ordered by account id and starting after the
+	/// `after` account (the `next` of the previous page). Flip balances, and asset balances of
+	/// liquidity providers and brokers, are included if `include_balances` is set.
+	#[method(name = "accounts_page")]
+	fn cf_accounts_page(
+		&self,
+		roles: Option<Vec<AccountRole>>,
+		after: Option<state_chain_runtime::AccountId>,
+		limit: Option<u32>,
+		include_balances: Option<bool>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcAccountsPage>;
+
+	/// Returns everything that would currently keep the validator out of the next authority set,
+	/// so that operators can check their setup ahead of a rotation. An empty list means the
+	/// validator would win a seat if the auction were resolved now.
+	#[method(name = "rotation_blockers")]
+	fn cf_rotation_blockers(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RotationBlocker>>;
+
+	/// Returns the rewards each validator earned in the given epoch, alongside its witnessing
+	/// record. Statements are only kept until the epoch expires.
+	#[method(name = "epoch_reward_statements")]
+	fn cf_epoch_reward_statements(
+		&self,
+		epoch_index: EpochIndex,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<EpochRewardStatement>>;
+
+	/// Returns the fetches and transfers waiting in the egress queue of the given chain, in the
+	/// order in which they will be considered for the next batch.
+	#[method(name = "scheduled_egresses")]
+	fn cf_scheduled_egresses(
+		&self,
+		chain: ForeignChain,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<ScheduledEgress>>;
+
+	/// Returns the fees paid to the LP from each pool, per epoch.
+	#[method(name = "lp_earned_fees_history")]
+	fn cf_lp_earned_fees_history(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<LpEpochEarnedFees>>;
+
+	/// Returns the decoded call, failure reason and fallback status of a failed CCM or vault
+	/// transfer on an EVM chain, along with the signed transaction.
+	#[method(name = "failed_call_details")]
+	fn cf_failed_call_details(
+		&self,
+		chain: ForeignChain,
+		broadcast_id: BroadcastId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<FailedCallDetails>>;
+
+	/// Returns, for each epoch and each validator with witnessing failures between `from_block`
+	/// and `to_block` inclusive, the number of calls they missed or witnessed after the deadline.
+	/// Within each epoch, validators with the most failures come first. Failures are only retained
+	/// for a limited number of blocks.
+	#[method(name = "witness_report")]
+	fn cf_witness_report(
+		&self,
+		from_block: BlockNumber,
+		to_block: BlockNumber,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<ValidatorWitnessReport>>;
+
+	/// Decodes raw vault swap data back into its swap parameters: the calldata of an EVM vault
+	/// call, a Bitcoin nulldata payload, the data of a Solana vault swap instruction or an
+	/// Assethub vault swap remark.
+	#[method(name = "decode_vault_swap")]
+	fn cf_decode_vault_swap(
+		&self,
+		chain: ForeignChain,
+		data: sp_core::Bytes,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<DecodedVaultSwap>;
+
+	/// Fetches the given storage items in a single round trip. Each item is identified by its
+	/// pallet and storage item name, plus the hashed key for storage maps. Values are returned
+	/// SCALE-encoded, and additionally decoded to JSON if the item is known to the runtime.
+	#[method(name = "storage_batch")]
+	fn cf_storage_batch(
+		&self,
+		queries: Vec<RpcStorageQuery>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcStorageQueryResult>>;
+
+	/// Returns the fee, in units of `asset`, that would be withheld from an egress of `asset` to
+	/// `destination_chain`. This is the same estimate that is deducted when the egress is
+	/// scheduled, based on the currently tracked gas prices and fee rates of the chain. CCM
+	/// egresses are estimated using the given gas budget and message length.
+	#[method(name = "estimate_egress_fee")]
+	fn cf_estimate_egress_fee(
+		&self,
+		asset: Asset,
+		destination_chain: ForeignChain,
+		with_ccm_gas: Option<CcmData>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<NumberOrHex>;
+}
+
+/// An RPC extension for the state chain node.
+pub struct CustomRpc<C, B, BE> {
+	pub rpc_backend: CustomRpcBackend<C, B, BE>,
+}
+
+impl<C, B, BE> CustomRpc<C, B, BE>
+where
+	B: BlockT<Hash = state_chain_runtime::Hash>,
+	C: Send + Sync + 'static + HeaderBackend<B>,
+{
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		executor: Arc<dyn sp_core::traits::SpawnNamed>,
+	) -> Self {
+		Self { rpc_backend: CustomRpcBackend::new(client, backend, executor) }
+	}
+}
+
+pub struct StorageQueryApi<'a, C, B>(&'a C, PhantomData<B>);
+
+impl<'a, C, B> StorageQueryApi<'a, C, B>
+where
+	B: BlockT<Hash = state_chain_runtime::Hash>,
+	C: Send + Sync + 'static + CallApiAt<B>,
+{
+	pub fn new(client: &'a C) -> Self {
+		Self(client, Default::default())
+	}
+
+	pub fn get_storage_value<
+		V: frame_support::storage::StorageValue<T> + 'static,
+		T: codec::FullCodec,
+	>(
+		&self,
+		hash: <B as BlockT>::Hash,
+	) -> RpcResult<<V as frame_support::storage::StorageValue<T>>::Query> {
+		self.with_state_backend(hash, || V::get())
+	}
+
+	pub fn collect_from_storage_map<
+		M: frame_support::storage::IterableStorageMap<K, V> + 'static,
 		K: codec::FullEncode + codec::Decode,
 		V: codec::FullCodec,
 		I: FromIterator<(K, V)>,
@@ -1153,6 +1936,14 @@ impl From<CfApiError> for RpcApiError {
 
 #[macro_export]
 macro_rules! pass_through {
+	(since: $version:literal; $( $name:ident ( $( $arg:ident: $argt:ty ),* $(,)? ) -> $result_type:ty $([map: $mapping:expr])? ),+ $(,)?) => {
+		$(
+			fn $name(&self, $( $arg: $argt, )* at: Option<state_chain_runtime::Hash>,) -> RpcResult<$result_type> {
+				self.rpc_backend.with_runtime_api_since(at, $version, |api, hash| api.$name(hash, $($arg),* ))
+					$(.map($mapping))?
+			}
+		)+
+	};
 	($( $name:ident ( $( $arg:ident: $argt:ty ),* $(,)? ) -> $result_type:ty $([map: $mapping:expr])? ),+ $(,)?) => {
 		$(
 			fn $name(&self, $( $arg: $argt, )* at: Option<state_chain_runtime::Hash>,) -> RpcResult<$result_type> {
@@ -1165,6 +1956,16 @@ macro_rules! pass_through {
 
 #[macro_export]
 macro_rules! pass_through_and_flatten {
+	(since: $version:literal; $( $name:ident ( $( $arg:ident: $argt:ty ),* $(,)? ) -> $result_type:ty $([map: $mapping:expr])? ),+ $(,)?) => {
+		$(
+			fn $name(&self, $( $arg: $argt, )* at: Option<state_chain_runtime::Hash>,) -> RpcResult<$result_type> {
+				flatten_into_error(
+					self.rpc_backend.with_runtime_api_since(at, $version, |api, hash| api.$name(hash, $($arg),* ))
+						$(.map($mapping))?
+				)
+			}
+		)+
+	};
 	($( $name:ident ( $( $arg:ident: $argt:ty ),* $(,)? ) -> $result_type:ty $([map: $mapping:expr])? ),+ $(,)?) => {
 		$(
 			fn $name(&self, $( $arg: $argt, )* at: Option<state_chain_runtime::Hash>,) -> RpcResult<$result_type> {
@@ -1206,7 +2007,7 @@ where
 		+ BlockchainEvents<B>
 		+ CallApiAt<B>
 		+ StorageProvider<B, BE>,
-	C::Api: CustomRuntimeApi<B> + ElectoralRuntimeApi<B, SolanaInstance>,
+	C::Api: CustomRuntimeApi<B> + ElectoralRuntimeApi<B, SolanaInstance> + sp_api::Metadata<B>,
 {
 	pass_through! {
 		cf_is_auction_phase() -> bool,
@@ -1262,6 +2063,26 @@ where
 		cf_trading_strategy_limits() -> TradingStrategyLimits,
 	}
 
+	pass_through! {
+		since: 5;
+		cf_affiliates(broker: state_chain_runtime::AccountId) -> Vec<AffiliateAccount> [map: |affiliates| affiliates.into_iter().map(Into::into).collect()],
+		cf_affiliate_signing_payload(broker: state_chain_runtime::AccountId, short_id: AffiliateShortId, action: AffiliateAction) -> Option<H256> [map: |payload| payload.map(H256::from)],
+		cf_broker_channel_defaults(broker: state_chain_runtime::AccountId) -> Option<BrokerChannelDefaults>,
+		cf_dca_update_signing_payload(swap_request_id: SwapRequestId, update: DcaUpdate) -> Option<H256> [map: |payload| payload.map(H256::from)],
+		cf_private_evm_deposit_addresses(broker: state_chain_runtime::AccountId) -> Vec<(ForeignChain, Vec<(Asset, EncodedAddress)>)>,
+		cf_swap_status(swap_request_id: SwapRequestId) -> Option<SwapStatus>,
+		cf_pool_twap(base_asset: Asset, quote_asset: Asset, window_blocks: u32) -> Option<PoolPriceV1>,
+		cf_fee_scaling_multiplier(account_id: state_chain_runtime::AccountId, base_asset: Asset) -> RpcFeeScalingInfo [map: Into::into],
+		cf_validator_performance() -> Vec<ValidatorPerformance>,
+		cf_projected_auction_outcome() -> Option<ProjectedAuctionOutcome>,
+		cf_rotation_blockers(account_id: state_chain_runtime::AccountId) -> Vec<RotationBlocker>,
+		cf_epoch_reward_statements(epoch_index: EpochIndex) -> Vec<EpochRewardStatement>,
+		cf_scheduled_egresses(chain: ForeignChain) -> Vec<ScheduledEgress>,
+		cf_lp_earned_fees_history(lp: state_chain_runtime::AccountId) -> Vec<LpEpochEarnedFees>,
+		cf_failed_call_details(chain: ForeignChain, broadcast_id: BroadcastId) -> Option<FailedCallDetails>,
+		cf_witness_report(from_block: BlockNumber, to_block: BlockNumber) -> Vec<ValidatorWitnessReport>,
+	}
+
 	pass_through_and_flatten! {
 		cf_required_asset_ratio_for_range_order(base_asset: Asset, quote_asset: Asset, tick_range: Range<Tick>) -> PoolPairsMap<AmmAmount>,
 		cf_pool_orderbook(base_asset: Asset, quote_asset: Asset, orders: u32) -> PoolOrderbook,
@@ -1278,6 +2099,19 @@ where
 		cf_validate_refund_params(retry_duration: BlockNumber) -> (),
 	}
 
+	pass_through_and_flatten! {
+		since: 5;
+		cf_pool_statistics(base_asset: Asset, quote_asset: Asset) -> Vec<(EpochIndex, PoolStatistics)>,
+		cf_pool_protocol_fee(base_asset: Asset, quote_asset: Asset) -> Permill,
+		cf_fee_tier_pools(base_asset: Asset, quote_asset: Asset) -> Vec<PoolInfo>,
+		cf_pool_range_order_mint_quote(
+			base_asset: Asset,
+			quote_asset: Asset,
+			tick_range: Range<Tick>,
+			size: RangeOrderSize,
+		) -> RangeOrderMintQuote,
+	}
+
 	fn cf_current_compatibility_version(&self) -> RpcResult<SemVer> {
 		#[allow(deprecated)]
 		self.rpc_backend
@@ -1308,6 +2142,66 @@ where
 		})
 	}
 
+	fn cf_accounts_page(
+		&self,
+		roles: Option<Vec<AccountRole>>,
+		after: Option<state_chain_runtime::AccountId>,
+		limit: Option<u32>,
+		include_balances: Option<bool>,
+		at: Option<Hash>,
+	) -> RpcResult<RpcAccountsPage> {
+		let page = self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_accounts_page(
+				hash,
+				roles.unwrap_or_default(),
+				after,
+				limit.unwrap_or(MAX_ACCOUNTS_PAGE_SIZE),
+				include_balances.unwrap_or_default(),
+			)
+		})?;
+
+		Ok(RpcAccountsPage {
+			accounts: page.accounts.into_iter().map(Into::into).collect(),
+			next: page.next,
+		})
+	}
+
+	fn cf_decode_vault_swap(
+		&self,
+		chain: ForeignChain,
+		data: sp_core::Bytes,
+		at: Option<Hash>,
+	) -> RpcResult<DecodedVaultSwap> {
+		flatten_into_error(self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_decode_vault_swap(hash, chain, data.to_vec())
+		}))
+	}
+
+	fn cf_storage_batch(
+		&self,
+		queries: Vec<RpcStorageQuery>,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<RpcStorageQueryResult>> {
+		storage_batch::storage_batch(
+			self.rpc_backend.client.as_ref(),
+			self.rpc_backend.unwrap_or_best(at),
+			queries,
+		)
+	}
+
+	fn cf_estimate_egress_fee(
+		&self,
+		asset: Asset,
+		destination_chain: ForeignChain,
+		with_ccm_gas: Option<CcmData>,
+		at: Option<Hash>,
+	) -> RpcResult<NumberOrHex> {
+		flatten_into_error(self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_estimate_egress_fee(hash, asset, destination_chain, with_ccm_gas)
+		}))
+		.map(Into::into)
+	}
+
 	fn cf_pool_orders(
 		&self,
 		base_asset: Asset,
@@ -1320,6 +2214,70 @@ where
 			api.cf_pool_orders(hash, base_asset, quote_asset, lp, filled_orders.unwrap_or_default())
 		}))
 	}
+
+	fn cf_pool_orders_page(
+		&self,
+		base_asset: Asset,
+		quote_asset: Asset,
+		lp: Option<state_chain_runtime::AccountId>,
+		filled_orders: Option<bool>,
+		after: Option<PoolOrdersCursor<state_chain_runtime::AccountId>>,
+		limit: Option<u32>,
+		at: Option<Hash>,
+	) -> RpcResult<PoolOrdersPage<state_chain_runtime::Runtime>> {
+		flatten_into_error(self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_pool_orders_page(
+				hash,
+				base_asset,
+				quote_asset,
+				lp,
+				filled_orders.unwrap_or_default(),
+				after,
+				limit.unwrap_or(pallet_cf_pools::MAX_POOL_ORDERS_PAGE_SIZE),
+			)
+		}))
+	}
+
+	fn cf_lp_orders(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		filled_orders: Option<bool>,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<(Asset, Asset, PoolOrders<state_chain_runtime::Runtime>)>> {
+		flatten_into_error(self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_lp_orders(hash, lp, filled_orders.unwrap_or_default())
+		}))
+	}
+
+	fn cf_lp_orders_valuation(
+		&self,
+		lp: state_chain_runtime::AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<PoolOrdersValuation<state_chain_runtime::Runtime>>> {
+		flatten_into_error(
+			self.rpc_backend
+				.with_runtime_api_since(at, 5, |api, hash| api.cf_lp_orders_valuation(hash, lp)),
+		)
+	}
+
+	fn cf_safe_mode_status(&self, at: Option<Hash>) -> RpcResult<RpcSafeModeStatus> {
+		self.rpc_backend
+			.with_runtime_api(at, |api, hash| api.cf_safe_mode_statuses(hash))
+			.map(Into::into)
+	}
+
+	fn cf_call_fee_quote(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		call: Vec<u8>,
+		len: u32,
+		at: Option<Hash>,
+	) -> RpcResult<RpcCallFeeQuote> {
+		flatten_into_error(self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_call_fee_quote(hash, account_id, call, len)
+		}))
+		.map(Into::into)
+	}
 	fn cf_pool_price_v2(
 		&self,
 		base_asset: Asset,
@@ -1335,6 +2293,16 @@ where
 		})
 	}
 
+	fn cf_accounts_by_vanity_name(
+		&self,
+		name: String,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<state_chain_runtime::AccountId>> {
+		self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_accounts_by_vanity_name(hash, name.into_bytes())
+		})
+	}
+
 	fn cf_account_info(
 		&self,
 		account_id: state_chain_runtime::AccountId,
@@ -1343,19 +2311,23 @@ where
 		self.rpc_backend.with_runtime_api(at, |api, hash| {
 			let balance = api.cf_account_flip_balance(hash, &account_id)?;
 			let asset_balances = api.cf_free_balances(hash, account_id.clone())?;
+			let api_version = api
+				.api_version::<dyn CustomRuntimeApi<state_chain_runtime::Block>>(hash)?
+				.unwrap_or_default();
 
-			Ok::<_, CfApiError>(
-				match api
-					.cf_account_role(hash, account_id.clone())?
-					.unwrap_or(AccountRole::Unregistered)
-				{
+			let roles: Vec<AccountRole> = if api_version < 5 {
+				api.cf_account_role_before_version_5(hash, account_id.clone())?
+					.into_iter()
+					.collect()
+			} else {
+				api.cf_account_role(hash, account_id.clone())?
+			};
+
+			let role_info = |role| {
+				Ok::<_, CfApiError>(match role {
 					AccountRole::Unregistered =>
-						RpcAccountInfo::unregistered(balance, asset_balances),
+						RpcAccountInfo::unregistered(balance, asset_balances.clone()),
 					AccountRole::Broker => {
-						let api_version = api
-							.api_version::<dyn CustomRuntimeApi<state_chain_runtime::Block>>(hash)?
-							.unwrap_or_default();
-
 						let info = if api_version < 3 {
 							api.cf_broker_info_before_version_3(hash, account_id.clone())?.into()
 						} else {
@@ -1365,7 +2337,7 @@ where
 						RpcAccountInfo::broker(info, balance)
 					},
 					AccountRole::LiquidityProvider => {
-						let info = api.cf_liquidity_provider_info(hash, account_id)?;
+						let info = api.cf_liquidity_provider_info(hash, account_id.clone())?;
 
 						RpcAccountInfo::lp(info, api.cf_network_environment(hash)?, balance)
 					},
@@ -1374,8 +2346,24 @@ where
 
 						RpcAccountInfo::validator(info)
 					},
-				},
-			)
+					AccountRole::Operator => RpcAccountInfo::operator(
+						balance,
+						api.cf_operator_validators(hash, account_id.clone())?,
+					),
+				})
+			};
+
+			match roles[..] {
+				[] => role_info(AccountRole::Unregistered),
+				[role] => role_info(role),
+				[AccountRole::Broker, AccountRole::LiquidityProvider] |
+				[AccountRole::LiquidityProvider, AccountRole::Broker] =>
+					Ok(RpcAccountInfo::BrokerAndLiquidityProvider {
+						broker: Box::new(role_info(AccountRole::Broker)?),
+						liquidity_provider: Box::new(role_info(AccountRole::LiquidityProvider)?),
+					}),
+				[role, ..] => role_info(role),
+			}
 		})
 	}
 
@@ -1449,49 +2437,9 @@ where
 		additional_orders: Option<Vec<SwapRateV2AdditionalOrder>>,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<RpcSwapOutputV2> {
-		let amount = amount
-			.try_into()
-			.map_err(|_| "Swap input amount too large.")
-			.and_then(|amount: u128| {
-				if amount == 0 {
-					Err("Swap input amount cannot be zero.")
-				} else {
-					Ok(amount)
-				}
-			})
-			.map_err(|s| ErrorObject::owned(ErrorCode::InvalidParams.code(), s, None::<()>))?;
+		let (amount, additional_orders) =
+			validate_swap_rate_params(amount, ccm_data, additional_orders)?;
 
-		if let Some(CcmData { message_length, .. }) = ccm_data {
-			if message_length > MAX_CCM_MSG_LENGTH {
-				return Err(RpcApiError::ErrorObject(ErrorObject::owned(
-					ErrorCode::InvalidParams.code(),
-					"CCM message size too large.",
-					None::<()>,
-				)));
-			}
-		}
-
-		let additional_orders = additional_orders.map(|additional_orders| {
-			additional_orders
-				.into_iter()
-				.map(|additional_order| match additional_order {
-					SwapRateV2AdditionalOrder::LimitOrder {
-						base_asset,
-						quote_asset,
-						side,
-						tick,
-						sell_amount,
-					} =>
-						state_chain_runtime::runtime_apis::SimulateSwapAdditionalOrder::LimitOrder {
-							base_asset,
-							quote_asset,
-							side,
-							tick,
-							sell_amount: sell_amount.unique_saturated_into(),
-						},
-				})
-				.collect()
-		});
 		self.rpc_backend.with_runtime_api(at, |api, hash| {
 			Ok::<_, CfApiError>(
 				api.cf_pool_simulate_swap(
@@ -1512,6 +2460,45 @@ where
 		})
 	}
 
+	fn cf_pool_swap_rate_with_fees(
+		&self,
+		from_asset: Asset,
+		to_asset: Asset,
+		amount: U256,
+		broker_commission: BasisPoints,
+		affiliate_fees: Option<Affiliates<state_chain_runtime::AccountId>>,
+		boost_fee: Option<BasisPoints>,
+		dca_parameters: Option<DcaParameters>,
+		ccm_data: Option<CcmData>,
+		exclude_fees: Option<BTreeSet<FeeTypes>>,
+		additional_orders: Option<Vec<SwapRateV2AdditionalOrder>>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcSwapOutputWithFees> {
+		let (amount, additional_orders) =
+			validate_swap_rate_params(amount, ccm_data, additional_orders)?;
+
+		self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			Ok::<_, CfApiError>(
+				api.cf_pool_simulate_swap_with_fees(
+					hash,
+					from_asset,
+					to_asset,
+					amount,
+					broker_commission,
+					affiliate_fees.unwrap_or_default(),
+					boost_fee.unwrap_or_default(),
+					dca_parameters,
+					ccm_data,
+					exclude_fees.unwrap_or_default(),
+					additional_orders,
+				)?
+				.map(|simulated_swap_info| {
+					into_rpc_swap_output_with_fees(simulated_swap_info, from_asset, to_asset)
+				})?,
+			)
+		})
+	}
+
 	fn cf_ingress_egress_environment(
 		&self,
 		at: Option<state_chain_runtime::Hash>,
@@ -1549,12 +2536,20 @@ where
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<SwappingEnvironment> {
 		self.rpc_backend.with_runtime_api(at, |api, hash| {
+			let api_version = api
+				.api_version::<dyn CustomRuntimeApi<state_chain_runtime::Block>>(hash)?
+				.unwrap_or_default();
 			let swap_limits = api.cf_swap_limits(hash)?;
 			Ok::<_, CfApiError>(SwappingEnvironment {
 				maximum_swap_amounts: any::AssetMap::try_from_fn(|asset| {
 					api.cf_max_swap_amount(hash, asset).map(|option| option.map(Into::into))
 				})?,
 				network_fee_hundredth_pips: NetworkFee::get(),
+				swap_delay_blocks: if api_version < 5 {
+					SWAP_DELAY_BLOCKS
+				} else {
+					api.cf_swap_delay_blocks(hash)?
+				},
 				swap_retry_delay_blocks: api.cf_swap_retry_delay_blocks(hash)?,
 				max_swap_retry_duration_blocks: swap_limits.max_swap_retry_duration_blocks,
 				max_swap_request_duration_blocks: swap_limits.max_swap_request_duration_blocks,
@@ -1606,6 +2601,28 @@ where
 		})
 	}
 
+	fn cf_full_environment(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcFullEnvironment> {
+		let block_hash = self.rpc_backend.unwrap_or_best(at);
+		let block_number = self
+			.rpc_backend
+			.client
+			.number(block_hash)
+			.map_err(|e| call_error(e, CfErrorCode::SubstrateClientError))?
+			.ok_or_else(|| {
+				internal_error(format!("Could not fetch block number for block {:?}", block_hash))
+			})?;
+
+		Ok(RpcFullEnvironment {
+			version: FULL_ENVIRONMENT_VERSION,
+			block_hash,
+			block_number,
+			environment: self.cf_environment(Some(block_hash))?,
+		})
+	}
+
 	async fn cf_subscribe_pool_price(
 		&self,
 		pending_sink: PendingSubscriptionSink,
@@ -1653,6 +2670,49 @@ where
 			.await
 	}
 
+	async fn cf_subscribe_pool_price_throttled(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+		base_asset: Asset,
+		quote_asset: Asset,
+		min_interval_ms: Option<u64>,
+		min_change_bps: Option<BasisPoints>,
+	) {
+		let min_interval = Duration::from_millis(min_interval_ms.unwrap_or_default());
+		let min_change_bps = min_change_bps.unwrap_or_default();
+
+		self.rpc_backend
+			.new_subscription_with_state(
+				Default::default(), /* notification_behaviour */
+				true,               /* only_on_changes */
+				true,               /* end_on_error */
+				pending_sink,
+				move |client, hash, last_update: Option<&(PoolPriceV2, Instant)>| {
+					let price = PoolPriceV2 {
+						base_asset,
+						quote_asset,
+						price: (*client.runtime_api())
+							.cf_pool_price_v2(hash, base_asset, quote_asset)
+							.map_err(CfApiError::from)?
+							.map_err(CfApiError::from)?,
+					};
+					Ok(match last_update {
+						// Repeating the previous update means no update is produced for this block.
+						Some((last_price, updated_at))
+							if updated_at.elapsed() < min_interval ||
+								pool_price_change_bps(
+									last_price.price.range_order,
+									price.price.range_order,
+								) < min_change_bps =>
+							(last_price.clone(), (last_price.clone(), *updated_at)),
+						_ => (price.clone(), (price, Instant::now())),
+					})
+				},
+				|_| false,
+			)
+			.await
+	}
+
 	async fn cf_subscribe_transaction_screening_events(
 		&self,
 		pending_sink: PendingSubscriptionSink,
@@ -1672,6 +2732,39 @@ where
 			.await;
 	}
 
+	async fn cf_subscribe_rotation_status(&self, pending_sink: PendingSubscriptionSink) {
+		self.rpc_backend
+			.new_subscription(
+				NotificationBehaviour::Finalized, /* notification_behaviour */
+				true,                             /* only_on_changes */
+				true,                             /* end_on_error */
+				pending_sink,
+				move |client, hash| {
+					let api = client.runtime_api();
+					ensure_api_version::<B, _>(&*api, hash, 5)?;
+					Ok(api.cf_rotation_status(hash).map_err(CfApiError::from)?)
+				},
+			)
+			.await;
+	}
+
+	async fn cf_subscribe_safe_mode_status(&self, pending_sink: PendingSubscriptionSink) {
+		self.rpc_backend
+			.new_subscription(
+				NotificationBehaviour::Finalized, /* notification_behaviour */
+				true,                             /* only_on_changes */
+				true,                             /* end_on_error */
+				pending_sink,
+				move |client, hash| {
+					Ok((*client.runtime_api())
+						.cf_safe_mode_statuses(hash)
+						.map(RpcSafeModeStatus::from)
+						.map_err(CfApiError::from)?)
+				},
+			)
+			.await;
+	}
+
 	async fn cf_subscribe_scheduled_swaps(
 		&self,
 		pending_sink: PendingSubscriptionSink,
@@ -1710,6 +2803,169 @@ where
 			.await;
 	}
 
+	async fn cf_subscribe_swap_status(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+		swap_request_id: SwapRequestId,
+	) {
+		// Check that the swap request is known:
+		let Ok(Some(_)) = self
+			.rpc_backend
+			.with_runtime_api_since(None, 5, |api, hash| api.cf_swap_status(hash, swap_request_id))
+		else {
+			pending_sink
+				.reject(call_error("unknown swap request", CfErrorCode::OtherError))
+				.await;
+			return;
+		};
+
+		self.rpc_backend
+			.new_subscription_with_state(
+				NotificationBehaviour::Finalized, /* notification_behaviour */
+				true,                             /* only_on_changes */
+				true,                             /* end_on_error */
+				pending_sink,
+				move |client, hash, broadcast: Option<&Option<SwapBroadcastStatus>>| {
+					let api = client.runtime_api();
+					ensure_api_version::<B, _>(&*api, hash, 5)?;
+					let mut update = SwapStatusUpdate {
+						swap_status: api
+							.cf_swap_status(hash, swap_request_id)
+							.map_err(CfApiError::from)?
+							.ok_or_else(|| {
+								call_error(
+									"swap request is no longer tracked",
+									CfErrorCode::OtherError,
+								)
+							})?,
+						broadcast: broadcast.cloned().flatten(),
+					};
+					if let SwapRequestStatus::AwaitingEgress { egress_id: Some(egress_id) } =
+						update.swap_status.status
+					{
+						for event in
+							api.cf_egress_broadcast_events(hash).map_err(CfApiError::from)?
+						{
+							update.apply_broadcast_event(egress_id, event);
+						}
+					}
+					let broadcast = update.broadcast.clone();
+					Ok((update, broadcast))
+				},
+				SwapStatusUpdate::is_final,
+			)
+			.await;
+	}
+
+	async fn cf_subscribe_broadcast_status(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+		chain: ForeignChain,
+		broadcast_id: BroadcastId,
+	) {
+		// Check that the broadcast is known:
+		let Ok(Some(_)) = self.rpc_backend.with_runtime_api_since(None, 5, |api, hash| {
+			api.cf_broadcast_state(hash, chain, broadcast_id)
+		}) else {
+			pending_sink
+				.reject(call_error("unknown broadcast", CfErrorCode::OtherError))
+				.await;
+			return;
+		};
+
+		self.rpc_backend
+			.new_subscription_with_state(
+				NotificationBehaviour::Finalized, /* notification_behaviour */
+				true,                             /* only_on_changes */
+				true,                             /* end_on_error */
+				pending_sink,
+				move |client, hash, _: Option<&()>| {
+					let api = client.runtime_api();
+					ensure_api_version::<B, _>(&*api, hash, 5)?;
+					let events = api
+						.cf_broadcast_events(hash)
+						.map_err(CfApiError::from)?
+						.into_iter()
+						.filter(|(event_chain, id, _)| (*event_chain, *id) == (chain, broadcast_id))
+						.map(|(_, _, event)| event)
+						.collect::<Vec<_>>();
+					let outcome = events.iter().rev().find_map(|event| match event {
+						BroadcastLifecycleEvent::Succeeded { transaction_ref } =>
+							Some(BroadcastStatus::Succeeded {
+								transaction_ref: Some(transaction_ref.clone()),
+							}),
+						BroadcastLifecycleEvent::Aborted => Some(BroadcastStatus::Aborted),
+						_ => None,
+					});
+					let status = match outcome {
+						Some(status) => status,
+						None => match api
+							.cf_broadcast_state(hash, chain, broadcast_id)
+							.map_err(CfApiError::from)?
+							.ok_or_else(|| {
+								call_error(
+									"broadcast is no longer tracked",
+									CfErrorCode::OtherError,
+								)
+							})? {
+							BroadcastState::Pending => BroadcastStatus::Pending,
+							BroadcastState::Aborted => BroadcastStatus::Aborted,
+							BroadcastState::Completed =>
+								BroadcastStatus::Succeeded { transaction_ref: None },
+						},
+					};
+					Ok((BroadcastStatusUpdate { status, events }, ()))
+				},
+				BroadcastStatusUpdate::is_final,
+			)
+			.await;
+	}
+
+	async fn cf_subscribe_deposits(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+		deposit_addresses: Option<Vec<AddressString>>,
+		broker: Option<state_chain_runtime::AccountId>,
+	) {
+		let deposit_addresses = deposit_addresses.unwrap_or_default();
+		if deposit_addresses.is_empty() && broker.is_none() {
+			pending_sink
+				.reject(call_error(
+					"either deposit addresses or a broker must be provided",
+					CfErrorCode::OtherError,
+				))
+				.await;
+			return;
+		}
+
+		self.rpc_backend
+			.new_subscription(
+				NotificationBehaviour::Finalized, /* notification_behaviour */
+				false,                            /* only_on_changes */
+				true,                             /* end_on_error */
+				pending_sink,
+				move |client, hash| {
+					let api = client.runtime_api();
+					ensure_api_version::<B, _>(&*api, hash, 5)?;
+					Ok(api
+						.cf_deposit_events(hash)
+						.map_err(CfApiError::from)?
+						.into_iter()
+						.filter(|event| {
+							broker.is_some() && event.channel_owner == broker ||
+								deposit_addresses.iter().any(|address| {
+									address
+										.clone()
+										.try_parse_to_encoded_address(event.deposit_address.chain())
+										.is_ok_and(|address| address == event.deposit_address)
+								})
+						})
+						.collect::<Vec<_>>())
+				},
+			)
+			.await;
+	}
+
 	fn cf_scheduled_swaps(
 		&self,
 		base_asset: Asset,
@@ -1757,6 +3013,70 @@ where
 		Ok(Asset::all().collect())
 	}
 
+	fn cf_supported_assets_v2(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcAssetCapabilities>> {
+		self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			let boost_pools = api.cf_boost_pools_depth(hash)?;
+			Asset::all()
+				.map(|asset| {
+					let chain = ForeignChain::from(asset);
+					let minimum_deposit_amount = api.cf_min_deposit_amount(hash, asset)?;
+					let status = api.cf_asset_ingress_egress_status(hash, asset)?;
+					let boost_tiers = boost_pools
+						.iter()
+						.filter(|pool| pool.asset == asset)
+						.map(|pool| pool.tier)
+						.collect::<Vec<_>>();
+					Ok::<_, CfApiError>(RpcAssetCapabilities {
+						asset,
+						decimals: asset.decimals(),
+						minimum_deposit_amount: minimum_deposit_amount.into(),
+						// Vault swaps are subject to the same minimum as deposits to channels.
+						minimum_vault_swap_amount: matches!(
+							chain,
+							ForeignChain::Ethereum |
+								ForeignChain::Arbitrum | ForeignChain::Bitcoin |
+								ForeignChain::Solana
+						)
+						.then_some(minimum_deposit_amount.into()),
+						boost_enabled: status.boost_deposits_enabled && !boost_tiers.is_empty(),
+						boost_tiers,
+						ccm_supported: chain.ccm_support(),
+						deposits_enabled: status.deposits_enabled,
+						egress_enabled: status.egress_enabled,
+					})
+				})
+				.collect()
+		})
+	}
+
+	fn cf_boost_quote(
+		&self,
+		asset: Asset,
+		amount: NumberOrHex,
+		max_boost_fee: Option<BasisPoints>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcBoostQuote> {
+		let amount =
+			u128::try_from(amount)
+				.map_err(|_| "Deposit amount too large.")
+				.and_then(|amount| {
+					if amount == 0 {
+						Err("Deposit amount cannot be zero.")
+					} else {
+						Ok(amount)
+					}
+				})
+				.map_err(|s| ErrorObject::owned(ErrorCode::InvalidParams.code(), s, None::<()>))?;
+
+		self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_boost_quote(hash, asset, amount, max_boost_fee.unwrap_or(BasisPoints::MAX))
+				.map(Into::into)
+		})
+	}
+
 	fn cf_boost_pool_details(
 		&self,
 		asset: Option<Asset>,
@@ -1791,6 +3111,68 @@ where
 		})
 	}
 
+	fn cf_boost_pool_details_page(
+		&self,
+		asset: Asset,
+		fee_tier: BoostPoolTier,
+		booster: Option<state_chain_runtime::AccountId>,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BoostPoolPageRpc<BoostPoolDetailsRpc>> {
+		let page = self
+			.rpc_backend
+			.with_runtime_api_since(at, 5, |api, hash| {
+				api.cf_boost_pool_details_page(
+					hash,
+					asset,
+					fee_tier,
+					booster,
+					offset.unwrap_or_default(),
+					limit.unwrap_or(MAX_BOOST_POOL_DETAILS_PAGE_SIZE),
+				)
+			})?
+			.ok_or_else(|| {
+				call_error("requested boost pool does not exist", CfErrorCode::OtherError)
+			})?;
+
+		Ok(BoostPoolPageRpc {
+			pool: BoostPoolDetailsRpc::new(asset, fee_tier, page.details),
+			next_offset: page.next_offset,
+		})
+	}
+
+	fn cf_boost_pool_pending_fees_page(
+		&self,
+		asset: Asset,
+		fee_tier: BoostPoolTier,
+		booster: Option<state_chain_runtime::AccountId>,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BoostPoolPageRpc<BoostPoolFeesRpc>> {
+		let page = self
+			.rpc_backend
+			.with_runtime_api_since(at, 5, |api, hash| {
+				api.cf_boost_pool_details_page(
+					hash,
+					asset,
+					fee_tier,
+					booster,
+					offset.unwrap_or_default(),
+					limit.unwrap_or(MAX_BOOST_POOL_DETAILS_PAGE_SIZE),
+				)
+			})?
+			.ok_or_else(|| {
+				call_error("requested boost pool does not exist", CfErrorCode::OtherError)
+			})?;
+
+		Ok(BoostPoolPageRpc {
+			pool: BoostPoolFeesRpc::new(asset, fee_tier, page.details),
+			next_offset: page.next_offset,
+		})
+	}
+
 	fn cf_available_pools(&self, at: Option<Hash>) -> RpcResult<Vec<PoolPairsMap<Asset>>> {
 		self.rpc_backend.with_runtime_api(at, |api, hash| api.cf_pools(hash))
 	}
@@ -1829,22 +3211,72 @@ where
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<VaultSwapDetails<AddressString>> {
 		self.rpc_backend.with_runtime_api(at, |api, hash| {
-			Ok::<_, CfApiError>(
+			let destination_address =
+				destination_address.try_parse_to_encoded_address(destination_asset.into())?;
+			let extra_parameters = extra_parameters.try_into_encoded_params(source_asset.into())?;
+			let boost_fee = boost_fee.unwrap_or_default();
+			let affiliate_fees = affiliate_fees.unwrap_or_default();
+
+			let api_version = api
+				.api_version::<dyn CustomRuntimeApi<state_chain_runtime::Block>>(hash)?
+				.unwrap_or_default();
+
+			let details = if api_version < 5 {
+				api.cf_request_swap_parameter_encoding_before_version_5(
+					hash,
+					broker,
+					source_asset,
+					destination_asset,
+					destination_address,
+					broker_commission,
+					extra_parameters,
+					channel_metadata,
+					boost_fee,
+					affiliate_fees,
+					dca_parameters,
+				)??
+				.into()
+			} else {
 				api.cf_request_swap_parameter_encoding(
 					hash,
 					broker,
 					source_asset,
 					destination_asset,
-					destination_address.try_parse_to_encoded_address(destination_asset.into())?,
+					destination_address,
 					broker_commission,
-					extra_parameters.try_into_encoded_params(source_asset.into())?,
+					extra_parameters,
 					channel_metadata,
-					boost_fee.unwrap_or_default(),
-					affiliate_fees.unwrap_or_default(),
+					boost_fee,
+					affiliate_fees,
 					dca_parameters,
 				)??
-				.map_btc_address(Into::into),
-			)
+			};
+
+			Ok::<_, CfApiError>(details.map_btc_address(Into::into))
+		})
+	}
+
+	fn cf_validate_ccm_channel_metadata(
+		&self,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		channel_metadata: CcmChannelMetadata,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<()> {
+		self.rpc_backend.with_runtime_api_since(at, 5, |api, hash| {
+			api.cf_validate_ccm_channel_metadata(
+				hash,
+				destination_asset,
+				destination_address.try_parse_to_encoded_address(destination_asset.into())?,
+				channel_metadata,
+			)?
+			.map_err(|error| {
+				CfApiError::ErrorObject(ErrorObject::owned(
+					ErrorCode::InvalidParams.code(),
+					format!("Invalid CCM: {error:?}"),
+					Some(error),
+				))
+			})
 		})
 	}
 
@@ -2085,6 +3517,7 @@ mod test {
 					hub: hub::AssetMap { dot: None, usdc: None, usdt: None },
 				},
 				network_fee_hundredth_pips: Permill::from_percent(100),
+				swap_delay_blocks: 2,
 				swap_retry_delay_blocks: 5,
 				max_swap_retry_duration_blocks: 600,
 				max_swap_request_duration_blocks: 14400,
@@ -2289,6 +3722,29 @@ mod test {
 		insta::assert_json_snapshot!(val);
 	}
 
+	#[test]
+	fn boost_pool_details_are_paginated_by_booster() {
+		let page = boost_details_2().into_page(None, 0, 1);
+		assert_eq!(page.next_offset, Some(1));
+		assert_eq!(page.details.pending_withdrawals, BTreeMap::from([(ID_1, BTreeSet::from([0]))]));
+		assert_eq!(
+			page.details.pending_boosts.get(&0).unwrap().keys().collect::<Vec<_>>(),
+			vec![&ID_1]
+		);
+
+		let page = boost_details_2().into_page(None, 1, 1);
+		assert_eq!(page.next_offset, None);
+		assert_eq!(page.details.pending_withdrawals, BTreeMap::from([(ID_2, BTreeSet::from([0]))]));
+
+		let page = boost_details_1().into_page(Some(ID_2), 0, 10);
+		assert_eq!(page.next_offset, None);
+		assert!(page.details.available_amounts.is_empty());
+		assert_eq!(
+			page.details.pending_boosts,
+			BTreeMap::from([(0, BTreeMap::from([(ID_2, OwedAmount { total: 2_000, fee: 100 })]))])
+		);
+	}
+
 	#[test]
 	fn test_swap_output_serialization() {
 		insta::assert_snapshot!(serde_json::to_value(RpcSwapOutputV2 {
@@ -2302,6 +3758,23 @@ mod test {
 		.unwrap());
 	}
 
+	#[test]
+	fn test_swap_output_with_fees_serialization() {
+		insta::assert_snapshot!(serde_json::to_value(RpcSwapOutputWithFees {
+			swap_output: RpcSwapOutputV2 {
+				output: 1_000_000_000_000_000_000u128.into(),
+				intermediary: Some(1_000_000u128.into()),
+				network_fee: RpcFee { asset: Asset::Usdc, amount: 1_000u128.into() },
+				ingress_fee: RpcFee { asset: Asset::Flip, amount: 500u128.into() },
+				egress_fee: RpcFee { asset: Asset::Eth, amount: 1_000_000u128.into() },
+				broker_commission: RpcFee { asset: Asset::Usdc, amount: 100u128.into() },
+			},
+			affiliate_fees: vec![(ID_1, RpcFee { asset: Asset::Usdc, amount: 50u128.into() })],
+			boost_fee: RpcFee { asset: Asset::Flip, amount: 200u128.into() },
+		})
+		.unwrap());
+	}
+
 	#[test]
 	fn test_vault_addresses_custom_rpc() {
 		let val: VaultAddresses = VaultAddresses {
@@ -2324,4 +3797,84 @@ mod test {
 		};
 		insta::assert_json_snapshot!(val);
 	}
+
+	#[test]
+	fn swap_status_update_follows_egress_broadcast() {
+		const EGRESS_ID: EgressId = (ForeignChain::Ethereum, 7);
+
+		let mut update = SwapStatusUpdate {
+			swap_status: SwapStatus {
+				status: SwapRequestStatus::AwaitingEgress { egress_id: Some(EGRESS_ID) },
+				input_asset: Asset::Btc,
+				output_asset: Asset::Eth,
+				input_amount: 1_000,
+				swapped_input_amount: 1_000,
+				intermediate_amount: 0,
+				output_amount: 2_000,
+				network_fee: 1,
+				broker_fee: 2,
+			},
+			broadcast: None,
+		};
+		assert!(!update.is_final());
+
+		// Broadcasts of other egresses, and outcomes of other broadcasts, are ignored.
+		update.apply_broadcast_event(
+			EGRESS_ID,
+			EgressBroadcastEvent::BroadcastRequested {
+				broadcast_id: 1,
+				egress_ids: vec![(ForeignChain::Ethereum, 6)],
+			},
+		);
+		assert_eq!(update.broadcast, None);
+		update.apply_broadcast_event(
+			EGRESS_ID,
+			EgressBroadcastEvent::BroadcastRequested {
+				broadcast_id: 2,
+				egress_ids: vec![(ForeignChain::Ethereum, 6), EGRESS_ID],
+			},
+		);
+		update.apply_broadcast_event(
+			EGRESS_ID,
+			EgressBroadcastEvent::BroadcastSucceeded {
+				chain: ForeignChain::Arbitrum,
+				broadcast_id: 2,
+			},
+		);
+		assert_eq!(
+			update.broadcast,
+			Some(SwapBroadcastStatus::Pending { chain: ForeignChain::Ethereum, broadcast_id: 2 })
+		);
+		assert!(!update.is_final());
+
+		update.apply_broadcast_event(
+			EGRESS_ID,
+			EgressBroadcastEvent::BroadcastSucceeded {
+				chain: ForeignChain::Ethereum,
+				broadcast_id: 2,
+			},
+		);
+		assert_eq!(
+			update.broadcast,
+			Some(SwapBroadcastStatus::Succeeded { chain: ForeignChain::Ethereum, broadcast_id: 2 })
+		);
+		assert!(update.is_final());
+	}
+
+	#[test]
+	fn pool_price_change_is_measured_in_bps() {
+		use cf_amm::math::{bounded_sqrt_price, Amount};
+
+		let sqrt_price =
+			|quote: u128| bounded_sqrt_price(Amount::from(quote), Amount::from(1_000_000u128));
+
+		assert_eq!(pool_price_change_bps(sqrt_price(1_000_000), sqrt_price(1_000_000)), 0);
+		// Rounding of the sqrt price loses some precision.
+		assert!((99..=100)
+			.contains(&pool_price_change_bps(sqrt_price(1_000_000), sqrt_price(1_010_000))));
+		assert!(
+			(99..=100).contains(&pool_price_change_bps(sqrt_price(1_000_000), sqrt_price(990_000)))
+		);
+		assert_eq!(pool_price_change_bps(U256::zero(), sqrt_price(1_000_000)), BasisPoints::MAX);
+	}
 }
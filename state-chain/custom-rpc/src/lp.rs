@@ -414,6 +414,7 @@ where
 		sell_amount: NumberOrHex,
 		dispatch_at: Option<BlockNumber>,
 		wait_for: Option<WaitFor>,
+		expiry_block: Option<BlockNumber>,
 	) -> RpcResult<ApiWaitForResult<Vec<LimitOrder>>> {
 		self.scheduled_or_immediate(
 			pallet_cf_pools::Call::set_limit_order {
@@ -423,6 +424,7 @@ where
 				id: id.try_into()?,
 				option_tick: tick,
 				sell_amount: try_parse_number_or_hex(sell_amount)?,
+				expiry_block,
 			},
 			dispatch_at,
 			wait_for.unwrap_or_default(),
@@ -727,13 +729,21 @@ where
 		Ok(channels
 			.into_iter()
 			.filter_map(|(_, DepositChannelDetails { action, deposit_channel, .. })| match action {
-				pallet_cf_ingress_egress::ChannelAction::Swap { destination_asset, .. } =>
+				pallet_cf_ingress_egress::ChannelAction::Swap { destination_asset, .. } |
+				pallet_cf_ingress_egress::ChannelAction::SplitSwap {
+					destination_asset, ..
+				} => Some(SwapChannelInfo {
+					deposit_address: deposit_channel.address.to_humanreadable(network_environment),
+					source_asset: deposit_channel.asset.into(),
+					destination_asset,
+				}),
+				pallet_cf_ingress_egress::ChannelAction::FundAccount { .. } =>
 					Some(SwapChannelInfo {
 						deposit_address: deposit_channel
 							.address
 							.to_humanreadable(network_environment),
 						source_asset: deposit_channel.asset.into(),
-						destination_asset,
+						destination_asset: Asset::Flip,
 					}),
 				_ => None,
 			})
@@ -15,8 +15,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::pass_through;
-use crate::{BlockT, CustomRpc, RpcAccountInfoV2, RpcResult};
-use cf_chains::{dot::PolkadotAccountId, sol::SolAddress};
+use crate::{
+	backend::CustomRpcBackend, BlockT, CfApiError, CustomRpc, RpcAccountInfoV2, RpcResult,
+};
+use cf_chains::{dot::PolkadotAccountId, eth::Address as EthereumAddress, sol::SolAddress};
+use cf_primitives::{CfeCompatibility, SemVer};
 use cf_utilities::rpc::NumberOrHex;
 use jsonrpsee::proc_macros::rpc;
 use sc_client_api::{BlockchainEvents, HeaderBackend};
@@ -25,12 +28,15 @@ use sp_core::{bounded_vec::BoundedVec, ConstU32};
 use state_chain_runtime::{
 	chainflip::Offence,
 	monitoring_apis::{
-		ActivateKeysBroadcastIds, AuthoritiesInfo, BtcUtxos, EpochState, ExternalChainsBlockHeight,
-		FeeImbalance, FlipSupply, LastRuntimeUpgradeInfo, MonitoringDataV2, MonitoringRuntimeApi,
-		OpenDepositChannels, PendingBroadcasts, PendingTssCeremonies, RedemptionsInfo,
-		SolanaNonces,
+		ActivateKeysBroadcastIds, AuthoritiesInfo, BitcoinUtxoSummary, BtcUtxos, EpochState,
+		ExternalChainsBlockHeight, ExternalChainsWitnessingLag, FeeImbalance, FlipSupply,
+		LastRuntimeUpgradeInfo, MonitoringDataV2, MonitoringRuntimeApi, OpenDepositChannels,
+		PendingBroadcasts, PendingRedemption, PendingTssCeremonies, RedemptionsInfo,
+		SolanaNonceAccountsStatus, SolanaNonces,
 	},
+	runtime_apis::CustomRuntimeApi,
 };
+use std::sync::Arc;
 
 impl From<EpochState> for RpcEpochState {
 	fn from(rotation_state: EpochState) -> Self {
@@ -55,6 +61,26 @@ impl From<RedemptionsInfo> for RpcRedemptionsInfo {
 	}
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RpcPendingRedemption {
+	pub account_id: state_chain_runtime::AccountId,
+	pub amount: NumberOrHex,
+	pub restricted_amount: NumberOrHex,
+	pub redeem_address: EthereumAddress,
+	pub expiry_time: u64,
+}
+impl From<PendingRedemption> for RpcPendingRedemption {
+	fn from(redemption: PendingRedemption) -> Self {
+		Self {
+			account_id: redemption.account_id,
+			amount: redemption.amount.into(),
+			restricted_amount: redemption.restricted_amount.into(),
+			redeem_address: redemption.redeem_address,
+			expiry_time: redemption.expiry_time,
+		}
+	}
+}
+
 pub type RpcFeeImbalance = FeeImbalance<NumberOrHex>;
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -180,6 +206,20 @@ pub trait MonitoringApi {
 	) -> RpcResult<ActivateKeysBroadcastIds>;
 	#[method(name = "sol_nonces")]
 	fn cf_sol_nonces(&self, at: Option<state_chain_runtime::Hash>) -> RpcResult<SolanaNonces>;
+	/// Lists the Solana durable nonce accounts with their current nonce, whether they are
+	/// available, and how many blocks ago they were last used.
+	#[method(name = "sol_nonce_accounts_status")]
+	fn cf_sol_nonce_accounts_status(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<SolanaNonceAccountsStatus>;
+	/// Summarises the UTXOs available to the Bitcoin vault, including how many of them are dust
+	/// and how close the vault is to the next consolidation.
+	#[method(name = "btc_utxo_summary")]
+	fn cf_btc_utxo_summary(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BitcoinUtxoSummary>;
 	#[method(name = "sol_aggkey")]
 	fn cf_sol_aggkey(&self, at: Option<state_chain_runtime::Hash>) -> RpcResult<SolAddress>;
 	#[method(name = "sol_onchain_key")]
@@ -195,6 +235,20 @@ pub trait MonitoringApi {
 		accounts: BoundedVec<state_chain_runtime::AccountId, ConstU32<10>>,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<RpcAccountInfoV2>>;
+	#[method(name = "external_chains_witnessing_lag")]
+	fn cf_external_chains_witnessing_lag(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<ExternalChainsWitnessingLag>;
+	/// Lists all pending redemptions, optionally only those expiring in the given window of unix
+	/// times (in seconds, end exclusive).
+	#[method(name = "pending_redemptions")]
+	fn cf_pending_redemptions(
+		&self,
+		expires_after: Option<u64>,
+		expires_before: Option<u64>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcPendingRedemption>>;
 }
 
 impl<C, B, BE> MonitoringApiServer for CustomRpc<C, B, BE>
@@ -224,9 +278,12 @@ where
 		cf_build_version() -> LastRuntimeUpgradeInfo,
 		cf_rotation_broadcast_ids() -> ActivateKeysBroadcastIds,
 		cf_sol_nonces() -> SolanaNonces,
+		cf_sol_nonce_accounts_status() -> SolanaNonceAccountsStatus,
+		cf_btc_utxo_summary() -> BitcoinUtxoSummary,
 		cf_sol_aggkey() -> SolAddress,
 		cf_sol_onchain_key() -> SolAddress,
 		cf_monitoring_data() -> RpcMonitoringData [map: Into::into],
+		cf_external_chains_witnessing_lag() -> ExternalChainsWitnessingLag,
 	}
 
 	fn cf_fee_imbalance(
@@ -238,6 +295,19 @@ where
 			.map(|imbalance| imbalance.map(|i| (*i).into()))
 	}
 
+	fn cf_pending_redemptions(
+		&self,
+		expires_after: Option<u64>,
+		expires_before: Option<u64>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<RpcPendingRedemption>> {
+		self.rpc_backend
+			.with_runtime_api(at, |api, hash| {
+				api.cf_pending_redemptions(hash, expires_after, expires_before)
+			})
+			.map(|redemptions| redemptions.into_iter().map(Into::into).collect())
+	}
+
 	fn cf_accounts_info(
 		&self,
 		accounts: BoundedVec<state_chain_runtime::AccountId, ConstU32<10>>,
@@ -266,3 +336,139 @@ where
 			.collect())
 	}
 }
+
+/// The default number of blocks in which a validator node is expected to have authored a block.
+pub const DEFAULT_AUTHORSHIP_WINDOW_BLOCKS: u32 = 600;
+
+/// Provides the parts of a node's health that are not part of the chain state.
+pub trait NodeStatusProvider: Send + Sync + 'static {
+	/// Whether the node is importing blocks far behind the best block of its peers.
+	fn is_major_syncing(&self) -> bool;
+	fn peer_count(&self) -> usize;
+	/// The version of the node, which should match the version of the engine it runs with.
+	fn node_version(&self) -> SemVer;
+	/// The public Aura key in the node's keystore, if any.
+	fn aura_key(&self) -> Option<[u8; 32]>;
+	fn has_grandpa_key(&self) -> bool;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcValidatorHealth {
+	pub account_id: state_chain_runtime::AccountId,
+	/// Whether the validator authored a block within the authorship window.
+	pub authored_recently: bool,
+	/// The engine version last reported by the validator.
+	pub engine_version: SemVer,
+	pub engine_compatible: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcNodeHealth {
+	pub is_syncing: bool,
+	pub peers: usize,
+	pub best_block_number: u32,
+	pub finalized_block_number: u32,
+	pub node_version: SemVer,
+	pub required_engine_version: SemVer,
+	pub has_aura_key: bool,
+	pub has_grandpa_key: bool,
+	/// Set if the node's Aura key belongs to a validator.
+	pub validator: Option<RpcValidatorHealth>,
+}
+
+#[rpc(server, client, namespace = "cf")]
+pub trait NodeHealthApi {
+	/// Returns the sync state, peer count, version compatibility and keystore keys of the node,
+	/// and whether the validator owning its keys authored a block in the last
+	/// `authorship_window_blocks` blocks.
+	#[method(name = "node_health")]
+	fn cf_node_health(&self, authorship_window_blocks: Option<u32>) -> RpcResult<RpcNodeHealth>;
+}
+
+pub struct NodeHealthRpc<C, B, BE, N> {
+	pub rpc_backend: CustomRpcBackend<C, B, BE>,
+	pub node_status: Arc<N>,
+}
+
+impl<C, B, BE, N> NodeHealthRpc<C, B, BE, N>
+where
+	B: BlockT<Hash = state_chain_runtime::Hash>,
+	C: Send + Sync + 'static + HeaderBackend<B>,
+{
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		executor: Arc<dyn sp_core::traits::SpawnNamed>,
+		node_status: Arc<N>,
+	) -> Self {
+		Self { rpc_backend: CustomRpcBackend::new(client, backend, executor), node_status }
+	}
+}
+
+impl<C, B, BE, N> NodeHealthApiServer for NodeHealthRpc<C, B, BE, N>
+where
+	B: BlockT<Hash = state_chain_runtime::Hash, Header = state_chain_runtime::Header>,
+	BE: Send + Sync + 'static,
+	C: sp_api::ProvideRuntimeApi<B> + Send + Sync + 'static + HeaderBackend<B>,
+	C::Api: MonitoringRuntimeApi<B> + CustomRuntimeApi<B>,
+	N: NodeStatusProvider,
+{
+	fn cf_node_health(&self, authorship_window_blocks: Option<u32>) -> RpcResult<RpcNodeHealth> {
+		let info = self.rpc_backend.client.info();
+		let required_engine_version =
+			self.rpc_backend.with_runtime_api(Some(info.best_hash), |api, hash| {
+				api.cf_current_compatibility_version(hash)
+			})?;
+		let aura_key = self.node_status.aura_key();
+		let aura_key_owner = |aura_key, hash| {
+			self.rpc_backend
+				.with_runtime_api(Some(hash), |api, hash| api.cf_aura_key_owner(hash, aura_key))
+		};
+
+		let validator = match aura_key {
+			Some(aura_key) => match aura_key_owner(aura_key, info.best_hash)? {
+				Some(owner) => {
+					let window_start_hash = self
+						.rpc_backend
+						.client
+						.hash(info.best_number.saturating_sub(
+							authorship_window_blocks.unwrap_or(DEFAULT_AUTHORSHIP_WINDOW_BLOCKS),
+						))
+						.map_err(CfApiError::from)?;
+					let authored_before = match window_start_hash {
+						Some(hash) => aura_key_owner(aura_key, hash)?
+							.map(|owner| owner.blocks_authored)
+							.unwrap_or_default(),
+						None => 0,
+					};
+					Some(RpcValidatorHealth {
+						// Authorship statistics are reset at the start of every epoch, so any
+						// change means that blocks were authored since the start of the window.
+						authored_recently: owner.blocks_authored > 0 &&
+							owner.blocks_authored != authored_before,
+						engine_compatible: owner
+							.cfe_version
+							.compatibility_with_runtime(required_engine_version) ==
+							CfeCompatibility::Compatible,
+						engine_version: owner.cfe_version,
+						account_id: owner.account_id,
+					})
+				},
+				None => None,
+			},
+			None => None,
+		};
+
+		Ok(RpcNodeHealth {
+			is_syncing: self.node_status.is_major_syncing(),
+			peers: self.node_status.peer_count(),
+			best_block_number: info.best_number,
+			finalized_block_number: info.finalized_number,
+			node_version: self.node_status.node_version(),
+			required_engine_version,
+			has_aura_key: aura_key.is_some(),
+			has_grandpa_key: self.node_status.has_grandpa_key(),
+			validator,
+		})
+	}
+}
@@ -0,0 +1,111 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use cf_utilities::scale_json::{
+	ext::{DecodeAsType, JsonValue},
+	ScaleDecodedToJson,
+};
+use codec::Decode;
+use sp_api::Metadata;
+use sp_core::{storage::StorageKey, Bytes};
+
+/// The maximum number of storage items that can be queried in a single batch.
+pub const MAX_STORAGE_BATCH_SIZE: usize = 256;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcStorageQuery {
+	/// The name of the pallet as it appears in the runtime metadata, eg. `Flip`.
+	pub pallet: String,
+	/// The name of the storage item, eg. `TotalIssuance`.
+	pub item: String,
+	/// The hashed map key, appended as-is to the storage prefix. Omitted for storage values.
+	pub key: Option<Bytes>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcStorageQueryResult {
+	/// The full storage key that was queried.
+	pub key: Bytes,
+	/// The raw SCALE-encoded value, or `None` if nothing is stored under the key.
+	pub value: Option<Bytes>,
+	/// The decoded value, if the storage item is known to the runtime metadata.
+	pub decoded: Option<JsonValue>,
+}
+
+pub(crate) fn storage_batch<C, B, BE>(
+	client: &C,
+	hash: Hash,
+	queries: Vec<RpcStorageQuery>,
+) -> RpcResult<Vec<RpcStorageQueryResult>>
+where
+	B: BlockT<Hash = Hash>,
+	BE: Send + Sync + 'static + Backend<B>,
+	C: sp_api::ProvideRuntimeApi<B> + Send + Sync + 'static + StorageProvider<B, BE>,
+	C::Api: sp_api::Metadata<B>,
+{
+	if queries.len() > MAX_STORAGE_BATCH_SIZE {
+		return Err(call_error(
+			format!("Too many storage queries: the maximum batch size is {MAX_STORAGE_BATCH_SIZE}"),
+			CfErrorCode::OtherError,
+		)
+		.into());
+	}
+
+	let metadata = client
+		.runtime_api()
+		.metadata_at_version(hash, 15)
+		.map_err(CfApiError::from)?
+		.ok_or_else(|| internal_error("Version 15 metadata is not supported by the runtime"))
+		.and_then(|opaque_metadata| {
+			subxt::Metadata::decode(&mut opaque_metadata.as_ref())
+				.map_err(|e| internal_error(format!("Failed to decode runtime metadata: {e}")))
+		})?;
+
+	queries
+		.into_iter()
+		.map(|RpcStorageQuery { pallet, item, key }| {
+			let storage_key = StorageKey(
+				frame_support::storage::storage_prefix(pallet.as_bytes(), item.as_bytes())
+					.into_iter()
+					.chain(key.map(|key| key.0).unwrap_or_default())
+					.collect(),
+			);
+			let value =
+				client.storage(hash, &storage_key).map_err(CfApiError::from)?.map(|data| data.0);
+			let decoded = value.as_ref().and_then(|value| {
+				let value_ty = metadata
+					.pallet_by_name(&pallet)?
+					.storage()?
+					.entry_by_name(&item)?
+					.entry_type()
+					.value_ty();
+				ScaleDecodedToJson::decode_as_type(&mut &value[..], value_ty, metadata.types())
+					.map(Into::into)
+					.inspect_err(|e| {
+						log::warn!("Failed to decode storage value of {pallet}::{item}: {e}")
+					})
+					.ok()
+			});
+			Ok(RpcStorageQueryResult {
+				key: storage_key.0.into(),
+				value: value.map(Into::into),
+				decoded,
+			})
+		})
+		.collect()
+}
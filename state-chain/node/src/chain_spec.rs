@@ -789,6 +789,7 @@ fn testnet_genesis(
 			auction_parameters,
 			auction_bid_cutoff_percentage,
 			max_authority_set_contraction_percentage,
+			..Default::default()
 		},
 		session: state_chain_runtime::SessionConfig {
 			keys: initial_authorities
@@ -805,6 +806,7 @@ fn testnet_genesis(
 			minimum_funding,
 			redemption_tax,
 			redemption_ttl: core::time::Duration::from_secs(redemption_ttl_secs),
+			..Default::default()
 		},
 		// These are set indirectly via the session pallet.
 		aura: state_chain_runtime::AuraConfig { authorities: vec![] },
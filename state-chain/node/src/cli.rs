@@ -23,6 +23,11 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Index the events touching each account in recently finalized blocks, and serve them via
+	/// the `cf_account_events` RPC.
+	#[arg(long)]
+	pub enable_account_events_index: bool,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -193,6 +193,7 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let enable_account_events_index = cli.enable_account_events_index;
 			runner.run_node_until_exit(|config| async move {
 				match config.network.network_backend {
 					sc_network::config::NetworkBackendType::Libp2p => service::new_full::<
@@ -200,11 +201,17 @@ pub fn run() -> sc_cli::Result<()> {
 							state_chain_runtime::opaque::Block,
 							<state_chain_runtime::opaque::Block as sp_runtime::traits::Block>::Hash,
 						>,
-					>(config)
+					>(
+						config,
+						enable_account_events_index,
+					)
 					.map_err(sc_cli::Error::Service),
 					sc_network::config::NetworkBackendType::Litep2p =>
-						service::new_full::<sc_network::Litep2pNetworkBackend>(config)
-							.map_err(sc_cli::Error::Service),
+						service::new_full::<sc_network::Litep2pNetworkBackend>(
+							config,
+							enable_account_events_index,
+						)
+						.map_err(sc_cli::Error::Service),
 				}
 			})
 		},
@@ -21,6 +21,7 @@ mod benchmarking;
 mod chain_spec;
 mod cli;
 mod command;
+mod metrics;
 mod service;
 
 fn main() -> sc_cli::Result<()> {
@@ -0,0 +1,145 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protocol-level Prometheus metrics of the State Chain.
+//!
+//! The gauges are registered with the node's Prometheus registry and updated from the runtime
+//! APIs on every finalized block.
+use crate::service::FullClient;
+use cf_primitives::{Asset, ForeignChain};
+use futures::StreamExt;
+use prometheus::{GaugeVec, IntGaugeVec, Opts, PrometheusError, Registry};
+use sc_client_api::BlockchainEvents;
+use sp_api::{ApiError, ProvideRuntimeApi};
+use state_chain_runtime::{runtime_apis::CustomRuntimeApi, Hash};
+use std::{collections::BTreeMap, sync::Arc};
+
+pub struct ProtocolMetrics {
+	scheduled_swaps: IntGaugeVec,
+	scheduled_egresses: IntGaugeVec,
+	boost_pool_utilisation: GaugeVec,
+	open_deposit_channels: IntGaugeVec,
+}
+
+impl ProtocolMetrics {
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		fn register<M: prometheus::core::Collector + Clone + 'static>(
+			registry: &Registry,
+			metric: M,
+		) -> Result<M, PrometheusError> {
+			registry.register(Box::new(metric.clone()))?;
+			Ok(metric)
+		}
+
+		Ok(Self {
+			scheduled_swaps: register(
+				registry,
+				IntGaugeVec::new(
+					Opts::new(
+						"chainflip_scheduled_swaps",
+						"Number of swaps scheduled to be executed in each pool",
+					),
+					&["base_asset"],
+				)?,
+			)?,
+			scheduled_egresses: register(
+				registry,
+				IntGaugeVec::new(
+					Opts::new(
+						"chainflip_scheduled_egresses",
+						"Number of egresses scheduled to be batched and broadcast",
+					),
+					&["chain"],
+				)?,
+			)?,
+			boost_pool_utilisation: register(
+				registry,
+				GaugeVec::new(
+					Opts::new(
+						"chainflip_boost_pool_utilisation",
+						"Share of the boost pool's liquidity that is lent out to pending boosts",
+					),
+					&["asset", "tier"],
+				)?,
+			)?,
+			open_deposit_channels: register(
+				registry,
+				IntGaugeVec::new(
+					Opts::new("chainflip_open_deposit_channels", "Number of open deposit channels"),
+					&["chain"],
+				)?,
+			)?,
+		})
+	}
+
+	fn update(&self, client: &FullClient, hash: Hash) -> Result<(), ApiError> {
+		let api = client.runtime_api();
+
+		for pool in api.cf_pools(hash)? {
+			let scheduled_swaps = api.cf_scheduled_swaps(hash, pool.base, pool.quote)?;
+			self.scheduled_swaps
+				.with_label_values(&[&pool.base.to_string()])
+				.set(scheduled_swaps.len() as i64);
+		}
+
+		for chain in ForeignChain::iter() {
+			let scheduled_egresses = api.cf_scheduled_egresses(hash, chain)?;
+			self.scheduled_egresses
+				.with_label_values(&[&chain.to_string()])
+				.set(scheduled_egresses.len() as i64);
+		}
+
+		for asset in Asset::all() {
+			for (tier, pool) in api.cf_boost_pool_details(hash, asset)? {
+				let available = pool.available_amounts.values().sum::<u128>() as f64;
+				let boosted = pool
+					.pending_boosts
+					.values()
+					.flat_map(|owed_amounts| owed_amounts.values())
+					.map(|owed_amount| owed_amount.total)
+					.sum::<u128>() as f64;
+				let utilisation =
+					if available + boosted > 0.0 { boosted / (available + boosted) } else { 0.0 };
+				self.boost_pool_utilisation
+					.with_label_values(&[&asset.to_string(), &tier.to_string()])
+					.set(utilisation);
+			}
+		}
+
+		let mut open_channels =
+			ForeignChain::iter().map(|chain| (chain, 0i64)).collect::<BTreeMap<_, _>>();
+		for (_, _, channels) in api.cf_all_open_deposit_channels(hash)? {
+			for address in channels.chain_accounts {
+				*open_channels.entry(address.chain()).or_default() += 1;
+			}
+		}
+		for (chain, count) in open_channels {
+			self.open_deposit_channels.with_label_values(&[&chain.to_string()]).set(count);
+		}
+
+		Ok(())
+	}
+}
+
+/// Updates the metrics on every finalized block until the client shuts down.
+pub async fn update_on_finalized_blocks(client: Arc<FullClient>, metrics: ProtocolMetrics) {
+	let mut finality_notifications = client.finality_notification_stream();
+	while let Some(notification) = finality_notifications.next().await {
+		if let Err(error) = metrics.update(&client, notification.hash) {
+			log::warn!("Failed to update protocol metrics at block {}: {error}", notification.hash);
+		}
+	}
+}
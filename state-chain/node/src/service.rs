@@ -15,11 +15,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
+use crate::metrics;
+use cf_primitives::SemVer;
 use cf_rpc_apis::{broker::BrokerRpcApiServer, lp::LpRpcApiServer};
 use custom_rpc::{
+	account_events::{AccountEventsApiServer, AccountEventsIndex, AccountEventsRpc},
 	broker::{broker_crypto, BrokerSignedRpc},
 	lp::{lp_crypto, LpSignedRpc},
-	monitoring::MonitoringApiServer,
+	monitoring::{MonitoringApiServer, NodeHealthApiServer, NodeHealthRpc, NodeStatusProvider},
 	CustomApiServer, CustomRpc,
 };
 use futures::FutureExt;
@@ -28,11 +31,14 @@ use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
 use sc_keystore::Keystore;
+use sc_network_sync::SyncingService;
 use sc_rpc_spec_v2::{chain_spec as chain_spec_rpc, chain_spec::ChainSpecApiServer};
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_consensus::SyncOracle;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_core::crypto::key_types;
 use state_chain_runtime::{self, opaque::Block, RuntimeApi};
 use std::{sync::Arc, time::Duration};
 
@@ -62,6 +68,44 @@ pub type Service = sc_service::PartialComponents<
 	),
 >;
 
+/// Reports the parts of the node's health that are not part of the chain state.
+struct NodeStatus {
+	sync_service: Arc<SyncingService<Block>>,
+	keystore: Arc<dyn Keystore>,
+}
+
+impl NodeStatusProvider for NodeStatus {
+	fn is_major_syncing(&self) -> bool {
+		self.sync_service.is_major_syncing()
+	}
+
+	fn peer_count(&self) -> usize {
+		self.sync_service.num_connected_peers()
+	}
+
+	fn node_version(&self) -> SemVer {
+		SemVer {
+			major: env!("CARGO_PKG_VERSION_MAJOR")
+				.parse::<u8>()
+				.expect("Cargo version must be set"),
+			minor: env!("CARGO_PKG_VERSION_MINOR")
+				.parse::<u8>()
+				.expect("Cargo version must be set"),
+			patch: env!("CARGO_PKG_VERSION_PATCH")
+				.parse::<u8>()
+				.expect("Cargo version must be set"),
+		}
+	}
+
+	fn aura_key(&self) -> Option<[u8; 32]> {
+		self.keystore.sr25519_public_keys(key_types::AURA).first().map(|key| key.0)
+	}
+
+	fn has_grandpa_key(&self) -> bool {
+		!self.keystore.ed25519_public_keys(key_types::GRANDPA).is_empty()
+	}
+}
+
 pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 	let telemetry = config
 		.telemetry_endpoints
@@ -156,6 +200,7 @@ pub fn new_full<
 	N: sc_network::NetworkBackend<Block, <Block as sp_runtime::traits::Block>::Hash>,
 >(
 	config: Configuration,
+	enable_account_events_index: bool,
 ) -> Result<TaskManager, ServiceError> {
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
 
@@ -261,6 +306,7 @@ pub fn new_full<
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
+	let account_events_index = enable_account_events_index.then(AccountEventsIndex::default);
 
 	let rpc_builder = {
 		let client = client.clone();
@@ -269,6 +315,11 @@ pub fn new_full<
 		let executor = Arc::new(task_manager.spawn_handle());
 		let chain_spec = config.chain_spec.cloned_box();
 		let keystore = keystore_container.local_keystore().clone();
+		let account_events_index = account_events_index.clone();
+		let node_status = Arc::new(NodeStatus {
+			sync_service: sync_service.clone(),
+			keystore: keystore_container.keystore(),
+		});
 
 		// try to get the broker key pair from the node keystore
 		let broker_key_pair =
@@ -359,6 +410,19 @@ pub fn new_full<
 					executor.clone(),
 				)))?;
 
+				if let Some(account_events_index) = &account_events_index {
+					module.merge(AccountEventsApiServer::into_rpc(AccountEventsRpc::new(
+						account_events_index.clone(),
+					)))?;
+				}
+
+				module.merge(NodeHealthApiServer::into_rpc(NodeHealthRpc::new(
+					client.clone(),
+					backend.clone(),
+					executor.clone(),
+					node_status.clone(),
+				)))?;
+
 				// Add broker RPCs if broker key was found
 				if let Some(pair) = broker_key_pair.clone() {
 					module.merge(BrokerRpcApiServer::into_rpc(BrokerSignedRpc::new(
@@ -402,6 +466,25 @@ pub fn new_full<
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let Some(account_events_index) = account_events_index {
+		task_manager.spawn_handle().spawn(
+			"account-events-index",
+			None,
+			account_events_index.index_finalized_blocks(client.clone()),
+		);
+	}
+
+	if let Some(registry) = prometheus_registry.as_ref() {
+		match metrics::ProtocolMetrics::register(registry) {
+			Ok(protocol_metrics) => task_manager.spawn_handle().spawn(
+				"protocol-metrics",
+				None,
+				metrics::update_on_finalized_blocks(client.clone(), protocol_metrics),
+			),
+			Err(error) => log::warn!("Failed to register protocol metrics: {error}"),
+		}
+	}
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -18,6 +18,7 @@
 
 use super::*;
 use frame_benchmarking::v2::*;
+use frame_support::{assert_ok, traits::UnfilteredDispatchable};
 
 #[benchmarks]
 mod benchmarks {
@@ -33,4 +34,29 @@ mod benchmarks {
 
 		assert_eq!(VanityNames::<T>::get().get(&caller), Some(&name));
 	}
+
+	#[benchmark]
+	fn switch_account_role() {
+		let caller: T::AccountId = whitelisted_caller();
+		<frame_system::Provider<T> as HandleLifetime<T::AccountId>>::created(&caller).unwrap();
+		Pallet::<T>::register_as_broker(&caller).unwrap();
+
+		#[extrinsic_call]
+		switch_account_role(RawOrigin::Signed(caller.clone()), AccountRole::LiquidityProvider);
+
+		assert_eq!(AccountRoles::<T>::get(&caller), Some(AccountRole::LiquidityProvider));
+	}
+
+	#[benchmark]
+	fn set_unique_vanity_names() {
+		let call = Call::<T>::set_unique_vanity_names { enabled: true };
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin));
+		}
+
+		assert!(UniqueVanityNames::<T>::get());
+	}
 }
@@ -26,8 +26,9 @@ pub use weights::WeightInfo;
 pub mod migrations;
 
 use cf_primitives::AccountRole;
-use cf_traits::{AccountRoleRegistry, DeregistrationCheck};
+use cf_traits::{AccountRoleRegistry, AccountRoleSwitch, DeregistrationCheck};
 use frame_support::{
+	ensure,
 	error::BadOrigin,
 	pallet_prelude::{DispatchResult, StorageVersion},
 	traits::{EnsureOrigin, HandleLifetime, IsType, OnKilledAccount, OnNewAccount},
@@ -57,6 +58,8 @@ pub mod pallet {
 		type DeregistrationCheck: DeregistrationCheck<
 			AccountId = <Self as frame_system::Config>::AccountId,
 		>;
+		/// Performs the role-specific deregistration and registration when switching roles.
+		type RoleSwitch: AccountRoleSwitch<Self::AccountId>;
 		type WeightInfo: WeightInfo;
 	}
 
@@ -73,12 +76,23 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type AccountRoles<T: Config> = StorageMap<_, Identity, T::AccountId, AccountRole>;
 
-	/// Vanity names of the validators stored as a Map with the current validator IDs as key.
+	/// A role held alongside the account's role in [AccountRoles]. Only the Broker and
+	/// LiquidityProvider roles can be held together.
+	#[pallet::storage]
+	pub type SecondaryAccountRoles<T: Config> =
+		StorageMap<_, Identity, T::AccountId, AccountRole, OptionQuery>;
+
+	/// Vanity names of accounts (validators, brokers, LPs etc.) stored as a Map with the account
+	/// IDs as key.
 	#[pallet::storage]
 	#[pallet::getter(fn vanity_names)]
 	pub type VanityNames<T: Config> =
 		StorageValue<_, BTreeMap<T::AccountId, VanityName>, ValueQuery>;
 
+	/// Whether a vanity name can only be held by a single account.
+	#[pallet::storage]
+	pub type UniqueVanityNames<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -95,6 +109,10 @@ pub mod pallet {
 			account_id: T::AccountId,
 			name: VanityName,
 		},
+		/// Uniqueness of vanity names has been enabled or disabled.
+		UniqueVanityNamesUpdated {
+			enabled: bool,
+		},
 	}
 
 	#[pallet::error]
@@ -105,6 +123,13 @@ pub mod pallet {
 		AccountRoleAlreadyRegistered,
 		/// Invalid characters in the name.
 		InvalidCharactersInName,
+		/// The name is empty or has leading or trailing whitespace.
+		InvalidVanityNameLength,
+		/// The name is already used by another account.
+		VanityNameAlreadyTaken,
+		/// Accounts can only switch between the Broker and LiquidityProvider roles, and only while
+		/// holding a single role.
+		InvalidRoleSwitch,
 	}
 
 	#[pallet::genesis_config]
@@ -135,10 +160,11 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Allow a node to set a "Vanity Name" for themselves. This is functionally
-		/// useless but can be used to make the network a bit more friendly for
-		/// observers. Names are required to be <= MAX_LENGTH_FOR_VANITY_NAME (64)
-		/// UTF-8 bytes.
+		/// Allow an account (validator, broker, LP etc.) to set a "Vanity Name" for itself. This
+		/// is functionally useless but can be used to make the network a bit more friendly for
+		/// observers. Names are required to be non-empty, <= MAX_LENGTH_FOR_VANITY_NAME (64)
+		/// UTF-8 bytes, without control characters or leading/trailing whitespace. If
+		/// [UniqueVanityNames] is enabled, the name must not be used by any other account.
 		///
 		/// The dispatch origin of this function must be signed.
 		///
@@ -150,48 +176,121 @@ pub mod pallet {
 		///
 		/// - [BadOrigin](frame_system::error::BadOrigin)
 		/// - [InvalidCharactersInName](Error::InvalidCharactersInName)
+		/// - [InvalidVanityNameLength](Error::InvalidVanityNameLength)
+		/// - [VanityNameAlreadyTaken](Error::VanityNameAlreadyTaken)
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::set_vanity_name())]
 		pub fn set_vanity_name(origin: OriginFor<T>, name: VanityName) -> DispatchResult {
 			let account_id = ensure_signed(origin)?;
-			ensure!(sp_std::str::from_utf8(&name).is_ok(), Error::<T>::InvalidCharactersInName);
-			VanityNames::<T>::mutate(|vanity_names| {
+			Self::ensure_valid_vanity_name(&name)?;
+			VanityNames::<T>::try_mutate(|vanity_names| {
+				ensure!(
+					!UniqueVanityNames::<T>::get() ||
+						!vanity_names.iter().any(
+							|(other, other_name)| other != &account_id && other_name == &name
+						),
+					Error::<T>::VanityNameAlreadyTaken
+				);
 				vanity_names.insert(account_id.clone(), name.clone());
-			});
+				Ok::<_, Error<T>>(())
+			})?;
 			Self::deposit_event(Event::VanityNameSet { account_id, name });
 			Ok(())
 		}
+
+		/// Switches a Broker account to the LiquidityProvider role or vice versa, without having
+		/// to redeem and re-fund the account. The account must meet the same conditions as for
+		/// deregistering its current role, eg. no open private channels, orders or balances.
+		///
+		/// The dispatch origin of this function must be signed.
+		///
+		/// ## Events
+		///
+		/// - [AccountRoleDeregistered](Event::AccountRoleDeregistered)
+		/// - [AccountRoleRegistered](Event::AccountRoleRegistered)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::error::BadOrigin)
+		/// - [UnknownAccount](Error::UnknownAccount)
+		/// - [InvalidRoleSwitch](Error::InvalidRoleSwitch)
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::switch_account_role())]
+		pub fn switch_account_role(origin: OriginFor<T>, new_role: AccountRole) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			let current_role =
+				AccountRoles::<T>::get(&account_id).ok_or(Error::<T>::UnknownAccount)?;
+			ensure!(
+				!SecondaryAccountRoles::<T>::contains_key(&account_id) &&
+					matches!(
+						(current_role, new_role),
+						(AccountRole::Broker, AccountRole::LiquidityProvider) |
+							(AccountRole::LiquidityProvider, AccountRole::Broker)
+					),
+				Error::<T>::InvalidRoleSwitch
+			);
+
+			T::RoleSwitch::deregister(&account_id, current_role)?;
+			T::RoleSwitch::register(&account_id, new_role)
+		}
+
+		/// Enables or disables uniqueness of vanity names. Names that are already shared by
+		/// several accounts are not affected, but can no longer be set by other accounts.
+		///
+		/// The dispatch origin of this function must be governance.
+		///
+		/// ## Events
+		///
+		/// - [UniqueVanityNamesUpdated](Event::UniqueVanityNamesUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::error::BadOrigin)
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_unique_vanity_names())]
+		pub fn set_unique_vanity_names(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			UniqueVanityNames::<T>::put(enabled);
+			Self::deposit_event(Event::UniqueVanityNamesUpdated { enabled });
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> AccountRoleRegistry<T> for Pallet<T> {
 	/// Register the account role for some account id.
 	///
-	/// Fails if an account role has already been registered for this account id, or if the account
-	/// doesn't exist.
+	/// Fails if the account doesn't exist, or if it already has a role that can't be held
+	/// together with the new one. Only the Broker and LiquidityProvider roles can be combined.
 	#[frame_support::transactional]
 	fn register_account_role(
 		account_id: &T::AccountId,
 		account_role: AccountRole,
 	) -> DispatchResult {
-		AccountRoles::<T>::try_mutate(account_id, |old_account_role| {
-			match old_account_role.replace(account_role) {
-				Some(AccountRole::Unregistered) => {
-					Self::deposit_event(Event::AccountRoleRegistered {
-						account_id: account_id.clone(),
-						role: account_role,
-					});
-					Ok(())
-				},
-				Some(_) => Err(Error::<T>::AccountRoleAlreadyRegistered),
-				None => Err(Error::<T>::UnknownAccount),
-			}
-		})?;
+		match AccountRoles::<T>::get(account_id) {
+			None => return Err(Error::<T>::UnknownAccount.into()),
+			Some(AccountRole::Unregistered) => AccountRoles::<T>::insert(account_id, account_role),
+			Some(current_role) => {
+				ensure!(
+					Self::can_combine_roles(current_role, account_role) &&
+						!SecondaryAccountRoles::<T>::contains_key(account_id),
+					Error::<T>::AccountRoleAlreadyRegistered
+				);
+				SecondaryAccountRoles::<T>::insert(account_id, account_role);
+			},
+		}
 		frame_system::Consumer::<T>::created(account_id)?;
+
+		Self::deposit_event(Event::AccountRoleRegistered {
+			account_id: account_id.clone(),
+			role: account_role,
+		});
+
 		Ok(())
 	}
 
-	/// Deregister the account role for some account id.
+	/// Deregister the account role for some account id. If the account holds another role, that
+	/// role is retained.
 	///
 	/// This is required in order to be able to redeem all funds. Callers should ensure that any
 	/// state associated with the account is cleaned up before calling this function. For example:
@@ -202,11 +301,18 @@ impl<T: Config> AccountRoleRegistry<T> for Pallet<T> {
 		account_role: AccountRole,
 	) -> DispatchResult {
 		T::DeregistrationCheck::check(account_id).map_err(Into::into)?;
-		AccountRoles::<T>::try_mutate(account_id, |role| {
-			role.replace(AccountRole::Unregistered)
-				.filter(|r| *r == account_role)
-				.ok_or(Error::<T>::UnknownAccount)
-		})?;
+		if SecondaryAccountRoles::<T>::get(account_id) == Some(account_role) {
+			SecondaryAccountRoles::<T>::remove(account_id);
+		} else {
+			ensure!(
+				AccountRoles::<T>::get(account_id) == Some(account_role),
+				Error::<T>::UnknownAccount
+			);
+			AccountRoles::<T>::insert(
+				account_id,
+				SecondaryAccountRoles::<T>::take(account_id).unwrap_or_default(),
+			);
+		}
 		<frame_system::Pallet<T>>::dec_consumers(account_id);
 
 		Self::deposit_event(Event::AccountRoleDeregistered {
@@ -218,7 +324,8 @@ impl<T: Config> AccountRoleRegistry<T> for Pallet<T> {
 	}
 
 	fn has_account_role(id: &T::AccountId, role: AccountRole) -> bool {
-		AccountRoles::<T>::get(id).unwrap_or_default() == role
+		AccountRoles::<T>::get(id).unwrap_or_default() == role ||
+			SecondaryAccountRoles::<T>::get(id) == Some(role)
 	}
 
 	fn ensure_account_role(
@@ -230,13 +337,47 @@ impl<T: Config> AccountRoleRegistry<T> for Pallet<T> {
 			AccountRole::Validator => ensure_validator::<T>(origin),
 			AccountRole::LiquidityProvider => ensure_liquidity_provider::<T>(origin),
 			AccountRole::Broker => ensure_broker::<T>(origin),
+			AccountRole::Operator => ensure_operator::<T>(origin),
 		}
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	fn can_combine_roles(role: AccountRole, other_role: AccountRole) -> bool {
+		matches!(
+			(role, other_role),
+			(AccountRole::Broker, AccountRole::LiquidityProvider) |
+				(AccountRole::LiquidityProvider, AccountRole::Broker)
+		)
+	}
+
+	fn ensure_valid_vanity_name(name: &[u8]) -> DispatchResult {
+		let name = sp_std::str::from_utf8(name).map_err(|_| Error::<T>::InvalidCharactersInName)?;
+		ensure!(!name.chars().any(char::is_control), Error::<T>::InvalidCharactersInName);
+		ensure!(!name.is_empty() && name.trim() == name, Error::<T>::InvalidVanityNameLength);
+		Ok(())
+	}
+
+	/// All accounts whose vanity name matches the given name exactly.
+	pub fn accounts_by_vanity_name(name: &[u8]) -> Vec<T::AccountId> {
+		VanityNames::<T>::get()
+			.into_iter()
+			.filter_map(|(account_id, vanity_name)| {
+				(&vanity_name[..] == name).then_some(account_id)
+			})
+			.collect()
+	}
+
+	/// The account's role and, if any, the second role held alongside it.
+	fn account_roles(account_id: &T::AccountId) -> [Option<AccountRole>; 2] {
+		[AccountRoles::<T>::get(account_id), SecondaryAccountRoles::<T>::get(account_id)]
+	}
+}
+
 impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
 	fn on_killed_account(who: &T::AccountId) {
 		AccountRoles::<T>::remove(who);
+		SecondaryAccountRoles::<T>::remove(who);
 		let _ = VanityNames::<T>::try_mutate(|vanity_names| vanity_names.remove(who).ok_or(()));
 	}
 }
@@ -258,9 +399,13 @@ macro_rules! define_ensure_origin {
 			fn try_origin(o: OriginFor<T>) -> Result<Self::Success, OriginFor<T>> {
 				match o.clone().into() {
 					Ok(RawOrigin::Signed(account_id)) =>
-						match AccountRoles::<T>::get(&account_id) {
-							Some($account_variant) => Ok(account_id),
-							_ => Err(o),
+						if Pallet::<T>::account_roles(&account_id)
+							.iter()
+							.any(|role| matches!(role, Some($account_variant)))
+						{
+							Ok(account_id)
+						} else {
+							Err(o)
 						},
 					Ok(o) => Err(o.into()),
 					Err(o) => Err(o),
@@ -276,9 +421,15 @@ macro_rules! define_ensure_origin {
 
 		/// Ensure that the origin is signed and that the signer operates the correct [AccountRole].
 		pub fn $fn_name<T: Config>(o: OriginFor<T>) -> Result<T::AccountId, BadOrigin> {
-			ensure_signed(o).and_then(|account_id| match AccountRoles::<T>::get(&account_id) {
-				Some($account_variant) => Ok(account_id),
-				_ => Err(BadOrigin),
+			ensure_signed(o).and_then(|account_id| {
+				if Pallet::<T>::account_roles(&account_id)
+					.iter()
+					.any(|role| matches!(role, Some($account_variant)))
+				{
+					Ok(account_id)
+				} else {
+					Err(BadOrigin)
+				}
 			})
 		}
 	};
@@ -286,6 +437,7 @@ macro_rules! define_ensure_origin {
 
 define_ensure_origin!(ensure_broker, EnsureBroker, AccountRole::Broker);
 define_ensure_origin!(ensure_validator, EnsureValidator, AccountRole::Validator);
+define_ensure_origin!(ensure_operator, EnsureOperator, AccountRole::Operator);
 define_ensure_origin!(
 	ensure_liquidity_provider,
 	EnsureLiquidityProvider,
@@ -17,8 +17,11 @@
 #![cfg(test)]
 
 use crate::{self as pallet_cf_account_roles, Config};
-use cf_traits::mocks::deregistration_check::MockDeregistrationCheck;
-use frame_support::derive_impl;
+use cf_primitives::AccountRole;
+use cf_traits::{
+	mocks::deregistration_check::MockDeregistrationCheck, AccountRoleRegistry, AccountRoleSwitch,
+};
+use frame_support::{derive_impl, pallet_prelude::DispatchResult};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -38,10 +41,23 @@ impl frame_system::Config for Test {
 	type OnKilledAccount = MockAccountRoles;
 }
 
+pub struct MockRoleSwitch;
+
+impl AccountRoleSwitch<u64> for MockRoleSwitch {
+	fn deregister(account_id: &u64, role: AccountRole) -> DispatchResult {
+		MockAccountRoles::deregister_account_role(account_id, role)
+	}
+
+	fn register(account_id: &u64, role: AccountRole) -> DispatchResult {
+		MockAccountRoles::register_account_role(account_id, role)
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type EnsureGovernance = frame_system::EnsureRoot<<Self as frame_system::Config>::AccountId>;
 	type DeregistrationCheck = MockDeregistrationCheck<Self::AccountId>;
+	type RoleSwitch = MockRoleSwitch;
 	type WeightInfo = ();
 }
 
@@ -180,6 +180,50 @@ fn test_setting_vanity_names_() {
 	});
 }
 
+#[test]
+fn vanity_names_are_validated_and_can_be_looked_up() {
+	new_test_ext().execute_with(|| {
+		for invalid_name in ["", " Padded", "Padded ", "Line\nBreak"] {
+			assert!(AccountRolesPallet::set_vanity_name(
+				RuntimeOrigin::signed(ALICE),
+				invalid_name.as_bytes().to_vec().try_into().unwrap()
+			)
+			.is_err());
+		}
+		assert_noop!(
+			AccountRolesPallet::set_vanity_name(
+				RuntimeOrigin::signed(ALICE),
+				b"".to_vec().try_into().unwrap()
+			),
+			Error::<Test>::InvalidVanityNameLength
+		);
+
+		let name: VanityName = b"Chainflip Broker".to_vec().try_into().unwrap();
+		assert_ok!(AccountRolesPallet::set_vanity_name(RuntimeOrigin::signed(ALICE), name.clone()));
+		assert_ok!(AccountRolesPallet::set_vanity_name(RuntimeOrigin::signed(BOB), name.clone()));
+		assert_eq!(AccountRolesPallet::accounts_by_vanity_name(&name), vec![ALICE, BOB]);
+		assert!(AccountRolesPallet::accounts_by_vanity_name(b"Unknown").is_empty());
+
+		assert_noop!(
+			AccountRolesPallet::set_unique_vanity_names(RuntimeOrigin::signed(ALICE), true),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(AccountRolesPallet::set_unique_vanity_names(RuntimeOrigin::root(), true));
+
+		// Names can no longer be shared, but existing holders can still set their name.
+		assert_noop!(
+			AccountRolesPallet::set_vanity_name(RuntimeOrigin::signed(CHARLIE), name.clone()),
+			Error::<Test>::VanityNameAlreadyTaken
+		);
+		assert_ok!(AccountRolesPallet::set_vanity_name(RuntimeOrigin::signed(ALICE), name.clone()));
+		assert_ok!(AccountRolesPallet::set_vanity_name(
+			RuntimeOrigin::signed(CHARLIE),
+			b"Chainflip LP".to_vec().try_into().unwrap()
+		));
+		assert_eq!(AccountRolesPallet::accounts_by_vanity_name(b"Chainflip LP"), vec![CHARLIE]);
+	});
+}
+
 #[test]
 fn deregistration_checks() {
 	new_test_ext().execute_with(|| {
@@ -199,3 +243,128 @@ fn deregistration_checks() {
 		);
 	});
 }
+
+#[test]
+fn accounts_can_be_both_broker_and_liquidity_provider() {
+	new_test_ext().execute_with(|| {
+		<Provider<Test> as HandleLifetime<u64>>::created(&ALICE).unwrap();
+		<Provider<Test> as HandleLifetime<u64>>::created(&BOB).unwrap();
+		AccountRolesPallet::register_as_broker(&ALICE).unwrap();
+		AccountRolesPallet::register_as_validator(&BOB).unwrap();
+
+		// Only brokers and LPs can hold a second role.
+		assert_noop!(
+			AccountRolesPallet::register_as_validator(&ALICE),
+			Error::<Test>::AccountRoleAlreadyRegistered
+		);
+		assert_noop!(
+			AccountRolesPallet::register_as_liquidity_provider(&BOB),
+			Error::<Test>::AccountRoleAlreadyRegistered
+		);
+
+		assert_ok!(AccountRolesPallet::register_as_liquidity_provider(&ALICE));
+		assert_noop!(
+			AccountRolesPallet::register_as_liquidity_provider(&ALICE),
+			Error::<Test>::AccountRoleAlreadyRegistered
+		);
+		assert!(AccountRolesPallet::has_account_role(&ALICE, AccountRole::Broker));
+		assert!(AccountRolesPallet::has_account_role(&ALICE, AccountRole::LiquidityProvider));
+		ensure_broker::<Test>(OriginFor::<Test>::signed(ALICE)).unwrap();
+		ensure_liquidity_provider::<Test>(OriginFor::<Test>::signed(ALICE)).unwrap();
+		EnsureLiquidityProvider::<Test>::ensure_origin(OriginFor::<Test>::signed(ALICE)).unwrap();
+		ensure_validator::<Test>(OriginFor::<Test>::signed(ALICE)).unwrap_err();
+
+		// Roles held together can't be switched.
+		assert_noop!(
+			AccountRolesPallet::switch_account_role(
+				RuntimeOrigin::signed(ALICE),
+				AccountRole::LiquidityProvider
+			),
+			Error::<Test>::InvalidRoleSwitch
+		);
+
+		// Deregistering one role retains the other.
+		assert_ok!(AccountRolesPallet::deregister_as_broker(&ALICE));
+		assert_eq!(AccountRoles::<Test>::get(ALICE), Some(AccountRole::LiquidityProvider));
+		assert_eq!(SecondaryAccountRoles::<Test>::get(ALICE), None);
+		ensure_broker::<Test>(OriginFor::<Test>::signed(ALICE)).unwrap_err();
+		ensure_liquidity_provider::<Test>(OriginFor::<Test>::signed(ALICE)).unwrap();
+
+		assert_ok!(AccountRolesPallet::register_as_broker(&ALICE));
+		assert_eq!(SecondaryAccountRoles::<Test>::get(ALICE), Some(AccountRole::Broker));
+		assert_ok!(AccountRolesPallet::deregister_as_broker(&ALICE));
+		assert_ok!(AccountRolesPallet::deregister_as_liquidity_provider(&ALICE));
+		assert!(AccountRolesPallet::is_unregistered(&ALICE));
+		assert_eq!(System::consumers(&ALICE), 0);
+	});
+}
+
+#[test]
+fn can_switch_between_broker_and_liquidity_provider() {
+	new_test_ext().execute_with(|| {
+		<Provider<Test> as HandleLifetime<u64>>::created(&ALICE).unwrap();
+		AccountRolesPallet::register_account_role(&ALICE, AccountRole::Broker).unwrap();
+
+		assert_ok!(AccountRolesPallet::switch_account_role(
+			RuntimeOrigin::signed(ALICE),
+			AccountRole::LiquidityProvider
+		));
+		assert_eq!(AccountRoles::<Test>::get(ALICE), Some(AccountRole::LiquidityProvider));
+		System::assert_has_event(RuntimeEvent::MockAccountRoles(Event::AccountRoleDeregistered {
+			account_id: ALICE,
+			role: AccountRole::Broker,
+		}));
+		System::assert_last_event(RuntimeEvent::MockAccountRoles(Event::AccountRoleRegistered {
+			account_id: ALICE,
+			role: AccountRole::LiquidityProvider,
+		}));
+
+		assert_ok!(AccountRolesPallet::switch_account_role(
+			RuntimeOrigin::signed(ALICE),
+			AccountRole::Broker
+		));
+		assert_eq!(AccountRoles::<Test>::get(ALICE), Some(AccountRole::Broker));
+
+		// Switching is subject to the same checks as deregistration.
+		MockDeregistrationCheck::set_should_fail(&ALICE, true);
+		assert!(AccountRolesPallet::switch_account_role(
+			RuntimeOrigin::signed(ALICE),
+			AccountRole::LiquidityProvider
+		)
+		.is_err());
+		assert_eq!(AccountRoles::<Test>::get(ALICE), Some(AccountRole::Broker));
+	});
+}
+
+#[test]
+fn can_only_switch_between_broker_and_liquidity_provider() {
+	new_test_ext().execute_with(|| {
+		<Provider<Test> as HandleLifetime<u64>>::created(&ALICE).unwrap();
+		AccountRolesPallet::register_account_role(&ALICE, AccountRole::Validator).unwrap();
+
+		for new_role in [AccountRole::Broker, AccountRole::LiquidityProvider, AccountRole::Operator]
+		{
+			assert_noop!(
+				AccountRolesPallet::switch_account_role(RuntimeOrigin::signed(ALICE), new_role),
+				Error::<Test>::InvalidRoleSwitch
+			);
+		}
+
+		<Provider<Test> as HandleLifetime<u64>>::created(&BOB).unwrap();
+		AccountRolesPallet::register_account_role(&BOB, AccountRole::Broker).unwrap();
+		for new_role in [AccountRole::Broker, AccountRole::Unregistered, AccountRole::Validator] {
+			assert_noop!(
+				AccountRolesPallet::switch_account_role(RuntimeOrigin::signed(BOB), new_role),
+				Error::<Test>::InvalidRoleSwitch
+			);
+		}
+
+		assert_noop!(
+			AccountRolesPallet::switch_account_role(
+				RuntimeOrigin::signed(CHARLIE),
+				AccountRole::Broker
+			),
+			Error::<Test>::UnknownAccount
+		);
+	});
+}
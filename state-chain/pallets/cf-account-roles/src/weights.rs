@@ -49,6 +49,8 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_cf_account_roles.
 pub trait WeightInfo {
 	fn set_vanity_name() -> Weight;
+	fn switch_account_role() -> Weight;
+	fn set_unique_vanity_names() -> Weight;
 }
 
 /// Weights for pallet_cf_account_roles using the Substrate node and recommended hardware.
@@ -62,7 +64,16 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		//  Estimated: `2031`
 		// Minimum execution time: 14_620_000 picoseconds.
 		Weight::from_parts(15_116_000, 2031)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn switch_account_role() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn set_unique_vanity_names() -> Weight {
+		Weight::from_parts(6_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
@@ -77,7 +88,16 @@ impl WeightInfo for () {
 		//  Estimated: `2031`
 		// Minimum execution time: 14_620_000 picoseconds.
 		Weight::from_parts(15_116_000, 2031)
-			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	fn switch_account_role() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(18_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+	}
+	fn set_unique_vanity_names() -> Weight {
+		Weight::from_parts(6_000_000, 0)
 			.saturating_add(ParityDbWeight::get().writes(1_u64))
 	}
 }
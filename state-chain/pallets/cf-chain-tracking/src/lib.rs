@@ -78,6 +78,11 @@ pub mod pallet {
 	pub type CurrentChainState<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, ChainState<T::TargetChain>>;
 
+	/// The State Chain block at which the tracked chain state was last updated.
+	#[pallet::storage]
+	pub type LastChainStateUpdate<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
 	/// The fee multiplier value used when estimating ingress/egress fees
 	#[pallet::storage]
 	#[pallet::getter(fn fee_multiplier)]
@@ -187,6 +192,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 			Ok(())
 		})?;
+		LastChainStateUpdate::<T, I>::put(frame_system::Pallet::<T>::block_number());
 
 		Self::deposit_event(Event::<T, I>::ChainStateUpdated { new_chain_state });
 
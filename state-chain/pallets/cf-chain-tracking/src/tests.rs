@@ -17,7 +17,7 @@
 #![cfg(test)]
 use crate::{
 	mock::*, Call as PalletCall, ChainState, CurrentChainState, Error, Event as PalletEvent,
-	FeeMultiplier,
+	FeeMultiplier, LastChainStateUpdate,
 };
 use cf_chains::mocks::MockTrackedData;
 use frame_support::{assert_noop, assert_ok, pallet_prelude::DispatchResult, traits::OriginTrait};
@@ -117,6 +117,8 @@ fn can_update_chain_state() {
 			MockChainTracking::update_chain_state(OriginTrait::none(), new_chain_state.clone()),
 			sp_runtime::DispatchError::BadOrigin,
 		);
+		assert_eq!(LastChainStateUpdate::<Test>::get(), None);
+		System::set_block_number(10);
 		assert_ok!(MockChainTracking::update_chain_state(
 			OriginTrait::root(),
 			new_chain_state.clone(),
@@ -125,5 +127,6 @@ fn can_update_chain_state() {
 			CurrentChainState::<Test>::get().unwrap().block_height,
 			new_chain_state.block_height
 		);
+		assert_eq!(LastChainStateUpdate::<Test>::get(), Some(10));
 	});
 }
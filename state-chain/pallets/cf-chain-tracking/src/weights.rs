@@ -63,7 +63,7 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		// Minimum execution time: 10_320_000 picoseconds.
 		Weight::from_parts(10_699_000, 1525)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 }
 
@@ -78,6 +78,6 @@ impl WeightInfo for () {
 		// Minimum execution time: 10_320_000 picoseconds.
 		Weight::from_parts(10_699_000, 1525)
 			.saturating_add(ParityDbWeight::get().reads(1_u64))
-			.saturating_add(ParityDbWeight::get().writes(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
 	}
 }
@@ -80,6 +80,35 @@ pub enum SafeModeUpdate<T: Config> {
 	CodeAmber(T::RuntimeSafeMode),
 }
 
+/// A summary of the UTXOs available to the Bitcoin vault.
+#[derive(
+	serde::Serialize,
+	serde::Deserialize,
+	Encode,
+	Decode,
+	TypeInfo,
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+)]
+pub struct BitcoinUtxoSummary {
+	pub count: u32,
+	pub total_value: BtcAmount,
+	/// The number of UTXOs whose amount doesn't cover the fee for spending them.
+	pub dust_count: u32,
+	pub largest: Option<BtcAmount>,
+	pub smallest: Option<BtcAmount>,
+	/// The number of spendable UTXOs that are still held by the previous vault key.
+	pub previous_key_count: u32,
+	pub consolidation_threshold: u32,
+	pub consolidation_size: u32,
+	/// The number of spendable UTXOs that can be added before a consolidation is triggered.
+	pub utxos_until_consolidation: u32,
+	/// Whether the next consolidation check will consolidate any UTXOs.
+	pub consolidation_due: bool,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -252,6 +281,11 @@ pub mod pallet {
 	pub type SolanaUnavailableNonceAccounts<T> =
 		StorageMap<_, Blake2_128Concat, SolAddress, SolHash>;
 
+	/// The State Chain block at which each Solana nonce account was last taken into use.
+	#[pallet::storage]
+	pub type SolanaNonceAccountLastUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, SolAddress, BlockNumberFor<T>>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn sol_genesis_hash)]
 	pub type SolanaGenesisHash<T> = StorageValue<_, SolHash, OptionQuery>;
@@ -821,6 +855,40 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	pub fn bitcoin_utxo_summary() -> BitcoinUtxoSummary {
+		let utxos = BitcoinAvailableUtxos::<T>::get();
+		let fee_info = T::BitcoinFeeInfo::bitcoin_fee_info();
+		let utxo_selection::ConsolidationParameters { consolidation_threshold, consolidation_size } =
+			Self::consolidation_parameters();
+		let previous_key =
+			T::BitcoinKeyProvider::active_epoch_key().and_then(|epoch_key| epoch_key.key.previous);
+
+		let spendable =
+			utxos.iter().filter(|utxo| utxo.net_value(&fee_info) > 0).collect::<Vec<_>>();
+		let spendable_count = spendable.len() as u32;
+		let previous_key_count = previous_key
+			.map(|previous_key| {
+				spendable
+					.iter()
+					.filter(|utxo| utxo.deposit_address.pubkey_x == previous_key)
+					.count() as u32
+			})
+			.unwrap_or_default();
+
+		BitcoinUtxoSummary {
+			count: utxos.len() as u32,
+			total_value: utxos.iter().map(|utxo| utxo.amount).sum(),
+			dust_count: utxos.len() as u32 - spendable_count,
+			largest: utxos.iter().map(|utxo| utxo.amount).max(),
+			smallest: utxos.iter().map(|utxo| utxo.amount).min(),
+			previous_key_count,
+			consolidation_threshold,
+			consolidation_size,
+			utxos_until_consolidation: consolidation_threshold.saturating_sub(spendable_count),
+			consolidation_due: spendable_count >= consolidation_threshold || previous_key_count > 0,
+		}
+	}
+
 	fn consolidation_transaction_change_amount(
 		spendable_utxos: &[Utxo],
 		fee_info: &cf_chains::btc::BitcoinFeeInfo,
@@ -842,6 +910,10 @@ impl<T: Config> Pallet<T> {
 		});
 		nonce_and_account.map(|(account, nonce)| {
 			SolanaUnavailableNonceAccounts::<T>::insert(account, nonce);
+			SolanaNonceAccountLastUsed::<T>::insert(
+				account,
+				frame_system::Pallet::<T>::block_number(),
+			);
 			if let Err(err) = T::SolanaNonceWatch::watch_for_nonce_change(account, nonce) {
 				log::error!("Error initiating watch for nonce change: {:?}", err);
 			}
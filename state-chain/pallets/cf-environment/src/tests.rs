@@ -30,8 +30,9 @@ use cf_traits::SafeMode;
 use frame_support::{assert_noop, assert_ok, traits::OriginTrait};
 
 use crate::{
-	mock::*, BitcoinAvailableUtxos, ConsolidationParameters, Event, RuntimeSafeMode,
-	SafeModeUpdate, SolanaAvailableNonceAccounts, SolanaUnavailableNonceAccounts,
+	mock::*, BitcoinAvailableUtxos, BitcoinUtxoSummary, ConsolidationParameters, Event,
+	RuntimeSafeMode, SafeModeUpdate, SolanaAvailableNonceAccounts, SolanaNonceAccountLastUsed,
+	SolanaUnavailableNonceAccounts,
 };
 
 fn utxo(amount: BtcAmount, salt: u32, pub_key: Option<[u8; 32]>) -> Utxo {
@@ -273,6 +274,49 @@ fn can_consolidate_current_and_prev_utxos() {
 	});
 }
 
+#[test]
+fn summarises_bitcoin_utxos() {
+	let epoch_1 = [0xAA; 32];
+	let epoch_2 = [0xBB; 32];
+	new_test_ext().execute_with(|| {
+		MockBitcoinKeyProvider::set_key(AggKey { current: epoch_2, previous: None });
+		ConsolidationParameters::<Test>::set(utxo_selection::ConsolidationParameters {
+			consolidation_threshold: 4,
+			consolidation_size: 2,
+		});
+		BitcoinAvailableUtxos::<Test>::set(vec![
+			utxo(1_000_000, CHANGE_ADDRESS_SALT, Some(epoch_2)),
+			utxo(3_000_000, CHANGE_ADDRESS_SALT, Some(epoch_2)),
+			// Not enough to cover the fee for spending it.
+			utxo(1, CHANGE_ADDRESS_SALT, Some(epoch_2)),
+		]);
+
+		assert_eq!(
+			Environment::bitcoin_utxo_summary(),
+			BitcoinUtxoSummary {
+				count: 3,
+				total_value: 4_000_001,
+				dust_count: 1,
+				largest: Some(3_000_000),
+				smallest: Some(1),
+				previous_key_count: 0,
+				consolidation_threshold: 4,
+				consolidation_size: 2,
+				utxos_until_consolidation: 2,
+				consolidation_due: false,
+			}
+		);
+
+		// Utxos of the previous key are due to be transferred to the current key.
+		MockBitcoinKeyProvider::set_key(AggKey { current: epoch_2, previous: Some(epoch_1) });
+		BitcoinAvailableUtxos::<Test>::append(utxo_with_key(epoch_1));
+		let summary = Environment::bitcoin_utxo_summary();
+		assert_eq!(summary.previous_key_count, 1);
+		assert_eq!(summary.utxos_until_consolidation, 1);
+		assert!(summary.consolidation_due);
+	});
+}
+
 #[test]
 fn can_consolidate_old_utxo_only() {
 	let epoch_1 = [0xFE; 32];
@@ -440,6 +484,30 @@ fn test_sol_nonces_and_accounts_usage() {
 	});
 }
 
+#[test]
+fn records_when_sol_nonce_accounts_were_last_used() {
+	new_test_ext().execute_with(|| {
+		SolanaAvailableNonceAccounts::<Test>::set(vec![
+			(SolAddress([1u8; 32]), SolHash([10u8; 32])),
+			(SolAddress([2u8; 32]), SolHash([20u8; 32])),
+		]);
+
+		System::set_block_number(10);
+		let (account, _) = Environment::get_sol_nonce_and_account().unwrap();
+		assert_eq!(SolanaNonceAccountLastUsed::<Test>::get(account), Some(10));
+		assert_eq!(SolanaNonceAccountLastUsed::<Test>::get(SolAddress([1u8; 32])), None);
+
+		// Returning the nonce account doesn't affect when it was last used.
+		System::set_block_number(15);
+		Environment::update_sol_nonce(account, SolHash([200; 32]));
+		assert_eq!(SolanaNonceAccountLastUsed::<Test>::get(account), Some(10));
+
+		System::set_block_number(20);
+		assert_eq!(Environment::get_sol_nonce_and_account().unwrap().0, account);
+		assert_eq!(SolanaNonceAccountLastUsed::<Test>::get(account), Some(20));
+	});
+}
+
 #[test]
 fn test_get_all_nonce_accounts() {
 	new_test_ext().execute_with(|| {
@@ -73,5 +73,124 @@ mod benchmarks {
 		assert_eq!(Pallet::<T>::slashing_rate(), slashing_rate)
 	}
 
+	#[benchmark]
+	fn set_fee_scaling_config() {
+		let rate = FeeScalingRateConfig::DelayedExponential { threshold: 5, exponent: 2 };
+		let call = Call::<T>::set_fee_scaling_config { rate, call_classes: None };
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin));
+		}
+
+		assert_eq!(FeeScalingRate::<T>::get(), rate);
+	}
+
+	#[benchmark]
+	fn set_fee_scaling_exemption() {
+		let exemption = FeeScalingExemption::Role(AccountRole::LiquidityProvider);
+		let call =
+			Call::<T>::set_fee_scaling_exemption { exemption: exemption.clone(), exempt: true };
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin));
+		}
+
+		assert!(FeeScalingExemptions::<T>::contains_key(exemption));
+	}
+
+	#[benchmark]
+	fn set_slashing_insurance() {
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Validator).unwrap();
+
+		#[extrinsic_call]
+		set_slashing_insurance(frame_system::RawOrigin::Signed(caller.clone()), true);
+
+		assert!(InsurancePolicies::<T>::contains_key(caller));
+	}
+
+	#[benchmark]
+	fn settle_insurance_claim() {
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Validator).unwrap();
+		InsurancePolicies::<T>::insert(&caller, InsurancePolicy::default());
+		SlashingInsurance::<T>::set(SlashingInsuranceParameters {
+			daily_premium: Permill::from_percent(1),
+			reimbursement: Permill::from_percent(50),
+			max_payout: 1_000,
+			min_cover_days: 0,
+			..Default::default()
+		});
+		let _ = Pallet::<T>::deposit_reserves(SLASHING_INSURANCE_POT, 1_000u128.into())
+			.offset(Pallet::<T>::mint(1_000u128.into()));
+		Pallet::<T>::record_insurance_claim(&caller, 100u128.into());
+		let call = Call::<T>::settle_insurance_claim { claim_id: 0, approved: true };
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin));
+		}
+
+		assert!(!InsuranceClaims::<T>::contains_key(0));
+	}
+
+	#[benchmark]
+	fn collect_insurance_premiums(n: Linear<1, 100>) {
+		SlashingInsurance::<T>::set(SlashingInsuranceParameters {
+			daily_premium: Permill::from_percent(1),
+			reimbursement: Permill::from_percent(50),
+			max_payout: 1_000,
+			min_cover_days: 0,
+			..Default::default()
+		});
+		for account_id in T::AccountRoleRegistry::generate_whitelisted_callers_with_role(
+			AccountRole::Validator,
+			n,
+		)
+		.unwrap()
+		{
+			<Pallet<T> as FeePayment>::mint_to_account(&account_id, 1_000_000u128.into());
+			Bonder::<T>::update_bond(&account_id, 100_000u128.into());
+			InsurancePolicies::<T>::insert(&account_id, InsurancePolicy::default());
+		}
+
+		#[block]
+		{
+			assert_eq!(Pallet::<T>::collect_insurance_premiums(), n);
+		}
+
+		assert!(!Reserve::<T>::get(SLASHING_INSURANCE_POT).is_zero());
+	}
+
+	#[benchmark]
+	fn expire_insurance_claims(n: Linear<1, MAX_INSURANCE_CLAIMS_EXPIRED_PER_BLOCK>) {
+		SlashingInsurance::<T>::set(SlashingInsuranceParameters {
+			min_cover_days: 0,
+			claim_expiry_days: 0,
+			..Default::default()
+		});
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Validator).unwrap();
+		InsurancePolicies::<T>::insert(&caller, InsurancePolicy::default());
+		for _ in 0..n {
+			Pallet::<T>::record_insurance_claim(&caller, 100u128.into());
+		}
+
+		#[block]
+		{
+			assert_eq!(
+				Pallet::<T>::expire_insurance_claims(frame_system::Pallet::<T>::block_number()),
+				n
+			);
+		}
+
+		assert_eq!(NextInsuranceClaimToExpire::<T>::get(), n as InsuranceClaimId);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -27,23 +27,25 @@ mod imbalances;
 mod on_charge_transaction;
 
 pub mod weights;
-use cf_primitives::FlipBalance;
+use cf_primitives::{AccountRole, FlipBalance};
 use scale_info::TypeInfo;
 pub use weights::WeightInfo;
 
 use cf_traits::{
-	AccountInfo, Bonding, DeregistrationCheck, FeePayment, FundingInfo, OnAccountFunded, Slashing,
+	AccountInfo, AccountRoleRegistry, Bonding, DeregistrationCheck, EpochInfo, FeePayment,
+	FundingInfo, OnAccountFunded, Slashing,
 };
 pub use imbalances::{Deficit, ImbalanceSource, InternalSource, Surplus};
 pub use on_charge_transaction::{CallIndexer, FeeScalingRateConfig, FlipTransactionPayment};
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
+	dispatch::GetDispatchInfo,
 	ensure,
 	pallet_prelude::*,
 	sp_runtime::{
 		traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Saturating, Zero},
-		DispatchError, Permill, RuntimeDebug,
+		DispatchError, PerThing, Permill, RuntimeDebug, SaturatedConversion,
 	},
 	traits::{Get, Imbalance, OnKilledAccount, SignedImbalance},
 };
@@ -53,6 +55,9 @@ use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 
 pub use pallet::*;
 
+/// The maximum number of call classes that can be configured for fee scaling.
+pub const MAX_FEE_SCALED_CALL_CLASSES: u32 = 32;
+
 #[derive(
 	CloneNoBound,
 	Copy,
@@ -68,6 +73,95 @@ pub enum PalletConfigUpdate {
 	SetSlashingRate(Permill),
 	// Set fee scaling rate for any calls that are scaled.
 	SetFeeScalingRate(FeeScalingRateConfig),
+	// Set the proportion of each call counter that decays away every block.
+	SetCallCounterDecay(Permill),
+	// Set how the extra fees collected due to fee scaling are distributed.
+	SetFeeSurchargeDistribution(FeeSurchargeDistribution),
+	// Set the call weight (ref time) that counts as a single call for fee scaling. If unset,
+	// every call counts once regardless of its weight.
+	SetFeeScalingWeightUnit(Option<u64>),
+	// Set the maximum fee scaling multiplier. If unset, the multiplier is uncapped.
+	SetMaxFeeScalingMultiplier(Option<u16>),
+	// Set the premiums and payout limits of the slashing insurance pool.
+	SetSlashingInsuranceParameters(SlashingInsuranceParameters),
+}
+
+/// Parameters of the slashing insurance pool, which validators can opt into by paying a daily
+/// premium. Governance decides which slashes of insured validators were caused by infrastructure
+/// failure and are reimbursed from the pool.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SlashingInsuranceParameters {
+	/// The premium charged every day, as a proportion of the insured validator's bond.
+	pub daily_premium: Permill,
+	/// The proportion of an approved slash that is reimbursed.
+	pub reimbursement: Permill,
+	/// The maximum amount reimbursed for a single slash.
+	pub max_payout: FlipBalance,
+	/// The number of days a validator has to be insured before its slashes can be claimed.
+	pub min_cover_days: u32,
+	/// The number of days after which a claim that governance hasn't adjudicated expires.
+	pub claim_expiry_days: u32,
+}
+
+impl Default for SlashingInsuranceParameters {
+	fn default() -> Self {
+		Self {
+			daily_premium: Permill::zero(),
+			reimbursement: Permill::zero(),
+			max_payout: 0,
+			min_cover_days: 7,
+			claim_expiry_days: 28,
+		}
+	}
+}
+
+/// The premiums paid and payouts received by an insured validator.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct InsurancePolicy<Balance, BlockNumber> {
+	pub insured_since: BlockNumber,
+	pub premiums_paid: Balance,
+	pub payouts_received: Balance,
+}
+
+/// A slash of an insured validator that is awaiting adjudication by governance.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct InsuranceClaim<AccountId, Balance, BlockNumber> {
+	pub account_id: AccountId,
+	pub slashed: Balance,
+	pub slashed_at: BlockNumber,
+}
+
+pub type InsuranceClaimId = u64;
+
+/// Determines how the extra fees collected due to fee scaling are distributed. Whatever is not
+/// allocated to the network fee pot or the validators is burned.
+#[derive(
+	Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default,
+)]
+pub struct FeeSurchargeDistribution {
+	pub network_fee_pot: Permill,
+	pub validators: Permill,
+}
+
+impl FeeSurchargeDistribution {
+	pub fn is_valid(&self) -> bool {
+		self.network_fee_pot.deconstruct() + self.validators.deconstruct() <= Permill::ACCURACY
+	}
+}
+
+/// An account, or all accounts with a given role, exempt from fee scaling.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum FeeScalingExemption<AccountId> {
+	Account(AccountId),
+	Role(AccountRole),
+}
+
+pub struct DefaultCallCounterDecay;
+impl Get<Permill> for DefaultCallCounterDecay {
+	fn get() -> Permill {
+		// Counters are reset every block by default.
+		Permill::one()
+	}
 }
 
 #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
@@ -95,6 +189,20 @@ pub mod pallet {
 	/// A 4-byte identifier for different reserves.
 	pub type ReserveId = [u8; 4];
 
+	/// The reserve into which the network fee pot's share of fee scaling surcharges is deposited.
+	pub const NETWORK_FEE_POT: ReserveId = *b"NFEE";
+
+	/// The reserve into which slashing insurance premiums are paid, and from which approved
+	/// claims are reimbursed.
+	pub const SLASHING_INSURANCE_POT: ReserveId = *b"SINS";
+
+	/// The maximum number of insurance claims that are expired in a single block.
+	pub const MAX_INSURANCE_CLAIMS_EXPIRED_PER_BLOCK: u32 = 50;
+
+	/// The reserve into which delegators' shares of validator rewards are minted until they are
+	/// claimed.
+	pub const DELEGATION_REWARDS_POT: ReserveId = *b"DELG";
+
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: Chainflip<Amount = Self::Balance> {
@@ -166,13 +274,81 @@ pub mod pallet {
 	#[pallet::getter(fn offchain_funds)]
 	pub type OffchainFunds<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
 
-	// Counts the number of calls within a block against a particular call info id.
+	// Counts the number of recent calls against a particular call info id. Counters decay every
+	// block according to `CallCounterDecay`.
 	#[pallet::storage]
 	pub type CallCounter<T: Config> = StorageMap<_, Identity, OpaqueCallIndex<T>, u16, ValueQuery>;
 
 	#[pallet::storage]
 	pub type FeeScalingRate<T: Config> = StorageValue<_, FeeScalingRateConfig, ValueQuery>;
 
+	/// The call weight (ref time) that counts as a single call for fee scaling, so that heavy
+	/// calls are counted proportionally to their weight. If unset, every call counts once.
+	#[pallet::storage]
+	pub type FeeScalingWeightUnit<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+	/// The maximum multiplier that fee scaling can apply to a call's fee.
+	#[pallet::storage]
+	pub type MaxFeeScalingMultiplier<T: Config> = StorageValue<_, u16, OptionQuery>;
+
+	/// How the extra fees collected due to fee scaling are distributed.
+	#[pallet::storage]
+	pub type FeeScalingSurchargeDistribution<T: Config> =
+		StorageValue<_, FeeSurchargeDistribution, ValueQuery>;
+
+	/// Accounts and account roles that are exempt from fee scaling.
+	#[pallet::storage]
+	pub type FeeScalingExemptions<T: Config> =
+		StorageMap<_, Blake2_128Concat, FeeScalingExemption<T::AccountId>, (), OptionQuery>;
+
+	/// The proportion of each call counter that is removed at the start of every block, so that
+	/// scaled fees slide back down gradually after a burst of calls.
+	#[pallet::storage]
+	pub type CallCounterDecay<T: Config> =
+		StorageValue<_, Permill, ValueQuery, DefaultCallCounterDecay>;
+
+	/// The call classes that fee scaling applies to. If unset, all indexed calls are scaled.
+	#[pallet::storage]
+	pub type FeeScaledCallClasses<T: Config> = StorageValue<
+		_,
+		BoundedVec<CallIndexFor<T>, ConstU32<MAX_FEE_SCALED_CALL_CLASSES>>,
+		OptionQuery,
+	>;
+
+	/// The parameters of the slashing insurance pool.
+	#[pallet::storage]
+	pub type SlashingInsurance<T: Config> =
+		StorageValue<_, SlashingInsuranceParameters, ValueQuery>;
+
+	/// The validators that are insured against slashing, with their premiums and payouts.
+	#[pallet::storage]
+	pub type InsurancePolicies<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		InsurancePolicy<T::Balance, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Slashes of insured validators that are awaiting adjudication by governance.
+	#[pallet::storage]
+	pub type InsuranceClaims<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		InsuranceClaimId,
+		InsuranceClaim<T::AccountId, T::Balance, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The id of the next insurance claim.
+	#[pallet::storage]
+	pub type NextInsuranceClaimId<T: Config> = StorageValue<_, InsuranceClaimId, ValueQuery>;
+
+	/// The id of the oldest insurance claim that may still be awaiting adjudication. Claims are
+	/// recorded in order, so claims are expired starting from this one.
+	#[pallet::storage]
+	pub type NextInsuranceClaimToExpire<T: Config> = StorageValue<_, InsuranceClaimId, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -192,6 +368,66 @@ pub mod pallet {
 		PalletConfigUpdated {
 			update: PalletConfigUpdate,
 		},
+		FeeScalingConfigUpdated {
+			rate: FeeScalingRateConfig,
+			call_classes:
+				Option<BoundedVec<CallIndexFor<T>, ConstU32<MAX_FEE_SCALED_CALL_CLASSES>>>,
+		},
+		FeeScalingExemptionUpdated {
+			exemption: FeeScalingExemption<T::AccountId>,
+			exempt: bool,
+		},
+		/// The fee for a call by an exempt account would have been scaled by `multiplier`.
+		FeeScalingWaived {
+			who: T::AccountId,
+			multiplier: u16,
+		},
+		/// The fee for a call was scaled up due to the account's recent call count.
+		FeeScaled {
+			account: T::AccountId,
+			call_index: CallIndexFor<T>,
+			base_fee: T::Balance,
+			scaled_fee: T::Balance,
+			multiplier: u16,
+		},
+		/// The fee scaling multiplier for a call was limited by the maximum multiplier.
+		FeeScalingMultiplierCapped {
+			who: T::AccountId,
+			uncapped_multiplier: u16,
+			multiplier: u16,
+		},
+		/// A validator opted into or out of the slashing insurance pool.
+		SlashingInsuranceUpdated {
+			who: T::AccountId,
+			insured: bool,
+		},
+		/// An insured validator paid its daily premium into the slashing insurance pool.
+		InsurancePremiumPaid {
+			who: T::AccountId,
+			amount: T::Balance,
+		},
+		/// An insured validator could not pay its premium, so its cover has lapsed.
+		InsurancePolicyLapsed {
+			who: T::AccountId,
+		},
+		/// An insured validator was slashed and can be reimbursed if governance approves.
+		InsuranceClaimRecorded {
+			claim_id: InsuranceClaimId,
+			who: T::AccountId,
+			slashed: T::Balance,
+		},
+		/// Governance adjudicated an insurance claim. The payout is zero if it was rejected.
+		InsuranceClaimSettled {
+			claim_id: InsuranceClaimId,
+			who: T::AccountId,
+			approved: bool,
+			payout: T::Balance,
+		},
+		/// An insurance claim was not adjudicated in time and has expired.
+		InsuranceClaimExpired {
+			claim_id: InsuranceClaimId,
+			who: T::AccountId,
+		},
 	}
 
 	#[pallet::error]
@@ -204,15 +440,43 @@ pub mod pallet {
 		NoPendingRedemptionForThisID,
 		/// Account is bonded.
 		AccountBonded,
+		/// The fee surcharge distribution allocates more than the whole surcharge.
+		InvalidFeeSurchargeDistribution,
+		/// The maximum fee scaling multiplier must be at least 1.
+		InvalidMaxFeeScalingMultiplier,
+		/// The account is already insured against slashing.
+		AlreadyInsured,
+		/// The account is not insured against slashing.
+		NotInsured,
+		/// There is no insurance claim with this ID.
+		UnknownInsuranceClaim,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_current_block: BlockNumberFor<T>) -> Weight {
-			// Clear the call counter on every block. Do it in on_initialize (instead of
+		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
+			// Decay the call counters on every block. Do it in on_initialize (instead of
 			// `on_finalize`) so it's inspectable.
-			let _ = CallCounter::<T>::clear(u32::MAX, None);
-			T::WeightInfo::on_initialize()
+			let decay = CallCounterDecay::<T>::get();
+			if decay == Permill::one() {
+				let _ = CallCounter::<T>::clear(u32::MAX, None);
+			} else {
+				let retention = Permill::one() - decay;
+				CallCounter::<T>::translate::<u16, _>(|_, count| {
+					Some(retention.mul_floor(count)).filter(|count| *count > 0)
+				});
+			}
+
+			let mut weight = T::WeightInfo::on_initialize();
+			if (current_block % T::BlocksPerDay::get()).is_zero() {
+				weight.saturating_accrue(T::WeightInfo::collect_insurance_premiums(
+					Self::collect_insurance_premiums(),
+				));
+			}
+			weight.saturating_accrue(T::WeightInfo::expire_insurance_claims(
+				Self::expire_insurance_claims(current_block),
+			));
+			weight
 		}
 	}
 
@@ -242,11 +506,170 @@ pub mod pallet {
 					PalletConfigUpdate::SetFeeScalingRate(fee_scaling_rate) => {
 						FeeScalingRate::<T>::set(fee_scaling_rate);
 					},
+					PalletConfigUpdate::SetCallCounterDecay(decay) => {
+						CallCounterDecay::<T>::set(decay);
+					},
+					PalletConfigUpdate::SetMaxFeeScalingMultiplier(max_multiplier) => {
+						ensure!(
+							max_multiplier != Some(0),
+							Error::<T>::InvalidMaxFeeScalingMultiplier
+						);
+						MaxFeeScalingMultiplier::<T>::set(max_multiplier);
+					},
+					PalletConfigUpdate::SetFeeScalingWeightUnit(weight_unit) => {
+						FeeScalingWeightUnit::<T>::set(weight_unit);
+					},
+					PalletConfigUpdate::SetFeeSurchargeDistribution(distribution) => {
+						ensure!(
+							distribution.is_valid(),
+							Error::<T>::InvalidFeeSurchargeDistribution
+						);
+						FeeScalingSurchargeDistribution::<T>::set(distribution);
+					},
+					PalletConfigUpdate::SetSlashingInsuranceParameters(parameters) => {
+						SlashingInsurance::<T>::set(parameters);
+					},
 				};
 				Self::deposit_event(Event::PalletConfigUpdated { update });
 			}
 			Ok(())
 		}
+
+		/// Sets the fee scaling rate (including the threshold call count after which scaling
+		/// kicks in) together with the call classes it applies to. Passing `None` for the call
+		/// classes applies scaling to every indexed call.
+		///
+		/// Requires Governance.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_fee_scaling_config())]
+		pub fn set_fee_scaling_config(
+			origin: OriginFor<T>,
+			rate: FeeScalingRateConfig,
+			call_classes: Option<
+				BoundedVec<CallIndexFor<T>, ConstU32<MAX_FEE_SCALED_CALL_CLASSES>>,
+			>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			FeeScalingRate::<T>::set(rate);
+			FeeScaledCallClasses::<T>::set(call_classes.clone());
+
+			Self::deposit_event(Event::FeeScalingConfigUpdated { rate, call_classes });
+			Ok(())
+		}
+
+		/// Adds an account or account role to, or removes it from, the set of exemptions from fee
+		/// scaling.
+		///
+		/// Requires Governance.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_fee_scaling_exemption())]
+		pub fn set_fee_scaling_exemption(
+			origin: OriginFor<T>,
+			exemption: FeeScalingExemption<T::AccountId>,
+			exempt: bool,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			if exempt {
+				FeeScalingExemptions::<T>::insert(&exemption, ());
+			} else {
+				FeeScalingExemptions::<T>::remove(&exemption);
+			}
+
+			Self::deposit_event(Event::FeeScalingExemptionUpdated { exemption, exempt });
+			Ok(())
+		}
+
+		/// Opts a validator into, or out of, the slashing insurance pool. Insured validators pay
+		/// a daily premium from their liquid funds, the first one upon opting in, and their
+		/// slashes can be reimbursed from the pool if governance finds they were caused by
+		/// infrastructure failure. Slashes are only covered once the validator has been insured
+		/// for the minimum cover period.
+		///
+		/// Opting out does not affect claims that have already been recorded.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_slashing_insurance())]
+		pub fn set_slashing_insurance(origin: OriginFor<T>, insured: bool) -> DispatchResult {
+			let account_id = T::AccountRoleRegistry::ensure_validator(origin)?;
+
+			if insured {
+				ensure!(
+					!InsurancePolicies::<T>::contains_key(&account_id),
+					Error::<T>::AlreadyInsured
+				);
+				let premium = Self::insurance_premium(&account_id);
+				if !premium.is_zero() {
+					let surplus = Self::try_debit_from_liquid_funds(&account_id, premium)
+						.ok_or(Error::<T>::InsufficientLiquidity)?;
+					let _ = surplus.offset(Self::deposit_reserves(SLASHING_INSURANCE_POT, premium));
+					Self::deposit_event(Event::InsurancePremiumPaid {
+						who: account_id.clone(),
+						amount: premium,
+					});
+				}
+				InsurancePolicies::<T>::insert(
+					&account_id,
+					InsurancePolicy {
+						insured_since: frame_system::Pallet::<T>::block_number(),
+						premiums_paid: premium,
+						payouts_received: Zero::zero(),
+					},
+				);
+			} else {
+				ensure!(
+					InsurancePolicies::<T>::take(&account_id).is_some(),
+					Error::<T>::NotInsured
+				);
+			}
+
+			Self::deposit_event(Event::SlashingInsuranceUpdated { who: account_id, insured });
+			Ok(())
+		}
+
+		/// Approves or rejects an insurance claim. An approved claim reimburses the configured
+		/// proportion of the slash, up to the maximum payout and the funds held by the pool.
+		///
+		/// Requires Governance.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::settle_insurance_claim())]
+		pub fn settle_insurance_claim(
+			origin: OriginFor<T>,
+			claim_id: InsuranceClaimId,
+			approved: bool,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			let InsuranceClaim { account_id, slashed, .. } =
+				InsuranceClaims::<T>::take(claim_id).ok_or(Error::<T>::UnknownInsuranceClaim)?;
+
+			let payout = if approved {
+				let parameters = SlashingInsurance::<T>::get();
+				let payout = (parameters.reimbursement * slashed)
+					.min(parameters.max_payout.into())
+					.min(Reserve::<T>::get(SLASHING_INSURANCE_POT));
+				Self::settle(
+					&account_id,
+					Self::withdraw_reserves(SLASHING_INSURANCE_POT, payout).into(),
+				);
+				InsurancePolicies::<T>::mutate(&account_id, |maybe_policy| {
+					if let Some(policy) = maybe_policy {
+						policy.payouts_received.saturating_accrue(payout);
+					}
+				});
+				payout
+			} else {
+				Zero::zero()
+			};
+
+			Self::deposit_event(Event::InsuranceClaimSettled {
+				claim_id,
+				who: account_id,
+				approved,
+				payout,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -336,6 +759,99 @@ impl<T: Config> Pallet<T> {
 		Reserve::<T>::get(reserve_id)
 	}
 
+	/// The amount by which a call of the given weight increments its call counter.
+	pub fn call_count_increment(weight: Weight) -> u16 {
+		FeeScalingWeightUnit::<T>::get().map_or(1, |weight_unit| {
+			weight.ref_time().div_ceil(weight_unit.max(1)).max(1).saturated_into()
+		})
+	}
+
+	/// Returns the number of recent calls counted against the account for the given call index,
+	/// and the fee multiplier that will apply to the account's next such call, given its weight.
+	pub fn fee_scaling_multiplier(
+		account_id: &T::AccountId,
+		call_index: CallIndexFor<T>,
+		weight: Weight,
+	) -> (u16, u16) {
+		if !Self::fee_scaling_applies_to_call_index(&call_index) {
+			return (0, 1)
+		}
+		let call_count =
+			CallCounter::<T>::get(OpaqueCallIndex::from((account_id.clone(), call_index)));
+		if Self::is_exempt_from_fee_scaling(account_id) {
+			return (call_count, 1)
+		}
+		(
+			call_count,
+			Self::cap_fee_scaling_multiplier(FeeScalingRate::<T>::get().multiplier_at_call_count(
+				call_count.saturating_add(Self::call_count_increment(weight)),
+			)),
+		)
+	}
+
+	/// Limits the multiplier to the configured maximum, if any.
+	pub fn cap_fee_scaling_multiplier(multiplier: u16) -> u16 {
+		MaxFeeScalingMultiplier::<T>::get()
+			.map_or(multiplier, |max_multiplier| multiplier.min(max_multiplier))
+	}
+
+	/// Returns the fee multiplier that would apply if the account submitted the call next, or
+	/// `None` if the account's fees for the call are waived.
+	pub fn next_call_fee_multiplier(
+		account_id: &T::AccountId,
+		call: &<T as frame_system::Config>::RuntimeCall,
+	) -> Option<u16> {
+		if T::WaivedFees::should_waive_fees(call, account_id) {
+			return None
+		}
+		Some(T::CallIndexer::call_index(call).map_or(1, |call_index| {
+			Self::fee_scaling_multiplier(account_id, call_index, call.get_dispatch_info().weight).1
+		}))
+	}
+
+	/// Distributes the extra fees collected due to fee scaling according to the configured
+	/// [FeeSurchargeDistribution]. Anything not allocated is burned.
+	pub(crate) fn distribute_fee_surcharge(surcharge: Surplus<T>) {
+		let distribution = FeeScalingSurchargeDistribution::<T>::get();
+		let total = surcharge.peek();
+
+		let (to_pot, remaining) = surcharge.split(distribution.network_fee_pot.mul_floor(total));
+		let amount = to_pot.peek();
+		let _ = to_pot.offset(Self::deposit_reserves(NETWORK_FEE_POT, amount));
+
+		let (mut to_validators, mut remaining) =
+			remaining.split(distribution.validators.mul_floor(total));
+		let authorities = T::EpochInfo::current_authorities();
+		if !authorities.is_empty() {
+			let share = to_validators.peek() / T::Balance::from(authorities.len() as u32);
+			for authority in authorities {
+				let (reward, rest) = to_validators.split(share);
+				let _ = reward.offset(Self::credit(&authority.into(), share));
+				to_validators = rest;
+			}
+		}
+		remaining.subsume(to_validators);
+
+		let amount = remaining.peek();
+		let _ = remaining.offset(Self::burn(amount));
+	}
+
+	/// Whether the account, or one of its roles, is exempt from fee scaling.
+	pub fn is_exempt_from_fee_scaling(account_id: &T::AccountId) -> bool {
+		FeeScalingExemptions::<T>::contains_key(FeeScalingExemption::Account(account_id.clone())) ||
+			FeeScalingExemptions::<T>::iter_keys().any(|exemption| match exemption {
+				FeeScalingExemption::Role(role) =>
+					T::AccountRoleRegistry::has_account_role(account_id, role),
+				FeeScalingExemption::Account(_) => false,
+			})
+	}
+
+	/// Whether fees for calls with the given call index should be scaled.
+	pub fn fee_scaling_applies_to_call_index(call_index: &CallIndexFor<T>) -> bool {
+		FeeScaledCallClasses::<T>::get()
+			.map_or(true, |call_classes| call_classes.contains(call_index))
+	}
+
 	/// Debits an account balance.
 	///
 	/// *Warning:* Creates the flip account if it doesn't exist already, but *doesn't* ensure that
@@ -463,6 +979,106 @@ impl<T: Config> Pallet<T> {
 			.ok_or_else(|| Error::<T>::NoPendingRedemptionForThisID.into())
 	}
 
+	/// Charges every insured validator its daily premium. Validators that can't cover the premium
+	/// from their liquid funds lose their cover. Returns the number of policies processed.
+	pub(crate) fn collect_insurance_premiums() -> u32 {
+		let mut processed = 0u32;
+		let mut lapsed = Vec::new();
+		for (account_id, mut policy) in InsurancePolicies::<T>::iter() {
+			processed.saturating_inc();
+			let premium = Self::insurance_premium(&account_id);
+			if premium.is_zero() {
+				continue
+			}
+			match Self::try_debit_from_liquid_funds(&account_id, premium) {
+				Some(surplus) => {
+					let _ = surplus.offset(Self::deposit_reserves(SLASHING_INSURANCE_POT, premium));
+					policy.premiums_paid.saturating_accrue(premium);
+					InsurancePolicies::<T>::insert(&account_id, policy);
+					Self::deposit_event(Event::InsurancePremiumPaid {
+						who: account_id,
+						amount: premium,
+					});
+				},
+				None => lapsed.push(account_id),
+			}
+		}
+		for account_id in lapsed {
+			InsurancePolicies::<T>::remove(&account_id);
+			Self::deposit_event(Event::InsurancePolicyLapsed { who: account_id });
+		}
+		processed
+	}
+
+	/// The daily premium of the validator, based on its current bond.
+	fn insurance_premium(account_id: &T::AccountId) -> T::Balance {
+		SlashingInsurance::<T>::get().daily_premium * Account::<T>::get(account_id).bond()
+	}
+
+	/// Records a slash of an insured validator as a claim against the insurance pool, provided the
+	/// validator has been insured for at least the minimum cover period.
+	fn record_insurance_claim(account_id: &T::AccountId, slashed: T::Balance) {
+		let Some(policy) = InsurancePolicies::<T>::get(account_id) else { return };
+		let min_cover_period = T::BlocksPerDay::get()
+			.saturating_mul(SlashingInsurance::<T>::get().min_cover_days.into());
+		if frame_system::Pallet::<T>::block_number() <
+			policy.insured_since.saturating_add(min_cover_period)
+		{
+			return
+		}
+		let claim_id = NextInsuranceClaimId::<T>::mutate(|id| {
+			let claim_id = *id;
+			id.saturating_inc();
+			claim_id
+		});
+		InsuranceClaims::<T>::insert(
+			claim_id,
+			InsuranceClaim {
+				account_id: account_id.clone(),
+				slashed,
+				slashed_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+		Self::deposit_event(Event::InsuranceClaimRecorded {
+			claim_id,
+			who: account_id.clone(),
+			slashed,
+		});
+	}
+
+	/// Removes the claims that governance hasn't adjudicated within the claim expiry period, up to
+	/// [MAX_INSURANCE_CLAIMS_EXPIRED_PER_BLOCK] at a time. Returns the number of claims checked.
+	pub(crate) fn expire_insurance_claims(current_block: BlockNumberFor<T>) -> u32 {
+		let expiry_period = T::BlocksPerDay::get()
+			.saturating_mul(SlashingInsurance::<T>::get().claim_expiry_days.into());
+		let next_claim_id = NextInsuranceClaimId::<T>::get();
+		let mut claim_id = NextInsuranceClaimToExpire::<T>::get();
+		let mut checked = 0u32;
+
+		while claim_id < next_claim_id && checked < MAX_INSURANCE_CLAIMS_EXPIRED_PER_BLOCK {
+			checked.saturating_inc();
+			match InsuranceClaims::<T>::get(claim_id) {
+				Some(claim) if claim.slashed_at.saturating_add(expiry_period) > current_block =>
+					break,
+				Some(claim) => {
+					InsuranceClaims::<T>::remove(claim_id);
+					Self::deposit_event(Event::InsuranceClaimExpired {
+						claim_id,
+						who: claim.account_id,
+					});
+				},
+				// Already settled.
+				None => {},
+			}
+			claim_id.saturating_inc();
+		}
+
+		if checked > 0 {
+			NextInsuranceClaimToExpire::<T>::put(claim_id);
+		}
+		checked
+	}
+
 	/// Deposit `amount` into the reserve identified by a `reserve_id`. Creates the reserve it it
 	/// doesn't exist already.
 	pub fn deposit_reserves(reserve_id: ReserveId, amount: T::Balance) -> Deficit<T> {
@@ -631,6 +1247,7 @@ impl<T: Config> OnKilledAccount<T::AccountId> for BurnFlipAccount<T> {
 		let dust = Pallet::<T>::total_balance_of(account_id);
 		Pallet::<T>::settle(account_id, Pallet::<T>::burn(dust).into());
 		Account::<T>::remove(account_id);
+		InsurancePolicies::<T>::remove(account_id);
 		Pallet::<T>::deposit_event(Event::AccountReaped {
 			who: account_id.clone(),
 			dust_burned: dust,
@@ -652,6 +1269,7 @@ impl<T: Config> FlipSlasher<T> {
 				who: account_id.clone(),
 				amount: slash_amount,
 			});
+			Pallet::<T>::record_insurance_claim(account_id, slash_amount);
 		}
 	}
 }
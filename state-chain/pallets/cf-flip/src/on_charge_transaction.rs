@@ -24,12 +24,13 @@ use cf_primitives::{FlipBalance, FLIPPERINOS_PER_FLIP};
 use cf_traits::WaivedFees;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-	pallet_prelude::InvalidTransaction,
+	pallet_prelude::{InvalidTransaction, Member},
 	sp_runtime::{
 		traits::{DispatchInfoOf, Zero},
 		RuntimeDebug,
 	},
 	traits::Imbalance,
+	Parameter,
 };
 use frame_system::Config;
 use pallet_transaction_payment::{Config as TxConfig, OnChargeTransaction};
@@ -82,10 +83,10 @@ impl<T: TxConfig + FlipConfig + Config> OnChargeTransaction<T> for FlipTransacti
 
 	fn correct_and_deposit_fee(
 		who: &T::AccountId,
-		_dispatch_info: &sp_runtime::traits::DispatchInfoOf<
+		dispatch_info: &sp_runtime::traits::DispatchInfoOf<
 			<T as frame_system::Config>::RuntimeCall,
 		>,
-		_post_info: &sp_runtime::traits::PostDispatchInfoOf<
+		post_info: &sp_runtime::traits::PostDispatchInfoOf<
 			<T as frame_system::Config>::RuntimeCall,
 		>,
 		corrected_fee: Self::Balance,
@@ -95,27 +96,59 @@ impl<T: TxConfig + FlipConfig + Config> OnChargeTransaction<T> for FlipTransacti
 		if let Some((surplus, call_index)) = escrow {
 			// It's possible the account was deleted during extrinsic execution. If this is the
 			// case, we shouldn't refund anything, we can just burn all fees in escrow.
-			let to_burn = if frame_system::Pallet::<T>::account_exists(who) {
-				if let Some(call_index) = call_index {
-					corrected_fee.saturating_mul(
-						crate::CallCounter::<T>::mutate(
-							OpaqueCallIndex::from((who.clone(), call_index)),
-							|count| {
-								*count += 1;
-								crate::FeeScalingRate::<T>::get().multiplier_at_call_count(*count)
-							},
-						)
-						.into(),
-					)
+			let (to_burn, surcharge) = if frame_system::Pallet::<T>::account_exists(who) {
+				if let Some(call_index) =
+					call_index.filter(Flip::<T>::fee_scaling_applies_to_call_index)
+				{
+					let uncapped_multiplier = crate::CallCounter::<T>::mutate(
+						OpaqueCallIndex::from((who.clone(), call_index.clone())),
+						|count| {
+							*count = count.saturating_add(Flip::<T>::call_count_increment(
+								post_info.calc_actual_weight(dispatch_info),
+							));
+							crate::FeeScalingRate::<T>::get().multiplier_at_call_count(*count)
+						},
+					);
+					let multiplier = Flip::<T>::cap_fee_scaling_multiplier(uncapped_multiplier);
+					if multiplier > 1 && Flip::<T>::is_exempt_from_fee_scaling(who) {
+						Flip::<T>::deposit_event(crate::Event::FeeScalingWaived {
+							who: who.clone(),
+							multiplier,
+						});
+						(corrected_fee, Zero::zero())
+					} else if multiplier > 1 {
+						if multiplier < uncapped_multiplier {
+							Flip::<T>::deposit_event(crate::Event::FeeScalingMultiplierCapped {
+								who: who.clone(),
+								uncapped_multiplier,
+								multiplier,
+							});
+						}
+						let scaled_fee = corrected_fee.saturating_mul(multiplier.into());
+						Flip::<T>::deposit_event(crate::Event::FeeScaled {
+							account: who.clone(),
+							call_index,
+							base_fee: corrected_fee,
+							scaled_fee,
+							multiplier,
+						});
+						(corrected_fee, scaled_fee - corrected_fee)
+					} else {
+						(corrected_fee, Zero::zero())
+					}
 				} else {
-					corrected_fee
+					(corrected_fee, Zero::zero())
 				}
 			} else {
-				surplus.peek()
+				(surplus.peek(), Zero::zero())
 			};
 
+			let (fee, remaining) = surplus.split(to_burn);
+			let (surcharge, _refund) = remaining.split(surcharge);
+			Flip::<T>::distribute_fee_surcharge(surcharge);
+
 			// If there is a difference this will be reconciled when the result goes out of scope.
-			let _imbalance = surplus.offset(Flip::<T>::burn(to_burn));
+			let _imbalance = fee.offset(Flip::<T>::burn(to_burn));
 		}
 		Ok(())
 	}
@@ -123,7 +156,7 @@ impl<T: TxConfig + FlipConfig + Config> OnChargeTransaction<T> for FlipTransacti
 
 /// Converts a call into a call index to allow it to be categorised for fee scaling.
 pub trait CallIndexer<Call> {
-	type CallIndex: Encode + MaxEncodedLen;
+	type CallIndex: Member + Parameter + MaxEncodedLen;
 
 	fn call_index(_call: &Call) -> Option<Self::CallIndex>;
 }
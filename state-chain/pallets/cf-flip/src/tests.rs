@@ -646,6 +646,170 @@ mod test_tx_payments {
 			assert_eq!(FlipIssuance::<Test>::total_issuance(), 1000 - POST_FEE);
 		});
 	}
+
+	#[test]
+	fn scaled_fees_emit_an_event() {
+		new_test_ext().execute_with(|| {
+			const FEE: FlipBalance = 5;
+
+			FeeScalingRate::<Test>::set(FeeScalingRateConfig::DelayedExponential {
+				threshold: 1,
+				exponent: 1,
+			});
+
+			for (expected_fee, scaled) in [(FEE, false), (2 * FEE, true)] {
+				let balance_before = Flip::total_balance_of(&ALICE);
+				// The mock call indexer doesn't index any calls, so provide the call index
+				// directly.
+				let escrow = Some((Flip::try_debit(&ALICE, 3 * FEE).unwrap(), Some(())));
+				FlipTransactionPayment::<Test>::correct_and_deposit_fee(
+					&ALICE,
+					&CALL.get_dispatch_info(),
+					&().into(),
+					FEE,
+					0,
+					escrow,
+				)
+				.expect("Fee correction never fails.");
+
+				assert_eq!(Flip::total_balance_of(&ALICE), balance_before - expected_fee);
+				if scaled {
+					System::assert_last_event(RuntimeEvent::Flip(crate::Event::FeeScaled {
+						account: ALICE,
+						call_index: (),
+						base_fee: FEE,
+						scaled_fee: expected_fee,
+						multiplier: 2,
+					}));
+				} else {
+					assert!(!System::events().iter().any(|record| matches!(
+						record.event,
+						RuntimeEvent::Flip(crate::Event::FeeScaled { .. })
+					)));
+				}
+			}
+			assert!(check_balance_integrity());
+		});
+	}
+
+	#[test]
+	fn fee_surcharge_is_distributed() {
+		new_test_ext().execute_with(|| {
+			const FEE: FlipBalance = 40;
+
+			assert_noop!(
+				Flip::update_pallet_config(
+					RuntimeOrigin::root(),
+					vec![PalletConfigUpdate::SetFeeSurchargeDistribution(
+						crate::FeeSurchargeDistribution {
+							network_fee_pot: Permill::from_percent(60),
+							validators: Permill::from_percent(50),
+						}
+					)]
+					.try_into()
+					.unwrap(),
+				),
+				Error::<Test>::InvalidFeeSurchargeDistribution,
+			);
+			assert_ok!(Flip::update_pallet_config(
+				RuntimeOrigin::root(),
+				vec![PalletConfigUpdate::SetFeeSurchargeDistribution(
+					crate::FeeSurchargeDistribution {
+						network_fee_pot: Permill::from_percent(50),
+						validators: Permill::from_percent(25),
+					}
+				)]
+				.try_into()
+				.unwrap(),
+			));
+			MockEpochInfo::set_authorities(vec![BOB]);
+			FeeScalingRate::<Test>::set(FeeScalingRateConfig::DelayedExponential {
+				threshold: 0,
+				exponent: 1,
+			});
+			crate::CallCounter::<Test>::insert(
+				crate::OpaqueCallIndex::<Test>::from((ALICE, ())),
+				1,
+			);
+
+			let escrow = Some((Flip::try_debit(&ALICE, 3 * FEE).unwrap(), Some(())));
+			FlipTransactionPayment::<Test>::correct_and_deposit_fee(
+				&ALICE,
+				&CALL.get_dispatch_info(),
+				&().into(),
+				FEE,
+				0,
+				escrow,
+			)
+			.expect("Fee correction never fails.");
+
+			// Alice paid double the fee: half the surcharge went to the network fee pot, a quarter
+			// to the validators and the rest was burned along with the base fee.
+			assert_eq!(Flip::total_balance_of(&ALICE), 100 - 2 * FEE);
+			assert_eq!(Reserve::<Test>::get(crate::NETWORK_FEE_POT), FEE / 2);
+			assert_eq!(Flip::total_balance_of(&BOB), 50 + FEE / 4);
+			assert_eq!(FlipIssuance::<Test>::total_issuance(), 1000 - FEE - FEE / 4);
+			assert!(check_balance_integrity());
+		});
+	}
+
+	#[test]
+	fn fee_scaling_multiplier_is_capped() {
+		new_test_ext().execute_with(|| {
+			const FEE: FlipBalance = 1;
+			const MAX_MULTIPLIER: u16 = 50;
+
+			assert_noop!(
+				Flip::update_pallet_config(
+					RuntimeOrigin::root(),
+					vec![PalletConfigUpdate::SetMaxFeeScalingMultiplier(Some(0))]
+						.try_into()
+						.unwrap(),
+				),
+				Error::<Test>::InvalidMaxFeeScalingMultiplier,
+			);
+			assert_ok!(Flip::update_pallet_config(
+				RuntimeOrigin::root(),
+				vec![PalletConfigUpdate::SetMaxFeeScalingMultiplier(Some(MAX_MULTIPLIER))]
+					.try_into()
+					.unwrap(),
+			));
+			FeeScalingRate::<Test>::set(FeeScalingRateConfig::DelayedExponential {
+				threshold: 0,
+				exponent: 3,
+			});
+			// The uncapped multiplier saturates at u16::MAX.
+			crate::CallCounter::<Test>::insert(
+				crate::OpaqueCallIndex::<Test>::from((ALICE, ())),
+				u16::MAX - 1,
+			);
+			assert_eq!(
+				FeeScalingRateConfig::DelayedExponential { threshold: 0, exponent: 3 }
+					.multiplier_at_call_count(u16::MAX),
+				u16::MAX
+			);
+
+			let escrow = Some((Flip::try_debit(&ALICE, 100).unwrap(), Some(())));
+			FlipTransactionPayment::<Test>::correct_and_deposit_fee(
+				&ALICE,
+				&CALL.get_dispatch_info(),
+				&().into(),
+				FEE,
+				0,
+				escrow,
+			)
+			.expect("Fee correction never fails.");
+
+			assert_eq!(Flip::total_balance_of(&ALICE), 100 - FEE * MAX_MULTIPLIER as u128);
+			assert!(System::events().iter().any(|record| record.event ==
+				RuntimeEvent::Flip(crate::Event::FeeScalingMultiplierCapped {
+					who: ALICE,
+					uncapped_multiplier: u16::MAX,
+					multiplier: MAX_MULTIPLIER,
+				})));
+			assert!(check_balance_integrity());
+		});
+	}
 }
 
 #[test]
@@ -682,3 +846,309 @@ fn update_pallet_config() {
 		assert_eq!(FeeScalingRate::<Test>::get(), update_to_fee_scaling_rate);
 	});
 }
+
+#[test]
+fn set_fee_scaling_config() {
+	new_test_ext().execute_with(|| {
+		let rate = FeeScalingRateConfig::DelayedExponential { threshold: 5, exponent: 3 };
+
+		assert_noop!(
+			Flip::set_fee_scaling_config(RuntimeOrigin::signed(ALICE), rate, None),
+			sp_runtime::traits::BadOrigin,
+		);
+
+		// By default, scaling applies to all indexed calls.
+		assert!(Flip::fee_scaling_applies_to_call_index(&()));
+
+		// Restricting to no call classes disables scaling for all calls.
+		assert_ok!(Flip::set_fee_scaling_config(
+			RuntimeOrigin::root(),
+			rate,
+			Some(Default::default()),
+		));
+		assert_eq!(FeeScalingRate::<Test>::get(), rate);
+		assert!(!Flip::fee_scaling_applies_to_call_index(&()));
+
+		assert_ok!(Flip::set_fee_scaling_config(
+			RuntimeOrigin::root(),
+			rate,
+			Some(vec![()].try_into().unwrap()),
+		));
+		assert!(Flip::fee_scaling_applies_to_call_index(&()));
+		System::assert_last_event(RuntimeEvent::Flip(crate::Event::FeeScalingConfigUpdated {
+			rate,
+			call_classes: Some(vec![()].try_into().unwrap()),
+		}));
+	});
+}
+
+#[test]
+fn call_counters_decay_gradually() {
+	use frame_support::traits::Hooks;
+
+	new_test_ext().execute_with(|| {
+		let call_index = crate::OpaqueCallIndex::<Test>::from((ALICE, ()));
+
+		// By default, counters are reset every block.
+		crate::CallCounter::<Test>::insert(&call_index, 10);
+		Flip::on_initialize(1);
+		assert!(!crate::CallCounter::<Test>::contains_key(&call_index));
+
+		assert_ok!(Flip::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetCallCounterDecay(Permill::from_percent(50))]
+				.try_into()
+				.unwrap(),
+		));
+
+		crate::CallCounter::<Test>::insert(&call_index, 10);
+		for (block, expected) in [(2, 5), (3, 2), (4, 1)] {
+			Flip::on_initialize(block);
+			assert_eq!(crate::CallCounter::<Test>::get(&call_index), expected);
+		}
+		Flip::on_initialize(5);
+		assert!(!crate::CallCounter::<Test>::contains_key(&call_index));
+	});
+}
+
+#[test]
+fn fee_scaling_multiplier_reflects_call_count() {
+	new_test_ext().execute_with(|| {
+		FeeScalingRate::<Test>::set(FeeScalingRateConfig::DelayedExponential {
+			threshold: 1,
+			exponent: 2,
+		});
+		assert_eq!(Flip::fee_scaling_multiplier(&ALICE, (), Weight::zero()), (0, 1));
+
+		crate::CallCounter::<Test>::insert(crate::OpaqueCallIndex::<Test>::from((ALICE, ())), 3);
+		assert_eq!(Flip::fee_scaling_multiplier(&ALICE, (), Weight::zero()), (3, 9));
+		assert_eq!(Flip::fee_scaling_multiplier(&BOB, (), Weight::zero()), (0, 1));
+	});
+}
+
+#[test]
+fn fee_scaling_exemptions() {
+	use cf_primitives::AccountRole;
+	use cf_traits::{mocks::account_role_registry::MockAccountRoleRegistry, AccountRoleRegistry};
+
+	new_test_ext().execute_with(|| {
+		FeeScalingRate::<Test>::set(FeeScalingRateConfig::DelayedExponential {
+			threshold: 0,
+			exponent: 1,
+		});
+		crate::CallCounter::<Test>::insert(crate::OpaqueCallIndex::<Test>::from((ALICE, ())), 2);
+		crate::CallCounter::<Test>::insert(crate::OpaqueCallIndex::<Test>::from((BOB, ())), 2);
+		assert_ok!(<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_account_role(
+			&BOB,
+			AccountRole::LiquidityProvider
+		));
+
+		assert_noop!(
+			Flip::set_fee_scaling_exemption(
+				RuntimeOrigin::signed(ALICE),
+				crate::FeeScalingExemption::Account(ALICE),
+				true
+			),
+			sp_runtime::traits::BadOrigin,
+		);
+		assert_eq!(Flip::fee_scaling_multiplier(&ALICE, (), Weight::zero()), (2, 3));
+		assert_eq!(Flip::fee_scaling_multiplier(&BOB, (), Weight::zero()), (2, 3));
+
+		assert_ok!(Flip::set_fee_scaling_exemption(
+			RuntimeOrigin::root(),
+			crate::FeeScalingExemption::Account(ALICE),
+			true
+		));
+		assert_ok!(Flip::set_fee_scaling_exemption(
+			RuntimeOrigin::root(),
+			crate::FeeScalingExemption::Role(AccountRole::LiquidityProvider),
+			true
+		));
+		assert_eq!(Flip::fee_scaling_multiplier(&ALICE, (), Weight::zero()), (2, 1));
+		assert_eq!(Flip::fee_scaling_multiplier(&BOB, (), Weight::zero()), (2, 1));
+
+		assert_ok!(Flip::set_fee_scaling_exemption(
+			RuntimeOrigin::root(),
+			crate::FeeScalingExemption::Role(AccountRole::LiquidityProvider),
+			false
+		));
+		assert!(Flip::is_exempt_from_fee_scaling(&ALICE));
+		assert!(!Flip::is_exempt_from_fee_scaling(&BOB));
+	});
+}
+
+#[test]
+fn call_counts_can_be_weight_proportional() {
+	new_test_ext().execute_with(|| {
+		let heavy_call = Weight::from_parts(250, 0);
+		assert_eq!(Flip::call_count_increment(heavy_call), 1);
+
+		assert_ok!(Flip::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetFeeScalingWeightUnit(Some(100))].try_into().unwrap(),
+		));
+		assert_eq!(Flip::call_count_increment(Weight::zero()), 1);
+		assert_eq!(Flip::call_count_increment(Weight::from_parts(100, 0)), 1);
+		assert_eq!(Flip::call_count_increment(heavy_call), 3);
+
+		FeeScalingRate::<Test>::set(FeeScalingRateConfig::DelayedExponential {
+			threshold: 0,
+			exponent: 1,
+		});
+		assert_eq!(Flip::fee_scaling_multiplier(&ALICE, (), heavy_call), (0, 3));
+	});
+}
+
+#[test]
+fn slashing_insurance_reimburses_approved_claims() {
+	use cf_primitives::AccountRole;
+	use cf_traits::{mocks::account_role_registry::MockAccountRoleRegistry, AccountRoleRegistry};
+	use frame_support::traits::Hooks;
+
+	new_test_ext().execute_with(|| {
+		for account_id in [ALICE, BOB] {
+			assert_ok!(
+				<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_account_role(
+					&account_id,
+					AccountRole::Validator
+				)
+			);
+			Bonder::<Test>::update_bond(&account_id, 50);
+			assert_ok!(Flip::set_slashing_insurance(RuntimeOrigin::signed(account_id), true));
+		}
+		assert_noop!(
+			Flip::set_slashing_insurance(RuntimeOrigin::signed(ALICE), true),
+			Error::<Test>::AlreadyInsured
+		);
+		assert_ok!(Flip::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetSlashingInsuranceParameters(SlashingInsuranceParameters {
+				daily_premium: Permill::from_percent(10),
+				reimbursement: Permill::from_percent(50),
+				max_payout: 4,
+				min_cover_days: 0,
+				..Default::default()
+			})]
+			.try_into()
+			.unwrap(),
+		));
+
+		// Premiums are charged once a day. Bob has no liquid funds, so his cover lapses.
+		Flip::on_initialize(BlocksPerDay::get() - 1);
+		assert_eq!(Reserve::<Test>::get(SLASHING_INSURANCE_POT), 0);
+		Flip::on_initialize(BlocksPerDay::get());
+		assert_eq!(Reserve::<Test>::get(SLASHING_INSURANCE_POT), 5);
+		assert_eq!(Flip::total_balance_of(&ALICE), 95);
+		assert_eq!(InsurancePolicies::<Test>::get(ALICE).unwrap().premiums_paid, 5);
+		assert!(!InsurancePolicies::<Test>::contains_key(BOB));
+		System::assert_has_event(RuntimeEvent::Flip(Event::InsurancePolicyLapsed { who: BOB }));
+
+		// Only slashes of insured validators are recorded as claims.
+		FlipSlasher::<Test>::slash_balance(&BOB, 10);
+		FlipSlasher::<Test>::slash_balance(&ALICE, 20);
+		assert_eq!(NextInsuranceClaimId::<Test>::get(), 1);
+		System::assert_last_event(RuntimeEvent::Flip(Event::InsuranceClaimRecorded {
+			claim_id: 0,
+			who: ALICE,
+			slashed: 20,
+		}));
+
+		// Only governance can settle claims, and the payout is capped.
+		assert_noop!(
+			Flip::settle_insurance_claim(RuntimeOrigin::signed(ALICE), 0, true),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(Flip::settle_insurance_claim(RuntimeOrigin::root(), 0, true));
+		assert_eq!(Flip::total_balance_of(&ALICE), 79);
+		assert_eq!(Reserve::<Test>::get(SLASHING_INSURANCE_POT), 1);
+		assert_eq!(InsurancePolicies::<Test>::get(ALICE).unwrap().payouts_received, 4);
+		assert_noop!(
+			Flip::settle_insurance_claim(RuntimeOrigin::root(), 0, true),
+			Error::<Test>::UnknownInsuranceClaim
+		);
+
+		// Rejected claims are not reimbursed.
+		FlipSlasher::<Test>::slash_balance(&ALICE, 20);
+		assert_ok!(Flip::settle_insurance_claim(RuntimeOrigin::root(), 1, false));
+		assert_eq!(Flip::total_balance_of(&ALICE), 59);
+		System::assert_last_event(RuntimeEvent::Flip(Event::InsuranceClaimSettled {
+			claim_id: 1,
+			who: ALICE,
+			approved: false,
+			payout: 0,
+		}));
+
+		assert_ok!(Flip::set_slashing_insurance(RuntimeOrigin::signed(ALICE), false));
+		assert_noop!(
+			Flip::set_slashing_insurance(RuntimeOrigin::signed(ALICE), false),
+			Error::<Test>::NotInsured
+		);
+		assert!(check_balance_integrity());
+	});
+}
+
+#[test]
+fn slashing_insurance_premiums_cover_periods_and_claim_expiry() {
+	use cf_primitives::AccountRole;
+	use cf_traits::{mocks::account_role_registry::MockAccountRoleRegistry, AccountRoleRegistry};
+	use frame_support::traits::Hooks;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_account_role(
+			&ALICE,
+			AccountRole::Validator
+		));
+		Bonder::<Test>::update_bond(&ALICE, 50);
+		assert_ok!(Flip::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetSlashingInsuranceParameters(SlashingInsuranceParameters {
+				daily_premium: Permill::from_percent(10),
+				reimbursement: Permill::from_percent(50),
+				max_payout: 4,
+				min_cover_days: 1,
+				claim_expiry_days: 2,
+			})]
+			.try_into()
+			.unwrap(),
+		));
+
+		// The first premium is charged when opting in.
+		assert_ok!(Flip::set_slashing_insurance(RuntimeOrigin::signed(ALICE), true));
+		assert_eq!(Reserve::<Test>::get(SLASHING_INSURANCE_POT), 5);
+		assert_eq!(InsurancePolicies::<Test>::get(ALICE).unwrap().premiums_paid, 5);
+
+		// Slashes within the minimum cover period are not covered.
+		FlipSlasher::<Test>::slash_balance(&ALICE, 10);
+		assert_eq!(NextInsuranceClaimId::<Test>::get(), 0);
+
+		let insured_since = System::block_number();
+		System::set_block_number(insured_since + BlocksPerDay::get());
+		FlipSlasher::<Test>::slash_balance(&ALICE, 10);
+		assert_eq!(NextInsuranceClaimId::<Test>::get(), 1);
+
+		// Claims that aren't adjudicated in time expire.
+		let slashed_at = System::block_number();
+		Flip::on_initialize(slashed_at + 2 * BlocksPerDay::get() - 1);
+		assert!(InsuranceClaims::<Test>::contains_key(0));
+		Flip::on_initialize(slashed_at + 2 * BlocksPerDay::get());
+		assert!(!InsuranceClaims::<Test>::contains_key(0));
+		assert_eq!(NextInsuranceClaimToExpire::<Test>::get(), 1);
+		System::assert_has_event(RuntimeEvent::Flip(Event::InsuranceClaimExpired {
+			claim_id: 0,
+			who: ALICE,
+		}));
+		assert_noop!(
+			Flip::settle_insurance_claim(RuntimeOrigin::root(), 0, true),
+			Error::<Test>::UnknownInsuranceClaim
+		);
+
+		// Validators that can't pay the first premium can't opt in.
+		assert_ok!(Flip::set_slashing_insurance(RuntimeOrigin::signed(ALICE), false));
+		Bonder::<Test>::update_bond(&ALICE, Flip::total_balance_of(&ALICE));
+		assert_noop!(
+			Flip::set_slashing_insurance(RuntimeOrigin::signed(ALICE), true),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert!(check_balance_integrity());
+	});
+}
@@ -22,9 +22,10 @@ use cf_traits::Chainflip;
 use frame_benchmarking::v2::*;
 use frame_support::{
 	assert_ok,
-	traits::{EnsureOrigin, UnfilteredDispatchable},
+	traits::{EnsureOrigin, Hooks, UnfilteredDispatchable},
 };
 use frame_system::RawOrigin;
+use sp_std::vec;
 
 fn fund_with_minimum<T: Config>(account_id: &T::AccountId) {
 	assert_ok!(Call::<T>::funded {
@@ -181,6 +182,49 @@ mod benchmarks {
 		}
 	}
 
+	#[benchmark]
+	fn propose_restricted_address_migration() {
+		let from = EthereumAddress::repeat_byte(0x01);
+		let to = EthereumAddress::repeat_byte(0x02);
+		RestrictedAddresses::<T>::insert(from, ());
+		let call = Call::<T>::propose_restricted_address_migration { from, to };
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert_eq!(PendingRestrictedAddressMigrations::<T>::get(from), Some(to));
+	}
+
+	#[benchmark]
+	fn confirm_restricted_address_migration(c: Linear<1, 100>) {
+		let from = EthereumAddress::repeat_byte(0x01);
+		let to = EthereumAddress::repeat_byte(0x02);
+		RestrictedAddresses::<T>::insert(from, ());
+		PendingRestrictedAddressMigrations::<T>::insert(from, to);
+		for i in 0..c {
+			let some_account: AccountId<T> = account("doogle", 0, i);
+			RestrictedBalances::<T>::insert(
+				some_account,
+				BTreeMap::from([(from, FlipBalance::<T>::from(100_u32))]),
+			);
+		}
+		let call =
+			Call::<T>::confirm_restricted_address_migration { from, to, restricted_accounts: c };
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert!(RestrictedAddresses::<T>::contains_key(to));
+	}
+
 	#[benchmark]
 	fn bind_executor_address() {
 		let caller: T::AccountId = whitelisted_caller();
@@ -191,5 +235,129 @@ mod benchmarks {
 		assert!(BoundExecutorAddress::<T>::contains_key(&caller));
 	}
 
+	#[benchmark]
+	fn schedule_redemptions(n: Linear<1, MAX_SCHEDULED_REDEMPTIONS>) {
+		let caller: T::AccountId = whitelisted_caller();
+		for _ in 0..n {
+			fund_with_minimum::<T>(&caller);
+		}
+		let redemptions = (1..=n)
+			.map(|i| (FlipBalance::<T>::from(1_u32), (i + 1).into()))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+
+		#[extrinsic_call]
+		schedule_redemptions(
+			RawOrigin::Signed(caller.clone()),
+			redemptions,
+			Default::default(),
+			Default::default(),
+		);
+
+		assert!(ScheduledRedemptions::<T>::contains_key(BlockNumberFor::<T>::from(2_u32), &caller));
+	}
+
+	#[benchmark]
+	fn execute_scheduled_redemptions(n: Linear<0, 100>) {
+		let execute_at = BlockNumberFor::<T>::from(10_u32);
+		let accounts =
+			(0..n).map(|i| account::<T::AccountId>("redeemer", i, 0)).collect::<Vec<_>>();
+		for account_id in &accounts {
+			// Fund enough that the remaining balance stays above the minimum after redeeming.
+			for _ in 0..3 {
+				fund_with_minimum::<T>(account_id);
+			}
+			ScheduledRedemptions::<T>::insert(
+				execute_at,
+				account_id,
+				BoundedVec::truncate_from(vec![ScheduledRedemption {
+					amount: MinimumFunding::<T>::get(),
+					address: Default::default(),
+					executor: Default::default(),
+				}]),
+			);
+		}
+
+		#[block]
+		{
+			Pallet::<T>::on_initialize(execute_at);
+		}
+
+		for account_id in &accounts {
+			assert!(PendingRedemptions::<T>::contains_key(account_id));
+		}
+	}
+
+	#[benchmark]
+	fn request_redeem_address_update() {
+		let caller: T::AccountId = whitelisted_caller();
+		BoundRedeemAddress::<T>::insert(&caller, EthereumAddress::zero());
+
+		#[extrinsic_call]
+		request_redeem_address_update(RawOrigin::Signed(caller.clone()), Default::default());
+
+		assert!(PendingRedeemAddressUpdates::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn update_redeem_address_update_delay() {
+		let delay: BlockNumberFor<T> = MIN_REDEEM_ADDRESS_UPDATE_DELAY.into();
+		let call = Call::<T>::update_redeem_address_update_delay { delay };
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert_eq!(RedeemAddressUpdateDelay::<T>::get(), delay);
+	}
+
+	#[benchmark]
+	fn cancel_redeem_address_update() {
+		let account_id: T::AccountId = account("binder", 0, 0);
+		PendingRedeemAddressUpdates::<T>::insert(
+			&account_id,
+			(EthereumAddress::zero(), BlockNumberFor::<T>::from(10_u32)),
+		);
+		let call = Call::<T>::cancel_redeem_address_update { account_id: account_id.clone() };
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert!(!PendingRedeemAddressUpdates::<T>::contains_key(&account_id));
+	}
+
+	#[benchmark]
+	fn apply_redeem_address_updates(n: Linear<0, 100>) {
+		let effective_at = BlockNumberFor::<T>::from(10_u32);
+		let accounts = (0..n).map(|i| account::<T::AccountId>("binder", i, 0)).collect::<Vec<_>>();
+		for account_id in &accounts {
+			PendingRedeemAddressUpdates::<T>::insert(
+				account_id,
+				(EthereumAddress::repeat_byte(1), effective_at),
+			);
+		}
+		RedeemAddressUpdatesDue::<T>::insert(effective_at, accounts.clone());
+
+		#[block]
+		{
+			Pallet::<T>::on_initialize(effective_at);
+		}
+
+		for account_id in &accounts {
+			assert_eq!(
+				BoundRedeemAddress::<T>::get(account_id),
+				Some(EthereumAddress::repeat_byte(1))
+			);
+		}
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
@@ -31,9 +31,11 @@ pub use weights::WeightInfo;
 mod tests;
 
 use cf_chains::{eth::Address as EthereumAddress, RegisterRedemption};
+use cf_primitives::EgressId;
+use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
 	impl_pallet_safe_mode, AccountInfo, AccountRoleRegistry, Broadcaster, Chainflip, FeePayment,
-	Funding,
+	Funding, OnEgressSuccess, RedemptionCheck,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -43,29 +45,61 @@ use frame_support::{
 		traits::{CheckedSub, One, UniqueSaturatedInto, Zero},
 		Saturating,
 	},
-	traits::{EnsureOrigin, HandleLifetime, IsType, OnKilledAccount, StorageVersion, UnixTime},
+	traits::{
+		ConstU32, EnsureOrigin, Get, HandleLifetime, IsType, OnKilledAccount, StorageVersion,
+		UnixTime,
+	},
+	BoundedVec,
 };
-use frame_system::pallet_prelude::OriginFor;
+use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
 pub use pallet::*;
 use scale_info::TypeInfo;
 use sp_std::{
 	cmp::{max, min},
-	collections::btree_map::BTreeMap,
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 	prelude::*,
 };
 #[derive(Encode, Decode, PartialEq, Debug, TypeInfo)]
 pub enum Pending {
 	Pending,
 }
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(4);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(6);
 
 #[derive(Encode, Decode, PartialEq, Debug, TypeInfo)]
 pub struct PendingRedemptionInfo<FlipBalance> {
 	pub total: FlipBalance,
 	pub restricted: FlipBalance,
 	pub redeem_address: EthereumAddress,
+	/// The unix time (in seconds) after which the redemption can no longer be executed.
+	pub expiry_time: u64,
+}
+
+/// The maximum number of redemptions an account can have scheduled at any time.
+pub const MAX_SCHEDULED_REDEMPTIONS: u32 = 24;
+
+/// The number of blocks after which a scheduled redemption is retried if redeeming is not
+/// currently possible: one hour of 6 second blocks.
+pub const SCHEDULED_REDEMPTION_RETRY_INTERVAL: u32 = 600;
+
+/// The shortest delay, in blocks, that governance can set before bound redeem address updates
+/// take effect: one day of 6 second blocks.
+pub const MIN_REDEEM_ADDRESS_UPDATE_DELAY: u32 = 14_400;
+
+/// The delay, in blocks, before bound redeem address updates take effect unless governance sets
+/// another one: seven days of 6 second blocks.
+pub const DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY: u32 = 7 * MIN_REDEEM_ADDRESS_UPDATE_DELAY;
+
+#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo)]
+pub struct ScheduledRedemption<FlipBalance> {
+	pub amount: FlipBalance,
+	pub address: EthereumAddress,
+	pub executor: Option<EthereumAddress>,
 }
 
+/// Redemptions scheduled by a single account, bounded by [MAX_SCHEDULED_REDEMPTIONS].
+pub type ScheduledRedemptionsFor<FlipBalance> =
+	BoundedVec<ScheduledRedemption<FlipBalance>, ConstU32<MAX_SCHEDULED_REDEMPTIONS>>;
+
 impl_pallet_safe_mode!(PalletSafeMode; redeem_enabled);
 
 #[frame_support::pallet]
@@ -73,7 +107,6 @@ pub mod pallet {
 	use super::*;
 	use cf_chains::eth::Ethereum;
 	use cf_primitives::BroadcastId;
-	use cf_traits::RedemptionCheck;
 	use frame_support::{pallet_prelude::*, Parameter};
 	use frame_system::pallet_prelude::*;
 
@@ -191,6 +224,66 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type RedemptionTax<T: Config> = StorageValue<_, T::Amount, ValueQuery>;
 
+	/// Redemptions scheduled for execution at a given block, by account.
+	#[pallet::storage]
+	pub type ScheduledRedemptions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		AccountId<T>,
+		ScheduledRedemptionsFor<FlipBalance<T>>,
+		ValueQuery,
+	>;
+
+	/// The number of redemptions each account has scheduled and not yet executed.
+	#[pallet::storage]
+	pub type ScheduledRedemptionCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountId<T>, u32, ValueQuery>;
+
+	/// Scheduled redemptions that fell due while another redemption was pending. They are
+	/// rescheduled once the pending redemption has been settled or has expired.
+	#[pallet::storage]
+	pub type DeferredRedemptions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountId<T>,
+		ScheduledRedemptionsFor<FlipBalance<T>>,
+		ValueQuery,
+	>;
+
+	/// The number of blocks before an update to an account's bound redeem address takes effect.
+	#[pallet::storage]
+	pub type RedeemAddressUpdateDelay<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// Requested updates to bound redeem addresses, with the block at which they take effect.
+	/// Redemptions are suspended for the account until then.
+	#[pallet::storage]
+	pub type PendingRedeemAddressUpdates<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountId<T>,
+		(EthereumAddress, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
+	/// Accounts whose bound redeem address update is due at a given block.
+	#[pallet::storage]
+	pub type RedeemAddressUpdatesDue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<AccountId<T>>, ValueQuery>;
+
+	/// FLIP bought by swaps that is on its way to the State Chain Gateway, by the id of its
+	/// egress. It is added to the account's funds once the transfer has been witnessed.
+	#[pallet::storage]
+	pub type PendingSwapFunding<T: Config> =
+		StorageMap<_, Twox64Concat, EgressId, (AccountId<T>, FlipBalance<T>), OptionQuery>;
+
+	/// Proposed migrations of restricted balances from one restricted address to another,
+	/// awaiting confirmation.
+	#[pallet::storage]
+	pub type PendingRestrictedAddressMigrations<T: Config> =
+		StorageMap<_, Blake2_128Concat, EthereumAddress, EthereumAddress, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -246,6 +339,50 @@ pub mod pallet {
 
 		/// An account has been bound to an executor address.
 		BoundExecutorAddress { account_id: AccountId<T>, address: EthereumAddress },
+
+		/// An account has scheduled a series of redemptions.
+		RedemptionsScheduled {
+			account_id: AccountId<T>,
+			address: EthereumAddress,
+			redemptions: Vec<(FlipBalance<T>, BlockNumberFor<T>)>,
+		},
+
+		/// An update to an account's bound redeem address has been requested.
+		BoundRedeemAddressUpdateRequested {
+			account_id: AccountId<T>,
+			address: EthereumAddress,
+			effective_at: BlockNumberFor<T>,
+		},
+
+		/// The delay before bound redeem address updates take effect has been updated.
+		RedeemAddressUpdateDelayUpdated { delay: BlockNumberFor<T> },
+
+		/// A pending update to an account's bound redeem address has been cancelled.
+		BoundRedeemAddressUpdateCancelled { account_id: AccountId<T>, address: EthereumAddress },
+
+		/// A scheduled redemption could not be executed and has been dropped.
+		ScheduledRedemptionFailed {
+			account_id: AccountId<T>,
+			amount: FlipBalance<T>,
+			error: DispatchError,
+		},
+
+		/// A migration of restricted balances to a new address has been proposed.
+		RestrictedAddressMigrationProposed { from: EthereumAddress, to: EthereumAddress },
+
+		/// Restricted balances have been migrated to a new address.
+		RestrictedAddressMigrated {
+			from: EthereumAddress,
+			to: EthereumAddress,
+			accounts_migrated: u32,
+		},
+
+		/// An account has been funded with FLIP bought by a swap.
+		FundedFromSwap {
+			account_id: AccountId<T>,
+			funds_added: FlipBalance<T>,
+			total_balance: FlipBalance<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -291,6 +428,52 @@ pub mod pallet {
 
 		/// The account cannot be reaped before it is unregistered.
 		AccountMustBeUnregistered,
+
+		/// Scheduled redemptions must be non-zero and at distinct future blocks.
+		InvalidRedemptionSchedule,
+
+		/// Accounts can have no more than [MAX_SCHEDULED_REDEMPTIONS] redemptions scheduled.
+		TooManyScheduledRedemptions,
+
+		/// The account has no bound redeem address to update.
+		AccountNotBound,
+
+		/// Redemptions are suspended until the pending bound redeem address update takes effect.
+		RedeemAddressUpdatePending,
+
+		/// The delay before bound redeem address updates take effect must be at least
+		/// [MIN_REDEEM_ADDRESS_UPDATE_DELAY] blocks.
+		RedeemAddressUpdateDelayTooShort,
+
+		/// The account has no pending bound redeem address update.
+		NoPendingRedeemAddressUpdate,
+
+		/// Restricted balances can only be migrated from a restricted address to a different one.
+		InvalidRestrictedAddressMigration,
+
+		/// No matching restricted address migration has been proposed.
+		NoPendingRestrictedAddressMigration,
+
+		/// More accounts have restricted balances than the given number.
+		TooManyRestrictedAccounts,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
+			let updated_accounts = RedeemAddressUpdatesDue::<T>::take(current_block);
+			for account_id in &updated_accounts {
+				Self::apply_redeem_address_update(current_block, account_id);
+			}
+
+			let mut processed = 0u32;
+			for (account_id, scheduled) in ScheduledRedemptions::<T>::drain_prefix(current_block) {
+				processed.saturating_accrue(scheduled.len() as u32);
+				Self::execute_scheduled_redemptions(current_block, account_id, scheduled);
+			}
+			T::WeightInfo::apply_redeem_address_updates(updated_accounts.len() as u32)
+				.saturating_add(T::WeightInfo::execute_scheduled_redemptions(processed))
+		}
 	}
 
 	#[pallet::call]
@@ -352,157 +535,12 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			amount: RedemptionAmount<FlipBalance<T>>,
 			address: EthereumAddress,
-			// Only this address can execute the redemption.
+			// Only this address can execute the redemption. Defaults to the bound executor
+			// address.
 			executor: Option<EthereumAddress>,
 		) -> DispatchResult {
 			let account_id = ensure_signed(origin)?;
-
-			ensure!(T::SafeMode::get().redeem_enabled, Error::<T>::RedeemDisabled);
-
-			// Not allowed to redeem if we are an active bidder in the auction phase
-			T::RedemptionChecker::ensure_can_redeem(&account_id)?;
-
-			// The redemption must be executed before a new one can be requested.
-			ensure!(
-				!PendingRedemptions::<T>::contains_key(&account_id),
-				Error::<T>::PendingRedemption
-			);
-
-			let mut restricted_balances = RestrictedBalances::<T>::get(&account_id);
-
-			// Ignore executor binding restrictions for withdrawals of restricted funds.
-			if !restricted_balances.contains_key(&address) {
-				if let Some(bound_executor) = BoundExecutorAddress::<T>::get(&account_id) {
-					ensure!(
-						executor == Some(bound_executor),
-						Error::<T>::ExecutorBindingRestrictionViolated
-					);
-				}
-			}
-
-			if let Some(bound_address) = BoundRedeemAddress::<T>::get(&account_id) {
-				ensure!(
-					bound_address == address || restricted_balances.contains_key(&address),
-					Error::<T>::AccountBindingRestrictionViolated
-				);
-			}
-
-			// In case the balance is lower than the sum of restricted addresses we take this
-			// discrepancy into account so that restricted addresses can still redeem.
-			let restricted_deficit: FlipBalance<T> = restricted_balances
-				.values()
-				.copied()
-				.sum::<FlipBalance<T>>()
-				.saturating_sub(T::Flip::balance(&account_id));
-
-			// The available funds are the total balance minus whichever is larger from:
-			// - The bond.
-			// - The total restricted funds that need to remain in the account after the redemption.
-			let liquid_balance = T::Flip::balance(&account_id).saturating_sub(max(
-				T::Flip::bond(&account_id),
-				restricted_balances.values().copied().sum::<FlipBalance<T>>().saturating_sub(
-					restricted_deficit +
-						restricted_balances.get(&address).copied().unwrap_or_default(),
-				),
-			));
-
-			let redemption_fee = match amount {
-				RedemptionAmount::Max if liquid_balance == T::Flip::balance(&account_id) =>
-					Zero::zero(),
-				_ => RedemptionTax::<T>::get(),
-			};
-
-			let (debit_amount, redeem_amount) = match amount {
-				RedemptionAmount::Max =>
-					(liquid_balance, liquid_balance.saturating_sub(redemption_fee)),
-				RedemptionAmount::Exact(amount) => (amount.saturating_add(redemption_fee), amount),
-			};
-
-			ensure!(
-				T::Flip::try_burn_fee(&account_id, redemption_fee).is_ok(),
-				Error::<T>::InsufficientBalance
-			);
-
-			let mut total_restricted_balance: FlipBalance<T> = T::Amount::zero();
-
-			// If necessary, update account restrictions.
-			if let Some(restricted_balance) = restricted_balances.get_mut(&address) {
-				// Use the full debit amount here - fees are paid by restricted funds by default.
-				total_restricted_balance = *restricted_balance;
-				restricted_balance.saturating_reduce(debit_amount);
-				// ensure that the remaining restricted balance is zero or above MinimumFunding
-				ensure!(
-					restricted_balance.is_zero() ||
-						*restricted_balance >= MinimumFunding::<T>::get(),
-					Error::<T>::RestrictedBalanceBelowMinimumFunding
-				);
-
-				if restricted_balance.is_zero() {
-					restricted_balances.remove(&address);
-				}
-				RestrictedBalances::<T>::insert(&account_id, &restricted_balances);
-			}
-
-			let remaining_balance = T::Flip::balance(&account_id)
-				.checked_sub(&redeem_amount)
-				.ok_or(Error::<T>::InsufficientBalance)?;
-
-			ensure!(
-				remaining_balance == Zero::zero() ||
-					remaining_balance >= MinimumFunding::<T>::get(),
-				Error::<T>::BelowMinimumFunding
-			);
-			ensure!(
-				remaining_balance >=
-					restricted_balances.values().copied().sum::<FlipBalance<T>>() -
-						restricted_deficit,
-				Error::<T>::InsufficientUnrestrictedFunds
-			);
-
-			// Update the account balance.
-			if redeem_amount > Zero::zero() {
-				T::Flip::try_initiate_redemption(&account_id, redeem_amount)?;
-				if T::Flip::balance(&account_id).is_zero() {
-					ensure!(
-						T::AccountRoleRegistry::is_unregistered(&account_id),
-						Error::<T>::AccountMustBeUnregistered
-					);
-				}
-
-				// Send the transaction.
-				let contract_expiry =
-					T::TimeSource::now().as_secs() + RedemptionTTLSeconds::<T>::get();
-				let call = T::RegisterRedemption::new_unsigned(
-					<T as Config>::FunderId::from_ref(&account_id).as_ref(),
-					redeem_amount.unique_saturated_into(),
-					address.as_fixed_bytes(),
-					contract_expiry,
-					executor,
-				);
-
-				PendingRedemptions::<T>::insert(
-					&account_id,
-					PendingRedemptionInfo {
-						total: redeem_amount,
-						restricted: min(
-							total_restricted_balance.saturating_sub(redemption_fee),
-							redeem_amount,
-						),
-						redeem_address: address,
-					},
-				);
-
-				Self::deposit_event(Event::RedemptionRequested {
-					account_id,
-					amount: redeem_amount,
-					broadcast_id: T::Broadcaster::threshold_sign_and_broadcast(call).0,
-					expiry_time: contract_expiry,
-				});
-			} else {
-				Self::deposit_event(Event::RedemptionAmountZero { account_id })
-			}
-
-			Ok(())
+			Self::try_redeem(account_id, amount, address, executor)
 		}
 
 		/// **This call can only be dispatched from the configured witness origin.**
@@ -523,7 +561,9 @@ pub mod pallet {
 		/// - [NoPendingRedemption](Error::NoPendingRedemption)
 		/// - [BadOrigin](frame_support::error::BadOrigin)
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::redeemed())]
+		#[pallet::weight(
+			T::WeightInfo::redeemed().saturating_add(T::DbWeight::get().reads_writes(2, 2))
+		)]
 		pub fn redeemed(
 			origin: OriginFor<T>,
 			account_id: AccountId<T>,
@@ -549,6 +589,8 @@ pub mod pallet {
 						e
 					);
 				})
+			} else {
+				Self::reschedule_deferred_redemptions(&account_id);
 			}
 
 			Self::deposit_event(Event::RedemptionSettled(account_id, redeemed_amount));
@@ -557,7 +599,9 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::redemption_expired())]
+		#[pallet::weight(
+			T::WeightInfo::redemption_expired().saturating_add(T::DbWeight::get().reads_writes(2, 2))
+		)]
 		pub fn redemption_expired(
 			origin: OriginFor<T>,
 			account_id: AccountId<T>,
@@ -584,6 +628,8 @@ pub mod pallet {
 				});
 			}
 
+			Self::reschedule_deferred_redemptions(&account_id);
+
 			Self::deposit_event(Event::<T>::RedemptionExpired { account_id });
 
 			Ok(())
@@ -691,7 +737,8 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Binds executor address to an account.
+		/// Binds an executor address to an account. Redemptions that don't specify their own
+		/// executor address are executed by this address.
 		///
 		/// ## Events
 		///
@@ -719,6 +766,273 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Schedules a series of partial redemptions, each to be executed automatically at the
+		/// given block. Redemptions are subject to the same rules as [redeem](Call::redeem) at
+		/// the time they are executed, so restricted and bound address rules are respected.
+		///
+		/// Redemptions that fall due while a previous redemption is still pending are deferred
+		/// until it has been settled or has expired, and are then batched together. If redeeming
+		/// is not possible for any other reason, they are retried after
+		/// [SCHEDULED_REDEMPTION_RETRY_INTERVAL] blocks.
+		///
+		/// An account can have no more than [MAX_SCHEDULED_REDEMPTIONS] redemptions scheduled.
+		///
+		/// ## Events
+		///
+		/// - [RedemptionsScheduled](Event::RedemptionsScheduled)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidRedemptionSchedule](Error::InvalidRedemptionSchedule)
+		/// - [TooManyScheduledRedemptions](Error::TooManyScheduledRedemptions)
+		/// - [InsufficientBalance](Error::InsufficientBalance)
+		/// - [AccountBindingRestrictionViolated](Error::AccountBindingRestrictionViolated)
+		/// - [ExecutorBindingRestrictionViolated](Error::ExecutorBindingRestrictionViolated)
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::schedule_redemptions(redemptions.len() as u32))]
+		pub fn schedule_redemptions(
+			origin: OriginFor<T>,
+			redemptions: BoundedVec<
+				(FlipBalance<T>, BlockNumberFor<T>),
+				ConstU32<MAX_SCHEDULED_REDEMPTIONS>,
+			>,
+			address: EthereumAddress,
+			executor: Option<EthereumAddress>,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				!redemptions.is_empty() &&
+					redemptions.iter().all(|(amount, execute_at)| {
+						!amount.is_zero() && *execute_at > current_block
+					}) && redemptions
+					.iter()
+					.map(|(_, execute_at)| execute_at)
+					.collect::<BTreeSet<_>>()
+					.len() == redemptions.len(),
+				Error::<T>::InvalidRedemptionSchedule
+			);
+			let scheduled_count = ScheduledRedemptionCount::<T>::get(&account_id)
+				.saturating_add(redemptions.len() as u32);
+			ensure!(
+				scheduled_count <= MAX_SCHEDULED_REDEMPTIONS,
+				Error::<T>::TooManyScheduledRedemptions
+			);
+			ensure!(
+				redemptions.iter().map(|(amount, _)| *amount).sum::<FlipBalance<T>>() <=
+					T::Flip::balance(&account_id),
+				Error::<T>::InsufficientBalance
+			);
+			let executor = executor.or_else(|| BoundExecutorAddress::<T>::get(&account_id));
+			Self::ensure_redemption_address_allowed(
+				&account_id,
+				&RestrictedBalances::<T>::get(&account_id),
+				address,
+				executor,
+			)?;
+
+			ScheduledRedemptionCount::<T>::insert(&account_id, scheduled_count);
+			for (amount, execute_at) in &redemptions {
+				Self::reschedule_redemptions(
+					*execute_at,
+					&account_id,
+					[ScheduledRedemption { amount: *amount, address, executor }],
+				);
+			}
+
+			Self::deposit_event(Event::RedemptionsScheduled {
+				account_id,
+				address,
+				redemptions: redemptions.into_inner(),
+			});
+			Ok(())
+		}
+
+		/// Requests an update of the account's bound redeem address. The new address takes effect
+		/// after [RedeemAddressUpdateDelay] blocks, during which redemptions are suspended and
+		/// governance can cancel the update with
+		/// [cancel_redeem_address_update](Call::cancel_redeem_address_update). Requesting another
+		/// update restarts the delay.
+		///
+		/// ## Events
+		///
+		/// - [BoundRedeemAddressUpdateRequested](Event::BoundRedeemAddressUpdateRequested)
+		///
+		/// ## Errors
+		///
+		/// - [AccountNotBound](Error::AccountNotBound)
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::request_redeem_address_update())]
+		pub fn request_redeem_address_update(
+			origin: OriginFor<T>,
+			address: EthereumAddress,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			ensure!(
+				BoundRedeemAddress::<T>::contains_key(&account_id),
+				Error::<T>::AccountNotBound
+			);
+
+			// The update always takes effect in a future block, when the hook next runs.
+			let effective_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(max(RedeemAddressUpdateDelay::<T>::get(), One::one()));
+			PendingRedeemAddressUpdates::<T>::insert(&account_id, (address, effective_at));
+			RedeemAddressUpdatesDue::<T>::append(effective_at, &account_id);
+
+			Self::deposit_event(Event::BoundRedeemAddressUpdateRequested {
+				account_id,
+				address,
+				effective_at,
+			});
+			Ok(())
+		}
+
+		/// Sets the delay before updates to bound redeem addresses take effect.
+		///
+		/// Requires Governance
+		///
+		/// ## Events
+		///
+		/// - [RedeemAddressUpdateDelayUpdated](Event::RedeemAddressUpdateDelayUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [RedeemAddressUpdateDelayTooShort](Error::RedeemAddressUpdateDelayTooShort)
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::update_redeem_address_update_delay())]
+		pub fn update_redeem_address_update_delay(
+			origin: OriginFor<T>,
+			delay: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			ensure!(
+				delay >= MIN_REDEEM_ADDRESS_UPDATE_DELAY.into(),
+				Error::<T>::RedeemAddressUpdateDelayTooShort
+			);
+			RedeemAddressUpdateDelay::<T>::set(delay);
+			Self::deposit_event(Event::RedeemAddressUpdateDelayUpdated { delay });
+			Ok(())
+		}
+
+		/// Cancels a pending update of an account's bound redeem address, for example if the
+		/// account's key has been compromised. Redemptions to the current bound address are
+		/// possible again immediately.
+		///
+		/// Requires Governance
+		///
+		/// ## Events
+		///
+		/// - [BoundRedeemAddressUpdateCancelled](Event::BoundRedeemAddressUpdateCancelled)
+		///
+		/// ## Errors
+		///
+		/// - [NoPendingRedeemAddressUpdate](Error::NoPendingRedeemAddressUpdate)
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::cancel_redeem_address_update())]
+		pub fn cancel_redeem_address_update(
+			origin: OriginFor<T>,
+			account_id: AccountId<T>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			// The entry in `RedeemAddressUpdatesDue` is left to expire: it is ignored once the
+			// pending update is gone.
+			let (address, _) = PendingRedeemAddressUpdates::<T>::take(&account_id)
+				.ok_or(Error::<T>::NoPendingRedeemAddressUpdate)?;
+			Self::deposit_event(Event::BoundRedeemAddressUpdateCancelled { account_id, address });
+			Ok(())
+		}
+
+		/// Proposes migrating all balances restricted to `from` to the address `to`, for example
+		/// when a vesting contract has been redeployed. The migration only takes effect once
+		/// confirmed with [confirm_restricted_address_migration](
+		/// Call::confirm_restricted_address_migration). Proposing another migration from the
+		/// same address replaces the previous proposal.
+		///
+		/// Requires Governance
+		///
+		/// ## Events
+		///
+		/// - [RestrictedAddressMigrationProposed](Event::RestrictedAddressMigrationProposed)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidRestrictedAddressMigration](Error::InvalidRestrictedAddressMigration)
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::propose_restricted_address_migration())]
+		pub fn propose_restricted_address_migration(
+			origin: OriginFor<T>,
+			from: EthereumAddress,
+			to: EthereumAddress,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			ensure!(
+				from != to && RestrictedAddresses::<T>::contains_key(from),
+				Error::<T>::InvalidRestrictedAddressMigration
+			);
+			PendingRestrictedAddressMigrations::<T>::insert(from, to);
+			Self::deposit_event(Event::RestrictedAddressMigrationProposed { from, to });
+			Ok(())
+		}
+
+		/// Confirms a proposed restricted address migration. `to` becomes a restricted address in
+		/// place of `from`, and every account's balance restricted to `from` is moved to `to`.
+		///
+		/// `restricted_accounts` must be at least the number of accounts with restricted balances,
+		/// all of which are checked, and determines the weight of the call.
+		///
+		/// Requires Governance
+		///
+		/// ## Events
+		///
+		/// - [RestrictedAddressMigrated](Event::RestrictedAddressMigrated)
+		///
+		/// ## Errors
+		///
+		/// - [NoPendingRestrictedAddressMigration](Error::NoPendingRestrictedAddressMigration)
+		/// - [TooManyRestrictedAccounts](Error::TooManyRestrictedAccounts)
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::confirm_restricted_address_migration(*restricted_accounts))]
+		pub fn confirm_restricted_address_migration(
+			origin: OriginFor<T>,
+			from: EthereumAddress,
+			to: EthereumAddress,
+			restricted_accounts: u32,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			ensure!(
+				PendingRestrictedAddressMigrations::<T>::get(from) == Some(to),
+				Error::<T>::NoPendingRestrictedAddressMigration
+			);
+			let account_ids = RestrictedBalances::<T>::iter_keys()
+				.take(restricted_accounts.saturating_add(1) as usize)
+				.collect::<Vec<_>>();
+			ensure!(
+				account_ids.len() <= restricted_accounts as usize,
+				Error::<T>::TooManyRestrictedAccounts
+			);
+			PendingRestrictedAddressMigrations::<T>::remove(from);
+
+			RestrictedAddresses::<T>::remove(from);
+			RestrictedAddresses::<T>::insert(to, ());
+
+			let mut accounts_migrated = 0u32;
+			for account_id in account_ids {
+				RestrictedBalances::<T>::mutate(&account_id, |balances| {
+					if let Some(balance) = balances.remove(&from) {
+						balances.entry(to).or_default().saturating_accrue(balance);
+						accounts_migrated.saturating_inc();
+					}
+				});
+			}
+
+			Self::deposit_event(Event::RestrictedAddressMigrated { from, to, accounts_migrated });
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -727,6 +1041,7 @@ pub mod pallet {
 		pub redemption_tax: T::Amount,
 		pub minimum_funding: T::Amount,
 		pub redemption_ttl: Duration,
+		pub redeem_address_update_delay: BlockNumberFor<T>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
@@ -736,6 +1051,7 @@ pub mod pallet {
 				redemption_tax: Default::default(),
 				minimum_funding: One::one(),
 				redemption_ttl: Default::default(),
+				redeem_address_update_delay: DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY.into(),
 			}
 		}
 	}
@@ -751,6 +1067,7 @@ pub mod pallet {
 			MinimumFunding::<T>::set(self.minimum_funding);
 			RedemptionTax::<T>::set(self.redemption_tax);
 			RedemptionTTLSeconds::<T>::set(self.redemption_ttl.as_secs());
+			RedeemAddressUpdateDelay::<T>::set(self.redeem_address_update_delay);
 			for (account_id, amount) in self.genesis_accounts.iter() {
 				Pallet::<T>::add_funds_to_account(account_id, *amount);
 			}
@@ -759,6 +1076,299 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	fn try_redeem(
+		account_id: AccountId<T>,
+		amount: RedemptionAmount<FlipBalance<T>>,
+		address: EthereumAddress,
+		executor: Option<EthereumAddress>,
+	) -> DispatchResult {
+		ensure!(T::SafeMode::get().redeem_enabled, Error::<T>::RedeemDisabled);
+
+		// Not allowed to redeem if we are an active bidder in the auction phase
+		T::RedemptionChecker::ensure_can_redeem(&account_id)?;
+
+		// The redemption must be executed before a new one can be requested.
+		ensure!(!PendingRedemptions::<T>::contains_key(&account_id), Error::<T>::PendingRedemption);
+
+		ensure!(
+			!PendingRedeemAddressUpdates::<T>::contains_key(&account_id),
+			Error::<T>::RedeemAddressUpdatePending
+		);
+
+		let mut restricted_balances = RestrictedBalances::<T>::get(&account_id);
+
+		// Each redemption may specify its own executor, falling back to the bound one.
+		let executor = executor.or_else(|| BoundExecutorAddress::<T>::get(&account_id));
+		Self::ensure_redemption_address_allowed(
+			&account_id,
+			&restricted_balances,
+			address,
+			executor,
+		)?;
+
+		// In case the balance is lower than the sum of restricted addresses we take this
+		// discrepancy into account so that restricted addresses can still redeem.
+		let restricted_deficit: FlipBalance<T> = restricted_balances
+			.values()
+			.copied()
+			.sum::<FlipBalance<T>>()
+			.saturating_sub(T::Flip::balance(&account_id));
+
+		// The available funds are the total balance minus whichever is larger from:
+		// - The bond.
+		// - The total restricted funds that need to remain in the account after the redemption.
+		let liquid_balance = T::Flip::balance(&account_id).saturating_sub(max(
+			T::Flip::bond(&account_id),
+			restricted_balances.values().copied().sum::<FlipBalance<T>>().saturating_sub(
+				restricted_deficit + restricted_balances.get(&address).copied().unwrap_or_default(),
+			),
+		));
+
+		let redemption_fee = match amount {
+			RedemptionAmount::Max if liquid_balance == T::Flip::balance(&account_id) =>
+				Zero::zero(),
+			_ => RedemptionTax::<T>::get(),
+		};
+
+		let (debit_amount, redeem_amount) = match amount {
+			RedemptionAmount::Max =>
+				(liquid_balance, liquid_balance.saturating_sub(redemption_fee)),
+			RedemptionAmount::Exact(amount) => (amount.saturating_add(redemption_fee), amount),
+		};
+
+		ensure!(
+			T::Flip::try_burn_fee(&account_id, redemption_fee).is_ok(),
+			Error::<T>::InsufficientBalance
+		);
+
+		let mut total_restricted_balance: FlipBalance<T> = T::Amount::zero();
+
+		// If necessary, update account restrictions.
+		if let Some(restricted_balance) = restricted_balances.get_mut(&address) {
+			// Use the full debit amount here - fees are paid by restricted funds by default.
+			total_restricted_balance = *restricted_balance;
+			restricted_balance.saturating_reduce(debit_amount);
+			// ensure that the remaining restricted balance is zero or above MinimumFunding
+			ensure!(
+				restricted_balance.is_zero() || *restricted_balance >= MinimumFunding::<T>::get(),
+				Error::<T>::RestrictedBalanceBelowMinimumFunding
+			);
+
+			if restricted_balance.is_zero() {
+				restricted_balances.remove(&address);
+			}
+			RestrictedBalances::<T>::insert(&account_id, &restricted_balances);
+		}
+
+		let remaining_balance = T::Flip::balance(&account_id)
+			.checked_sub(&redeem_amount)
+			.ok_or(Error::<T>::InsufficientBalance)?;
+
+		ensure!(
+			remaining_balance == Zero::zero() || remaining_balance >= MinimumFunding::<T>::get(),
+			Error::<T>::BelowMinimumFunding
+		);
+		ensure!(
+			remaining_balance >=
+				restricted_balances.values().copied().sum::<FlipBalance<T>>() -
+					restricted_deficit,
+			Error::<T>::InsufficientUnrestrictedFunds
+		);
+
+		// Update the account balance.
+		if redeem_amount > Zero::zero() {
+			T::Flip::try_initiate_redemption(&account_id, redeem_amount)?;
+			if T::Flip::balance(&account_id).is_zero() {
+				ensure!(
+					T::AccountRoleRegistry::is_unregistered(&account_id),
+					Error::<T>::AccountMustBeUnregistered
+				);
+			}
+
+			// Send the transaction.
+			let contract_expiry = T::TimeSource::now().as_secs() + RedemptionTTLSeconds::<T>::get();
+			let call = T::RegisterRedemption::new_unsigned(
+				<T as Config>::FunderId::from_ref(&account_id).as_ref(),
+				redeem_amount.unique_saturated_into(),
+				address.as_fixed_bytes(),
+				contract_expiry,
+				executor,
+			);
+
+			PendingRedemptions::<T>::insert(
+				&account_id,
+				PendingRedemptionInfo {
+					total: redeem_amount,
+					restricted: min(
+						total_restricted_balance.saturating_sub(redemption_fee),
+						redeem_amount,
+					),
+					redeem_address: address,
+					expiry_time: contract_expiry,
+				},
+			);
+
+			Self::deposit_event(Event::RedemptionRequested {
+				account_id,
+				amount: redeem_amount,
+				broadcast_id: T::Broadcaster::threshold_sign_and_broadcast(call).0,
+				expiry_time: contract_expiry,
+			});
+		} else {
+			Self::deposit_event(Event::RedemptionAmountZero { account_id })
+		}
+
+		Ok(())
+	}
+
+	/// Restricted funds may always be redeemed to their originating address, with any executor.
+	/// Otherwise, any redeem address or executor address binding must be respected: a redemption
+	/// can only name an executor other than the bound one if no executor is bound to the account.
+	fn ensure_redemption_address_allowed(
+		account_id: &AccountId<T>,
+		restricted_balances: &BTreeMap<EthereumAddress, FlipBalance<T>>,
+		address: EthereumAddress,
+		executor: Option<EthereumAddress>,
+	) -> DispatchResult {
+		if !restricted_balances.contains_key(&address) {
+			if let Some(bound_executor) = BoundExecutorAddress::<T>::get(account_id) {
+				ensure!(
+					executor == Some(bound_executor),
+					Error::<T>::ExecutorBindingRestrictionViolated
+				);
+			}
+		}
+
+		if let Some(bound_address) = BoundRedeemAddress::<T>::get(account_id) {
+			ensure!(
+				bound_address == address || restricted_balances.contains_key(&address),
+				Error::<T>::AccountBindingRestrictionViolated
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Executes the redemptions scheduled for an account at the current block. Redemptions to the
+	/// same destination are batched into a single redemption, and any others are tried again at
+	/// the next block. If a redemption is already pending, everything is deferred until it has
+	/// been settled or has expired. If a redeem address update is pending, redemptions are
+	/// retried once it has taken effect, and if redeeming is otherwise not possible, after
+	/// [SCHEDULED_REDEMPTION_RETRY_INTERVAL] blocks.
+	fn execute_scheduled_redemptions(
+		current_block: BlockNumberFor<T>,
+		account_id: AccountId<T>,
+		scheduled: ScheduledRedemptionsFor<FlipBalance<T>>,
+	) {
+		if PendingRedemptions::<T>::contains_key(&account_id) {
+			DeferredRedemptions::<T>::mutate(&account_id, |deferred| {
+				Self::extend_scheduled_redemptions(deferred, scheduled)
+			});
+			return
+		}
+		if let Some((_, effective_at)) = PendingRedeemAddressUpdates::<T>::get(&account_id) {
+			Self::reschedule_redemptions(effective_at, &account_id, scheduled);
+			return
+		}
+		if !T::SafeMode::get().redeem_enabled ||
+			T::RedemptionChecker::ensure_can_redeem(&account_id).is_err()
+		{
+			Self::reschedule_redemptions(
+				current_block.saturating_add(SCHEDULED_REDEMPTION_RETRY_INTERVAL.into()),
+				&account_id,
+				scheduled,
+			);
+			return
+		}
+
+		let Some(ScheduledRedemption { address, executor, .. }) = scheduled.first().cloned() else {
+			return
+		};
+		let (batch, deferred): (Vec<_>, Vec<_>) = scheduled.into_iter().partition(|redemption| {
+			redemption.address == address && redemption.executor == executor
+		});
+		if !deferred.is_empty() {
+			Self::reschedule_redemptions(
+				current_block.saturating_add(One::one()),
+				&account_id,
+				deferred,
+			);
+		}
+
+		ScheduledRedemptionCount::<T>::mutate_exists(&account_id, |count| {
+			*count = count
+				.map(|count| count.saturating_sub(batch.len() as u32))
+				.filter(|count| *count > 0);
+		});
+		let amount = batch.into_iter().map(|redemption| redemption.amount).sum::<FlipBalance<T>>();
+		if let Err(error) = frame_support::storage::with_storage_layer(|| {
+			Self::try_redeem(account_id.clone(), amount.into(), address, executor)
+		}) {
+			Self::deposit_event(Event::ScheduledRedemptionFailed { account_id, amount, error });
+		}
+	}
+
+	/// Schedules an account's deferred redemptions for execution at the next block, once its
+	/// pending redemption has been settled or has expired.
+	fn reschedule_deferred_redemptions(account_id: &AccountId<T>) {
+		let deferred = DeferredRedemptions::<T>::take(account_id);
+		if !deferred.is_empty() {
+			Self::reschedule_redemptions(
+				frame_system::Pallet::<T>::block_number().saturating_add(One::one()),
+				account_id,
+				deferred,
+			);
+		}
+	}
+
+	fn reschedule_redemptions(
+		execute_at: BlockNumberFor<T>,
+		account_id: &AccountId<T>,
+		redemptions: impl IntoIterator<Item = ScheduledRedemption<FlipBalance<T>>>,
+	) {
+		ScheduledRedemptions::<T>::mutate(execute_at, account_id, |scheduled| {
+			Self::extend_scheduled_redemptions(scheduled, redemptions)
+		});
+	}
+
+	/// The number of redemptions an account has scheduled is capped at
+	/// [MAX_SCHEDULED_REDEMPTIONS], so they always fit.
+	fn extend_scheduled_redemptions(
+		scheduled: &mut ScheduledRedemptionsFor<FlipBalance<T>>,
+		redemptions: impl IntoIterator<Item = ScheduledRedemption<FlipBalance<T>>>,
+	) {
+		for redemption in redemptions {
+			if scheduled.try_push(redemption).is_err() {
+				log_or_panic!("Scheduled redemptions exceed the per-account limit.");
+			}
+		}
+	}
+
+	/// Applies a pending bound redeem address update, unless it has since been superseded by a
+	/// later request.
+	fn apply_redeem_address_update(current_block: BlockNumberFor<T>, account_id: &AccountId<T>) {
+		if let Some((address, effective_at)) = PendingRedeemAddressUpdates::<T>::get(account_id) {
+			if effective_at == current_block {
+				PendingRedeemAddressUpdates::<T>::remove(account_id);
+				BoundRedeemAddress::<T>::insert(account_id, address);
+				Self::deposit_event(Event::BoundRedeemAddress {
+					account_id: account_id.clone(),
+					address,
+				});
+			}
+		}
+	}
+
+	/// Records FLIP bought by a swap that is being sent to the State Chain Gateway by the given
+	/// egress. It is added to the account's funds once the egress has been witnessed.
+	pub fn fund_account_from_swap(
+		egress_id: EgressId,
+		account_id: &AccountId<T>,
+		amount: FlipBalance<T>,
+	) {
+		PendingSwapFunding::<T>::insert(egress_id, (account_id.clone(), amount));
+	}
+
 	/// Add funds to an account, creating the account if it doesn't exist. An account is not
 	/// an implicit bidder and needs to start bidding explicitly.
 	fn add_funds_to_account(account_id: &AccountId<T>, amount: T::Amount) -> T::Amount {
@@ -778,5 +1388,23 @@ impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
 		RestrictedBalances::<T>::remove(account_id);
 		BoundExecutorAddress::<T>::remove(account_id);
 		BoundRedeemAddress::<T>::remove(account_id);
+		PendingRedeemAddressUpdates::<T>::remove(account_id);
+		ScheduledRedemptionCount::<T>::remove(account_id);
+		DeferredRedemptions::<T>::remove(account_id);
+	}
+}
+
+/// Adds FLIP bought by a swap to the account's funds once its transfer to the State Chain Gateway
+/// has been witnessed.
+impl<T: Config> OnEgressSuccess for Pallet<T> {
+	fn on_egress_success(egress_id: EgressId) {
+		if let Some((account_id, amount)) = PendingSwapFunding::<T>::take(egress_id) {
+			let total_balance = Self::add_funds_to_account(&account_id, amount);
+			Self::deposit_event(Event::FundedFromSwap {
+				account_id,
+				funds_added: amount,
+				total_balance,
+			});
+		}
 	}
 }
@@ -16,5 +16,24 @@
 
 use crate::Pallet;
 use cf_runtime_utilities::PlaceholderMigration;
+use frame_support::migrations::VersionedMigration;
+mod redeem_address_update_delay;
+mod redemption_expiry;
 
-pub type PalletMigration<T> = PlaceholderMigration<4, Pallet<T>>;
+pub type PalletMigration<T> = (
+	VersionedMigration<
+		4,
+		5,
+		redemption_expiry::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		5,
+		6,
+		redeem_address_update_delay::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	PlaceholderMigration<6, Pallet<T>>,
+);
@@ -0,0 +1,72 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+	Config, RedeemAddressUpdateDelay, DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY,
+	MIN_REDEEM_ADDRESS_UPDATE_DELAY,
+};
+use frame_support::{
+	traits::{Get, UncheckedOnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::pallet_prelude::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Sets the delay before bound redeem address updates take effect, which was not initialised at
+/// genesis, to its default value.
+pub struct Migration<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if RedeemAddressUpdateDelay::<T>::get() < MIN_REDEEM_ADDRESS_UPDATE_DELAY.into() {
+			RedeemAddressUpdateDelay::<T>::set(DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY.into());
+		}
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		frame_support::ensure!(
+			RedeemAddressUpdateDelay::<T>::get() >= MIN_REDEEM_ADDRESS_UPDATE_DELAY.into(),
+			"The redeem address update delay is too short"
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+
+	#[test]
+	fn sets_the_default_delay() {
+		new_test_ext().execute_with(|| {
+			RedeemAddressUpdateDelay::<Test>::kill();
+
+			Migration::<Test>::on_runtime_upgrade();
+
+			assert_eq!(
+				RedeemAddressUpdateDelay::<Test>::get(),
+				DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY as u64
+			);
+		});
+	}
+}
@@ -0,0 +1,117 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Config, FlipBalance, PendingRedemptionInfo, PendingRedemptions, RedemptionTTLSeconds};
+use frame_support::{
+	traits::{Get, UncheckedOnRuntimeUpgrade, UnixTime},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::pallet_prelude::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+mod old {
+	use cf_chains::eth::Address as EthereumAddress;
+	use codec::{Decode, Encode};
+
+	#[derive(Encode, Decode)]
+	pub struct PendingRedemptionInfo<FlipBalance> {
+		pub total: FlipBalance,
+		pub restricted: FlipBalance,
+		pub redeem_address: EthereumAddress,
+	}
+}
+
+/// Adds the expiry time to pending redemptions.
+///
+/// The expiry of redemptions requested before the upgrade was not stored, so it is set to the
+/// latest time at which they could expire.
+pub struct Migration<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let latest_expiry_time = T::TimeSource::now().as_secs() + RedemptionTTLSeconds::<T>::get();
+		let mut redemptions = 0u64;
+		PendingRedemptions::<T>::translate_values::<old::PendingRedemptionInfo<FlipBalance<T>>, _>(
+			|old::PendingRedemptionInfo { total, restricted, redeem_address }| {
+				redemptions += 1;
+				Some(PendingRedemptionInfo {
+					total,
+					restricted,
+					redeem_address,
+					expiry_time: latest_expiry_time,
+				})
+			},
+		);
+		T::DbWeight::get().reads_writes(redemptions + 1, redemptions)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((PendingRedemptions::<T>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_upgrade_count = u32::decode(&mut &state[..])
+			.map_err(|_| DispatchError::from("Failed to decode pre-upgrade state"))?;
+		frame_support::ensure!(
+			PendingRedemptions::<T>::iter().count() as u32 == pre_upgrade_count,
+			"Pending redemptions were lost during the migration"
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use cf_traits::mocks::time_source;
+	use sp_std::time::Duration;
+
+	#[test]
+	fn sets_expiry_of_pending_redemptions() {
+		new_test_ext().execute_with(|| {
+			time_source::Mock::reset_to(Duration::from_secs(100));
+			frame_support::storage::unhashed::put(
+				&PendingRedemptions::<Test>::hashed_key_for(ALICE),
+				&old::PendingRedemptionInfo::<FlipBalance<Test>> {
+					total: 1_000,
+					restricted: 100,
+					redeem_address: Default::default(),
+				},
+			);
+
+			Migration::<Test>::on_runtime_upgrade();
+
+			assert_eq!(
+				PendingRedemptions::<Test>::get(ALICE),
+				Some(PendingRedemptionInfo {
+					total: 1_000,
+					restricted: 100,
+					redeem_address: Default::default(),
+					expiry_time: 100 + REDEMPTION_TTL_SECS,
+				})
+			);
+		});
+	}
+}
@@ -15,7 +15,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate as pallet_cf_funding;
-use crate::PalletSafeMode;
+use crate::{PalletSafeMode, DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY};
 use cf_chains::{evm::EvmCrypto, ApiCall, Chain, ChainCrypto, Ethereum};
 use cf_primitives::FlipBalance;
 use cf_traits::{
@@ -73,6 +73,7 @@ cf_traits::impl_mock_on_account_funded!(AccountId, u128);
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct MockRegisterRedemption {
 	amount: <Ethereum as Chain>::ChainAmount,
+	pub executor: Option<cf_chains::eth::Address>,
 }
 
 impl cf_chains::RegisterRedemption for MockRegisterRedemption {
@@ -81,9 +82,9 @@ impl cf_chains::RegisterRedemption for MockRegisterRedemption {
 		amount: u128,
 		_address: &[u8; 20],
 		_expiry: u64,
-		_executor: Option<cf_chains::eth::Address>,
+		executor: Option<cf_chains::eth::Address>,
 	) -> Self {
-		Self { amount }
+		Self { amount, executor }
 	}
 }
 
@@ -180,6 +181,7 @@ cf_test_utilities::impl_test_helpers! {
 			redemption_tax: REDEMPTION_TAX,
 			minimum_funding: MIN_FUNDING,
 			redemption_ttl: Duration::from_secs(REDEMPTION_TTL_SECS),
+			redeem_address_update_delay: DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY.into(),
 		},
 	},
 	|| {
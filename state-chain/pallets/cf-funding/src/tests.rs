@@ -15,21 +15,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-	mock::*, pallet, BoundExecutorAddress, Error, EthereumAddress, Event, PendingRedemptions,
-	RedemptionAmount, RedemptionTax, RestrictedAddresses, RestrictedBalances,
+	mock::*, pallet, BoundExecutorAddress, DeferredRedemptions, Error, EthereumAddress, Event,
+	PendingRedeemAddressUpdates, PendingRedemptions, PendingRestrictedAddressMigrations,
+	PendingSwapFunding, RedeemAddressUpdateDelay, RedemptionAmount, RedemptionTax,
+	RestrictedAddresses, RestrictedBalances, ScheduledRedemption, ScheduledRedemptionCount,
+	ScheduledRedemptions, DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY, MAX_SCHEDULED_REDEMPTIONS,
+	MIN_REDEEM_ADDRESS_UPDATE_DELAY, SCHEDULED_REDEMPTION_RETRY_INTERVAL,
 };
-use cf_primitives::FlipBalance;
+use cf_primitives::{EgressId, FlipBalance, ForeignChain};
 use cf_test_utilities::assert_event_sequence;
 use cf_traits::{
 	mocks::account_role_registry::MockAccountRoleRegistry, AccountInfo, AccountRoleRegistry,
-	Bonding, Chainflip, SetSafeMode, Slashing,
+	Bonding, Chainflip, OnEgressSuccess, SetSafeMode, Slashing,
 };
 use sp_core::H160;
 
 use crate::BoundRedeemAddress;
-use frame_support::{assert_noop, assert_ok, traits::OriginTrait};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, OriginTrait},
+};
 use pallet_cf_flip::{Bonder, FlipSlasher};
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, DispatchResult};
 
 type FlipError = pallet_cf_flip::Error<Test>;
 
@@ -1008,7 +1015,7 @@ fn redeem_funds_to_restricted_address_overrides_bound_and_executor_restrictions(
 				RuntimeOrigin::signed(ALICE),
 				(AMOUNT).into(),
 				REDEEM_ADDRESS,
-				Default::default()
+				Some(RANDOM_ADDRESS)
 			),
 			Error::<Test>::ExecutorBindingRestrictionViolated
 		);
@@ -1620,6 +1627,98 @@ fn check_restricted_balances_are_getting_removed() {
 	});
 }
 
+#[test]
+fn restricted_balances_can_be_migrated_to_a_new_address() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: FlipBalance = 50;
+		const OLD_ADDRESS: EthereumAddress = H160([0x01; 20]);
+		const NEW_ADDRESS: EthereumAddress = H160([0x02; 20]);
+		assert_ok!(Funding::update_restricted_addresses(
+			RuntimeOrigin::root(),
+			vec![OLD_ADDRESS],
+			Default::default(),
+		));
+		for account_id in [ALICE, BOB] {
+			assert_ok!(Funding::funded(
+				RuntimeOrigin::root(),
+				account_id,
+				AMOUNT,
+				OLD_ADDRESS,
+				Default::default(),
+			));
+		}
+
+		// Only restricted addresses can be migrated, and only to a different address.
+		for (from, to) in [(NEW_ADDRESS, OLD_ADDRESS), (OLD_ADDRESS, OLD_ADDRESS)] {
+			assert_noop!(
+				Funding::propose_restricted_address_migration(RuntimeOrigin::root(), from, to),
+				Error::<Test>::InvalidRestrictedAddressMigration
+			);
+		}
+		// The migration must be proposed before it can be confirmed.
+		assert_noop!(
+			Funding::confirm_restricted_address_migration(
+				RuntimeOrigin::root(),
+				OLD_ADDRESS,
+				NEW_ADDRESS,
+				2
+			),
+			Error::<Test>::NoPendingRestrictedAddressMigration
+		);
+
+		assert_ok!(Funding::propose_restricted_address_migration(
+			RuntimeOrigin::root(),
+			OLD_ADDRESS,
+			NEW_ADDRESS
+		));
+		System::assert_last_event(RuntimeEvent::Funding(
+			Event::RestrictedAddressMigrationProposed { from: OLD_ADDRESS, to: NEW_ADDRESS },
+		));
+		// Nothing changes until the migration is confirmed.
+		assert!(RestrictedAddresses::<Test>::contains_key(OLD_ADDRESS));
+		assert_noop!(
+			Funding::confirm_restricted_address_migration(
+				RuntimeOrigin::root(),
+				OLD_ADDRESS,
+				H160([0x03; 20]),
+				2
+			),
+			Error::<Test>::NoPendingRestrictedAddressMigration
+		);
+		// All accounts with restricted balances must be accounted for.
+		assert_noop!(
+			Funding::confirm_restricted_address_migration(
+				RuntimeOrigin::root(),
+				OLD_ADDRESS,
+				NEW_ADDRESS,
+				1
+			),
+			Error::<Test>::TooManyRestrictedAccounts
+		);
+
+		assert_ok!(Funding::confirm_restricted_address_migration(
+			RuntimeOrigin::root(),
+			OLD_ADDRESS,
+			NEW_ADDRESS,
+			2
+		));
+		System::assert_last_event(RuntimeEvent::Funding(Event::RestrictedAddressMigrated {
+			from: OLD_ADDRESS,
+			to: NEW_ADDRESS,
+			accounts_migrated: 2,
+		}));
+		assert!(!RestrictedAddresses::<Test>::contains_key(OLD_ADDRESS));
+		assert!(RestrictedAddresses::<Test>::contains_key(NEW_ADDRESS));
+		assert!(PendingRestrictedAddressMigrations::<Test>::get(OLD_ADDRESS).is_none());
+		for account_id in [ALICE, BOB] {
+			assert_eq!(
+				RestrictedBalances::<Test>::get(account_id),
+				BTreeMap::from([(NEW_ADDRESS, AMOUNT)])
+			);
+		}
+	});
+}
+
 #[test]
 fn bind_executor_address() {
 	new_test_ext().execute_with(|| {
@@ -1659,6 +1758,54 @@ fn detect_wrong_executor_address() {
 	});
 }
 
+#[test]
+fn redemptions_can_specify_their_own_executor_address_if_none_is_bound() {
+	new_test_ext().execute_with(|| {
+		const EXECUTOR_ADDRESS: EthereumAddress = H160([0x01; 20]);
+		const OTHER_EXECUTOR_ADDRESS: EthereumAddress = H160([0x02; 20]);
+		const AMOUNT: u128 = 100;
+		assert_ok!(Funding::funded(
+			RuntimeOrigin::root(),
+			ALICE,
+			AMOUNT * 4,
+			ETH_DUMMY_ADDR,
+			TX_HASH
+		));
+
+		let redeem_and_get_executor = |executor| {
+			assert_ok!(Funding::redeem(
+				RuntimeOrigin::signed(ALICE),
+				AMOUNT.into(),
+				ETH_DUMMY_ADDR,
+				executor
+			));
+			assert_ok!(Funding::redeemed(RuntimeOrigin::root(), ALICE, AMOUNT, TX_HASH));
+			MockFundingBroadcaster::get_pending_api_calls().last().unwrap().executor
+		};
+
+		// Without a bound executor, each redemption can name its own executor.
+		assert_eq!(
+			redeem_and_get_executor(Some(OTHER_EXECUTOR_ADDRESS)),
+			Some(OTHER_EXECUTOR_ADDRESS)
+		);
+
+		// Once an executor is bound, only the bound executor is accepted.
+		assert_ok!(Funding::bind_executor_address(RuntimeOrigin::signed(ALICE), EXECUTOR_ADDRESS));
+		assert_noop!(
+			Funding::redeem(
+				RuntimeOrigin::signed(ALICE),
+				AMOUNT.into(),
+				ETH_DUMMY_ADDR,
+				Some(OTHER_EXECUTOR_ADDRESS)
+			),
+			Error::<Test>::ExecutorBindingRestrictionViolated
+		);
+		assert_eq!(redeem_and_get_executor(Some(EXECUTOR_ADDRESS)), Some(EXECUTOR_ADDRESS));
+		// Redemptions that don't name an executor fall back to the bound one.
+		assert_eq!(redeem_and_get_executor(None), Some(EXECUTOR_ADDRESS));
+	});
+}
+
 #[test]
 fn can_redeem_if_balance_lower_than_restricted_funds() {
 	new_test_ext().execute_with(|| {
@@ -1835,3 +1982,361 @@ fn only_governance_can_update_settings() {
 		);
 	});
 }
+
+#[test]
+fn swap_funding_is_added_once_the_egress_is_witnessed() {
+	const EGRESS_ID: EgressId = (ForeignChain::Ethereum, 1);
+	const AMOUNT: FlipBalance = 1_000;
+
+	new_test_ext().execute_with(|| {
+		Funding::fund_account_from_swap(EGRESS_ID, &ALICE, AMOUNT);
+		assert_eq!(Flip::total_balance_of(&ALICE), 0);
+
+		Funding::on_egress_success((ForeignChain::Ethereum, 2));
+		assert_eq!(Flip::total_balance_of(&ALICE), 0);
+
+		Funding::on_egress_success(EGRESS_ID);
+		assert_eq!(Flip::total_balance_of(&ALICE), AMOUNT);
+		assert!(!PendingSwapFunding::<Test>::contains_key(EGRESS_ID));
+		System::assert_last_event(RuntimeEvent::Funding(Event::FundedFromSwap {
+			account_id: ALICE,
+			funds_added: AMOUNT,
+			total_balance: AMOUNT,
+		}));
+	});
+}
+
+mod scheduled_redemptions {
+	use super::*;
+
+	const FUNDS: u128 = 100;
+
+	fn fund_alice() {
+		assert_ok!(Funding::funded(RuntimeOrigin::root(), ALICE, FUNDS, ETH_ZERO_ADDRESS, TX_HASH));
+	}
+
+	fn pending_amount() -> Option<u128> {
+		PendingRedemptions::<Test>::get(&ALICE).map(|pending| pending.total)
+	}
+
+	fn schedule(redemptions: Vec<(u128, u64)>) -> DispatchResult {
+		Funding::schedule_redemptions(
+			RuntimeOrigin::signed(ALICE),
+			redemptions.try_into().unwrap(),
+			ETH_DUMMY_ADDR,
+			None,
+		)
+	}
+
+	#[test]
+	fn scheduled_redemptions_are_executed_at_their_block() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			assert_ok!(schedule(vec![(20, 5), (30, 10)]));
+			System::assert_last_event(RuntimeEvent::Funding(Event::RedemptionsScheduled {
+				account_id: ALICE,
+				address: ETH_DUMMY_ADDR,
+				redemptions: vec![(20, 5), (30, 10)],
+			}));
+
+			Funding::on_initialize(4);
+			assert_eq!(pending_amount(), None);
+
+			Funding::on_initialize(5);
+			assert_eq!(pending_amount(), Some(20));
+			assert!(!ScheduledRedemptions::<Test>::contains_key(5, &ALICE));
+			assert_eq!(ScheduledRedemptionCount::<Test>::get(&ALICE), 1);
+
+			assert_ok!(Funding::redeemed(RuntimeOrigin::root(), ALICE, 20, TX_HASH));
+
+			Funding::on_initialize(10);
+			assert_eq!(pending_amount(), Some(30));
+			assert_eq!(Flip::total_balance_of(&ALICE), FUNDS - 20 - 30 - 2 * REDEMPTION_TAX);
+			assert!(!ScheduledRedemptionCount::<Test>::contains_key(&ALICE));
+		});
+	}
+
+	#[test]
+	fn redemptions_are_deferred_and_batched_while_a_redemption_is_pending() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			assert_ok!(schedule(vec![(10, 5), (15, 6)]));
+			assert_ok!(Funding::redeem(
+				RuntimeOrigin::signed(ALICE),
+				20.into(),
+				ETH_DUMMY_ADDR,
+				Default::default()
+			));
+
+			// Both redemptions are deferred until the pending redemption is settled.
+			Funding::on_initialize(5);
+			Funding::on_initialize(6);
+			assert_eq!(pending_amount(), Some(20));
+			assert!(!ScheduledRedemptions::<Test>::contains_key(7, &ALICE));
+			assert_eq!(
+				DeferredRedemptions::<Test>::get(&ALICE)
+					.into_iter()
+					.map(|ScheduledRedemption { amount, .. }| amount)
+					.collect::<Vec<_>>(),
+				vec![10, 15]
+			);
+
+			assert_ok!(Funding::redeemed(RuntimeOrigin::root(), ALICE, 20, TX_HASH));
+			assert!(!DeferredRedemptions::<Test>::contains_key(&ALICE));
+
+			// Both redemptions are executed together at the next block.
+			let next_block = System::block_number() + 1;
+			Funding::on_initialize(next_block);
+			assert_eq!(pending_amount(), Some(25));
+			assert!(!ScheduledRedemptions::<Test>::contains_key(next_block, &ALICE));
+			assert!(!ScheduledRedemptionCount::<Test>::contains_key(&ALICE));
+		});
+	}
+
+	#[test]
+	fn deferred_redemptions_are_rescheduled_when_the_pending_redemption_expires() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			assert_ok!(schedule(vec![(10, 5)]));
+			assert_ok!(Funding::redeem(
+				RuntimeOrigin::signed(ALICE),
+				20.into(),
+				ETH_DUMMY_ADDR,
+				Default::default()
+			));
+
+			Funding::on_initialize(5);
+			assert_eq!(DeferredRedemptions::<Test>::get(&ALICE).len(), 1);
+
+			assert_ok!(Funding::redemption_expired(RuntimeOrigin::root(), ALICE, 0));
+			assert!(!DeferredRedemptions::<Test>::contains_key(&ALICE));
+
+			Funding::on_initialize(System::block_number() + 1);
+			assert_eq!(pending_amount(), Some(10));
+		});
+	}
+
+	#[test]
+	fn redemptions_are_deferred_while_redeeming_is_not_possible() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			assert_ok!(schedule(vec![(10, 5)]));
+
+			const RETRY_AT: u64 = 5 + SCHEDULED_REDEMPTION_RETRY_INTERVAL as u64;
+			CanRedeem::set(false);
+			Funding::on_initialize(5);
+			assert_eq!(pending_amount(), None);
+			assert!(!ScheduledRedemptions::<Test>::contains_key(6, &ALICE));
+			assert!(ScheduledRedemptions::<Test>::contains_key(RETRY_AT, &ALICE));
+
+			CanRedeem::set(true);
+			Funding::on_initialize(RETRY_AT);
+			assert_eq!(pending_amount(), Some(10));
+		});
+	}
+
+	#[test]
+	fn failed_scheduled_redemptions_are_dropped() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			assert_ok!(schedule(vec![(FUNDS - 1, 5)]));
+
+			Funding::on_initialize(5);
+			assert_eq!(pending_amount(), None);
+			System::assert_last_event(RuntimeEvent::Funding(Event::ScheduledRedemptionFailed {
+				account_id: ALICE,
+				amount: FUNDS - 1,
+				error: Error::<Test>::InsufficientBalance.into(),
+			}));
+			// The redemption tax is not levied.
+			assert_eq!(Flip::total_balance_of(&ALICE), FUNDS);
+			assert!(!ScheduledRedemptions::<Test>::contains_key(6, &ALICE));
+			assert!(!ScheduledRedemptionCount::<Test>::contains_key(&ALICE));
+		});
+	}
+
+	#[test]
+	fn scheduled_redemptions_are_capped_per_account() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			let half = MAX_SCHEDULED_REDEMPTIONS as u64 / 2;
+			assert_ok!(schedule((0..half).map(|i| (1, 5 + i)).collect()));
+			assert_ok!(schedule((half..2 * half).map(|i| (1, 5 + i)).collect()));
+			assert_eq!(ScheduledRedemptionCount::<Test>::get(&ALICE), MAX_SCHEDULED_REDEMPTIONS);
+			assert_noop!(
+				schedule(vec![(1, 5 + 2 * half)]),
+				Error::<Test>::TooManyScheduledRedemptions
+			);
+
+			// Executed redemptions no longer count towards the limit.
+			Funding::on_initialize(5);
+			assert_eq!(
+				ScheduledRedemptionCount::<Test>::get(&ALICE),
+				MAX_SCHEDULED_REDEMPTIONS - 1
+			);
+			assert_ok!(schedule(vec![(1, 5 + 2 * half)]));
+		});
+	}
+
+	#[test]
+	fn redemption_schedule_must_be_valid() {
+		new_test_ext().execute_with(|| {
+			fund_alice();
+			for invalid_schedule in
+				[vec![], vec![(0, 5)], vec![(10, System::block_number())], vec![(10, 5), (10, 5)]]
+			{
+				assert_noop!(schedule(invalid_schedule), Error::<Test>::InvalidRedemptionSchedule);
+			}
+
+			assert_noop!(schedule(vec![(FUNDS, 5), (1, 6)]), Error::<Test>::InsufficientBalance);
+
+			assert_ok!(Funding::bind_redeem_address(
+				RuntimeOrigin::signed(ALICE),
+				ETH_ZERO_ADDRESS
+			));
+			assert_noop!(schedule(vec![(10, 5)]), Error::<Test>::AccountBindingRestrictionViolated);
+		});
+	}
+}
+
+mod redeem_address_updates {
+	use super::*;
+
+	const DELAY: u64 = MIN_REDEEM_ADDRESS_UPDATE_DELAY as u64;
+	const NEW_ADDRESS: EthereumAddress = H160([0xab; 20]);
+
+	fn setup() {
+		assert_ok!(Funding::funded(RuntimeOrigin::root(), ALICE, 100, ETH_ZERO_ADDRESS, TX_HASH));
+		assert_ok!(Funding::bind_redeem_address(RuntimeOrigin::signed(ALICE), ETH_DUMMY_ADDR));
+		assert_ok!(Funding::update_redeem_address_update_delay(RuntimeOrigin::root(), DELAY));
+	}
+
+	#[test]
+	fn bound_redeem_address_can_be_updated_after_delay() {
+		new_test_ext().execute_with(|| {
+			setup();
+			let effective_at = System::block_number() + DELAY;
+
+			assert_ok!(Funding::request_redeem_address_update(
+				RuntimeOrigin::signed(ALICE),
+				NEW_ADDRESS
+			));
+			System::assert_last_event(RuntimeEvent::Funding(
+				Event::BoundRedeemAddressUpdateRequested {
+					account_id: ALICE,
+					address: NEW_ADDRESS,
+					effective_at,
+				},
+			));
+
+			// Redemptions are suspended while the update is pending.
+			assert_noop!(
+				Funding::redeem(RuntimeOrigin::signed(ALICE), 10.into(), ETH_DUMMY_ADDR, None),
+				Error::<Test>::RedeemAddressUpdatePending
+			);
+
+			Funding::on_initialize(effective_at - 1);
+			assert_eq!(BoundRedeemAddress::<Test>::get(&ALICE), Some(ETH_DUMMY_ADDR));
+
+			Funding::on_initialize(effective_at);
+			assert_eq!(BoundRedeemAddress::<Test>::get(&ALICE), Some(NEW_ADDRESS));
+			assert!(!PendingRedeemAddressUpdates::<Test>::contains_key(&ALICE));
+
+			assert_noop!(
+				Funding::redeem(RuntimeOrigin::signed(ALICE), 10.into(), ETH_DUMMY_ADDR, None),
+				Error::<Test>::AccountBindingRestrictionViolated
+			);
+			assert_ok!(Funding::redeem(RuntimeOrigin::signed(ALICE), 10.into(), NEW_ADDRESS, None));
+		});
+	}
+
+	#[test]
+	fn requesting_another_update_restarts_the_delay() {
+		new_test_ext().execute_with(|| {
+			setup();
+			let first_request = System::block_number();
+			assert_ok!(Funding::request_redeem_address_update(
+				RuntimeOrigin::signed(ALICE),
+				ETH_ZERO_ADDRESS
+			));
+
+			System::set_block_number(first_request + 5);
+			assert_ok!(Funding::request_redeem_address_update(
+				RuntimeOrigin::signed(ALICE),
+				NEW_ADDRESS
+			));
+
+			Funding::on_initialize(first_request + DELAY);
+			assert_eq!(BoundRedeemAddress::<Test>::get(&ALICE), Some(ETH_DUMMY_ADDR));
+
+			Funding::on_initialize(first_request + 5 + DELAY);
+			assert_eq!(BoundRedeemAddress::<Test>::get(&ALICE), Some(NEW_ADDRESS));
+		});
+	}
+
+	#[test]
+	fn governance_can_cancel_a_pending_update() {
+		new_test_ext().execute_with(|| {
+			setup();
+			let effective_at = System::block_number() + DELAY;
+			assert_ok!(Funding::request_redeem_address_update(
+				RuntimeOrigin::signed(ALICE),
+				NEW_ADDRESS
+			));
+
+			assert_noop!(
+				Funding::cancel_redeem_address_update(RuntimeOrigin::signed(ALICE), ALICE),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Funding::cancel_redeem_address_update(RuntimeOrigin::root(), ALICE));
+			System::assert_last_event(RuntimeEvent::Funding(
+				Event::BoundRedeemAddressUpdateCancelled {
+					account_id: ALICE,
+					address: NEW_ADDRESS,
+				},
+			));
+			assert_noop!(
+				Funding::cancel_redeem_address_update(RuntimeOrigin::root(), ALICE),
+				Error::<Test>::NoPendingRedeemAddressUpdate
+			);
+
+			// Redemptions to the original address are possible again and the update never applies.
+			assert_ok!(Funding::redeem(
+				RuntimeOrigin::signed(ALICE),
+				10.into(),
+				ETH_DUMMY_ADDR,
+				None
+			));
+			Funding::on_initialize(effective_at);
+			assert_eq!(BoundRedeemAddress::<Test>::get(&ALICE), Some(ETH_DUMMY_ADDR));
+		});
+	}
+
+	#[test]
+	fn update_delay_cannot_be_set_below_the_minimum() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(
+				RedeemAddressUpdateDelay::<Test>::get(),
+				DEFAULT_REDEEM_ADDRESS_UPDATE_DELAY as u64
+			);
+			assert_noop!(
+				Funding::update_redeem_address_update_delay(RuntimeOrigin::root(), DELAY - 1),
+				Error::<Test>::RedeemAddressUpdateDelayTooShort
+			);
+		});
+	}
+
+	#[test]
+	fn only_bound_accounts_can_request_an_update() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Funding::request_redeem_address_update(RuntimeOrigin::signed(ALICE), NEW_ADDRESS),
+				Error::<Test>::AccountNotBound
+			);
+			assert_noop!(
+				Funding::update_redeem_address_update_delay(RuntimeOrigin::signed(ALICE), DELAY),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+}
@@ -57,6 +57,14 @@ pub trait WeightInfo {
 	fn bind_redeem_address() -> Weight;
 	fn update_restricted_addresses(a: u32, b: u32, c: u32, ) -> Weight;
 	fn bind_executor_address() -> Weight;
+	fn schedule_redemptions(n: u32, ) -> Weight;
+	fn execute_scheduled_redemptions(n: u32, ) -> Weight;
+	fn request_redeem_address_update() -> Weight;
+	fn update_redeem_address_update_delay() -> Weight;
+	fn cancel_redeem_address_update() -> Weight;
+	fn apply_redeem_address_updates(n: u32, ) -> Weight;
+	fn propose_restricted_address_migration() -> Weight;
+	fn confirm_restricted_address_migration(c: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_funding using the Substrate node and recommended hardware.
@@ -299,6 +307,56 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	fn schedule_redemptions(n: u32, ) -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(Weight::from_parts(3_200_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn execute_scheduled_redemptions(n: u32, ) -> Weight {
+		Weight::from_parts(6_500_000, 0)
+			.saturating_add(Weight::from_parts(176_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((38_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((18_u64).saturating_mul(n.into())))
+	}
+	fn request_redeem_address_update() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn update_redeem_address_update_delay() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn cancel_redeem_address_update() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn apply_redeem_address_updates(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	fn propose_restricted_address_migration() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn confirm_restricted_address_migration(c: u32, ) -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+	}
 }
 
 // For backwards compatibility and tests
@@ -540,4 +598,54 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(1_u64))
 			.saturating_add(ParityDbWeight::get().writes(1_u64))
 	}
+	fn schedule_redemptions(n: u32, ) -> Weight {
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(Weight::from_parts(3_200_000, 0).saturating_mul(n.into()))
+			.saturating_add(ParityDbWeight::get().reads(5_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+			.saturating_add(ParityDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(ParityDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	fn execute_scheduled_redemptions(n: u32, ) -> Weight {
+		Weight::from_parts(6_500_000, 0)
+			.saturating_add(Weight::from_parts(176_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().reads((38_u64).saturating_mul(n.into())))
+			.saturating_add(ParityDbWeight::get().writes((18_u64).saturating_mul(n.into())))
+	}
+	fn request_redeem_address_update() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(3_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	fn update_redeem_address_update_delay() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	fn cancel_redeem_address_update() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	fn apply_redeem_address_updates(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+			.saturating_add(ParityDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	fn propose_restricted_address_migration() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	fn confirm_restricted_address_migration(c: u32, ) -> Weight {
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+			.saturating_add(ParityDbWeight::get().writes((1_u64).saturating_mul(c.into())))
+	}
 }
@@ -340,6 +340,7 @@ mod benchmarks {
 				boost_fee: 0,
 				channel_id: None,
 				deposit_address: None,
+				refund_ccm_metadata: None,
 			}),
 		};
 
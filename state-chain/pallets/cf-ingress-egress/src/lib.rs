@@ -52,7 +52,7 @@ use cf_chains::{
 use cf_primitives::{
 	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, Beneficiaries,
 	Beneficiary, BoostPoolTier, BroadcastId, ChannelId, DcaParameters, EgressCounter, EgressId,
-	EpochIndex, ForeignChain, GasAmount, PrewitnessedDepositId, SwapRequestId,
+	EpochIndex, ForeignChain, GasAmount, PrewitnessedDepositId, SwapRefundReason, SwapRequestId,
 	ThresholdSignatureRequestId, SECONDS_PER_BLOCK,
 };
 use cf_runtime_utilities::log_or_panic;
@@ -61,18 +61,22 @@ use cf_traits::{
 	AssetConverter, AssetWithholding, BalanceApi, BoostApi, Broadcaster, Chainflip,
 	ChannelIdAllocator, DepositApi, EgressApi, EpochInfo, FeePayment,
 	FetchesTransfersLimitProvider, GetBlockHeight, IngressEgressFeeApi, IngressSink, IngressSource,
-	NetworkEnvironmentProvider, OnDeposit, PoolApi, ScheduledEgressDetails, SwapOutputAction,
-	SwapParameterValidation, SwapRequestHandler, SwapRequestType,
+	NetworkEnvironmentProvider, OnDeposit, PoolApi, PrivateChannelManager, ScheduledEgressDetails,
+	SwapOutputAction, SwapParameterValidation, SwapRequestHandler, SwapRequestType,
 };
 use frame_support::{
 	pallet_prelude::{OptionQuery, *},
-	sp_runtime::{traits::Zero, DispatchError, Permill, Saturating},
+	sp_runtime::{traits::Zero, DispatchError, Permill, Saturating, TransactionOutcome},
+	storage::with_transaction_unchecked,
 	transactional,
 };
 use frame_system::pallet_prelude::*;
 use generic_typeinfo_derive::GenericTypeInfo;
 pub use pallet::*;
-use sp_runtime::{traits::UniqueSaturatedInto, Percent};
+use sp_runtime::{
+	traits::{Bounded, UniqueSaturatedInto},
+	Percent,
+};
 use sp_std::{
 	boxed::Box,
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -80,6 +84,7 @@ use sp_std::{
 	vec,
 	vec::Vec,
 };
+use strum::IntoEnumIterator;
 pub use weights::WeightInfo;
 
 const MARKED_TX_EXPIRATION_BLOCKS: u32 = 3600 / SECONDS_PER_BLOCK as u32;
@@ -174,8 +179,8 @@ pub struct BoostPoolId<C: Chain> {
 }
 
 pub struct BoostOutput<C: Chain> {
-	used_pools: BTreeMap<BoostPoolTier, C::ChainAmount>,
-	total_fee: C::ChainAmount,
+	pub used_pools: BTreeMap<BoostPoolTier, C::ChainAmount>,
+	pub total_fee: C::ChainAmount,
 }
 
 /// Enum wrapper for fetch and egress requests.
@@ -224,6 +229,19 @@ pub enum RefundReason {
 	InvalidDestinationAddress,
 }
 
+impl From<RefundReason> for SwapRefundReason {
+	fn from(reason: RefundReason) -> Self {
+		match reason {
+			RefundReason::CcmUnsupportedForTargetChain | RefundReason::CcmInvalidMetadata =>
+				SwapRefundReason::CcmInvalid,
+			RefundReason::InvalidBrokerFees |
+			RefundReason::InvalidRefundParameters |
+			RefundReason::InvalidDcaParameters |
+			RefundReason::InvalidDestinationAddress => SwapRefundReason::InvalidParameters,
+		}
+	}
+}
+
 enum FullWitnessDepositOutcome {
 	/// Either boost has been finalised, or it was still pending (due to a delay)
 	/// and should no longer be scheduled for processing
@@ -237,6 +255,7 @@ pub struct ValidatedVaultSwapParams<AccountId> {
 	pub channel_metadata: Option<CcmChannelMetadata>,
 	pub source_address: Option<ForeignChainAddress>,
 	pub destination_address: ForeignChainAddress,
+	pub refund_ccm_metadata: Option<CcmDepositMetadata>,
 }
 
 mod deposit_origin {
@@ -423,7 +442,7 @@ pub mod pallet {
 	use super::*;
 	use cf_chains::{address::EncodedAddress, ExecutexSwapAndCall, TransferFallback};
 	use cf_primitives::{BroadcastId, EpochIndex};
-	use cf_traits::{OnDeposit, SwapParameterValidation};
+	use cf_traits::{OnDeposit, OnEgressSuccess, SwapParameterValidation};
 	use core::marker::PhantomData;
 	use frame_support::traits::{ConstU128, EnsureOrigin, IsType};
 	use frame_system::WeightInfo as SystemWeightInfo;
@@ -487,6 +506,10 @@ pub mod pallet {
 		pub dca_params: Option<DcaParameters>,
 		#[skip_name_expansion]
 		pub boost_fee: BasisPoints,
+		/// CCM to send along with any refund, so that the refund can be handled by the source
+		/// contract.
+		#[skip_name_expansion]
+		pub refund_ccm_metadata: Option<CcmChannelMetadata>,
 	}
 
 	#[derive(
@@ -559,6 +582,28 @@ pub mod pallet {
 			reason: RefundReason,
 			refund_address: C::ChainAccount,
 		},
+		/// A swap whose output is split between several destination addresses, each receiving
+		/// the given share (in basis points) of the output.
+		SplitSwap {
+			destination_asset: Asset,
+			destination_addresses: Vec<(ForeignChainAddress, BasisPoints)>,
+			broker_fees: Beneficiaries<AccountId>,
+			refund_params: ChannelRefundParameters<ForeignChainAddress>,
+			dca_params: Option<DcaParameters>,
+		},
+		/// A swap into FLIP whose output is added to the funds of the given state chain account.
+		FundAccount {
+			account_id: AccountId,
+			broker_fees: Beneficiaries<AccountId>,
+			refund_params: ChannelRefundParameters<ForeignChainAddress>,
+			dca_params: Option<DcaParameters>,
+		},
+		/// A deposit to one of the addresses of a broker's private channel, credited to the
+		/// broker's balance.
+		PrivateBrokerDeposit {
+			broker_id: AccountId,
+			refund_address: ForeignChainAddress,
+		},
 	}
 
 	/// Contains identifying information about the particular actions that have occurred for a
@@ -572,6 +617,9 @@ pub mod pallet {
 		LiquidityProvision {
 			lp_account: T::AccountId,
 		},
+		PrivateBrokerDeposit {
+			broker_id: T::AccountId,
+		},
 		CcmTransfer {
 			swap_request_id: SwapRequestId,
 		},
@@ -584,6 +632,7 @@ pub mod pallet {
 		Refund {
 			egress_id: Option<EgressId>,
 			reason: RefundReason,
+			refund_reason: SwapRefundReason,
 			amount: TargetChainAmount<T, I>,
 		},
 	}
@@ -676,6 +725,9 @@ pub mod pallet {
 		/// Provides callbacks for deposit lifecycle events.
 		type DepositHandler: OnDeposit<Self::TargetChain>;
 
+		/// Notified once scheduled transfers have been witnessed on the target chain.
+		type EgressSuccessHandler: OnEgressSuccess;
+
 		type NetworkEnvironment: NetworkEnvironmentProvider;
 
 		/// Allows assets to be converted through the AMM.
@@ -746,6 +798,17 @@ pub mod pallet {
 	pub type ScheduledEgressFetchOrTransfer<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, Vec<FetchOrTransfer<T::TargetChain>>, ValueQuery>;
 
+	/// The block at which each transfer in [ScheduledEgressFetchOrTransfer] was scheduled.
+	#[pallet::storage]
+	pub type ScheduledTransferBlocks<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, EgressId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The block at which a fetch from each deposit address was first scheduled, for as long as any
+	/// fetch from that address remains in [ScheduledEgressFetchOrTransfer].
+	#[pallet::storage]
+	pub type ScheduledFetchBlocks<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAccount<T, I>, BlockNumberFor<T>, OptionQuery>;
+
 	/// Scheduled cross chain messages for the Ethereum chain.
 	#[pallet::storage]
 	pub type ScheduledEgressCcm<T: Config<I>, I: 'static = ()> =
@@ -1019,6 +1082,7 @@ pub mod pallet {
 		TransactionRejectedByBroker {
 			broadcast_id: BroadcastId,
 			tx_id: <T::TargetChain as Chain>::DepositDetails,
+			refund_reason: SwapRefundReason,
 		},
 		TransactionRejectionFailed {
 			tx_id: <T::TargetChain as Chain>::DepositDetails,
@@ -1039,6 +1103,17 @@ pub mod pallet {
 		BoostDelaySet {
 			delay_blocks: BlockNumberFor<T>,
 		},
+		/// A deposit has been pre-witnessed and will be processed once finalised (unless it is
+		/// boosted first).
+		DepositPrewitnessed {
+			deposit_address: Option<TargetChainAccount<T, I>>,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			block_height: TargetChainBlockNumber<T, I>,
+			prewitnessed_deposit_id: PrewitnessedDepositId,
+			channel_id: Option<ChannelId>,
+			origin_type: DepositOriginType,
+		},
 	}
 
 	#[derive(CloneNoBound, PartialEqNoBound, EqNoBound)]
@@ -1340,6 +1415,28 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Callback for when a batch of fetches and transfers is accepted by the chain.
+		#[pallet::call_index(14)]
+		#[pallet::weight(
+			T::WeightInfo::finalise_ingress(addresses.len() as u32).saturating_add(
+				T::DbWeight::get()
+					.reads_writes(3, 3)
+					.saturating_mul(egress_ids.len() as u64)
+			)
+		)]
+		pub fn finalise_batch(
+			origin: OriginFor<T>,
+			addresses: Vec<TargetChainAccount<T, I>>,
+			egress_ids: Vec<EgressId>,
+		) -> DispatchResult {
+			Self::finalise_ingress(origin, addresses)?;
+
+			for egress_id in egress_ids {
+				T::EgressSuccessHandler::on_egress_success(egress_id);
+			}
+			Ok(())
+		}
+
 		/// Sets if an asset is not allowed to be sent out of the chain via Egress.
 		/// Requires Governance
 		///
@@ -1786,6 +1883,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Self::deposit_event(Event::<T, I>::TransactionRejectedByBroker {
 				broadcast_id,
 				tx_id: tx.deposit_details.clone(),
+				refund_reason: SwapRefundReason::Screened,
 			});
 		} else {
 			FailedRejections::<T, I>::append(tx.clone());
@@ -1839,7 +1937,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				let mut maybe_no_of_fetches_remaining =
 					T::FetchesTransfersLimitProvider::maybe_fetches_limit();
 				// Filter out disabled assets and requests that are not ready to be egressed.
-				requests
+				let batch = requests
 					.extract_if(.., |request| {
 						!DisabledEgressAssets::<T, I>::contains_key(request.asset()) &&
 							match request {
@@ -1898,7 +1996,29 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 								),
 							}
 					})
-					.collect()
+					.collect::<Vec<_>>();
+
+				for request in &batch {
+					match request {
+						FetchOrTransfer::Fetch { deposit_address, .. } => {
+							// Other fetches from the same address may still be waiting.
+							if !requests.iter().any(|remaining| {
+								matches!(
+									remaining,
+									FetchOrTransfer::Fetch { deposit_address: remaining_address, .. }
+										if remaining_address == deposit_address
+								)
+							}) {
+								ScheduledFetchBlocks::<T, I>::remove(deposit_address);
+							}
+						},
+						FetchOrTransfer::Transfer { egress_id, .. } => {
+							ScheduledTransferBlocks::<T, I>::remove(egress_id);
+						},
+					}
+				}
+
+				batch
 			});
 
 		if batch_to_send.is_empty() {
@@ -1946,7 +2066,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				egress_transactions.into_iter().for_each(|(egress_transaction, egress_ids)| {
 					let broadcast_id = T::Broadcaster::threshold_sign_and_broadcast_with_callback(
 						egress_transaction,
-						Some(Call::finalise_ingress { addresses: addresses.clone() }.into()),
+						Some(
+							Call::finalise_batch {
+								addresses: addresses.clone(),
+								egress_ids: egress_ids.clone(),
+							}
+							.into(),
+						),
 						|_| None,
 					);
 					Self::deposit_event(Event::<T, I>::BatchBroadcastRequested {
@@ -2078,6 +2204,21 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Err("Insufficient boost funds".into())
 	}
 
+	/// Simulates boosting a deposit of `amount` using the pools with a fee of at most
+	/// `max_boost_fee_bps`. None of the pools are modified. Returns `None` if the current boost
+	/// liquidity is insufficient to boost the full amount.
+	pub fn boost_quote(
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+		max_boost_fee_bps: BasisPoints,
+	) -> Option<BoostOutput<T::TargetChain>> {
+		with_transaction_unchecked(|| {
+			TransactionOutcome::Rollback(
+				Self::try_boosting(asset, amount, max_boost_fee_bps, Default::default()).ok(),
+			)
+		})
+	}
+
 	fn process_channel_deposit_prewitness(
 		DepositWitness { deposit_address, asset, amount, deposit_details }: DepositWitness<
 			T::TargetChain,
@@ -2140,12 +2281,17 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		source_address: Option<ForeignChainAddress>,
 		amount_after_fees: TargetChainAmount<T, I>,
 		origin: DepositOrigin<T, I>,
+		refund_ccm_metadata: Option<CcmDepositMetadata>,
 	) -> DepositAction<T, I> {
 		match action.clone() {
 			ChannelAction::LiquidityProvision { lp_account, .. } => {
 				T::Balance::credit_account(&lp_account, asset.into(), amount_after_fees.into());
 				DepositAction::LiquidityProvision { lp_account }
 			},
+			ChannelAction::PrivateBrokerDeposit { broker_id, .. } => {
+				T::Balance::credit_account(&broker_id, asset.into(), amount_after_fees.into());
+				DepositAction::PrivateBrokerDeposit { broker_id }
+			},
 			ChannelAction::Swap {
 				destination_asset,
 				destination_address,
@@ -2181,6 +2327,62 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					dca_params,
 					origin.into(),
 				);
+				if let Some(refund_ccm_metadata) = refund_ccm_metadata {
+					T::SwapRequestHandler::set_refund_ccm_metadata(
+						swap_request_id,
+						refund_ccm_metadata,
+					);
+				}
+				DepositAction::Swap { swap_request_id }
+			},
+			ChannelAction::SplitSwap {
+				destination_asset,
+				destination_addresses,
+				broker_fees,
+				refund_params,
+				dca_params,
+			} => {
+				let swap_request_id = T::SwapRequestHandler::init_swap_request(
+					asset.into(),
+					amount_after_fees.into(),
+					destination_asset,
+					SwapRequestType::Regular {
+						output_action: SwapOutputAction::SplitEgress {
+							output_addresses: destination_addresses,
+						},
+					},
+					broker_fees,
+					Some(RefundParametersExtended {
+						retry_duration: refund_params.retry_duration,
+						refund_destination: cf_chains::AccountOrAddress::ExternalAddress(
+							refund_params.refund_address,
+						),
+						min_price: refund_params.min_price,
+					}),
+					dca_params,
+					origin.into(),
+				);
+				DepositAction::Swap { swap_request_id }
+			},
+			ChannelAction::FundAccount { account_id, broker_fees, refund_params, dca_params } => {
+				let swap_request_id = T::SwapRequestHandler::init_swap_request(
+					asset.into(),
+					amount_after_fees.into(),
+					Asset::Flip,
+					SwapRequestType::Regular {
+						output_action: SwapOutputAction::FundAccount { account_id },
+					},
+					broker_fees,
+					Some(RefundParametersExtended {
+						retry_duration: refund_params.retry_duration,
+						refund_destination: cf_chains::AccountOrAddress::ExternalAddress(
+							refund_params.refund_address,
+						),
+						min_price: refund_params.min_price,
+					}),
+					dca_params,
+					origin.into(),
+				);
 				DepositAction::Swap { swap_request_id }
 			},
 			ChannelAction::Refund { refund_address, reason } => {
@@ -2192,7 +2394,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							None
 						},
 					};
-				DepositAction::Refund { egress_id, amount: amount_after_fees, reason }
+				DepositAction::Refund {
+					egress_id,
+					amount: amount_after_fees,
+					refund_reason: reason.clone().into(),
+					reason,
+				}
 			},
 		}
 	}
@@ -2259,6 +2466,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			deposit_channel_details.action,
 			block_height,
 			deposit_origin,
+			None, /* refund ccm */
 		) {
 			Ok(outcome) => {
 				if matches!(outcome, FullWitnessDepositOutcome::BoostConsumed) {
@@ -2432,6 +2640,16 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			*id
 		});
 
+		Self::deposit_event(Event::DepositPrewitnessed {
+			deposit_address: deposit_address.clone(),
+			asset,
+			amount,
+			block_height,
+			prewitnessed_deposit_id,
+			channel_id,
+			origin_type: origin.clone().into(),
+		});
+
 		// Only boost on non-zero fee and if the channel isn't already boosted:
 		if T::SafeMode::get().boost_deposits_enabled && boost_fee > 0 {
 			match Self::try_boosting(asset, amount, boost_fee, prewitnessed_deposit_id) {
@@ -2456,6 +2674,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						source_address,
 						amount_after_fees,
 						origin.clone(),
+						None, /* boosted vault swaps never carry a refund ccm */
 					);
 
 					Self::deposit_event(Event::DepositBoosted {
@@ -2514,11 +2733,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			broker_fee.as_ref().map(|Beneficiary { account, .. }| account.clone()),
 		);
 
+		// Vault swaps that attach a CCM to their refunds are not boosted, since boosted swaps are
+		// initiated from the prewitnessed deposit.
+		if vault_deposit_witness.refund_ccm_metadata.is_some() {
+			return;
+		}
+
 		if let Ok(ValidatedVaultSwapParams {
 			broker_fees,
 			channel_metadata,
 			source_address,
 			destination_address,
+			..
 		}) = Self::try_validate_vault_swap(vault_deposit_witness.clone())
 		{
 			let action = ChannelAction::Swap {
@@ -2584,6 +2810,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		action: ChannelAction<T::AccountId, T::TargetChain>,
 		block_height: TargetChainBlockNumber<T, I>,
 		origin: DepositOrigin<T, I>,
+		refund_ccm_metadata: Option<CcmDepositMetadata>,
 	) -> Result<FullWitnessDepositOutcome, DepositFailedReason> {
 		// Deposits can only fail or be rejected if we haven't already boosted.
 		if !matches!(boost_status, BoostStatus::Boosted { .. }) {
@@ -2617,10 +2844,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 					if is_marked_by_broker_or_screening_id {
 						let refund_address = match &action {
-							ChannelAction::Swap { refund_params, .. } =>
-								refund_params.refund_address.clone(),
-							ChannelAction::LiquidityProvision { refund_address, .. } =>
-								refund_address.clone(),
+							ChannelAction::Swap { refund_params, .. } |
+							ChannelAction::SplitSwap { refund_params, .. } |
+							ChannelAction::FundAccount { refund_params, .. } => refund_params.refund_address.clone(),
+							ChannelAction::LiquidityProvision { refund_address, .. } |
+							ChannelAction::PrivateBrokerDeposit { refund_address, .. } => refund_address.clone(),
 							ChannelAction::Refund { refund_address, .. } =>
 								refund_address.clone().into_foreign_chain_address(),
 						};
@@ -2651,6 +2879,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						amount: deposit_amount,
 					},
 				);
+				ScheduledFetchBlocks::<T, I>::mutate(deposit_address, |scheduled_at| {
+					scheduled_at.get_or_insert_with(frame_system::Pallet::<T>::block_number);
+				});
 				Self::deposit_event(Event::<T, I>::DepositFetchesScheduled {
 					channel_id: *channel_id,
 					asset,
@@ -2773,6 +3004,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						source_address,
 						amount_after_fees,
 						origin.clone(),
+						refund_ccm_metadata,
 					);
 
 					Self::deposit_event(Event::DepositFinalised {
@@ -2798,6 +3030,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		vault_deposit_witness: VaultDepositWitness<T, I>,
 	) -> Result<ValidatedVaultSwapParams<T::AccountId>, RefundReason> {
 		let VaultDepositWitness {
+			input_asset: source_asset,
 			output_asset: destination_asset,
 			destination_address,
 			deposit_metadata,
@@ -2805,6 +3038,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			affiliate_fees,
 			refund_params,
 			dca_params,
+			refund_ccm_metadata,
 			..
 		} = vault_deposit_witness.clone();
 
@@ -2813,7 +3047,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Err(RefundReason::InvalidBrokerFees);
 		};
 
-		if T::SwapParameterValidation::validate_broker_fees(&broker_fees).is_err() {
+		if T::SwapParameterValidation::validate_broker_fees(&broker_fees, source_asset.into())
+			.is_err()
+		{
 			return Err(RefundReason::InvalidBrokerFees);
 		}
 
@@ -2844,6 +3080,33 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Err(RefundReason::InvalidRefundParameters);
 		}
 
+		let refund_ccm_metadata = if let Some(metadata) = refund_ccm_metadata {
+			let source_chain = T::TargetChain::get();
+			if !source_chain.ccm_support() {
+				return Err(RefundReason::CcmUnsupportedForTargetChain);
+			}
+
+			if T::CcmValidityChecker::check_and_decode(
+				&metadata,
+				source_asset.into(),
+				T::AddressConverter::to_encoded_address(
+					refund_params.refund_address.clone().into_foreign_chain_address(),
+				),
+			)
+			.is_err()
+			{
+				return Err(RefundReason::CcmInvalidMetadata);
+			}
+
+			Some(CcmDepositMetadata {
+				channel_metadata: metadata,
+				source_chain,
+				source_address: None,
+			})
+		} else {
+			None
+		};
+
 		if let Some(params) = &dca_params {
 			if T::SwapParameterValidation::validate_dca_params(params).is_err() {
 				return Err(RefundReason::InvalidDcaParameters);
@@ -2866,6 +3129,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			channel_metadata,
 			source_address,
 			destination_address: destination_address_internal,
+			refund_ccm_metadata,
 		})
 	}
 
@@ -2888,13 +3152,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			..
 		} = vault_deposit_witness.clone();
 
-		let (action, source_address) =
+		let (action, source_address, refund_ccm_metadata) =
 			match Self::try_validate_vault_swap(vault_deposit_witness.clone()) {
 				Ok(ValidatedVaultSwapParams {
 					broker_fees,
 					channel_metadata,
 					source_address,
 					destination_address,
+					refund_ccm_metadata,
 				}) => (
 					ChannelAction::Swap {
 						destination_asset,
@@ -2906,6 +3171,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						dca_params: dca_params.clone(),
 					},
 					source_address,
+					refund_ccm_metadata,
 				),
 				Err(reason) => (
 					ChannelAction::Refund {
@@ -2913,6 +3179,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						refund_address: refund_params.refund_address,
 					},
 					None,
+					None,
 				),
 			};
 
@@ -2931,6 +3198,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				tx_id.clone(),
 				broker_fee.as_ref().map(|Beneficiary { account, .. }| account.clone()),
 			),
+			refund_ccm_metadata,
 		) {
 			Ok(outcome) =>
 				if matches!(outcome, FullWitnessDepositOutcome::BoostConsumed) {
@@ -3029,20 +3297,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			let next_channel_id = Self::allocate_next_channel_id()?;
 			(
 				DepositChannel::generate_new::<T::AddressDerivation>(next_channel_id, source_asset)
-					.map_err(|e| match e {
-						AddressDerivationError::MissingPolkadotVault =>
-							Error::<T, I>::MissingPolkadotVault,
-						AddressDerivationError::MissingBitcoinVault =>
-							Error::<T, I>::MissingBitcoinVault,
-						AddressDerivationError::BitcoinChannelIdTooLarge =>
-							Error::<T, I>::BitcoinChannelIdTooLarge,
-						AddressDerivationError::SolanaDerivationError { .. } =>
-							Error::<T, I>::SolanaAddressDerivationError,
-						AddressDerivationError::MissingSolanaApiEnvironment =>
-							Error::<T, I>::MissingSolanaApiEnvironment,
-						AddressDerivationError::MissingAssethubVault =>
-							Error::<T, I>::MissingAssethubVault,
-					})?,
+					.map_err(Self::address_derivation_error)?,
 				next_channel_id,
 			)
 		};
@@ -3078,6 +3333,20 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok((channel_id, deposit_address, expiry_height, channel_opening_fee))
 	}
 
+	fn address_derivation_error(error: AddressDerivationError) -> Error<T, I> {
+		match error {
+			AddressDerivationError::MissingPolkadotVault => Error::<T, I>::MissingPolkadotVault,
+			AddressDerivationError::MissingBitcoinVault => Error::<T, I>::MissingBitcoinVault,
+			AddressDerivationError::BitcoinChannelIdTooLarge =>
+				Error::<T, I>::BitcoinChannelIdTooLarge,
+			AddressDerivationError::SolanaDerivationError { .. } =>
+				Error::<T, I>::SolanaAddressDerivationError,
+			AddressDerivationError::MissingSolanaApiEnvironment =>
+				Error::<T, I>::MissingSolanaApiEnvironment,
+			AddressDerivationError::MissingAssethubVault => Error::<T, I>::MissingAssethubVault,
+		}
+	}
+
 	pub fn get_failed_call(broadcast_id: BroadcastId) -> Option<FailedForeignChainCall> {
 		let epoch = T::EpochInfo::epoch_index();
 		FailedForeignChainCalls::<T, I>::get(epoch)
@@ -3117,21 +3386,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		asset: TargetChainAsset<T, I>,
 		available_amount: TargetChainAmount<T, I>,
 	) -> AmountAndFeesWithheld<T, I> {
-		let fee_estimate = match ingress_or_egress {
-			IngressOrEgress::IngressDepositChannel => T::ChainTracking::estimate_ingress_fee(asset),
-			IngressOrEgress::IngressVaultSwap => T::ChainTracking::estimate_ingress_fee_vault_swap()
-			.unwrap_or_else(|| {
-				log::warn!("Unable to get the ingress fee for Vault swaps for ${asset:?}. Ignoring ingres fees.");
-				<T::TargetChain as Chain>::ChainAmount::zero()
-			}),
-			IngressOrEgress::Egress => T::ChainTracking::estimate_egress_fee(asset),
-			IngressOrEgress::EgressCcm { gas_budget, message_length } =>
-				T::ChainTracking::estimate_ccm_fee(asset, gas_budget, message_length)
-				.unwrap_or_else(|| {
-					log::warn!("Unable to get the ccm fee estimate for ${gas_budget:?} ${asset:?}. Ignoring ccm egress fees.");
-					<T::TargetChain as Chain>::ChainAmount::zero()
-				})
-		};
+		let fee_estimate = Self::estimate_fee_in_gas(ingress_or_egress, asset);
 
 		let fees_withheld = if asset == <T::TargetChain as Chain>::GAS_ASSET {
 			// No need to schedule a swap for gas, it's already in the gas asset.
@@ -3163,6 +3418,43 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Estimates the fee that [Self::withhold_ingress_or_egress_fee] withholds for the given
+	/// ingress or egress, measured in units of the given asset.
+	///
+	/// Returns `None` if the fee can't be converted from the gas asset into the given asset.
+	pub fn estimate_ingress_or_egress_fee(
+		ingress_or_egress: IngressOrEgress,
+		asset: TargetChainAsset<T, I>,
+	) -> Option<TargetChainAmount<T, I>> {
+		let fee_estimate = Self::estimate_fee_in_gas(ingress_or_egress, asset);
+		if asset == <T::TargetChain as Chain>::GAS_ASSET {
+			Some(fee_estimate)
+		} else {
+			T::AssetConverter::calculate_input_for_gas_output::<T::TargetChain>(asset, fee_estimate)
+		}
+	}
+
+	fn estimate_fee_in_gas(
+		ingress_or_egress: IngressOrEgress,
+		asset: TargetChainAsset<T, I>,
+	) -> TargetChainAmount<T, I> {
+		match ingress_or_egress {
+			IngressOrEgress::IngressDepositChannel => T::ChainTracking::estimate_ingress_fee(asset),
+			IngressOrEgress::IngressVaultSwap => T::ChainTracking::estimate_ingress_fee_vault_swap()
+			.unwrap_or_else(|| {
+				log::warn!("Unable to get the ingress fee for Vault swaps for ${asset:?}. Ignoring ingres fees.");
+				<T::TargetChain as Chain>::ChainAmount::zero()
+			}),
+			IngressOrEgress::Egress => T::ChainTracking::estimate_egress_fee(asset),
+			IngressOrEgress::EgressCcm { gas_budget, message_length } =>
+				T::ChainTracking::estimate_ccm_fee(asset, gas_budget, message_length)
+				.unwrap_or_else(|| {
+					log::warn!("Unable to get the ccm fee estimate for ${gas_budget:?} ${asset:?}. Ignoring ccm egress fees.");
+					<T::TargetChain as Chain>::ChainAmount::zero()
+				})
+		}
+	}
+
 	/// If a Ccm failed, we want to refund the user their assets.
 	/// This function will schedule a transfer to the fallback address, and emit an event on
 	/// success. IMPORTANT: Currently only used for Solana.
@@ -3302,6 +3594,10 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 								egress_id: egress_details.egress_id,
 							}
 						});
+						ScheduledTransferBlocks::<T, I>::insert(
+							egress_details.egress_id,
+							frame_system::Pallet::<T>::block_number(),
+						);
 
 						Ok(egress_details)
 					} else {
@@ -3322,6 +3618,65 @@ impl<T: Config<I>, I: 'static> ChannelIdAllocator for Pallet<T, I> {
 	}
 }
 
+impl<T: Config<I>, I: 'static> PrivateChannelManager for Pallet<T, I> {
+	type AccountId = T::AccountId;
+
+	fn open_private_channel(
+		broker_id: &T::AccountId,
+		refund_address: ForeignChainAddress,
+	) -> Result<ChannelId, DispatchError> {
+		ensure!(T::SafeMode::get().deposits_enabled, Error::<T, I>::DepositChannelCreationDisabled);
+
+		let channel_id = Self::allocate_next_channel_id()?;
+		let (current_height, ..) = Self::expiry_and_recycle_block_height();
+
+		for asset in TargetChainAsset::<T, I>::iter() {
+			let deposit_channel =
+				DepositChannel::generate_new::<T::AddressDerivation>(channel_id, asset)
+					.map_err(Self::address_derivation_error)?;
+			let deposit_address = deposit_channel.address.clone();
+
+			// Private channels stay open until the broker closes them, so they are not added to
+			// the recycle queue here.
+			DepositChannelLookup::<T, I>::insert(
+				&deposit_address,
+				DepositChannelDetails {
+					owner: broker_id.clone(),
+					deposit_channel,
+					opened_at: current_height,
+					expires_at: TargetChainBlockNumber::<T, I>::max_value(),
+					action: ChannelAction::PrivateBrokerDeposit {
+						broker_id: broker_id.clone(),
+						refund_address: refund_address.clone(),
+					},
+					boost_fee: 0,
+					boost_status: BoostStatus::NotBoosted,
+				},
+			);
+		}
+
+		Ok(channel_id)
+	}
+
+	fn close_private_channel(channel_id: ChannelId) {
+		let (current_height, _, recycle_height) = Self::expiry_and_recycle_block_height();
+
+		for asset in TargetChainAsset::<T, I>::iter() {
+			let Ok(address) = T::AddressDerivation::generate_address(asset, channel_id) else {
+				continue
+			};
+			DepositChannelLookup::<T, I>::mutate(&address, |details| {
+				if let Some(details) = details {
+					details.expires_at = current_height;
+				}
+			});
+			if T::MANAGE_CHANNEL_LIFETIME {
+				DepositChannelRecycleBlocks::<T, I>::append((recycle_height, address));
+			}
+		}
+	}
+}
+
 impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 	type AccountId = T::AccountId;
 	type Amount = T::Amount;
@@ -3392,6 +3747,79 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 			channel_opening_fee,
 		))
 	}
+
+	// This should only be callable by the broker.
+	fn request_split_swap_deposit_address(
+		source_asset: TargetChainAsset<T, I>,
+		destination_asset: Asset,
+		destination_addresses: Vec<(ForeignChainAddress, BasisPoints)>,
+		broker_fees: Beneficiaries<Self::AccountId>,
+		broker_id: T::AccountId,
+		boost_fee: BasisPoints,
+		refund_params: ChannelRefundParametersDecoded,
+		dca_params: Option<DcaParameters>,
+	) -> Result<
+		(ChannelId, ForeignChainAddress, <T::TargetChain as Chain>::ChainBlockNumber, Self::Amount),
+		DispatchError,
+	> {
+		T::SwapParameterValidation::validate_refund_params(refund_params.retry_duration)?;
+		if let Some(params) = &dca_params {
+			T::SwapParameterValidation::validate_dca_params(params)?;
+		}
+
+		let (channel_id, deposit_address, expiry_height, channel_opening_fee) = Self::open_channel(
+			&broker_id,
+			source_asset,
+			ChannelAction::SplitSwap {
+				destination_asset,
+				destination_addresses,
+				broker_fees,
+				refund_params,
+				dca_params,
+			},
+			boost_fee,
+		)?;
+
+		Ok((
+			channel_id,
+			<T::TargetChain as Chain>::ChainAccount::into_foreign_chain_address(deposit_address),
+			expiry_height,
+			channel_opening_fee,
+		))
+	}
+
+	// This should only be callable by the broker.
+	fn request_account_funding_deposit_address(
+		source_asset: TargetChainAsset<T, I>,
+		account_id: T::AccountId,
+		broker_fees: Beneficiaries<Self::AccountId>,
+		broker_id: T::AccountId,
+		boost_fee: BasisPoints,
+		refund_params: ChannelRefundParametersDecoded,
+		dca_params: Option<DcaParameters>,
+	) -> Result<
+		(ChannelId, ForeignChainAddress, <T::TargetChain as Chain>::ChainBlockNumber, Self::Amount),
+		DispatchError,
+	> {
+		T::SwapParameterValidation::validate_refund_params(refund_params.retry_duration)?;
+		if let Some(params) = &dca_params {
+			T::SwapParameterValidation::validate_dca_params(params)?;
+		}
+
+		let (channel_id, deposit_address, expiry_height, channel_opening_fee) = Self::open_channel(
+			&broker_id,
+			source_asset,
+			ChannelAction::FundAccount { account_id, broker_fees, refund_params, dca_params },
+			boost_fee,
+		)?;
+
+		Ok((
+			channel_id,
+			<T::TargetChain as Chain>::ChainAccount::into_foreign_chain_address(deposit_address),
+			expiry_height,
+			channel_opening_fee,
+		))
+	}
 }
 
 impl<T: Config<I>, I: 'static> IngressEgressFeeApi<T::TargetChain> for Pallet<T, I> {
@@ -127,6 +127,7 @@ impl Config<Instance1> for Test {
 	type ChainApiCall = MockEthereumApiCall<MockEvmEnvironment>;
 	type Broadcaster = MockEgressBroadcasterEth;
 	type DepositHandler = MockDepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = ChainTracker<Ethereum>;
 	type WeightInfo = ();
 	type NetworkEnvironment = MockNetworkEnvironmentProvider;
@@ -156,6 +157,7 @@ impl Config<Instance2> for Test {
 	type ChainApiCall = MockBitcoinApiCall<MockBtcEnvironment>;
 	type Broadcaster = MockEgressBroadcasterBtc;
 	type DepositHandler = MockDepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = ChainTracker<Bitcoin>;
 	type WeightInfo = ();
 	type NetworkEnvironment = MockNetworkEnvironmentProvider;
@@ -25,10 +25,11 @@ use crate::{
 	Event as PalletEvent, FailedForeignChainCall, FailedForeignChainCalls, FailedRejections,
 	FetchOrTransfer, MinimumDeposit, NetworkFeeDeductionFromBoostPercent, Pallet,
 	PalletConfigUpdate, PalletSafeMode, PrewitnessedDepositIdCounter, RefundReason,
-	ScheduledEgressCcm, ScheduledEgressFetchOrTransfer, VaultDepositWitness, WitnessSafetyMargin,
+	ScheduledEgressCcm, ScheduledEgressFetchOrTransfer, ScheduledFetchBlocks,
+	ScheduledTransferBlocks, VaultDepositWitness, WitnessSafetyMargin,
 };
 use cf_chains::{
-	address::{AddressConverter, EncodedAddress},
+	address::{AddressConverter, AddressDerivationApi, EncodedAddress},
 	assets::{any::Asset, eth::Asset as EthAsset},
 	btc::{BitcoinNetwork, ScriptPubkey},
 	eth::Address as EthereumAddress,
@@ -40,7 +41,7 @@ use cf_chains::{
 };
 use cf_primitives::{
 	AffiliateShortId, Affiliates, AssetAmount, BasisPoints, Beneficiaries, Beneficiary, ChannelId,
-	DcaParameters, ForeignChain, MAX_AFFILIATES,
+	DcaParameters, ForeignChain, SwapRefundReason, MAX_AFFILIATES,
 };
 use cf_test_utilities::{assert_events_eq, assert_has_event, assert_has_matching_event};
 use cf_traits::{
@@ -60,7 +61,8 @@ use cf_traits::{
 		swap_request_api::{MockSwapRequest, MockSwapRequestHandler},
 	},
 	BalanceApi, DepositApi, EgressApi, EpochInfo, FetchesTransfersLimitProvider, FundingInfo,
-	GetBlockHeight, SafeMode, ScheduledEgressDetails, SwapOutputAction, SwapRequestType,
+	GetBlockHeight, PrivateChannelManager, SafeMode, ScheduledEgressDetails, SwapOutputAction,
+	SwapRequestType,
 };
 
 #[cfg(test)]
@@ -74,6 +76,7 @@ use frame_support::{
 };
 use sp_core::{bounded_vec, H160, U256};
 use sp_runtime::{DispatchError, DispatchResult, Percent};
+use strum::IntoEnumIterator;
 
 const ALICE_ETH_ADDRESS: EthereumAddress = H160([100u8; 20]);
 const BOB_ETH_ADDRESS: EthereumAddress = H160([101u8; 20]);
@@ -337,6 +340,27 @@ fn can_schedule_deposit_fetch() {
 	});
 }
 
+#[test]
+fn scheduled_egress_blocks_are_tracked_until_sent() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		let egress_id =
+			EthereumIngressEgress::schedule_egress(ETH_ETH, 1_000, ALICE_ETH_ADDRESS, None)
+				.unwrap()
+				.egress_id;
+		let (_, deposit_address) = request_address_and_deposit(1u64, EthAsset::Eth);
+
+		assert_eq!(ScheduledTransferBlocks::<Test, Instance1>::get(egress_id), Some(5));
+		assert_eq!(ScheduledFetchBlocks::<Test, Instance1>::get(deposit_address), Some(5));
+
+		EthereumIngressEgress::on_finalize(5);
+
+		assert!(ScheduledEgressFetchOrTransfer::<Test, Instance1>::get().is_empty());
+		assert_eq!(ScheduledTransferBlocks::<Test, Instance1>::get(egress_id), None);
+		assert_eq!(ScheduledFetchBlocks::<Test, Instance1>::get(deposit_address), None);
+	});
+}
+
 #[test]
 fn on_finalize_can_send_batch_all() {
 	new_test_ext().execute_with(|| {
@@ -850,7 +874,7 @@ fn multi_use_deposit_same_block() {
 			);
 			assert_matches!(
 				pending_callbacks.last().unwrap(),
-				RuntimeCall::EthereumIngressEgress(PalletCall::finalise_ingress { .. })
+				RuntimeCall::EthereumIngressEgress(PalletCall::finalise_batch { .. })
 			);
 		})
 		.then_execute_at_next_block(|ctx| {
@@ -1749,6 +1773,54 @@ fn ingress_fee_is_withheld_or_scheduled_for_swap() {
 	test_ingress_or_egress_fee_is_withheld_or_scheduled_for_swap(ingress_function)
 }
 
+#[test]
+fn egress_fee_estimate_matches_withheld_fee() {
+	new_test_ext().execute_with(|| {
+		const GAS_FEE: u128 = DEFAULT_DEPOSIT_AMOUNT / 10;
+		ChainTracker::<cf_chains::Ethereum>::set_fee(GAS_FEE);
+		MockAssetConverter::set_price(cf_primitives::Asset::Flip, cf_primitives::Asset::Eth, 2u128);
+
+		assert_eq!(
+			EthereumIngressEgress::estimate_ingress_or_egress_fee(
+				crate::IngressOrEgress::Egress,
+				EthAsset::Eth
+			),
+			Some(GAS_FEE)
+		);
+		let estimate = EthereumIngressEgress::estimate_ingress_or_egress_fee(
+			crate::IngressOrEgress::Egress,
+			EthAsset::Flip,
+		);
+		assert_eq!(estimate, Some(2 * GAS_FEE));
+		// Estimating the fee has no side effects.
+		assert!(MockSwapRequestHandler::<Test>::get_swap_requests().is_empty());
+
+		<EthereumIngressEgress as EgressApi<Ethereum>>::schedule_egress(
+			EthAsset::Flip,
+			DEFAULT_DEPOSIT_AMOUNT,
+			Default::default(),
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			MockSwapRequestHandler::<Test>::get_swap_requests()
+				.into_iter()
+				.map(|request| request.input_amount)
+				.collect::<Vec<_>>(),
+			vec![estimate.unwrap()]
+		);
+
+		// Without a price, the fee can't be estimated in the input asset.
+		assert_eq!(
+			EthereumIngressEgress::estimate_ingress_or_egress_fee(
+				crate::IngressOrEgress::Egress,
+				EthAsset::Usdc
+			),
+			None
+		);
+	});
+}
+
 #[test]
 fn safe_mode_prevents_deposit_channel_creation() {
 	new_test_ext().execute_with(|| {
@@ -2031,6 +2103,7 @@ fn submit_vault_swap_request(
 			refund_params,
 			dca_params,
 			boost_fee,
+			refund_ccm_metadata: None,
 		}),
 	)
 }
@@ -2082,6 +2155,49 @@ fn can_request_swap_via_extrinsic() {
 	});
 }
 
+#[test]
+fn vault_swaps_can_attach_ccm_to_refunds() {
+	let refund_ccm = CcmChannelMetadata {
+		message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
+		gas_budget: 1_000,
+		ccm_additional_data: vec![].try_into().unwrap(),
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(EthereumIngressEgress::vault_swap_request(
+			RuntimeOrigin::root(),
+			0,
+			Box::new(VaultDepositWitness {
+				input_asset: EthAsset::Eth,
+				deposit_address: Some(Default::default()),
+				channel_id: Some(0),
+				deposit_amount: 1_000,
+				deposit_details: DepositDetails { tx_hashes: None },
+				output_asset: Asset::Flip,
+				destination_address: EncodedAddress::Eth([1; 20]),
+				deposit_metadata: None,
+				tx_id: Default::default(),
+				broker_fee: Some(Beneficiary { account: BROKER, bps: 0 }),
+				affiliate_fees: Default::default(),
+				refund_params: ETH_REFUND_PARAMS,
+				dca_params: None,
+				boost_fee: 0,
+				refund_ccm_metadata: Some(refund_ccm.clone()),
+			}),
+		));
+
+		assert_eq!(MockSwapRequestHandler::<Test>::get_swap_requests().len(), 1);
+		assert_eq!(
+			MockSwapRequestHandler::<Test>::get_refund_ccm_metadata(0.into()),
+			Some(CcmDepositMetadata {
+				channel_metadata: refund_ccm,
+				source_chain: ForeignChain::Ethereum,
+				source_address: None,
+			})
+		);
+	});
+}
+
 #[test]
 fn vault_swaps_support_affiliate_fees() {
 	new_test_ext().execute_with(|| {
@@ -2381,6 +2497,7 @@ fn failed_ccm_deposit_can_deposit_event() {
 			RuntimeEvent::EthereumIngressEgress(Event::DepositFinalised {
 				action: DepositAction::Refund {
 					reason: RefundReason::CcmUnsupportedForTargetChain,
+					refund_reason: SwapRefundReason::CcmInvalid,
 					..
 				},
 				..
@@ -2851,6 +2968,7 @@ mod evm_transaction_rejection {
 				RuntimeEvent::EthereumIngressEgress(PalletEvent::TransactionRejectedByBroker {
 					broadcast_id,
 					tx_id,
+					..
 				}) if tx_id.deposit_ids().unwrap().contains(&TAINTED_TX_ID_1) => Some(broadcast_id),
 				_ => None,
 			})
@@ -3283,10 +3401,11 @@ fn test_various_refund_reasons() {
 				RuntimeEvent::EthereumIngressEgress(Event::DepositFinalised {
 					action: DepositAction::Refund {
 						reason,
+						refund_reason,
 						..
 					},
 					..
-				}) if *reason == expected_reason
+				}) if *reason == expected_reason && *refund_reason == SwapRefundReason::InvalidParameters
 			);
 
 			System::reset_events();
@@ -3315,6 +3434,7 @@ fn test_various_refund_reasons() {
 			},
 			dca_params: None,
 			boost_fee: 0,
+			refund_ccm_metadata: None,
 		},
 		RefundReason::InvalidBrokerFees,
 	);
@@ -3340,6 +3460,7 @@ fn test_various_refund_reasons() {
 			},
 			dca_params: None,
 			boost_fee: 0,
+			refund_ccm_metadata: None,
 		},
 		RefundReason::InvalidRefundParameters,
 	);
@@ -3365,7 +3486,57 @@ fn test_various_refund_reasons() {
 			},
 			dca_params: Some(DcaParameters { number_of_chunks: 0, chunk_interval: 100 }),
 			boost_fee: 0,
+			refund_ccm_metadata: None,
 		},
 		RefundReason::InvalidDcaParameters,
 	);
 }
+
+#[test]
+fn private_broker_channels_are_witnessed_until_closed() {
+	const DEPOSIT_AMOUNT: AssetAmount = 1_000;
+
+	new_test_ext().execute_with(|| {
+		let channel_id = <EthereumIngressEgress as PrivateChannelManager>::open_private_channel(
+			&BROKER,
+			ForeignChainAddress::Eth(Default::default()),
+		)
+		.unwrap();
+
+		// The mock address derivation returns the same address for every asset.
+		let deposit_address =
+			<Test as crate::Config<Instance1>>::AddressDerivation::generate_address(
+				EthAsset::Eth,
+				channel_id,
+			)
+			.unwrap();
+		let details = DepositChannelLookup::<Test, Instance1>::get(deposit_address)
+			.expect("private channel addresses must be tracked");
+		assert_eq!(details.owner, BROKER);
+		assert_eq!(details.expires_at, u64::MAX);
+
+		let asset = details.deposit_channel.asset;
+		assert_ok!(EthereumIngressEgress::process_channel_deposit_full_witness_inner(
+			&DepositWitness {
+				deposit_address,
+				asset,
+				amount: DEPOSIT_AMOUNT,
+				deposit_details: Default::default(),
+			},
+			Default::default()
+		));
+		assert!(MockBalance::get_balance(&BROKER, asset.into()) > 0);
+
+		<EthereumIngressEgress as PrivateChannelManager>::close_private_channel(channel_id);
+
+		assert!(
+			DepositChannelLookup::<Test, Instance1>::get(deposit_address)
+				.unwrap()
+				.expires_at < u64::MAX
+		);
+		assert_eq!(
+			crate::DepositChannelRecycleBlocks::<Test, Instance1>::get().len(),
+			EthAsset::iter().count()
+		);
+	});
+}
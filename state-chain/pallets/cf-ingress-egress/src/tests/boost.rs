@@ -18,7 +18,7 @@ use super::*;
 use crate as pallet_cf_ingress_egress;
 use cf_chains::{DepositOriginType, FeeEstimationApi};
 use cf_primitives::{AssetAmount, BasisPoints, PrewitnessedDepositId, SwapRequestId};
-use cf_test_utilities::assert_event_sequence;
+use cf_test_utilities::{assert_event_sequence, assert_has_event};
 use cf_traits::{
 	mocks::{
 		account_role_registry::MockAccountRoleRegistry, tracked_data_provider::TrackedDataProvider,
@@ -30,7 +30,7 @@ use sp_runtime::Percent;
 use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 
 use crate::{
-	BoostDelayBlocks, BoostPoolId, BoostPoolTier, BoostPools, Event, PalletSafeMode,
+	BoostDelayBlocks, BoostOutput, BoostPoolId, BoostPoolTier, BoostPools, Event, PalletSafeMode,
 	PendingPrewitnessedDeposits,
 };
 
@@ -657,6 +657,29 @@ fn zero_boost_fee_deposit() {
 	});
 }
 
+#[test]
+fn prewitnessed_deposit_is_reported_even_if_not_boosted() {
+	new_test_ext().execute_with(|| {
+		const DEPOSIT_AMOUNT: AssetAmount = 250_000_000;
+
+		setup();
+
+		let (channel_id, deposit_address) = request_deposit_address_eth(LP_ACCOUNT, 0);
+		let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+
+		assert_has_event::<Test>(RuntimeEvent::EthereumIngressEgress(Event::DepositPrewitnessed {
+			deposit_address: Some(deposit_address),
+			asset: EthAsset::Eth,
+			amount: DEPOSIT_AMOUNT,
+			block_height: 0,
+			prewitnessed_deposit_id: deposit_id,
+			channel_id: Some(channel_id),
+			origin_type: DepositOriginType::DepositChannel,
+		}));
+		assert_not_boosted(deposit_address);
+	});
+}
+
 #[test]
 fn skip_zero_amount_pool() {
 	// 10 bps has 0 available funds, but we are able to skip it and
@@ -737,6 +760,52 @@ fn insufficient_funds_for_boost() {
 	});
 }
 
+#[test]
+fn boost_quote_uses_the_cheapest_pools_first() {
+	new_test_ext().execute_with(|| {
+		const DEPOSIT_AMOUNT: AssetAmount = 1_000_000_000;
+
+		setup();
+
+		assert_ok!(EthereumIngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			500_000_000,
+			TIER_5_BPS
+		));
+		assert_ok!(EthereumIngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_2),
+			EthAsset::Eth,
+			INIT_BOOSTER_ETH_BALANCE,
+			TIER_10_BPS
+		));
+
+		// The 5bps pool is used up first, the rest of the amount is boosted by the 10bps pool:
+		let BoostOutput { used_pools, total_fee } =
+			EthereumIngressEgress::boost_quote(EthAsset::Eth, DEPOSIT_AMOUNT, TIER_10_BPS).unwrap();
+		assert_eq!(used_pools.keys().copied().collect::<Vec<_>>(), vec![TIER_5_BPS, TIER_10_BPS]);
+		assert_eq!(used_pools.values().sum::<AssetAmount>(), DEPOSIT_AMOUNT);
+
+		// The pools are left untouched:
+		assert_eq!(get_available_amount(EthAsset::Eth, TIER_5_BPS), 500_000_000);
+		assert_eq!(get_available_amount(EthAsset::Eth, TIER_10_BPS), INIT_BOOSTER_ETH_BALANCE);
+
+		// The quote matches the actual boost of the deposit:
+		let (_, deposit_address) = request_deposit_address_eth(LP_ACCOUNT, TIER_10_BPS);
+		prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::EthereumIngressEgress(Event::DepositBoosted { amounts, boost_fee, .. })
+				if *amounts == used_pools && *boost_fee == total_fee
+		);
+
+		// The 5bps pool alone can't boost the full amount:
+		assert!(
+			EthereumIngressEgress::boost_quote(EthAsset::Eth, DEPOSIT_AMOUNT, TIER_5_BPS).is_none()
+		);
+	});
+}
+
 #[test]
 fn lost_funds_are_acknowledged_by_boost_pool() {
 	new_test_ext().execute_with(|| {
@@ -1253,6 +1322,7 @@ mod vault_swaps {
 				},
 				dca_params: None,
 				boost_fee: 5,
+				refund_ccm_metadata: None,
 			};
 
 			// Prewitnessing a deposit for the first time should result in a boost:
@@ -1592,6 +1662,7 @@ mod delayed_boosting {
 			},
 			dca_params: None,
 			boost_fee: 5,
+			refund_ccm_metadata: None,
 		}
 	}
 
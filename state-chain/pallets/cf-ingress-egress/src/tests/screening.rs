@@ -403,6 +403,7 @@ fn send_funds_back_after_they_have_been_rejected() {
 			RuntimeEvent::BitcoinIngressEgress(Event::TransactionRejectedByBroker {
 				broadcast_id: _,
 				tx_id: _,
+				refund_reason: SwapRefundReason::Screened,
 			})
 		);
 
@@ -559,6 +560,7 @@ fn gets_rejected_if_vault_transaction_was_aborted_and_rejected() {
 			},
 			dca_params: None,
 			boost_fee: 0,
+			refund_ccm_metadata: None,
 		};
 
 		assert_ok!(BitcoinIngressEgress::mark_transaction_for_rejection(
@@ -341,6 +341,9 @@ pub mod pallet {
 			)
 		}
 
+		/// Swap some amount of an asset from the free balance into another asset. The swap is
+		/// routed through the AMM like any other swap, but the output (or any refund) is credited
+		/// straight back to the LP's free balance, so no deposit channel or egress is involved.
 		#[pallet::call_index(7)]
 		#[pallet::weight(T::WeightInfo::schedule_swap())]
 		pub fn schedule_swap(
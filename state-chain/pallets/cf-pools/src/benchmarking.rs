@@ -28,19 +28,26 @@ use frame_support::{
 };
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 
-fn new_lp_account<T: Chainflip + Config>() -> T::AccountId {
-	let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+fn new_lp_accounts<T: Chainflip + Config>(n: u32) -> Vec<T::AccountId> {
+	let callers = <T as Chainflip>::AccountRoleRegistry::generate_whitelisted_callers_with_role(
 		AccountRole::LiquidityProvider,
+		n,
 	)
 	.unwrap();
-	for address in [
-		ForeignChainAddress::Eth(Default::default()),
-		ForeignChainAddress::Dot(Default::default()),
-		ForeignChainAddress::Btc(cf_chains::btc::ScriptPubkey::P2PKH(Default::default())),
-	] {
-		T::LpRegistrationApi::register_liquidity_refund_address(&caller, address);
+	for caller in &callers {
+		for address in [
+			ForeignChainAddress::Eth(Default::default()),
+			ForeignChainAddress::Dot(Default::default()),
+			ForeignChainAddress::Btc(cf_chains::btc::ScriptPubkey::P2PKH(Default::default())),
+		] {
+			T::LpRegistrationApi::register_liquidity_refund_address(caller, address);
+		}
 	}
-	caller
+	callers
+}
+
+fn new_lp_account<T: Chainflip + Config>() -> T::AccountId {
+	new_lp_accounts::<T>(1).remove(0)
 }
 
 #[benchmarks]
@@ -74,6 +81,7 @@ mod benchmarks {
 				i,
 				Some(0),
 				1_000,
+				None,
 			));
 		}
 	}
@@ -97,6 +105,35 @@ mod benchmarks {
 		assert!(Pools::<T>::get(AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap()).is_some());
 	}
 
+	#[benchmark]
+	fn new_fee_tier_pool() {
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		let call = Call::<T>::new_fee_tier_pool {
+			base_asset: Asset::Eth,
+			quote_asset: Asset::Usdc,
+			fee_hundredth_pips: 500u32,
+			initial_price: price_at_tick(0).unwrap(),
+		};
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert!(FeeTierPools::<T>::contains_key(
+			AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap(),
+			500
+		));
+	}
+
 	#[benchmark]
 	fn update_range_order() {
 		let caller = new_lp_account::<T>();
@@ -155,6 +192,43 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn set_fee_tier_range_order() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		assert_ok!(Pallet::<T>::new_fee_tier_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			500,
+			price_at_tick(0).unwrap()
+		));
+		T::LpBalance::credit_account(&caller, Asset::Eth, 1_000_000);
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000);
+
+		create_some_orders::<T>(caller.clone());
+
+		#[extrinsic_call]
+		set_fee_tier_range_order(
+			RawOrigin::Signed(caller),
+			Asset::Eth,
+			Asset::Usdc,
+			500,
+			0,
+			Some(-100..100),
+			RangeOrderSize::AssetAmounts {
+				maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+				minimum: AssetAmounts { base: 500_000, quote: 500_000 },
+			},
+		);
+	}
+
 	#[benchmark]
 	fn update_limit_order() {
 		let caller = new_lp_account::<T>();
@@ -206,6 +280,7 @@ mod benchmarks {
 			0,
 			Some(100),
 			1_000,
+			None,
 		);
 	}
 
@@ -229,6 +304,7 @@ mod benchmarks {
 			0,
 			Some(0),
 			10_000,
+			None,
 		));
 		assert_ok!(Pallet::<T>::set_limit_order(
 			RawOrigin::Signed(caller.clone()).into(),
@@ -238,6 +314,7 @@ mod benchmarks {
 			1,
 			Some(0),
 			10_000,
+			None,
 		));
 		assert_ok!(Pallet::<T>::swap_single_leg(STABLE_ASSET, Asset::Eth, 1_000));
 		let fee = 1_000;
@@ -284,6 +361,7 @@ mod benchmarks {
 				id: 0,
 				option_tick: Some(0),
 				sell_amount: 100,
+				expiry_block: None,
 			}),
 			BlockNumberFor::<T>::from(5u32),
 		);
@@ -360,5 +438,344 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn set_orders_batch(c: Linear<0, MAX_ORDERS_DELETE>, p: Linear<0, MAX_ORDERS_SET>) {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		T::LpBalance::credit_account(&caller, Asset::Eth, 1_000_000_000);
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000_000);
+
+		let range_order_size = RangeOrderSize::AssetAmounts {
+			maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+			minimum: AssetAmounts { base: 500_000, quote: 500_000 },
+		};
+		let cancel = (1i32..=c as i32)
+			.map(|i| {
+				assert_ok!(Pallet::<T>::set_range_order(
+					RawOrigin::Signed(caller.clone()).into(),
+					Asset::Eth,
+					Asset::Usdc,
+					i as u64,
+					Some(-i..i),
+					range_order_size,
+				));
+				CloseOrder::Range { base_asset: Asset::Eth, quote_asset: Asset::Usdc, id: i as u64 }
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		let place = (1i32..=p as i32)
+			.map(|i| NewOrder::Range {
+				base_asset: Asset::Eth,
+				quote_asset: Asset::Usdc,
+				id: (MAX_ORDERS_DELETE as i32 + i) as u64,
+				option_tick_range: Some(-i..i),
+				size: range_order_size,
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+
+		#[extrinsic_call]
+		set_orders_batch(RawOrigin::Signed(caller), cancel, place);
+	}
+
+	#[benchmark]
+	fn simulate_swap() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		create_some_orders::<T>(caller);
+		let asset_pair = AssetPair::new(Asset::Eth, Asset::Usdc).unwrap();
+
+		#[block]
+		{
+			let mut pool = Pools::<T>::get(asset_pair).unwrap();
+			assert_ok!(Pallet::<T>::swap_in_pool(&mut pool, &asset_pair, Side::Sell, 1_000));
+		}
+	}
+
+	#[benchmark]
+	fn set_auto_rebalancing_range_order() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		T::LpBalance::credit_account(&caller, Asset::Eth, 1_000_000);
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000);
+
+		create_some_orders::<T>(caller.clone());
+
+		#[extrinsic_call]
+		set_auto_rebalancing_range_order(
+			RawOrigin::Signed(caller.clone()),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			100,
+			10,
+			RangeOrderSize::AssetAmounts {
+				maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+				minimum: AssetAmounts { base: 500_000, quote: 500_000 },
+			},
+		);
+
+		assert!(AutoRebalancingRangeOrders::<T>::contains_key(
+			AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap(),
+			(caller, 0)
+		));
+	}
+
+	#[benchmark]
+	fn stop_auto_rebalancing() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		T::LpBalance::credit_account(&caller, Asset::Eth, 1_000_000);
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000);
+		assert_ok!(Pallet::<T>::set_auto_rebalancing_range_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			100,
+			10,
+			RangeOrderSize::AssetAmounts {
+				maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+				minimum: AssetAmounts { base: 500_000, quote: 500_000 },
+			},
+		));
+
+		#[extrinsic_call]
+		stop_auto_rebalancing(RawOrigin::Signed(caller.clone()), Asset::Eth, Asset::Usdc, 0);
+
+		assert!(!AutoRebalancingRangeOrders::<T>::contains_key(
+			AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap(),
+			(caller, 0)
+		));
+	}
+
+	#[benchmark]
+	fn set_range_order_auto_compounding() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		create_some_orders::<T>(caller.clone());
+
+		#[extrinsic_call]
+		set_range_order_auto_compounding(
+			RawOrigin::Signed(caller.clone()),
+			Asset::Eth,
+			Asset::Usdc,
+			1,
+			true,
+		);
+
+		assert!(AutoCompoundingRangeOrders::<T>::contains_key((
+			caller,
+			AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap(),
+			OrderKind::Range { pool_fee_hundredth_pips: None },
+			1,
+		)));
+	}
+
+	#[benchmark]
+	fn offer_order_transfer() {
+		let mut lps = new_lp_accounts::<T>(2);
+		let (from, to) = (lps.remove(0), lps.remove(0));
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		create_some_orders::<T>(from.clone());
+
+		#[extrinsic_call]
+		offer_order_transfer(
+			RawOrigin::Signed(from),
+			CloseOrder::Range { base_asset: Asset::Eth, quote_asset: Asset::Usdc, id: 1 },
+			Some(to),
+		);
+	}
+
+	#[benchmark]
+	fn accept_order_transfer() {
+		let mut lps = new_lp_accounts::<T>(2);
+		let (from, to) = (lps.remove(0), lps.remove(0));
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		create_some_orders::<T>(from.clone());
+		create_some_orders::<T>(to.clone());
+		let order = CloseOrder::Range { base_asset: Asset::Eth, quote_asset: Asset::Usdc, id: 1 };
+		assert_ok!(Pallet::<T>::offer_order_transfer(
+			RawOrigin::Signed(from.clone()).into(),
+			order,
+			Some(to.clone()),
+		));
+
+		#[extrinsic_call]
+		accept_order_transfer(RawOrigin::Signed(to), from, order, 0);
+	}
+
+	#[benchmark]
+	fn fund_incentive_programme() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		// All but one of the programmes are taken, so that they are all counted:
+		for asset in Asset::all()
+			.filter(|asset| ![STABLE_ASSET, Asset::Eth].contains(asset))
+			.take(MAX_INCENTIVE_PROGRAMMES as usize - 1)
+		{
+			IncentiveProgrammes::<T>::insert(
+				AssetPair::new(asset, STABLE_ASSET).unwrap(),
+				IncentiveProgramme {
+					funder: caller.clone(),
+					reward_asset: Asset::Usdc,
+					reward_per_block: 1,
+					remaining: 1_000,
+				},
+			);
+		}
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000);
+
+		#[extrinsic_call]
+		fund_incentive_programme(
+			RawOrigin::Signed(caller),
+			Asset::Eth,
+			Asset::Usdc,
+			Asset::Usdc,
+			1_000_000,
+			1_000,
+		);
+
+		assert!(IncentiveProgrammes::<T>::contains_key(
+			AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap()
+		));
+	}
+
+	#[benchmark]
+	fn stop_incentive_programme() {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000);
+		assert_ok!(Pallet::<T>::fund_incentive_programme(
+			RawOrigin::Signed(caller).into(),
+			Asset::Eth,
+			Asset::Usdc,
+			Asset::Usdc,
+			1_000_000,
+			1_000,
+		));
+		let call = Call::<T>::stop_incentive_programme {
+			base_asset: Asset::Eth,
+			quote_asset: Asset::Usdc,
+		};
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert!(IncentiveProgrammes::<T>::iter_keys().next().is_none());
+	}
+
+	#[benchmark]
+	fn claim_incentive_rewards() {
+		let caller = new_lp_account::<T>();
+		IncentiveRewards::<T>::insert(&caller, Asset::Usdc, 1_000);
+
+		#[extrinsic_call]
+		claim_incentive_rewards(RawOrigin::Signed(caller.clone()), Asset::Usdc);
+
+		assert_eq!(IncentiveRewards::<T>::get(&caller, Asset::Usdc), 0);
+	}
+
+	#[benchmark]
+	fn distribute_incentive_rewards(n: Linear<0, 100>) {
+		let caller = new_lp_account::<T>();
+		assert_ok!(Pallet::<T>::new_pool(
+			T::EnsureGovernance::try_successful_origin().unwrap(),
+			Asset::Eth,
+			Asset::Usdc,
+			0,
+			price_at_tick(0).unwrap()
+		));
+		T::LpBalance::credit_account(&caller, Asset::Eth, 1_000_000_000);
+		T::LpBalance::credit_account(&caller, Asset::Usdc, 1_000_000_000);
+		// All orders are in range, so that each of them is rewarded:
+		for i in 1..=n {
+			assert_ok!(Pallet::<T>::set_range_order(
+				RawOrigin::Signed(caller.clone()).into(),
+				Asset::Eth,
+				Asset::Usdc,
+				i as u64,
+				Some(-(i as i32)..i as i32),
+				RangeOrderSize::AssetAmounts {
+					maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+					minimum: AssetAmounts { base: 500_000, quote: 500_000 },
+				},
+			));
+		}
+		assert_ok!(Pallet::<T>::fund_incentive_programme(
+			RawOrigin::Signed(caller).into(),
+			Asset::Eth,
+			Asset::Usdc,
+			Asset::Usdc,
+			1_000_000,
+			1_000,
+		));
+
+		#[block]
+		{
+			Pallet::<T>::distribute_incentive_rewards();
+		}
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
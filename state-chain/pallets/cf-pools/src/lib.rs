@@ -18,16 +18,19 @@
 use cf_amm::{
 	common::{PoolPairsMap, Side},
 	limit_orders::{self, Collected, PositionInfo},
-	math::{bounded_sqrt_price, Amount, Price, SqrtPriceQ64F96, Tick, MAX_SQRT_PRICE},
+	math::{
+		bounded_sqrt_price, output_amount_floor, sqrt_price_to_price, tick_at_sqrt_price, Amount,
+		Price, SqrtPriceQ64F96, Tick, MAX_SQRT_PRICE, MAX_TICK, MIN_TICK,
+	},
 	range_orders::{self, Liquidity},
 	PoolState,
 };
 use cf_chains::assets::any::AssetMap;
-use cf_primitives::{chains::assets::any, Asset, AssetAmount, STABLE_ASSET};
+use cf_primitives::{chains::assets::any, Asset, AssetAmount, EpochIndex, SwapId, STABLE_ASSET};
 use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
-	impl_pallet_safe_mode, AccountRoleRegistry, BalanceApi, Chainflip, LpOrdersWeightsProvider,
-	PoolApi, SwapRequestHandler, SwappingApi,
+	impl_pallet_safe_mode, AccountRoleRegistry, BalanceApi, Chainflip, EpochInfo,
+	LpOrdersWeightsProvider, PoolApi, SwapRequestHandler, SwappingApi,
 };
 
 use cf_traits::LpRegistration;
@@ -38,13 +41,15 @@ use frame_support::{
 	pallet_prelude::*,
 	storage::with_storage_layer,
 	traits::{OnKilledAccount, OriginTrait, StorageVersion, UnfilteredDispatchable},
-	transactional,
 };
 use frame_system::{pallet_prelude::OriginFor, WeightInfo as SystemWeightInfo};
 pub use pallet::*;
 use serde::{Deserialize, Serialize};
-use sp_arithmetic::traits::{SaturatedConversion, Zero};
-use sp_std::{boxed::Box, vec::Vec};
+use sp_arithmetic::{
+	traits::{SaturatedConversion, Saturating, Zero},
+	Permill,
+};
+use sp_std::{boxed::Box, collections::btree_map::BTreeMap, vec::Vec};
 
 mod benchmarking;
 pub mod migrations;
@@ -68,6 +73,111 @@ impl<const N: u128> Get<SweepingThresholds> for StablecoinDefaults<N> {
 }
 
 pub const MAX_ORDERS_DELETE: u32 = 100;
+pub const MAX_ORDERS_SET: u32 = 100;
+/// The most orders returned in a single page of a pool's orders.
+pub const MAX_POOL_ORDERS_PAGE_SIZE: u32 = 1_000;
+/// The longest window over which a time-weighted average price can be queried.
+pub const MAX_TWAP_WINDOW_BLOCKS: u32 = 3600 / cf_primitives::SECONDS_PER_BLOCK as u32; // 1 hour
+/// The longest a price circuit breaker can suspend swaps in a pool for.
+pub const MAX_CIRCUIT_BREAKER_COOL_DOWN_BLOCKS: u32 =
+	24 * 3600 / cf_primitives::SECONDS_PER_BLOCK as u32; // 1 day
+/// The most the observed tick can move per block. Limits how far the time-weighted average price
+/// can be moved by manipulating a pool's price for a single block.
+pub const MAX_OBSERVED_TICK_CHANGE_PER_BLOCK: Tick = 100; // ~1%
+/// The minimum number of blocks between two rebalances of the same auto-rebalancing range order.
+pub const MIN_BLOCKS_BETWEEN_REBALANCES: u32 = 600 / cf_primitives::SECONDS_PER_BLOCK as u32; // 10 minutes
+/// The most pools that can have an incentive programme at the same time.
+pub const MAX_INCENTIVE_PROGRAMMES: u32 = 10;
+/// The most range orders whose incentive rewards are computed in a single block, across all
+/// incentive programmes. A programme whose pool has more range orders than are left in the budget
+/// doesn't pay out any rewards that block.
+pub const MAX_INCENTIVISED_RANGE_ORDERS_PER_BLOCK: u32 = 1_000;
+
+/// Parameters of a pool's price circuit breaker: if the pool's price moves by more than
+/// `max_movement_ticks` within `window_blocks` blocks, swaps in the pool are suspended for
+/// `cool_down_blocks` blocks. Price movements are measured using the pool's [PriceObservation]s.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub struct PriceCircuitBreaker {
+	/// One tick is a price movement of ~0.01%.
+	pub max_movement_ticks: u32,
+	/// At most [MAX_TWAP_WINDOW_BLOCKS].
+	pub window_blocks: u32,
+	/// At most [MAX_CIRCUIT_BREAKER_COOL_DOWN_BLOCKS].
+	pub cool_down_blocks: u32,
+}
+
+/// A liquidity mining incentive programme for a pool, see
+/// [fund_incentive_programme](Pallet::fund_incentive_programme).
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct IncentiveProgramme<AccountId> {
+	/// The account the programme was funded from, to which any remaining rewards are returned
+	/// if the programme is stopped.
+	pub funder: AccountId,
+	pub reward_asset: Asset,
+	pub reward_per_block: AssetAmount,
+	/// The rewards that have not yet been distributed.
+	pub remaining: AssetAmount,
+}
+
+/// The tick of a pool's price from a given block onwards, together with the accumulated sum of
+/// the pool's tick over all blocks up to that block. The difference between the accumulated ticks
+/// at two blocks, divided by the number of blocks between them, is the average tick (and hence
+/// the geometric mean price) over that window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct PriceObservation {
+	pub block: u32,
+	pub tick: Tick,
+	pub tick_cumulative: i64,
+}
+
+impl PriceObservation {
+	/// The accumulated tick at a block at or after this observation, assuming the price did not
+	/// change in between.
+	fn tick_cumulative_at(&self, block: u32) -> i64 {
+		self.tick_cumulative.saturating_add(
+			(self.tick as i64).saturating_mul(block.saturating_sub(self.block) as i64),
+		)
+	}
+}
+
+/// Parameters of a range order whose range is automatically re-centred around the pool's price.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct AutoRebalancingParameters {
+	/// Half the width of the order's range, in ticks.
+	pub half_width: Tick,
+	/// How far (in ticks) the price must move outside of the order's range before the range is
+	/// re-centred.
+	pub threshold: Tick,
+	/// The block at which the order's range was last centred.
+	pub last_rebalanced_at: u32,
+}
+
+impl AutoRebalancingParameters {
+	/// The range of width `2 * half_width` centred on `tick`, clamped to the valid tick range.
+	fn range_centred_on(&self, tick: Tick) -> Range<Tick> {
+		tick.saturating_sub(self.half_width).max(MIN_TICK)..
+			tick.saturating_add(self.half_width).min(MAX_TICK)
+	}
+
+	/// Whether the price has moved far enough outside of `tick_range` for it to be re-centred.
+	fn should_rebalance(&self, tick_range: &Range<Tick>, tick: Tick) -> bool {
+		tick < tick_range.start.saturating_sub(self.threshold) ||
+			tick >= tick_range.end.saturating_add(self.threshold)
+	}
+}
+
 #[derive(
 	serde::Serialize,
 	serde::Deserialize,
@@ -110,9 +220,13 @@ impl AssetPair {
 	pub fn new(base_asset: Asset, quote_asset: Asset) -> Option<Self> {
 		Some(AssetPair {
 			assets: match (base_asset, quote_asset) {
-				(STABLE_ASSET, STABLE_ASSET) => None,
+				(STABLE_ASSET, _) => None,
 				(_unstable_asset, STABLE_ASSET) =>
 					Some(PoolPairsMap { base: base_asset, quote: quote_asset }),
+				// Direct pools between two other assets are quoted in whichever asset comes first
+				// in the ordering of assets, so that each pair of assets has at most one pool.
+				(base_asset, quote_asset) if quote_asset < base_asset =>
+					Some(PoolPairsMap { base: base_asset, quote: quote_asset }),
 				_ => None,
 			}?,
 		})
@@ -181,10 +295,35 @@ impl<T> AskBidMap<T> {
 
 #[derive(Clone, RuntimeDebugNoBound, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum PalletConfigUpdate {
-	LimitOrderAutoSweepingThreshold { asset: Asset, amount: AssetAmount },
+	LimitOrderAutoSweepingThreshold {
+		asset: Asset,
+		amount: AssetAmount,
+	},
+	MinimumOrderAgeForFees {
+		blocks: u32,
+	},
+	ProtocolFee {
+		base_asset: Asset,
+		quote_asset: Asset,
+		fee: Permill,
+	},
+	PriceCircuitBreaker {
+		base_asset: Asset,
+		quote_asset: Asset,
+		circuit_breaker: Option<PriceCircuitBreaker>,
+	},
+}
+
+/// Identifies the kind of an order and the pool it is in, so that together with the LP, asset pair
+/// and order id it identifies a single order. The pool is `None` for the asset pair's pool in
+/// [Pools], otherwise it is the fee of the pool in [FeeTierPools].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum OrderKind {
+	Limit { side: Side, pool_fee_hundredth_pips: Option<u32> },
+	Range { pool_fee_hundredth_pips: Option<u32> },
 }
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(6);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(7);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -193,7 +332,6 @@ pub mod pallet {
 		range_orders::{self, Liquidity},
 	};
 	use frame_system::pallet_prelude::BlockNumberFor;
-	use sp_std::collections::btree_map::BTreeMap;
 
 	use super::*;
 
@@ -252,6 +390,41 @@ pub mod pallet {
 		}
 	}
 
+	impl From<RangeOrderSize> for range_orders::Size {
+		fn from(size: RangeOrderSize) -> Self {
+			match size {
+				RangeOrderSize::Liquidity { liquidity } =>
+					range_orders::Size::Liquidity { liquidity },
+				RangeOrderSize::AssetAmounts { maximum, minimum } => range_orders::Size::Amount {
+					maximum: maximum.map(Into::into),
+					minimum: minimum.map(Into::into),
+				},
+			}
+		}
+	}
+
+	/// An order placed via [set_orders_batch](Pallet::set_orders_batch). The order is set in the
+	/// same way as by [set_limit_order](Pallet::set_limit_order) or
+	/// [set_range_order](Pallet::set_range_order).
+	#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
+	pub enum NewOrder {
+		Limit {
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Side,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			sell_amount: AssetAmount,
+		},
+		Range {
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			option_tick_range: Option<Range<Tick>>,
+			size: RangeOrderSize,
+		},
+	}
+
 	/// Indicates the change caused by an operation in the positions size, both in terms of
 	/// liquidity and equivalently in asset amounts
 	#[derive(
@@ -302,11 +475,39 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Pools<T: Config> = StorageMap<_, Twox64Concat, AssetPair, Pool<T>, OptionQuery>;
 
+	/// Additional pools for asset pairs in [Pools], each with a different LP fee (in hundredth
+	/// pips). These pools support both range and limit orders. Swaps are routed to whichever pool
+	/// for the pair gives the best output.
+	#[pallet::storage]
+	pub type FeeTierPools<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, AssetPair, Twox64Concat, u32, Pool<T>, OptionQuery>;
+
 	/// Queue of limit orders, indexed by block number waiting to get minted or burned.
 	#[pallet::storage]
 	pub(super) type ScheduledLimitOrderUpdates<T: Config> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<LimitOrderUpdate<T>>, ValueQuery>;
 
+	/// The block at which each expiring limit order is cancelled, see
+	/// [set_limit_order](Pallet::set_limit_order).
+	#[pallet::storage]
+	pub(super) type LimitOrderExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		(T::AccountId, AssetPair, Side, OrderId),
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Queue of limit orders, indexed by the block number at which they expire.
+	#[pallet::storage]
+	pub(super) type ExpiringLimitOrders<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Vec<(T::AccountId, AssetPair, Side, OrderId)>,
+		ValueQuery,
+	>;
+
 	/// Maximum price impact for a single swap, measured in number of ticks. Configurable
 	/// for each pool.
 	#[pallet::storage]
@@ -320,16 +521,153 @@ pub mod pallet {
 	pub(super) type LimitOrderAutoSweepingThresholds<T: Config> =
 		StorageValue<_, SweepingThresholds, ValueQuery, StablecoinDefaults<1_000_000_000>>; // $1000 USD
 
+	/// Recent observations of each pool's price, used to calculate time-weighted average prices.
+	/// Only observations that are needed to cover [MAX_TWAP_WINDOW_BLOCKS] are kept.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, Vec<PriceObservation>, ValueQuery>;
+
+	/// Range orders which are automatically re-centred around the pool's price, see
+	/// [set_auto_rebalancing_range_order](Pallet::set_auto_rebalancing_range_order).
+	#[pallet::storage]
+	pub type AutoRebalancingRangeOrders<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		AssetPair,
+		Twox64Concat,
+		(T::AccountId, OrderId),
+		AutoRebalancingParameters,
+		OptionQuery,
+	>;
+
+	/// The storage key of the last auto-rebalancing range order that was checked, if the last
+	/// block's idle weight ran out before all of them were checked. Checking resumes after it.
+	#[pallet::storage]
+	pub(super) type AutoRebalancingCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// The share of the fees earned by LPs in each pool (including its fee tier pools) that is
+	/// paid to the network fee instead.
+	#[pallet::storage]
+	pub type ProtocolFees<T: Config> = StorageMap<_, Twox64Concat, AssetPair, Permill, ValueQuery>;
+
+	/// Range orders whose collected fees are added back to the order's liquidity, see
+	/// [set_range_order_auto_compounding](Pallet::set_range_order_auto_compounding).
+	#[pallet::storage]
+	pub type AutoCompoundingRangeOrders<T: Config> =
+		StorageMap<_, Twox64Concat, (T::AccountId, AssetPair, OrderKind, OrderId), (), OptionQuery>;
+
+	/// The number of orders each LP has in each asset pair's pools (including fee tier pools), so
+	/// that an LP's orders can be found without reading every pool.
+	#[pallet::storage]
+	pub type LpOrderCounts<T: Config> =
+		StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, AssetPair, u32, ValueQuery>;
+
+	/// Liquidity mining incentive programmes, by pool.
+	#[pallet::storage]
+	pub type IncentiveProgrammes<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, IncentiveProgramme<T::AccountId>, OptionQuery>;
+
+	/// Incentive rewards that each LP has earned but not yet claimed, see
+	/// [claim_incentive_rewards](Pallet::claim_incentive_rewards).
+	#[pallet::storage]
+	pub type IncentiveRewards<T: Config> =
+		StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, Asset, AssetAmount, ValueQuery>;
+
+	/// Price circuit breakers of pools that have one.
+	#[pallet::storage]
+	pub type PriceCircuitBreakers<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, PriceCircuitBreaker, OptionQuery>;
+
+	/// The block at which each pool whose circuit breaker has tripped resumes (or resumed) swaps.
+	/// Price movements before this block don't count towards tripping the circuit breaker again.
+	#[pallet::storage]
+	pub type PoolSuspensions<T: Config> =
+		StorageMap<_, Twox64Concat, AssetPair, BlockNumberFor<T>, OptionQuery>;
+
+	/// Orders their owner has offered to transfer to another LP, with the LP they are offered to.
+	/// See [offer_order_transfer](Pallet::offer_order_transfer).
+	#[pallet::storage]
+	pub type OrderTransferOffers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		(T::AccountId, AssetPair, OrderKind, OrderId),
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// The minimum number of blocks an order must have been resting for before it earns fees. Fees
+	/// earned by orders younger than this go to the network fee instead. Adding liquidity to an
+	/// order resets its age.
+	#[pallet::storage]
+	pub type MinimumOrderAgeForFees<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Orders that are too young to earn fees, with the block at which they mature. See
+	/// [MinimumOrderAgeForFees].
+	#[pallet::storage]
+	pub(super) type YoungOrders<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		(T::AccountId, AssetPair, OrderKind, OrderId),
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Queue of young orders, indexed by the block number at which they mature.
+	#[pallet::storage]
+	pub(super) type MaturingOrders<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Vec<(T::AccountId, AssetPair, OrderKind, OrderId)>,
+		ValueQuery,
+	>;
+
+	/// Swap volume and fees of each pool (including its fee tier pools), per epoch. Cumulative
+	/// statistics are the sum over all epochs.
+	#[pallet::storage]
+	pub type EpochPoolStatistics<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		AssetPair,
+		Twox64Concat,
+		EpochIndex,
+		PoolStatistics,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	/// Historical earned fees for an account.
 	pub type HistoricalEarnedFees<T: Config> =
 		StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, Asset, AssetAmount, ValueQuery>;
 
+	/// Fees paid to each LP from each pool, per epoch. Fees are attributed to the epoch in which
+	/// they were collected from the LP's orders.
+	#[pallet::storage]
+	pub type EpochLpEarnedFees<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Identity, T::AccountId>,
+			NMapKey<Twox64Concat, AssetPair>,
+			NMapKey<Twox64Concat, EpochIndex>,
+		),
+		PoolPairsMap<AssetAmount>,
+		ValueQuery,
+	>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
 			let mut weight_used: Weight = T::DbWeight::get().reads(1);
 
+			// Orders are matured before anything else can collect their fees:
+			for (lp, asset_pair, kind, id) in MaturingOrders::<T>::take(current_block) {
+				Self::mature_order(lp, asset_pair, kind, id, current_block);
+				weight_used.saturating_accrue(T::WeightInfo::update_range_order());
+			}
+
+			weight_used.saturating_accrue(Self::check_price_circuit_breakers(current_block));
+			weight_used.saturating_accrue(Self::distribute_incentive_rewards());
+
 			Self::auto_sweep_limit_orders();
 
 			for LimitOrderUpdate { ref lp, id, call } in
@@ -354,8 +692,17 @@ pub mod pallet {
 				});
 				weight_used.saturating_accrue(call_weight);
 			}
+
+			for (lp, asset_pair, side, id) in ExpiringLimitOrders::<T>::take(current_block) {
+				Self::expire_limit_order(lp, asset_pair, side, id, current_block);
+				weight_used.saturating_accrue(T::WeightInfo::set_limit_order());
+			}
 			weight_used
 		}
+
+		fn on_idle(current_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::rebalance_range_orders(current_block.saturated_into(), remaining_weight)
+		}
 	}
 
 	#[pallet::error]
@@ -399,8 +746,41 @@ pub mod pallet {
 		UnsupportedCall,
 		/// The update can't be scheduled because it has expired (dispatch_at is in the past).
 		LimitOrderUpdateExpired,
+		/// The expiry block of a limit order must be in the future.
+		InvalidExpiryBlock,
 		/// The range order size is invalid.
 		InvalidSize,
+		/// A pool with this fee already exists for the asset pair.
+		FeeTierAlreadyExists,
+		/// The half width of an auto-rebalancing range order must be positive, and its threshold
+		/// must not be negative.
+		InvalidRebalancingParameters,
+		/// The range order is not auto-rebalancing.
+		RangeOrderNotAutoRebalancing,
+		/// The order has not been offered to the caller.
+		NoOrderTransferOffer,
+		/// The caller already has an order with the requested id.
+		OrderIdInUse,
+		/// The circuit breaker's window must be positive and no longer than
+		/// [MAX_TWAP_WINDOW_BLOCKS], and its cool down positive and no longer than
+		/// [MAX_CIRCUIT_BREAKER_COOL_DOWN_BLOCKS].
+		InvalidCircuitBreakerParameters,
+		/// Swaps in the pool are suspended because its price moved too far, see
+		/// [PriceCircuitBreakers].
+		PoolSuspended,
+		/// The incentive programme must pay out a non-zero reward per block.
+		InvalidIncentiveProgramme,
+		/// The pool's existing incentive programme pays out its rewards in a different asset.
+		IncentiveProgrammeAssetMismatch,
+		/// The pool's existing incentive programme was funded by another account.
+		IncentiveProgrammeFunderMismatch,
+		/// The maximum number of incentive programmes are already running, see
+		/// [MAX_INCENTIVE_PROGRAMMES].
+		TooManyIncentiveProgrammes,
+		/// The pool has no incentive programme.
+		NoIncentiveProgramme,
+		/// There are no incentive rewards to claim.
+		NoIncentiveRewards,
 	}
 
 	#[pallet::event]
@@ -468,6 +848,68 @@ pub mod pallet {
 			order_id: OrderId,
 			dispatch_at: BlockNumberFor<T>,
 		},
+		/// A limit order was filled, partially or completely, by a swap. `sold_amount` is the
+		/// amount of the order's asset that was sold, and `remaining_amount` what is left of the
+		/// order. `swap_ids` are the swaps that the liquidity was consumed by; swaps of the same
+		/// asset executed in the same block are processed together, so there may be more than one.
+		LimitOrderFilled {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Side,
+			id: OrderId,
+			tick: Tick,
+			sold_amount: AssetAmount,
+			bought_amount: AssetAmount,
+			fees_earned: AssetAmount,
+			remaining_amount: AssetAmount,
+			swap_ids: Vec<SwapId>,
+		},
+		/// Fees collected from an order that was too young to earn them were paid to the network
+		/// fee instead of the LP. See [MinimumOrderAgeForFees].
+		YoungOrderFeesForfeited {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			kind: OrderKind,
+			id: OrderId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		/// A limit order was moved to a new tick and its amount set, see
+		/// [modify_limit_order](Pallet::modify_limit_order). The amounts are the amounts
+		/// remaining to be sold.
+		LimitOrderModified {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Side,
+			id: OrderId,
+			old_tick: Tick,
+			new_tick: Tick,
+			old_sell_amount: AssetAmount,
+			new_sell_amount: AssetAmount,
+		},
+		/// The protocol's share of the fees collected from an order was paid to the network fee.
+		/// See [ProtocolFees].
+		ProtocolFeeTaken {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			kind: OrderKind,
+			id: OrderId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		/// A limit order reached its expiry block and was cancelled. Its funds have been returned
+		/// to the LP's free balance.
+		LimitOrderExpired {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			side: Side,
+			id: OrderId,
+		},
 		/// The Price Impact limit has been set for a pool.
 		PriceImpactLimitSet {
 			asset_pair: AssetPair,
@@ -480,6 +922,96 @@ pub mod pallet {
 		PalletConfigUpdated {
 			update: PalletConfigUpdate,
 		},
+		NewFeeTierPoolCreated {
+			base_asset: Asset,
+			quote_asset: Asset,
+			fee_hundredth_pips: u32,
+			initial_price: Price,
+		},
+		/// A range order was set to be automatically rebalanced, or stopped being rebalanced if
+		/// `parameters` is `None`.
+		AutoRebalancingUpdated {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			parameters: Option<AutoRebalancingParameters>,
+		},
+		/// An attempt to re-centre an auto-rebalancing range order failed. The order is left
+		/// unchanged, and rebalancing will be retried later.
+		RangeOrderRebalanceFailed {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			error: DispatchError,
+		},
+		/// A range order's fees will be (or will no longer be) automatically compounded.
+		AutoCompoundingUpdated {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			auto_compounding: bool,
+		},
+		/// Fees collected by an auto-compounding range order were added to its liquidity.
+		RangeOrderFeesCompounded {
+			lp: T::AccountId,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			liquidity: Liquidity,
+			amounts: AssetAmounts,
+		},
+		/// An order was offered to another LP, or the offer was withdrawn if `to` is `None`.
+		OrderTransferOffered {
+			from: T::AccountId,
+			to: Option<T::AccountId>,
+			order: CloseOrder,
+		},
+		/// An order was transferred to another LP, who now owns it under `new_id`.
+		OrderTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			order: CloseOrder,
+			new_id: OrderId,
+		},
+		/// The pool's price moved too far within its circuit breaker's window, so its swaps are
+		/// suspended until `resumes_at`.
+		PoolSuspended {
+			asset_pair: AssetPair,
+			resumes_at: BlockNumberFor<T>,
+		},
+		/// Swaps in the pool have resumed after a suspension.
+		PoolResumed {
+			asset_pair: AssetPair,
+		},
+		/// An incentive programme was funded, or an existing programme was topped up.
+		IncentiveProgrammeFunded {
+			asset_pair: AssetPair,
+			funder: T::AccountId,
+			reward_asset: Asset,
+			amount: AssetAmount,
+			reward_per_block: AssetAmount,
+		},
+		/// An incentive programme ended, either because all of its rewards were distributed or
+		/// because it was stopped, in which case `refunded` was returned to its funder.
+		IncentiveProgrammeEnded {
+			asset_pair: AssetPair,
+			refunded: AssetAmount,
+		},
+		/// The remaining rewards of an incentive programme that has paid out its last block of
+		/// rewards could not be returned to its funder. The programme is kept, and its remaining
+		/// rewards are distributed in the next block.
+		IncentiveProgrammeRefundFailed {
+			asset_pair: AssetPair,
+			error: DispatchError,
+		},
+		IncentiveRewardsClaimed {
+			lp: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
 	}
 
 	#[pallet::call]
@@ -487,6 +1019,11 @@ pub mod pallet {
 		/// Create a new pool.
 		/// Requires Governance.
 		///
+		/// Pools are normally quoted in the Stable asset. Direct pools between two other assets
+		/// must be quoted in whichever of them comes first in the ordering of assets, and are used
+		/// by swaps between those assets whenever they give a better output than swapping via the
+		/// Stable asset.
+		///
 		/// ## Events
 		///
 		/// - [On success](Event::NewPoolCreated)
@@ -624,59 +1161,7 @@ pub mod pallet {
 				[base_asset, quote_asset],
 			)?;
 			Self::try_mutate_order(&lp, base_asset, quote_asset, |asset_pair, pool| {
-				let tick_range = match (
-					pool.range_orders_cache
-						.get(&lp)
-						.and_then(|range_orders| range_orders.get(&id))
-						.cloned(),
-					option_tick_range,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick_range)) => Ok(tick_range),
-					(Some(previous_tick_range), option_new_tick_range) => {
-						Self::inner_update_range_order(
-							pool,
-							&lp,
-							asset_pair,
-							id,
-							previous_tick_range.clone(),
-							IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
-								liquidity: Liquidity::MAX,
-							}),
-							NoOpStatus::Error,
-						)?;
-
-						Ok(option_new_tick_range.unwrap_or(previous_tick_range))
-					},
-				}?;
-				let (_, new_order_liquidity) = Self::inner_update_range_order(
-					pool,
-					&lp,
-					asset_pair,
-					id,
-					tick_range,
-					IncreaseOrDecrease::Increase(match size {
-						RangeOrderSize::Liquidity { liquidity } =>
-							range_orders::Size::Liquidity { liquidity },
-						RangeOrderSize::AssetAmounts { maximum, minimum } =>
-							range_orders::Size::Amount {
-								maximum: maximum.map(Into::into),
-								minimum: minimum.map(Into::into),
-							},
-					}),
-					NoOpStatus::Allow,
-				)?;
-
-				// Asset input and resultant liquidity changes should be consistent.
-				// This condition can be breached in cases where eg. the assets amounts are rounded
-				// to zero liquidity.
-				ensure!(
-					(size.max_is_zero() && new_order_liquidity.is_zero()) ||
-						(!size.max_is_zero() && !new_order_liquidity.is_zero()),
-					Error::<T>::InvalidSize
-				);
-
-				Ok(())
+				Self::inner_set_range_order(pool, &lp, asset_pair, id, option_tick_range, size)
 			})
 		}
 
@@ -728,6 +1213,9 @@ pub mod pallet {
 		/// limit orders i.e. those in different directions are independent. Therefore you may have
 		/// two limit orders with the same order_id in the same pool, one to buy Eth and one to sell
 		/// Eth for example.
+		///
+		/// If an `expiry_block` is given, the order is cancelled at that block and its funds are
+		/// returned to your free balance. Setting the order again replaces any previous expiry.
 		#[pallet::call_index(6)]
 		#[pallet::weight(T::WeightInfo::set_limit_order())]
 		pub fn set_limit_order(
@@ -738,6 +1226,7 @@ pub mod pallet {
 			id: OrderId,
 			option_tick: Option<Tick>,
 			sell_amount: AssetAmount,
+			expiry_block: Option<BlockNumberFor<T>>,
 		) -> DispatchResult {
 			ensure!(
 				T::SafeMode::get().limit_order_update_enabled,
@@ -748,6 +1237,13 @@ pub mod pallet {
 				&lp,
 				[base_asset, quote_asset],
 			)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			ensure!(
+				expiry_block.is_none_or(
+					|expiry_block| expiry_block > frame_system::Pallet::<T>::block_number()
+				),
+				Error::<T>::InvalidExpiryBlock
+			);
 
 			Self::inner_set_limit_order(
 				&lp,
@@ -757,42 +1253,133 @@ pub mod pallet {
 				id,
 				option_tick,
 				sell_amount,
-			)
+			)?;
+
+			let order = (lp, asset_pair, side, id);
+			match expiry_block {
+				Some(expiry_block) => {
+					ExpiringLimitOrders::<T>::append(expiry_block, order.clone());
+					LimitOrderExpiries::<T>::insert(order, expiry_block);
+				},
+				None => LimitOrderExpiries::<T>::remove(order),
+			}
+
+			Ok(())
 		}
 
-		/// Sets the Liquidity Pool fees. Also collect earned fees and bought amount for
-		/// all positions within the fee and accredit them to the liquidity provider.
-		/// Requires governance origin.
+		/// Moves an existing limit order to a new tick and sets its amount, in a single step. This
+		/// is the same as using [set_limit_order](Pallet::set_limit_order) on an existing order,
+		/// except that the order's expiry, if any, is kept.
 		///
 		/// ## Events
 		///
-		/// - [On success](Event::PoolFeeSet)
+		/// - [On success](Event::LimitOrderModified)
+		/// - [On success](Event::LimitOrderUpdated)
 		///
 		/// ## Errors
 		///
-		/// - [BadOrigin](frame_system::BadOrigin)
-		/// - [InvalidFeeAmount](pallet_cf_pools::Error::InvalidFeeAmount)
-		#[pallet::call_index(7)]
-		#[pallet::weight(T::WeightInfo::set_pool_fees())]
-		pub fn set_pool_fees(
+		/// - [OrderDoesNotExist](Error::OrderDoesNotExist)
+		/// - [UpdatingLimitOrdersDisabled](Error::UpdatingLimitOrdersDisabled)
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::set_limit_order())]
+		pub fn modify_limit_order(
 			origin: OriginFor<T>,
-			base_asset: Asset,
-			quote_asset: Asset,
-			fee_hundredth_pips: u32,
+			base_asset: any::Asset,
+			quote_asset: any::Asset,
+			side: Side,
+			id: OrderId,
+			new_tick: Tick,
+			new_sell_amount: AssetAmount,
 		) -> DispatchResult {
-			T::EnsureGovernance::ensure_origin(origin)?;
 			ensure!(
-				PoolState::<(T::AccountId, OrderId)>::validate_fees(fee_hundredth_pips),
-				Error::<T>::InvalidFeeAmount
+				T::SafeMode::get().limit_order_update_enabled,
+				Error::<T>::UpdatingLimitOrdersDisabled
 			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+				&lp,
+				[base_asset, quote_asset],
+			)?;
 			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
-			Self::try_mutate_pool(asset_pair, |_asset_pair: &AssetPair, pool| {
+			let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+			let old_tick = pool.limit_orders_cache[side.to_sold_pair()]
+				.get(&lp)
+				.and_then(|limit_orders| limit_orders.get(&id))
+				.cloned()
+				.ok_or(Error::<T>::OrderDoesNotExist)?;
+			let old_sell_amount = AssetAmount::try_from(
 				pool.pool_state
-					.set_range_order_fees(fee_hundredth_pips)
-					.map_err(|_| Error::<T>::InvalidFeeAmount)
-			})?;
+					.limit_order(&(lp.clone(), id), side, old_tick)
+					.map_err(|_| Error::<T>::OrderDoesNotExist)?
+					.1
+					.amount,
+			)
+			.map_err(Into::<DispatchError>::into)?;
 
-			Self::deposit_event(Event::<T>::PoolFeeSet {
+			Self::inner_set_limit_order(
+				&lp,
+				base_asset,
+				quote_asset,
+				side,
+				id,
+				Some(new_tick),
+				new_sell_amount,
+			)?;
+
+			Self::deposit_event(Event::<T>::LimitOrderModified {
+				lp,
+				base_asset,
+				quote_asset,
+				side,
+				id,
+				old_tick,
+				new_tick,
+				old_sell_amount,
+				new_sell_amount,
+			});
+
+			Ok(())
+		}
+
+		/// Sets the Liquidity Pool fees. Also collect earned fees and bought amount for
+		/// all positions within the fee and accredit them to the liquidity provider.
+		/// Requires governance origin.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::PoolFeeSet)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [InvalidFeeAmount](pallet_cf_pools::Error::InvalidFeeAmount)
+		/// - [FeeTierAlreadyExists](pallet_cf_pools::Error::FeeTierAlreadyExists)
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_pool_fees())]
+		pub fn set_pool_fees(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			fee_hundredth_pips: u32,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			ensure!(
+				PoolState::<(T::AccountId, OrderId)>::validate_fees(fee_hundredth_pips),
+				Error::<T>::InvalidFeeAmount
+			);
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			// Each of the pair's pools must have a different fee, see [OrderKind].
+			ensure!(
+				!FeeTierPools::<T>::contains_key(asset_pair, fee_hundredth_pips),
+				Error::<T>::FeeTierAlreadyExists
+			);
+			Self::try_mutate_pool(asset_pair, |_asset_pair: &AssetPair, pool| {
+				pool.pool_state
+					.set_range_order_fees(fee_hundredth_pips)
+					.map_err(|_| Error::<T>::InvalidFeeAmount)
+			})?;
+
+			Self::deposit_event(Event::<T>::PoolFeeSet {
 				base_asset,
 				quote_asset,
 				fee_hundredth_pips,
@@ -895,68 +1482,9 @@ pub mod pallet {
 				Error::<T>::UpdatingLimitOrdersDisabled
 			);
 			for order in orders {
-				match order {
-					CloseOrder::Limit { base_asset, quote_asset, side, id } => {
-						let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
-						Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
-							match pool.limit_orders_cache[side.to_sold_pair()]
-								.get(lp)
-								.and_then(|limit_orders| limit_orders.get(&id))
-								.cloned()
-							{
-								None => {
-									Self::deposit_event(Event::<T>::OrderDeletionFailed { order });
-									Ok::<(), DispatchError>(())
-								},
-								Some(previous_tick) => {
-									Self::inner_update_limit_order_at_tick(
-										pool,
-										lp,
-										asset_pair,
-										side,
-										id,
-										previous_tick,
-										crate::pallet::IncreaseOrDecrease::Decrease(Amount::MAX),
-										crate::NoOpStatus::Allow,
-									)?;
-									Ok(())
-								},
-							}
-						})?;
-					},
-					CloseOrder::Range { base_asset, quote_asset, id } => {
-						let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
-						Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
-							match (pool
-								.range_orders_cache
-								.get(lp)
-								.and_then(|range_orders| range_orders.get(&id))
-								.cloned(),)
-							{
-								(None,) => {
-									Self::deposit_event(Event::<T>::OrderDeletionFailed { order });
-									Ok::<(), DispatchError>(())
-								},
-								(Some(previous_tick_range),) => {
-									Self::inner_update_range_order(
-										pool,
-										lp,
-										asset_pair,
-										id,
-										previous_tick_range.clone(),
-										IncreaseOrDecrease::Decrease(
-											range_orders::Size::Liquidity {
-												liquidity: Liquidity::MAX,
-											},
-										),
-										NoOpStatus::Allow,
-									)?;
-									Ok(())
-								},
-							}
-						})?;
-					},
-				};
+				if !Self::inner_cancel_order(lp, order)? {
+					Self::deposit_event(Event::<T>::OrderDeletionFailed { order });
+				}
 			}
 
 			Ok(())
@@ -980,72 +1508,718 @@ pub mod pallet {
 							thresholds.try_insert(asset, amount).expect("Every asset will fit");
 						});
 					},
+					PalletConfigUpdate::MinimumOrderAgeForFees { blocks } => {
+						MinimumOrderAgeForFees::<T>::put(blocks);
+					},
+					PalletConfigUpdate::ProtocolFee { base_asset, quote_asset, fee } => {
+						let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+						if fee.is_zero() {
+							ProtocolFees::<T>::remove(asset_pair);
+						} else {
+							ProtocolFees::<T>::insert(asset_pair, fee);
+						}
+					},
+					PalletConfigUpdate::PriceCircuitBreaker {
+						base_asset,
+						quote_asset,
+						circuit_breaker,
+					} => {
+						let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+						match circuit_breaker {
+							Some(circuit_breaker) => {
+								ensure!(
+									(1..=MAX_TWAP_WINDOW_BLOCKS)
+										.contains(&circuit_breaker.window_blocks) &&
+										(1..=MAX_CIRCUIT_BREAKER_COOL_DOWN_BLOCKS)
+											.contains(&circuit_breaker.cool_down_blocks),
+									Error::<T>::InvalidCircuitBreakerParameters
+								);
+								PriceCircuitBreakers::<T>::insert(asset_pair, circuit_breaker);
+							},
+							None => {
+								PriceCircuitBreakers::<T>::remove(asset_pair);
+								// Removing the circuit breaker also ends any suspension:
+								PoolSuspensions::<T>::remove(asset_pair);
+							},
+						}
+					},
 				}
 				Self::deposit_event(Event::<T>::PalletConfigUpdated { update });
 			}
 
 			Ok(())
 		}
+
+		/// Create an additional pool for an existing asset pair, with a different fee. Swaps
+		/// between the pair's assets are routed to whichever pool gives the best output.
+		/// Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::NewFeeTierPoolCreated)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [PoolDoesNotExist](pallet_cf_pools::Error::PoolDoesNotExist)
+		/// - [FeeTierAlreadyExists](pallet_cf_pools::Error::FeeTierAlreadyExists)
+		/// - [InvalidFeeAmount](pallet_cf_pools::Error::InvalidFeeAmount)
+		/// - [InvalidInitialPrice](pallet_cf_pools::Error::InvalidInitialPrice)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::new_fee_tier_pool())]
+		pub fn new_fee_tier_pool(
+			origin: OriginFor<T>,
+			base_asset: any::Asset,
+			quote_asset: any::Asset,
+			fee_hundredth_pips: u32,
+			initial_price: Price,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			let primary_pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+			ensure!(
+				primary_pool.pool_state.range_order_fee() != fee_hundredth_pips,
+				Error::<T>::FeeTierAlreadyExists
+			);
+			FeeTierPools::<T>::try_mutate(asset_pair, fee_hundredth_pips, |maybe_pool| {
+				ensure!(maybe_pool.is_none(), Error::<T>::FeeTierAlreadyExists);
+				*maybe_pool = Some(Self::empty_pool(fee_hundredth_pips, initial_price)?);
+				Ok::<_, Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::<T>::NewFeeTierPoolCreated {
+				base_asset,
+				quote_asset,
+				fee_hundredth_pips,
+				initial_price,
+			});
+
+			Ok(())
+		}
+
+		/// Same as [set_range_order](Pallet::set_range_order), but for the pool of the asset pair
+		/// with the given fee, created via [new_fee_tier_pool](Pallet::new_fee_tier_pool).
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_fee_tier_range_order())]
+		pub fn set_fee_tier_range_order(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			fee_hundredth_pips: u32,
+			id: OrderId,
+			option_tick_range: Option<core::ops::Range<Tick>>,
+			size: RangeOrderSize,
+		) -> DispatchResult {
+			ensure!(
+				T::SafeMode::get().range_order_update_enabled,
+				Error::<T>::UpdatingRangeOrdersDisabled
+			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+				&lp,
+				[base_asset, quote_asset],
+			)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			FeeTierPools::<T>::try_mutate(asset_pair, fee_hundredth_pips, |maybe_pool| {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
+				Self::inner_set_range_order(pool, &lp, &asset_pair, id, option_tick_range, size)
+			})
+		}
+
+		/// Same as [set_limit_order](Pallet::set_limit_order), but for the pool of the asset pair
+		/// with the given fee, created via [new_fee_tier_pool](Pallet::new_fee_tier_pool). Limit
+		/// orders in fee tier pools cannot have an expiry block.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::set_limit_order())]
+		pub fn set_fee_tier_limit_order(
+			origin: OriginFor<T>,
+			base_asset: any::Asset,
+			quote_asset: any::Asset,
+			fee_hundredth_pips: u32,
+			side: Side,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			sell_amount: AssetAmount,
+		) -> DispatchResult {
+			ensure!(
+				T::SafeMode::get().limit_order_update_enabled,
+				Error::<T>::UpdatingLimitOrdersDisabled
+			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+				&lp,
+				[base_asset, quote_asset],
+			)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			Self::inner_sweep(&lp)?;
+			FeeTierPools::<T>::try_mutate(asset_pair, fee_hundredth_pips, |maybe_pool| {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
+				Self::inner_set_limit_order_in_pool(
+					pool,
+					&lp,
+					&asset_pair,
+					side,
+					id,
+					option_tick,
+					sell_amount,
+				)
+			})
+		}
+
+		/// Sets a range order over a range of `2 * half_width` ticks centred on the pool's current
+		/// range order price, and keeps it centred: whenever the price moves more than `threshold`
+		/// ticks outside of the order's range, the order is moved to a new range centred on the
+		/// current price, keeping the same amount of liquidity. Rebalancing happens at most once
+		/// every [MIN_BLOCKS_BETWEEN_REBALANCES] blocks per order, in blocks with spare weight.
+		///
+		/// Once the price has left an order's range the order holds only one of the two assets, so
+		/// rebalancing requires a free balance of the other asset. If rebalancing fails the order
+		/// is left as it is and rebalancing is retried later. Closing the order, for example via
+		/// [set_range_order](Pallet::set_range_order), stops it from being rebalanced.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::AutoRebalancingUpdated)
+		/// - [On success](Event::RangeOrderUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidRebalancingParameters](Error::InvalidRebalancingParameters)
+		/// - [PoolDoesNotExist](Error::PoolDoesNotExist)
+		/// - [UpdatingRangeOrdersDisabled](Error::UpdatingRangeOrdersDisabled)
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_auto_rebalancing_range_order())]
+		pub fn set_auto_rebalancing_range_order(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			half_width: Tick,
+			threshold: Tick,
+			size: RangeOrderSize,
+		) -> DispatchResult {
+			ensure!(
+				T::SafeMode::get().range_order_update_enabled,
+				Error::<T>::UpdatingRangeOrdersDisabled
+			);
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+				&lp,
+				[base_asset, quote_asset],
+			)?;
+			ensure!(half_width > 0 && threshold >= 0, Error::<T>::InvalidRebalancingParameters);
+
+			let parameters = AutoRebalancingParameters {
+				half_width,
+				threshold,
+				last_rebalanced_at: frame_system::Pallet::<T>::block_number().saturated_into(),
+			};
+			Self::try_mutate_order(&lp, base_asset, quote_asset, |asset_pair, pool| {
+				let tick_range = parameters.range_centred_on(tick_at_sqrt_price(
+					pool.pool_state.current_range_order_pool_price(),
+				));
+				Self::inner_set_range_order(pool, &lp, asset_pair, id, Some(tick_range), size)?;
+				AutoRebalancingRangeOrders::<T>::insert(asset_pair, (lp.clone(), id), parameters);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::AutoRebalancingUpdated {
+				lp,
+				base_asset,
+				quote_asset,
+				id,
+				parameters: Some(parameters),
+			});
+
+			Ok(())
+		}
+
+		/// Stops an auto-rebalancing range order from being rebalanced. The order itself is left
+		/// open at its current range.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::AutoRebalancingUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [RangeOrderNotAutoRebalancing](Error::RangeOrderNotAutoRebalancing)
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::stop_auto_rebalancing())]
+		pub fn stop_auto_rebalancing(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+		) -> DispatchResult {
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			AutoRebalancingRangeOrders::<T>::take(asset_pair, (lp.clone(), id))
+				.ok_or(Error::<T>::RangeOrderNotAutoRebalancing)?;
+
+			Self::deposit_event(Event::<T>::AutoRebalancingUpdated {
+				lp,
+				base_asset,
+				quote_asset,
+				id,
+				parameters: None,
+			});
+
+			Ok(())
+		}
+
+		/// Sets whether the fees collected by a range order are automatically added back to the
+		/// order's liquidity. Fees are collected whenever the order is updated or swept. As much of
+		/// the collected fees as possible are used, at the order's current asset ratio; anything
+		/// that can't be used remains in the LP's free balance. Fees forfeited by young orders
+		/// are not compounded, and compounding does not reset the order's age.
+		///
+		/// Closing the order stops its fees from being compounded.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::AutoCompoundingUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [PoolDoesNotExist](Error::PoolDoesNotExist)
+		/// - [OrderDoesNotExist](Error::OrderDoesNotExist)
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::set_range_order_auto_compounding())]
+		pub fn set_range_order_auto_compounding(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			id: OrderId,
+			auto_compounding: bool,
+		) -> DispatchResult {
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+			ensure!(
+				pool.range_orders_cache.get(&lp).is_some_and(|orders| orders.contains_key(&id)),
+				Error::<T>::OrderDoesNotExist
+			);
+
+			let key =
+				(lp.clone(), asset_pair, OrderKind::Range { pool_fee_hundredth_pips: None }, id);
+			if auto_compounding {
+				AutoCompoundingRangeOrders::<T>::insert(key, ());
+			} else {
+				AutoCompoundingRangeOrders::<T>::remove(key);
+			}
+
+			Self::deposit_event(Event::<T>::AutoCompoundingUpdated {
+				lp,
+				base_asset,
+				quote_asset,
+				id,
+				auto_compounding,
+			});
+
+			Ok(())
+		}
+
+		/// Cancels and then places a set of orders atomically: if any of the cancellations or new
+		/// orders fail, none of them take effect. This allows a whole set of orders to be replaced
+		/// without an intermediate state in which only some of them have been updated.
+		///
+		/// Unlike with [cancel_orders_batch](Pallet::cancel_orders_batch), cancelling an order
+		/// that doesn't exist is an error.
+		///
+		/// ## Errors
+		///
+		/// - [OrderDoesNotExist](Error::OrderDoesNotExist)
+		/// - [UpdatingLimitOrdersDisabled](Error::UpdatingLimitOrdersDisabled)
+		/// - [UpdatingRangeOrdersDisabled](Error::UpdatingRangeOrdersDisabled)
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_orders_batch(cancel.len() as u32, place.len() as u32))]
+		pub fn set_orders_batch(
+			origin: OriginFor<T>,
+			cancel: BoundedVec<CloseOrder, ConstU32<MAX_ORDERS_DELETE>>,
+			place: BoundedVec<NewOrder, ConstU32<MAX_ORDERS_SET>>,
+		) -> DispatchResult {
+			let lp = &T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			ensure!(
+				T::SafeMode::get().limit_order_update_enabled,
+				Error::<T>::UpdatingLimitOrdersDisabled
+			);
+
+			for order in cancel {
+				ensure!(Self::inner_cancel_order(lp, order)?, Error::<T>::OrderDoesNotExist);
+			}
+
+			for order in place {
+				match order {
+					NewOrder::Limit {
+						base_asset,
+						quote_asset,
+						side,
+						id,
+						option_tick,
+						sell_amount,
+					} => {
+						T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+							lp,
+							[base_asset, quote_asset],
+						)?;
+						Self::inner_set_limit_order(
+							lp,
+							base_asset,
+							quote_asset,
+							side,
+							id,
+							option_tick,
+							sell_amount,
+						)?;
+					},
+					NewOrder::Range { base_asset, quote_asset, id, option_tick_range, size } => {
+						ensure!(
+							T::SafeMode::get().range_order_update_enabled,
+							Error::<T>::UpdatingRangeOrdersDisabled
+						);
+						T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+							lp,
+							[base_asset, quote_asset],
+						)?;
+						Self::try_mutate_order(lp, base_asset, quote_asset, |asset_pair, pool| {
+							Self::inner_set_range_order(
+								pool,
+								lp,
+								asset_pair,
+								id,
+								option_tick_range,
+								size,
+							)
+						})?;
+					},
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Offers one of the caller's orders to another LP, who takes ownership of it by calling
+		/// [accept_order_transfer](Pallet::accept_order_transfer). Only one offer can be pending
+		/// for an order: offering it again replaces the previous offer, and offering it to `None`
+		/// withdraws the offer. The offer lapses if the order is closed, including when a limit
+		/// order is moved to another tick.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::OrderTransferOffered)
+		///
+		/// ## Errors
+		///
+		/// - [PoolDoesNotExist](Error::PoolDoesNotExist)
+		/// - [OrderDoesNotExist](Error::OrderDoesNotExist)
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::offer_order_transfer())]
+		pub fn offer_order_transfer(
+			origin: OriginFor<T>,
+			order: CloseOrder,
+			to: Option<T::AccountId>,
+		) -> DispatchResult {
+			let from = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let key = Self::order_transfer_key(&from, &order)?;
+
+			match &to {
+				Some(to) => OrderTransferOffers::<T>::insert(key, to),
+				None => OrderTransferOffers::<T>::remove(key),
+			}
+
+			Self::deposit_event(Event::<T>::OrderTransferOffered { from, to, order });
+
+			Ok(())
+		}
+
+		/// Accepts an order offered to the caller by
+		/// [offer_order_transfer](Pallet::offer_order_transfer). The order is moved to the caller
+		/// as it is, under `new_id`, which must not already be used by one of the caller's
+		/// orders of the same kind. Anything the order earned before the transfer is paid to the
+		/// previous owner. The order keeps its age, but its expiry, auto-rebalancing and
+		/// auto-compounding settings are not transferred.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::OrderTransferred)
+		///
+		/// ## Errors
+		///
+		/// - [NoOrderTransferOffer](Error::NoOrderTransferOffer)
+		/// - [OrderDoesNotExist](Error::OrderDoesNotExist)
+		/// - [OrderIdInUse](Error::OrderIdInUse)
+		/// - [UpdatingLimitOrdersDisabled](Error::UpdatingLimitOrdersDisabled)
+		/// - [UpdatingRangeOrdersDisabled](Error::UpdatingRangeOrdersDisabled)
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::accept_order_transfer())]
+		pub fn accept_order_transfer(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			order: CloseOrder,
+			new_id: OrderId,
+		) -> DispatchResult {
+			let to = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let (base_asset, quote_asset) = match order {
+				CloseOrder::Limit { base_asset, quote_asset, .. } => {
+					ensure!(
+						T::SafeMode::get().limit_order_update_enabled,
+						Error::<T>::UpdatingLimitOrdersDisabled
+					);
+					(base_asset, quote_asset)
+				},
+				CloseOrder::Range { base_asset, quote_asset, .. } => {
+					ensure!(
+						T::SafeMode::get().range_order_update_enabled,
+						Error::<T>::UpdatingRangeOrdersDisabled
+					);
+					(base_asset, quote_asset)
+				},
+			};
+			T::LpRegistrationApi::ensure_has_refund_address_for_assets(
+				&to,
+				[base_asset, quote_asset],
+			)?;
+
+			let (asset_pair, kind, id) = Self::order_transfer_key(&from, &order)?;
+			ensure!(
+				OrderTransferOffers::<T>::get((&from, asset_pair, kind, id)).as_ref() == Some(&to),
+				Error::<T>::NoOrderTransferOffer
+			);
+
+			Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
+				Self::inner_transfer_order(pool, &from, &to, asset_pair, kind, id, new_id)
+			})?;
+
+			Self::deposit_event(Event::<T>::OrderTransferred { from, to, order, new_id });
+
+			Ok(())
+		}
+
+		/// Funds a liquidity mining incentive programme for a pool. `amount` is debited from the
+		/// caller's free balance, and paid out over `duration_blocks` blocks: each block's
+		/// rewards are shared between the range orders in the pool whose range contains the
+		/// pool's current price, in proportion to their liquidity. Blocks in which no range order
+		/// is in range don't use up any rewards.
+		///
+		/// Funding a pool that already has a programme adds to its remaining rewards, which are
+		/// then paid out over `duration_blocks` blocks. Only the programme's funder can top it up.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::IncentiveProgrammeFunded)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [PoolDoesNotExist](Error::PoolDoesNotExist)
+		/// - [InvalidIncentiveProgramme](Error::InvalidIncentiveProgramme)
+		/// - [IncentiveProgrammeAssetMismatch](Error::IncentiveProgrammeAssetMismatch)
+		/// - [IncentiveProgrammeFunderMismatch](Error::IncentiveProgrammeFunderMismatch)
+		/// - [TooManyIncentiveProgrammes](Error::TooManyIncentiveProgrammes)
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::fund_incentive_programme())]
+		pub fn fund_incentive_programme(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+			reward_asset: Asset,
+			amount: AssetAmount,
+			duration_blocks: u32,
+		) -> DispatchResult {
+			let funder = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+
+			let programme = IncentiveProgrammes::<T>::try_mutate(asset_pair, |maybe_programme| {
+				let remaining = match maybe_programme {
+					Some(programme) => {
+						ensure!(
+							programme.reward_asset == reward_asset,
+							Error::<T>::IncentiveProgrammeAssetMismatch
+						);
+						ensure!(
+							programme.funder == funder,
+							Error::<T>::IncentiveProgrammeFunderMismatch
+						);
+						programme.remaining.saturating_add(amount)
+					},
+					None => {
+						ensure!(
+							IncentiveProgrammes::<T>::iter_keys().count() <
+								MAX_INCENTIVE_PROGRAMMES as usize,
+							Error::<T>::TooManyIncentiveProgrammes
+						);
+						amount
+					},
+				};
+				let reward_per_block = remaining.checked_div(duration_blocks.into()).unwrap_or(0);
+				ensure!(reward_per_block > 0, Error::<T>::InvalidIncentiveProgramme);
+
+				T::LpBalance::try_debit_account(&funder, reward_asset, amount)?;
+				Ok::<_, DispatchError>(
+					maybe_programme
+						.insert(IncentiveProgramme {
+							funder: funder.clone(),
+							reward_asset,
+							reward_per_block,
+							remaining,
+						})
+						.clone(),
+				)
+			})?;
+
+			Self::deposit_event(Event::<T>::IncentiveProgrammeFunded {
+				asset_pair,
+				funder,
+				reward_asset,
+				amount,
+				reward_per_block: programme.reward_per_block,
+			});
+
+			Ok(())
+		}
+
+		/// Stops a pool's incentive programme, returning its remaining rewards to its funder.
+		/// Rewards already earned by LPs can still be claimed. Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::IncentiveProgrammeEnded)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_system::BadOrigin)
+		/// - [NoIncentiveProgramme](Error::NoIncentiveProgramme)
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::stop_incentive_programme())]
+		pub fn stop_incentive_programme(
+			origin: OriginFor<T>,
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+			let programme = IncentiveProgrammes::<T>::take(asset_pair)
+				.ok_or(Error::<T>::NoIncentiveProgramme)?;
+			Self::end_incentive_programme(asset_pair, programme)
+		}
+
+		/// Credits the incentive rewards the caller has earned in the given asset to their free
+		/// balance.
+		///
+		/// ## Events
+		///
+		/// - [On success](Event::IncentiveRewardsClaimed)
+		///
+		/// ## Errors
+		///
+		/// - [NoIncentiveRewards](Error::NoIncentiveRewards)
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::claim_incentive_rewards())]
+		pub fn claim_incentive_rewards(origin: OriginFor<T>, asset: Asset) -> DispatchResult {
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			let amount = IncentiveRewards::<T>::take(&lp, asset);
+			ensure!(amount > 0, Error::<T>::NoIncentiveRewards);
+
+			T::LpBalance::try_credit_account(&lp, asset, amount)?;
+
+			Self::deposit_event(Event::<T>::IncentiveRewardsClaimed { lp, asset, amount });
+
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> SwappingApi for Pallet<T> {
-	#[transactional]
 	fn swap_single_leg(
 		from: any::Asset,
 		to: any::Asset,
 		input_amount: AssetAmount,
 	) -> Result<AssetAmount, DispatchError> {
-		use cf_amm::math::tick_at_sqrt_price;
+		Self::swap_single_leg_for_swaps(&[], from, to, input_amount)
+	}
 
+	fn swap_single_leg_for_swaps(
+		swap_ids: &[SwapId],
+		from: any::Asset,
+		to: any::Asset,
+		input_amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
 		let (asset_pair, order) =
 			AssetPair::from_swap(from, to).ok_or(Error::<T>::PoolDoesNotExist)?;
-		Self::try_mutate_pool(asset_pair, |_asset_pair, pool| {
-			let output_amount = if input_amount == 0 {
-				0
-			} else {
-				let input_amount: Amount = input_amount.into();
+		ensure!(!Self::pool_suspended(asset_pair), Error::<T>::PoolSuspended);
+
+		// If the pair has additional fee tiers, use the pool that gives the best output:
+		let fee_tier_pools = FeeTierPools::<T>::iter_prefix(asset_pair).collect::<Vec<_>>();
+		let best_fee_tier = if fee_tier_pools.is_empty() {
+			None
+		} else {
+			// Swaps are executed outside of any extrinsic, so the simulations (one per pool) are
+			// accounted for here, whether or not the swap succeeds.
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				T::WeightInfo::simulate_swap()
+					.saturating_mul(fee_tier_pools.len().saturating_add(1) as u64),
+				DispatchClass::Mandatory,
+			);
+			let primary_output = Pools::<T>::get(asset_pair).and_then(|mut pool| {
+				Self::swap_in_pool(&mut pool, &asset_pair, order, input_amount).ok()
+			});
+			fee_tier_pools
+				.into_iter()
+				.filter_map(|(fee_hundredth_pips, mut pool)| {
+					Self::swap_in_pool(&mut pool, &asset_pair, order, input_amount)
+						.ok()
+						.map(|output_amount| (fee_hundredth_pips, output_amount))
+				})
+				.max_by_key(|(_, output_amount)| *output_amount)
+				.filter(|(_, output_amount)| {
+					primary_output.is_none_or(|primary_output| *output_amount > primary_output)
+				})
+				.map(|(fee_hundredth_pips, _)| fee_hundredth_pips)
+		};
+
+		// The swap consumes limit orders on the opposite side:
+		let swap_in_pool = |asset_pair: &AssetPair, pool: &mut Pool<T>| {
+			let orders_before = Self::uncollected_limit_order_amounts(pool, !order);
+			let output_amount =
+				Self::swap_in_pool_recording_statistics(pool, asset_pair, order, input_amount)?;
+			Self::deposit_limit_order_fills(pool, asset_pair, !order, orders_before, swap_ids);
+			Ok::<_, DispatchError>(output_amount)
+		};
+		let output_amount = with_storage_layer(|| match best_fee_tier {
+			Some(fee_hundredth_pips) =>
+				FeeTierPools::<T>::try_mutate(asset_pair, fee_hundredth_pips, |maybe_pool| {
+					let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
+					swap_in_pool(&asset_pair, pool)
+				}),
+			None => Self::try_mutate_pool(asset_pair, swap_in_pool),
+		})?;
 
-				let tick_before = pool
-					.pool_state
-					.current_price(order)
-					.ok_or(Error::<T>::InsufficientLiquidity)?
-					.2;
-				let (output_amount, _remaining_amount) =
-					pool.pool_state.swap(order, input_amount, None);
-				let tick_after = pool
-					.pool_state
-					.current_price(order)
-					.ok_or(Error::<T>::InsufficientLiquidity)?
-					.2;
-
-				let swap_tick =
-					tick_at_sqrt_price(PoolState::<(T::AccountId, OrderId)>::swap_sqrt_price(
-						order,
-						input_amount,
-						output_amount,
-					));
-				let bounded_swap_tick = if tick_after < tick_before {
-					core::cmp::min(core::cmp::max(tick_after, swap_tick), tick_before)
-				} else {
-					core::cmp::min(core::cmp::max(tick_before, swap_tick), tick_after)
-				};
+		Self::deposit_event(Event::<T>::AssetSwapped { from, to, input_amount, output_amount });
+		Ok(output_amount)
+	}
 
-				if let Some(maximum_price_impact) = MaximumPriceImpact::<T>::get(asset_pair) {
-					if core::cmp::min(
-						bounded_swap_tick.abs_diff(tick_after),
-						bounded_swap_tick.abs_diff(tick_before),
-					) > maximum_price_impact
-					{
-						return Err(Error::<T>::InsufficientLiquidity.into());
-					}
-				}
+	fn swap_single_leg_weight() -> Weight {
+		T::WeightInfo::simulate_swap()
+	}
 
-				output_amount.try_into().map_err(|_| Error::<T>::OutputOverflow)?
-			};
-			Self::deposit_event(Event::<T>::AssetSwapped { from, to, input_amount, output_amount });
-			Ok(output_amount)
-		})
+	fn pool_exists(from: any::Asset, to: any::Asset) -> bool {
+		AssetPair::from_swap(from, to)
+			.is_some_and(|(asset_pair, _)| Pools::<T>::contains_key(asset_pair))
+	}
+
+	fn record_network_fee(asset: any::Asset, fee: AssetAmount) {
+		if let Some((asset_pair, _)) = AssetPair::from_swap(asset, STABLE_ASSET) {
+			EpochPoolStatistics::<T>::mutate(
+				asset_pair,
+				T::EpochInfo::epoch_index(),
+				|statistics| {
+					statistics.network_fees.saturating_accrue(fee);
+				},
+			);
+		}
 	}
 }
 
@@ -1062,17 +2236,33 @@ impl<T: Config> PoolApi for Pallet<T> {
 	) -> Result<u32, DispatchError> {
 		let pool_orders =
 			Self::pool_orders(asset_pair.base, asset_pair.quote, Some(who.clone()), true)?;
+		let fee_tier_orders: u32 = FeeTierPools::<T>::iter_prefix_values(AssetPair::try_new::<T>(
+			asset_pair.base,
+			asset_pair.quote,
+		)?)
+		.map(|pool| {
+			pool.range_orders_cache.get(who).map_or(0, |orders| orders.len() as u32) +
+				pool.limit_orders_cache
+					.as_ref()
+					.into_iter()
+					.map(|(_, cache)| cache.get(who).map_or(0, |orders| orders.len() as u32))
+					.sum::<u32>()
+		})
+		.sum();
 		Ok(pool_orders.limit_orders.asks.len() as u32 +
 			pool_orders.limit_orders.bids.len() as u32 +
-			pool_orders.range_orders.len() as u32)
+			pool_orders.range_orders.len() as u32 +
+			fee_tier_orders)
 	}
 
 	fn open_order_balances(who: &Self::AccountId) -> AssetMap<AssetAmount> {
 		let mut result: AssetMap<AssetAmount> = AssetMap::from_fn(|_| 0);
 
-		for base_asset in Asset::all().filter(|asset| *asset != Asset::Usdc) {
+		// Covers the USDC pools as well as the direct pools between two other assets.
+		for asset_pair in Pools::<T>::iter_keys().collect::<Vec<_>>() {
+			let PoolPairsMap { base: base_asset, quote: quote_asset } = asset_pair.assets();
 			let pool_orders =
-				match Self::pool_orders(base_asset, Asset::Usdc, Some(who.clone()), false) {
+				match Self::pool_orders(base_asset, quote_asset, Some(who.clone()), false) {
 					Ok(orders) => orders,
 					Err(_) => continue,
 				};
@@ -1081,21 +2271,53 @@ impl<T: Config> PoolApi for Pallet<T> {
 					.saturating_add(ask.sell_amount.saturated_into::<AssetAmount>());
 			}
 			for bid in pool_orders.limit_orders.bids {
-				result[Asset::Usdc] = result[Asset::Usdc]
+				result[quote_asset] = result[quote_asset]
 					.saturating_add(bid.sell_amount.saturated_into::<AssetAmount>());
 			}
 			for range_order in pool_orders.range_orders {
 				let pair = Self::pool_range_order_liquidity_value(
 					base_asset,
-					Asset::Usdc,
+					quote_asset,
 					range_order.range,
 					range_order.liquidity,
 				)
 				.expect("Cannot fail we are sure the pool exists and the orders too");
 				result[base_asset] =
 					result[base_asset].saturating_add(pair.base.saturated_into::<AssetAmount>());
-				result[Asset::Usdc] =
-					result[Asset::Usdc].saturating_add(pair.quote.saturated_into::<AssetAmount>());
+				result[quote_asset] =
+					result[quote_asset].saturating_add(pair.quote.saturated_into::<AssetAmount>());
+			}
+			for pool in FeeTierPools::<T>::iter_prefix_values(asset_pair) {
+				for (id, range) in pool.range_orders_cache.get(who).into_iter().flatten() {
+					let Ok((_, position_info)) =
+						pool.pool_state.range_order(&(who.clone(), *id), range.clone())
+					else {
+						continue
+					};
+					let Ok(pair) = pool
+						.pool_state
+						.range_order_liquidity_value(range.clone(), position_info.liquidity)
+					else {
+						continue
+					};
+					result[base_asset] = result[base_asset]
+						.saturating_add(pair.base.saturated_into::<AssetAmount>());
+					result[quote_asset] = result[quote_asset]
+						.saturating_add(pair.quote.saturated_into::<AssetAmount>());
+				}
+				for (assets, cache) in pool.limit_orders_cache.as_ref().into_iter() {
+					let side = assets.sell_order();
+					for (id, tick) in cache.get(who).into_iter().flatten() {
+						let Ok((_, position_info)) =
+							pool.pool_state.limit_order(&(who.clone(), *id), side, *tick)
+						else {
+							continue
+						};
+						let sold_asset = asset_pair.assets()[side.to_sold_pair()];
+						result[sold_asset] = result[sold_asset]
+							.saturating_add(position_info.amount.saturated_into::<AssetAmount>());
+					}
+				}
 			}
 		}
 		result
@@ -1128,6 +2350,35 @@ impl<T: Config> PoolApi for Pallet<T> {
 			}
 		}
 
+		for (asset_pair, fee_hundredth_pips) in FeeTierPools::<T>::iter_keys().collect::<Vec<_>>() {
+			FeeTierPools::<T>::try_mutate(asset_pair, fee_hundredth_pips, |maybe_pool| {
+				let Some(pool) = maybe_pool.as_mut() else { return Ok(()) };
+				for (assets, orders) in pool
+					.limit_orders_cache
+					.as_ref()
+					.into_iter()
+					.filter_map(|(assets, cache)| {
+						cache.get(account).cloned().map(|orders| (assets, orders))
+					})
+					.collect::<Vec<_>>()
+				{
+					for (id, tick) in orders {
+						Self::inner_update_limit_order_at_tick(
+							pool,
+							account,
+							&asset_pair,
+							assets.sell_order(),
+							id,
+							tick,
+							IncreaseOrDecrease::Decrease(Amount::MAX),
+							NoOpStatus::Error,
+						)?;
+					}
+				}
+				Ok::<_, DispatchError>(())
+			})?;
+		}
+
 		Ok(())
 	}
 
@@ -1192,6 +2443,30 @@ pub struct PoolInfo {
 	pub limit_total_swap_inputs: PoolPairsMap<Amount>,
 }
 
+/// Swap volume and fees of a pool over a period.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Deserialize,
+	Serialize,
+)]
+pub struct PoolStatistics {
+	/// The total amount of each asset swapped into the pool, not including LP fees.
+	pub swap_inputs: PoolPairsMap<Amount>,
+	/// The fees earned by range and limit orders, in each asset.
+	pub lp_fees: PoolPairsMap<Amount>,
+	/// The network fees taken from swaps through the pool, in the stable asset.
+	pub network_fees: AssetAmount,
+}
+
 #[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct LimitOrder<T: Config> {
@@ -1223,6 +2498,59 @@ pub struct PoolOrders<T: Config> {
 	pub range_orders: Vec<RangeOrder<T>>,
 }
 
+/// An order together with the current value of the assets it holds, measured in the quote asset.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValuedOrder<O> {
+	#[serde(flatten)]
+	pub order: O,
+	pub value: Amount,
+}
+
+/// An LP's open orders in a pool, valued at the pool's current range order price.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PoolOrdersValuation<T: Config> {
+	pub base_asset: Asset,
+	pub quote_asset: Asset,
+	/// The price used to value the orders, in units of the quote asset per base asset.
+	pub price: Price,
+	pub limit_orders: AskBidMap<Vec<ValuedOrder<LimitOrder<T>>>>,
+	pub range_orders: Vec<ValuedOrder<RangeOrder<T>>>,
+	/// The combined value of all orders, in the quote asset.
+	pub total_value: Amount,
+}
+
+/// The assets that would be debited for a new range order, and the liquidity it would have.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeOrderMintQuote {
+	pub amounts: AssetAmounts,
+	pub liquidity: Liquidity,
+}
+
+impl<T: Config> Default for PoolOrders<T> {
+	fn default() -> Self {
+		Self { limit_orders: AskBidMap::from_fn(|_| Vec::new()), range_orders: Vec::new() }
+	}
+}
+
+/// Identifies the last order in a page of [PoolOrders]. Orders are returned asks first, then bids,
+/// then range orders, each ordered by LP and then by order id.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PoolOrdersCursor<AccountId> {
+	Ask { lp: AccountId, id: OrderId },
+	Bid { lp: AccountId, id: OrderId },
+	Range { lp: AccountId, id: OrderId },
+}
+
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PoolOrdersPage<T: Config> {
+	pub orders: PoolOrders<T>,
+	/// Pass this to get the next page. `None` if there are no more orders.
+	pub next: Option<PoolOrdersCursor<T::AccountId>>,
+}
+
 #[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
 pub struct LimitOrderLiquidity {
 	tick: Tick,
@@ -1306,32 +2634,34 @@ enum NoOpStatus {
 }
 
 impl<T: Config> Pallet<T> {
+	fn empty_pool(fee_hundredth_pips: u32, initial_price: Price) -> Result<Pool<T>, Error<T>> {
+		use cf_amm::NewError;
+
+		Ok(Pool {
+			range_orders_cache: Default::default(),
+			limit_orders_cache: Default::default(),
+			pool_state: PoolState::new(fee_hundredth_pips, initial_price).map_err(|e| match e {
+				NewError::LimitOrders(limit_orders::NewError::InvalidFeeAmount) =>
+					Error::<T>::InvalidFeeAmount,
+				NewError::RangeOrders(range_orders::NewError::InvalidFeeAmount) =>
+					Error::<T>::InvalidFeeAmount,
+				NewError::RangeOrders(range_orders::NewError::InvalidInitialPrice) =>
+					Error::<T>::InvalidInitialPrice,
+			})?,
+		})
+	}
+
 	fn create_pool(
 		base_asset: any::Asset,
 		quote_asset: any::Asset,
 		fee_hundredth_pips: u32,
 		initial_price: Price,
 	) -> DispatchResult {
-		use cf_amm::NewError;
-
 		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
 		Pools::<T>::try_mutate(asset_pair, |maybe_pool| {
 			ensure!(maybe_pool.is_none(), Error::<T>::PoolAlreadyExists);
 
-			*maybe_pool = Some(Pool {
-				range_orders_cache: Default::default(),
-				limit_orders_cache: Default::default(),
-				pool_state: PoolState::new(fee_hundredth_pips, initial_price).map_err(
-					|e| match e {
-						NewError::LimitOrders(limit_orders::NewError::InvalidFeeAmount) =>
-							Error::<T>::InvalidFeeAmount,
-						NewError::RangeOrders(range_orders::NewError::InvalidFeeAmount) =>
-							Error::<T>::InvalidFeeAmount,
-						NewError::RangeOrders(range_orders::NewError::InvalidInitialPrice) =>
-							Error::<T>::InvalidInitialPrice,
-					},
-				)?,
-			});
+			*maybe_pool = Some(Self::empty_pool(fee_hundredth_pips, initial_price)?);
 
 			Ok::<_, Error<T>>(())
 		})?;
@@ -1351,139 +2681,523 @@ impl<T: Config> Pallet<T> {
 		// Note that we read one pool at a time to optimise memory usage.
 		for asset_pair in Pools::<T>::iter_keys().collect::<Vec<_>>() {
 			let mut pool = Pools::<T>::get(asset_pair).unwrap();
+			Self::sweep_pool(&mut pool, lp, &asset_pair)?;
+			Pools::<T>::insert(asset_pair, pool);
+		}
 
-			if let Some(range_orders_cache) = pool.range_orders_cache.get(lp).cloned() {
-				for (id, range) in range_orders_cache.iter() {
-					Self::inner_update_range_order(
-						&mut pool,
-						lp,
-						&asset_pair,
-						*id,
-						range.clone(),
-						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
-							liquidity: 0,
-						}),
-						NoOpStatus::Error,
-					)?;
-				}
+		for (asset_pair, fee_hundredth_pips) in FeeTierPools::<T>::iter_keys().collect::<Vec<_>>() {
+			let mut pool = FeeTierPools::<T>::get(asset_pair, fee_hundredth_pips).unwrap();
+			Self::sweep_pool(&mut pool, lp, &asset_pair)?;
+			FeeTierPools::<T>::insert(asset_pair, fee_hundredth_pips, pool);
+		}
+
+		Ok(())
+	}
+
+	/// Collects the fees and filled amounts of all of the LP's orders in the given pool.
+	fn sweep_pool(pool: &mut Pool<T>, lp: &T::AccountId, asset_pair: &AssetPair) -> DispatchResult {
+		if let Some(range_orders_cache) = pool.range_orders_cache.get(lp).cloned() {
+			for (id, range) in range_orders_cache.iter() {
+				Self::inner_update_range_order(
+					pool,
+					lp,
+					asset_pair,
+					*id,
+					range.clone(),
+					IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity { liquidity: 0 }),
+					NoOpStatus::Error,
+				)?;
 			}
+		}
 
-			for (assets, limit_orders_cache) in pool
-				.limit_orders_cache
-				.as_ref()
-				.into_iter()
-				.filter_map(|(assets, limit_orders_cache)| {
-					limit_orders_cache
+		for (assets, limit_orders_cache) in pool
+			.limit_orders_cache
+			.as_ref()
+			.into_iter()
+			.filter_map(|(assets, limit_orders_cache)| {
+				limit_orders_cache
+					.get(lp)
+					.cloned()
+					.map(|limit_orders_cache| (assets, limit_orders_cache))
+			})
+			.collect::<Vec<_>>()
+		{
+			for (id, tick) in limit_orders_cache {
+				Self::sweep_limit_order(pool, lp, asset_pair, assets.sell_order(), id, tick)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn collect_and_mint_limit_order_with_dispatch_error(
+		pool: &mut Pool<T>,
+		lp: &T::AccountId,
+		side: Side,
+		id: OrderId,
+		tick: Tick,
+		sold_amount: Amount,
+		noop_status: NoOpStatus,
+	) -> Result<(Collected, PositionInfo), DispatchError> {
+		let (collected, position_info) = match pool.pool_state.collect_and_mint_limit_order(
+			&(lp.clone(), id),
+			side,
+			tick,
+			sold_amount,
+		) {
+			Ok(ok) => Ok(ok),
+			Err(error) => Err(match error {
+				limit_orders::PositionError::NonExistent =>
+					if noop_status == NoOpStatus::Allow {
+						return Ok(Default::default())
+					} else {
+						Error::<T>::OrderDoesNotExist
+					},
+				limit_orders::PositionError::InvalidTick => Error::<T>::InvalidTick,
+				limit_orders::PositionError::Other(limit_orders::MintError::MaximumLiquidity) =>
+					Error::<T>::MaximumGrossLiquidity,
+				limit_orders::PositionError::Other(
+					limit_orders::MintError::MaximumPoolInstances,
+				) => Error::<T>::MaximumPoolInstances,
+			}),
+		}?;
+
+		Ok((collected, position_info))
+	}
+
+	pub fn inner_set_limit_order(
+		lp: &T::AccountId,
+		base_asset: any::Asset,
+		quote_asset: any::Asset,
+		side: Side,
+		id: OrderId,
+		option_tick: Option<Tick>,
+		sell_amount: AssetAmount,
+	) -> DispatchResult {
+		ensure!(
+			T::SafeMode::get().limit_order_update_enabled,
+			Error::<T>::UpdatingLimitOrdersDisabled
+		);
+		Self::try_mutate_order(lp, base_asset, quote_asset, |asset_pair, pool| {
+			Self::inner_set_limit_order_in_pool(
+				pool,
+				lp,
+				asset_pair,
+				side,
+				id,
+				option_tick,
+				sell_amount,
+			)
+		})
+	}
+
+	fn inner_set_limit_order_in_pool(
+		pool: &mut Pool<T>,
+		lp: &T::AccountId,
+		asset_pair: &AssetPair,
+		side: Side,
+		id: OrderId,
+		option_tick: Option<Tick>,
+		sell_amount: AssetAmount,
+	) -> DispatchResult {
+		let tick = match (
+			pool.limit_orders_cache[side.to_sold_pair()]
+				.get(lp)
+				.and_then(|limit_orders| limit_orders.get(&id))
+				.cloned(),
+			option_tick,
+		) {
+			(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+			(None, Some(tick)) => Ok(tick),
+			(Some(previous_tick), option_new_tick) => {
+				Self::inner_update_limit_order_at_tick(
+					pool,
+					lp,
+					asset_pair,
+					side,
+					id,
+					previous_tick,
+					IncreaseOrDecrease::Decrease(Amount::MAX),
+					NoOpStatus::Error,
+				)?;
+
+				Ok(option_new_tick.unwrap_or(previous_tick))
+			},
+		}?;
+		Self::inner_update_limit_order_at_tick(
+			pool,
+			lp,
+			asset_pair,
+			side,
+			id,
+			tick,
+			IncreaseOrDecrease::Increase(sell_amount.into()),
+			NoOpStatus::Allow,
+		)?;
+
+		Ok(())
+	}
+
+	/// Closes an order, returning whether it existed.
+	fn inner_cancel_order(lp: &T::AccountId, order: CloseOrder) -> Result<bool, DispatchError> {
+		match order {
+			CloseOrder::Limit { base_asset, quote_asset, side, id } => {
+				let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+				Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
+					let Some(previous_tick) = pool.limit_orders_cache[side.to_sold_pair()]
 						.get(lp)
+						.and_then(|limit_orders| limit_orders.get(&id))
 						.cloned()
-						.map(|limit_orders_cache| (assets, limit_orders_cache))
+					else {
+						return Ok(false)
+					};
+					Self::inner_update_limit_order_at_tick(
+						pool,
+						lp,
+						asset_pair,
+						side,
+						id,
+						previous_tick,
+						IncreaseOrDecrease::Decrease(Amount::MAX),
+						NoOpStatus::Allow,
+					)?;
+					Ok(true)
 				})
-				.collect::<Vec<_>>()
-			{
-				for (id, tick) in limit_orders_cache {
-					Self::sweep_limit_order(
-						&mut pool,
+			},
+			CloseOrder::Range { base_asset, quote_asset, id } => {
+				let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+				Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
+					let Some(previous_tick_range) = pool
+						.range_orders_cache
+						.get(lp)
+						.and_then(|range_orders| range_orders.get(&id))
+						.cloned()
+					else {
+						return Ok(false)
+					};
+					Self::inner_update_range_order(
+						pool,
 						lp,
-						&asset_pair,
-						assets.sell_order(),
+						asset_pair,
 						id,
-						tick,
+						previous_tick_range,
+						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+							liquidity: Liquidity::MAX,
+						}),
+						NoOpStatus::Allow,
 					)?;
+					Ok(true)
+				})
+			},
+		}
+	}
+
+	/// Returns the key of an existing order in [Pools], as used by [OrderTransferOffers].
+	fn order_transfer_key(
+		lp: &T::AccountId,
+		order: &CloseOrder,
+	) -> Result<(AssetPair, OrderKind, OrderId), DispatchError> {
+		let (base_asset, quote_asset) = match *order {
+			CloseOrder::Limit { base_asset, quote_asset, .. } |
+			CloseOrder::Range { base_asset, quote_asset, .. } => (base_asset, quote_asset),
+		};
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+		let (kind, id, exists) = match *order {
+			CloseOrder::Limit { side, id, .. } => (
+				OrderKind::Limit { side, pool_fee_hundredth_pips: None },
+				id,
+				pool.limit_orders_cache[side.to_sold_pair()]
+					.get(lp)
+					.is_some_and(|orders| orders.contains_key(&id)),
+			),
+			CloseOrder::Range { id, .. } => (
+				OrderKind::Range { pool_fee_hundredth_pips: None },
+				id,
+				pool.range_orders_cache.get(lp).is_some_and(|orders| orders.contains_key(&id)),
+			),
+		};
+		ensure!(exists, Error::<T>::OrderDoesNotExist);
+		Ok((asset_pair, kind, id))
+	}
+
+	/// Moves an order to another LP without changing it. Anything the order earned is paid to its
+	/// previous owner first.
+	fn inner_transfer_order(
+		pool: &mut Pool<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		asset_pair: &AssetPair,
+		kind: OrderKind,
+		id: OrderId,
+		new_id: OrderId,
+	) -> DispatchResult {
+		match kind {
+			OrderKind::Limit { side, .. } => {
+				let limit_orders = &pool.limit_orders_cache[side.to_sold_pair()];
+				ensure!(
+					!limit_orders.get(to).is_some_and(|orders| orders.contains_key(&new_id)),
+					Error::<T>::OrderIdInUse
+				);
+				let tick = limit_orders
+					.get(from)
+					.and_then(|orders| orders.get(&id))
+					.cloned()
+					.ok_or(Error::<T>::OrderDoesNotExist)?;
+
+				Self::sweep_limit_order(pool, from, asset_pair, side, id, tick)?;
+				pool.pool_state
+					.transfer_limit_order(&(from.clone(), id), (to.clone(), new_id), side, tick)
+					.map_err(|error| match error {
+						limit_orders::PositionError::InvalidTick => Error::<T>::InvalidTick,
+						// The order may have been fully executed, and so closed by the sweep.
+						limit_orders::PositionError::NonExistent => Error::<T>::OrderDoesNotExist,
+						limit_orders::PositionError::Other(
+							limit_orders::TransferError::PositionAlreadyExists,
+						) => Error::<T>::OrderIdInUse,
+					})?;
+
+				let limit_orders = &mut pool.limit_orders_cache[side.to_sold_pair()];
+				if let Some(from_orders) = limit_orders.get_mut(from) {
+					from_orders.remove(&id);
+					if from_orders.is_empty() {
+						limit_orders.remove(from);
+					}
+				}
+				limit_orders.entry(to.clone()).or_default().insert(new_id, tick);
+				Self::note_order_removed(from, asset_pair);
+				Self::note_order_added(to, asset_pair);
+
+				LimitOrderExpiries::<T>::remove((from, asset_pair, side, id));
+			},
+			OrderKind::Range { .. } => {
+				ensure!(
+					!pool
+						.range_orders_cache
+						.get(to)
+						.is_some_and(|orders| orders.contains_key(&new_id)),
+					Error::<T>::OrderIdInUse
+				);
+				let tick_range = pool
+					.range_orders_cache
+					.get(from)
+					.and_then(|orders| orders.get(&id))
+					.cloned()
+					.ok_or(Error::<T>::OrderDoesNotExist)?;
+
+				Self::inner_update_range_order(
+					pool,
+					from,
+					asset_pair,
+					id,
+					tick_range.clone(),
+					IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity { liquidity: 0 }),
+					NoOpStatus::Allow,
+				)?;
+				pool.pool_state
+					.transfer_range_order(
+						&(from.clone(), id),
+						(to.clone(), new_id),
+						tick_range.clone(),
+					)
+					.map_err(|error| match error {
+						range_orders::PositionError::InvalidTickRange =>
+							Error::<T>::InvalidTickRange,
+						range_orders::PositionError::NonExistent => Error::<T>::OrderDoesNotExist,
+						range_orders::PositionError::Other(
+							range_orders::TransferError::PositionAlreadyExists,
+						) => Error::<T>::OrderIdInUse,
+					})?;
+
+				if let Some(from_orders) = pool.range_orders_cache.get_mut(from) {
+					from_orders.remove(&id);
+					if from_orders.is_empty() {
+						pool.range_orders_cache.remove(from);
+					}
 				}
-			}
+				pool.range_orders_cache
+					.entry(to.clone())
+					.or_default()
+					.insert(new_id, tick_range);
+				Self::note_order_removed(from, asset_pair);
+				Self::note_order_added(to, asset_pair);
+
+				AutoRebalancingRangeOrders::<T>::remove(asset_pair, (from, id));
+			},
+		}
 
-			Pools::<T>::insert(asset_pair, pool);
+		let old_order = (from.clone(), *asset_pair, kind, id);
+		OrderTransferOffers::<T>::remove(&old_order);
+		AutoCompoundingRangeOrders::<T>::remove(&old_order);
+		if let Some(matures_at) = YoungOrders::<T>::take(&old_order) {
+			let new_order = (to.clone(), *asset_pair, kind, new_id);
+			MaturingOrders::<T>::append(matures_at, new_order.clone());
+			YoungOrders::<T>::insert(new_order, matures_at);
 		}
 
 		Ok(())
 	}
 
-	fn collect_and_mint_limit_order_with_dispatch_error(
-		pool: &mut Pool<T>,
+	/// Pays fees collected from an order to the LP, unless the order is too young to earn fees, in
+	/// which case they go to the network fee. The pool's protocol fee is also paid to the network
+	/// fee. Returns the amount paid to the LP.
+	fn credit_collected_fees(
 		lp: &T::AccountId,
-		side: Side,
+		asset_pair: &AssetPair,
+		kind: OrderKind,
 		id: OrderId,
-		tick: Tick,
-		sold_amount: Amount,
-		noop_status: NoOpStatus,
-	) -> Result<(Collected, PositionInfo), DispatchError> {
-		let (collected, position_info) = match pool.pool_state.collect_and_mint_limit_order(
-			&(lp.clone(), id),
-			side,
-			tick,
-			sold_amount,
-		) {
-			Ok(ok) => Ok(ok),
-			Err(error) => Err(match error {
-				limit_orders::PositionError::NonExistent =>
-					if noop_status == NoOpStatus::Allow {
-						return Ok(Default::default())
-					} else {
-						Error::<T>::OrderDoesNotExist
-					},
-				limit_orders::PositionError::InvalidTick => Error::<T>::InvalidTick,
-				limit_orders::PositionError::Other(limit_orders::MintError::MaximumLiquidity) =>
-					Error::<T>::MaximumGrossLiquidity,
-				limit_orders::PositionError::Other(
-					limit_orders::MintError::MaximumPoolInstances,
-				) => Error::<T>::MaximumPoolInstances,
-			}),
-		}?;
+		asset: Asset,
+		collected_fees: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
+		if collected_fees.is_zero() {
+			return Ok(0)
+		}
+		if YoungOrders::<T>::contains_key((lp, asset_pair, kind, id)) {
+			T::SwapRequestHandler::init_network_fee_swap_request(asset, collected_fees);
+			Self::deposit_event(Event::<T>::YoungOrderFeesForfeited {
+				lp: lp.clone(),
+				base_asset: asset_pair.assets().base,
+				quote_asset: asset_pair.assets().quote,
+				kind,
+				id,
+				asset,
+				amount: collected_fees,
+			});
+			return Ok(0)
+		}
+		let protocol_fee = ProtocolFees::<T>::get(asset_pair) * collected_fees;
+		if !protocol_fee.is_zero() {
+			T::SwapRequestHandler::init_network_fee_swap_request(asset, protocol_fee);
+			Self::deposit_event(Event::<T>::ProtocolFeeTaken {
+				lp: lp.clone(),
+				base_asset: asset_pair.assets().base,
+				quote_asset: asset_pair.assets().quote,
+				kind,
+				id,
+				asset,
+				amount: protocol_fee,
+			});
+		}
+		let collected_fees = collected_fees - protocol_fee;
+		HistoricalEarnedFees::<T>::mutate(lp, asset, |balance| {
+			*balance = balance.saturating_add(collected_fees)
+		});
+		EpochLpEarnedFees::<T>::mutate((lp, asset_pair, T::EpochInfo::epoch_index()), |fees| {
+			if asset == asset_pair.assets().base {
+				fees.base.saturating_accrue(collected_fees);
+			} else {
+				fees.quote.saturating_accrue(collected_fees);
+			}
+		});
+		T::LpBalance::try_credit_account(lp, asset, collected_fees)?;
+		Ok(collected_fees)
+	}
 
-		Ok((collected, position_info))
+	/// Marks an order that liquidity was just added to as too young to earn fees, if there is a
+	/// [MinimumOrderAgeForFees].
+	fn reset_order_age(lp: &T::AccountId, asset_pair: &AssetPair, kind: OrderKind, id: OrderId) {
+		let minimum_age = MinimumOrderAgeForFees::<T>::get();
+		if minimum_age == 0 {
+			return
+		}
+		let matures_at = frame_system::Pallet::<T>::block_number() + minimum_age.into();
+		let order = (lp.clone(), *asset_pair, kind, id);
+		MaturingOrders::<T>::append(matures_at, order.clone());
+		YoungOrders::<T>::insert(order, matures_at);
 	}
 
-	pub fn inner_set_limit_order(
-		lp: &T::AccountId,
-		base_asset: any::Asset,
-		quote_asset: any::Asset,
-		side: Side,
+	/// Collects the fees an order earned while it was young, which are forfeited, after which the
+	/// order earns fees as normal.
+	fn mature_order(
+		lp: T::AccountId,
+		asset_pair: AssetPair,
+		kind: OrderKind,
 		id: OrderId,
-		option_tick: Option<Tick>,
-		sell_amount: AssetAmount,
-	) -> DispatchResult {
-		ensure!(
-			T::SafeMode::get().limit_order_update_enabled,
-			Error::<T>::UpdatingLimitOrdersDisabled
-		);
-		Self::try_mutate_order(lp, base_asset, quote_asset, |asset_pair, pool| {
-			let tick = match (
-				pool.limit_orders_cache[side.to_sold_pair()]
-					.get(lp)
+		current_block: BlockNumberFor<T>,
+	) {
+		// Liquidity may have been added to the order since it was queued:
+		if YoungOrders::<T>::get((&lp, asset_pair, kind, id)) != Some(current_block) {
+			return
+		}
+
+		let result = with_storage_layer(|| match kind {
+			OrderKind::Limit { side, pool_fee_hundredth_pips } => Self::try_mutate_order_pool(
+				asset_pair,
+				pool_fee_hundredth_pips,
+				|asset_pair, pool| match pool.limit_orders_cache[side.to_sold_pair()]
+					.get(&lp)
 					.and_then(|limit_orders| limit_orders.get(&id))
-					.cloned(),
-				option_tick,
-			) {
-				(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-				(None, Some(tick)) => Ok(tick),
-				(Some(previous_tick), option_new_tick) => {
-					Self::inner_update_limit_order_at_tick(
+					.cloned()
+				{
+					Some(tick) => Self::sweep_limit_order(pool, &lp, asset_pair, side, id, tick),
+					None => Ok(()),
+				},
+			),
+			OrderKind::Range { pool_fee_hundredth_pips } => Self::try_mutate_order_pool(
+				asset_pair,
+				pool_fee_hundredth_pips,
+				|asset_pair, pool| match pool
+					.range_orders_cache
+					.get(&lp)
+					.and_then(|orders| orders.get(&id))
+					.cloned()
+				{
+					Some(tick_range) => Self::inner_update_range_order(
 						pool,
-						lp,
+						&lp,
 						asset_pair,
-						side,
 						id,
-						previous_tick,
-						IncreaseOrDecrease::Decrease(Amount::MAX),
+						tick_range,
+						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+							liquidity: 0,
+						}),
 						NoOpStatus::Error,
-					)?;
-
-					Ok(option_new_tick.unwrap_or(previous_tick))
+					)
+					.map(|_| ()),
+					None => Ok(()),
 				},
-			}?;
-			Self::inner_update_limit_order_at_tick(
-				pool,
+			),
+		});
+		if let Err(error) = result {
+			log_or_panic!("Failed to collect fees of maturing order {id} of {lp:?}: {error:?}");
+		}
+
+		YoungOrders::<T>::remove((&lp, asset_pair, kind, id));
+	}
+
+	/// Cancels a limit order that has reached its expiry block, returning its funds to the LP.
+	fn expire_limit_order(
+		lp: T::AccountId,
+		asset_pair: AssetPair,
+		side: Side,
+		id: OrderId,
+		current_block: BlockNumberFor<T>,
+	) {
+		// The order may have been set again with a different expiry since it was queued:
+		if LimitOrderExpiries::<T>::get((&lp, asset_pair, side, id)) != Some(current_block) {
+			return
+		}
+		LimitOrderExpiries::<T>::remove((&lp, asset_pair, side, id));
+
+		let assets = asset_pair.assets();
+		match with_storage_layer(|| {
+			Self::inner_cancel_order(
+				&lp,
+				CloseOrder::Limit { base_asset: assets.base, quote_asset: assets.quote, side, id },
+			)
+		}) {
+			Ok(true) => Self::deposit_event(Event::<T>::LimitOrderExpired {
 				lp,
-				asset_pair,
+				base_asset: assets.base,
+				quote_asset: assets.quote,
 				side,
 				id,
-				tick,
-				IncreaseOrDecrease::Increase(sell_amount.into()),
-				NoOpStatus::Allow,
-			)?;
-
-			Ok(())
-		})
+			}),
+			Ok(false) => {},
+			Err(error) => {
+				log_or_panic!("Failed to cancel expired limit order {id} of {lp:?}: {error:?}");
+			},
+		}
 	}
 
 	/// Updates limit order closing the previous one if necessary (in case of tick change)
@@ -1653,9 +3367,272 @@ impl<T: Config> Pallet<T> {
 			sold_amount_change,
 		)?;
 
+		if matches!(sold_amount_change, IncreaseOrDecrease::Increase(amount) if amount > 0) {
+			Self::reset_order_age(
+				lp,
+				asset_pair,
+				OrderKind::Limit {
+					side,
+					pool_fee_hundredth_pips: Self::pool_fee_tier(asset_pair, pool),
+				},
+				id,
+			);
+		}
+
 		Ok(*sold_amount_change.abs())
 	}
 
+	/// Same as [Self::swap_in_pool], also adding the swap to the pool's statistics for the current
+	/// epoch.
+	fn swap_in_pool_recording_statistics(
+		pool: &mut Pool<T>,
+		asset_pair: &AssetPair,
+		order: Side,
+		input_amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
+		let totals = |pool: &Pool<T>| {
+			(
+				pool.pool_state
+					.range_order_swap_inputs()
+					.zip(pool.pool_state.limit_order_swap_inputs())
+					.map(|(range, limit)| range.saturating_add(limit)),
+				pool.pool_state
+					.range_order_total_fees_earned()
+					.zip(pool.pool_state.limit_order_total_fees_earned())
+					.map(|(range, limit)| range.saturating_add(limit)),
+			)
+		};
+
+		let (swap_inputs_before, lp_fees_before) = totals(pool);
+		let output_amount = Self::swap_in_pool(pool, asset_pair, order, input_amount)?;
+		let (swap_inputs_after, lp_fees_after) = totals(pool);
+
+		let accrue = |total: PoolPairsMap<Amount>,
+		              after: PoolPairsMap<Amount>,
+		              before: PoolPairsMap<Amount>| {
+			total
+				.zip(after.zip(before))
+				.map(|(total, (after, before))| total.saturating_add(after.saturating_sub(before)))
+		};
+		EpochPoolStatistics::<T>::mutate(asset_pair, T::EpochInfo::epoch_index(), |statistics| {
+			statistics.swap_inputs =
+				accrue(statistics.swap_inputs, swap_inputs_after, swap_inputs_before);
+			statistics.lp_fees = accrue(statistics.lp_fees, lp_fees_after, lp_fees_before);
+		});
+
+		Ok(output_amount)
+	}
+
+	fn swap_in_pool(
+		pool: &mut Pool<T>,
+		asset_pair: &AssetPair,
+		order: Side,
+		input_amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
+		use cf_amm::math::tick_at_sqrt_price;
+
+		let output_amount = if input_amount == 0 {
+			0
+		} else {
+			let input_amount: Amount = input_amount.into();
+
+			let tick_before =
+				pool.pool_state.current_price(order).ok_or(Error::<T>::InsufficientLiquidity)?.2;
+			let (output_amount, _remaining_amount) =
+				pool.pool_state.swap(order, input_amount, None);
+			let tick_after =
+				pool.pool_state.current_price(order).ok_or(Error::<T>::InsufficientLiquidity)?.2;
+
+			let swap_tick =
+				tick_at_sqrt_price(PoolState::<(T::AccountId, OrderId)>::swap_sqrt_price(
+					order,
+					input_amount,
+					output_amount,
+				));
+			let bounded_swap_tick = if tick_after < tick_before {
+				core::cmp::min(core::cmp::max(tick_after, swap_tick), tick_before)
+			} else {
+				core::cmp::min(core::cmp::max(tick_before, swap_tick), tick_after)
+			};
+
+			if let Some(maximum_price_impact) = MaximumPriceImpact::<T>::get(*asset_pair) {
+				if core::cmp::min(
+					bounded_swap_tick.abs_diff(tick_after),
+					bounded_swap_tick.abs_diff(tick_before),
+				) > maximum_price_impact
+				{
+					return Err(Error::<T>::InsufficientLiquidity.into());
+				}
+			}
+
+			output_amount.try_into().map_err(|_| Error::<T>::OutputOverflow)?
+		};
+
+		Ok(output_amount)
+	}
+
+	/// The amounts sold and bought, and the fees earned, by each of the pool's limit orders on one
+	/// side since they were last collected, keyed by order.
+	fn uncollected_limit_order_amounts(
+		pool: &Pool<T>,
+		side: Side,
+	) -> BTreeMap<(T::AccountId, OrderId), (Tick, Collected)> {
+		pool.limit_orders_cache[side.to_sold_pair()]
+			.iter()
+			.flat_map(|(lp, orders)| {
+				orders.iter().filter_map(move |(id, tick)| {
+					let (collected, _) =
+						pool.pool_state.limit_order(&(lp.clone(), *id), side, *tick).ok()?;
+					Some(((lp.clone(), *id), (*tick, collected)))
+				})
+			})
+			.collect()
+	}
+
+	/// Emits a [LimitOrderFilled](Event::LimitOrderFilled) event for each limit order on the given
+	/// side that sold any of its assets since `orders_before` was taken.
+	fn deposit_limit_order_fills(
+		pool: &Pool<T>,
+		asset_pair: &AssetPair,
+		side: Side,
+		orders_before: BTreeMap<(T::AccountId, OrderId), (Tick, Collected)>,
+		swap_ids: &[SwapId],
+	) {
+		for ((lp, id), (tick, collected_before)) in orders_before {
+			let Ok((collected, position_info)) =
+				pool.pool_state.limit_order(&(lp.clone(), id), side, tick)
+			else {
+				continue
+			};
+			let sold_amount = collected.sold_amount.saturating_sub(collected_before.sold_amount);
+			if sold_amount.is_zero() {
+				continue
+			}
+			Self::deposit_event(Event::<T>::LimitOrderFilled {
+				lp,
+				base_asset: asset_pair.assets().base,
+				quote_asset: asset_pair.assets().quote,
+				side,
+				id,
+				tick,
+				sold_amount: sold_amount.saturated_into(),
+				bought_amount: collected
+					.bought_amount
+					.saturating_sub(collected_before.bought_amount)
+					.saturated_into(),
+				fees_earned: collected.fees.saturating_sub(collected_before.fees).saturated_into(),
+				remaining_amount: position_info.amount.saturated_into(),
+				swap_ids: swap_ids.to_vec(),
+			});
+		}
+	}
+
+	fn inner_set_range_order(
+		pool: &mut Pool<T>,
+		lp: &T::AccountId,
+		asset_pair: &AssetPair,
+		id: OrderId,
+		option_tick_range: Option<Range<Tick>>,
+		size: RangeOrderSize,
+	) -> DispatchResult {
+		let tick_range = match (
+			pool.range_orders_cache
+				.get(lp)
+				.and_then(|range_orders| range_orders.get(&id))
+				.cloned(),
+			option_tick_range,
+		) {
+			(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+			(None, Some(tick_range)) => Ok(tick_range),
+			(Some(previous_tick_range), option_new_tick_range) => {
+				Self::inner_update_range_order(
+					pool,
+					lp,
+					asset_pair,
+					id,
+					previous_tick_range.clone(),
+					IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+						liquidity: Liquidity::MAX,
+					}),
+					NoOpStatus::Error,
+				)?;
+
+				Ok(option_new_tick_range.unwrap_or(previous_tick_range))
+			},
+		}?;
+		let (_, new_order_liquidity) = Self::inner_update_range_order(
+			pool,
+			lp,
+			asset_pair,
+			id,
+			tick_range,
+			IncreaseOrDecrease::Increase(size.into()),
+			NoOpStatus::Allow,
+		)?;
+
+		// Asset input and resultant liquidity changes should be consistent.
+		// This condition can be breached in cases where eg. the assets amounts are rounded
+		// to zero liquidity.
+		ensure!(
+			(size.max_is_zero() && new_order_liquidity.is_zero()) ||
+				(!size.max_is_zero() && !new_order_liquidity.is_zero()),
+			Error::<T>::InvalidSize
+		);
+
+		Ok(())
+	}
+
+	/// Mints liquidity into a range order, debiting the required assets from the LP. Returns
+	/// `None` if the order doesn't exist and `noop_status` allows it.
+	#[allow(clippy::type_complexity)]
+	fn mint_range_order(
+		pool: &mut Pool<T>,
+		lp: &T::AccountId,
+		asset_pair: &AssetPair,
+		id: OrderId,
+		tick_range: Range<Tick>,
+		size: range_orders::Size,
+		noop_status: NoOpStatus,
+	) -> Result<
+		Option<(AssetAmounts, Liquidity, range_orders::Collected, range_orders::PositionInfo)>,
+		DispatchError,
+	> {
+		match pool.pool_state.collect_and_mint_range_order(
+			&(lp.clone(), id),
+			tick_range.clone(),
+			size,
+			|required_amounts| {
+				asset_pair.assets().zip(required_amounts).try_map(|(asset, required_amount)| {
+					AssetAmount::try_from(required_amount).map_err(Into::into).and_then(
+						|required_amount| {
+							T::LpBalance::try_debit_account(lp, asset, required_amount)
+								.map(|()| required_amount)
+						},
+					)
+				})
+			},
+		) {
+			Ok(ok) => Ok(Some(ok)),
+			Err(error) => Err(match error {
+				range_orders::PositionError::InvalidTickRange =>
+					Error::<T>::InvalidTickRange.into(),
+				range_orders::PositionError::NonExistent =>
+					if noop_status == NoOpStatus::Allow {
+						return Ok(None)
+					} else {
+						Error::<T>::OrderDoesNotExist.into()
+					},
+				range_orders::PositionError::Other(range_orders::MintError::CallbackFailed(e)) => e,
+				range_orders::PositionError::Other(
+					range_orders::MintError::MaximumGrossLiquidity,
+				) => Error::<T>::MaximumGrossLiquidity.into(),
+				range_orders::PositionError::Other(
+					cf_amm::range_orders::MintError::AssetRatioUnachieveable,
+				) => Error::<T>::AssetRatioUnachieveable.into(),
+			}),
+		}
+	}
+
 	fn inner_update_range_order(
 		pool: &mut Pool<T>,
 		lp: &T::AccountId,
@@ -1667,49 +3644,19 @@ impl<T: Config> Pallet<T> {
 	) -> Result<(AssetAmounts, Liquidity), DispatchError> {
 		let (liquidity_change, position_info, assets_change, collected) = match size_change {
 			IncreaseOrDecrease::Increase(size) => {
-				let (assets_debited, minted_liquidity, collected, position_info) =
-					match pool.pool_state.collect_and_mint_range_order(
-						&(lp.clone(), id),
+				let Some((assets_debited, minted_liquidity, collected, position_info)) =
+					Self::mint_range_order(
+						pool,
+						lp,
+						asset_pair,
+						id,
 						tick_range.clone(),
 						size,
-						|required_amounts| {
-							asset_pair.assets().zip(required_amounts).try_map(
-								|(asset, required_amount)| {
-									AssetAmount::try_from(required_amount)
-										.map_err(Into::into)
-										.and_then(|required_amount| {
-											T::LpBalance::try_debit_account(
-												lp,
-												asset,
-												required_amount,
-											)
-											.map(|()| required_amount)
-										})
-								},
-							)
-						},
-					) {
-						Ok(ok) => Ok(ok),
-						Err(error) => Err(match error {
-							range_orders::PositionError::InvalidTickRange =>
-								Error::<T>::InvalidTickRange.into(),
-							range_orders::PositionError::NonExistent =>
-								if noop_status == NoOpStatus::Allow {
-									return Ok(Default::default())
-								} else {
-									Error::<T>::OrderDoesNotExist.into()
-								},
-							range_orders::PositionError::Other(
-								range_orders::MintError::CallbackFailed(e),
-							) => e,
-							range_orders::PositionError::Other(
-								range_orders::MintError::MaximumGrossLiquidity,
-							) => Error::<T>::MaximumGrossLiquidity.into(),
-							range_orders::PositionError::Other(
-								cf_amm::range_orders::MintError::AssetRatioUnachieveable,
-							) => Error::<T>::AssetRatioUnachieveable.into(),
-						}),
-					}?;
+						noop_status,
+					)?
+				else {
+					return Ok(Default::default())
+				};
 
 				(
 					IncreaseOrDecrease::Increase(minted_liquidity),
@@ -1759,29 +3706,73 @@ impl<T: Config> Pallet<T> {
 			},
 		};
 
+		let kind =
+			OrderKind::Range { pool_fee_hundredth_pips: Self::pool_fee_tier(asset_pair, pool) };
 		let collected_fees =
 			asset_pair.assets().zip(collected.fees).try_map(|(asset, collected_fees)| {
 				AssetAmount::try_from(collected_fees).map_err(Into::into).and_then(
 					|collected_fees| {
-						HistoricalEarnedFees::<T>::mutate(lp, asset, |balance| {
-							*balance = balance.saturating_add(collected_fees)
-						});
-						T::LpBalance::try_credit_account(lp, asset, collected_fees)
-							.map(|()| collected_fees)
+						Self::credit_collected_fees(lp, asset_pair, kind, id, asset, collected_fees)
 					},
 				)
 			})?;
+		if matches!(liquidity_change, IncreaseOrDecrease::Increase(liquidity) if liquidity > 0) {
+			Self::reset_order_age(lp, asset_pair, kind, id);
+		}
+
+		// Roll the collected fees back into the order if it is auto-compounding. This is not new
+		// liquidity for the purposes of the order's age.
+		let position_info = if position_info.liquidity > 0 &&
+			collected_fees != Default::default() &&
+			AutoCompoundingRangeOrders::<T>::contains_key((lp, asset_pair, kind, id))
+		{
+			match Self::mint_range_order(
+				pool,
+				lp,
+				asset_pair,
+				id,
+				tick_range.clone(),
+				range_orders::Size::Amount {
+					maximum: collected_fees.map(Into::into),
+					minimum: Default::default(),
+				},
+				NoOpStatus::Error,
+			)? {
+				Some((amounts, liquidity, _, position_info)) => {
+					if liquidity > 0 {
+						Self::deposit_event(Event::<T>::RangeOrderFeesCompounded {
+							lp: lp.clone(),
+							base_asset: asset_pair.assets().base,
+							quote_asset: asset_pair.assets().quote,
+							id,
+							liquidity,
+							amounts,
+						});
+					}
+					position_info
+				},
+				None => position_info,
+			}
+		} else {
+			position_info
+		};
 
 		if position_info.liquidity == 0 {
+			AutoCompoundingRangeOrders::<T>::remove((lp, asset_pair, kind, id));
+			OrderTransferOffers::<T>::remove((lp, asset_pair, kind, id));
 			if let Some(range_orders) = pool.range_orders_cache.get_mut(lp) {
-				range_orders.remove(&id);
+				if range_orders.remove(&id).is_some() {
+					Self::note_order_removed(lp, asset_pair);
+				}
 				if range_orders.is_empty() {
 					pool.range_orders_cache.remove(lp);
 				}
 			}
 		} else {
 			let range_orders = pool.range_orders_cache.entry(lp.clone()).or_default();
-			range_orders.insert(id, tick_range.clone());
+			if range_orders.insert(id, tick_range.clone()).is_none() {
+				Self::note_order_added(lp, asset_pair);
+			}
 		}
 
 		let zero_change = *liquidity_change.abs() == 0;
@@ -1845,7 +3836,453 @@ impl<T: Config> Pallet<T> {
 	) -> Result<R, E> {
 		Pools::<T>::try_mutate(asset_pair, |maybe_pool| {
 			let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
-			f(&asset_pair, pool)
+			let result = f(&asset_pair, pool)?;
+			Self::record_price_observation(asset_pair, pool);
+			Ok(result)
+		})
+	}
+
+	/// Same as [Self::try_mutate_pool], but for the pool identified by the fee in an [OrderKind].
+	fn try_mutate_order_pool<
+		R,
+		E: From<pallet::Error<T>>,
+		F: FnOnce(&AssetPair, &mut Pool<T>) -> Result<R, E>,
+	>(
+		asset_pair: AssetPair,
+		pool_fee_hundredth_pips: Option<u32>,
+		f: F,
+	) -> Result<R, E> {
+		match pool_fee_hundredth_pips {
+			Some(fee_hundredth_pips) =>
+				FeeTierPools::<T>::try_mutate(asset_pair, fee_hundredth_pips, |maybe_pool| {
+					let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
+					f(&asset_pair, pool)
+				}),
+			None => Self::try_mutate_pool(asset_pair, f),
+		}
+	}
+
+	/// The fee identifying a pool in an [OrderKind]: `None` for the asset pair's pool in [Pools],
+	/// otherwise the fee of the pool in [FeeTierPools]. Fees are unique per asset pair, and only
+	/// the fee of the pool in [Pools] can be changed, so this doesn't change over a pool's life.
+	fn pool_fee_tier(asset_pair: &AssetPair, pool: &Pool<T>) -> Option<u32> {
+		let fee_hundredth_pips = pool.pool_state.range_order_fee();
+		FeeTierPools::<T>::contains_key(asset_pair, fee_hundredth_pips)
+			.then_some(fee_hundredth_pips)
+	}
+
+	/// Re-centres auto-rebalancing range orders whose range the price has moved too far away from,
+	/// within the given weight. If the weight runs out, the remaining orders are checked in the
+	/// next block(s), see [AutoRebalancingCursor].
+	fn rebalance_range_orders(current_block: u32, remaining_weight: Weight) -> Weight {
+		let read_weight = T::DbWeight::get().reads(1);
+		let rebalance_weight =
+			T::WeightInfo::set_range_order().saturating_add(T::DbWeight::get().writes(1));
+		// Reading and writing the cursor:
+		let mut weight_used = T::DbWeight::get().reads_writes(1, 1);
+		if weight_used.any_gt(remaining_weight) {
+			return Weight::zero()
+		}
+		let has_weight_for = |weight_used: Weight, weight: Weight| {
+			!weight_used.saturating_add(weight).any_gt(remaining_weight)
+		};
+
+		let mut orders = match AutoRebalancingCursor::<T>::take() {
+			Some(cursor) => AutoRebalancingRangeOrders::<T>::iter_from(cursor),
+			None => AutoRebalancingRangeOrders::<T>::iter(),
+		};
+		// The key after which the orders that haven't been checked yet start:
+		let mut cursor = orders.last_raw_key().to_vec();
+		let mut current_pool: Option<(AssetPair, Pool<T>, Tick)> = None;
+
+		loop {
+			if !has_weight_for(weight_used, read_weight) {
+				AutoRebalancingCursor::<T>::put(cursor);
+				break
+			}
+			weight_used.saturating_accrue(read_weight);
+			let Some((asset_pair, (lp, id), mut parameters)) = orders.next() else { break };
+
+			if current_pool.as_ref().is_none_or(|(pool_pair, ..)| *pool_pair != asset_pair) {
+				if !has_weight_for(weight_used, read_weight) {
+					AutoRebalancingCursor::<T>::put(cursor);
+					break
+				}
+				weight_used.saturating_accrue(read_weight);
+				// Minting and burning range orders doesn't move the price, so the tick holds for
+				// all of the pair's orders:
+				current_pool = Pools::<T>::get(asset_pair).map(|pool| {
+					let current_tick =
+						tick_at_sqrt_price(pool.pool_state.current_range_order_pool_price());
+					(asset_pair, pool, current_tick)
+				});
+			}
+			let Some((_, pool, current_tick)) = &current_pool else {
+				cursor = orders.last_raw_key().to_vec();
+				continue
+			};
+			let current_tick = *current_tick;
+
+			let Some(tick_range) =
+				pool.range_orders_cache.get(&lp).and_then(|orders| orders.get(&id)).cloned()
+			else {
+				// The order has been closed:
+				AutoRebalancingRangeOrders::<T>::remove(asset_pair, (&lp, id));
+				weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+				cursor = orders.last_raw_key().to_vec();
+				continue
+			};
+			if current_block.saturating_sub(parameters.last_rebalanced_at) <
+				MIN_BLOCKS_BETWEEN_REBALANCES ||
+				!parameters.should_rebalance(&tick_range, current_tick)
+			{
+				cursor = orders.last_raw_key().to_vec();
+				continue
+			}
+			if !has_weight_for(weight_used, rebalance_weight) {
+				// Check this order again once there is enough weight to rebalance it:
+				AutoRebalancingCursor::<T>::put(cursor);
+				break
+			}
+			weight_used.saturating_accrue(rebalance_weight);
+
+			let new_tick_range = parameters.range_centred_on(current_tick);
+			if let Err(error) = with_storage_layer(|| {
+				Self::try_mutate_pool(asset_pair, |asset_pair, pool| {
+					Self::rebalance_range_order(
+						pool,
+						&lp,
+						asset_pair,
+						id,
+						tick_range,
+						new_tick_range,
+					)
+				})
+			}) {
+				Self::deposit_event(Event::<T>::RangeOrderRebalanceFailed {
+					lp: lp.clone(),
+					base_asset: asset_pair.assets().base,
+					quote_asset: asset_pair.assets().quote,
+					id,
+					error,
+				});
+			}
+
+			// Also limit the rate of retries if rebalancing failed:
+			parameters.last_rebalanced_at = current_block;
+			AutoRebalancingRangeOrders::<T>::insert(asset_pair, (lp, id), parameters);
+			cursor = orders.last_raw_key().to_vec();
+		}
+
+		weight_used
+	}
+
+	/// Moves a range order to a new range, keeping its liquidity.
+	fn rebalance_range_order(
+		pool: &mut Pool<T>,
+		lp: &T::AccountId,
+		asset_pair: &AssetPair,
+		id: OrderId,
+		tick_range: Range<Tick>,
+		new_tick_range: Range<Tick>,
+	) -> DispatchResult {
+		let (_, liquidity) = Self::inner_update_range_order(
+			pool,
+			lp,
+			asset_pair,
+			id,
+			tick_range,
+			IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+				liquidity: Liquidity::MAX,
+			}),
+			NoOpStatus::Error,
+		)?;
+		Self::inner_update_range_order(
+			pool,
+			lp,
+			asset_pair,
+			id,
+			new_tick_range,
+			IncreaseOrDecrease::Increase(range_orders::Size::Liquidity { liquidity }),
+			NoOpStatus::Error,
+		)?;
+		Ok(())
+	}
+
+	/// Records the pool's current mid price (as a tick) so that it can be used to calculate
+	/// time-weighted average prices.
+	fn record_price_observation(asset_pair: AssetPair, pool: &mut Pool<T>) {
+		let tick = match (
+			pool.pool_state.current_price(Side::Sell).map(|(_, _, tick)| tick),
+			pool.pool_state.current_price(Side::Buy).map(|(_, _, tick)| tick),
+		) {
+			(Some(sell_tick), Some(buy_tick)) => (sell_tick + buy_tick) / 2,
+			(Some(tick), None) | (None, Some(tick)) => tick,
+			(None, None) => return,
+		};
+		let current_block: u32 = frame_system::Pallet::<T>::block_number().saturated_into();
+
+		PriceObservations::<T>::mutate(asset_pair, |observations| {
+			// Bound the change relative to the last observation from a previous block:
+			let previous = observations
+				.iter()
+				.rev()
+				.find(|observation| observation.block < current_block)
+				.copied();
+			let tick = previous.map_or(tick, |previous| {
+				let max_change = MAX_OBSERVED_TICK_CHANGE_PER_BLOCK
+					.saturating_mul((current_block - previous.block).min(i32::MAX as u32) as Tick);
+				tick.clamp(
+					previous.tick.saturating_sub(max_change),
+					previous.tick.saturating_add(max_change),
+				)
+			});
+
+			match observations.last_mut() {
+				// No time has passed since the last observation, so only the tick changes:
+				Some(last) if last.block == current_block => last.tick = tick,
+				last => {
+					let tick_cumulative =
+						last.map_or(0, |last| last.tick_cumulative_at(current_block));
+					observations.push(PriceObservation {
+						block: current_block,
+						tick,
+						tick_cumulative,
+					});
+				},
+			}
+
+			// Keep the latest observation at or before the start of the longest window, and all
+			// observations after it:
+			let window_start = current_block.saturating_sub(MAX_TWAP_WINDOW_BLOCKS);
+			if let Some(first_needed) =
+				observations.iter().rposition(|observation| observation.block <= window_start)
+			{
+				observations.drain(..first_needed);
+			}
+		});
+	}
+
+	/// Suspends swaps in pools whose price has moved too far within their circuit breaker's
+	/// window, and resumes those whose suspension has ended.
+	fn check_price_circuit_breakers(current_block: BlockNumberFor<T>) -> Weight {
+		let mut weight_used = Weight::zero();
+		for (asset_pair, circuit_breaker) in PriceCircuitBreakers::<T>::iter() {
+			weight_used.saturating_accrue(T::DbWeight::get().reads(3));
+
+			let resumes_at = PoolSuspensions::<T>::get(asset_pair);
+			match resumes_at {
+				Some(resumes_at) if resumes_at > current_block => continue,
+				Some(resumes_at) if resumes_at == current_block =>
+					Self::deposit_event(Event::<T>::PoolResumed { asset_pair }),
+				_ => (),
+			}
+
+			let observations = PriceObservations::<T>::get(asset_pair);
+			let Some(current) = observations.last() else { continue };
+			let window_start = core::cmp::max(
+				current_block
+					.saturated_into::<u32>()
+					.saturating_sub(circuit_breaker.window_blocks),
+				resumes_at.map_or(0, |resumes_at| resumes_at.saturated_into()),
+			);
+			// The latest observation at or before the start of the window is the price at the
+			// start of the window:
+			let first_in_window = observations
+				.iter()
+				.rposition(|observation| observation.block <= window_start)
+				.unwrap_or_default();
+			let price_movement = observations[first_in_window..]
+				.iter()
+				.map(|observation| observation.tick.abs_diff(current.tick))
+				.max()
+				.unwrap_or_default();
+
+			if price_movement > circuit_breaker.max_movement_ticks {
+				let resumes_at =
+					current_block.saturating_add(circuit_breaker.cool_down_blocks.max(1).into());
+				PoolSuspensions::<T>::insert(asset_pair, resumes_at);
+				Self::deposit_event(Event::<T>::PoolSuspended { asset_pair, resumes_at });
+				weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+			}
+		}
+		weight_used
+	}
+
+	/// Shares each incentive programme's rewards for this block between the range orders in its
+	/// pool whose range contains the current price, in proportion to their liquidity. At most
+	/// [MAX_INCENTIVISED_RANGE_ORDERS_PER_BLOCK] range orders are considered in total.
+	fn distribute_incentive_rewards() -> Weight {
+		let mut weight_used = Weight::zero();
+		let mut order_budget = MAX_INCENTIVISED_RANGE_ORDERS_PER_BLOCK as usize;
+		for (asset_pair, mut programme) in IncentiveProgrammes::<T>::iter().collect::<Vec<_>>() {
+			let Some(mut pool) = Pools::<T>::get(asset_pair) else {
+				weight_used.saturating_accrue(T::WeightInfo::distribute_incentive_rewards(0));
+				continue
+			};
+
+			let order_count =
+				pool.range_orders_cache.values().map(|range_orders| range_orders.len()).sum();
+			if order_count > order_budget {
+				weight_used.saturating_accrue(T::WeightInfo::distribute_incentive_rewards(0));
+				continue
+			}
+			order_budget.saturating_reduce(order_count);
+			weight_used
+				.saturating_accrue(T::WeightInfo::distribute_incentive_rewards(order_count as u32));
+
+			let current_tick = tick_at_sqrt_price(pool.pool_state.current_range_order_pool_price());
+			let in_range_orders = pool
+				.range_orders_cache
+				.iter()
+				.flat_map(|(lp, range_orders)| {
+					range_orders.iter().map(move |(id, tick_range)| (lp, *id, tick_range))
+				})
+				.filter(|(_, _, tick_range)| tick_range.contains(&current_tick))
+				.filter_map(|(lp, id, tick_range)| {
+					pool.pool_state
+						.range_order(&(lp.clone(), id), tick_range.clone())
+						.ok()
+						.map(|(_, position_info)| (lp, position_info.liquidity))
+				})
+				.collect::<Vec<_>>();
+			let total_liquidity = in_range_orders
+				.iter()
+				.fold(0u128, |total, (_, liquidity)| total.saturating_add(*liquidity));
+			if total_liquidity == 0 {
+				continue
+			}
+
+			let block_reward = core::cmp::min(programme.reward_per_block, programme.remaining);
+			let mut distributed: AssetAmount = 0;
+			for (lp, liquidity) in in_range_orders {
+				let reward = cf_amm::math::mul_div_floor(
+					block_reward.into(),
+					liquidity.into(),
+					total_liquidity,
+				)
+				.saturated_into::<AssetAmount>();
+				if reward > 0 {
+					IncentiveRewards::<T>::mutate(lp, programme.reward_asset, |rewards| {
+						rewards.saturating_accrue(reward)
+					});
+					distributed.saturating_accrue(reward);
+				}
+			}
+			programme.remaining.saturating_reduce(distributed);
+
+			// Once the last block's rewards are paid out, anything left over from rounding is
+			// returned to the funder:
+			if block_reward < programme.reward_per_block || programme.remaining == 0 {
+				match Self::end_incentive_programme(asset_pair, programme.clone()) {
+					Ok(()) => IncentiveProgrammes::<T>::remove(asset_pair),
+					Err(error) => {
+						Self::deposit_event(Event::<T>::IncentiveProgrammeRefundFailed {
+							asset_pair,
+							error,
+						});
+						IncentiveProgrammes::<T>::insert(asset_pair, programme);
+					},
+				}
+			} else {
+				IncentiveProgrammes::<T>::insert(asset_pair, programme);
+			}
+		}
+		weight_used
+	}
+
+	fn end_incentive_programme(
+		asset_pair: AssetPair,
+		programme: IncentiveProgramme<T::AccountId>,
+	) -> DispatchResult {
+		if programme.remaining > 0 {
+			T::LpBalance::try_credit_account(
+				&programme.funder,
+				programme.reward_asset,
+				programme.remaining,
+			)?;
+		}
+		Self::deposit_event(Event::<T>::IncentiveProgrammeEnded {
+			asset_pair,
+			refunded: programme.remaining,
+		});
+		Ok(())
+	}
+
+	/// Whether swaps in the pool are suspended by its circuit breaker.
+	pub fn pool_suspended(asset_pair: AssetPair) -> bool {
+		PoolSuspensions::<T>::get(asset_pair)
+			.is_some_and(|resumes_at| frame_system::Pallet::<T>::block_number() < resumes_at)
+	}
+
+	/// The share of LP fees in the pool that is paid to the network fee.
+	pub fn protocol_fee(base_asset: Asset, quote_asset: Asset) -> Result<Permill, DispatchError> {
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+		Ok(ProtocolFees::<T>::get(asset_pair))
+	}
+
+	/// Swap volume and fees of the pool for each epoch in which it was used.
+	pub fn pool_statistics(
+		base_asset: Asset,
+		quote_asset: Asset,
+	) -> Result<Vec<(EpochIndex, PoolStatistics)>, DispatchError> {
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+		let mut statistics = EpochPoolStatistics::<T>::iter_prefix(asset_pair).collect::<Vec<_>>();
+		statistics.sort_by_key(|(epoch, _)| *epoch);
+		Ok(statistics)
+	}
+
+	/// Fees paid to the LP from each pool, per epoch, ordered by pool and then by epoch.
+	pub fn lp_earned_fees_history(
+		lp: &T::AccountId,
+	) -> Vec<(PoolPairsMap<Asset>, EpochIndex, PoolPairsMap<AssetAmount>)> {
+		let mut history = EpochLpEarnedFees::<T>::iter_prefix((lp,))
+			.map(|((asset_pair, epoch), fees)| (asset_pair.assets(), epoch, fees))
+			.collect::<Vec<_>>();
+		history.sort_by_key(|(assets, epoch, _)| (*assets, *epoch));
+		history
+	}
+
+	/// The time-weighted average tick of the pool over the last `window_blocks` blocks. Returns
+	/// `None` if the pool's price history does not cover the whole window.
+	pub fn twap_tick(base_asset: Asset, quote_asset: Asset, window_blocks: u32) -> Option<Tick> {
+		if window_blocks == 0 || window_blocks > MAX_TWAP_WINDOW_BLOCKS {
+			return None
+		}
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset).ok()?;
+		let observations = PriceObservations::<T>::get(asset_pair);
+
+		let current_block: u32 = frame_system::Pallet::<T>::block_number().saturated_into();
+		let window_start = current_block.checked_sub(window_blocks)?;
+
+		let start = observations
+			.iter()
+			.rev()
+			.find(|observation| observation.block <= window_start)?;
+		let end = observations.last()?;
+
+		let average_tick = end
+			.tick_cumulative_at(current_block)
+			.saturating_sub(start.tick_cumulative_at(window_start)) /
+			window_blocks as i64;
+
+		Tick::try_from(average_tick).ok()
+	}
+
+	/// The time-weighted average price of the pool over the last `window_blocks` blocks. See
+	/// [Self::twap_tick].
+	pub fn twap_price(
+		base_asset: Asset,
+		quote_asset: Asset,
+		window_blocks: u32,
+	) -> Option<PoolPriceV1> {
+		let tick = Self::twap_tick(base_asset, quote_asset, window_blocks)?;
+		Some(PoolPriceV1 {
+			price: cf_amm::math::price_at_tick(tick)?,
+			sqrt_price: cf_amm::math::sqrt_price_at_tick(tick),
+			tick,
 		})
 	}
 
@@ -2008,11 +4445,28 @@ impl<T: Config> Pallet<T> {
 					limit_orders::DepthError::InvalidTick => Error::<T>::InvalidTick,
 				})?;
 
-		let range_orders =
-			pool.pool_state.range_order_depth(tick_range).map_err(|error| match error {
-				range_orders::DepthError::InvalidTickRange => Error::<T>::InvalidTickRange,
-				range_orders::DepthError::InvalidTick => Error::<T>::InvalidTick,
-			})?;
+		let range_order_depth_error = |error| match error {
+			range_orders::DepthError::InvalidTickRange => Error::<T>::InvalidTickRange,
+			range_orders::DepthError::InvalidTick => Error::<T>::InvalidTick,
+		};
+		let range_orders = pool
+			.pool_state
+			.range_order_depth(tick_range.clone())
+			.map_err(range_order_depth_error)?;
+		// Range orders in the pair's additional fee tier pools are also available to swaps:
+		let range_orders = FeeTierPools::<T>::iter_prefix_values(asset_pair)
+			.try_fold(range_orders, |range_orders, fee_tier_pool| {
+				fee_tier_pool.pool_state.range_order_depth(tick_range.clone()).map(
+					|fee_tier_range_orders| {
+						range_orders.zip(fee_tier_range_orders).map(
+							|((price, depth), (_, fee_tier_depth))| {
+								(price, depth.saturating_add(fee_tier_depth))
+							},
+						)
+					},
+				)
+			})
+			.map_err(range_order_depth_error)?;
 
 		Ok(AskBidMap::from_sell_map(limit_orders.zip(range_orders).map(
 			|(limit_orders, range_orders)| {
@@ -2041,6 +4495,26 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// The info of each additional fee tier pool of the asset pair, see
+	/// [new_fee_tier_pool](Pallet::new_fee_tier_pool).
+	pub fn fee_tier_pools(
+		base_asset: any::Asset,
+		quote_asset: any::Asset,
+	) -> Result<Vec<PoolInfo>, DispatchError> {
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+		Ok(FeeTierPools::<T>::iter_prefix_values(asset_pair)
+			.map(|pool| PoolInfo {
+				limit_order_fee_hundredth_pips: pool.pool_state.limit_order_fee(),
+				range_order_fee_hundredth_pips: pool.pool_state.range_order_fee(),
+				range_order_total_fees_earned: pool.pool_state.range_order_total_fees_earned(),
+				limit_order_total_fees_earned: pool.pool_state.limit_order_total_fees_earned(),
+				range_total_swap_inputs: pool.pool_state.range_order_swap_inputs(),
+				limit_total_swap_inputs: pool.pool_state.limit_order_swap_inputs(),
+			})
+			.collect())
+	}
+
 	pub fn pool_liquidity(
 		base_asset: any::Asset,
 		quote_asset: any::Asset,
@@ -2071,8 +4545,15 @@ impl<T: Config> Pallet<T> {
 		option_lp: Option<T::AccountId>,
 		filled_orders: bool,
 	) -> Result<PoolOrders<T>, DispatchError> {
-		let pool = Pools::<T>::get(AssetPair::try_new::<T>(base_asset, quote_asset)?)
-			.ok_or(Error::<T>::PoolDoesNotExist)?;
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		if option_lp
+			.as_ref()
+			.is_some_and(|lp| !LpOrderCounts::<T>::contains_key(lp, asset_pair))
+		{
+			ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+			return Ok(Default::default())
+		}
+		let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
 		let option_lp = option_lp.as_ref();
 		Ok(PoolOrders {
 			limit_orders: AskBidMap::from_sell_map(pool.limit_orders_cache.as_ref().map_with_pair(
@@ -2149,6 +4630,202 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Returns a page of at most `limit` orders in the pool, optionally only those of the given LP,
+	/// starting after the order identified by `after`.
+	pub fn pool_orders_page(
+		base_asset: any::Asset,
+		quote_asset: any::Asset,
+		option_lp: Option<T::AccountId>,
+		filled_orders: bool,
+		after: Option<PoolOrdersCursor<T::AccountId>>,
+		limit: u32,
+	) -> Result<PoolOrdersPage<T>, DispatchError> {
+		let asset_pair = AssetPair::try_new::<T>(base_asset, quote_asset)?;
+		let mut page = PoolOrdersPage { orders: Default::default(), next: None };
+		if option_lp
+			.as_ref()
+			.is_some_and(|lp| !LpOrderCounts::<T>::contains_key(lp, asset_pair))
+		{
+			ensure!(Pools::<T>::contains_key(asset_pair), Error::<T>::PoolDoesNotExist);
+			return Ok(page)
+		}
+		let pool = Pools::<T>::get(asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+		let limit = limit.clamp(1, MAX_POOL_ORDERS_PAGE_SIZE) as usize;
+		let mut count = 0;
+		let mut last = None;
+
+		for side in [Side::Sell, Side::Buy] {
+			let side_after = match (&after, side) {
+				(None, _) | (Some(PoolOrdersCursor::Ask { .. }), Side::Buy) => None,
+				(Some(PoolOrdersCursor::Ask { lp, id }), Side::Sell) |
+				(Some(PoolOrdersCursor::Bid { lp, id }), Side::Buy) => Some((lp, *id)),
+				(Some(PoolOrdersCursor::Bid { .. }), Side::Sell) |
+				(Some(PoolOrdersCursor::Range { .. }), _) => continue,
+			};
+			for (lp, id, tick) in Self::cached_orders_after(
+				&pool.limit_orders_cache[side.to_sold_pair()],
+				option_lp.as_ref(),
+				side_after,
+			) {
+				if count == limit {
+					page.next = last;
+					return Ok(page)
+				}
+				let (collected, position_info) =
+					pool.pool_state.limit_order(&(lp.clone(), id), side, *tick).unwrap();
+				if filled_orders || !position_info.amount.is_zero() {
+					let limit_order = LimitOrder {
+						lp: lp.clone(),
+						id: id.into(),
+						tick: *tick,
+						sell_amount: position_info.amount,
+						fees_earned: collected.accumulative_fees,
+						original_sell_amount: collected.original_amount,
+					};
+					last = Some(match side {
+						Side::Sell => {
+							page.orders.limit_orders.asks.push(limit_order);
+							PoolOrdersCursor::Ask { lp: lp.clone(), id }
+						},
+						Side::Buy => {
+							page.orders.limit_orders.bids.push(limit_order);
+							PoolOrdersCursor::Bid { lp: lp.clone(), id }
+						},
+					});
+					count += 1;
+				}
+			}
+		}
+
+		let range_after = match &after {
+			Some(PoolOrdersCursor::Range { lp, id }) => Some((lp, *id)),
+			_ => None,
+		};
+		for (lp, id, tick_range) in
+			Self::cached_orders_after(&pool.range_orders_cache, option_lp.as_ref(), range_after)
+		{
+			if count == limit {
+				page.next = last;
+				return Ok(page)
+			}
+			let (collected, position_info) =
+				pool.pool_state.range_order(&(lp.clone(), id), tick_range.clone()).unwrap();
+			page.orders.range_orders.push(RangeOrder {
+				lp: lp.clone(),
+				id: id.into(),
+				range: tick_range.clone(),
+				liquidity: position_info.liquidity,
+				fees_earned: collected.accumulative_fees,
+			});
+			last = Some(PoolOrdersCursor::Range { lp: lp.clone(), id });
+			count += 1;
+		}
+
+		Ok(page)
+	}
+
+	/// The orders in one of a pool's order caches, ordered by LP and then by id, optionally only
+	/// those of a single LP, and starting after the given order.
+	fn cached_orders_after<'a, V>(
+		cache: &'a BTreeMap<T::AccountId, BTreeMap<OrderId, V>>,
+		option_lp: Option<&'a T::AccountId>,
+		after: Option<(&'a T::AccountId, OrderId)>,
+	) -> impl Iterator<Item = (&'a T::AccountId, OrderId, &'a V)> + 'a {
+		use core::ops::Bound;
+
+		let lp_range = match (option_lp, after) {
+			(Some(lp), _) => (Bound::Included(lp), Bound::Included(lp)),
+			(None, Some((after_lp, _))) => (Bound::Included(after_lp), Bound::Unbounded),
+			(None, None) => (Bound::Unbounded, Bound::Unbounded),
+		};
+		cache
+			.range::<T::AccountId, _>(lp_range)
+			.filter(move |(lp, _)| after.is_none_or(|(after_lp, _)| *lp >= after_lp))
+			.flat_map(move |(lp, orders)| {
+				let id_range = match after {
+					Some((after_lp, after_id)) if after_lp == lp =>
+						(Bound::Excluded(after_id), Bound::Unbounded),
+					_ => (Bound::Unbounded, Bound::Unbounded),
+				};
+				orders.range(id_range).map(move |(id, value)| (lp, *id, value))
+			})
+	}
+
+	/// Returns the orders of the given LP in every pool in which it has orders.
+	pub fn lp_orders(
+		lp: T::AccountId,
+		filled_orders: bool,
+	) -> Result<Vec<(Asset, Asset, PoolOrders<T>)>, DispatchError> {
+		LpOrderCounts::<T>::iter_key_prefix(&lp)
+			.filter(|asset_pair| Pools::<T>::contains_key(asset_pair))
+			.map(|asset_pair| {
+				let PoolPairsMap { base, quote } = asset_pair.assets();
+				Self::pool_orders(base, quote, Some(lp.clone()), filled_orders)
+					.map(|orders| (base, quote, orders))
+			})
+			.collect()
+	}
+
+	/// Returns the LP's open orders in every pool in which it has orders, along with the value of
+	/// the assets each order currently holds at the pool's range order price. Since the stable
+	/// asset is the quote asset of every pool, all values are measured in the stable asset. Fees
+	/// earned by the orders are not included in their value.
+	pub fn lp_orders_valuation(
+		lp: T::AccountId,
+	) -> Result<Vec<PoolOrdersValuation<T>>, DispatchError> {
+		Self::lp_orders(lp, false)?
+			.into_iter()
+			.map(|(base_asset, quote_asset, PoolOrders { limit_orders, range_orders })| {
+				let mut pool = Pools::<T>::get(AssetPair::try_new::<T>(base_asset, quote_asset)?)
+					.ok_or(Error::<T>::PoolDoesNotExist)?;
+				let price = sqrt_price_to_price(pool.pool_state.current_range_order_pool_price());
+				let base_value = |amount: Amount| output_amount_floor(amount, price);
+
+				let limit_orders = AskBidMap {
+					asks: limit_orders
+						.asks
+						.into_iter()
+						.map(|order| ValuedOrder { value: base_value(order.sell_amount), order })
+						.collect::<Vec<_>>(),
+					bids: limit_orders
+						.bids
+						.into_iter()
+						.map(|order| ValuedOrder { value: order.sell_amount, order })
+						.collect::<Vec<_>>(),
+				};
+				let range_orders = range_orders
+					.into_iter()
+					.map(|order| {
+						let PoolPairsMap { base, quote } = Self::pool_range_order_liquidity_value(
+							base_asset,
+							quote_asset,
+							order.range.clone(),
+							order.liquidity,
+						)?;
+						Ok(ValuedOrder { value: base_value(base).saturating_add(quote), order })
+					})
+					.collect::<Result<Vec<_>, DispatchError>>()?;
+
+				let total_value = limit_orders
+					.asks
+					.iter()
+					.chain(&limit_orders.bids)
+					.map(|valued_order| valued_order.value)
+					.chain(range_orders.iter().map(|valued_order| valued_order.value))
+					.fold(Amount::zero(), |total, value| total.saturating_add(value));
+
+				Ok(PoolOrdersValuation {
+					base_asset,
+					quote_asset,
+					price,
+					limit_orders,
+					range_orders,
+					total_value,
+				})
+			})
+			.collect()
+	}
+
 	pub fn pool_range_order_liquidity_value(
 		base_asset: any::Asset,
 		quote_asset: any::Asset,
@@ -2170,6 +4847,53 @@ impl<T: Config> Pallet<T> {
 			})
 	}
 
+	/// Returns the assets that [set_range_order](Pallet::set_range_order) would debit for a new
+	/// range order of the given size at the pool's current price, and the order's liquidity.
+	pub fn pool_range_order_mint_quote(
+		base_asset: any::Asset,
+		quote_asset: any::Asset,
+		tick_range: Range<Tick>,
+		size: RangeOrderSize,
+	) -> Result<RangeOrderMintQuote, DispatchError> {
+		let pool = Pools::<T>::get(AssetPair::try_new::<T>(base_asset, quote_asset)?)
+			.ok_or(Error::<T>::PoolDoesNotExist)?;
+		let (amounts, liquidity) = pool
+			.pool_state
+			.range_order_mint_quote(tick_range, size.into())
+			.map_err(|error| match error {
+				range_orders::PositionError::InvalidTickRange => Error::<T>::InvalidTickRange,
+				range_orders::PositionError::NonExistent => Error::<T>::OrderDoesNotExist,
+				range_orders::PositionError::Other(range_orders::MintError::CallbackFailed(
+					never,
+				)) => match never {},
+				range_orders::PositionError::Other(
+					range_orders::MintError::MaximumGrossLiquidity,
+				) => Error::<T>::MaximumGrossLiquidity,
+				range_orders::PositionError::Other(
+					range_orders::MintError::AssetRatioUnachieveable,
+				) => Error::<T>::AssetRatioUnachieveable,
+			})?;
+		// As for set_range_order, the order must have some liquidity:
+		ensure!(!liquidity.is_zero(), Error::<T>::InvalidSize);
+
+		Ok(RangeOrderMintQuote {
+			amounts: amounts.try_map(|amount| {
+				AssetAmount::try_from(amount).map_err(Into::<DispatchError>::into)
+			})?,
+			liquidity,
+		})
+	}
+
+	fn note_order_added(lp: &T::AccountId, asset_pair: &AssetPair) {
+		LpOrderCounts::<T>::mutate(lp, asset_pair, |count| count.saturating_inc());
+	}
+
+	fn note_order_removed(lp: &T::AccountId, asset_pair: &AssetPair) {
+		LpOrderCounts::<T>::mutate_exists(lp, asset_pair, |count| {
+			*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+		});
+	}
+
 	/// Process changes to limit order:
 	/// - Payout collected `fee` and `bought_amount`
 	/// - Update cache storage for Pool
@@ -2185,12 +4909,18 @@ impl<T: Config> Pallet<T> {
 		position_info: PositionInfo,
 		amount_change: IncreaseOrDecrease<AssetAmount>,
 	) -> DispatchResult {
-		let collected_fees: AssetAmount = collected.fees.try_into()?;
-		let asset = asset_pair.assets()[!order.to_sold_pair()];
-		HistoricalEarnedFees::<T>::mutate(lp, asset, |balance| {
-			*balance = balance.saturating_add(collected_fees)
-		});
-		T::LpBalance::try_credit_account(lp, asset, collected_fees)?;
+		let kind = OrderKind::Limit {
+			side: order,
+			pool_fee_hundredth_pips: Self::pool_fee_tier(asset_pair, pool),
+		};
+		let collected_fees = Self::credit_collected_fees(
+			lp,
+			asset_pair,
+			kind,
+			id,
+			asset_pair.assets()[!order.to_sold_pair()],
+			collected.fees.try_into()?,
+		)?;
 
 		let bought_amount: AssetAmount = collected.bought_amount.try_into()?;
 		T::LpBalance::try_credit_account(
@@ -2201,14 +4931,17 @@ impl<T: Config> Pallet<T> {
 
 		let limit_orders = &mut pool.limit_orders_cache[order.to_sold_pair()];
 		if position_info.amount.is_zero() {
+			OrderTransferOffers::<T>::remove((lp, asset_pair, kind, id));
 			if let Some(lp_limit_orders) = limit_orders.get_mut(lp) {
-				lp_limit_orders.remove(&id);
+				if lp_limit_orders.remove(&id).is_some() {
+					Self::note_order_removed(lp, asset_pair);
+				}
 				if lp_limit_orders.is_empty() {
 					limit_orders.remove(lp);
 				}
 			}
-		} else {
-			limit_orders.entry(lp.clone()).or_default().insert(id, tick);
+		} else if limit_orders.entry(lp.clone()).or_default().insert(id, tick).is_none() {
+			Self::note_order_added(lp, asset_pair);
 		}
 
 		let zero_change = *amount_change.abs() == 0;
@@ -2285,6 +5018,7 @@ pub struct DeleteHistoricalEarnedFees<T: Config>(sp_std::marker::PhantomData<T>)
 impl<T: Config> OnKilledAccount<T::AccountId> for DeleteHistoricalEarnedFees<T> {
 	fn on_killed_account(who: &T::AccountId) {
 		let _ = HistoricalEarnedFees::<T>::clear_prefix(who, u32::MAX, None);
+		let _ = EpochLpEarnedFees::<T>::clear_prefix((who,), u32::MAX, None);
 	}
 }
 
@@ -2315,4 +5049,8 @@ impl<T: Config> cf_traits::PoolPriceProvider for Pallet<T> {
 				.unwrap_or_else(|| sqrt_price_to_price(MAX_SQRT_PRICE)),
 		})
 	}
+
+	fn twap_tick(base_asset: Asset, quote_asset: Asset, window_blocks: u32) -> Option<Tick> {
+		Self::twap_tick(base_asset, quote_asset, window_blocks)
+	}
 }
@@ -16,5 +16,17 @@
 
 use crate::Pallet;
 use cf_runtime_utilities::PlaceholderMigration;
+use frame_support::migrations::VersionedMigration;
 
-pub type PalletMigration<T> = (PlaceholderMigration<6, Pallet<T>>,);
+mod lp_order_counts;
+
+pub type PalletMigration<T> = (
+	VersionedMigration<
+		6,
+		7,
+		lp_order_counts::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	PlaceholderMigration<7, Pallet<T>>,
+);
@@ -0,0 +1,130 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Config, FeeTierPools, LpOrderCounts, Pool, Pools};
+use frame_support::{
+	traits::{Get, UncheckedOnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::pallet_prelude::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Populates [LpOrderCounts] from the order caches of every pool.
+pub struct Migration<T>(PhantomData<T>);
+
+fn count_orders<T: Config>(asset_pair: &crate::AssetPair, pool: &Pool<T>) {
+	for (lp, orders) in pool
+		.limit_orders_cache
+		.as_ref()
+		.into_iter()
+		.flat_map(|(_, cache)| cache.iter())
+		.map(|(lp, orders)| (lp, orders.len()))
+		.chain(pool.range_orders_cache.iter().map(|(lp, orders)| (lp, orders.len())))
+	{
+		LpOrderCounts::<T>::mutate(lp, asset_pair, |count| {
+			*count = count.saturating_add(orders as u32)
+		});
+	}
+}
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut pools = 0u64;
+		for (asset_pair, pool) in Pools::<T>::iter() {
+			count_orders(&asset_pair, &pool);
+			pools += 1;
+		}
+		for (asset_pair, _fee, pool) in FeeTierPools::<T>::iter() {
+			count_orders(&asset_pair, &pool);
+			pools += 1;
+		}
+		T::DbWeight::get().reads_writes(pools, pools)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		for (asset_pair, pool) in Pools::<T>::iter() {
+			for lp in pool.range_orders_cache.keys() {
+				frame_support::ensure!(
+					LpOrderCounts::<T>::contains_key(lp, asset_pair),
+					"Missing order count for LP with range orders"
+				);
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, AssetPair, RangeOrderSize};
+	use cf_amm::math::price_at_tick;
+	use cf_primitives::{Asset, STABLE_ASSET};
+	use cf_traits::mocks::balance_api::MockBalance;
+	use frame_support::assert_ok;
+
+	#[test]
+	fn populates_lp_order_counts() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				Asset::Eth,
+				STABLE_ASSET,
+				0,
+				price_at_tick(0).unwrap(),
+			));
+			MockBalance::credit_account(&ALICE, Asset::Eth, 1_000_000);
+			MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000);
+			for id in 0..2 {
+				assert_ok!(LiquidityPools::set_range_order(
+					RuntimeOrigin::signed(ALICE),
+					Asset::Eth,
+					STABLE_ASSET,
+					id,
+					Some(-100..100),
+					RangeOrderSize::Liquidity { liquidity: 1_000 },
+				));
+			}
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				cf_amm::common::Side::Sell,
+				0,
+				Some(0),
+				1_000,
+				None,
+			));
+			let asset_pair = AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap();
+			assert_eq!(LpOrderCounts::<Test>::get(ALICE, asset_pair), 3);
+
+			let _ = LpOrderCounts::<Test>::clear(u32::MAX, None);
+			Migration::<Test>::on_runtime_upgrade();
+
+			assert_eq!(LpOrderCounts::<Test>::get(ALICE, asset_pair), 3);
+		});
+	}
+}
@@ -17,13 +17,15 @@
 use crate::{self as pallet_cf_pools, mock::*, *};
 use cf_amm::{
 	common::Side,
-	math::{price_at_tick, Tick},
+	math::{price_at_tick, tick_at_sqrt_price, Tick},
 };
 use cf_primitives::{chains::assets::any::Asset, AssetAmount};
 use cf_test_utilities::{
 	assert_events_eq, assert_events_match, assert_matching_event_count, last_event,
 };
-use cf_traits::{mocks::balance_api::MockBalance, BalanceApi, PoolApi, SwappingApi};
+use cf_traits::{
+	mocks::balance_api::MockBalance, AccountRoleRegistry, BalanceApi, PoolApi, SwappingApi,
+};
 use frame_support::{assert_noop, assert_ok, traits::Hooks};
 use sp_core::bounded_vec;
 use sp_runtime::BoundedVec;
@@ -166,6 +168,7 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 			0,
 			Some(0),
 			100,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(BOB),
@@ -175,6 +178,7 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 			0,
 			Some(tick),
 			100_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(BOB),
@@ -184,6 +188,7 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 			1,
 			Some(tick),
 			10_000,
+			None,
 		));
 		assert_eq!(
 			LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(ALICE), false),
@@ -394,6 +399,7 @@ fn can_execute_scheduled_limit_order() {
 				id: order_id,
 				option_tick: Some(100),
 				sell_amount: AMOUNT,
+				expiry_block: None,
 			}),
 			6
 		));
@@ -422,6 +428,86 @@ fn can_execute_scheduled_limit_order() {
 	});
 }
 
+#[test]
+fn limit_orders_are_cancelled_at_expiry() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		let asset_pair = AssetPair::try_new::<Test>(Asset::Flip, STABLE_ASSET).unwrap();
+		let open_order_ids = || {
+			Pools::<Test>::get(asset_pair).unwrap().limit_orders_cache[Side::Buy.to_sold_pair()]
+				.get(&ALICE)
+				.map(|orders| orders.keys().copied().collect::<Vec<_>>())
+				.unwrap_or_default()
+		};
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 2 * AMOUNT);
+
+		assert_noop!(
+			LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Flip,
+				STABLE_ASSET,
+				Side::Buy,
+				0,
+				Some(0),
+				AMOUNT,
+				Some(1),
+			),
+			Error::<Test>::InvalidExpiryBlock
+		);
+		for id in [0, 1] {
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Flip,
+				STABLE_ASSET,
+				Side::Buy,
+				id,
+				Some(0),
+				AMOUNT,
+				Some(5),
+			));
+		}
+		// Setting an order again replaces its expiry:
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Buy,
+			1,
+			Some(0),
+			AMOUNT,
+			None,
+		));
+		assert_eq!(MockBalance::get_balance(&ALICE, STABLE_ASSET), 0);
+
+		System::set_block_number(5);
+		LiquidityPools::on_initialize(5);
+
+		assert_eq!(open_order_ids(), vec![1]);
+		assert_eq!(MockBalance::get_balance(&ALICE, STABLE_ASSET), AMOUNT);
+		assert_eq!(
+			last_event::<Test>(),
+			RuntimeEvent::LiquidityPools(Event::LimitOrderExpired {
+				lp: ALICE,
+				base_asset: Asset::Flip,
+				quote_asset: STABLE_ASSET,
+				side: Side::Buy,
+				id: 0,
+			})
+		);
+		assert!(ExpiringLimitOrders::<Test>::get(5).is_empty());
+		assert!(LimitOrderExpiries::<Test>::iter().next().is_none());
+	});
+}
+
 #[test]
 fn schedule_rejects_unsupported_calls() {
 	new_test_ext().execute_with(|| {
@@ -453,6 +539,7 @@ fn cant_schedule_in_the_past() {
 					id: 0,
 					option_tick: Some(0),
 					sell_amount: 55,
+					expiry_block: None,
 				}),
 				9
 			),
@@ -523,6 +610,7 @@ fn can_get_all_pool_orders() {
 			4,
 			Some(100),
 			500_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(ALICE),
@@ -532,6 +620,7 @@ fn can_get_all_pool_orders() {
 			5,
 			Some(1000),
 			600_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(ALICE),
@@ -541,6 +630,7 @@ fn can_get_all_pool_orders() {
 			6,
 			Some(100),
 			700_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(ALICE),
@@ -550,6 +640,7 @@ fn can_get_all_pool_orders() {
 			7,
 			Some(1000),
 			800_000,
+			None,
 		));
 
 		assert_eq!(
@@ -662,6 +753,111 @@ fn range_order_fees_are_recorded() {
 	});
 }
 
+#[test]
+fn twap_tick_requires_history_covering_the_window() {
+	const WINDOW: u32 = 10;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			100,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000_000_000_000);
+		MockBalance::credit_account(&ALICE, Asset::Eth, 1_000_000_000_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000_000_000_000_000 },
+		));
+		assert_eq!(
+			PriceObservations::<Test>::get(
+				AssetPair::try_new::<Test>(Asset::Eth, STABLE_ASSET).unwrap()
+			)
+			.len(),
+			1
+		);
+
+		// Not enough history to cover the window yet.
+		System::set_block_number(1 + WINDOW / 2);
+		assert_eq!(LiquidityPools::twap_tick(Asset::Eth, STABLE_ASSET, WINDOW), None);
+
+		// Windows outside the supported range are rejected.
+		System::set_block_number(1 + MAX_TWAP_WINDOW_BLOCKS * 2);
+		assert_eq!(LiquidityPools::twap_tick(Asset::Eth, STABLE_ASSET, 0), None);
+		assert_eq!(
+			LiquidityPools::twap_tick(Asset::Eth, STABLE_ASSET, MAX_TWAP_WINDOW_BLOCKS + 1),
+			None
+		);
+
+		let twap = LiquidityPools::twap_tick(Asset::Eth, STABLE_ASSET, WINDOW)
+			.expect("History covers the window");
+		assert!((-100..100).contains(&twap), "Unexpected TWAP tick: {twap}");
+
+		// Swapping moves the price, which is reflected in the TWAP once time passes.
+		assert!(
+			LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Eth, 1_000_000_000_000_000)
+				.unwrap() > 0
+		);
+		System::set_block_number(1 + MAX_TWAP_WINDOW_BLOCKS * 2 + WINDOW);
+		assert_ne!(LiquidityPools::twap_tick(Asset::Eth, STABLE_ASSET, WINDOW), Some(twap));
+	});
+}
+
+#[test]
+fn observed_tick_change_is_bounded_per_block() {
+	new_test_ext().execute_with(|| {
+		let asset_pair = AssetPair::try_new::<Test>(Asset::Eth, STABLE_ASSET).unwrap();
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000);
+		MockBalance::credit_account(&ALICE, Asset::Eth, 1_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(-10_000..10_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		let initial_tick = PriceObservations::<Test>::get(asset_pair).last().unwrap().tick;
+
+		// A large swap moves the pool's price far more than the observed tick is allowed to move:
+		System::set_block_number(2);
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Eth, 100_000));
+		let pool_tick = Pools::<Test>::get(asset_pair)
+			.unwrap()
+			.pool_state
+			.current_price(Side::Buy)
+			.unwrap()
+			.2;
+		assert!(pool_tick - initial_tick > MAX_OBSERVED_TICK_CHANGE_PER_BLOCK);
+		assert_eq!(
+			PriceObservations::<Test>::get(asset_pair).last().unwrap().tick,
+			initial_tick + MAX_OBSERVED_TICK_CHANGE_PER_BLOCK
+		);
+
+		// The TWAP only reflects the bounded tick:
+		System::set_block_number(3);
+		let twap = LiquidityPools::twap_price(Asset::Eth, STABLE_ASSET, 2).unwrap();
+		assert_eq!(twap.tick, initial_tick + MAX_OBSERVED_TICK_CHANGE_PER_BLOCK / 2);
+		assert_eq!(Some(twap.price), price_at_tick(twap.tick));
+	});
+}
+
 #[test]
 fn test_maximum_slippage_limits() {
 	use cf_utilities::{assert_err, assert_ok};
@@ -887,7 +1083,8 @@ fn test_cancel_orders_batch() {
 			Side::Sell,
 			0,
 			Some(TICK),
-			5_000
+			5_000,
+			None
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(ALICE),
@@ -896,7 +1093,8 @@ fn test_cancel_orders_batch() {
 			Side::Sell,
 			1,
 			Some(TICK + POOL_FEE_BPS as Tick),
-			15_000
+			15_000,
+			None
 		));
 
 		assert_eq!(
@@ -946,6 +1144,96 @@ fn test_cancel_orders_batch() {
 	});
 }
 
+#[test]
+fn can_replace_orders_atomically() {
+	new_test_ext().execute_with(|| {
+		let asset_pair = AssetPair::try_new::<Test>(Asset::Flip, STABLE_ASSET).unwrap();
+		let open_orders = || {
+			let pool = Pools::<Test>::get(asset_pair).unwrap();
+			(
+				pool.limit_orders_cache[Side::Sell.to_sold_pair()].get(&ALICE).cloned(),
+				pool.range_orders_cache.get(&ALICE).cloned(),
+			)
+		};
+		let set_orders_batch = |cancel: Vec<CloseOrder>, place: Vec<NewOrder>| {
+			pallet_cf_pools::Call::<Test>::set_orders_batch {
+				cancel: cancel.try_into().unwrap(),
+				place: place.try_into().unwrap(),
+			}
+			.dispatch_bypass_filter(RuntimeOrigin::signed(ALICE))
+			.map(|_| ())
+			.map_err(|e| e.error)
+		};
+		let limit_order = |id, tick| NewOrder::Limit {
+			base_asset: Asset::Flip,
+			quote_asset: STABLE_ASSET,
+			side: Side::Sell,
+			id,
+			option_tick: Some(tick),
+			sell_amount: 1_000,
+		};
+		let range_order = |id, tick_range| NewOrder::Range {
+			base_asset: Asset::Flip,
+			quote_asset: STABLE_ASSET,
+			id,
+			option_tick_range: Some(tick_range),
+			size: RangeOrderSize::Liquidity { liquidity: 1_000 },
+		};
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000);
+
+		assert_ok!(set_orders_batch(vec![], vec![limit_order(0, 10), range_order(0, -10..10)]));
+		assert_eq!(
+			open_orders(),
+			(Some([(0, 10)].into_iter().collect()), Some([(0, -10..10)].into_iter().collect()))
+		);
+
+		assert_ok!(set_orders_batch(
+			vec![
+				CloseOrder::Limit {
+					base_asset: Asset::Flip,
+					quote_asset: STABLE_ASSET,
+					side: Side::Sell,
+					id: 0
+				},
+				CloseOrder::Range { base_asset: Asset::Flip, quote_asset: STABLE_ASSET, id: 0 },
+			],
+			vec![limit_order(1, 20), range_order(1, -20..20)],
+		));
+		assert_eq!(
+			open_orders(),
+			(Some([(1, 20)].into_iter().collect()), Some([(1, -20..20)].into_iter().collect()))
+		);
+
+		// If any part of the batch fails, nothing changes:
+		assert_noop!(
+			set_orders_batch(
+				vec![
+					CloseOrder::Range { base_asset: Asset::Flip, quote_asset: STABLE_ASSET, id: 1 },
+					CloseOrder::Range { base_asset: Asset::Flip, quote_asset: STABLE_ASSET, id: 2 },
+				],
+				vec![range_order(2, -30..30)],
+			),
+			Error::<Test>::OrderDoesNotExist
+		);
+		assert_noop!(
+			set_orders_batch(
+				vec![],
+				vec![range_order(2, -30..30), limit_order(2, 30), limit_order(3, 1_000_000)]
+			),
+			Error::<Test>::InvalidTick
+		);
+	});
+}
+
 #[test]
 fn only_governance_can_set_pool_fee() {
 	new_test_ext().execute_with(|| {
@@ -1032,7 +1320,8 @@ fn auto_sweeping() {
 					Side::Sell,
 					1,
 					Some(100),
-					amount
+					amount,
+					None
 				));
 			}
 
@@ -1067,7 +1356,8 @@ fn auto_sweeping() {
 				Side::Buy,
 				1,
 				Some(0),
-				5_000
+				5_000,
+				None
 			));
 
 			// Note: increase due to implicit sweeping in `set_limit_order`
@@ -1121,7 +1411,8 @@ fn cancel_all_limit_orders_for_account() {
 				side,
 				ORDER_ID,
 				Some(100),
-				AMOUNT
+				AMOUNT,
+				None
 			));
 		}
 
@@ -1152,6 +1443,9 @@ fn can_update_all_config_items() {
 	new_test_ext().execute_with(|| {
 		const NEW_LIMIT_ORDER_THRESHOLD_USDC: AssetAmount = 5_000 * 10u128.pow(6);
 		const NEW_LIMIT_ORDER_THRESHOLD_USDT: AssetAmount = 6_000 * 10u128.pow(6);
+		const NEW_MINIMUM_ORDER_AGE: u32 = 5;
+		const NEW_PROTOCOL_FEE: Permill = Permill::from_percent(10);
+		let flip_pair = AssetPair::new(Asset::Flip, STABLE_ASSET).unwrap();
 
 		// Check that the default values are different from the new ones
 		assert_ne!(
@@ -1168,6 +1462,8 @@ fn can_update_all_config_items() {
 				.unwrap_or_default(),
 			NEW_LIMIT_ORDER_THRESHOLD_USDT
 		);
+		assert_ne!(MinimumOrderAgeForFees::<Test>::get(), NEW_MINIMUM_ORDER_AGE);
+		assert_ne!(ProtocolFees::<Test>::get(flip_pair), NEW_PROTOCOL_FEE);
 
 		// Update all config items at the same time
 		assert_ok!(LiquidityPools::update_pallet_config(
@@ -1181,6 +1477,12 @@ fn can_update_all_config_items() {
 					asset: Asset::Usdt,
 					amount: NEW_LIMIT_ORDER_THRESHOLD_USDT
 				},
+				PalletConfigUpdate::MinimumOrderAgeForFees { blocks: NEW_MINIMUM_ORDER_AGE },
+				PalletConfigUpdate::ProtocolFee {
+					base_asset: Asset::Flip,
+					quote_asset: STABLE_ASSET,
+					fee: NEW_PROTOCOL_FEE
+				},
 			]
 			.try_into()
 			.unwrap()
@@ -1201,6 +1503,8 @@ fn can_update_all_config_items() {
 				.unwrap_or_default(),
 			NEW_LIMIT_ORDER_THRESHOLD_USDT
 		);
+		assert_eq!(MinimumOrderAgeForFees::<Test>::get(), NEW_MINIMUM_ORDER_AGE);
+		assert_eq!(ProtocolFees::<Test>::get(flip_pair), NEW_PROTOCOL_FEE);
 
 		// Check that the events were emitted
 		assert_events_eq!(
@@ -1217,6 +1521,18 @@ fn can_update_all_config_items() {
 					amount: NEW_LIMIT_ORDER_THRESHOLD_USDT,
 				},
 			}),
+			RuntimeEvent::LiquidityPools(Event::PalletConfigUpdated {
+				update: PalletConfigUpdate::MinimumOrderAgeForFees {
+					blocks: NEW_MINIMUM_ORDER_AGE,
+				},
+			}),
+			RuntimeEvent::LiquidityPools(Event::PalletConfigUpdated {
+				update: PalletConfigUpdate::ProtocolFee {
+					base_asset: Asset::Flip,
+					quote_asset: STABLE_ASSET,
+					fee: NEW_PROTOCOL_FEE,
+				},
+			}),
 		);
 
 		// Make sure that only governance can update the config
@@ -1262,7 +1578,8 @@ fn test_sweeping_when_updating_limit_order() {
 				Side::Sell,
 				1,
 				Some(0),
-				amount
+				amount,
+				None
 			));
 			MockBalance::credit_account(&lp, STABLE_ASSET, amount);
 			assert_ok!(LiquidityPools::set_limit_order(
@@ -1272,7 +1589,8 @@ fn test_sweeping_when_updating_limit_order() {
 				Side::Buy,
 				2,
 				Some(0),
-				amount
+				amount,
+				None
 			));
 		}
 		assert_eq!(get_balance(&ALICE), (0, 0));
@@ -1297,7 +1615,8 @@ fn test_sweeping_when_updating_limit_order() {
 			Side::Sell,
 			1,
 			None,
-			10_000
+			10_000,
+			None
 		));
 		assert_eq!(get_balance(&ALICE), (0, 5000));
 
@@ -1313,7 +1632,8 @@ fn test_sweeping_when_updating_limit_order() {
 			Side::Sell,
 			1,
 			None,
-			1000
+			1000,
+			None
 		));
 		assert_eq!(get_balance(&BOB), (9000, 5000));
 	});
@@ -1402,3 +1722,1830 @@ fn test_sweeping_when_updating_range_order() {
 		assert_eq!(HistoricalEarnedFees::<Test>::get(BOB, STABLE_ASSET), EXPECTED_FEES);
 	});
 }
+
+#[test]
+fn can_create_fee_tier_pools() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		const FEE: u32 = 500_000;
+		const TIER_FEE: u32 = 3_000;
+		let price = price_at_tick(0).unwrap();
+
+		// The pair's primary pool must exist first.
+		assert_noop!(
+			LiquidityPools::new_fee_tier_pool(
+				RuntimeOrigin::root(),
+				FLIP,
+				STABLE_ASSET,
+				TIER_FEE,
+				price
+			),
+			Error::<Test>::PoolDoesNotExist
+		);
+		assert_ok!(LiquidityPools::new_pool(RuntimeOrigin::root(), FLIP, STABLE_ASSET, FEE, price));
+
+		assert_noop!(
+			LiquidityPools::new_fee_tier_pool(
+				RuntimeOrigin::signed(ALICE),
+				FLIP,
+				STABLE_ASSET,
+				TIER_FEE,
+				price
+			),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			LiquidityPools::new_fee_tier_pool(
+				RuntimeOrigin::root(),
+				FLIP,
+				STABLE_ASSET,
+				FEE,
+				price
+			),
+			Error::<Test>::FeeTierAlreadyExists
+		);
+
+		assert_ok!(LiquidityPools::new_fee_tier_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			price
+		));
+		System::assert_last_event(RuntimeEvent::LiquidityPools(
+			Event::<Test>::NewFeeTierPoolCreated {
+				base_asset: FLIP,
+				quote_asset: STABLE_ASSET,
+				fee_hundredth_pips: TIER_FEE,
+				initial_price: price,
+			},
+		));
+		assert!(FeeTierPools::<Test>::contains_key(
+			AssetPair::new(FLIP, STABLE_ASSET).unwrap(),
+			TIER_FEE
+		));
+
+		assert_noop!(
+			LiquidityPools::new_fee_tier_pool(
+				RuntimeOrigin::root(),
+				FLIP,
+				STABLE_ASSET,
+				TIER_FEE,
+				price
+			),
+			Error::<Test>::FeeTierAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn primary_pool_fee_cannot_match_a_fee_tier_pool() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		const TIER_FEE: u32 = 3_000;
+		let price = price_at_tick(0).unwrap();
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			500_000,
+			price
+		));
+		assert_ok!(LiquidityPools::new_fee_tier_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			price
+		));
+
+		assert_noop!(
+			LiquidityPools::set_pool_fees(RuntimeOrigin::root(), FLIP, STABLE_ASSET, TIER_FEE),
+			Error::<Test>::FeeTierAlreadyExists
+		);
+		assert_ok!(LiquidityPools::set_pool_fees(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE + 1
+		));
+	});
+}
+
+#[test]
+fn orders_with_the_same_id_in_different_fee_tiers_are_tracked_separately() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		const TIER_FEE: u32 = 3_000;
+		const MINIMUM_AGE: u32 = 10;
+		let price = price_at_tick(0).unwrap();
+		let asset_pair = AssetPair::new(FLIP, STABLE_ASSET).unwrap();
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::MinimumOrderAgeForFees { blocks: MINIMUM_AGE }]
+				.try_into()
+				.unwrap()
+		));
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			500_000,
+			price
+		));
+		assert_ok!(LiquidityPools::new_fee_tier_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			price
+		));
+		MockBalance::credit_account(&ALICE, FLIP, 200_000);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			Some(0),
+			100_000,
+			None,
+		));
+		assert_ok!(LiquidityPools::set_fee_tier_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			Side::Sell,
+			0,
+			Some(0),
+			100_000,
+		));
+
+		let young_order = |pool_fee_hundredth_pips| {
+			YoungOrders::<Test>::get((
+				ALICE,
+				asset_pair,
+				OrderKind::Limit { side: Side::Sell, pool_fee_hundredth_pips },
+				0,
+			))
+		};
+		assert_eq!(young_order(None), Some(1 + MINIMUM_AGE as u64));
+		assert_eq!(young_order(Some(TIER_FEE)), Some(1 + MINIMUM_AGE as u64));
+
+		// Changing the primary pool's fee doesn't affect how its orders are identified:
+		assert_ok!(LiquidityPools::set_pool_fees(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE + 1
+		));
+
+		System::set_block_number(1 + MINIMUM_AGE as u64);
+		LiquidityPools::on_initialize(1 + MINIMUM_AGE as u64);
+		assert_eq!(young_order(None), None);
+		assert_eq!(young_order(Some(TIER_FEE)), None);
+	});
+}
+
+#[test]
+fn swaps_are_routed_to_the_fee_tier_with_the_best_output() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		const POSITION: core::ops::Range<Tick> = -100_000..100_000;
+		const TIER_FEE: u32 = 0;
+		let price = price_at_tick(0).unwrap();
+		let asset_pair = AssetPair::new(FLIP, STABLE_ASSET).unwrap();
+		let size = RangeOrderSize::AssetAmounts {
+			maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+			minimum: AssetAmounts { base: 0, quote: 0 },
+		};
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			500_000,
+			price
+		));
+		assert_ok!(LiquidityPools::new_fee_tier_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			price
+		));
+
+		MockBalance::credit_account(&ALICE, FLIP, 2_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 2_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			0,
+			Some(POSITION),
+			size,
+		));
+		assert_ok!(LiquidityPools::set_fee_tier_range_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			0,
+			Some(POSITION),
+			size,
+		));
+		assert_eq!(
+			LiquidityPools::open_order_count(
+				&ALICE,
+				&PoolPairsMap { base: FLIP, quote: STABLE_ASSET }
+			),
+			Ok(2)
+		);
+
+		// The fee tier pool has the same liquidity but a lower fee, so it gives a better output:
+		let primary_pool = Pools::<Test>::get(asset_pair).unwrap();
+		let fee_tier_pool = FeeTierPools::<Test>::get(asset_pair, TIER_FEE).unwrap();
+
+		assert_ok!(LiquidityPools::swap_single_leg(FLIP, STABLE_ASSET, 1_000));
+
+		assert_eq!(Pools::<Test>::get(asset_pair).unwrap(), primary_pool);
+		assert_ne!(FeeTierPools::<Test>::get(asset_pair, TIER_FEE).unwrap(), fee_tier_pool);
+	});
+}
+
+#[test]
+fn limit_orders_in_fee_tier_pools_are_filled_by_swaps() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		const TIER_FEE: u32 = 0;
+		const ORDER_AMOUNT: AssetAmount = 100_000;
+		let price = price_at_tick(0).unwrap();
+		let swap_ids = vec![SwapId::from(1)];
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			500_000,
+			price
+		));
+		assert_ok!(LiquidityPools::new_fee_tier_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			price
+		));
+		assert_noop!(
+			LiquidityPools::set_fee_tier_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				FLIP,
+				STABLE_ASSET,
+				TIER_FEE + 1,
+				Side::Sell,
+				0,
+				Some(0),
+				ORDER_AMOUNT,
+			),
+			Error::<Test>::PoolDoesNotExist
+		);
+
+		MockBalance::credit_account(&ALICE, FLIP, ORDER_AMOUNT);
+		assert_ok!(LiquidityPools::set_fee_tier_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			Side::Sell,
+			0,
+			Some(0),
+			ORDER_AMOUNT,
+		));
+		assert_eq!(
+			LiquidityPools::open_order_count(
+				&ALICE,
+				&PoolPairsMap { base: FLIP, quote: STABLE_ASSET }
+			),
+			Ok(1)
+		);
+		assert_eq!(LiquidityPools::open_order_balances(&ALICE)[FLIP], ORDER_AMOUNT);
+
+		// Only the fee tier pool has liquidity, so the swap is routed to it:
+		System::reset_events();
+		let output =
+			LiquidityPools::swap_single_leg_for_swaps(&swap_ids, STABLE_ASSET, FLIP, 5_000)
+				.unwrap();
+		assert!(output > 0);
+		assert_matching_event_count!(
+			Test,
+			RuntimeEvent::LiquidityPools(Event::LimitOrderFilled {
+				lp: ALICE,
+				sold_amount,
+				swap_ids: filled_by,
+				..
+			}) if *sold_amount == output && *filled_by == swap_ids => 1
+		);
+
+		// The fee tier pool is listed and its order is cancelled with the LP's other orders:
+		assert_eq!(
+			LiquidityPools::fee_tier_pools(FLIP, STABLE_ASSET)
+				.unwrap()
+				.into_iter()
+				.map(|pool_info| pool_info.range_order_fee_hundredth_pips)
+				.collect::<Vec<_>>(),
+			vec![TIER_FEE]
+		);
+		assert_ok!(LiquidityPools::cancel_all_limit_orders(&ALICE));
+		assert_eq!(
+			LiquidityPools::open_order_count(
+				&ALICE,
+				&PoolPairsMap { base: FLIP, quote: STABLE_ASSET }
+			),
+			Ok(0)
+		);
+		assert_eq!(MockBalance::get_balance(&ALICE, FLIP), ORDER_AMOUNT - output);
+		assert_eq!(MockBalance::get_balance(&ALICE, STABLE_ASSET), 5_000);
+	});
+}
+
+#[test]
+fn auto_rebalancing_range_orders_are_recentred() {
+	new_test_ext().execute_with(|| {
+		const ID: OrderId = 0;
+		let asset_pair = AssetPair::try_new::<Test>(Asset::Eth, STABLE_ASSET).unwrap();
+		let range_of = |lp| {
+			Pools::<Test>::get(asset_pair)
+				.unwrap()
+				.range_orders_cache
+				.get(&lp)
+				.and_then(|orders| orders.get(&ID))
+				.cloned()
+		};
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		for lp in [ALICE, BOB] {
+			MockBalance::credit_account(&lp, STABLE_ASSET, 1_000_000_000);
+			MockBalance::credit_account(&lp, Asset::Eth, 1_000_000_000);
+		}
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(BOB),
+			Asset::Eth,
+			STABLE_ASSET,
+			ID,
+			Some(-10_000..10_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+
+		assert_noop!(
+			LiquidityPools::set_auto_rebalancing_range_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				ID,
+				0,
+				50,
+				RangeOrderSize::Liquidity { liquidity: 1_000 },
+			),
+			Error::<Test>::InvalidRebalancingParameters
+		);
+		assert_ok!(LiquidityPools::set_auto_rebalancing_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			ID,
+			100,
+			50,
+			RangeOrderSize::Liquidity { liquidity: 1_000 },
+		));
+		assert_eq!(range_of(ALICE), Some(-100..100));
+
+		// Move the price well outside of the order's range:
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Eth, 100_000));
+		let current_tick = tick_at_sqrt_price(
+			Pools::<Test>::get(asset_pair)
+				.unwrap()
+				.pool_state
+				.current_range_order_pool_price(),
+		);
+		assert!(current_tick > 150);
+
+		// Rebalancing is rate limited:
+		System::set_block_number(2);
+		LiquidityPools::on_idle(2, Weight::MAX);
+		assert_eq!(range_of(ALICE), Some(-100..100));
+
+		let rebalance_block = 1 + MIN_BLOCKS_BETWEEN_REBALANCES;
+		System::set_block_number(rebalance_block as u64);
+		LiquidityPools::on_idle(rebalance_block as u64, Weight::MAX);
+		assert_eq!(range_of(ALICE), Some(current_tick - 100..current_tick + 100));
+		assert_eq!(
+			AutoRebalancingRangeOrders::<Test>::get(asset_pair, (ALICE, ID))
+				.unwrap()
+				.last_rebalanced_at,
+			rebalance_block
+		);
+
+		// Once stopped, the order is no longer rebalanced:
+		assert_ok!(LiquidityPools::stop_auto_rebalancing(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			ID,
+		));
+		assert!(AutoRebalancingRangeOrders::<Test>::get(asset_pair, (ALICE, ID)).is_none());
+		assert_noop!(
+			LiquidityPools::stop_auto_rebalancing(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				ID,
+			),
+			Error::<Test>::RangeOrderNotAutoRebalancing
+		);
+	});
+}
+
+#[test]
+fn auto_rebalancing_resumes_where_the_weight_ran_out() {
+	new_test_ext().execute_with(|| {
+		const ID: OrderId = 0;
+		let asset_pair = AssetPair::try_new::<Test>(Asset::Eth, STABLE_ASSET).unwrap();
+		let is_rebalanced = |lp| {
+			Pools::<Test>::get(asset_pair)
+				.unwrap()
+				.range_orders_cache
+				.get(&lp)
+				.and_then(|orders| orders.get(&ID))
+				.cloned() != Some(-100..100)
+		};
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&BOB, STABLE_ASSET, 1_000_000_000);
+		MockBalance::credit_account(&BOB, Asset::Eth, 1_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(BOB),
+			Asset::Eth,
+			STABLE_ASSET,
+			1,
+			Some(-10_000..10_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		for lp in [ALICE, BOB] {
+			MockBalance::credit_account(&lp, STABLE_ASSET, 1_000_000_000);
+			MockBalance::credit_account(&lp, Asset::Eth, 1_000_000_000);
+			assert_ok!(LiquidityPools::set_auto_rebalancing_range_order(
+				RuntimeOrigin::signed(lp),
+				Asset::Eth,
+				STABLE_ASSET,
+				ID,
+				100,
+				50,
+				RangeOrderSize::Liquidity { liquidity: 1_000 },
+			));
+		}
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Eth, 100_000));
+
+		// Only enough weight to rebalance one of the orders:
+		let rebalance_block = 1 + MIN_BLOCKS_BETWEEN_REBALANCES;
+		System::set_block_number(rebalance_block as u64);
+		let weight = <() as WeightInfo>::set_range_order()
+			.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads_writes(4, 2));
+		assert_eq!(LiquidityPools::on_idle(rebalance_block as u64, weight), weight);
+		assert_eq!([ALICE, BOB].into_iter().filter(|lp| is_rebalanced(*lp)).count(), 1);
+		assert!(AutoRebalancingCursor::<Test>::get().is_some());
+
+		// The next block continues with the other order:
+		System::set_block_number(rebalance_block as u64 + 1);
+		LiquidityPools::on_idle(rebalance_block as u64 + 1, Weight::MAX);
+		assert!(is_rebalanced(ALICE) && is_rebalanced(BOB));
+		assert!(AutoRebalancingCursor::<Test>::get().is_none());
+
+		// Without enough weight to read anything, nothing is done:
+		assert_eq!(
+			LiquidityPools::on_idle(rebalance_block as u64 + 2, Weight::zero()),
+			Weight::zero()
+		);
+	});
+}
+
+#[test]
+fn limit_order_fills_are_reported() {
+	new_test_ext().execute_with(|| {
+		const ORDER_AMOUNT: AssetAmount = 10_000;
+		let swap_ids = vec![SwapId::from(1), SwapId::from(2)];
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			10_000,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Flip, ORDER_AMOUNT);
+		MockBalance::credit_account(&BOB, STABLE_ASSET, ORDER_AMOUNT);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			Some(0),
+			ORDER_AMOUNT,
+			None,
+		));
+		// An order on the other side is not consumed by the swap:
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(BOB),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Buy,
+			0,
+			Some(-10),
+			ORDER_AMOUNT,
+			None,
+		));
+
+		System::reset_events();
+		let output =
+			LiquidityPools::swap_single_leg_for_swaps(&swap_ids, STABLE_ASSET, Asset::Flip, 5_000)
+				.unwrap();
+
+		let fills = System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::LiquidityPools(event @ Event::LimitOrderFilled { .. }) =>
+					Some(event),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(fills.len(), 1);
+		assert!(matches!(
+			&fills[0],
+			Event::LimitOrderFilled {
+				lp: ALICE,
+				base_asset: Asset::Flip,
+				quote_asset: STABLE_ASSET,
+				side: Side::Sell,
+				id: 0,
+				tick: 0,
+				sold_amount,
+				bought_amount,
+				fees_earned,
+				remaining_amount,
+				swap_ids: filled_by,
+			} if *sold_amount == output &&
+				*remaining_amount == ORDER_AMOUNT - output &&
+				*bought_amount > 0 &&
+				*fees_earned > 0 &&
+				*filled_by == swap_ids
+		));
+
+		// Swaps not executed on behalf of any swap are reported without swap ids:
+		System::reset_events();
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 1_000));
+		assert_matching_event_count!(
+			Test,
+			RuntimeEvent::LiquidityPools(Event::LimitOrderFilled { swap_ids, .. }) if swap_ids.is_empty() => 1
+		);
+	});
+}
+
+#[test]
+fn pool_depth_includes_fee_tier_pools() {
+	new_test_ext().execute_with(|| {
+		const FLIP: Asset = Asset::Flip;
+		const POSITION: core::ops::Range<Tick> = -1_000..1_000;
+		const TIER_FEE: u32 = 1_000;
+		let price = price_at_tick(0).unwrap();
+		let size = RangeOrderSize::Liquidity { liquidity: 1_000_000 };
+		let range_order_depth = || {
+			let depth = LiquidityPools::pool_depth(FLIP, STABLE_ASSET, -2_000..2_000).unwrap();
+			(depth.asks.range_orders.depth, depth.bids.range_orders.depth)
+		};
+
+		assert_ok!(LiquidityPools::new_pool(RuntimeOrigin::root(), FLIP, STABLE_ASSET, 0, price));
+		assert_ok!(LiquidityPools::new_fee_tier_pool(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			price
+		));
+		MockBalance::credit_account(&ALICE, FLIP, 1_000_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000);
+
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			0,
+			Some(POSITION),
+			size,
+		));
+		let (asks, bids) = range_order_depth();
+		assert!(!asks.is_zero() && !bids.is_zero());
+
+		assert_ok!(LiquidityPools::set_fee_tier_range_order(
+			RuntimeOrigin::signed(ALICE),
+			FLIP,
+			STABLE_ASSET,
+			TIER_FEE,
+			0,
+			Some(POSITION),
+			size,
+		));
+		assert_eq!(range_order_depth(), (asks + asks, bids + bids));
+	});
+}
+
+#[test]
+fn young_orders_forfeit_fees_to_the_network() {
+	new_test_ext().execute_with(|| {
+		const MINIMUM_AGE: u32 = 10;
+		let young_order_fees_forfeited = || {
+			System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					RuntimeEvent::LiquidityPools(Event::YoungOrderFeesForfeited {
+						amount, ..
+					}) => Some(amount),
+					_ => None,
+				})
+				.sum::<AssetAmount>()
+		};
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::MinimumOrderAgeForFees { blocks: MINIMUM_AGE }]
+				.try_into()
+				.unwrap()
+		));
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			10_000,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Flip, 100_000);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			Some(0),
+			100_000,
+			None,
+		));
+
+		// Fees earned by the order while it is young are forfeited when collected:
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 10_000));
+		System::set_block_number(5);
+		assert_ok!(LiquidityPools::sweep(&ALICE));
+		let forfeited = young_order_fees_forfeited();
+		assert!(forfeited > 0);
+		assert_eq!(HistoricalEarnedFees::<Test>::get(ALICE, STABLE_ASSET), 0);
+		assert_eq!(
+			<<Test as Config>::SwapRequestHandler>::get_swap_requests()
+				.into_iter()
+				.map(|request| (request.input_asset, request.input_amount))
+				.collect::<Vec<_>>(),
+			vec![(STABLE_ASSET, forfeited)]
+		);
+
+		// ... and also when the order matures:
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 10_000));
+		System::reset_events();
+		System::set_block_number(1 + MINIMUM_AGE as u64);
+		LiquidityPools::on_initialize(1 + MINIMUM_AGE as u64);
+		assert!(young_order_fees_forfeited() > 0);
+		assert_eq!(HistoricalEarnedFees::<Test>::get(ALICE, STABLE_ASSET), 0);
+
+		// Once mature, the order earns fees:
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 10_000));
+		System::reset_events();
+		assert_ok!(LiquidityPools::sweep(&ALICE));
+		assert_eq!(young_order_fees_forfeited(), 0);
+		assert!(HistoricalEarnedFees::<Test>::get(ALICE, STABLE_ASSET) > 0);
+	});
+}
+
+#[test]
+fn auto_compounding_range_orders_reinvest_collected_fees() {
+	new_test_ext().execute_with(|| {
+		const RANGE: core::ops::Range<Tick> = -1000..1000;
+		let order_liquidity = || {
+			LiquidityPools::pool_orders(Asset::Flip, STABLE_ASSET, Some(ALICE), false)
+				.unwrap()
+				.range_orders
+				.into_iter()
+				.map(|order| order.liquidity)
+				.sum::<Liquidity>()
+		};
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			10_000,
+			price_at_tick(0).unwrap(),
+		));
+
+		// The order must exist before it can be set to auto-compound:
+		assert_noop!(
+			LiquidityPools::set_range_order_auto_compounding(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Flip,
+				STABLE_ASSET,
+				0,
+				true,
+			),
+			Error::<Test>::OrderDoesNotExist,
+		);
+
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			Some(RANGE),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000_000 },
+		));
+		assert_ok!(LiquidityPools::set_range_order_auto_compounding(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			true,
+		));
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::AutoCompoundingUpdated {
+			lp: ALICE,
+			base_asset: Asset::Flip,
+			quote_asset: STABLE_ASSET,
+			id: 0,
+			auto_compounding: true,
+		}));
+
+		// Earn fees in both assets, then collect them:
+		let liquidity_before = order_liquidity();
+		let balances_before =
+			[Asset::Flip, STABLE_ASSET].map(|asset| MockBalance::get_balance(&ALICE, asset));
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 1_000_000));
+		assert_ok!(LiquidityPools::swap_single_leg(Asset::Flip, STABLE_ASSET, 1_000_000));
+		assert_ok!(LiquidityPools::sweep(&ALICE));
+
+		assert!(order_liquidity() > liquidity_before);
+		assert_matching_event_count!(
+			Test,
+			RuntimeEvent::LiquidityPools(Event::RangeOrderFeesCompounded { .. }) => 1
+		);
+		// Only fees that could not be added at the order's asset ratio are left over:
+		assert!([Asset::Flip, STABLE_ASSET]
+			.map(|asset| MockBalance::get_balance(&ALICE, asset))
+			.into_iter()
+			.zip(balances_before)
+			.any(|(after, before)| after == before));
+
+		// Closing the order stops it compounding:
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			None,
+			RangeOrderSize::Liquidity { liquidity: 0 },
+		));
+		assert_eq!(AutoCompoundingRangeOrders::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn pool_statistics_are_recorded_per_epoch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			10_000,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			Some(-1000..1000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000_000 },
+		));
+		assert_eq!(LiquidityPools::pool_statistics(Asset::Flip, STABLE_ASSET), Ok(vec![]));
+
+		MockEpochInfo::set_epoch(1);
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 1_000_000));
+		<LiquidityPools as SwappingApi>::record_network_fee(Asset::Flip, 1_000);
+		MockEpochInfo::set_epoch(2);
+		assert_ok!(LiquidityPools::swap_single_leg(Asset::Flip, STABLE_ASSET, 2_000_000));
+
+		let statistics = LiquidityPools::pool_statistics(Asset::Flip, STABLE_ASSET).unwrap();
+		assert_eq!(statistics.iter().map(|(epoch, _)| *epoch).collect::<Vec<_>>(), vec![1, 2]);
+		// Swap inputs don't include the 1% LP fee:
+		let (_, first_epoch) = statistics[0];
+		assert_eq!(first_epoch.swap_inputs, PoolPairsMap { base: 0.into(), quote: 990_000.into() });
+		assert_eq!(first_epoch.lp_fees, PoolPairsMap { base: 0.into(), quote: 10_000.into() });
+		assert_eq!(first_epoch.network_fees, 1_000);
+		let (_, second_epoch) = statistics[1];
+		assert_eq!(
+			second_epoch.swap_inputs,
+			PoolPairsMap { base: 1_980_000.into(), quote: 0.into() }
+		);
+		assert_eq!(second_epoch.lp_fees, PoolPairsMap { base: 20_000.into(), quote: 0.into() });
+		assert_eq!(second_epoch.network_fees, 0);
+	});
+}
+
+#[test]
+fn lp_earned_fees_are_recorded_per_epoch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			10_000,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			Some(-1000..1000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000_000 },
+		));
+		assert_eq!(LiquidityPools::lp_earned_fees_history(&ALICE), vec![]);
+
+		MockEpochInfo::set_epoch(1);
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 1_000_000));
+		LiquidityPools::sweep(&ALICE).unwrap();
+		MockEpochInfo::set_epoch(2);
+		assert_ok!(LiquidityPools::swap_single_leg(Asset::Flip, STABLE_ASSET, 2_000_000));
+		LiquidityPools::sweep(&ALICE).unwrap();
+
+		let assets = PoolPairsMap { base: Asset::Flip, quote: STABLE_ASSET };
+		let history = LiquidityPools::lp_earned_fees_history(&ALICE);
+		assert_eq!(
+			history.iter().map(|(pool, epoch, _)| (*pool, *epoch)).collect::<Vec<_>>(),
+			vec![(assets, 1), (assets, 2)]
+		);
+		let (_, _, first_epoch) = history[0];
+		assert_eq!(first_epoch.base, 0);
+		assert!(first_epoch.quote > 0);
+		let (_, _, second_epoch) = history[1];
+		assert!(second_epoch.base > 0);
+		assert_eq!(second_epoch.quote, 0);
+		assert_eq!(
+			first_epoch.quote,
+			HistoricalEarnedFees::<Test>::get(ALICE, STABLE_ASSET),
+			"Fees earned per epoch should add up to the historical total"
+		);
+		assert_eq!(second_epoch.base, HistoricalEarnedFees::<Test>::get(ALICE, Asset::Flip));
+	});
+}
+
+#[test]
+fn can_create_and_swap_through_direct_pools() {
+	new_test_ext().execute_with(|| {
+		// Direct pools must be quoted in the asset that comes first:
+		assert_noop!(
+			LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				Asset::Eth,
+				Asset::Btc,
+				0,
+				price_at_tick(0).unwrap(),
+			),
+			Error::<Test>::PoolDoesNotExist,
+		);
+		assert!(!<LiquidityPools as SwappingApi>::pool_exists(Asset::Btc, Asset::Eth));
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Btc,
+			Asset::Eth,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		assert!(<LiquidityPools as SwappingApi>::pool_exists(Asset::Eth, Asset::Btc));
+
+		MockBalance::credit_account(&ALICE, Asset::Btc, 1_000_000);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Btc,
+			Asset::Eth,
+			Side::Sell,
+			0,
+			Some(0),
+			1_000_000,
+			None,
+		));
+		assert_eq!(LiquidityPools::swap_single_leg(Asset::Eth, Asset::Btc, 1_000), Ok(1_000));
+	});
+}
+
+#[test]
+fn open_order_balances_include_direct_pools() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Btc,
+			Asset::Eth,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+
+		MockBalance::credit_account(&ALICE, Asset::Btc, 1_000_000);
+		MockBalance::credit_account(&ALICE, Asset::Eth, 500_000);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Btc,
+			Asset::Eth,
+			Side::Sell,
+			0,
+			Some(0),
+			1_000_000,
+			None,
+		));
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Btc,
+			Asset::Eth,
+			Side::Buy,
+			1,
+			Some(0),
+			500_000,
+			None,
+		));
+
+		let balances = LiquidityPools::open_order_balances(&ALICE);
+		assert_eq!(balances[Asset::Btc], 1_000_000);
+		assert_eq!(balances[Asset::Eth], 500_000);
+		assert_eq!(balances[STABLE_ASSET], 0);
+	});
+}
+
+#[test]
+fn protocol_fee_is_taken_from_lp_fees() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			10_000,
+			price_at_tick(0).unwrap(),
+		));
+		assert_eq!(LiquidityPools::protocol_fee(Asset::Flip, STABLE_ASSET), Ok(Permill::zero()));
+		assert_ok!(LiquidityPools::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::ProtocolFee {
+				base_asset: Asset::Flip,
+				quote_asset: STABLE_ASSET,
+				fee: Permill::from_percent(20),
+			}]
+			.try_into()
+			.unwrap()
+		));
+		assert_eq!(
+			LiquidityPools::protocol_fee(Asset::Flip, STABLE_ASSET),
+			Ok(Permill::from_percent(20))
+		);
+
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			Some(0),
+			1_000_000,
+			None,
+		));
+		assert_ok!(LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Flip, 100_000));
+		assert_ok!(LiquidityPools::sweep(&ALICE));
+
+		// The 1% LP fee is 1_000 USDC, of which 20% goes to the network:
+		assert_eq!(HistoricalEarnedFees::<Test>::get(ALICE, STABLE_ASSET), 800);
+		assert_eq!(
+			<<Test as Config>::SwapRequestHandler>::get_swap_requests()
+				.into_iter()
+				.map(|request| (request.input_asset, request.input_amount))
+				.collect::<Vec<_>>(),
+			vec![(STABLE_ASSET, 200)]
+		);
+		assert_matching_event_count!(
+			Test,
+			RuntimeEvent::LiquidityPools(Event::ProtocolFeeTaken { amount: 200, .. }) => 1
+		);
+	});
+}
+
+#[test]
+fn can_modify_limit_orders() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000);
+
+		assert_noop!(
+			LiquidityPools::modify_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Flip,
+				STABLE_ASSET,
+				Side::Sell,
+				0,
+				10,
+				1_000,
+			),
+			Error::<Test>::OrderDoesNotExist,
+		);
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			Some(0),
+			1_000,
+			Some(100),
+		));
+		assert_ok!(LiquidityPools::modify_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			10,
+			3_000,
+		));
+
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::LimitOrderModified {
+			lp: ALICE,
+			base_asset: Asset::Flip,
+			quote_asset: STABLE_ASSET,
+			side: Side::Sell,
+			id: 0,
+			old_tick: 0,
+			new_tick: 10,
+			old_sell_amount: 1_000,
+			new_sell_amount: 3_000,
+		}));
+		let pool = Pools::<Test>::get(AssetPair::new(Asset::Flip, STABLE_ASSET).unwrap()).unwrap();
+		assert_eq!(
+			pool.limit_orders_cache[Side::Sell.to_sold_pair()].get(&ALICE).cloned(),
+			Some([(0, 10)].into_iter().collect())
+		);
+		assert_eq!(MockBalance::get_balance(&ALICE, Asset::Flip), 1_000_000 - 3_000);
+		// The order's expiry is kept:
+		assert_eq!(
+			LimitOrderExpiries::<Test>::get((
+				ALICE,
+				AssetPair::new(Asset::Flip, STABLE_ASSET).unwrap(),
+				Side::Sell,
+				0
+			)),
+			Some(100)
+		);
+	});
+}
+
+#[test]
+fn can_transfer_orders_between_lps() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		let asset_pair = AssetPair::new(Asset::Flip, STABLE_ASSET).unwrap();
+		MockBalance::credit_account(&ALICE, Asset::Flip, 1_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000);
+
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			0,
+			Some(0),
+			1_000,
+			None,
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		let limit_order = CloseOrder::Limit {
+			base_asset: Asset::Flip,
+			quote_asset: STABLE_ASSET,
+			side: Side::Sell,
+			id: 0,
+		};
+		let range_order =
+			CloseOrder::Range { base_asset: Asset::Flip, quote_asset: STABLE_ASSET, id: 0 };
+
+		// Orders can only be taken once they are offered, and only by the LP they are offered to:
+		assert_noop!(
+			LiquidityPools::accept_order_transfer(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				limit_order,
+				5
+			),
+			Error::<Test>::NoOrderTransferOffer,
+		);
+		assert_noop!(
+			LiquidityPools::offer_order_transfer(
+				RuntimeOrigin::signed(ALICE),
+				CloseOrder::Limit {
+					base_asset: Asset::Flip,
+					quote_asset: STABLE_ASSET,
+					side: Side::Buy,
+					id: 0
+				},
+				Some(BOB),
+			),
+			Error::<Test>::OrderDoesNotExist,
+		);
+		assert_ok!(LiquidityPools::offer_order_transfer(
+			RuntimeOrigin::signed(ALICE),
+			limit_order,
+			Some(BOB)
+		));
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::OrderTransferOffered {
+			from: ALICE,
+			to: Some(BOB),
+			order: limit_order,
+		}));
+		assert_noop!(
+			LiquidityPools::accept_order_transfer(
+				RuntimeOrigin::signed(ALICE),
+				ALICE,
+				limit_order,
+				5
+			),
+			Error::<Test>::NoOrderTransferOffer,
+		);
+
+		assert_ok!(LiquidityPools::accept_order_transfer(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			limit_order,
+			5
+		));
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::OrderTransferred {
+			from: ALICE,
+			to: BOB,
+			order: limit_order,
+			new_id: 5,
+		}));
+		let pool = Pools::<Test>::get(asset_pair).unwrap();
+		assert_eq!(pool.limit_orders_cache[Side::Sell.to_sold_pair()].get(&ALICE), None);
+		assert_eq!(
+			pool.limit_orders_cache[Side::Sell.to_sold_pair()].get(&BOB).cloned(),
+			Some([(5, 0)].into_iter().collect())
+		);
+		assert_eq!(
+			pool.pool_state.limit_order(&(BOB, 5), Side::Sell, 0).unwrap().1.amount,
+			1_000.into()
+		);
+		// The offer was used up:
+		assert_noop!(
+			LiquidityPools::accept_order_transfer(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				limit_order,
+				6
+			),
+			Error::<Test>::OrderDoesNotExist,
+		);
+
+		// The new owner can close the order and receives its funds:
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(BOB),
+			Asset::Flip,
+			STABLE_ASSET,
+			Side::Sell,
+			5,
+			None,
+			0,
+			None,
+		));
+		assert_eq!(MockBalance::get_balance(&BOB, Asset::Flip), 1_000);
+
+		// Offers lapse when the order is closed:
+		assert_ok!(LiquidityPools::offer_order_transfer(
+			RuntimeOrigin::signed(ALICE),
+			range_order,
+			Some(BOB)
+		));
+		assert_ok!(LiquidityPools::cancel_orders_batch(
+			RuntimeOrigin::signed(ALICE),
+			vec![range_order].try_into().unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Flip,
+			STABLE_ASSET,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		assert_noop!(
+			LiquidityPools::accept_order_transfer(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				range_order,
+				0
+			),
+			Error::<Test>::NoOrderTransferOffer,
+		);
+
+		assert_ok!(LiquidityPools::offer_order_transfer(
+			RuntimeOrigin::signed(ALICE),
+			range_order,
+			Some(BOB)
+		));
+		assert_ok!(LiquidityPools::accept_order_transfer(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			range_order,
+			0
+		));
+		let pool = Pools::<Test>::get(asset_pair).unwrap();
+		assert_eq!(pool.range_orders_cache.get(&ALICE), None);
+		assert_eq!(
+			pool.range_orders_cache.get(&BOB).cloned(),
+			Some([(0, -100..100)].into_iter().collect())
+		);
+		assert_eq!(
+			pool.pool_state.range_order(&(BOB, 0), -100..100).unwrap().1.liquidity,
+			1_000_000
+		);
+	});
+}
+
+#[test]
+fn price_circuit_breaker_parameters_are_validated() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+
+		let set_circuit_breaker = |window_blocks, cool_down_blocks| {
+			LiquidityPools::update_pallet_config(
+				RuntimeOrigin::root(),
+				bounded_vec![PalletConfigUpdate::PriceCircuitBreaker {
+					base_asset: Asset::Eth,
+					quote_asset: STABLE_ASSET,
+					circuit_breaker: Some(PriceCircuitBreaker {
+						max_movement_ticks: 50,
+						window_blocks,
+						cool_down_blocks,
+					}),
+				}],
+			)
+		};
+
+		for (window_blocks, cool_down_blocks) in [
+			(0, 10),
+			(MAX_TWAP_WINDOW_BLOCKS + 1, 10),
+			(10, 0),
+			(10, MAX_CIRCUIT_BREAKER_COOL_DOWN_BLOCKS + 1),
+		] {
+			assert_noop!(
+				set_circuit_breaker(window_blocks, cool_down_blocks),
+				Error::<Test>::InvalidCircuitBreakerParameters,
+			);
+		}
+		assert_ok!(set_circuit_breaker(
+			MAX_TWAP_WINDOW_BLOCKS,
+			MAX_CIRCUIT_BREAKER_COOL_DOWN_BLOCKS
+		));
+	});
+}
+
+#[test]
+fn price_circuit_breaker_suspends_swaps() {
+	const COOL_DOWN: u64 = 5;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		let asset_pair = AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap();
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000_000_000_000_000);
+		MockBalance::credit_account(&ALICE, Asset::Eth, 1_000_000_000_000_000_000);
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(-1_000..1_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000_000_000_000_000 },
+		));
+
+		assert_noop!(
+			LiquidityPools::update_pallet_config(
+				RuntimeOrigin::root(),
+				bounded_vec![PalletConfigUpdate::PriceCircuitBreaker {
+					base_asset: Asset::Eth,
+					quote_asset: STABLE_ASSET,
+					circuit_breaker: Some(PriceCircuitBreaker {
+						max_movement_ticks: 50,
+						window_blocks: MAX_TWAP_WINDOW_BLOCKS + 1,
+						cool_down_blocks: COOL_DOWN as u32,
+					}),
+				}],
+			),
+			Error::<Test>::InvalidCircuitBreakerParameters,
+		);
+		assert_ok!(LiquidityPools::update_pallet_config(
+			RuntimeOrigin::root(),
+			bounded_vec![PalletConfigUpdate::PriceCircuitBreaker {
+				base_asset: Asset::Eth,
+				quote_asset: STABLE_ASSET,
+				circuit_breaker: Some(PriceCircuitBreaker {
+					max_movement_ticks: 50,
+					window_blocks: 10,
+					cool_down_blocks: COOL_DOWN as u32,
+				}),
+			}],
+		));
+
+		// Small price movements don't trip the circuit breaker:
+		System::set_block_number(2);
+		LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Eth, 1_000_000).unwrap();
+		LiquidityPools::on_initialize(3);
+		assert!(!PoolSuspensions::<Test>::contains_key(asset_pair));
+
+		System::set_block_number(3);
+		LiquidityPools::swap_single_leg(STABLE_ASSET, Asset::Eth, 20_000_000_000_000_000).unwrap();
+
+		System::set_block_number(4);
+		LiquidityPools::on_initialize(4);
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::PoolSuspended {
+			asset_pair,
+			resumes_at: 4 + COOL_DOWN,
+		}));
+		assert_noop!(
+			LiquidityPools::swap_single_leg(Asset::Eth, STABLE_ASSET, 1_000_000),
+			Error::<Test>::PoolSuspended,
+		);
+
+		// Swaps resume after the cool-down, and earlier price movements don't count:
+		System::set_block_number(4 + COOL_DOWN);
+		LiquidityPools::on_initialize(4 + COOL_DOWN);
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::PoolResumed { asset_pair }));
+		LiquidityPools::swap_single_leg(Asset::Eth, STABLE_ASSET, 1_000_000).unwrap();
+		LiquidityPools::on_initialize(5 + COOL_DOWN);
+		assert!(!LiquidityPools::pool_suspended(asset_pair));
+	});
+}
+
+#[test]
+fn pool_orders_can_be_paged() {
+	const CHARLIE: u64 = 125;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		for lp in [ALICE, BOB] {
+			MockBalance::credit_account(&lp, Asset::Eth, 1_000_000);
+			MockBalance::credit_account(&lp, STABLE_ASSET, 1_000_000);
+			for id in 0..3 {
+				for side in [Side::Sell, Side::Buy] {
+					assert_ok!(LiquidityPools::set_limit_order(
+						RuntimeOrigin::signed(lp),
+						Asset::Eth,
+						STABLE_ASSET,
+						side,
+						id,
+						Some(id as Tick * 10),
+						1_000,
+						None,
+					));
+				}
+			}
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(lp),
+				Asset::Eth,
+				STABLE_ASSET,
+				0,
+				Some(-100..100),
+				RangeOrderSize::Liquidity { liquidity: 1_000 },
+			));
+		}
+		let asset_pair = AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap();
+		assert_eq!(LpOrderCounts::<Test>::get(ALICE, asset_pair), 7);
+
+		let collect_pages = |option_lp: Option<u64>, limit: u32| {
+			let mut orders = PoolOrders::<Test>::default();
+			let mut after = None;
+			let mut pages = 0;
+			loop {
+				let page = LiquidityPools::pool_orders_page(
+					Asset::Eth,
+					STABLE_ASSET,
+					option_lp,
+					false,
+					after,
+					limit,
+				)
+				.unwrap();
+				pages += 1;
+				orders.limit_orders.asks.extend(page.orders.limit_orders.asks);
+				orders.limit_orders.bids.extend(page.orders.limit_orders.bids);
+				orders.range_orders.extend(page.orders.range_orders);
+				match page.next {
+					Some(next) => after = Some(next),
+					None => return (orders, pages),
+				}
+			}
+		};
+
+		for (option_lp, limit, expected_pages) in
+			[(None, 4, 4), (None, 14, 1), (Some(BOB), 3, 3), (Some(ALICE), 1_000, 1)]
+		{
+			let (orders, pages) = collect_pages(option_lp, limit);
+			assert_eq!(
+				orders,
+				LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, option_lp, false).unwrap()
+			);
+			assert_eq!(pages, expected_pages);
+		}
+
+		// LPs without orders in the pool don't need the pool to be read:
+		assert_eq!(
+			LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, Some(CHARLIE), false).unwrap(),
+			Default::default()
+		);
+
+		// Closing orders removes them from the index:
+		assert_ok!(LiquidityPools::cancel_orders_batch(
+			RuntimeOrigin::signed(BOB),
+			(0..3)
+				.flat_map(|id| {
+					[Side::Sell, Side::Buy].map(|side| CloseOrder::Limit {
+						base_asset: Asset::Eth,
+						quote_asset: STABLE_ASSET,
+						side,
+						id,
+					})
+				})
+				.chain([CloseOrder::Range {
+					base_asset: Asset::Eth,
+					quote_asset: STABLE_ASSET,
+					id: 0
+				}])
+				.collect::<Vec<_>>()
+				.try_into()
+				.unwrap(),
+		));
+		assert!(!LpOrderCounts::<Test>::contains_key(BOB, asset_pair));
+		assert_eq!(
+			LiquidityPools::lp_orders(ALICE, false).unwrap(),
+			vec![(
+				Asset::Eth,
+				STABLE_ASSET,
+				LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, None, false).unwrap()
+			)]
+		);
+		assert_eq!(LiquidityPools::lp_orders(BOB, false).unwrap(), vec![]);
+	});
+}
+
+#[test]
+fn lp_orders_are_valued_at_the_range_order_price() {
+	new_test_ext().execute_with(|| {
+		// At tick 0 one unit of the base asset is worth exactly one unit of the quote asset.
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		MockBalance::credit_account(&ALICE, Asset::Eth, 1_000_000);
+		MockBalance::credit_account(&ALICE, STABLE_ASSET, 1_000_000);
+
+		for (side, id, sell_amount) in [(Side::Sell, 0, 1_000), (Side::Buy, 1, 500)] {
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				STABLE_ASSET,
+				side,
+				id,
+				Some(100),
+				sell_amount,
+				None,
+			));
+		}
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			Some(-100..100),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+
+		let [valuation] =
+			<[_; 1]>::try_from(LiquidityPools::lp_orders_valuation(ALICE).unwrap()).unwrap();
+		let range_order_amounts = LiquidityPools::pool_range_order_liquidity_value(
+			Asset::Eth,
+			STABLE_ASSET,
+			-100..100,
+			1_000_000,
+		)
+		.unwrap();
+
+		assert_eq!((valuation.base_asset, valuation.quote_asset), (Asset::Eth, STABLE_ASSET));
+		assert_eq!(valuation.price, price_at_tick(0).unwrap());
+		assert_eq!(
+			valuation
+				.limit_orders
+				.asks
+				.iter()
+				.chain(&valuation.limit_orders.bids)
+				.map(|valued_order| valued_order.value)
+				.collect::<Vec<_>>(),
+			vec![1_000.into(), 500.into()]
+		);
+		assert_eq!(
+			valuation
+				.range_orders
+				.iter()
+				.map(|valued_order| valued_order.value)
+				.collect::<Vec<_>>(),
+			vec![range_order_amounts.base + range_order_amounts.quote]
+		);
+		assert_eq!(
+			valuation.total_value,
+			Amount::from(1_500) + range_order_amounts.base + range_order_amounts.quote
+		);
+
+		// LPs without orders have nothing to value.
+		assert_eq!(LiquidityPools::lp_orders_valuation(BOB).unwrap(), vec![]);
+	});
+}
+
+#[test]
+fn range_order_mint_quote_matches_debited_amounts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(10).unwrap(),
+		));
+		const INITIAL_BALANCE: AssetAmount = 1_000_000_000;
+
+		for (id, size) in [
+			RangeOrderSize::Liquidity { liquidity: 1_234_567 },
+			RangeOrderSize::AssetAmounts {
+				maximum: AssetAmounts { base: 1_000_000, quote: 1_000_000 },
+				minimum: AssetAmounts { base: 0, quote: 0 },
+			},
+		]
+		.into_iter()
+		.enumerate()
+		{
+			let lp = [ALICE, BOB][id];
+			MockBalance::credit_account(&lp, Asset::Eth, INITIAL_BALANCE);
+			MockBalance::credit_account(&lp, STABLE_ASSET, INITIAL_BALANCE);
+
+			let quote = LiquidityPools::pool_range_order_mint_quote(
+				Asset::Eth,
+				STABLE_ASSET,
+				-100..100,
+				size,
+			)
+			.unwrap();
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(lp),
+				Asset::Eth,
+				STABLE_ASSET,
+				0,
+				Some(-100..100),
+				size,
+			));
+
+			assert_eq!(
+				quote.amounts,
+				AssetAmounts {
+					base: INITIAL_BALANCE - MockBalance::get_balance(&lp, Asset::Eth),
+					quote: INITIAL_BALANCE - MockBalance::get_balance(&lp, STABLE_ASSET),
+				}
+			);
+			assert_eq!(
+				Pools::<Test>::get(AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap())
+					.unwrap()
+					.pool_state
+					.range_order(&(lp, 0), -100..100)
+					.unwrap()
+					.1
+					.liquidity,
+				quote.liquidity
+			);
+		}
+
+		assert_noop!(
+			LiquidityPools::pool_range_order_mint_quote(
+				Asset::Eth,
+				STABLE_ASSET,
+				core::ops::Range { start: 100, end: -100 },
+				RangeOrderSize::Liquidity { liquidity: 1_000 },
+			),
+			Error::<Test>::InvalidTickRange,
+		);
+		assert_noop!(
+			LiquidityPools::pool_range_order_mint_quote(
+				Asset::Eth,
+				STABLE_ASSET,
+				-100..100,
+				RangeOrderSize::Liquidity { liquidity: 0 },
+			),
+			Error::<Test>::InvalidSize,
+		);
+	});
+}
+
+#[test]
+fn incentive_programmes_reward_in_range_liquidity() {
+	const FUNDER: u64 = 125;
+	const REWARDS: AssetAmount = 1_000;
+	const DURATION: u32 = 4;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			STABLE_ASSET,
+			0,
+			price_at_tick(0).unwrap(),
+		));
+		let asset_pair = AssetPair::new(Asset::Eth, STABLE_ASSET).unwrap();
+		for lp in [ALICE, BOB] {
+			MockBalance::credit_account(&lp, Asset::Eth, 1_000_000);
+			MockBalance::credit_account(&lp, STABLE_ASSET, 1_000_000);
+		}
+		// Alice has three times as much in-range liquidity as Bob, whose other order is out of
+		// range:
+		for (lp, id, tick_range, liquidity) in [
+			(ALICE, 0, -100..100, 300_000),
+			(BOB, 0, -200..200, 100_000),
+			(BOB, 1, 100..200, 1_000_000),
+		] {
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(lp),
+				Asset::Eth,
+				STABLE_ASSET,
+				id,
+				Some(tick_range),
+				RangeOrderSize::Liquidity { liquidity },
+			));
+		}
+
+		MockBalance::credit_account(&FUNDER, Asset::Flip, REWARDS);
+		// Programmes are funded by the LP that signs the call:
+		assert_noop!(
+			LiquidityPools::fund_incentive_programme(
+				RuntimeOrigin::signed(FUNDER),
+				Asset::Eth,
+				STABLE_ASSET,
+				Asset::Flip,
+				REWARDS,
+				DURATION,
+			),
+			sp_runtime::DispatchError::BadOrigin,
+		);
+		assert_ok!(
+			<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_liquidity_provider(
+				&FUNDER,
+			)
+		);
+		assert_noop!(
+			LiquidityPools::fund_incentive_programme(
+				RuntimeOrigin::signed(FUNDER),
+				Asset::Eth,
+				STABLE_ASSET,
+				Asset::Flip,
+				REWARDS,
+				REWARDS as u32 + 1,
+			),
+			Error::<Test>::InvalidIncentiveProgramme,
+		);
+		assert_ok!(LiquidityPools::fund_incentive_programme(
+			RuntimeOrigin::signed(FUNDER),
+			Asset::Eth,
+			STABLE_ASSET,
+			Asset::Flip,
+			REWARDS,
+			DURATION,
+		));
+		assert_eq!(MockBalance::get_balance(&FUNDER, Asset::Flip), 0);
+		// Only the funder can top up the programme:
+		assert_noop!(
+			LiquidityPools::fund_incentive_programme(
+				RuntimeOrigin::signed(BOB),
+				Asset::Eth,
+				STABLE_ASSET,
+				Asset::Flip,
+				1,
+				DURATION,
+			),
+			Error::<Test>::IncentiveProgrammeFunderMismatch,
+		);
+
+		LiquidityPools::on_initialize(1);
+		assert_eq!(IncentiveRewards::<Test>::get(ALICE, Asset::Flip), 187);
+		assert_eq!(IncentiveRewards::<Test>::get(BOB, Asset::Flip), 62);
+
+		assert_ok!(LiquidityPools::claim_incentive_rewards(
+			RuntimeOrigin::signed(BOB),
+			Asset::Flip
+		));
+		assert_eq!(MockBalance::get_balance(&BOB, Asset::Flip), 62);
+		assert_noop!(
+			LiquidityPools::claim_incentive_rewards(RuntimeOrigin::signed(BOB), Asset::Flip),
+			Error::<Test>::NoIncentiveRewards,
+		);
+
+		for block in 2..=DURATION as u64 {
+			LiquidityPools::on_initialize(block);
+		}
+		assert_eq!(IncentiveRewards::<Test>::get(ALICE, Asset::Flip), 187 * 4);
+		// Rounding leaves some rewards undistributed, which are paid out in one more block:
+		LiquidityPools::on_initialize(DURATION as u64 + 1);
+		assert!(!IncentiveProgrammes::<Test>::contains_key(asset_pair));
+		let distributed = IncentiveRewards::<Test>::get(ALICE, Asset::Flip) +
+			IncentiveRewards::<Test>::get(BOB, Asset::Flip) +
+			MockBalance::get_balance(&BOB, Asset::Flip);
+		assert_eq!(distributed + MockBalance::get_balance(&FUNDER, Asset::Flip), REWARDS);
+		System::assert_last_event(RuntimeEvent::LiquidityPools(Event::IncentiveProgrammeEnded {
+			asset_pair,
+			refunded: REWARDS - distributed,
+		}));
+	});
+}
@@ -148,6 +148,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaximumAccruableReputation: Get<ReputationPoints>;
 
+		/// The longest suspension that governance can impose for any offence.
+		#[pallet::constant]
+		type MaximumSuspensionDuration: Get<BlockNumberFor<Self>>;
+
 		/// Safe mode access
 		type SafeMode: Get<PalletSafeMode>;
 	}
@@ -229,6 +233,8 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Tried to set the accrual ration to something invalid.
 		InvalidAccrualRatio,
+		/// The penalty is negative, exceeds the reputation range, or suspends for too long.
+		InvalidPenalty,
 	}
 
 	#[pallet::call]
@@ -270,6 +276,10 @@ pub mod pallet {
 		/// ## Events
 		///
 		/// - [MissedHeartbeatPenaltyUpdated](Event::MissedHeartbeatPenaltyUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidPenalty](Error::InvalidPenalty)
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::update_missed_heartbeat_penalty())]
 		pub fn update_missed_heartbeat_penalty(
@@ -278,19 +288,31 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::EnsureGovernance::ensure_origin(origin)?;
 
-			Penalties::<T>::insert(
-				T::Offence::from(PalletOffence::MissedHeartbeat),
-				Penalty::<T> {
-					reputation: new_reputation_penalty,
-					suspension: T::HeartbeatBlockInterval::get(),
-				},
-			);
+			let new_penalty = Penalty::<T> {
+				reputation: new_reputation_penalty,
+				suspension: T::HeartbeatBlockInterval::get(),
+			};
+			Self::ensure_valid_penalty(&new_penalty)?;
+
+			Penalties::<T>::insert(T::Offence::from(PalletOffence::MissedHeartbeat), new_penalty);
 
 			Self::deposit_event(Event::MissedHeartbeatPenaltyUpdated { new_reputation_penalty });
 			Ok(())
 		}
 
 		/// Set the [Penalty] for an [Offence].
+		///
+		/// The reputation penalty must be non-negative and no greater than the full range of
+		/// reputation points, and the suspension no longer than
+		/// [Config::MaximumSuspensionDuration].
+		///
+		/// ## Events
+		///
+		/// - [PenaltyUpdated](Event::PenaltyUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidPenalty](Error::InvalidPenalty)
 		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::set_penalty())]
 		pub fn set_penalty(
@@ -299,6 +321,7 @@ pub mod pallet {
 			new_penalty: Penalty<T>,
 		) -> DispatchResult {
 			T::EnsureGovernance::ensure_origin(origin)?;
+			Self::ensure_valid_penalty(&new_penalty)?;
 
 			let old_penalty = Penalties::<T>::mutate(offence, |penalty| {
 				let old = penalty.clone();
@@ -521,6 +544,16 @@ impl<T: Config, L: OffenceList<T>> QualifyNode<T::ValidatorId> for ExclusionList
 }
 
 impl<T: Config> Pallet<T> {
+	fn ensure_valid_penalty(penalty: &Penalty<T>) -> DispatchResult {
+		let (floor, ceiling) = T::ReputationPointFloorAndCeiling::get();
+		ensure!(
+			(0..=ceiling.saturating_sub(floor)).contains(&penalty.reputation) &&
+				penalty.suspension <= T::MaximumSuspensionDuration::get(),
+			Error::<T>::InvalidPenalty
+		);
+		Ok(())
+	}
+
 	pub fn penalise_offline_authorities(offline_authorities: Vec<T::ValidatorId>) {
 		<Self as OffenceReporter>::report_many(
 			PalletOffence::MissedHeartbeat,
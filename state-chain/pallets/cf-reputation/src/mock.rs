@@ -59,11 +59,13 @@ pub const MAX_ACCRUABLE_REPUTATION: ReputationPoints = 25;
 pub const MISSED_HEARTBEAT_PENALTY_POINTS: ReputationPoints = 2;
 pub const GRANDPA_EQUIVOCATION_PENALTY_POINTS: ReputationPoints = 50;
 pub const GRANDPA_SUSPENSION_DURATION: u64 = HEARTBEAT_BLOCK_INTERVAL * 10;
+pub const MAX_SUSPENSION_DURATION: u64 = HEARTBEAT_BLOCK_INTERVAL * 100;
 
 parameter_types! {
 	pub const HeartbeatBlockInterval: u64 = HEARTBEAT_BLOCK_INTERVAL;
 	pub const ReputationPointFloorAndCeiling: (i32, i32) = (-2880, 2880);
 	pub const MaximumAccruableReputation: ReputationPoints = MAX_ACCRUABLE_REPUTATION;
+	pub const MaximumSuspensionDuration: u64 = MAX_SUSPENSION_DURATION;
 }
 
 // Mocking the `Slasher` trait
@@ -160,6 +162,7 @@ impl Config for Test {
 	type Slasher = MockSlasher;
 	type WeightInfo = ();
 	type MaximumAccruableReputation = MaximumAccruableReputation;
+	type MaximumSuspensionDuration = MaximumSuspensionDuration;
 	type SafeMode = MockRuntimeSafeMode;
 }
 
@@ -684,6 +684,45 @@ fn reputation_cutoff_threshold() {
 	);
 }
 
+#[test]
+fn penalty_updates_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let (floor, ceiling) = ReputationPointFloorAndCeiling::get();
+		for invalid_penalty in [
+			Penalty::<Test> { reputation: -1, suspension: 0 },
+			Penalty::<Test> { reputation: ceiling - floor + 1, suspension: 0 },
+			Penalty::<Test> { reputation: 0, suspension: MAX_SUSPENSION_DURATION + 1 },
+		] {
+			assert_noop!(
+				ReputationPallet::set_penalty(
+					RuntimeOrigin::root(),
+					AllOffences::ForgettingYourYubiKey,
+					invalid_penalty
+				),
+				Error::<Test>::InvalidPenalty,
+			);
+		}
+		assert_noop!(
+			ReputationPallet::update_missed_heartbeat_penalty(RuntimeOrigin::root(), -1),
+			Error::<Test>::InvalidPenalty,
+		);
+
+		let new_penalty =
+			Penalty::<Test> { reputation: ceiling - floor, suspension: MAX_SUSPENSION_DURATION };
+		assert_ok!(ReputationPallet::set_penalty(
+			RuntimeOrigin::root(),
+			AllOffences::ForgettingYourYubiKey,
+			new_penalty.clone()
+		));
+		assert_eq!(ReputationPallet::penalties(AllOffences::ForgettingYourYubiKey), new_penalty);
+		System::assert_last_event(RuntimeEvent::ReputationPallet(Event::PenaltyUpdated {
+			offence: AllOffences::ForgettingYourYubiKey,
+			old_penalty: Penalty { reputation: 15, suspension: HEARTBEAT_BLOCK_INTERVAL },
+			new_penalty,
+		}));
+	});
+}
+
 #[test]
 fn ensure_governance_origin_checks() {
 	new_test_ext().execute_with(|| {
@@ -115,6 +115,85 @@ mod benchmarks {
 		}
 	}
 
+	#[benchmark]
+	fn request_split_swap_deposit_address() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		let share = (MAX_BASIS_POINTS as u32 / MAX_SPLIT_OUTPUT_ADDRESSES) as BasisPoints;
+		let destination_addresses = (0..MAX_SPLIT_OUTPUT_ADDRESSES)
+			.map(|i| {
+				(
+					EncodedAddress::benchmark_value(),
+					if i == 0 {
+						MAX_BASIS_POINTS - share * (MAX_SPLIT_OUTPUT_ADDRESSES as u16 - 1)
+					} else {
+						share
+					},
+				)
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_split_swap_deposit_address {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_addresses,
+			broker_commission: 10,
+			boost_fee: 0,
+			affiliate_fees: Default::default(),
+			refund_parameters: ChannelRefundParametersEncoded {
+				retry_duration: 100,
+				refund_address: EncodedAddress::benchmark_value(),
+				min_price: U256::from(0),
+			},
+			dca_parameters: None,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+	}
+
+	#[benchmark]
+	fn request_account_funding_deposit_address() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_account_funding_deposit_address {
+			source_asset: Asset::Eth,
+			account_id: account("funded_account", 0, 0),
+			broker_commission: 10,
+			boost_fee: 0,
+			affiliate_fees: Default::default(),
+			refund_parameters: ChannelRefundParametersEncoded {
+				retry_duration: 100,
+				refund_address: EncodedAddress::benchmark_value(),
+				min_price: U256::from(0),
+			},
+			dca_parameters: None,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+	}
+
 	#[benchmark]
 	fn withdraw() {
 		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
@@ -202,6 +281,70 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn open_private_evm_channel() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+
+		let caller = OriginFor::<T>::signed(broker_id.clone());
+
+		T::FeePayment::mint_to_account(&broker_id, (600 * FLIPPERINOS_PER_FLIP).into());
+
+		// Worst case: the broker already has private channels open on other chains.
+		assert_ok!(Pallet::<T>::open_private_btc_channel(caller.clone()));
+		assert_ok!(Pallet::<T>::open_private_evm_channel(
+			caller.clone(),
+			ForeignChain::Arbitrum,
+			EncodedAddress::Arb(Default::default()),
+		));
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::open_private_evm_channel(
+				caller,
+				ForeignChain::Ethereum,
+				EncodedAddress::Eth(Default::default()),
+			));
+		}
+
+		assert!(
+			BrokerPrivateEvmChannels::<T>::contains_key(&broker_id, ForeignChain::Ethereum),
+			"Private channel must have been opened"
+		);
+	}
+
+	#[benchmark]
+	fn close_private_evm_channel() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+
+		let caller = OriginFor::<T>::signed(broker_id.clone());
+
+		T::FeePayment::mint_to_account(&broker_id, (600 * FLIPPERINOS_PER_FLIP).into());
+
+		assert_ok!(Pallet::<T>::open_private_btc_channel(caller.clone()));
+		assert_ok!(Pallet::<T>::open_private_evm_channel(
+			caller.clone(),
+			ForeignChain::Arbitrum,
+			EncodedAddress::Arb(Default::default()),
+		));
+		assert_ok!(Pallet::<T>::open_private_evm_channel(
+			caller.clone(),
+			ForeignChain::Ethereum,
+			EncodedAddress::Eth(Default::default()),
+		));
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::close_private_evm_channel(caller, ForeignChain::Ethereum));
+		}
+
+		assert!(
+			!BrokerPrivateEvmChannels::<T>::contains_key(&broker_id, ForeignChain::Ethereum),
+			"Private channel must have been closed"
+		);
+	}
+
 	#[benchmark]
 	fn register_affiliate() {
 		let broker_id =
@@ -255,6 +398,98 @@ mod benchmarks {
 		);
 	}
 
+	/// Generates a key in the keystore and returns its Ethereum address together with a closure
+	/// that signs a payload with it.
+	fn ethereum_signing_key() -> (EthereumAddress, impl Fn(&[u8; 32]) -> EthereumSignature) {
+		const KEY_TYPE: sp_runtime::KeyTypeId = sp_runtime::KeyTypeId(*b"affl");
+
+		let public_key = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let sign = move |payload: &[u8; 32]| {
+			let mut signature = [0u8; 65];
+			signature.copy_from_slice(
+				sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public_key, payload)
+					.expect("Key was generated above.")
+					.as_ref(),
+			);
+			signature
+		};
+
+		let uncompressed_public_key =
+			sp_io::crypto::secp256k1_ecdsa_recover(&sign(&[1u8; 32]), &[1u8; 32]).unwrap();
+		(
+			EthereumAddress::from_slice(
+				&sp_io::hashing::keccak_256(&uncompressed_public_key)[12..],
+			),
+			sign,
+		)
+	}
+
+	/// Registers an affiliate whose withdrawal address is controlled by a key in the keystore.
+	/// Returns a closure that signs the given action on behalf of the affiliate.
+	fn register_affiliate_with_signing_key<T: Config>(
+		broker_id: &T::AccountId,
+	) -> (T::AccountId, impl Fn(&AffiliateAction) -> EthereumSignature) {
+		let (withdrawal_address, sign) = ethereum_signing_key();
+
+		assert_ok!(Pallet::<T>::register_affiliate(
+			OriginFor::<T>::signed(broker_id.clone()),
+			withdrawal_address,
+		));
+		let affiliate_id = AffiliateIdMapping::<T>::get(broker_id, AffiliateShortId::from(0))
+			.expect("Affiliate must be registered!");
+
+		let signing_affiliate_id = affiliate_id.clone();
+		(affiliate_id, move |action: &AffiliateAction| {
+			sign(&Pallet::<T>::affiliate_signing_payload(&signing_affiliate_id, action))
+		})
+	}
+
+	#[benchmark]
+	fn update_affiliate_withdrawal_address() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let (affiliate_id, sign) = register_affiliate_with_signing_key::<T>(&broker_id);
+
+		let new_withdrawal_address = EthereumAddress::repeat_byte(0x01);
+		let signature = sign(&AffiliateAction::UpdateWithdrawalAddress(new_withdrawal_address));
+
+		#[extrinsic_call]
+		update_affiliate_withdrawal_address(
+			RawOrigin::Signed(whitelisted_caller()),
+			broker_id.clone(),
+			AffiliateShortId::from(0),
+			new_withdrawal_address,
+			signature,
+		);
+
+		assert_eq!(
+			AffiliateAccountDetails::<T>::get(&broker_id, &affiliate_id)
+				.unwrap()
+				.withdrawal_address,
+			new_withdrawal_address
+		);
+	}
+
+	#[benchmark]
+	fn signed_affiliate_withdrawal_request() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let (affiliate_id, sign) = register_affiliate_with_signing_key::<T>(&broker_id);
+
+		T::BalanceApi::credit_account(&affiliate_id, Asset::Usdc, 200);
+		let signature = sign(&AffiliateAction::Withdraw);
+
+		#[extrinsic_call]
+		signed_affiliate_withdrawal_request(
+			RawOrigin::Signed(whitelisted_caller()),
+			broker_id,
+			AffiliateShortId::from(0),
+			signature,
+		);
+
+		assert_eq!(T::BalanceApi::get_balance(&affiliate_id, Asset::Usdc), 0);
+	}
+
 	#[benchmark]
 	fn set_vault_swap_minimum_broker_fee() {
 		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
@@ -270,5 +505,279 @@ mod benchmarks {
 		assert_eq!(VaultSwapMinimumBrokerFee::<T>::get(caller.clone()), 100);
 	}
 
+	#[benchmark]
+	fn update_dca_swap_request() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+		// Authorising the update with a signature of the refund address is the expensive path.
+		let (refund_address, sign) = ethereum_signing_key();
+
+		let swap_request_id = Pallet::<T>::init_swap_request(
+			Asset::Eth,
+			1_000_000,
+			Asset::Usdc,
+			SwapRequestType::Regular {
+				output_action: SwapOutputAction::CreditOnChain { account_id: caller.clone() },
+			},
+			Default::default(),
+			Some(RefundParametersExtended {
+				retry_duration: 100,
+				refund_destination: AccountOrAddress::ExternalAddress(ForeignChainAddress::Eth(
+					refund_address,
+				)),
+				min_price: U256::from(0),
+			}),
+			Some(DcaParameters { number_of_chunks: 10, chunk_interval: SWAP_DELAY_BLOCKS }),
+			SwapOrigin::OnChainAccount(caller.clone()),
+		);
+		let signature = sign(
+			&Pallet::<T>::dca_update_signing_payload(
+				swap_request_id,
+				&DcaUpdate::CancelRemainingChunks,
+			)
+			.unwrap(),
+		);
+
+		#[extrinsic_call]
+		update_dca_swap_request(
+			RawOrigin::Signed(whitelisted_caller()),
+			swap_request_id,
+			DcaUpdate::CancelRemainingChunks,
+			Some(signature),
+		);
+
+		assert_eq!(DcaUpdateNonces::<T>::get(refund_address), 1);
+	}
+
+	#[benchmark]
+	fn cancel_swap_request() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		let swap_request_id = Pallet::<T>::init_swap_request(
+			Asset::Eth,
+			1_000_000,
+			Asset::Usdc,
+			SwapRequestType::Regular {
+				output_action: SwapOutputAction::CreditOnChain { account_id: caller.clone() },
+			},
+			Default::default(),
+			Some(RefundParametersExtended {
+				retry_duration: 100,
+				refund_destination: AccountOrAddress::InternalAccount(caller.clone()),
+				min_price: U256::from(0),
+			}),
+			None,
+			SwapOrigin::Vault {
+				tx_id: cf_chains::TransactionInIdForAnyChain::Evm(Default::default()),
+				broker_id: Some(caller.clone()),
+			},
+		);
+
+		#[extrinsic_call]
+		cancel_swap_request(RawOrigin::Signed(caller.clone()), swap_request_id);
+
+		assert!(!SwapRequests::<T>::contains_key(swap_request_id));
+	}
+
+	#[benchmark]
+	fn request_scheduled_swap_deposit_address() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		let execute_at = frame_system::Pallet::<T>::block_number() +
+			BlockNumberFor::<T>::from(MaxSwapSchedulingHorizonBlocks::<T>::get());
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_scheduled_swap_deposit_address {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_address: EncodedAddress::benchmark_value(),
+			broker_commission: 10,
+			boost_fee: 0,
+			channel_metadata: None,
+			refund_parameters: ChannelRefundParametersEncoded {
+				retry_duration: 100,
+				refund_address: EncodedAddress::benchmark_value(),
+				min_price: U256::from(0),
+			},
+			affiliate_fees: Default::default(),
+			dca_parameters: None,
+			execute_at,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+
+		assert_eq!(ScheduledChannelSwapsQueue::<T>::get(execute_at).len(), 1);
+	}
+
+	#[benchmark]
+	fn register_referral_code() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		register_referral_code(RawOrigin::Signed(caller.clone()), *b"REFERRAL");
+
+		assert_eq!(ReferralCodes::<T>::get(*b"REFERRAL"), Some(caller));
+	}
+
+	#[benchmark]
+	fn request_referred_swap_deposit_address() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		let referrer: T::AccountId = account("referrer", 0, 0);
+		ReferralCodes::<T>::insert(*b"REFERRAL", referrer);
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_referred_swap_deposit_address {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_address: EncodedAddress::benchmark_value(),
+			broker_commission: 10,
+			boost_fee: 0,
+			channel_metadata: None,
+			refund_parameters: ChannelRefundParametersEncoded {
+				retry_duration: 100,
+				refund_address: EncodedAddress::benchmark_value(),
+				min_price: U256::from(0),
+			},
+			affiliate_fees: Default::default(),
+			dca_parameters: None,
+			referral_code: *b"REFERRAL",
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+
+		assert_eq!(ChannelReferrers::<T>::iter().count(), 1);
+	}
+
+	#[benchmark]
+	fn claim_referral_rewards() {
+		let caller: T::AccountId = whitelisted_caller();
+		ReferralRewards::<T>::insert(&caller, 1_000);
+
+		#[extrinsic_call]
+		claim_referral_rewards(RawOrigin::Signed(caller.clone()));
+
+		assert!(!ReferralRewards::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn request_twap_protected_swap_deposit_address() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_twap_protected_swap_deposit_address {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_address: EncodedAddress::benchmark_value(),
+			broker_commission: 10,
+			boost_fee: 0,
+			channel_metadata: None,
+			refund_parameters: ChannelRefundParametersEncoded {
+				retry_duration: 100,
+				refund_address: EncodedAddress::benchmark_value(),
+				min_price: U256::from(0),
+			},
+			affiliate_fees: Default::default(),
+			dca_parameters: None,
+			max_slippage_bps_vs_twap: 100,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+
+		assert_eq!(ChannelTwapSlippage::<T>::iter().count(), 1);
+	}
+
+	#[benchmark]
+	fn set_broker_channel_defaults() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+		let defaults = BrokerChannelDefaults {
+			broker_commission: 10,
+			retry_duration: 10,
+			boost_fee: 5,
+			dca_parameters: Some(DcaParameters { number_of_chunks: 2, chunk_interval: 2 }),
+		};
+
+		#[extrinsic_call]
+		set_broker_channel_defaults(RawOrigin::Signed(caller.clone()), Some(defaults.clone()));
+
+		assert_eq!(BrokerChannelDefaultParameters::<T>::get(&caller), Some(defaults));
+	}
+
+	#[benchmark]
+	fn request_swap_deposit_address_with_broker_defaults() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		BrokerChannelDefaultParameters::<T>::insert(
+			&caller,
+			BrokerChannelDefaults {
+				broker_commission: 10,
+				retry_duration: 10,
+				boost_fee: 0,
+				dca_parameters: None,
+			},
+		);
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_swap_deposit_address_with_broker_defaults {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_address: EncodedAddress::benchmark_value(),
+			broker_commission: None,
+			channel_metadata: None,
+			boost_fee: None,
+			affiliate_fees: Default::default(),
+			refund_address: EncodedAddress::benchmark_value(),
+			min_price: U256::from(0),
+			retry_duration: None,
+			dca_parameters: None,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
@@ -17,7 +17,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![feature(extract_if)]
 
-use cf_amm::common::Side;
+use cf_amm::{
+	common::Side,
+	math::{mul_div_floor_checked, Price, PRICE_FRACTIONAL_BITS},
+};
 use cf_chains::{
 	address::{AddressConverter, AddressError, ForeignChainAddress},
 	ccm_checker::CcmValidityCheck,
@@ -27,16 +30,17 @@ use cf_chains::{
 };
 use cf_primitives::{
 	AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, Beneficiaries, Beneficiary,
-	BlockNumber, ChannelId, DcaParameters, ForeignChain, SwapId, SwapLeg, SwapRequestId,
-	BASIS_POINTS_PER_MILLION, FLIPPERINOS_PER_FLIP, MAX_BASIS_POINTS, SECONDS_PER_BLOCK,
-	STABLE_ASSET, SWAP_DELAY_BLOCKS,
+	BlockNumber, ChannelId, DcaParameters, EgressId, ForeignChain, SwapId, SwapLeg,
+	SwapRefundReason, SwapRequestId, BASIS_POINTS_PER_MILLION, FLIPPERINOS_PER_FLIP,
+	MAX_BASIS_POINTS, MAX_SPLIT_OUTPUT_ADDRESSES, SECONDS_PER_BLOCK, STABLE_ASSET,
+	SWAP_DELAY_BLOCKS,
 };
 use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
 	impl_pallet_safe_mode, AffiliateRegistry, AssetConverter, BalanceApi, Bonding,
-	ChannelIdAllocator, DepositApi, FundingInfo, IngressEgressFeeApi, SwapOutputAction,
-	SwapParameterValidation, SwapRequestHandler, SwapRequestType, SwapRequestTypeEncoded, SwapType,
-	SwappingApi,
+	ChannelIdAllocator, DepositApi, FundAccountFromSwap, FundingInfo, IngressEgressFeeApi,
+	PrivateChannelManager, SwapOutputAction, SwapParameterValidation, SwapRequestHandler,
+	SwapRequestType, SwapRequestTypeEncoded, SwapType, SwappingApi,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -71,11 +75,31 @@ pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(9);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(10);
 
 pub(crate) const DEFAULT_SWAP_RETRY_DELAY_BLOCKS: u32 = 5;
+/// Upper bound on the swap delay that governance can set.
+pub const MAX_SWAP_DELAY_BLOCKS: u32 = 60 / SECONDS_PER_BLOCK as u32; // 1 minute
+/// Upper bound on the swap retry delay that governance can set.
+pub const MAX_SWAP_RETRY_DELAY_BLOCKS: u32 = 600 / SECONDS_PER_BLOCK as u32; // 10 minutes
 const DEFAULT_MAX_SWAP_RETRY_DURATION_BLOCKS: u32 = 3600 / SECONDS_PER_BLOCK as u32; // 1 hour
 const DEFAULT_MAX_SWAP_REQUEST_DURATION_BLOCKS: u32 = 86_400 / SECONDS_PER_BLOCK as u32; // 24 hours
+const DEFAULT_MAX_SWAP_SCHEDULING_HORIZON_BLOCKS: u32 = 7 * 86_400 / SECONDS_PER_BLOCK as u32; // 7 days
+/// Upper bound on the swap scheduling horizon that governance can set.
+pub const MAX_SWAP_SCHEDULING_HORIZON_BLOCKS: u32 = 30 * 86_400 / SECONDS_PER_BLOCK as u32; // 30 days
+/// How long the status of a swap request is kept after the request has completed.
+pub const SWAP_STATUS_RETENTION_BLOCKS: u32 = 3600 / SECONDS_PER_BLOCK as u32; // 1 hour
+/// How long the optional swap parameters of a deposit channel (such as its referrer) are kept.
+/// This comfortably exceeds the lifetime of deposit channels on all supported chains.
+pub const CHANNEL_PARAMETERS_RETENTION_BLOCKS: u32 = 7 * 86_400 / SECONDS_PER_BLOCK as u32; // 7 days
+/// The window over which the time-weighted average price used for slippage protection is taken.
+pub const SLIPPAGE_TWAP_WINDOW_BLOCKS: u32 = 300 / SECONDS_PER_BLOCK as u32; // 5 minutes
+/// The maximum number of blocks for which the swap volume of an asset is recorded separately, see
+/// [SwapVolumeHistory].
+pub const MAX_SWAP_VOLUME_HISTORY_BLOCKS: u32 = 500;
+
+/// A short code that swappers can provide to credit a referrer with part of the network fee.
+pub type ReferralCode = [u8; 8];
 
 pub struct DefaultSwapRetryDelay<T> {
 	_phantom: PhantomData<T>,
@@ -89,6 +113,8 @@ impl<T: Config> Get<BlockNumberFor<T>> for DefaultSwapRetryDelay<T> {
 struct FeeTaken {
 	pub remaining_amount: AssetAmount,
 	pub fee: AssetAmount,
+	/// The part of the fee that was waived due to a discount.
+	pub discount: AssetAmount,
 }
 
 #[derive(Encode, Decode, TypeInfo, Serialize, Deserialize, Copy, Clone)]
@@ -97,14 +123,30 @@ pub struct AffiliateDetails {
 	pub withdrawal_address: EthereumAddress,
 }
 
+/// A 65-byte recoverable ECDSA signature, as produced by an Ethereum wallet's `personal_sign`.
+pub type EthereumSignature = [u8; 65];
+
+/// An action that an affiliate can authorise by signing it with the key of its withdrawal address.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub enum AffiliateAction {
+	Withdraw,
+	UpdateWithdrawalAddress(EthereumAddress),
+}
+
 #[derive(CloneNoBound, DebugNoBound)]
 pub struct SwapState<T: Config> {
 	pub swap: Swap<T>,
 	pub network_fee_taken: Option<AssetAmount>,
+	pub network_fee_discount: Option<AssetAmount>,
 	pub broker_fee_taken: Option<AssetAmount>,
 	pub stable_amount: Option<AssetAmount>,
 	pub final_output: Option<AssetAmount>,
 	pub stable_amount_before_fees: Option<AssetAmount>,
+	/// The part of the input swapped through a direct pool between the input and output assets,
+	/// if the swap is routed that way. The rest of the input is swapped via the Stable asset to
+	/// cover the swap's fees.
+	pub direct_input_amount: Option<AssetAmount>,
+	pub direct_output: Option<AssetAmount>,
 }
 
 impl<T: Config> SwapState<T> {
@@ -113,9 +155,12 @@ impl<T: Config> SwapState<T> {
 			stable_amount: if swap.from == STABLE_ASSET { Some(swap.input_amount) } else { None },
 			final_output: if swap.from == swap.to { Some(swap.input_amount) } else { None },
 			network_fee_taken: None,
+			network_fee_discount: None,
 			broker_fee_taken: None,
 			swap,
 			stable_amount_before_fees: None,
+			direct_input_amount: None,
+			direct_output: None,
 		}
 	}
 
@@ -147,13 +192,17 @@ impl<T: Config> SwapState<T> {
 					self.final_output = Some(output);
 				}
 			},
-			SwapLeg::FromStable => self.final_output = Some(output),
+			SwapLeg::FromStable =>
+				self.final_output =
+					Some(output.saturating_add(self.direct_output.unwrap_or_default())),
 		}
 	}
 
 	fn swap_amount(&self, direction: SwapLeg) -> Option<AssetAmount> {
 		match direction {
-			SwapLeg::ToStable => Some(self.input_amount()),
+			SwapLeg::ToStable => Some(
+				self.input_amount().saturating_sub(self.direct_input_amount.unwrap_or_default()),
+			),
 			SwapLeg::FromStable => self.stable_amount,
 		}
 	}
@@ -202,6 +251,13 @@ impl<T: Config> Get<T::Amount> for DefaultBrokerBond<T> {
 	}
 }
 
+pub struct DefaultBrokerCommissionBounds;
+impl Get<BrokerCommissionBounds> for DefaultBrokerCommissionBounds {
+	fn get() -> BrokerCommissionBounds {
+		BrokerCommissionBounds { min_bps: 0, max_bps: 1000 }
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct SwapLegInfo {
 	pub swap_id: SwapId,
@@ -257,7 +313,8 @@ pub enum BatchExecutionError<T: Config> {
 #[derive(DebugNoBound)]
 struct BatchExecutionOutcomes<T: Config> {
 	successful_swaps: Vec<SwapState<T>>,
-	failed_swaps: Vec<Swap<T>>,
+	/// Failed swaps, along with the reason to give should they be refunded.
+	failed_swaps: Vec<(Swap<T>, SwapRefundReason)>,
 }
 
 /// This impl is never used. This is purely used to satisfy trait requirement
@@ -293,6 +350,7 @@ impl DcaState {
 	fn create_with_first_chunk(
 		input_amount: AssetAmount,
 		params: Option<DcaParameters>,
+		swap_delay: BlockNumber,
 	) -> (DcaState, AssetAmount) {
 		let mut state = DcaState {
 			status: DcaStatus::ChunkToBeScheduled,
@@ -300,7 +358,7 @@ impl DcaState {
 			remaining_chunks: params.as_ref().map(|p| p.number_of_chunks).unwrap_or(1),
 			// Chunk interval won't be used for non-DCA swaps but seems nicer to
 			// set a reasonable default than unwrap Option when it is needed:
-			chunk_interval: params.as_ref().map(|p| p.chunk_interval).unwrap_or(SWAP_DELAY_BLOCKS),
+			chunk_interval: params.as_ref().map(|p| p.chunk_interval).unwrap_or(swap_delay),
 			accumulated_output_amount: 0,
 			network_fee_collected: 0,
 			accumulated_stable_amount: 0,
@@ -349,6 +407,67 @@ impl DcaState {
 			None
 		}
 	}
+
+	// Moves remaining input into the chunk being prepared so that it reaches `target` (if enough
+	// input remains). If no input remains afterwards, the request completes with this chunk.
+	fn enlarge_chunk(
+		&mut self,
+		chunk_input_amount: AssetAmount,
+		target: AssetAmount,
+	) -> AssetAmount {
+		let extra_amount =
+			target.saturating_sub(chunk_input_amount).min(self.remaining_input_amount);
+
+		self.remaining_input_amount.saturating_reduce(extra_amount);
+		if self.remaining_input_amount.is_zero() {
+			self.remaining_chunks = 0;
+		}
+
+		chunk_input_amount.saturating_add(extra_amount)
+	}
+}
+
+/// The stage of a user swap request, as reported by [SwapStatus].
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub enum SwapRequestStatus {
+	/// The first chunk is scheduled but has not been executed yet.
+	Scheduled,
+	/// Chunk number `chunk` (1-based) out of `total_chunks` is scheduled for execution.
+	Executing { chunk: u32, total_chunks: u32 },
+	/// All chunks have been executed and the output is being egressed.
+	AwaitingEgress { egress_id: Option<EgressId> },
+	/// All chunks have been executed and the output was credited to an account on chain.
+	Credited,
+	/// The remaining input of the request has been refunded.
+	Refunded,
+}
+
+/// The current state of a user swap request along with the amounts swapped and fees taken so far.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct SwapStatus {
+	pub status: SwapRequestStatus,
+	pub input_asset: Asset,
+	pub output_asset: Asset,
+	pub input_amount: AssetAmount,
+	/// The part of the input amount that has been swapped so far.
+	pub swapped_input_amount: AssetAmount,
+	/// The total amount of the stable asset swapped through, if the swap has two legs.
+	pub intermediate_amount: AssetAmount,
+	pub output_amount: AssetAmount,
+	pub network_fee: AssetAmount,
+	pub broker_fee: AssetAmount,
+}
+
+/// An update to the remaining chunks of a DCA swap request that is already in flight.
+#[derive(
+	Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Serialize, Deserialize,
+)]
+pub enum DcaUpdate {
+	/// Cancel all chunks that have not been scheduled yet and refund their input amount. The
+	/// chunk that is already scheduled (if any) will still be executed.
+	CancelRemainingChunks,
+	/// Change the interval (in blocks) between the remaining chunks.
+	SetChunkInterval { chunk_interval: u32 },
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -379,6 +498,100 @@ enum MinFeePolicy {
 	NotEnforced,
 }
 
+/// What happens to new swap requests that would exceed the swap volume limit of their input
+/// asset.
+#[derive(
+	Clone,
+	Copy,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub enum SwapVolumeLimitAction {
+	/// Delay the execution of the swap until there is room within the limit.
+	Queue,
+	/// Refund the swap if it has refund parameters, otherwise queue it.
+	Refund,
+}
+
+/// A cap on the total input amount of swaps from an asset within a rolling window of blocks.
+#[derive(
+	Clone,
+	Copy,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub struct SwapVolumeLimit {
+	pub max_volume: AssetAmount,
+	pub window_blocks: u32,
+	pub on_exceeded: SwapVolumeLimitAction,
+}
+
+/// The range of total broker and affiliate commission (in bps) that swaps are allowed to carry.
+#[derive(
+	Clone,
+	Copy,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub struct BrokerCommissionBounds {
+	pub min_bps: BasisPoints,
+	pub max_bps: BasisPoints,
+}
+
+impl BrokerCommissionBounds {
+	pub fn is_valid(&self) -> bool {
+		self.min_bps <= self.max_bps && self.max_bps <= MAX_BASIS_POINTS
+	}
+}
+
+/// Parameters that a broker can register once and that are then used for deposit channels opened
+/// through [Pallet::request_swap_deposit_address_with_broker_defaults] whenever the corresponding
+/// parameter is omitted.
+#[derive(
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub struct BrokerChannelDefaults {
+	pub broker_commission: BasisPoints,
+	pub retry_duration: BlockNumber,
+	pub boost_fee: BasisPoints,
+	pub dca_parameters: Option<DcaParameters>,
+}
+
+enum SwapVolumeCheck<BlockNumber> {
+	Proceed { delay: BlockNumber },
+	Refund,
+}
+
 #[derive(Clone, RuntimeDebugNoBound, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T, I))]
 pub enum PalletConfigUpdate<T: Config> {
@@ -407,6 +620,35 @@ pub enum PalletConfigUpdate<T: Config> {
 	/// Set the minimum network fee in USDC that will be used just for internal swaps (credit
 	/// on-chain swaps)
 	SetInternalSwapMinimumNetworkFee { min_fee: AssetAmount },
+	/// Set how far into the future (in blocks) the execution of swaps from a deposit channel can
+	/// be scheduled.
+	SetMaxSwapSchedulingHorizon { blocks: BlockNumber },
+	/// Set the maximum price impact (in bps) that a single swap may cause in the pool of the given
+	/// asset. Swaps that are expected to exceed it are split into DCA chunks. Setting to `None`
+	/// removes the limit.
+	SetMaxSwapPriceImpact { asset: Asset, max_impact: Option<BasisPoints> },
+	/// Set the discount on the network fee for swaps submitted through the given broker. A
+	/// discount of 20% means that the broker's swaps pay 80% of the regular network fee. Setting
+	/// to zero removes the discount.
+	SetBrokerNetworkFeeDiscount { broker_id: T::AccountId, discount: Permill },
+	/// Enable or disable adaptive sizing of DCA chunks. When enabled, each chunk is made as large
+	/// as the price impact limits of the pools involved allow, so that requests may finish in
+	/// fewer chunks than requested.
+	SetAdaptiveDca { enabled: bool },
+	/// Set the limit on the total input volume of swaps from the given asset within a rolling
+	/// window. Setting to `None` removes the limit.
+	SetSwapVolumeLimit { asset: Asset, limit: Option<SwapVolumeLimit> },
+	/// Set the minimum and maximum total broker commission (including affiliates) allowed when
+	/// opening deposit channels and encoding or witnessing vault swaps.
+	SetBrokerCommissionBounds { bounds: BrokerCommissionBounds },
+	/// Override the broker commission bounds for swaps from the given asset. Setting to `None`
+	/// removes the override, so that the global bounds apply.
+	SetBrokerCommissionBoundsForAsset { asset: Asset, bounds: Option<BrokerCommissionBounds> },
+	/// Set the share of the network fee of referred swaps that is credited to the referrer.
+	SetReferralRewardShare { share: Permill },
+	/// Set the number of blocks a swap waits before it is executed. This is also the default
+	/// (and minimum) interval between DCA chunks.
+	SetSwapDelay { blocks: BlockNumber },
 }
 
 impl_pallet_safe_mode! {
@@ -493,10 +735,21 @@ pub mod pallet {
 
 		type ChannelIdAllocator: ChannelIdAllocator;
 
+		/// Opens and closes the deposit channels of private Ethereum broker channels.
+		type EthereumPrivateChannels: PrivateChannelManager<AccountId = Self::AccountId>;
+
+		/// Opens and closes the deposit channels of private Arbitrum broker channels.
+		type ArbitrumPrivateChannels: PrivateChannelManager<AccountId = Self::AccountId>;
+
 		type Bonder: Bonding<
 			AccountId = <Self as frame_system::Config>::AccountId,
 			Amount = <Self as Chainflip>::Amount,
 		>;
+
+		/// Adds the output of swaps into FLIP to the funds of state chain accounts.
+		type FundAccountFromSwap: FundAccountFromSwap<
+			AccountId = <Self as frame_system::Config>::AccountId,
+		>;
 	}
 
 	#[pallet::pallet]
@@ -508,12 +761,22 @@ pub mod pallet {
 	pub(super) type SwapRequests<T: Config> =
 		StorageMap<_, Twox64Concat, SwapRequestId, SwapRequest<T>>;
 
+	/// The broker through which a (user) swap request was submitted, if any.
+	#[pallet::storage]
+	pub type SwapRequestBrokers<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, T::AccountId, OptionQuery>;
+
 	/// Scheduled Swaps
 	#[pallet::storage]
 	#[pallet::getter(fn swap_queue)]
 	pub type SwapQueue<T: Config> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<Swap<T>>, ValueQuery>;
 
+	/// The block at which each swap in [SwapQueue] is scheduled for execution.
+	#[pallet::storage]
+	pub type ScheduledSwapBlocks<T: Config> =
+		StorageMap<_, Twox64Concat, SwapId, BlockNumberFor<T>, OptionQuery>;
+
 	/// SwapId Counter
 	#[pallet::storage]
 	pub type SwapIdCounter<T: Config> = StorageValue<_, SwapId, ValueQuery>;
@@ -548,6 +811,11 @@ pub mod pallet {
 	pub type SwapRetryDelay<T: Config> =
 		StorageValue<_, BlockNumberFor<T>, ValueQuery, DefaultSwapRetryDelay<T>>;
 
+	/// Number of blocks in the future a swap is scheduled for.
+	#[pallet::storage]
+	pub type SwapDelayBlocks<T> =
+		StorageValue<_, BlockNumber, ValueQuery, ConstU32<SWAP_DELAY_BLOCKS>>;
+
 	/// Max allowed value for the number of blocks to keep retrying a swap before it is refunded
 	#[pallet::storage]
 	pub type MaxSwapRetryDurationBlocks<T> =
@@ -574,6 +842,19 @@ pub mod pallet {
 	pub type BrokerPrivateBtcChannels<T: Config> =
 		StorageMap<_, Identity, T::AccountId, ChannelId, OptionQuery>;
 
+	/// The private channels of brokers on EVM chains. The channel id determines the family of
+	/// deposit addresses (one per asset) reserved for the broker on that chain.
+	#[pallet::storage]
+	pub type BrokerPrivateEvmChannels<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Twox64Concat,
+		ForeignChain,
+		ChannelId,
+		OptionQuery,
+	>;
+
 	/// Associates for a given broker an affiliate broker account with short id (u8) so that
 	/// it can be used in place of the full account id in order to save space (e.g. in UTXO encoding
 	/// for BTC)
@@ -601,6 +882,16 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The nonce to be included in the next payload signed by an affiliate, used to prevent
+	/// replaying signed affiliate actions.
+	#[pallet::storage]
+	pub type AffiliateNonces<T: Config> = StorageMap<_, Identity, T::AccountId, u32, ValueQuery>;
+
+	/// The nonce to be included in the next DCA update payload signed by an external refund
+	/// address, used to prevent replaying signed DCA updates.
+	#[pallet::storage]
+	pub type DcaUpdateNonces<T: Config> = StorageMap<_, Identity, EthereumAddress, u32, ValueQuery>;
+
 	/// The bond for a broker to open a private channel.
 	#[pallet::storage]
 	pub type BrokerBond<T: Config> = StorageValue<_, T::Amount, ValueQuery, DefaultBrokerBond<T>>;
@@ -626,6 +917,143 @@ pub mod pallet {
 	pub type VaultSwapMinimumBrokerFee<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, BasisPoints, ValueQuery>;
 
+	/// The block at which swaps from a scheduled deposit channel are executed, keyed by the source
+	/// chain and channel id.
+	#[pallet::storage]
+	pub type ScheduledChannelSwaps<T: Config> =
+		StorageMap<_, Twox64Concat, (ForeignChain, ChannelId), BlockNumberFor<T>, OptionQuery>;
+
+	/// Scheduled deposit channels, keyed by the block at which their schedule elapses. Used to
+	/// clean up [ScheduledChannelSwaps].
+	#[pallet::storage]
+	pub type ScheduledChannelSwapsQueue<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<(ForeignChain, ChannelId)>, ValueQuery>;
+
+	/// Whether DCA chunks are sized adaptively based on the price impact limits of the pools.
+	#[pallet::storage]
+	pub type AdaptiveDcaEnabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Limits on the total input volume of swaps from an asset within a rolling window.
+	#[pallet::storage]
+	pub type SwapVolumeLimits<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, SwapVolumeLimit, OptionQuery>;
+
+	/// The total input amount of recent swap requests from assets with a volume limit, per block
+	/// from which they count towards the limit, in ascending order of block.
+	#[pallet::storage]
+	pub type SwapVolumeHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		Asset,
+		BoundedVec<(BlockNumberFor<T>, AssetAmount), ConstU32<MAX_SWAP_VOLUME_HISTORY_BLOCKS>>,
+		ValueQuery,
+	>;
+
+	/// The allowed range of total broker commission, unless overridden for the source asset.
+	#[pallet::storage]
+	pub type BrokerCommissionLimits<T: Config> =
+		StorageValue<_, BrokerCommissionBounds, ValueQuery, DefaultBrokerCommissionBounds>;
+
+	/// Per-asset overrides of [BrokerCommissionLimits], keyed by source asset.
+	#[pallet::storage]
+	pub type AssetBrokerCommissionLimits<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, BrokerCommissionBounds, OptionQuery>;
+
+	/// The accounts that registered each referral code.
+	#[pallet::storage]
+	pub type ReferralCodes<T: Config> =
+		StorageMap<_, Twox64Concat, ReferralCode, T::AccountId, OptionQuery>;
+
+	/// The referral code registered by an account, if any.
+	#[pallet::storage]
+	pub type AccountReferralCodes<T: Config> =
+		StorageMap<_, Identity, T::AccountId, ReferralCode, OptionQuery>;
+
+	/// The share of the network fee paid by referred swaps that is credited to the referrer.
+	#[pallet::storage]
+	pub type ReferralRewardShare<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// Referral rewards (in the stable asset) that have accrued to a referrer and not yet been
+	/// claimed.
+	#[pallet::storage]
+	pub type ReferralRewards<T: Config> =
+		StorageMap<_, Identity, T::AccountId, AssetAmount, ValueQuery>;
+
+	/// The referrer of swaps from a deposit channel, keyed by the source chain and channel id.
+	#[pallet::storage]
+	pub type ChannelReferrers<T: Config> =
+		StorageMap<_, Twox64Concat, (ForeignChain, ChannelId), T::AccountId, OptionQuery>;
+
+	/// The maximum slippage (in bps) relative to the pools' time-weighted average price allowed for
+	/// swaps from a deposit channel, keyed by the source chain and channel id.
+	#[pallet::storage]
+	pub type ChannelTwapSlippage<T: Config> =
+		StorageMap<_, Twox64Concat, (ForeignChain, ChannelId), BasisPoints, OptionQuery>;
+
+	/// Deposit channels keyed by the block at which their [ChannelReferrers] and
+	/// [ChannelTwapSlippage] entries are removed.
+	#[pallet::storage]
+	pub type ChannelParameterExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<(ForeignChain, ChannelId)>, ValueQuery>;
+
+	/// The referrer of a (user) swap request, if any.
+	#[pallet::storage]
+	pub type SwapRequestReferrers<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, T::AccountId, OptionQuery>;
+
+	/// The maximum slippage (in bps) relative to the pools' time-weighted average price allowed for
+	/// a (user) swap request, if any.
+	#[pallet::storage]
+	pub type SwapRequestTwapSlippage<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, BasisPoints, OptionQuery>;
+
+	/// CCM to attach to refunds of a (user) swap request, if any.
+	#[pallet::storage]
+	pub type SwapRequestRefundCcm<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, CcmDepositMetadata, OptionQuery>;
+
+	/// The origin of an account funding swap request, carried over to the reverse swap if the
+	/// account can't be funded.
+	#[pallet::storage]
+	pub type FundingSwapOrigins<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, SwapOrigin<T::AccountId>, OptionQuery>;
+
+	/// Default deposit channel parameters registered by a broker.
+	#[pallet::storage]
+	pub type BrokerChannelDefaultParameters<T: Config> =
+		StorageMap<_, Identity, T::AccountId, BrokerChannelDefaults, OptionQuery>;
+
+	/// Discount on the network fee for swaps submitted through a broker.
+	#[pallet::storage]
+	pub type BrokerNetworkFeeDiscounts<T: Config> =
+		StorageMap<_, Identity, T::AccountId, Permill, ValueQuery>;
+
+	/// The status of user swap requests. Kept for [SWAP_STATUS_RETENTION_BLOCKS] after the request
+	/// has completed.
+	#[pallet::storage]
+	pub type SwapStatuses<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, SwapStatus, OptionQuery>;
+
+	/// Statuses of completed swap requests, keyed by the block at which they should be removed.
+	#[pallet::storage]
+	pub type SwapStatusExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<SwapRequestId>, ValueQuery>;
+
+	/// Max number of blocks into the future that the execution of swaps can be scheduled.
+	#[pallet::storage]
+	pub type MaxSwapSchedulingHorizonBlocks<T> = StorageValue<
+		_,
+		BlockNumber,
+		ValueQuery,
+		ConstU32<DEFAULT_MAX_SWAP_SCHEDULING_HORIZON_BLOCKS>,
+	>;
+
+	/// Maximum price impact (in bps) that a single swap may cause in the pool of an asset. Swaps
+	/// that are expected to exceed it are split into (more) DCA chunks.
+	#[pallet::storage]
+	pub type MaxSwapPriceImpact<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, BasisPoints, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -674,6 +1102,19 @@ pub mod pallet {
 			swap_id: SwapId,
 			execute_at: BlockNumberFor<T>,
 		},
+		/// The size of the next chunk of a DCA swap request was chosen adaptively.
+		DcaChunkSized {
+			swap_request_id: SwapRequestId,
+			chunk_input_amount: AssetAmount,
+			remaining_input_amount: AssetAmount,
+			remaining_chunks: u32,
+		},
+		/// A swap request would have exceeded the swap volume limit of its input asset.
+		SwapVolumeLimitReached {
+			swap_request_id: SwapRequestId,
+			asset: Asset,
+			action: SwapVolumeLimitAction,
+		},
 		/// A swap has been executed.
 		SwapExecuted {
 			swap_request_id: SwapRequestId,
@@ -683,6 +1124,7 @@ pub mod pallet {
 			// this amount excludes all fees (e.g. network fee, broker fee, etc.)
 			input_amount: AssetAmount,
 			network_fee: AssetAmount,
+			network_fee_discount: AssetAmount,
 			broker_fee: AssetAmount,
 			intermediate_amount: Option<AssetAmount>,
 			output_amount: AssetAmount,
@@ -701,6 +1143,7 @@ pub mod pallet {
 			asset: Asset,
 			amount: AssetAmount,
 			egress_fee: (AssetAmount, Asset),
+			refund_reason: SwapRefundReason,
 		},
 		/// A broker fee withdrawal has been requested.
 		WithdrawalRequested {
@@ -735,6 +1178,7 @@ pub mod pallet {
 			asset: Asset,
 			amount: AssetAmount,
 			reason: DispatchError,
+			refund_reason: SwapRefundReason,
 		},
 		PrivateBrokerChannelOpened {
 			broker_id: T::AccountId,
@@ -744,6 +1188,16 @@ pub mod pallet {
 			broker_id: T::AccountId,
 			channel_id: ChannelId,
 		},
+		PrivateEvmBrokerChannelOpened {
+			broker_id: T::AccountId,
+			chain: ForeignChain,
+			channel_id: ChannelId,
+		},
+		PrivateEvmBrokerChannelClosed {
+			broker_id: T::AccountId,
+			chain: ForeignChain,
+			channel_id: ChannelId,
+		},
 		AffiliateRegistration {
 			broker_id: T::AccountId,
 			short_id: AffiliateShortId,
@@ -763,6 +1217,7 @@ pub mod pallet {
 			account_id: T::AccountId,
 			asset: Asset,
 			amount: AssetAmount,
+			refund_reason: SwapRefundReason,
 		},
 		PalletConfigUpdated {
 			update: PalletConfigUpdate<T>,
@@ -771,6 +1226,83 @@ pub mod pallet {
 			broker_id: T::AccountId,
 			minimum_fee_bps: BasisPoints,
 		},
+		/// The remaining chunks of a DCA swap request have been updated.
+		DcaSwapRequestUpdated {
+			swap_request_id: SwapRequestId,
+			updated_by: T::AccountId,
+			update: DcaUpdate,
+		},
+		/// A swap request has been cancelled by its broker before any of it was executed.
+		SwapRequestCancelled {
+			swap_request_id: SwapRequestId,
+			broker_id: T::AccountId,
+		},
+		/// An affiliate has updated its withdrawal address.
+		AffiliateWithdrawalAddressUpdated {
+			broker_id: T::AccountId,
+			affiliate_id: T::AccountId,
+			withdrawal_address: EthereumAddress,
+		},
+		/// Swaps from a deposit channel will not be executed before the given block.
+		SwapDepositChannelScheduled {
+			source_chain: ForeignChain,
+			channel_id: ChannelId,
+			execute_at: BlockNumberFor<T>,
+		},
+		/// The output of swaps from a deposit channel is split between the given addresses.
+		SwapDepositChannelOutputSplit {
+			source_chain: ForeignChain,
+			channel_id: ChannelId,
+			destination_addresses: Vec<(EncodedAddress, BasisPoints)>,
+		},
+		ReferralCodeRegistered {
+			referral_code: ReferralCode,
+			account_id: T::AccountId,
+		},
+		/// Swaps from a deposit channel were referred by the given account.
+		SwapDepositChannelReferred {
+			source_chain: ForeignChain,
+			channel_id: ChannelId,
+			referrer: T::AccountId,
+		},
+		/// The output of swaps from a deposit channel must not be lower than the output at the
+		/// pools' time-weighted average price by more than the given slippage.
+		SwapDepositChannelTwapSlippageSet {
+			source_chain: ForeignChain,
+			channel_id: ChannelId,
+			max_slippage_bps_vs_twap: BasisPoints,
+		},
+		ReferralRewardsClaimed {
+			account_id: T::AccountId,
+			amount: AssetAmount,
+		},
+		BrokerChannelDefaultsSet {
+			broker_id: T::AccountId,
+			defaults: Option<BrokerChannelDefaults>,
+		},
+		/// Swaps from a deposit channel are into FLIP, which is added to the funds of the given
+		/// account.
+		AccountFundingDepositAddressReady {
+			deposit_address: EncodedAddress,
+			source_asset: Asset,
+			channel_id: ChannelId,
+			account_id: T::AccountId,
+			broker_id: T::AccountId,
+			broker_commission_rate: BasisPoints,
+			source_chain_expiry_block: <AnyChain as Chain>::ChainBlockNumber,
+			boost_fee: BasisPoints,
+			channel_opening_fee: T::Amount,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+		},
+		/// The output of a swap was sent to the State Chain Gateway, and will be added to the
+		/// funds of the given account once the transfer has been witnessed.
+		AccountFundedFromSwap {
+			swap_request_id: SwapRequestId,
+			account_id: T::AccountId,
+			amount: AssetAmount,
+		},
 	}
 	#[pallet::error]
 	pub enum Error<T> {
@@ -788,7 +1320,7 @@ pub mod pallet {
 		WithdrawalsDisabled,
 		/// Broker registration is disabled due to Safe Mode.
 		BrokerRegistrationDisabled,
-		/// Broker commission bps is limited to 1000 points.
+		/// The broker commission is above the maximum allowed for the source asset.
 		BrokerCommissionBpsTooHigh,
 		/// Brokers should withdraw their earned fees before deregistering.
 		EarnedFeesNotWithdrawn,
@@ -798,13 +1330,19 @@ pub mod pallet {
 		ZeroBuyIntervalNotAllowed,
 		/// Setting the swap retry delay to zero is not allowed.
 		ZeroSwapRetryDelayNotAllowed,
+		/// The swap retry delay is above the max allowed.
+		SwapRetryDelayTooHigh,
+		/// The swap delay must be non-zero and no greater than the max allowed.
+		InvalidSwapDelay,
+		/// The max swap scheduling horizon must be non-zero and no greater than the max allowed.
+		InvalidMaxSwapSchedulingHorizon,
 		/// Setting the max swap request duration to less than the swap delay is not allowed.
 		MaxSwapRequestDurationTooShort,
 		/// Swap Retry duration is set above the max allowed.
 		RetryDurationTooHigh,
 		/// The number of DCA chunks must be greater than 0.
 		ZeroNumberOfChunksNotAllowed,
-		/// The chunk interval must be greater than the swap delay (2).
+		/// The chunk interval must be at least the swap delay.
 		ChunkIntervalTooLow,
 		/// The total duration of a DCA swap request must be less then the max allowed.
 		SwapRequestDurationTooLong,
@@ -836,6 +1374,53 @@ pub mod pallet {
 		/// The affiliate has not withdrawn their earned fees. This is a pre-requisite for
 		/// deregistration of a broker.
 		AffiliateEarnedFeesNotWithdrawn,
+		/// The swap request does not exist or has already completed.
+		SwapRequestNotFound,
+		/// Only the broker of a swap request, the owner of its refund account or (with a signed
+		/// payload) its EVM refund address can update it.
+		NotAuthorisedToUpdateSwapRequest,
+		/// The swap request has no chunks left that have not been scheduled yet.
+		NoRemainingDcaChunks,
+		/// The swap request cannot be refunded because it has no refund parameters.
+		MissingRefundParameters,
+		/// The swap request cannot be cancelled because (some of) it has already been executed.
+		SwapExecutionAlreadyStarted,
+		/// The scheduled execution block must be later than the swap delay allows for.
+		ScheduledExecutionTooSoon,
+		/// The scheduled execution block is beyond the max allowed scheduling horizon.
+		ScheduledExecutionTooFarInFuture,
+		/// The price impact limit must be non-zero, at most 100% and can't be set for the stable
+		/// asset.
+		InvalidPriceImpactLimit,
+		/// The signature was not produced by the affiliate's withdrawal address.
+		InvalidAffiliateSignature,
+		/// Private EVM channels can only be opened on Ethereum and Arbitrum.
+		UnsupportedPrivateChannelChain,
+		/// The swap volume limit must have a non-zero volume and window.
+		InvalidSwapVolumeLimit,
+		/// The output must be split between at least two addresses, each receiving a non-zero
+		/// share, with the shares adding up to 100%.
+		InvalidOutputSplit,
+		/// The broker commission is below the minimum required for the source asset.
+		BrokerCommissionBpsTooLow,
+		/// The minimum broker commission must not exceed the maximum, which must not exceed 100%.
+		InvalidBrokerCommissionBounds,
+		/// The referral code has already been registered.
+		ReferralCodeAlreadyRegistered,
+		/// The account has already registered a referral code.
+		AccountAlreadyHasReferralCode,
+		/// No account has registered the given referral code.
+		UnknownReferralCode,
+		/// The referrer of a swap cannot be the broker that submits it.
+		SelfReferralNotAllowed,
+		/// There are no referral rewards to claim.
+		NoReferralRewards,
+		/// The maximum slippage relative to the TWAP must be greater than zero and at most 100%.
+		InvalidTwapSlippage,
+		/// A channel parameter was omitted, but the broker has not registered defaults.
+		NoBrokerChannelDefaults,
+		/// The signature was not produced by the EVM refund address of the swap request.
+		InvalidDcaUpdateSignature,
 	}
 
 	#[pallet::genesis_config]
@@ -860,6 +1445,27 @@ pub mod pallet {
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
 			let mut weight_used: Weight = T::DbWeight::get().reads(1);
+
+			// Swaps from channels whose schedule has elapsed are executed without delay:
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			for channel in ScheduledChannelSwapsQueue::<T>::take(current_block) {
+				weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+				ScheduledChannelSwaps::<T>::remove(channel);
+			}
+
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			for swap_request_id in SwapStatusExpiries::<T>::take(current_block) {
+				weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+				SwapStatuses::<T>::remove(swap_request_id);
+			}
+
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			for channel in ChannelParameterExpiries::<T>::take(current_block) {
+				weight_used.saturating_accrue(T::DbWeight::get().writes(2));
+				ChannelReferrers::<T>::remove(channel);
+				ChannelTwapSlippage::<T>::remove(channel);
+			}
+
 			let interval = FlipBuyInterval::<T>::get();
 			if interval.is_zero() {
 				log::debug!("Flip buy interval is zero, skipping.")
@@ -882,6 +1488,9 @@ pub mod pallet {
 		/// Execute all swaps in the SwapQueue
 		fn on_finalize(current_block: BlockNumberFor<T>) {
 			let swaps_to_execute = SwapQueue::<T>::take(current_block);
+			for swap in &swaps_to_execute {
+				ScheduledSwapBlocks::<T>::remove(swap.swap_id);
+			}
 			let retry_block = current_block + max(SwapRetryDelay::<T>::get(), 1u32.into());
 
 			if !T::SafeMode::get().swaps_enabled {
@@ -900,13 +1509,13 @@ pub mod pallet {
 				Self::process_swap_outcome(swap);
 			}
 
-			for swap in failed_swaps {
+			for (swap, refund_reason) in failed_swaps {
 				match swap.refund_params {
 					Some(ref params)
 						if BlockNumberFor::<T>::from(params.refund_block) < retry_block =>
 					{
 						// Reached refund block, process refund:
-						Self::refund_failed_swap(swap);
+						Self::refund_failed_swap(swap, refund_reason);
 					},
 					_ => {
 						// Either refund parameters not set, or refund block not
@@ -1004,7 +1613,7 @@ pub mod pallet {
 		#[pallet::weight(<T as frame_system::Config>::SystemWeightInfo::set_storage(updates.len() as u32))]
 		pub fn update_pallet_config(
 			origin: OriginFor<T>,
-			updates: BoundedVec<PalletConfigUpdate<T>, ConstU32<10>>,
+			updates: BoundedVec<PalletConfigUpdate<T>, ConstU32<20>>,
 		) -> DispatchResult {
 			T::EnsureGovernance::ensure_origin(origin)?;
 
@@ -1018,6 +1627,10 @@ pub mod pallet {
 							delay != BlockNumberFor::<T>::zero(),
 							Error::<T>::ZeroSwapRetryDelayNotAllowed
 						);
+						ensure!(
+							delay <= MAX_SWAP_RETRY_DELAY_BLOCKS.into(),
+							Error::<T>::SwapRetryDelayTooHigh
+						);
 						SwapRetryDelay::<T>::set(delay);
 					},
 					PalletConfigUpdate::FlipBuyInterval { interval } => {
@@ -1032,7 +1645,7 @@ pub mod pallet {
 					},
 					PalletConfigUpdate::SetMaxSwapRequestDuration { blocks } => {
 						ensure!(
-							blocks >= SWAP_DELAY_BLOCKS,
+							blocks >= SwapDelayBlocks::<T>::get(),
 							Error::<T>::MaxSwapRequestDurationTooShort
 						);
 						MaxSwapRequestDurationBlocks::<T>::set(blocks);
@@ -1052,6 +1665,60 @@ pub mod pallet {
 					PalletConfigUpdate::SetInternalSwapMinimumNetworkFee { min_fee } => {
 						InternalSwapMinimumNetworkFee::<T>::set(min_fee);
 					},
+					PalletConfigUpdate::SetMaxSwapSchedulingHorizon { blocks } => {
+						ensure!(
+							(1..=MAX_SWAP_SCHEDULING_HORIZON_BLOCKS).contains(&blocks),
+							Error::<T>::InvalidMaxSwapSchedulingHorizon
+						);
+						MaxSwapSchedulingHorizonBlocks::<T>::set(blocks);
+					},
+					PalletConfigUpdate::SetMaxSwapPriceImpact { asset, max_impact } => {
+						if let Some(max_impact) = max_impact {
+							ensure!(
+								asset != STABLE_ASSET &&
+									max_impact > 0 && max_impact <= MAX_BASIS_POINTS,
+								Error::<T>::InvalidPriceImpactLimit
+							);
+						}
+						MaxSwapPriceImpact::<T>::set(asset, max_impact);
+					},
+					PalletConfigUpdate::SetBrokerNetworkFeeDiscount { ref broker_id, discount } => {
+						BrokerNetworkFeeDiscounts::<T>::set(broker_id, discount);
+					},
+					PalletConfigUpdate::SetAdaptiveDca { enabled } => {
+						AdaptiveDcaEnabled::<T>::set(enabled);
+					},
+					PalletConfigUpdate::SetSwapVolumeLimit { asset, limit } => {
+						if let Some(limit) = limit {
+							ensure!(
+								limit.max_volume > 0 && limit.window_blocks > 0,
+								Error::<T>::InvalidSwapVolumeLimit
+							);
+						} else {
+							SwapVolumeHistory::<T>::remove(asset);
+						}
+						SwapVolumeLimits::<T>::set(asset, limit);
+					},
+					PalletConfigUpdate::SetBrokerCommissionBounds { bounds } => {
+						ensure!(bounds.is_valid(), Error::<T>::InvalidBrokerCommissionBounds);
+						BrokerCommissionLimits::<T>::set(bounds);
+					},
+					PalletConfigUpdate::SetBrokerCommissionBoundsForAsset { asset, bounds } => {
+						if let Some(bounds) = bounds {
+							ensure!(bounds.is_valid(), Error::<T>::InvalidBrokerCommissionBounds);
+						}
+						AssetBrokerCommissionLimits::<T>::set(asset, bounds);
+					},
+					PalletConfigUpdate::SetReferralRewardShare { share } => {
+						ReferralRewardShare::<T>::set(share);
+					},
+					PalletConfigUpdate::SetSwapDelay { blocks } => {
+						ensure!(
+							(1..=MAX_SWAP_DELAY_BLOCKS).contains(&blocks),
+							Error::<T>::InvalidSwapDelay
+						);
+						SwapDelayBlocks::<T>::set(blocks);
+					},
 				}
 				Self::deposit_event(Event::<T>::PalletConfigUpdated { update });
 			}
@@ -1068,7 +1735,8 @@ pub mod pallet {
 			let account_id = T::AccountRoleRegistry::ensure_broker(who)?;
 
 			ensure!(
-				!BrokerPrivateBtcChannels::<T>::contains_key(&account_id),
+				!BrokerPrivateBtcChannels::<T>::contains_key(&account_id) &&
+					BrokerPrivateEvmChannels::<T>::iter_prefix(&account_id).next().is_none(),
 				Error::<T>::PrivateChannelExistsForBroker
 			);
 
@@ -1097,7 +1765,11 @@ pub mod pallet {
 			// Clear the affiliate account details and affiliate id mapping.
 			// With this the broker has no longer access to the affiliate's account.
 			let _ = AffiliateAccountDetails::<T>::clear_prefix(&account_id, u32::MAX, None);
+			for affiliate_account_id in AffiliateIdMapping::<T>::iter_prefix_values(&account_id) {
+				AffiliateNonces::<T>::remove(affiliate_account_id);
+			}
 			let _ = AffiliateIdMapping::<T>::clear_prefix(&account_id, u32::MAX, None);
+			BrokerChannelDefaultParameters::<T>::remove(&account_id);
 
 			T::AccountRoleRegistry::deregister_as_broker(&account_id)?;
 
@@ -1125,112 +1797,47 @@ pub mod pallet {
 		) -> DispatchResult {
 			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
 
-			let beneficiaries = Pallet::<T>::assemble_and_validate_broker_fees(
-				broker.clone(),
+			Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
 				broker_commission,
-				affiliate_fees.clone(),
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				None,
 			)?;
 
-			let destination_address_internal =
-				T::AddressConverter::decode_and_validate_address_for_asset(
-					destination_address.clone(),
-					destination_asset,
-				)
-				.map_err(address_error_to_pallet_error::<T>)?;
+			Ok(())
+		}
 
-			// Convert the refund parameter from `EncodedAddress` into `ForeignChainAddress` type.
-			let refund_params_internal = refund_parameters.clone().try_map_address(|addr| {
-				T::AddressConverter::try_from_encoded_address(addr)
-					.map_err(|_| Error::<T>::InvalidRefundAddress)
-			})?;
+		/// Opens a private broker channel for Bitcoin vault swaps.
+		///
+		/// This requires the broker to have sufficient funds to cover the bond.
+		///
+		/// ## Events
+		///
+		/// - [PrivateBrokerChannelOpened](Event::PrivateBrokerChannelOpened)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::open_private_btc_channel())]
+		pub fn open_private_btc_channel(origin: OriginFor<T>) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
 
-			if let Some(ccm) = channel_metadata.as_ref() {
-				let destination_chain: ForeignChain = destination_asset.into();
-				ensure!(destination_chain.ccm_support(), Error::<T>::CcmUnsupportedForTargetChain);
+			ensure!(
+				!BrokerPrivateBtcChannels::<T>::contains_key(&broker_id),
+				Error::<T>::PrivateChannelExistsForBroker
+			);
 
-				let _ = T::CcmValidityChecker::check_and_decode(
-					ccm,
-					destination_asset,
-					destination_address.clone(),
-				)
-				.map_err(|e| {
-					log::warn!(
-						"Failed to open channel due to invalid CCM. Broker: {:?}, Error: {:?}",
-						broker,
-						e
-					);
-					Error::<T>::InvalidCcm
-				})?;
-			}
-
-			let (channel_id, deposit_address, expiry_height, channel_opening_fee) =
-				T::DepositHandler::request_swap_deposit_address(
-					source_asset,
-					destination_asset,
-					destination_address_internal,
-					beneficiaries.clone(),
-					broker.clone(),
-					channel_metadata.clone(),
-					boost_fee,
-					refund_params_internal,
-					dca_parameters.clone(),
-				)?;
-
-			// TODO: deduplicate this with assemble_and_validate_broker_fees
-			let affiliate_fees = affiliate_fees
-				.into_iter()
-				.filter(|beneficiary| beneficiary.bps > 0)
-				.collect::<Vec<_>>()
-				.try_into()
-				.expect("Filtering out will always fit");
-
-			Self::deposit_event(Event::<T>::SwapDepositAddressReady {
-				deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
-				destination_address,
-				source_asset,
-				destination_asset,
-				channel_id,
-				broker_id: broker,
-				broker_commission_rate: broker_commission,
-				channel_metadata,
-				source_chain_expiry_block: expiry_height,
-				boost_fee,
-				channel_opening_fee,
-				affiliate_fees,
-				refund_parameters,
-				dca_parameters,
-			});
-
-			Ok(())
-		}
-
-		/// Opens a private broker channel for Bitcoin vault swaps.
-		///
-		/// This requires the broker to have sufficient funds to cover the bond.
-		///
-		/// ## Events
-		///
-		/// - [PrivateBrokerChannelOpened](Event::PrivateBrokerChannelOpened)
-		#[pallet::call_index(12)]
-		#[pallet::weight(T::WeightInfo::open_private_btc_channel())]
-		pub fn open_private_btc_channel(origin: OriginFor<T>) -> DispatchResult {
-			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
-
-			ensure!(
-				!BrokerPrivateBtcChannels::<T>::contains_key(&broker_id),
-				Error::<T>::PrivateChannelExistsForBroker
-			);
-
-			ensure!(
-				T::FundingInfo::total_balance_of(&broker_id) >= BrokerBond::<T>::get(),
-				Error::<T>::InsufficientFunds
-			);
+			Self::ensure_sufficient_funds_for_private_channel(&broker_id)?;
 
 			let channel_id = T::ChannelIdAllocator::allocate_private_channel_id()?;
 
 			BrokerPrivateBtcChannels::<T>::insert(broker_id.clone(), channel_id);
 
-			T::Bonder::update_bond(&broker_id, BrokerBond::<T>::get());
+			Self::update_private_channel_bond(&broker_id);
 
 			Self::deposit_event(Event::<T>::PrivateBrokerChannelOpened { broker_id, channel_id });
 
@@ -1253,7 +1860,7 @@ pub mod pallet {
 				return Err(Error::<T>::NoPrivateChannelExistsForBroker.into())
 			};
 
-			T::Bonder::update_bond(&broker_id, 0u128.into());
+			Self::update_private_channel_bond(&broker_id);
 
 			Self::deposit_event(Event::<T>::PrivateBrokerChannelClosed { broker_id, channel_id });
 
@@ -1346,121 +1953,1178 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Sets the brokers personal minimum fee for vault swaps.
-		/// This minimum is used to stop encoding vault swaps with a lower broker fee.
-		/// If a swap is witnessed with a lower fee, it will be changed to the minimum.
-		///
-		/// ## Events
-		///
-		/// - [VaultSwapMinimumBrokerFeeSet](Event::VaultSwapMinimumBrokerFeeSet)
-		#[pallet::call_index(17)]
-		#[pallet::weight(T::WeightInfo::set_vault_swap_minimum_broker_fee())]
-		pub fn set_vault_swap_minimum_broker_fee(
-			origin: OriginFor<T>,
-			minimum_fee_bps: BasisPoints,
-		) -> DispatchResult {
-			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+		/// Sets the brokers personal minimum fee for vault swaps.
+		/// This minimum is used to stop encoding vault swaps with a lower broker fee.
+		/// If a swap is witnessed with a lower fee, it will be changed to the minimum.
+		///
+		/// ## Events
+		///
+		/// - [VaultSwapMinimumBrokerFeeSet](Event::VaultSwapMinimumBrokerFeeSet)
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::set_vault_swap_minimum_broker_fee())]
+		pub fn set_vault_swap_minimum_broker_fee(
+			origin: OriginFor<T>,
+			minimum_fee_bps: BasisPoints,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			ensure!(
+				minimum_fee_bps <= BrokerCommissionLimits::<T>::get().max_bps,
+				Error::<T>::BrokerCommissionBpsTooHigh
+			);
+
+			VaultSwapMinimumBrokerFee::<T>::insert(broker_id.clone(), minimum_fee_bps);
+			Self::deposit_event(Event::<T>::VaultSwapMinimumBrokerFeeSet {
+				broker_id,
+				minimum_fee_bps,
+			});
+
+			Ok(())
+		}
+
+		/// Updates the remaining chunks of a DCA swap request that is already in flight: either
+		/// cancels the chunks that have not been scheduled yet (refunding their input amount), or
+		/// changes the interval between them.
+		///
+		/// Can be called by the broker through which the swap was requested, or by the owner of
+		/// the on-chain account that the swap refunds to. If the swap refunds to an external EVM
+		/// address instead, anyone can submit the update together with a signature of that
+		/// address over the payload returned by [Pallet::dca_update_signing_payload].
+		///
+		/// ## Events
+		///
+		/// - [DcaSwapRequestUpdated](Event::DcaSwapRequestUpdated)
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::update_dca_swap_request())]
+		pub fn update_dca_swap_request(
+			origin: OriginFor<T>,
+			swap_request_id: SwapRequestId,
+			update: DcaUpdate,
+			signature: Option<EthereumSignature>,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			SwapRequests::<T>::try_mutate(swap_request_id, |maybe_request| {
+				let request = maybe_request.as_mut().ok_or(Error::<T>::SwapRequestNotFound)?;
+
+				let SwapRequestState::UserSwap { refund_params, output_action, dca_state, .. } =
+					&mut request.state
+				else {
+					return Err(Error::<T>::NotAuthorisedToUpdateSwapRequest.into())
+				};
+
+				if let Some(signature) = signature {
+					let refund_address = Self::evm_refund_address(refund_params)
+						.ok_or(Error::<T>::NotAuthorisedToUpdateSwapRequest)?;
+					Self::ensure_signed_by_refund_address(
+						swap_request_id,
+						&update,
+						refund_address,
+						&signature,
+					)?;
+				} else {
+					ensure!(
+						SwapRequestBrokers::<T>::get(swap_request_id).as_ref() == Some(&account_id) ||
+							matches!(
+								refund_params,
+								Some(RefundParametersExtended {
+									refund_destination: AccountOrAddress::InternalAccount(
+										refund_account
+									),
+									..
+								}) if *refund_account == account_id
+							),
+						Error::<T>::NotAuthorisedToUpdateSwapRequest
+					);
+				}
+
+				ensure!(dca_state.remaining_chunks > 0, Error::<T>::NoRemainingDcaChunks);
+
+				match update {
+					DcaUpdate::CancelRemainingChunks => {
+						let refund_params =
+							refund_params.as_ref().ok_or(Error::<T>::MissingRefundParameters)?;
+
+						let cancelled_amount =
+							core::mem::take(&mut dca_state.remaining_input_amount);
+						dca_state.remaining_chunks = 0;
+
+						let amount_to_refund = Self::take_refund_fee(
+							cancelled_amount,
+							request.input_asset,
+							matches!(output_action, SwapOutputAction::CreditOnChain { .. }),
+						)?;
+
+						Self::refund_to_destination(
+							swap_request_id,
+							request.input_asset,
+							amount_to_refund,
+							&refund_params.refund_destination,
+							SwapRefundReason::Cancelled,
+						);
+
+						// Only the currently scheduled chunk is left:
+						Self::update_swap_status(swap_request_id, |status| {
+							if let SwapRequestStatus::Executing { chunk, total_chunks } = status {
+								*total_chunks = *chunk;
+							}
+						});
+					},
+					DcaUpdate::SetChunkInterval { chunk_interval } => {
+						// The remaining chunks plus the one currently scheduled must fit within
+						// the same limits as a newly requested DCA swap.
+						Self::validate_dca_params(&DcaParameters {
+							number_of_chunks: dca_state.remaining_chunks.saturating_add(1),
+							chunk_interval,
+						})?;
+						dca_state.chunk_interval = chunk_interval;
+					},
+				}
+
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::<T>::DcaSwapRequestUpdated {
+				swap_request_id,
+				updated_by: account_id,
+				update,
+			});
+
+			Ok(())
+		}
+
+		/// Cancels a swap request that is still waiting in the swap queue and refunds its full
+		/// input amount (less the refund fee) to the refund destination.
+		///
+		/// Only the broker through which the swap was requested can cancel it, and only as long
+		/// as none of it has been executed yet.
+		///
+		/// ## Events
+		///
+		/// - [SwapRequestCancelled](Event::SwapRequestCancelled)
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::cancel_swap_request())]
+		pub fn cancel_swap_request(
+			origin: OriginFor<T>,
+			swap_request_id: SwapRequestId,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let request =
+				SwapRequests::<T>::get(swap_request_id).ok_or(Error::<T>::SwapRequestNotFound)?;
+
+			ensure!(
+				SwapRequestBrokers::<T>::get(swap_request_id).as_ref() == Some(&broker_id),
+				Error::<T>::NotAuthorisedToUpdateSwapRequest
+			);
+
+			let SwapRequestState::UserSwap { refund_params, dca_state, .. } = request.state else {
+				return Err(Error::<T>::NotAuthorisedToUpdateSwapRequest.into())
+			};
+
+			ensure!(refund_params.is_some(), Error::<T>::MissingRefundParameters);
+
+			let DcaStatus::ChunkScheduled(swap_id) = dca_state.status else {
+				return Err(Error::<T>::SwapExecutionAlreadyStarted.into())
+			};
+			ensure!(
+				dca_state.accumulated_output_amount.is_zero() &&
+					dca_state.accumulated_stable_amount.is_zero() &&
+					dca_state.network_fee_collected.is_zero(),
+				Error::<T>::SwapExecutionAlreadyStarted
+			);
+
+			let swap = Self::take_scheduled_swap(swap_id)
+				.ok_or(Error::<T>::SwapExecutionAlreadyStarted)?;
+
+			Self::deposit_event(Event::<T>::SwapRequestCancelled { swap_request_id, broker_id });
+
+			Self::refund_failed_swap(swap, SwapRefundReason::Cancelled);
+
+			Ok(())
+		}
+
+		/// Updates the withdrawal address of an affiliate.
+		///
+		/// The update must be authorised by a signature of the affiliate's current withdrawal
+		/// address over the payload returned by [Pallet::affiliate_signing_payload]. Anyone can
+		/// submit the signed update.
+		///
+		/// ## Events
+		///
+		/// - [AffiliateWithdrawalAddressUpdated](Event::AffiliateWithdrawalAddressUpdated)
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::update_affiliate_withdrawal_address())]
+		pub fn update_affiliate_withdrawal_address(
+			origin: OriginFor<T>,
+			broker_id: T::AccountId,
+			short_id: AffiliateShortId,
+			withdrawal_address: EthereumAddress,
+			signature: EthereumSignature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let (affiliate_id, details) = Self::ensure_signed_by_affiliate(
+				&broker_id,
+				short_id,
+				&AffiliateAction::UpdateWithdrawalAddress(withdrawal_address),
+				&signature,
+			)?;
+
+			AffiliateAccountDetails::<T>::insert(
+				&broker_id,
+				&affiliate_id,
+				AffiliateDetails { withdrawal_address, ..details },
+			);
+
+			Self::deposit_event(Event::<T>::AffiliateWithdrawalAddressUpdated {
+				broker_id,
+				affiliate_id,
+				withdrawal_address,
+			});
+
+			Ok(())
+		}
+
+		/// Triggers a withdrawal of an affiliate's earned fees to its withdrawal address.
+		///
+		/// Unlike [Pallet::affiliate_withdrawal_request], this does not require the broker: the
+		/// withdrawal is authorised by a signature of the affiliate's withdrawal address over the
+		/// payload returned by [Pallet::affiliate_signing_payload].
+		///
+		/// ## Events
+		///
+		/// - [WithdrawalRequested](Event::WithdrawalRequested)
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::signed_affiliate_withdrawal_request())]
+		pub fn signed_affiliate_withdrawal_request(
+			origin: OriginFor<T>,
+			broker_id: T::AccountId,
+			short_id: AffiliateShortId,
+			signature: EthereumSignature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(T::SafeMode::get().withdrawals_enabled, Error::<T>::WithdrawalsDisabled);
+
+			let (affiliate_id, details) = Self::ensure_signed_by_affiliate(
+				&broker_id,
+				short_id,
+				&AffiliateAction::Withdraw,
+				&signature,
+			)?;
+
+			Self::trigger_withdrawal(
+				&affiliate_id,
+				Asset::Usdc,
+				ForeignChainAddress::Eth(details.withdrawal_address),
+			)
+		}
+
+		/// Opens a private broker channel on an EVM chain (Ethereum or Arbitrum).
+		///
+		/// The channel id reserves a stable family of deposit addresses for the broker, one per
+		/// asset of the chain. Deposits to these addresses are credited to the broker's balance,
+		/// or sent to the refund address if they are rejected. Each open private channel requires
+		/// the broker bond.
+		///
+		/// ## Events
+		///
+		/// - [PrivateEvmBrokerChannelOpened](Event::PrivateEvmBrokerChannelOpened)
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::open_private_evm_channel())]
+		pub fn open_private_evm_channel(
+			origin: OriginFor<T>,
+			chain: ForeignChain,
+			refund_address: EncodedAddress,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			ensure!(
+				matches!(chain, ForeignChain::Ethereum | ForeignChain::Arbitrum),
+				Error::<T>::UnsupportedPrivateChannelChain
+			);
+
+			ensure!(
+				!BrokerPrivateEvmChannels::<T>::contains_key(&broker_id, chain),
+				Error::<T>::PrivateChannelExistsForBroker
+			);
+
+			Self::ensure_sufficient_funds_for_private_channel(&broker_id)?;
+
+			let refund_address = T::AddressConverter::try_from_encoded_address(refund_address)
+				.map_err(|_| Error::<T>::InvalidRefundAddress)?;
+			ensure!(refund_address.chain() == chain, Error::<T>::InvalidRefundAddress);
+
+			let channel_id = match chain {
+				ForeignChain::Ethereum =>
+					T::EthereumPrivateChannels::open_private_channel(&broker_id, refund_address),
+				_ => T::ArbitrumPrivateChannels::open_private_channel(&broker_id, refund_address),
+			}?;
+
+			BrokerPrivateEvmChannels::<T>::insert(&broker_id, chain, channel_id);
+
+			Self::update_private_channel_bond(&broker_id);
+
+			Self::deposit_event(Event::<T>::PrivateEvmBrokerChannelOpened {
+				broker_id,
+				chain,
+				channel_id,
+			});
+
+			Ok(())
+		}
+
+		/// Closes the broker's private channel on the given EVM chain.
+		///
+		/// Closing the channel releases the bond associated with it.
+		///
+		/// ## Events
+		///
+		/// - [PrivateEvmBrokerChannelClosed](Event::PrivateEvmBrokerChannelClosed)
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::close_private_evm_channel())]
+		pub fn close_private_evm_channel(
+			origin: OriginFor<T>,
+			chain: ForeignChain,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let channel_id = BrokerPrivateEvmChannels::<T>::take(&broker_id, chain)
+				.ok_or(Error::<T>::NoPrivateChannelExistsForBroker)?;
+
+			match chain {
+				ForeignChain::Ethereum =>
+					T::EthereumPrivateChannels::close_private_channel(channel_id),
+				ForeignChain::Arbitrum =>
+					T::ArbitrumPrivateChannels::close_private_channel(channel_id),
+				_ => log_or_panic!("Private EVM channel opened on non-EVM chain {chain:?}"),
+			}
+
+			Self::update_private_channel_bond(&broker_id);
+
+			Self::deposit_event(Event::<T>::PrivateEvmBrokerChannelClosed {
+				broker_id,
+				chain,
+				channel_id,
+			});
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address for which the execution of swaps is deferred until the
+		/// given state chain block. Deposits witnessed after that block are swapped as usual.
+		///
+		/// The execution block must be within the max swap scheduling horizon.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		/// - [SwapDepositChannelScheduled](Event::SwapDepositChannelScheduled)
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::request_scheduled_swap_deposit_address())]
+		pub fn request_scheduled_swap_deposit_address(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+			execute_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				execute_at > current_block.saturating_add(SwapDelayBlocks::<T>::get().into()),
+				Error::<T>::ScheduledExecutionTooSoon
+			);
+			ensure!(
+				execute_at <=
+					current_block
+						.saturating_add(MaxSwapSchedulingHorizonBlocks::<T>::get().into()),
+				Error::<T>::ScheduledExecutionTooFarInFuture
+			);
+
+			let channel_id = Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				None,
+			)?;
+
+			let source_chain = ForeignChain::from(source_asset);
+			ScheduledChannelSwaps::<T>::insert((source_chain, channel_id), execute_at);
+			ScheduledChannelSwapsQueue::<T>::append(execute_at, (source_chain, channel_id));
+
+			Self::deposit_event(Event::<T>::SwapDepositChannelScheduled {
+				source_chain,
+				channel_id,
+				execute_at,
+			});
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address for swaps whose output is split between several
+		/// destination addresses on the same chain. Each address receives the given share (in
+		/// basis points) of the output, and the shares must add up to 100%.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		/// - [SwapDepositChannelOutputSplit](Event::SwapDepositChannelOutputSplit)
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::request_split_swap_deposit_address())]
+		pub fn request_split_swap_deposit_address(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_addresses: BoundedVec<
+				(EncodedAddress, BasisPoints),
+				ConstU32<MAX_SPLIT_OUTPUT_ADDRESSES>,
+			>,
+			broker_commission: BasisPoints,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			ensure!(
+				destination_addresses.len() > 1 &&
+					destination_addresses.iter().all(|(_, share)| *share > 0) &&
+					destination_addresses.iter().map(|(_, share)| *share as u32).sum::<u32>() ==
+						MAX_BASIS_POINTS as u32,
+				Error::<T>::InvalidOutputSplit
+			);
+
+			let output_split = destination_addresses
+				.iter()
+				.map(|(address, share)| {
+					T::AddressConverter::decode_and_validate_address_for_asset(
+						address.clone(),
+						destination_asset,
+					)
+					.map(|address| (address, *share))
+					.map_err(address_error_to_pallet_error::<T>)
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+
+			let channel_id = Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_addresses[0].0.clone(),
+				broker_commission,
+				None,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				Some(output_split),
+			)?;
+
+			Self::deposit_event(Event::<T>::SwapDepositChannelOutputSplit {
+				source_chain: ForeignChain::from(source_asset),
+				channel_id,
+				destination_addresses: destination_addresses.into_inner(),
+			});
+
+			Ok(())
+		}
+
+		/// Register a referral code for the caller. Swaps that are referred using the code credit
+		/// the caller with a share of their network fee. Each code can only be registered once,
+		/// and each account can only register one code.
+		///
+		/// ## Events
+		///
+		/// - [ReferralCodeRegistered](Event::ReferralCodeRegistered)
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::register_referral_code())]
+		pub fn register_referral_code(
+			origin: OriginFor<T>,
+			referral_code: ReferralCode,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			ensure!(
+				!ReferralCodes::<T>::contains_key(referral_code),
+				Error::<T>::ReferralCodeAlreadyRegistered
+			);
+			ensure!(
+				!AccountReferralCodes::<T>::contains_key(&account_id),
+				Error::<T>::AccountAlreadyHasReferralCode
+			);
+
+			ReferralCodes::<T>::insert(referral_code, account_id.clone());
+			AccountReferralCodes::<T>::insert(&account_id, referral_code);
+
+			Self::deposit_event(Event::<T>::ReferralCodeRegistered { referral_code, account_id });
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address whose swaps are referred by the owner of the given
+		/// referral code. The referrer is credited with a share of the network fee of these
+		/// swaps, which does not affect the output received by the user.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		/// - [SwapDepositChannelReferred](Event::SwapDepositChannelReferred)
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::request_referred_swap_deposit_address())]
+		pub fn request_referred_swap_deposit_address(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+			referral_code: ReferralCode,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let referrer =
+				ReferralCodes::<T>::get(referral_code).ok_or(Error::<T>::UnknownReferralCode)?;
+			ensure!(referrer != broker, Error::<T>::SelfReferralNotAllowed);
+
+			let channel_id = Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				None,
+			)?;
+
+			let source_chain = ForeignChain::from(source_asset);
+			ChannelReferrers::<T>::insert((source_chain, channel_id), referrer.clone());
+			Self::schedule_channel_parameters_expiry(source_chain, channel_id);
+
+			Self::deposit_event(Event::<T>::SwapDepositChannelReferred {
+				source_chain,
+				channel_id,
+				referrer,
+			});
+
+			Ok(())
+		}
+
+		/// Credit the caller's accrued referral rewards to their balance of the stable asset, from
+		/// which they can be withdrawn.
+		///
+		/// ## Events
+		///
+		/// - [ReferralRewardsClaimed](Event::ReferralRewardsClaimed)
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::claim_referral_rewards())]
+		pub fn claim_referral_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			let amount = ReferralRewards::<T>::take(&account_id);
+			ensure!(!amount.is_zero(), Error::<T>::NoReferralRewards);
+
+			T::BalanceApi::credit_account(&account_id, STABLE_ASSET, amount);
+
+			Self::deposit_event(Event::<T>::ReferralRewardsClaimed { account_id, amount });
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address whose swaps are protected by a minimum output derived
+		/// from the pools' time-weighted average price at the time of execution, rather than (or
+		/// in addition to) the fixed minimum price in the refund parameters. Each swap (or DCA
+		/// chunk) is refunded if its output is lower than the output at the TWAP by more than
+		/// `max_slippage_bps_vs_twap`, net of fees.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		/// - [SwapDepositChannelTwapSlippageSet](Event::SwapDepositChannelTwapSlippageSet)
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::request_twap_protected_swap_deposit_address())]
+		pub fn request_twap_protected_swap_deposit_address(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+			max_slippage_bps_vs_twap: BasisPoints,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			ensure!(
+				max_slippage_bps_vs_twap > 0 && max_slippage_bps_vs_twap <= MAX_BASIS_POINTS,
+				Error::<T>::InvalidTwapSlippage
+			);
+
+			let channel_id = Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				None,
+			)?;
+
+			let source_chain = ForeignChain::from(source_asset);
+			ChannelTwapSlippage::<T>::insert((source_chain, channel_id), max_slippage_bps_vs_twap);
+			Self::schedule_channel_parameters_expiry(source_chain, channel_id);
+
+			Self::deposit_event(Event::<T>::SwapDepositChannelTwapSlippageSet {
+				source_chain,
+				channel_id,
+				max_slippage_bps_vs_twap,
+			});
+
+			Ok(())
+		}
+
+		/// Register (or, if `None`, remove) the default parameters that are used when the
+		/// broker opens a deposit channel through
+		/// [request_swap_deposit_address_with_broker_defaults](Pallet::request_swap_deposit_address_with_broker_defaults)
+		/// without specifying them.
+		///
+		/// ## Events
+		///
+		/// - [BrokerChannelDefaultsSet](Event::BrokerChannelDefaultsSet)
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::set_broker_channel_defaults())]
+		pub fn set_broker_channel_defaults(
+			origin: OriginFor<T>,
+			defaults: Option<BrokerChannelDefaults>,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			if let Some(defaults) = defaults.as_ref() {
+				ensure!(
+					defaults.broker_commission <= BrokerCommissionLimits::<T>::get().max_bps,
+					Error::<T>::BrokerCommissionBpsTooHigh
+				);
+				Self::validate_refund_params(defaults.retry_duration)?;
+				if let Some(params) = defaults.dca_parameters.as_ref() {
+					Self::validate_dca_params(params)?;
+				}
+				ensure!(defaults.boost_fee <= u8::MAX.into(), Error::<T>::BoostFeeTooHigh);
+			}
+
+			BrokerChannelDefaultParameters::<T>::set(&broker_id, defaults.clone());
+
+			Self::deposit_event(Event::<T>::BrokerChannelDefaultsSet { broker_id, defaults });
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address, using the broker's registered
+		/// [BrokerChannelDefaults] for any of `broker_commission`, `boost_fee`, `retry_duration`
+		/// and `dca_parameters` that are omitted.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::request_swap_deposit_address_with_broker_defaults())]
+		pub fn request_swap_deposit_address_with_broker_defaults(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: Option<BasisPoints>,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: Option<BasisPoints>,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_address: EncodedAddress,
+			min_price: Price,
+			retry_duration: Option<BlockNumber>,
+			dca_parameters: Option<DcaParameters>,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let defaults = BrokerChannelDefaultParameters::<T>::get(&broker);
+
+			let broker_commission = broker_commission
+				.or_else(|| defaults.as_ref().map(|defaults| defaults.broker_commission))
+				.ok_or(Error::<T>::NoBrokerChannelDefaults)?;
+			let boost_fee = boost_fee
+				.or_else(|| defaults.as_ref().map(|defaults| defaults.boost_fee))
+				.ok_or(Error::<T>::NoBrokerChannelDefaults)?;
+			let retry_duration = retry_duration
+				.or_else(|| defaults.as_ref().map(|defaults| defaults.retry_duration))
+				.ok_or(Error::<T>::NoBrokerChannelDefaults)?;
+			// Without registered defaults, omitting the DCA parameters means no DCA.
+			let dca_parameters =
+				dca_parameters.or_else(|| defaults.and_then(|defaults| defaults.dca_parameters));
+
+			Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				ChannelRefundParametersEncoded { retry_duration, refund_address, min_price },
+				dca_parameters,
+				None,
+			)?;
+
+			Ok(())
+		}
+
+		/// Request a deposit address for swaps into FLIP whose output is added to the funds of
+		/// the given state chain account, eg. to top up a validator's bond without having to
+		/// bridge to Ethereum and use the State Chain Gateway.
+		///
+		/// ## Events
+		///
+		/// - [AccountFundingDepositAddressReady](Event::AccountFundingDepositAddressReady)
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::request_account_funding_deposit_address())]
+		pub fn request_account_funding_deposit_address(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			account_id: T::AccountId,
+			broker_commission: BasisPoints,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let beneficiaries = Self::assemble_and_validate_broker_fees(
+				broker.clone(),
+				broker_commission,
+				affiliate_fees.clone(),
+				source_asset,
+			)?;
+
+			let refund_params_internal = refund_parameters.clone().try_map_address(|addr| {
+				T::AddressConverter::try_from_encoded_address(addr)
+					.map_err(|_| Error::<T>::InvalidRefundAddress)
+			})?;
+
+			let (channel_id, deposit_address, expiry_height, channel_opening_fee) =
+				T::DepositHandler::request_account_funding_deposit_address(
+					source_asset,
+					account_id.clone(),
+					beneficiaries,
+					broker.clone(),
+					boost_fee,
+					refund_params_internal,
+					dca_parameters.clone(),
+				)?;
+
+			Self::deposit_event(Event::<T>::AccountFundingDepositAddressReady {
+				deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
+				source_asset,
+				channel_id,
+				account_id,
+				broker_id: broker,
+				broker_commission_rate: broker_commission,
+				source_chain_expiry_block: expiry_height,
+				boost_fee,
+				channel_opening_fee,
+				affiliate_fees: affiliate_fees
+					.into_iter()
+					.filter(|beneficiary| beneficiary.bps > 0)
+					.collect::<Vec<_>>()
+					.try_into()
+					.expect("Filtering out will always fit"),
+				refund_parameters,
+				dca_parameters,
+			});
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		#[allow(clippy::result_unit_err)]
+		pub fn get_scheduled_swap_legs(swaps: Vec<Swap<T>>, base_asset: Asset) -> Vec<SwapLegInfo> {
+			let mut swaps: Vec<_> = swaps.into_iter().map(SwapState::new).collect();
+
+			// Can ignore the result here because we use pool price fallback below
+			let _res = Self::swap_into_stable_taking_fees(&mut swaps);
+
+			swaps
+				.into_iter()
+				.filter_map(|swap| {
+					let swap_request = SwapRequests::<T>::get(swap.swap.swap_request_id)
+						.expect("Swap request should exist");
+					let dca_state = match swap_request.state {
+						SwapRequestState::UserSwap { dca_state, .. } => Some(dca_state),
+						_ => None,
+					};
+					let remaining_chunks =
+						dca_state.as_ref().map(|dca| dca.remaining_chunks).unwrap_or(0);
+					let chunk_interval = dca_state
+						.map(|dca| dca.chunk_interval)
+						.unwrap_or(SwapDelayBlocks::<T>::get());
+
+					if swap.input_asset() == base_asset {
+						Some(SwapLegInfo {
+							swap_id: swap.swap_id(),
+							swap_request_id: swap.swap.swap_request_id,
+							base_asset,
+							// All swaps from `base_asset` have to go through the stable asset:
+							quote_asset: STABLE_ASSET,
+							side: Side::Sell,
+							amount: swap.input_amount(),
+							source_asset: None,
+							source_amount: None,
+							remaining_chunks,
+							chunk_interval,
+						})
+					} else if swap.output_asset() == base_asset {
+						// In case the swap is "simulated", the amount is just an estimate,
+						// so we additionally include `source_asset` and `source_amount`:
+						let (source_asset, source_amount) = if swap.input_asset() != STABLE_ASSET {
+							(Some(swap.input_asset()), Some(swap.input_amount()))
+						} else {
+							(None, None)
+						};
+
+						let amount = swap.stable_amount.or_else(|| {
+							// If the swap into stable asset failed, fallback to estimating the
+							// amount via pool price.
+
+							// Should be able to successfully retrieve the price since the pool
+							// should exist as we wouldn't need to estimate if input asset
+							// was already STABLE_ASSET):
+							let sell_price =
+								T::PoolPriceApi::pool_price(swap.input_asset(), STABLE_ASSET)
+									.ok()
+									.map(|price| price.sell)?;
+
+							Some(
+								output_amount_ceil(
+									cf_amm::math::Amount::from(swap.input_amount()),
+									sell_price,
+								)
+								.saturated_into(),
+							)
+						})?;
+
+						Some(SwapLegInfo {
+							swap_id: swap.swap_id(),
+							swap_request_id: swap.swap.swap_request_id,
+							base_asset,
+							// All swaps to `base_asset` have to go through the stable asset:
+							quote_asset: STABLE_ASSET,
+							side: Side::Buy,
+							amount,
+							source_asset,
+							source_amount,
+							remaining_chunks,
+							chunk_interval,
+						})
+					} else {
+						None
+					}
+				})
+				.collect()
+		}
+
+		#[allow(clippy::too_many_arguments)]
+		fn open_swap_deposit_channel(
+			broker: T::AccountId,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: ChannelRefundParametersEncoded,
+			dca_parameters: Option<DcaParameters>,
+			output_split: Option<Vec<(ForeignChainAddress, BasisPoints)>>,
+		) -> Result<ChannelId, DispatchError> {
+			let beneficiaries = Pallet::<T>::assemble_and_validate_broker_fees(
+				broker.clone(),
+				broker_commission,
+				affiliate_fees.clone(),
+				source_asset,
+			)?;
+
+			let destination_address_internal =
+				T::AddressConverter::decode_and_validate_address_for_asset(
+					destination_address.clone(),
+					destination_asset,
+				)
+				.map_err(address_error_to_pallet_error::<T>)?;
+
+			// Convert the refund parameter from `EncodedAddress` into `ForeignChainAddress` type.
+			let refund_params_internal = refund_parameters.clone().try_map_address(|addr| {
+				T::AddressConverter::try_from_encoded_address(addr)
+					.map_err(|_| Error::<T>::InvalidRefundAddress)
+			})?;
+
+			if let Some(ccm) = channel_metadata.as_ref() {
+				let destination_chain: ForeignChain = destination_asset.into();
+				ensure!(destination_chain.ccm_support(), Error::<T>::CcmUnsupportedForTargetChain);
+
+				let _ = T::CcmValidityChecker::check_and_decode(
+					ccm,
+					destination_asset,
+					destination_address.clone(),
+				)
+				.map_err(|e| {
+					log::warn!(
+						"Failed to open channel due to invalid CCM. Broker: {:?}, Error: {:?}",
+						broker,
+						e
+					);
+					Error::<T>::InvalidCcm
+				})?;
+			}
+
+			let (channel_id, deposit_address, expiry_height, channel_opening_fee) =
+				match output_split {
+					Some(destination_addresses) => {
+						ensure!(channel_metadata.is_none(), Error::<T>::InvalidOutputSplit);
+						T::DepositHandler::request_split_swap_deposit_address(
+							source_asset,
+							destination_asset,
+							destination_addresses,
+							beneficiaries.clone(),
+							broker.clone(),
+							boost_fee,
+							refund_params_internal,
+							dca_parameters.clone(),
+						)?
+					},
+					None => T::DepositHandler::request_swap_deposit_address(
+						source_asset,
+						destination_asset,
+						destination_address_internal,
+						beneficiaries.clone(),
+						broker.clone(),
+						channel_metadata.clone(),
+						boost_fee,
+						refund_params_internal,
+						dca_parameters.clone(),
+					)?,
+				};
+
+			// TODO: deduplicate this with assemble_and_validate_broker_fees
+			let affiliate_fees = affiliate_fees
+				.into_iter()
+				.filter(|beneficiary| beneficiary.bps > 0)
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("Filtering out will always fit");
+
+			Self::deposit_event(Event::<T>::SwapDepositAddressReady {
+				deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
+				destination_address,
+				source_asset,
+				destination_asset,
+				channel_id,
+				broker_id: broker,
+				broker_commission_rate: broker_commission,
+				channel_metadata,
+				source_chain_expiry_block: expiry_height,
+				boost_fee,
+				channel_opening_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+			});
+
+			Ok(channel_id)
+		}
+
+		fn private_channel_count(broker_id: &T::AccountId) -> u128 {
+			u128::from(BrokerPrivateBtcChannels::<T>::contains_key(broker_id)) +
+				BrokerPrivateEvmChannels::<T>::iter_prefix(broker_id).count() as u128
+		}
+
+		/// The broker must be able to cover the bond of all of its private channels, including the
+		/// one about to be opened.
+		fn ensure_sufficient_funds_for_private_channel(broker_id: &T::AccountId) -> DispatchResult {
+			ensure!(
+				T::FundingInfo::total_balance_of(broker_id) >=
+					BrokerBond::<T>::get()
+						.saturating_mul((Self::private_channel_count(broker_id) + 1).into()),
+				Error::<T>::InsufficientFunds
+			);
+			Ok(())
+		}
+
+		/// Sets the broker's bond according to the number of its open private channels.
+		fn update_private_channel_bond(broker_id: &T::AccountId) {
+			T::Bonder::update_bond(
+				broker_id,
+				BrokerBond::<T>::get()
+					.saturating_mul(Self::private_channel_count(broker_id).into()),
+			);
+		}
 
-			Pallet::<T>::validate_broker_fees(
-				&vec![Beneficiary { account: broker_id.clone(), bps: minimum_fee_bps }]
-					.try_into()
-					.expect("Single broker will fit"),
-			)?;
+		/// The hash that an affiliate has to sign (using `personal_sign` with the key of its
+		/// withdrawal address) in order to authorise the given action.
+		pub fn affiliate_signing_payload(
+			affiliate_id: &T::AccountId,
+			action: &AffiliateAction,
+		) -> [u8; 32] {
+			Self::ethereum_signed_message_hash(
+				&(
+					*b"chainflip/affiliate",
+					frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero()),
+					affiliate_id,
+					action,
+					AffiliateNonces::<T>::get(affiliate_id),
+				)
+					.encode(),
+			)
+		}
 
-			VaultSwapMinimumBrokerFee::<T>::insert(broker_id.clone(), minimum_fee_bps);
-			Self::deposit_event(Event::<T>::VaultSwapMinimumBrokerFeeSet {
-				broker_id,
-				minimum_fee_bps,
-			});
+		/// The hash that the EVM refund address of a DCA swap request has to sign (using
+		/// `personal_sign`) in order to authorise the given update, or `None` if the swap request
+		/// does not exist or does not refund to an EVM address.
+		pub fn dca_update_signing_payload(
+			swap_request_id: SwapRequestId,
+			update: &DcaUpdate,
+		) -> Option<[u8; 32]> {
+			match SwapRequests::<T>::get(swap_request_id)?.state {
+				SwapRequestState::UserSwap { refund_params, .. } =>
+					Self::evm_refund_address(&refund_params).map(|refund_address| {
+						Self::dca_update_message_hash(swap_request_id, update, refund_address)
+					}),
+				_ => None,
+			}
+		}
 
-			Ok(())
+		fn dca_update_message_hash(
+			swap_request_id: SwapRequestId,
+			update: &DcaUpdate,
+			refund_address: EthereumAddress,
+		) -> [u8; 32] {
+			Self::ethereum_signed_message_hash(
+				&(
+					*b"chainflip/dca-update",
+					frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero()),
+					swap_request_id,
+					update,
+					DcaUpdateNonces::<T>::get(refund_address),
+				)
+					.encode(),
+			)
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		#[allow(clippy::result_unit_err)]
-		pub fn get_scheduled_swap_legs(swaps: Vec<Swap<T>>, base_asset: Asset) -> Vec<SwapLegInfo> {
-			let mut swaps: Vec<_> = swaps.into_iter().map(SwapState::new).collect();
+		/// Hashes the message in the same way as `personal_sign` does before signing it.
+		fn ethereum_signed_message_hash(message: &[u8]) -> [u8; 32] {
+			let mut prefixed_message = b"\x19Ethereum Signed Message:\n32".to_vec();
+			prefixed_message.extend_from_slice(&sp_io::hashing::keccak_256(message));
+			sp_io::hashing::keccak_256(&prefixed_message)
+		}
 
-			// Can ignore the result here because we use pool price fallback below
-			let _res = Self::swap_into_stable_taking_fees(&mut swaps);
+		/// Recovers the Ethereum address whose key produced the signature over the payload.
+		fn recover_ethereum_signer(
+			signature: &EthereumSignature,
+			payload: &[u8; 32],
+		) -> Option<EthereumAddress> {
+			sp_io::crypto::secp256k1_ecdsa_recover(signature, payload)
+				.ok()
+				.map(|public_key| {
+					EthereumAddress::from_slice(&sp_io::hashing::keccak_256(&public_key)[12..])
+				})
+		}
 
-			swaps
-				.into_iter()
-				.filter_map(|swap| {
-					let swap_request = SwapRequests::<T>::get(swap.swap.swap_request_id)
-						.expect("Swap request should exist");
-					let dca_state = match swap_request.state {
-						SwapRequestState::UserSwap { dca_state, .. } => Some(dca_state),
-						_ => None,
-					};
-					let remaining_chunks =
-						dca_state.as_ref().map(|dca| dca.remaining_chunks).unwrap_or(0);
-					let chunk_interval =
-						dca_state.map(|dca| dca.chunk_interval).unwrap_or(SWAP_DELAY_BLOCKS);
+		fn evm_refund_address(
+			refund_params: &Option<RefundParametersExtended<T::AccountId>>,
+		) -> Option<EthereumAddress> {
+			match refund_params {
+				Some(RefundParametersExtended {
+					refund_destination:
+						AccountOrAddress::ExternalAddress(
+							ForeignChainAddress::Eth(address) | ForeignChainAddress::Arb(address),
+						),
+					..
+				}) => Some(*address),
+				_ => None,
+			}
+		}
 
-					if swap.input_asset() == base_asset {
-						Some(SwapLegInfo {
-							swap_id: swap.swap_id(),
-							swap_request_id: swap.swap.swap_request_id,
-							base_asset,
-							// All swaps from `base_asset` have to go through the stable asset:
-							quote_asset: STABLE_ASSET,
-							side: Side::Sell,
-							amount: swap.input_amount(),
-							source_asset: None,
-							source_amount: None,
-							remaining_chunks,
-							chunk_interval,
-						})
-					} else if swap.output_asset() == base_asset {
-						// In case the swap is "simulated", the amount is just an estimate,
-						// so we additionally include `source_asset` and `source_amount`:
-						let (source_asset, source_amount) = if swap.input_asset() != STABLE_ASSET {
-							(Some(swap.input_asset()), Some(swap.input_amount()))
-						} else {
-							(None, None)
-						};
+		/// Checks that the DCA update was signed by the refund address of the swap request and
+		/// consumes the refund address's nonce.
+		fn ensure_signed_by_refund_address(
+			swap_request_id: SwapRequestId,
+			update: &DcaUpdate,
+			refund_address: EthereumAddress,
+			signature: &EthereumSignature,
+		) -> DispatchResult {
+			ensure!(
+				Self::recover_ethereum_signer(
+					signature,
+					&Self::dca_update_message_hash(swap_request_id, update, refund_address),
+				) == Some(refund_address),
+				Error::<T>::InvalidDcaUpdateSignature
+			);
 
-						let amount = swap.stable_amount.or_else(|| {
-							// If the swap into stable asset failed, fallback to estimating the
-							// amount via pool price.
+			DcaUpdateNonces::<T>::mutate(refund_address, |nonce| nonce.saturating_accrue(1));
 
-							// Should be able to successfully retrieve the price since the pool
-							// should exist as we wouldn't need to estimate if input asset
-							// was already STABLE_ASSET):
-							let sell_price =
-								T::PoolPriceApi::pool_price(swap.input_asset(), STABLE_ASSET)
-									.ok()
-									.map(|price| price.sell)?;
+			Ok(())
+		}
 
-							Some(
-								output_amount_ceil(
-									cf_amm::math::Amount::from(swap.input_amount()),
-									sell_price,
-								)
-								.saturated_into(),
-							)
-						})?;
+		/// Checks that the action was signed by the withdrawal address of the affiliate and
+		/// consumes the affiliate's nonce.
+		fn ensure_signed_by_affiliate(
+			broker_id: &T::AccountId,
+			short_id: AffiliateShortId,
+			action: &AffiliateAction,
+			signature: &EthereumSignature,
+		) -> Result<(T::AccountId, AffiliateDetails), DispatchError> {
+			let affiliate_id = AffiliateIdMapping::<T>::get(broker_id, short_id)
+				.ok_or(Error::<T>::AffiliateNotRegisteredForBroker)?;
+			let details = AffiliateAccountDetails::<T>::get(broker_id, &affiliate_id)
+				.ok_or(Error::<T>::AffiliateNotRegisteredForBroker)?;
 
-						Some(SwapLegInfo {
-							swap_id: swap.swap_id(),
-							swap_request_id: swap.swap.swap_request_id,
-							base_asset,
-							// All swaps to `base_asset` have to go through the stable asset:
-							quote_asset: STABLE_ASSET,
-							side: Side::Buy,
-							amount,
-							source_asset,
-							source_amount,
-							remaining_chunks,
-							chunk_interval,
-						})
-					} else {
-						None
-					}
-				})
-				.collect()
+			ensure!(
+				Self::recover_ethereum_signer(
+					signature,
+					&Self::affiliate_signing_payload(&affiliate_id, action),
+				) == Some(details.withdrawal_address),
+				Error::<T>::InvalidAffiliateSignature
+			);
+
+			AffiliateNonces::<T>::mutate(&affiliate_id, |nonce| nonce.saturating_accrue(1));
+
+			Ok((affiliate_id, details))
 		}
 
 		fn trigger_withdrawal(
@@ -1506,7 +3170,7 @@ pub mod pallet {
 				});
 
 			if total_fee_bps > MAX_BASIS_POINTS {
-				FeeTaken { remaining_amount: stable_amount, fee: 0 }
+				FeeTaken { remaining_amount: stable_amount, fee: 0, discount: 0 }
 			} else {
 				let total_fee = broker_fees.iter().fold(
 					0u128,
@@ -1525,8 +3189,137 @@ pub mod pallet {
 				FeeTaken {
 					remaining_amount: stable_amount.saturating_sub(total_fee),
 					fee: total_fee,
+					discount: 0,
+				}
+			}
+		}
+
+		/// Routes swaps between two non-Stable assets through a direct pool between them, if one
+		/// exists and gives a better output than swapping via the Stable asset. Fees are still
+		/// charged in the Stable asset, so the part of the input needed to cover them is swapped
+		/// via the Stable asset as usual.
+		fn route_direct_swaps(swaps: &mut [SwapState<T>]) {
+			for swap in swaps.iter_mut() {
+				let (from, to) = (swap.input_asset(), swap.output_asset());
+				if from != STABLE_ASSET &&
+					to != STABLE_ASSET &&
+					from != to && T::SwappingApi::pool_exists(from, to)
+				{
+					swap.direct_input_amount = Self::direct_input_amount(swap);
+				}
+			}
+		}
+
+		/// The part of the swap's input to swap through the direct pool, or `None` if swapping via
+		/// the Stable asset gives a better output.
+		fn direct_input_amount(swap: &SwapState<T>) -> Option<AssetAmount> {
+			let simulate = |legs: &[(Asset, Asset)], input_amount: AssetAmount| {
+				with_transaction_unchecked(|| {
+					TransactionOutcome::Rollback(
+						legs.iter().try_fold(input_amount, |amount, (from, to)| {
+							T::SwappingApi::swap_single_leg(*from, *to, amount)
+						}),
+					)
+				})
+				.ok()
+			};
+			let (from, to) = (swap.input_asset(), swap.output_asset());
+			let via_stable = [(from, STABLE_ASSET), (STABLE_ASSET, to)];
+
+			let stable_amount = simulate(&via_stable[..1], swap.input_amount())?;
+			if stable_amount == 0 {
+				return None
+			}
+			let estimated_fees = swap.swap.fees.iter().fold(0, |total: AssetAmount, fee_type| {
+				total.saturating_add(match fee_type {
+					// The fee depends on the kind of swap request, so err on the high side:
+					FeeType::NetworkFee { min_fee_enforced } => max(
+						max(T::NetworkFee::get(), InternalSwapNetworkFee::<T>::get()) *
+							stable_amount,
+						if *min_fee_enforced {
+							max(
+								MinimumNetworkFee::<T>::get(),
+								InternalSwapMinimumNetworkFee::<T>::get(),
+							)
+						} else {
+							0
+						},
+					),
+					FeeType::BrokerFee(beneficiaries) => beneficiaries.iter().fold(
+						0,
+						|total: AssetAmount, Beneficiary { bps, .. }| {
+							total.saturating_add(
+								Permill::from_parts(*bps as u32 * BASIS_POINTS_PER_MILLION) *
+									stable_amount,
+							)
+						},
+					),
+				})
+			});
+			// Leave some margin for the price to move within the batch. Anything left over after
+			// fees is swapped via the Stable asset.
+			let fee_input_amount = multiply_by_rational_with_rounding(
+				swap.input_amount(),
+				estimated_fees.saturating_add(estimated_fees / 10),
+				stable_amount,
+				Rounding::Up,
+			)?;
+			let direct_input_amount = swap.input_amount().checked_sub(fee_input_amount)?;
+			if direct_input_amount == 0 {
+				return None
+			}
+
+			let direct_output = simulate(&[(from, to)], direct_input_amount)?;
+			simulate(&via_stable, direct_input_amount)
+				.is_none_or(|output_via_stable| direct_output > output_via_stable)
+				.then_some(direct_input_amount)
+		}
+
+		/// Executes the direct legs of swaps routed through direct pools, bundling swaps between
+		/// the same assets together.
+		fn swap_direct(swaps: &mut [SwapState<T>]) -> Result<(), BatchExecutionError<T>> {
+			let swap_groups =
+				swaps.iter_mut().fold(BTreeMap::new(), |mut groups: BTreeMap<_, Vec<_>>, swap| {
+					if swap.direct_input_amount.is_some() {
+						groups
+							.entry((swap.input_asset(), swap.output_asset()))
+							.or_default()
+							.push(swap);
+					}
+					groups
+				});
+
+			for ((from, to), mut swaps) in swap_groups {
+				let bundle_input: AssetAmount =
+					swaps.iter().map(|swap| swap.direct_input_amount.unwrap_or_default()).sum();
+
+				let bundle_output = T::SwappingApi::swap_single_leg_for_swaps(
+					&swaps.iter().map(|swap| swap.swap_id()).collect::<Vec<_>>(),
+					from,
+					to,
+					bundle_input,
+				)
+				.map_err(|_| BatchExecutionError::SwapLegFailed {
+					asset: from,
+					direction: SwapLeg::ToStable,
+					amount: bundle_input,
+					failed_swap_group: swaps.iter().map(|swap| (*swap).clone()).collect(),
+				})?;
+
+				for swap in swaps.iter_mut() {
+					swap.direct_output = Some(
+						multiply_by_rational_with_rounding(
+							swap.direct_input_amount.unwrap_or_default(),
+							bundle_output,
+							bundle_input,
+							Rounding::Down,
+						)
+						.unwrap_or_default(),
+					);
 				}
 			}
+
+			Ok(())
 		}
 
 		fn swap_into_stable_taking_fees(
@@ -1541,26 +3334,56 @@ pub mod pallet {
 					"All swaps should have Stable amount set here"
 				);
 
+				// For swaps routed through a direct pool only part of the input was swapped into
+				// the Stable asset. Fees are charged on the Stable value of the whole input, as
+				// implied by that part, and whatever is left of it after fees is swapped into the
+				// output asset as usual.
+				let stable_amount = swap.stable_amount.unwrap_or_default();
+				if let Some(direct_input_amount) = swap.direct_input_amount {
+					let fee_input_amount = swap.input_amount().saturating_sub(direct_input_amount);
+					swap.stable_amount = Some(if fee_input_amount > 0 {
+						multiply_by_rational_with_rounding(
+							stable_amount,
+							swap.input_amount(),
+							fee_input_amount,
+							Rounding::Down,
+						)
+						.unwrap_or(stable_amount)
+					} else {
+						0
+					});
+				}
+
 				swap.stable_amount_before_fees = swap.stable_amount;
 
 				for fee_type in &swap.swap.fees {
 					let remaining_amount = match fee_type {
 						FeeType::NetworkFee { min_fee_enforced } => {
-							let FeeTaken { remaining_amount, fee } = Self::take_network_fee(
-								swap.stable_amount.unwrap_or_default(),
-								if *min_fee_enforced {
-									MinFeePolicy::Enforced {
-										swap_request_id: swap.swap.swap_request_id,
-									}
+							let FeeTaken { remaining_amount, fee, discount } =
+								Self::take_network_fee(
+									swap.stable_amount.unwrap_or_default(),
+									if *min_fee_enforced {
+										MinFeePolicy::Enforced {
+											swap_request_id: swap.swap.swap_request_id,
+										}
+									} else {
+										MinFeePolicy::NotEnforced
+									},
+								);
+							T::SwappingApi::record_network_fee(
+								if swap.input_asset() == STABLE_ASSET {
+									swap.output_asset()
 								} else {
-									MinFeePolicy::NotEnforced
+									swap.input_asset()
 								},
+								fee,
 							);
 							swap.network_fee_taken = Some(fee);
+							swap.network_fee_discount = Some(discount);
 							remaining_amount
 						},
 						FeeType::BrokerFee(beneficiaries) => {
-							let FeeTaken { remaining_amount, fee } = Self::take_broker_fees(
+							let FeeTaken { remaining_amount, fee, .. } = Self::take_broker_fees(
 								swap.stable_amount.unwrap_or_default(),
 								beneficiaries,
 							);
@@ -1571,6 +3394,14 @@ pub mod pallet {
 					swap.stable_amount = Some(remaining_amount);
 				}
 
+				if swap.direct_input_amount.is_some() {
+					let fees_taken = swap
+						.stable_amount_before_fees
+						.unwrap_or_default()
+						.saturating_sub(swap.stable_amount.unwrap_or_default());
+					swap.stable_amount = Some(stable_amount.saturating_sub(fees_taken));
+				}
+
 				if swap.output_asset() == STABLE_ASSET {
 					swap.final_output = swap.stable_amount;
 				}
@@ -1584,6 +3415,8 @@ pub mod pallet {
 			swaps: Vec<Swap<T>>,
 		) -> Result<Vec<SwapState<T>>, BatchExecutionError<T>> {
 			let mut swaps: Vec<_> = swaps.into_iter().map(SwapState::new).collect();
+			Self::route_direct_swaps(&mut swaps);
+			Self::swap_direct(&mut swaps)?;
 			Self::swap_into_stable_taking_fees(&mut swaps)?;
 
 			// Swap from Stable asset, and complete the swap logic.
@@ -1594,9 +3427,13 @@ pub mod pallet {
 			let (non_violating, violating): (Vec<_>, Vec<_>) =
 				swaps.into_iter().partition(|swap| {
 					let final_output = swap.final_output.unwrap();
-					swap.refund_params()
-						.as_ref()
-						.is_none_or(|params| final_output >= params.min_output)
+					swap.refund_params().as_ref().is_none_or(|params| {
+						final_output >=
+							max(
+								params.min_output,
+								Self::twap_min_output(swap).unwrap_or_default(),
+							)
+					})
 				});
 
 			if violating.is_empty() {
@@ -1643,7 +3480,7 @@ pub mod pallet {
 							&failed_swap_group,
 							direction,
 						) {
-							failed_swaps.push(removed_swap);
+							failed_swaps.push((removed_swap, SwapRefundReason::DurationExpired));
 						} else {
 							break;
 						}
@@ -1652,7 +3489,11 @@ pub mod pallet {
 						violating_swaps,
 						non_violating_swaps,
 					}) => {
-						failed_swaps.extend(violating_swaps);
+						failed_swaps.extend(
+							violating_swaps
+								.into_iter()
+								.map(|swap| (swap, SwapRefundReason::PriceLimitExceeded)),
+						);
 						swaps_to_execute = non_violating_swaps;
 					},
 					Err(BatchExecutionError::DispatchError { error }) => {
@@ -1665,11 +3506,15 @@ pub mod pallet {
 			}
 
 			// If we are here, consider all swaps as failed:
-			failed_swaps.extend(swaps_to_execute);
+			failed_swaps.extend(
+				swaps_to_execute
+					.into_iter()
+					.map(|swap| (swap, SwapRefundReason::DurationExpired)),
+			);
 			BatchExecutionOutcomes { successful_swaps: vec![], failed_swaps }
 		}
 
-		fn refund_failed_swap(swap: Swap<T>) {
+		fn refund_failed_swap(swap: Swap<T>, refund_reason: SwapRefundReason) {
 			let swap_request_id = swap.swap_request_id;
 
 			let Some(mut request) = SwapRequests::<T>::take(swap_request_id) else {
@@ -1704,34 +3549,13 @@ pub mod pallet {
 						},
 					};
 
-					if amount_to_refund > 0 {
-						match &refund_params.refund_destination {
-							AccountOrAddress::ExternalAddress(address) => {
-								Self::egress_for_swap(
-									request.id,
-									amount_to_refund,
-									request.input_asset,
-									address.clone(),
-									None, /* refunds don't use ccm parameters */
-									true, /* refund */
-								);
-							},
-							AccountOrAddress::InternalAccount(account_id) => {
-								Self::deposit_event(Event::<T>::RefundedOnChain {
-									swap_request_id,
-									account_id: account_id.clone(),
-									asset: request.input_asset,
-									amount: amount_to_refund,
-								});
-
-								T::BalanceApi::credit_account(
-									account_id,
-									request.input_asset,
-									amount_to_refund,
-								);
-							},
-						}
-					}
+					Self::refund_to_destination(
+						swap_request_id,
+						request.input_asset,
+						amount_to_refund,
+						&refund_params.refund_destination,
+						refund_reason,
+					);
 
 					// In case of DCA we may have partially swapped and now have some output
 					// asset to egress to the output address:
@@ -1744,7 +3568,15 @@ pub mod pallet {
 									request.output_asset,
 									output_address.clone(),
 									ccm_deposit_metadata.clone(),
-									false, /* refund */
+									None, /* refund_reason */
+								);
+							},
+							SwapOutputAction::SplitEgress { output_addresses } => {
+								Self::egress_split_output(
+									swap_request_id,
+									*accumulated_output_amount,
+									request.output_asset,
+									output_addresses,
 								);
 							},
 							SwapOutputAction::CreditOnChain { account_id } => {
@@ -1761,6 +3593,15 @@ pub mod pallet {
 									*accumulated_output_amount,
 								);
 							},
+							SwapOutputAction::FundAccount { account_id } => {
+								Self::fund_account_from_swap(
+									swap_request_id,
+									account_id,
+									*accumulated_output_amount,
+									request.input_asset,
+									Some(refund_params),
+								);
+							},
 						}
 					}
 				},
@@ -1771,9 +3612,51 @@ pub mod pallet {
 				},
 			};
 
+			SwapRequestBrokers::<T>::remove(swap_request_id);
+			SwapRequestReferrers::<T>::remove(swap_request_id);
+			SwapRequestTwapSlippage::<T>::remove(swap_request_id);
+			SwapRequestRefundCcm::<T>::remove(swap_request_id);
+			FundingSwapOrigins::<T>::remove(swap_request_id);
+			Self::finalise_swap_status(swap_request_id, SwapRequestStatus::Refunded);
 			Self::deposit_event(Event::<T>::SwapRequestCompleted { swap_request_id: request.id });
 		}
 
+		fn refund_to_destination(
+			swap_request_id: SwapRequestId,
+			asset: Asset,
+			amount: AssetAmount,
+			refund_destination: &AccountOrAddress<ForeignChainAddress, T::AccountId>,
+			refund_reason: SwapRefundReason,
+		) {
+			if amount.is_zero() {
+				return
+			}
+
+			match refund_destination {
+				AccountOrAddress::ExternalAddress(address) => {
+					Self::egress_for_swap(
+						swap_request_id,
+						amount,
+						asset,
+						address.clone(),
+						SwapRequestRefundCcm::<T>::get(swap_request_id),
+						Some(refund_reason),
+					);
+				},
+				AccountOrAddress::InternalAccount(account_id) => {
+					Self::deposit_event(Event::<T>::RefundedOnChain {
+						swap_request_id,
+						account_id: account_id.clone(),
+						asset,
+						amount,
+						refund_reason,
+					});
+
+					T::BalanceApi::credit_account(account_id, asset, amount);
+				},
+			}
+		}
+
 		fn process_swap_outcome(swap: SwapState<T>) {
 			let swap_request_id = swap.swap.swap_request_id;
 
@@ -1803,12 +3686,31 @@ pub mod pallet {
 				},
 				input_asset: swap.input_asset(),
 				network_fee: swap.network_fee_taken.unwrap_or_default(),
+				network_fee_discount: swap.network_fee_discount.unwrap_or_default(),
 				broker_fee: swap.broker_fee_taken.unwrap_or_default(),
 				output_asset: swap.output_asset(),
 				output_amount,
 				intermediate_amount: swap.intermediate_amount(),
 			});
 
+			if matches!(request.state, SwapRequestState::UserSwap { .. }) {
+				SwapStatuses::<T>::mutate_exists(swap_request_id, |status| {
+					if let Some(status) = status {
+						status.swapped_input_amount.saturating_accrue(swap.input_amount());
+						status
+							.intermediate_amount
+							.saturating_accrue(swap.intermediate_amount().unwrap_or_default());
+						status.output_amount.saturating_accrue(output_amount);
+						status
+							.network_fee
+							.saturating_accrue(swap.network_fee_taken.unwrap_or_default());
+						status
+							.broker_fee
+							.saturating_accrue(swap.broker_fee_taken.unwrap_or_default());
+					}
+				});
+			}
+
 			let request_completed = match &mut request.state {
 				SwapRequestState::UserSwap {
 					output_action,
@@ -1819,6 +3721,14 @@ pub mod pallet {
 					if let Some(chunk_input_amount) =
 						dca_state.prepare_next_chunk(Some((swap.swap_id(), output_amount)))
 					{
+						let chunk_input_amount = Self::adapt_chunk_size(
+							swap_request_id,
+							request.input_asset,
+							request.output_asset,
+							dca_state,
+							chunk_input_amount,
+						);
+
 						let swap_id = Self::schedule_swap(
 							request.input_asset,
 							request.output_asset,
@@ -1834,6 +3744,16 @@ pub mod pallet {
 						dca_state
 							.network_fee_collected
 							.saturating_accrue(swap.network_fee_taken.unwrap_or_default());
+						Self::update_swap_status(swap_request_id, |status| {
+							let chunk = match status {
+								SwapRequestStatus::Executing { chunk, .. } => *chunk + 1,
+								_ => 2,
+							};
+							*status = SwapRequestStatus::Executing {
+								chunk,
+								total_chunks: chunk + dca_state.remaining_chunks,
+							};
+						});
 						dca_state
 							.accumulated_stable_amount
 							.saturating_accrue(swap.stable_amount_before_fees.unwrap_or_default());
@@ -1844,16 +3764,36 @@ pub mod pallet {
 
 						match output_action {
 							SwapOutputAction::Egress { ccm_deposit_metadata, output_address } => {
-								Self::egress_for_swap(
+								let egress_id = Self::egress_for_swap(
 									swap_request_id,
 									dca_state.accumulated_output_amount,
 									swap.output_asset(),
 									output_address.clone(),
 									ccm_deposit_metadata.clone(),
-									false, /* refund */
+									None, /* refund_reason */
+								);
+								Self::finalise_swap_status(
+									swap_request_id,
+									SwapRequestStatus::AwaitingEgress { egress_id },
+								);
+							},
+							SwapOutputAction::SplitEgress { output_addresses } => {
+								let egress_id = Self::egress_split_output(
+									swap_request_id,
+									dca_state.accumulated_output_amount,
+									swap.output_asset(),
+									output_addresses,
+								);
+								Self::finalise_swap_status(
+									swap_request_id,
+									SwapRequestStatus::AwaitingEgress { egress_id },
 								);
 							},
 							SwapOutputAction::CreditOnChain { account_id } => {
+								Self::finalise_swap_status(
+									swap_request_id,
+									SwapRequestStatus::Credited,
+								);
 								Self::deposit_event(Event::<T>::CreditedOnChain {
 									swap_request_id,
 									account_id: account_id.clone(),
@@ -1863,8 +3803,21 @@ pub mod pallet {
 
 								T::BalanceApi::credit_account(
 									account_id,
-									request.output_asset,
+									request.output_asset,
+									dca_state.accumulated_output_amount,
+								);
+							},
+							SwapOutputAction::FundAccount { account_id } => {
+								Self::finalise_swap_status(
+									swap_request_id,
+									SwapRequestStatus::Credited,
+								);
+								Self::fund_account_from_swap(
+									swap_request_id,
+									account_id,
 									dca_state.accumulated_output_amount,
+									request.input_asset,
+									refund_params.as_ref(),
 								);
 							},
 						}
@@ -1902,6 +3855,11 @@ pub mod pallet {
 			};
 
 			if request_completed {
+				SwapRequestBrokers::<T>::remove(swap_request_id);
+				SwapRequestReferrers::<T>::remove(swap_request_id);
+				SwapRequestTwapSlippage::<T>::remove(swap_request_id);
+				SwapRequestRefundCcm::<T>::remove(swap_request_id);
+				FundingSwapOrigins::<T>::remove(swap_request_id);
 				Self::deposit_event(Event::<T>::SwapRequestCompleted { swap_request_id });
 			} else {
 				SwapRequests::<T>::insert(swap_request_id, request);
@@ -1954,7 +3912,8 @@ pub mod pallet {
 				swaps.iter().map(|swap| swap.swap_amount(direction).unwrap_or_default()).sum();
 
 			// Process the swap leg as a bundle. No network fee is taken here.
-			let bundle_output = T::SwappingApi::swap_single_leg(
+			let bundle_output = T::SwappingApi::swap_single_leg_for_swaps(
+				&swaps.iter().map(|swap| swap.swap_id()).collect::<Vec<_>>(),
 				match direction {
 					SwapLeg::FromStable => STABLE_ASSET,
 					SwapLeg::ToStable => asset,
@@ -2047,6 +4006,7 @@ pub mod pallet {
 				fees
 			};
 
+			ScheduledSwapBlocks::<T>::insert(swap_id, execute_at);
 			SwapQueue::<T>::append(
 				execute_at,
 				Swap::new(
@@ -2071,8 +4031,276 @@ pub mod pallet {
 			swap_id
 		}
 
+		/// The number of blocks to wait before executing the first chunk of a swap request. This is
+		/// the regular swap delay, unless the swap comes from a deposit channel with a scheduled
+		/// execution block.
+		fn swap_delay_for_origin(
+			origin: &SwapOrigin<T::AccountId>,
+			input_asset: Asset,
+		) -> BlockNumberFor<T> {
+			let swap_delay = BlockNumberFor::<T>::from(SwapDelayBlocks::<T>::get());
+
+			match origin {
+				SwapOrigin::DepositChannel { channel_id, .. } =>
+					ScheduledChannelSwaps::<T>::get((ForeignChain::from(input_asset), *channel_id))
+						.map(|execute_at| {
+							max(
+								execute_at
+									.saturating_sub(frame_system::Pallet::<T>::block_number()),
+								swap_delay,
+							)
+						})
+						.unwrap_or(swap_delay),
+				_ => swap_delay,
+			}
+		}
+
+		/// Checks a new swap request against the swap volume limit of its input asset. Unless the
+		/// request is to be refunded, its volume is recorded from the earliest block at which it
+		/// fits within the limit, and the request is delayed until then.
+		pub(super) fn check_swap_volume_limit(
+			swap_request_id: SwapRequestId,
+			input_asset: Asset,
+			input_amount: AssetAmount,
+			refundable: bool,
+		) -> SwapVolumeCheck<BlockNumberFor<T>> {
+			let Some(limit) = SwapVolumeLimits::<T>::get(input_asset) else {
+				return SwapVolumeCheck::Proceed { delay: Zero::zero() }
+			};
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			let window = BlockNumberFor::<T>::from(limit.window_blocks);
+
+			SwapVolumeHistory::<T>::mutate(input_asset, |history| {
+				history.retain(|(block, _)| block.saturating_add(window) > current_block);
+
+				let exceeds_limit = |volume: AssetAmount| {
+					!volume.is_zero() && volume.saturating_add(input_amount) > limit.max_volume
+				};
+
+				let mut volume = history
+					.iter()
+					.fold(0, |total: AssetAmount, (_, amount)| total.saturating_add(*amount));
+				let mut counted_from = current_block;
+				if exceeds_limit(volume) {
+					let action =
+						if refundable { limit.on_exceeded } else { SwapVolumeLimitAction::Queue };
+					Self::deposit_event(Event::<T>::SwapVolumeLimitReached {
+						swap_request_id,
+						asset: input_asset,
+						action,
+					});
+
+					if action == SwapVolumeLimitAction::Refund {
+						return SwapVolumeCheck::Refund
+					}
+
+					// Wait for earlier volume to leave the window until there is enough room. The
+					// history is ordered by block, so volume leaves the window in order:
+					for (block, amount) in history.iter() {
+						if !exceeds_limit(volume) {
+							break
+						}
+						counted_from = core::cmp::max(counted_from, block.saturating_add(window));
+						volume.saturating_reduce(*amount);
+					}
+				}
+
+				let position = history.partition_point(|(block, _)| *block < counted_from);
+				if let Some((_, amount)) =
+					history.get_mut(position).filter(|(block, _)| *block == counted_from)
+				{
+					amount.saturating_accrue(input_amount);
+				} else if history.try_insert(position, (counted_from, input_amount)).is_err() {
+					// Without room for another block, the volume is counted from the next block
+					// with recorded volume, or the latest volume is moved to this block
+					// instead. Either way the limit only becomes stricter:
+					match history.get_mut(position) {
+						Some((block, amount)) => {
+							counted_from = *block;
+							amount.saturating_accrue(input_amount);
+						},
+						None =>
+							if let Some((block, amount)) = history.last_mut() {
+								*block = counted_from;
+								amount.saturating_accrue(input_amount);
+							},
+					}
+				}
+
+				SwapVolumeCheck::Proceed { delay: counted_from.saturating_sub(current_block) }
+			})
+		}
+
+		/// Estimates the price impact of swapping `input_amount` in each of the pools involved that
+		/// has a price impact limit. Returns the impact and the limit for the pool where the impact
+		/// is highest relative to its limit, or `None` if no limits apply.
+		fn price_impact_relative_to_limit(
+			input_asset: Asset,
+			output_asset: Asset,
+			input_amount: AssetAmount,
+		) -> Option<(BasisPoints, BasisPoints)> {
+			let legs = [
+				(input_asset != STABLE_ASSET).then_some((input_asset, STABLE_ASSET, input_asset)),
+				(output_asset != STABLE_ASSET).then_some((
+					STABLE_ASSET,
+					output_asset,
+					output_asset,
+				)),
+			];
+
+			if !legs
+				.iter()
+				.flatten()
+				.any(|(_, _, pool_asset)| MaxSwapPriceImpact::<T>::contains_key(pool_asset))
+			{
+				return None
+			}
+
+			// Swap requests aren't necessarily initiated by an extrinsic, so the swaps used for the
+			// estimate (two per leg) are accounted for here. This is done outside of the
+			// transaction below, which is rolled back.
+			let number_of_legs = legs.iter().flatten().count() as u64;
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				T::SwappingApi::swap_single_leg_weight()
+					.saturating_mul(2 * number_of_legs)
+					.saturating_add(T::DbWeight::get().reads(number_of_legs)),
+				DispatchClass::Mandatory,
+			);
+
+			with_transaction_unchecked(|| {
+				let mut leg_input = input_amount;
+				let mut highest_impact = None;
+
+				for (from, to, pool_asset) in legs.into_iter().flatten() {
+					let Some((leg_output, impact)) =
+						utilities::estimate_price_impact::<T>(from, to, leg_input)
+					else {
+						break
+					};
+
+					if let Some(max_impact) = MaxSwapPriceImpact::<T>::get(pool_asset) {
+						if highest_impact.is_none_or(|(highest, highest_max)| {
+							impact as u64 * highest_max as u64 > highest as u64 * max_impact as u64
+						}) {
+							highest_impact = Some((impact, max_impact));
+						}
+					}
+
+					leg_input = leg_output;
+				}
+
+				TransactionOutcome::Rollback(highest_impact)
+			})
+		}
+
+		/// If swapping a chunk of the request is expected to exceed the price impact limit of
+		/// either of the pools involved, increases the number of DCA chunks so that each chunk
+		/// stays (approximately) within the limit. The number of chunks is capped so that the
+		/// request does not exceed the max swap request duration.
+		fn split_to_respect_price_impact_limits(
+			input_asset: Asset,
+			output_asset: Asset,
+			input_amount: AssetAmount,
+			dca_params: Option<DcaParameters>,
+		) -> Option<DcaParameters> {
+			let number_of_chunks = dca_params.as_ref().map(|p| p.number_of_chunks).unwrap_or(1);
+			let chunk_interval = dca_params
+				.as_ref()
+				.map(|p| p.chunk_interval)
+				.unwrap_or(SwapDelayBlocks::<T>::get());
+
+			let Some((impact, max_impact)) = Self::price_impact_relative_to_limit(
+				input_asset,
+				output_asset,
+				input_amount / number_of_chunks.max(1) as u128,
+			) else {
+				return dca_params
+			};
+
+			if impact <= max_impact {
+				return dca_params
+			}
+
+			// Price impact is roughly proportional to the chunk size:
+			let required_chunks: u32 = (number_of_chunks as u64 * impact as u64)
+				.div_ceil(max_impact as u64)
+				.saturated_into();
+
+			let max_chunks = (MaxSwapRequestDurationBlocks::<T>::get() / chunk_interval.max(1))
+				.saturating_add(1);
+
+			Some(DcaParameters {
+				number_of_chunks: min(required_chunks, max(max_chunks, number_of_chunks)),
+				chunk_interval,
+			})
+		}
+
+		/// With adaptive DCA enabled, enlarges the next chunk of a swap request to the largest
+		/// amount that is expected to stay within the price impact limits of the pools involved,
+		/// so that the request finishes early when liquidity allows. Chunks are never made smaller
+		/// than their regular size, so the request still completes within the requested number
+		/// of chunks.
+		fn adapt_chunk_size(
+			swap_request_id: SwapRequestId,
+			input_asset: Asset,
+			output_asset: Asset,
+			dca_state: &mut DcaState,
+			chunk_input_amount: AssetAmount,
+		) -> AssetAmount {
+			if !AdaptiveDcaEnabled::<T>::get() || dca_state.remaining_input_amount.is_zero() {
+				return chunk_input_amount
+			}
+
+			let available_amount =
+				chunk_input_amount.saturating_add(dca_state.remaining_input_amount);
+
+			// Price impact is roughly proportional to the chunk size:
+			let target_amount = match Self::price_impact_relative_to_limit(
+				input_asset,
+				output_asset,
+				available_amount,
+			) {
+				None => return chunk_input_amount,
+				Some((impact, max_impact)) if impact > max_impact =>
+					multiply_by_rational_with_rounding(
+						available_amount,
+						max_impact.into(),
+						impact.into(),
+						Rounding::Down,
+					)
+					.unwrap_or(chunk_input_amount),
+				Some(_) => available_amount,
+			};
+
+			let chunk_input_amount = dca_state.enlarge_chunk(chunk_input_amount, target_amount);
+
+			Self::deposit_event(Event::<T>::DcaChunkSized {
+				swap_request_id,
+				chunk_input_amount,
+				remaining_input_amount: dca_state.remaining_input_amount,
+				remaining_chunks: dca_state.remaining_chunks,
+			});
+
+			chunk_input_amount
+		}
+
+		/// Removes the swap with the given id from the swap queue, if it is still scheduled.
+		fn take_scheduled_swap(swap_id: SwapId) -> Option<Swap<T>> {
+			let execute_at = ScheduledSwapBlocks::<T>::take(swap_id)?;
+			SwapQueue::<T>::mutate_exists(execute_at, |maybe_swaps| {
+				let swaps = maybe_swaps.as_mut()?;
+				let swap = swaps.extract_if(.., |swap| swap.swap_id == swap_id).next();
+				if swaps.is_empty() {
+					*maybe_swaps = None;
+				}
+				swap
+			})
+		}
+
 		fn reschedule_swap(swap: Swap<T>, execute_at: BlockNumberFor<T>) {
 			Self::deposit_event(Event::<T>::SwapRescheduled { swap_id: swap.swap_id, execute_at });
+			ScheduledSwapBlocks::<T>::insert(swap.swap_id, execute_at);
 			SwapQueue::<T>::append(execute_at, swap);
 		}
 
@@ -2084,16 +4312,18 @@ pub mod pallet {
 		) -> Result<SwapOutput, DispatchError> {
 			Ok(match (from, to) {
 				(_, STABLE_ASSET) => {
-					let FeeTaken { remaining_amount: output, fee } = Self::take_network_fee(
+					let FeeTaken { remaining_amount: output, fee, .. } = Self::take_network_fee(
 						T::SwappingApi::swap_single_leg(from, to, input_amount)?,
 						MinFeePolicy::NotEnforced,
 					);
+					T::SwappingApi::record_network_fee(from, fee);
 
 					SwapOutput { intermediary: None, output, network_fee: fee }
 				},
 				(STABLE_ASSET, _) => {
-					let FeeTaken { remaining_amount: input_amount, fee } =
+					let FeeTaken { remaining_amount: input_amount, fee, .. } =
 						Self::take_network_fee(input_amount, MinFeePolicy::NotEnforced);
+					T::SwappingApi::record_network_fee(to, fee);
 
 					SwapOutput {
 						intermediary: None,
@@ -2102,10 +4332,12 @@ pub mod pallet {
 					}
 				},
 				_ => {
-					let FeeTaken { remaining_amount: intermediary, fee } = Self::take_network_fee(
-						T::SwappingApi::swap_single_leg(from, STABLE_ASSET, input_amount)?,
-						MinFeePolicy::NotEnforced,
-					);
+					let FeeTaken { remaining_amount: intermediary, fee, .. } =
+						Self::take_network_fee(
+							T::SwappingApi::swap_single_leg(from, STABLE_ASSET, input_amount)?,
+							MinFeePolicy::NotEnforced,
+						);
+					T::SwappingApi::record_network_fee(from, fee);
 
 					SwapOutput {
 						intermediary: Some(intermediary),
@@ -2124,11 +4356,11 @@ pub mod pallet {
 			minimum_fee_policy: MinFeePolicy,
 		) -> FeeTaken {
 			if input.is_zero() {
-				return FeeTaken { remaining_amount: 0, fee: 0 };
+				return FeeTaken { remaining_amount: 0, fee: 0, discount: 0 };
 			}
 
 			// Check if minimum network fee still needs to be filled for this swap request.
-			let fee = match minimum_fee_policy {
+			let (fee, discount) = match minimum_fee_policy {
 				MinFeePolicy::Enforced { swap_request_id } => {
 					if let Some(swap_request) = SwapRequests::<T>::get(swap_request_id) {
 						match swap_request.state {
@@ -2152,35 +4384,260 @@ pub mod pallet {
 											.saturating_add(input)),
 									minimum_network_fee,
 								);
-								min(
+								let discounted_fee = calculated_fee.saturating_sub(
+									Self::network_fee_discount(swap_request_id) * calculated_fee,
+								);
+
+								let full_fee = min(
 									calculated_fee.saturating_sub(dca_state.network_fee_collected),
 									input,
-								)
+								);
+								let fee = min(
+									discounted_fee.saturating_sub(dca_state.network_fee_collected),
+									input,
+								);
+								(fee, full_fee.saturating_sub(fee))
 							},
 							SwapRequestState::NetworkFee | SwapRequestState::IngressEgressFee => {
 								log_or_panic!(
 									"Should not enforce minimum network fee for fee swaps"
 								);
-								0
+								(0, 0)
 							},
 						}
 					} else {
 						log_or_panic!(
 							"Swap request {swap_request_id} not found, ignoring fee collection history for network fee calculation"
 						);
-						max(T::NetworkFee::get() * input, MinimumNetworkFee::<T>::get())
+						(max(T::NetworkFee::get() * input, MinimumNetworkFee::<T>::get()), 0)
 					}
 				},
-				MinFeePolicy::NotEnforced => T::NetworkFee::get() * input,
+				MinFeePolicy::NotEnforced => (T::NetworkFee::get() * input, 0),
 			};
 
 			if !fee.is_zero() {
+				let referral_reward = match minimum_fee_policy {
+					MinFeePolicy::Enforced { swap_request_id } =>
+						Self::accrue_referral_reward(swap_request_id, fee),
+					MinFeePolicy::NotEnforced => 0,
+				};
 				CollectedNetworkFee::<T>::mutate(|total| {
-					total.saturating_accrue(fee);
+					total.saturating_accrue(fee.saturating_sub(referral_reward));
 				});
 			}
 
-			FeeTaken { remaining_amount: input.saturating_sub(fee), fee }
+			FeeTaken { remaining_amount: input.saturating_sub(fee), fee, discount }
+		}
+
+		fn schedule_channel_parameters_expiry(source_chain: ForeignChain, channel_id: ChannelId) {
+			ChannelParameterExpiries::<T>::append(
+				frame_system::Pallet::<T>::block_number()
+					.saturating_add(CHANNEL_PARAMETERS_RETENTION_BLOCKS.into()),
+				(source_chain, channel_id),
+			);
+		}
+
+		/// The minimum output of an executed swap implied by the maximum slippage relative to the
+		/// pools' TWAP that its swap request allows, if any. The output at the TWAP is reduced by
+		/// the same proportion as the swap's stable amount was reduced by fees.
+		fn twap_min_output(swap: &SwapState<T>) -> Option<AssetAmount> {
+			let max_slippage = SwapRequestTwapSlippage::<T>::get(swap.swap.swap_request_id)?;
+
+			let twap_tick_vs_stable = |asset: Asset| {
+				if asset == STABLE_ASSET {
+					Some(0)
+				} else {
+					T::PoolPriceApi::twap_tick(asset, STABLE_ASSET, SLIPPAGE_TWAP_WINDOW_BLOCKS)
+				}
+			};
+			let Some(twap_price) = twap_tick_vs_stable(swap.input_asset())
+				.zip(twap_tick_vs_stable(swap.output_asset()))
+				.and_then(|(input_tick, output_tick)| input_tick.checked_sub(output_tick))
+				.and_then(cf_amm::math::price_at_tick)
+			else {
+				log::warn!(
+					"No TWAP available for swap {:?}, only its minimum price is enforced.",
+					swap.swap_id()
+				);
+				return None
+			};
+
+			let output_at_twap: AssetAmount = cf_amm::math::output_amount_floor(
+				cf_amm::math::Amount::from(swap.input_amount()),
+				twap_price,
+			)
+			.saturated_into();
+			let output_at_twap_after_fees = match swap.stable_amount_before_fees {
+				Some(before_fees) if before_fees > 0 => multiply_by_rational_with_rounding(
+					output_at_twap,
+					before_fees
+						.saturating_sub(swap.network_fee_taken.unwrap_or_default())
+						.saturating_sub(swap.broker_fee_taken.unwrap_or_default()),
+					before_fees,
+					Rounding::Down,
+				)
+				.unwrap_or(output_at_twap),
+				_ => output_at_twap,
+			};
+
+			Some(output_at_twap_after_fees.saturating_sub(
+				Permill::from_parts(max_slippage as u32 * BASIS_POINTS_PER_MILLION) *
+					output_at_twap_after_fees,
+			))
+		}
+
+		/// Credits the referrer of the swap request (if any) with their share of the network fee.
+		/// Returns the amount credited.
+		fn accrue_referral_reward(
+			swap_request_id: SwapRequestId,
+			network_fee: AssetAmount,
+		) -> AssetAmount {
+			let Some(referrer) = SwapRequestReferrers::<T>::get(swap_request_id) else { return 0 };
+			let reward = ReferralRewardShare::<T>::get() * network_fee;
+			if !reward.is_zero() {
+				ReferralRewards::<T>::mutate(referrer, |rewards| rewards.saturating_accrue(reward));
+			}
+			reward
+		}
+
+		/// The network fee discount that applies to the given swap request, based on the broker
+		/// through which it was submitted.
+		fn network_fee_discount(swap_request_id: SwapRequestId) -> Permill {
+			SwapRequestBrokers::<T>::get(swap_request_id)
+				.map(BrokerNetworkFeeDiscounts::<T>::get)
+				.unwrap_or_default()
+		}
+
+		/// Adds the FLIP output of a swap to the account's funds. If this is not possible, eg.
+		/// because the amount is too small to be sent to the State Chain Gateway, the FLIP is
+		/// refunded to the swap's refund destination instead. Refunds to addresses on chains
+		/// other than Ethereum are swapped back into the input asset first, under the original
+		/// request's origin and refund parameters.
+		fn fund_account_from_swap(
+			swap_request_id: SwapRequestId,
+			account_id: &T::AccountId,
+			amount: AssetAmount,
+			input_asset: Asset,
+			refund_params: Option<&RefundParametersExtended<T::AccountId>>,
+		) {
+			match T::FundAccountFromSwap::fund_account(account_id, amount) {
+				Ok(amount) => {
+					Self::deposit_event(Event::<T>::AccountFundedFromSwap {
+						swap_request_id,
+						account_id: account_id.clone(),
+						amount,
+					});
+				},
+				Err(e) => {
+					log::warn!(
+						"Failed to fund account {account_id:?} from swap request {swap_request_id}: {e:?}"
+					);
+					let Some(refund_params) = refund_params else {
+						log_or_panic!(
+							"Missing refund parameters for account funding swap request {swap_request_id}"
+						);
+						return
+					};
+					match &refund_params.refund_destination {
+						AccountOrAddress::ExternalAddress(address)
+							if address.chain() != ForeignChain::Ethereum =>
+						{
+							// The minimum price applies to the reverse direction, so it is
+							// inverted. FLIP can't be refunded to the (non-Ethereum) refund
+							// address, so if the reverse swap is refunded, the FLIP is credited to
+							// the account instead.
+							let min_price = if refund_params.min_price.is_zero() {
+								Price::zero()
+							} else {
+								mul_div_floor_checked(
+									Price::one() << PRICE_FRACTIONAL_BITS,
+									Price::one() << PRICE_FRACTIONAL_BITS,
+									refund_params.min_price,
+								)
+								.unwrap_or(Price::MAX)
+							};
+							<Self as SwapRequestHandler>::init_swap_request(
+								Asset::Flip,
+								amount,
+								input_asset,
+								SwapRequestType::Regular {
+									output_action: SwapOutputAction::Egress {
+										ccm_deposit_metadata: None,
+										output_address: address.clone(),
+									},
+								},
+								Default::default(),
+								Some(RefundParametersExtended {
+									retry_duration: refund_params.retry_duration,
+									refund_destination: AccountOrAddress::InternalAccount(
+										account_id.clone(),
+									),
+									min_price,
+								}),
+								None,
+								FundingSwapOrigins::<T>::get(swap_request_id)
+									.unwrap_or(SwapOrigin::Internal),
+							);
+						},
+						refund_destination => Self::refund_to_destination(
+							swap_request_id,
+							Asset::Flip,
+							amount,
+							refund_destination,
+							SwapRefundReason::AccountFundingFailed,
+						),
+					}
+				},
+			}
+		}
+
+		/// Egresses the output of a swap to each of the addresses according to their share. The
+		/// first address also receives any remainder due to rounding. Returns the id of the egress
+		/// to the first address.
+		fn egress_split_output(
+			swap_request_id: SwapRequestId,
+			amount: AssetAmount,
+			asset: Asset,
+			output_addresses: &[(ForeignChainAddress, BasisPoints)],
+		) -> Option<EgressId> {
+			let Some(((first_address, _), other_addresses)) = output_addresses.split_first() else {
+				log_or_panic!("No output addresses for swap request {swap_request_id}");
+				return None
+			};
+
+			let other_amounts = other_addresses
+				.iter()
+				.map(|(address, share)| {
+					(
+						address,
+						Permill::from_parts(*share as u32 * BASIS_POINTS_PER_MILLION) * amount,
+					)
+				})
+				.collect::<Vec<_>>();
+			let first_amount = other_amounts.iter().fold(amount, |remaining, (_, share_amount)| {
+				remaining.saturating_sub(*share_amount)
+			});
+
+			let egress_id = Self::egress_for_swap(
+				swap_request_id,
+				first_amount,
+				asset,
+				first_address.clone(),
+				None,
+				None, /* refund_reason */
+			);
+			for (address, share_amount) in other_amounts {
+				Self::egress_for_swap(
+					swap_request_id,
+					share_amount,
+					asset,
+					address.clone(),
+					None,
+					None, /* refund_reason */
+				);
+			}
+
+			egress_id
 		}
 
 		fn egress_for_swap(
@@ -2189,19 +4646,20 @@ pub mod pallet {
 			asset: Asset,
 			address: ForeignChainAddress,
 			maybe_ccm_metadata: Option<CcmDepositMetadata>,
-			is_refund: bool,
-		) {
+			refund_reason: Option<SwapRefundReason>,
+		) -> Option<EgressId> {
 			let is_ccm_swap = maybe_ccm_metadata.is_some();
 
 			match T::EgressHandler::schedule_egress(asset, amount, address, maybe_ccm_metadata) {
-				Ok(ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }) =>
-					if is_refund {
+				Ok(ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }) => {
+					if let Some(refund_reason) = refund_reason {
 						Self::deposit_event(Event::<T>::RefundEgressScheduled {
 							swap_request_id,
 							egress_id,
 							asset,
 							amount: egress_amount,
 							egress_fee: (fee_withheld, asset),
+							refund_reason,
 						});
 					} else {
 						Self::deposit_event(Event::<T>::SwapEgressScheduled {
@@ -2211,18 +4669,21 @@ pub mod pallet {
 							amount: egress_amount,
 							egress_fee: (fee_withheld, asset),
 						});
-					},
+					}
+					Some(egress_id)
+				},
 				Err(err) => {
 					if is_ccm_swap {
 						log_or_panic!("CCM egress scheduling should never fail.");
 					}
 
-					if is_refund {
+					if let Some(refund_reason) = refund_reason {
 						Self::deposit_event(Event::<T>::RefundEgressIgnored {
 							swap_request_id,
 							asset,
 							amount,
 							reason: err.into(),
+							refund_reason,
 						});
 					} else {
 						Self::deposit_event(Event::<T>::SwapEgressIgnored {
@@ -2232,8 +4693,37 @@ pub mod pallet {
 							reason: err.into(),
 						});
 					}
+					None
 				},
-			};
+			}
+		}
+
+		fn update_swap_status(
+			swap_request_id: SwapRequestId,
+			f: impl FnOnce(&mut SwapRequestStatus),
+		) {
+			SwapStatuses::<T>::mutate_exists(swap_request_id, |status| {
+				if let Some(status) = status {
+					f(&mut status.status);
+				}
+			});
+		}
+
+		/// Records the final status of a swap request and schedules its removal.
+		fn finalise_swap_status(swap_request_id: SwapRequestId, final_status: SwapRequestStatus) {
+			if SwapStatuses::<T>::contains_key(swap_request_id) {
+				Self::update_swap_status(swap_request_id, |status| *status = final_status);
+				SwapStatusExpiries::<T>::append(
+					frame_system::Pallet::<T>::block_number()
+						.saturating_add(SWAP_STATUS_RETENTION_BLOCKS.into()),
+					swap_request_id,
+				);
+			}
+		}
+
+		/// Returns the current status of a user swap request, if it is still known.
+		pub fn swap_status(swap_request_id: SwapRequestId) -> Option<SwapStatus> {
+			SwapStatuses::<T>::get(swap_request_id)
 		}
 
 		pub(super) fn take_refund_fee(
@@ -2273,6 +4763,7 @@ pub mod pallet {
 			broker_id: T::AccountId,
 			broker_commission: BasisPoints,
 			affiliate_fees: Affiliates<T::AccountId>,
+			source_asset: Asset,
 		) -> Result<Beneficiaries<T::AccountId>, DispatchError> {
 			let beneficiaries = [Beneficiary { account: broker_id, bps: broker_commission }]
 				.into_iter()
@@ -2291,9 +4782,15 @@ pub mod pallet {
 				.expect(
 					"We are pushing affiliates + 1 which is exactly the maximum Beneficiaries size",
 				);
-			Pallet::<T>::validate_broker_fees(&beneficiaries)?;
+			Pallet::<T>::validate_broker_fees(&beneficiaries, source_asset)?;
 			Ok(beneficiaries)
 		}
+
+		/// The broker commission bounds that apply to swaps from the given asset.
+		pub fn broker_commission_bounds(source_asset: Asset) -> BrokerCommissionBounds {
+			AssetBrokerCommissionLimits::<T>::get(source_asset)
+				.unwrap_or_else(BrokerCommissionLimits::<T>::get)
+		}
 	}
 
 	impl<T: Config> SwapRequestHandler for Pallet<T> {
@@ -2353,6 +4850,17 @@ pub mod pallet {
 				dca_params
 			});
 
+			let dca_params = if matches!(request_type, SwapRequestType::Regular { .. }) {
+				Self::split_to_respect_price_impact_limits(
+					input_asset,
+					output_asset,
+					net_amount,
+					dca_params,
+				)
+			} else {
+				dca_params
+			};
+
 			Self::deposit_event(Event::<T>::SwapRequested {
 				swap_request_id: request_id,
 				input_asset,
@@ -2377,7 +4885,7 @@ pub mod pallet {
 						SwapType::NetworkFee,
 						Default::default(),
 						request_id,
-						SWAP_DELAY_BLOCKS.into(),
+						SwapDelayBlocks::<T>::get().into(),
 					);
 
 					SwapRequests::<T>::insert(
@@ -2399,7 +4907,7 @@ pub mod pallet {
 						SwapType::IngressEgressFee,
 						Default::default(),
 						request_id,
-						SWAP_DELAY_BLOCKS.into(),
+						SwapDelayBlocks::<T>::get().into(),
 					);
 
 					SwapRequests::<T>::insert(
@@ -2413,8 +4921,25 @@ pub mod pallet {
 					);
 				},
 				SwapRequestType::Regular { output_action } => {
-					let (mut dca_state, chunk_input_amount) =
-						DcaState::create_with_first_chunk(net_amount, dca_params);
+					let (mut dca_state, chunk_input_amount) = DcaState::create_with_first_chunk(
+						net_amount,
+						dca_params,
+						SwapDelayBlocks::<T>::get(),
+					);
+					let chunk_input_amount = Self::adapt_chunk_size(
+						request_id,
+						input_asset,
+						output_asset,
+						&mut dca_state,
+						chunk_input_amount,
+					);
+
+					let volume_check = Self::check_swap_volume_limit(
+						request_id,
+						input_asset,
+						net_amount,
+						refund_params.is_some(),
+					);
 
 					let swap_id = Self::schedule_swap(
 						input_asset,
@@ -2424,11 +4949,47 @@ pub mod pallet {
 						SwapType::Swap,
 						broker_fees.clone(),
 						request_id,
-						SWAP_DELAY_BLOCKS.into(),
+						match volume_check {
+							SwapVolumeCheck::Proceed { delay } =>
+								max(Self::swap_delay_for_origin(&origin, input_asset), delay),
+							SwapVolumeCheck::Refund => SwapDelayBlocks::<T>::get().into(),
+						},
 					);
 
 					dca_state.status = DcaStatus::ChunkScheduled(swap_id);
 
+					if let Some(broker_id) = origin.broker_id() {
+						SwapRequestBrokers::<T>::insert(request_id, broker_id);
+					}
+					if let SwapOrigin::DepositChannel { deposit_address, channel_id, .. } = &origin
+					{
+						let channel = (deposit_address.chain(), *channel_id);
+						if let Some(referrer) = ChannelReferrers::<T>::get(channel) {
+							SwapRequestReferrers::<T>::insert(request_id, referrer);
+						}
+						if let Some(max_slippage) = ChannelTwapSlippage::<T>::get(channel) {
+							SwapRequestTwapSlippage::<T>::insert(request_id, max_slippage);
+						}
+					}
+					if matches!(output_action, SwapOutputAction::FundAccount { .. }) {
+						FundingSwapOrigins::<T>::insert(request_id, origin.clone());
+					}
+
+					SwapStatuses::<T>::insert(
+						request_id,
+						SwapStatus {
+							status: SwapRequestStatus::Scheduled,
+							input_asset,
+							output_asset,
+							input_amount: net_amount,
+							swapped_input_amount: 0,
+							intermediate_amount: 0,
+							output_amount: 0,
+							network_fee: 0,
+							broker_fee: 0,
+						},
+					);
+
 					SwapRequests::<T>::insert(
 						request_id,
 						SwapRequest {
@@ -2443,11 +5004,27 @@ pub mod pallet {
 							},
 						},
 					);
+
+					if matches!(volume_check, SwapVolumeCheck::Refund) {
+						if let Some(swap) = Self::take_scheduled_swap(swap_id) {
+							Self::refund_failed_swap(swap, SwapRefundReason::VolumeLimitExceeded);
+						}
+					}
 				},
 			};
 
 			request_id
 		}
+
+		fn set_refund_ccm_metadata(
+			swap_request_id: SwapRequestId,
+			ccm_deposit_metadata: CcmDepositMetadata,
+		) {
+			// The request may have already been refunded (e.g. due to the volume limit):
+			if SwapRequests::<T>::contains_key(swap_request_id) {
+				SwapRequestRefundCcm::<T>::insert(swap_request_id, ccm_deposit_metadata);
+			}
+		}
 	}
 
 	impl<T: Config> AssetConverter for Pallet<T> {
@@ -2545,7 +5122,7 @@ impl<T: Config> SwapParameterValidation for Pallet<T> {
 			if params.number_of_chunks == 0 {
 				return Err(DispatchError::from(Error::<T>::ZeroNumberOfChunksNotAllowed));
 			}
-			if params.chunk_interval < SWAP_DELAY_BLOCKS {
+			if params.chunk_interval < SwapDelayBlocks::<T>::get() {
 				return Err(DispatchError::from(Error::<T>::ChunkIntervalTooLow));
 			}
 			if let Some(total_swap_request_duration) =
@@ -2563,12 +5140,15 @@ impl<T: Config> SwapParameterValidation for Pallet<T> {
 
 	fn validate_broker_fees(
 		broker_fees: &Beneficiaries<Self::AccountId>,
+		source_asset: Asset,
 	) -> Result<(), DispatchError> {
 		let total_bps = broker_fees
 			.iter()
 			.fold(0, |total, Beneficiary { bps, .. }| total.saturating_add(*bps));
 
-		ensure!(total_bps <= 1000, Error::<T>::BrokerCommissionBpsTooHigh);
+		let bounds = Self::broker_commission_bounds(source_asset);
+		ensure!(total_bps <= bounds.max_bps, Error::<T>::BrokerCommissionBpsTooHigh);
+		ensure!(total_bps >= bounds.min_bps, Error::<T>::BrokerCommissionBpsTooLow);
 
 		Ok(())
 	}
@@ -2656,6 +5236,42 @@ pub(crate) mod utilities {
 		}
 	}
 
+	/// Estimates the price impact (in bps) of swapping `amount` of `from` into `to` in a single
+	/// leg, relative to swapping a small reference amount. Returns the output amount alongside.
+	///
+	/// Note that this executes the swap: the caller is responsible for rolling back the changes.
+	pub(super) fn estimate_price_impact<T: Config>(
+		from: Asset,
+		to: Asset,
+		amount: AssetAmount,
+	) -> Option<(AssetAmount, BasisPoints)> {
+		let reference_input = core::cmp::min(fee_estimation_basis(from), amount / 100);
+		if reference_input.is_zero() {
+			return None
+		}
+
+		let reference_output = with_transaction_unchecked(|| {
+			TransactionOutcome::Rollback(T::SwappingApi::swap_single_leg(from, to, reference_input))
+		})
+		.ok()?;
+		let output = T::SwappingApi::swap_single_leg(from, to, amount).ok()?;
+
+		let expected_output = multiply_by_rational_with_rounding(
+			amount,
+			reference_output,
+			reference_input,
+			Rounding::Down,
+		)?;
+
+		let impact = if expected_output > output {
+			Permill::from_rational(expected_output - output, expected_output)
+		} else {
+			Permill::zero()
+		};
+
+		Some((output, (impact.deconstruct() / BASIS_POINTS_PER_MILLION) as BasisPoints))
+	}
+
 	pub(super) fn split_off_highest_impact_swap<T: Config>(
 		swaps: &mut Vec<Swap<T>>,
 		failed_swap_group: &[SwapState<T>],
@@ -17,5 +17,17 @@
 use crate::Pallet;
 
 use cf_runtime_utilities::PlaceholderMigration;
+use frame_support::migrations::VersionedMigration;
 
-pub type PalletMigration<T> = (PlaceholderMigration<9, Pallet<T>>,);
+mod scheduled_swap_blocks;
+
+pub type PalletMigration<T> = (
+	VersionedMigration<
+		9,
+		10,
+		scheduled_swap_blocks::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	PlaceholderMigration<10, Pallet<T>>,
+);
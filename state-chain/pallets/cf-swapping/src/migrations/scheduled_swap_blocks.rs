@@ -0,0 +1,63 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Config, ScheduledSwapBlocks, SwapQueue};
+use frame_support::{
+	traits::{Get, UncheckedOnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::pallet_prelude::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Populates [ScheduledSwapBlocks] from the swaps in [SwapQueue].
+pub struct Migration<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut reads = 0u64;
+		let mut writes = 0u64;
+		for (execute_at, swaps) in SwapQueue::<T>::iter() {
+			reads += 1;
+			for swap in swaps {
+				ScheduledSwapBlocks::<T>::insert(swap.swap_id, execute_at);
+				writes += 1;
+			}
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		for (execute_at, swaps) in SwapQueue::<T>::iter() {
+			for swap in swaps {
+				frame_support::ensure!(
+					ScheduledSwapBlocks::<T>::get(swap.swap_id) == Some(execute_at),
+					"Missing scheduled block for swap"
+				);
+			}
+		}
+		Ok(())
+	}
+}
@@ -17,8 +17,8 @@
 use core::cell::Cell;
 
 use crate::{self as pallet_cf_swapping, PalletSafeMode, WeightInfo};
-use cf_chains::{ccm_checker::CcmValidityCheck, AnyChain};
-use cf_primitives::{Asset, AssetAmount, ChannelId};
+use cf_chains::{ccm_checker::CcmValidityCheck, AnyChain, ForeignChainAddress};
+use cf_primitives::{Asset, AssetAmount, ChannelId, STABLE_ASSET};
 #[cfg(feature = "runtime-benchmarks")]
 use cf_traits::mocks::fee_payment::MockFeePayment;
 use cf_traits::{
@@ -26,9 +26,10 @@ use cf_traits::{
 	mocks::{
 		address_converter::MockAddressConverter, balance_api::MockBalance, bonding::MockBonderFor,
 		deposit_handler::MockDepositHandler, egress_handler::MockEgressHandler,
+		fund_account_from_swap::MockFundAccountFromSwap,
 		ingress_egress_fee_handler::MockIngressEgressFeeHandler, pool_price_api::MockPoolPriceApi,
 	},
-	AccountRoleRegistry, ChannelIdAllocator, SwappingApi,
+	AccountRoleRegistry, ChannelIdAllocator, PrivateChannelManager, SwappingApi,
 };
 use frame_support::{derive_impl, pallet_prelude::DispatchError, parameter_types, weights::Weight};
 use sp_core::ConstU32;
@@ -59,8 +60,14 @@ parameter_types! {
 	pub static NetworkFee: Permill = Permill::from_perthousand(0);
 	pub static Swaps: Vec<(Asset, Asset, AssetAmount)> = vec![];
 	pub static SwapRate: f64 = DEFAULT_SWAP_RATE as f64;
+	/// Pairs of non-stable assets with a direct pool between them, and the rate of those pools.
+	pub static DirectPools: Vec<(Asset, Asset)> = vec![];
+	pub static DirectSwapRate: f64 = DEFAULT_SWAP_RATE as f64;
+	/// If set, swaps incur price impact as if swapping against a pool of this depth.
+	pub static PoolDepth: Option<f64> = None;
 	pub storage Liquidity: BoundedBTreeMap<Asset, AssetAmount, ConstU32<100>> = Default::default();
 	pub storage NextChannelId: u64 = 0;
+	pub static OpenPrivateChannels: Vec<(u64, ChannelId)> = vec![];
 }
 
 thread_local! {
@@ -107,7 +114,17 @@ impl SwappingApi for MockSwappingApi {
 		swaps.push((from, to, input_amount));
 		Swaps::set(swaps);
 
-		let output_amount = (input_amount as f64 * SwapRate::get()) as AssetAmount;
+		let swap_rate = if from != STABLE_ASSET && to != STABLE_ASSET {
+			DirectSwapRate::get()
+		} else {
+			SwapRate::get()
+		};
+		let output_amount = match PoolDepth::get() {
+			Some(depth) =>
+				(input_amount as f64 * swap_rate * depth / (depth + input_amount as f64))
+					as AssetAmount,
+			None => (input_amount as f64 * swap_rate) as AssetAmount,
+		};
 
 		let mut liquidity = Liquidity::get();
 
@@ -129,6 +146,14 @@ impl SwappingApi for MockSwappingApi {
 
 		Ok(output_amount)
 	}
+
+	fn swap_single_leg_weight() -> Weight {
+		Weight::from_parts(1_000, 0)
+	}
+
+	fn pool_exists(from: Asset, to: Asset) -> bool {
+		DirectPools::get().iter().any(|pair| *pair == (from, to) || *pair == (to, from))
+	}
 }
 
 pub struct MockWeightInfo;
@@ -173,6 +198,66 @@ impl WeightInfo for MockWeightInfo {
 	fn set_vault_swap_minimum_broker_fee() -> Weight {
 		Weight::from_parts(100, 0)
 	}
+
+	fn update_dca_swap_request() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn cancel_swap_request() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_scheduled_swap_deposit_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn update_affiliate_withdrawal_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn signed_affiliate_withdrawal_request() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn open_private_evm_channel() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn close_private_evm_channel() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_split_swap_deposit_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn register_referral_code() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_referred_swap_deposit_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn claim_referral_rewards() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_twap_protected_swap_deposit_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn set_broker_channel_defaults() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_swap_deposit_address_with_broker_defaults() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn request_account_funding_deposit_address() -> Weight {
+		Weight::from_parts(100, 0)
+	}
 }
 
 pub struct AlwaysValid;
@@ -188,6 +273,25 @@ impl ChannelIdAllocator for MockChannelIdAllocator {
 	}
 }
 
+pub struct MockPrivateChannelManager {}
+
+impl PrivateChannelManager for MockPrivateChannelManager {
+	type AccountId = u64;
+
+	fn open_private_channel(
+		broker_id: &u64,
+		_refund_address: ForeignChainAddress,
+	) -> Result<ChannelId, DispatchError> {
+		let channel_id = MockChannelIdAllocator::allocate_private_channel_id()?;
+		OpenPrivateChannels::mutate(|channels| channels.push((*broker_id, channel_id)));
+		Ok(channel_id)
+	}
+
+	fn close_private_channel(channel_id: ChannelId) {
+		OpenPrivateChannels::mutate(|channels| channels.retain(|(_, id)| *id != channel_id));
+	}
+}
+
 impl pallet_cf_swapping::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type DepositHandler = MockDepositHandler<AnyChain, Self>;
@@ -203,8 +307,11 @@ impl pallet_cf_swapping::Config for Test {
 	type CcmValidityChecker = AlwaysValid;
 	type NetworkFee = NetworkFee;
 	type ChannelIdAllocator = MockChannelIdAllocator;
+	type EthereumPrivateChannels = MockPrivateChannelManager;
+	type ArbitrumPrivateChannels = MockPrivateChannelManager;
 	type Bonder = MockBonderFor<Self>;
 	type PoolPriceApi = MockPoolPriceApi;
+	type FundAccountFromSwap = MockFundAccountFromSwap<Self::AccountId>;
 }
 
 pub const ALICE: <Test as frame_system::Config>::AccountId = 123u64;
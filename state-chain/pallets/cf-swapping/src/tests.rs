@@ -14,11 +14,21 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod account_funding;
+mod broker_defaults;
+mod cancellation;
 mod ccm;
 mod config;
 mod dca;
+mod direct_routing;
 mod fees;
 mod fill_or_kill;
+mod referral;
+mod scheduled;
+mod split_output;
+mod status;
+mod twap_slippage;
+mod volume_limit;
 
 use std::sync::LazyLock;
 
@@ -58,7 +68,7 @@ use frame_support::{
 };
 
 use sp_arithmetic::Permill;
-use sp_core::{H160, U256};
+use sp_core::{ecdsa, Pair, H160, U256};
 use sp_std::iter;
 
 const GAS_BUDGET: AssetAmount = 100_000u128;
@@ -217,6 +227,16 @@ fn generate_test_swaps() -> Vec<TestSwapParams> {
 	]
 }
 
+fn sign(key: &ecdsa::Pair, payload: &[u8; 32]) -> EthereumSignature {
+	key.sign_prehashed(payload).as_ref().try_into().unwrap()
+}
+
+fn eth_address(key: &ecdsa::Pair) -> EthereumAddress {
+	let public_key =
+		sp_io::crypto::secp256k1_ecdsa_recover(&sign(key, &[1u8; 32]), &[1u8; 32]).unwrap();
+	EthereumAddress::from_slice(&sp_io::hashing::keccak_256(&public_key)[12..])
+}
+
 fn insert_swaps(swaps: &[TestSwapParams]) {
 	for (broker_id, swap) in swaps.iter().enumerate() {
 		let ccm_deposit_metadata = if swap.is_ccm { Some(generate_ccm_deposit()) } else { None };
@@ -272,6 +292,7 @@ fn get_broker_balance<T: Config>(who: &T::AccountId, asset: Asset) -> AssetAmoun
 #[track_caller]
 fn assert_swaps_queue_is_empty() {
 	assert_eq!(SwapQueue::<Test>::iter_keys().count(), 0);
+	assert_eq!(ScheduledSwapBlocks::<Test>::iter_keys().count(), 0);
 }
 
 #[track_caller]
@@ -720,6 +741,7 @@ fn can_handle_ccm_with_zero_swap_outputs() {
 					swap_request_id: SwapRequestId(1),
 					swap_id: SwapId(1),
 					network_fee: 0,
+					network_fee_discount: 0,
 					broker_fee: 0,
 					input_amount: PRINCIPAL_AMOUNT,
 					input_asset: INPUT_ASSET,
@@ -1681,6 +1703,7 @@ mod internal_swaps {
 					}),
 					RuntimeEvent::Swapping(Event::RefundedOnChain {
 						swap_request_id: SWAP_REQUEST_ID,
+						refund_reason: SwapRefundReason::PriceLimitExceeded,
 						account_id: LP_ACCOUNT,
 						asset: INPUT_ASSET,
 						amount: EXPECTED_REFUND_AMOUNT,
@@ -1711,7 +1734,9 @@ mod internal_swaps {
 mod private_channels {
 
 	use super::*;
-	use cf_traits::mocks::account_role_registry::MockAccountRoleRegistry;
+	use cf_traits::mocks::{
+		account_role_registry::MockAccountRoleRegistry, bonding::MockBonderFor,
+	};
 	use sp_runtime::DispatchError::BadOrigin;
 
 	#[test]
@@ -1802,6 +1827,123 @@ mod private_channels {
 		});
 	}
 
+	#[test]
+	fn open_and_close_private_evm_channels() {
+		new_test_ext().execute_with(|| {
+			const BOND: AssetAmount = FLIPPERINOS_PER_FLIP * 100;
+			const REFUND_ADDRESS: EncodedAddress = EncodedAddress::Eth([1; 20]);
+
+			assert_noop!(
+				Swapping::open_private_evm_channel(
+					OriginTrait::signed(ALICE),
+					ForeignChain::Ethereum,
+					REFUND_ADDRESS,
+				),
+				BadOrigin
+			);
+			// Chain support is checked before the broker's funds:
+			assert_noop!(
+				Swapping::open_private_evm_channel(
+					OriginTrait::signed(BROKER),
+					ForeignChain::Bitcoin,
+					REFUND_ADDRESS,
+				),
+				Error::<Test>::UnsupportedPrivateChannelChain
+			);
+			assert_noop!(
+				Swapping::open_private_evm_channel(
+					OriginTrait::signed(BROKER),
+					ForeignChain::Ethereum,
+					REFUND_ADDRESS,
+				),
+				Error::<Test>::InsufficientFunds
+			);
+
+			MockFundingInfo::<Test>::credit_funds(&BROKER, BOND * 2);
+
+			assert_noop!(
+				Swapping::open_private_evm_channel(
+					OriginTrait::signed(BROKER),
+					ForeignChain::Ethereum,
+					EncodedAddress::Dot([1; 32]),
+				),
+				Error::<Test>::InvalidRefundAddress
+			);
+
+			assert_ok!(Swapping::open_private_evm_channel(
+				OriginTrait::signed(BROKER),
+				ForeignChain::Ethereum,
+				REFUND_ADDRESS,
+			));
+			assert_eq!(OpenPrivateChannels::get(), vec![(BROKER, 0)]);
+			assert_eq!(
+				BrokerPrivateEvmChannels::<Test>::get(BROKER, ForeignChain::Ethereum),
+				Some(0)
+			);
+			assert_eq!(MockBonderFor::<Test>::get_bond(&BROKER), BOND);
+			System::assert_has_event(RuntimeEvent::Swapping(
+				Event::<Test>::PrivateEvmBrokerChannelOpened {
+					broker_id: BROKER,
+					chain: ForeignChain::Ethereum,
+					channel_id: 0,
+				},
+			));
+
+			assert_noop!(
+				Swapping::open_private_evm_channel(
+					OriginTrait::signed(BROKER),
+					ForeignChain::Ethereum,
+					REFUND_ADDRESS,
+				),
+				Error::<Test>::PrivateChannelExistsForBroker
+			);
+
+			// Each private channel requires its own bond:
+			assert_ok!(Swapping::open_private_btc_channel(OriginTrait::signed(BROKER)));
+			assert_eq!(MockBonderFor::<Test>::get_bond(&BROKER), BOND * 2);
+			assert_noop!(
+				Swapping::open_private_evm_channel(
+					OriginTrait::signed(BROKER),
+					ForeignChain::Arbitrum,
+					REFUND_ADDRESS,
+				),
+				Error::<Test>::InsufficientFunds
+			);
+
+			// The broker can't deregister while private channels are open.
+			assert_noop!(
+				Swapping::deregister_as_broker(OriginTrait::signed(BROKER)),
+				Error::<Test>::PrivateChannelExistsForBroker
+			);
+
+			assert_ok!(Swapping::close_private_evm_channel(
+				OriginTrait::signed(BROKER),
+				ForeignChain::Ethereum
+			));
+			assert_eq!(BrokerPrivateEvmChannels::<Test>::get(BROKER, ForeignChain::Ethereum), None);
+			assert!(OpenPrivateChannels::get().is_empty());
+			assert_eq!(MockBonderFor::<Test>::get_bond(&BROKER), BOND);
+			System::assert_has_event(RuntimeEvent::Swapping(
+				Event::<Test>::PrivateEvmBrokerChannelClosed {
+					broker_id: BROKER,
+					chain: ForeignChain::Ethereum,
+					channel_id: 0,
+				},
+			));
+
+			assert_noop!(
+				Swapping::close_private_evm_channel(
+					OriginTrait::signed(BROKER),
+					ForeignChain::Ethereum
+				),
+				Error::<Test>::NoPrivateChannelExistsForBroker
+			);
+
+			assert_ok!(Swapping::close_private_btc_channel(OriginTrait::signed(BROKER)));
+			assert_eq!(MockBonderFor::<Test>::get_bond(&BROKER), 0);
+		});
+	}
+
 	#[test]
 	fn default_broker_bond() {
 		new_test_ext().execute_with(|| {
@@ -1818,6 +1960,14 @@ mod affiliates {
 	use cf_traits::mocks::account_role_registry::MockAccountRoleRegistry;
 	use sp_runtime::DispatchError::BadOrigin;
 
+	fn sign_action(
+		key: &ecdsa::Pair,
+		affiliate_id: &<Test as frame_system::Config>::AccountId,
+		action: &AffiliateAction,
+	) -> EthereumSignature {
+		sign(key, &Swapping::affiliate_signing_payload(affiliate_id, action))
+	}
+
 	#[test]
 	fn register_affiliate() {
 		new_test_ext().execute_with(|| {
@@ -1988,4 +2138,131 @@ mod affiliates {
 			);
 		});
 	}
+
+	#[test]
+	fn affiliate_can_update_its_withdrawal_address() {
+		new_test_ext().execute_with(|| {
+			const SHORT_ID: AffiliateShortId = AffiliateShortId(0);
+			let key = ecdsa::Pair::from_seed(&[1u8; 32]);
+			let new_key = ecdsa::Pair::from_seed(&[2u8; 32]);
+
+			assert_ok!(Swapping::register_affiliate(
+				OriginTrait::signed(BROKER),
+				eth_address(&key)
+			));
+			let affiliate_id = AffiliateIdMapping::<Test>::get(BROKER, SHORT_ID).unwrap();
+
+			let action = AffiliateAction::UpdateWithdrawalAddress(eth_address(&new_key));
+
+			// The update must be signed by the current withdrawal address.
+			assert_noop!(
+				Swapping::update_affiliate_withdrawal_address(
+					OriginTrait::signed(ALICE),
+					BROKER,
+					SHORT_ID,
+					eth_address(&new_key),
+					sign_action(&new_key, &affiliate_id, &action),
+				),
+				Error::<Test>::InvalidAffiliateSignature
+			);
+
+			let signature = sign_action(&key, &affiliate_id, &action);
+			assert_ok!(Swapping::update_affiliate_withdrawal_address(
+				OriginTrait::signed(ALICE),
+				BROKER,
+				SHORT_ID,
+				eth_address(&new_key),
+				signature,
+			));
+
+			assert_eq!(
+				AffiliateAccountDetails::<Test>::get(BROKER, affiliate_id)
+					.unwrap()
+					.withdrawal_address,
+				eth_address(&new_key)
+			);
+			System::assert_has_event(RuntimeEvent::Swapping(
+				Event::<Test>::AffiliateWithdrawalAddressUpdated {
+					broker_id: BROKER,
+					affiliate_id,
+					withdrawal_address: eth_address(&new_key),
+				},
+			));
+
+			// The same signature can't be replayed.
+			assert_noop!(
+				Swapping::update_affiliate_withdrawal_address(
+					OriginTrait::signed(ALICE),
+					BROKER,
+					SHORT_ID,
+					eth_address(&new_key),
+					signature,
+				),
+				Error::<Test>::InvalidAffiliateSignature
+			);
+		});
+	}
+
+	#[test]
+	fn affiliate_can_withdraw_its_fees() {
+		new_test_ext().execute_with(|| {
+			const SHORT_ID: AffiliateShortId = AffiliateShortId(0);
+			const BALANCE: AssetAmount = 200;
+			let key = ecdsa::Pair::from_seed(&[1u8; 32]);
+
+			assert_ok!(Swapping::register_affiliate(
+				OriginTrait::signed(BROKER),
+				eth_address(&key)
+			));
+			let affiliate_id = AffiliateIdMapping::<Test>::get(BROKER, SHORT_ID).unwrap();
+			MockBalance::credit_account(&affiliate_id, Asset::Usdc, BALANCE);
+
+			assert_noop!(
+				Swapping::signed_affiliate_withdrawal_request(
+					OriginTrait::signed(ALICE),
+					BROKER,
+					AffiliateShortId(1),
+					sign_action(&key, &affiliate_id, &AffiliateAction::Withdraw),
+				),
+				Error::<Test>::AffiliateNotRegisteredForBroker
+			);
+
+			// A signature for a different action is rejected.
+			assert_noop!(
+				Swapping::signed_affiliate_withdrawal_request(
+					OriginTrait::signed(ALICE),
+					BROKER,
+					SHORT_ID,
+					sign_action(
+						&key,
+						&affiliate_id,
+						&AffiliateAction::UpdateWithdrawalAddress(Default::default())
+					),
+				),
+				Error::<Test>::InvalidAffiliateSignature
+			);
+
+			assert_ok!(Swapping::signed_affiliate_withdrawal_request(
+				OriginTrait::signed(ALICE),
+				BROKER,
+				SHORT_ID,
+				sign_action(&key, &affiliate_id, &AffiliateAction::Withdraw),
+			));
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::WithdrawalRequested {
+					account_id,
+					egress_amount: BALANCE,
+					..
+				}) if *account_id == affiliate_id
+			);
+			assert_eq!(MockBalance::get_balance(&affiliate_id, Asset::Usdc), 0);
+			assert_eq!(AffiliateNonces::<Test>::get(affiliate_id), 1);
+
+			let egresses = MockEgressHandler::<Ethereum>::get_scheduled_egresses();
+			assert_eq!(egresses.len(), 1);
+			assert_eq!(egresses.first().unwrap().amount(), BALANCE);
+		});
+	}
 }
@@ -0,0 +1,204 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use cf_chains::RefundParametersExtendedEncoded;
+use cf_traits::{
+	mocks::{
+		balance_api::MockBalance, deposit_handler::MockDepositHandler,
+		fund_account_from_swap::MockFundAccountFromSwap,
+	},
+	SwapOutputActionEncoded,
+};
+
+fn init_funding_swap(refund_address: ForeignChainAddress) {
+	init_funding_swap_with_min_price(refund_address, Default::default());
+}
+
+fn init_funding_swap_with_min_price(refund_address: ForeignChainAddress, min_price: Price) {
+	Swapping::init_swap_request(
+		Asset::Btc,
+		INPUT_AMOUNT,
+		Asset::Flip,
+		SwapRequestType::Regular {
+			output_action: SwapOutputAction::FundAccount { account_id: ALICE },
+		},
+		Default::default(),
+		Some(RefundParametersExtended {
+			retry_duration: 100,
+			refund_destination: AccountOrAddress::ExternalAddress(refund_address),
+			min_price,
+		}),
+		None,
+		SwapOrigin::Vault {
+			tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+			broker_id: Some(BROKER),
+		},
+	);
+}
+
+#[test]
+fn can_open_account_funding_channel() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::request_account_funding_deposit_address(
+			RuntimeOrigin::signed(BROKER),
+			Asset::Btc,
+			ALICE,
+			0,
+			0,
+			Default::default(),
+			REFUND_PARAMS,
+			None,
+		));
+
+		let channels = MockDepositHandler::<AnyChain, Test>::get_swap_channels();
+		assert_eq!(channels.len(), 1);
+		assert_eq!(channels[0].destination_asset, Asset::Flip);
+		assert_eq!(channels[0].funded_account, Some(ALICE));
+
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::AccountFundingDepositAddressReady {
+				source_asset: Asset::Btc,
+				account_id: ALICE,
+				broker_id: BROKER,
+				..
+			})
+		);
+
+		assert_noop!(
+			Swapping::request_account_funding_deposit_address(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Btc,
+				ALICE,
+				0,
+				0,
+				Default::default(),
+				REFUND_PARAMS,
+				None,
+			),
+			sp_runtime::traits::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn swap_output_is_added_to_account_funds() {
+	new_test_ext()
+		.execute_with(|| init_funding_swap((*EVM_OUTPUT_ADDRESS).clone()))
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			let funded_accounts = MockFundAccountFromSwap::<u64>::funded_accounts();
+			assert_eq!(funded_accounts.len(), 1);
+			let (account_id, amount) = funded_accounts[0];
+			assert_eq!(account_id, ALICE);
+			assert!(amount > 0);
+
+			assert!(MockEgressHandler::<AnyChain>::get_scheduled_egresses().is_empty());
+			assert_eq!(MockBalance::get_balance(&ALICE, Asset::Flip), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::AccountFundedFromSwap {
+					swap_request_id: SwapRequestId(1),
+					account_id: ALICE,
+					amount: funded_amount,
+				}) if *funded_amount == amount
+			);
+		});
+}
+
+#[test]
+fn swap_output_is_refunded_if_funding_fails() {
+	new_test_ext()
+		.execute_with(|| {
+			MockFundAccountFromSwap::<u64>::set_should_fail(true);
+			init_funding_swap((*EVM_OUTPUT_ADDRESS).clone());
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert!(MockFundAccountFromSwap::<u64>::funded_accounts().is_empty());
+			assert_eq!(MockBalance::get_balance(&ALICE, Asset::Flip), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+					swap_request_id: SwapRequestId(1),
+					asset: Asset::Flip,
+					refund_reason: SwapRefundReason::AccountFundingFailed,
+					..
+				})
+			);
+		});
+}
+
+#[test]
+fn swap_output_is_swapped_back_if_funding_fails_and_refund_address_is_not_on_ethereum() {
+	let refund_address = ForeignChainAddress::Dot(PolkadotAccountId::from_aliased([4; 32]));
+	new_test_ext()
+		.execute_with(|| {
+			MockFundAccountFromSwap::<u64>::set_should_fail(true);
+			init_funding_swap(refund_address.clone());
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert!(MockFundAccountFromSwap::<u64>::funded_accounts().is_empty());
+			assert_eq!(MockBalance::get_balance(&ALICE, Asset::Flip), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequested {
+					swap_request_id: SwapRequestId(2),
+					input_asset: Asset::Flip,
+					output_asset: Asset::Btc,
+					origin: SwapOrigin::Vault { broker_id: Some(BROKER), .. },
+					request_type: SwapRequestTypeEncoded::Regular {
+						output_action: SwapOutputActionEncoded::Egress { .. },
+					},
+					broker_fees,
+					refund_parameters: Some(RefundParametersExtendedEncoded {
+						retry_duration: 100,
+						refund_destination: AccountOrAddress::InternalAccount(ALICE),
+						..
+					}),
+					..
+				}) if broker_fees.is_empty()
+			);
+			assert!(!FundingSwapOrigins::<Test>::contains_key(SwapRequestId(1)));
+		});
+}
+
+#[test]
+fn min_price_is_inverted_for_swap_back_if_funding_fails() {
+	let refund_address = ForeignChainAddress::Dot(PolkadotAccountId::from_aliased([4; 32]));
+	new_test_ext()
+		.execute_with(|| {
+			MockFundAccountFromSwap::<u64>::set_should_fail(true);
+			init_funding_swap_with_min_price(
+				refund_address.clone(),
+				U256::from(2) << PRICE_FRACTIONAL_BITS,
+			);
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequested {
+					swap_request_id: SwapRequestId(2),
+					input_asset: Asset::Flip,
+					refund_parameters: Some(RefundParametersExtendedEncoded { min_price, .. }),
+					..
+				}) if *min_price == U256::one() << (PRICE_FRACTIONAL_BITS - 1)
+			);
+		});
+}
@@ -0,0 +1,186 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const DEFAULT_BROKER_COMMISSION: BasisPoints = 20;
+const DEFAULT_RETRY_DURATION: BlockNumber = 10;
+const DEFAULT_BOOST_FEE: BasisPoints = 5;
+
+fn broker_defaults() -> BrokerChannelDefaults {
+	BrokerChannelDefaults {
+		broker_commission: DEFAULT_BROKER_COMMISSION,
+		retry_duration: DEFAULT_RETRY_DURATION,
+		boost_fee: DEFAULT_BOOST_FEE,
+		dca_parameters: Some(DcaParameters { number_of_chunks: 3, chunk_interval: 4 }),
+	}
+}
+
+fn request_channel(
+	broker_commission: Option<BasisPoints>,
+	boost_fee: Option<BasisPoints>,
+	retry_duration: Option<BlockNumber>,
+	dca_parameters: Option<DcaParameters>,
+) -> DispatchResult {
+	Swapping::request_swap_deposit_address_with_broker_defaults(
+		RuntimeOrigin::signed(BROKER),
+		INPUT_ASSET,
+		OUTPUT_ASSET,
+		EncodedAddress::Eth(Default::default()),
+		broker_commission,
+		None,
+		boost_fee,
+		Default::default(),
+		REFUND_PARAMS.refund_address,
+		REFUND_PARAMS.min_price,
+		retry_duration,
+		dca_parameters,
+	)
+}
+
+#[test]
+fn broker_can_set_and_remove_channel_defaults() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::set_broker_channel_defaults(
+			RuntimeOrigin::signed(BROKER),
+			Some(broker_defaults())
+		));
+		assert_eq!(BrokerChannelDefaultParameters::<Test>::get(BROKER), Some(broker_defaults()));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::BrokerChannelDefaultsSet {
+				broker_id: BROKER,
+				defaults: Some(_),
+			})
+		);
+
+		assert_ok!(Swapping::set_broker_channel_defaults(RuntimeOrigin::signed(BROKER), None));
+		assert_eq!(BrokerChannelDefaultParameters::<Test>::get(BROKER), None);
+
+		// Only brokers can register defaults:
+		assert_noop!(
+			Swapping::set_broker_channel_defaults(
+				RuntimeOrigin::signed(ALICE),
+				Some(broker_defaults())
+			),
+			sp_runtime::traits::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn invalid_channel_defaults_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let set_defaults = |defaults: BrokerChannelDefaults| {
+			Swapping::set_broker_channel_defaults(RuntimeOrigin::signed(BROKER), Some(defaults))
+		};
+
+		assert_noop!(
+			set_defaults(BrokerChannelDefaults {
+				broker_commission: BrokerCommissionLimits::<Test>::get().max_bps + 1,
+				..broker_defaults()
+			}),
+			Error::<Test>::BrokerCommissionBpsTooHigh
+		);
+		assert_noop!(
+			set_defaults(BrokerChannelDefaults {
+				retry_duration: MaxSwapRetryDurationBlocks::<Test>::get() + 1,
+				..broker_defaults()
+			}),
+			Error::<Test>::RetryDurationTooHigh
+		);
+		assert_noop!(
+			set_defaults(BrokerChannelDefaults {
+				dca_parameters: Some(DcaParameters { number_of_chunks: 0, chunk_interval: 2 }),
+				..broker_defaults()
+			}),
+			Error::<Test>::ZeroNumberOfChunksNotAllowed
+		);
+		assert_noop!(
+			set_defaults(BrokerChannelDefaults {
+				boost_fee: u8::MAX as BasisPoints + 1,
+				..broker_defaults()
+			}),
+			Error::<Test>::BoostFeeTooHigh
+		);
+	});
+}
+
+#[test]
+fn omitted_channel_parameters_are_filled_in_from_defaults() {
+	new_test_ext().execute_with(|| {
+		// Without defaults, the broker commission, boost fee and retry duration must be provided:
+		assert_noop!(
+			request_channel(None, Some(DEFAULT_BOOST_FEE), Some(DEFAULT_RETRY_DURATION), None),
+			Error::<Test>::NoBrokerChannelDefaults
+		);
+		assert_noop!(
+			request_channel(Some(0), None, Some(DEFAULT_RETRY_DURATION), None),
+			Error::<Test>::NoBrokerChannelDefaults
+		);
+		assert_noop!(
+			request_channel(Some(0), Some(DEFAULT_BOOST_FEE), None, None),
+			Error::<Test>::NoBrokerChannelDefaults
+		);
+		assert_ok!(request_channel(Some(0), Some(0), Some(0), None));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+				broker_commission_rate: 0,
+				boost_fee: 0,
+				dca_parameters: None,
+				..
+			})
+		);
+
+		System::reset_events();
+		assert_ok!(Swapping::set_broker_channel_defaults(
+			RuntimeOrigin::signed(BROKER),
+			Some(broker_defaults())
+		));
+
+		assert_ok!(request_channel(None, None, None, None));
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::BrokerChannelDefaultsSet { .. }),
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+				broker_commission_rate: DEFAULT_BROKER_COMMISSION,
+				boost_fee: DEFAULT_BOOST_FEE,
+				refund_parameters: ChannelRefundParametersEncoded {
+					retry_duration: DEFAULT_RETRY_DURATION,
+					..
+				},
+				dca_parameters: Some(DcaParameters { number_of_chunks: 3, chunk_interval: 4 }),
+				..
+			})
+		);
+
+		// Explicitly provided parameters take precedence over the defaults:
+		let dca_parameters = DcaParameters { number_of_chunks: 2, chunk_interval: 2 };
+		System::reset_events();
+		assert_ok!(request_channel(Some(5), Some(0), Some(1), Some(dca_parameters.clone())));
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+				broker_commission_rate: 5,
+				boost_fee: 0,
+				refund_parameters: ChannelRefundParametersEncoded { retry_duration: 1, .. },
+				dca_parameters: Some(ref dca_params_in_event),
+				..
+			}) if dca_params_in_event == &dca_parameters
+		);
+	});
+}
@@ -0,0 +1,140 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const SWAP_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+
+fn insert_swap_with_refund_params(dca_params: Option<DcaParameters>) {
+	insert_swaps(&[TestSwapParams::new(
+		dca_params,
+		Some(TestRefundParams {
+			retry_duration: DEFAULT_SWAP_RETRY_DELAY_BLOCKS,
+			min_output: INPUT_AMOUNT,
+		}),
+		false,
+	)]);
+}
+
+#[test]
+fn broker_can_cancel_swap_before_execution() {
+	new_test_ext()
+		.execute_with(|| {
+			insert_swap_with_refund_params(None);
+			assert_eq!(SwapRequestBrokers::<Test>::get(SWAP_REQUEST_ID), Some(BROKER));
+			assert_eq!(ScheduledSwapBlocks::<Test>::get(SwapId(1)), Some(SWAP_BLOCK));
+
+			assert_ok!(Swapping::cancel_swap_request(
+				RuntimeOrigin::signed(BROKER),
+				SWAP_REQUEST_ID
+			));
+
+			assert_eq!(SwapRequests::<Test>::get(SWAP_REQUEST_ID), None);
+			assert_eq!(SwapRequestBrokers::<Test>::get(SWAP_REQUEST_ID), None);
+			assert!(!SwapQueue::<Test>::contains_key(SWAP_BLOCK));
+			assert_eq!(ScheduledSwapBlocks::<Test>::get(SwapId(1)), None);
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequestCancelled {
+					swap_request_id: SWAP_REQUEST_ID,
+					broker_id: BROKER,
+				})
+			);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::Cancelled,
+					asset: INPUT_ASSET,
+					amount: INPUT_AMOUNT,
+					..
+				})
+			);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
+					swap_request_id: SWAP_REQUEST_ID
+				})
+			);
+		})
+		.then_process_blocks_until_block(SWAP_BLOCK)
+		.then_execute_with(|_| {
+			assert!(Swaps::get().is_empty(), "Cancelled swap must not be executed");
+		});
+}
+
+#[test]
+fn cannot_cancel_swap_once_execution_started() {
+	const CHUNK_INTERVAL: u32 = 3;
+
+	new_test_ext()
+		.execute_with(|| {
+			insert_swap_with_refund_params(Some(DcaParameters {
+				number_of_chunks: 2,
+				chunk_interval: CHUNK_INTERVAL,
+			}));
+		})
+		.then_process_blocks_until_block(SWAP_BLOCK)
+		.then_execute_with(|_| {
+			// The first chunk has been executed:
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(1),
+					..
+				})
+			);
+
+			assert_noop!(
+				Swapping::cancel_swap_request(RuntimeOrigin::signed(BROKER), SWAP_REQUEST_ID),
+				Error::<Test>::SwapExecutionAlreadyStarted
+			);
+		});
+}
+
+#[test]
+fn only_originating_broker_can_cancel_swap() {
+	new_test_ext().execute_with(|| {
+		const OTHER_BROKER: u64 = BROKER + 1;
+		<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_broker(&OTHER_BROKER)
+			.unwrap();
+
+		insert_swap_with_refund_params(None);
+
+		assert_noop!(
+			Swapping::cancel_swap_request(RuntimeOrigin::signed(OTHER_BROKER), SWAP_REQUEST_ID),
+			Error::<Test>::NotAuthorisedToUpdateSwapRequest
+		);
+		assert_noop!(
+			Swapping::cancel_swap_request(RuntimeOrigin::signed(ALICE), SWAP_REQUEST_ID),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn cannot_cancel_swap_without_refund_parameters() {
+	new_test_ext().execute_with(|| {
+		insert_swaps(&[TestSwapParams::new(None, None, false)]);
+
+		assert_noop!(
+			Swapping::cancel_swap_request(RuntimeOrigin::signed(BROKER), SWAP_REQUEST_ID),
+			Error::<Test>::MissingRefundParameters
+		);
+	});
+}
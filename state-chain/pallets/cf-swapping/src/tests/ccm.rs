@@ -229,3 +229,72 @@ fn ccm_principal_swap_only() {
 			assert_eq!(CollectedRejectedFunds::<Test>::get(OUTPUT_ASSET), 0);
 		});
 }
+
+#[test]
+fn refunds_can_carry_a_ccm_back_to_the_source_contract() {
+	let refund_ccm = CcmDepositMetadata {
+		source_chain: ForeignChain::Ethereum,
+		source_address: None,
+		channel_metadata: CcmChannelMetadata {
+			message: vec![0x02].try_into().unwrap(),
+			gas_budget: GAS_BUDGET,
+			ccm_additional_data: Default::default(),
+		},
+	};
+
+	new_test_ext()
+		.execute_with(|| {
+			Swapping::init_swap_request(
+				INPUT_ASSET,
+				INPUT_AMOUNT,
+				OUTPUT_ASSET,
+				SwapRequestType::Regular {
+					output_action: SwapOutputAction::Egress {
+						ccm_deposit_metadata: Some(generate_ccm_deposit()),
+						output_address: (*EVM_OUTPUT_ADDRESS).clone(),
+					},
+				},
+				Default::default(),
+				// The minimum price can't be met, so the swap will be refunded:
+				Some(
+					TestRefundParams {
+						retry_duration: 0,
+						min_output: INPUT_AMOUNT * DEFAULT_SWAP_RATE + 1,
+					}
+					.into_extended_params(INPUT_AMOUNT),
+				),
+				None,
+				SwapOrigin::Vault {
+					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+					broker_id: Some(BROKER),
+				},
+			);
+			Swapping::set_refund_ccm_metadata(SWAP_REQUEST_ID, refund_ccm.clone());
+			assert_eq!(SwapRequestRefundCcm::<Test>::get(SWAP_REQUEST_ID), Some(refund_ccm));
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					asset: INPUT_ASSET,
+					refund_reason: SwapRefundReason::PriceLimitExceeded,
+					..
+				})
+			);
+
+			assert!(matches!(
+				MockEgressHandler::<AnyChain>::get_scheduled_egresses().as_slice(),
+				[MockEgressParameter::Ccm {
+					asset: INPUT_ASSET,
+					destination_address,
+					message,
+					gas_budget: GAS_BUDGET,
+					..
+				}] if *destination_address == ForeignChainAddress::Eth([10; 20].into()) &&
+					message.to_vec() == vec![0x02]
+			));
+			assert_eq!(SwapRequestRefundCcm::<Test>::get(SWAP_REQUEST_ID), None);
+		});
+}
@@ -21,7 +21,7 @@ fn can_update_all_config_items() {
 	new_test_ext().execute_with(|| {
 		const NEW_MAX_SWAP_AMOUNT_BTC: Option<AssetAmount> = Some(100);
 		const NEW_MAX_SWAP_AMOUNT_DOT: Option<AssetAmount> = Some(69);
-		let new_swap_retry_delay = BlockNumberFor::<Test>::from(1234u32);
+		let new_swap_retry_delay = BlockNumberFor::<Test>::from(12u32);
 		let new_flip_buy_interval = BlockNumberFor::<Test>::from(5678u32);
 		const NEW_MAX_SWAP_RETRY_DURATION: u32 = 69_u32;
 		const MAX_SWAP_REQUEST_DURATION: u32 = 420_u32;
@@ -29,6 +29,19 @@ fn can_update_all_config_items() {
 		const NEW_MINIMUM_NETWORK_FEE: AssetAmount = 10;
 		const NEW_INTERNAL_SWAP_NETWORK_FEE: Permill = Permill::from_percent(5);
 		const NEW_INTERNAL_SWAP_MINIMUM_NETWORK_FEE: AssetAmount = 50;
+		const NEW_MAX_SWAP_SCHEDULING_HORIZON: u32 = 1_000;
+		const NEW_MAX_SWAP_PRICE_IMPACT: BasisPoints = 250;
+		const NEW_BROKER_NETWORK_FEE_DISCOUNT: Permill = Permill::from_percent(20);
+		const NEW_SWAP_VOLUME_LIMIT: SwapVolumeLimit = SwapVolumeLimit {
+			max_volume: 1_000_000,
+			window_blocks: 600,
+			on_exceeded: SwapVolumeLimitAction::Refund,
+		};
+		const NEW_BROKER_COMMISSION_BOUNDS: BrokerCommissionBounds =
+			BrokerCommissionBounds { min_bps: 5, max_bps: 500 };
+		const NEW_ASSET_BROKER_COMMISSION_BOUNDS: BrokerCommissionBounds =
+			BrokerCommissionBounds { min_bps: 0, max_bps: 200 };
+		const NEW_SWAP_DELAY: u32 = 4;
 
 		NetworkFee::set(Permill::from_perthousand(1));
 
@@ -46,6 +59,14 @@ fn can_update_all_config_items() {
 			InternalSwapMinimumNetworkFee::<Test>::get(),
 			NEW_INTERNAL_SWAP_MINIMUM_NETWORK_FEE
 		);
+		assert_ne!(MaxSwapSchedulingHorizonBlocks::<Test>::get(), NEW_MAX_SWAP_SCHEDULING_HORIZON);
+		assert!(MaxSwapPriceImpact::<Test>::get(Asset::Eth).is_none());
+		assert_ne!(BrokerNetworkFeeDiscounts::<Test>::get(BROKER), NEW_BROKER_NETWORK_FEE_DISCOUNT);
+		assert!(!AdaptiveDcaEnabled::<Test>::get());
+		assert!(SwapVolumeLimits::<Test>::get(Asset::Btc).is_none());
+		assert_ne!(BrokerCommissionLimits::<Test>::get(), NEW_BROKER_COMMISSION_BOUNDS);
+		assert!(AssetBrokerCommissionLimits::<Test>::get(Asset::Btc).is_none());
+		assert_ne!(SwapDelayBlocks::<Test>::get(), NEW_SWAP_DELAY);
 
 		// Define the updates in a reusable vec
 		let updates = vec![
@@ -70,6 +91,28 @@ fn can_update_all_config_items() {
 			PalletConfigUpdate::SetInternalSwapMinimumNetworkFee {
 				min_fee: NEW_INTERNAL_SWAP_MINIMUM_NETWORK_FEE,
 			},
+			PalletConfigUpdate::SetMaxSwapSchedulingHorizon {
+				blocks: NEW_MAX_SWAP_SCHEDULING_HORIZON,
+			},
+			PalletConfigUpdate::SetMaxSwapPriceImpact {
+				asset: Asset::Eth,
+				max_impact: Some(NEW_MAX_SWAP_PRICE_IMPACT),
+			},
+			PalletConfigUpdate::SetBrokerNetworkFeeDiscount {
+				broker_id: BROKER,
+				discount: NEW_BROKER_NETWORK_FEE_DISCOUNT,
+			},
+			PalletConfigUpdate::SetAdaptiveDca { enabled: true },
+			PalletConfigUpdate::SetSwapVolumeLimit {
+				asset: Asset::Btc,
+				limit: Some(NEW_SWAP_VOLUME_LIMIT),
+			},
+			PalletConfigUpdate::SetBrokerCommissionBounds { bounds: NEW_BROKER_COMMISSION_BOUNDS },
+			PalletConfigUpdate::SetBrokerCommissionBoundsForAsset {
+				asset: Asset::Btc,
+				bounds: Some(NEW_ASSET_BROKER_COMMISSION_BOUNDS),
+			},
+			PalletConfigUpdate::SetSwapDelay { blocks: NEW_SWAP_DELAY },
 		];
 
 		// Update all config items at the same time
@@ -92,6 +135,17 @@ fn can_update_all_config_items() {
 			InternalSwapMinimumNetworkFee::<Test>::get(),
 			NEW_INTERNAL_SWAP_MINIMUM_NETWORK_FEE
 		);
+		assert_eq!(MaxSwapSchedulingHorizonBlocks::<Test>::get(), NEW_MAX_SWAP_SCHEDULING_HORIZON);
+		assert_eq!(MaxSwapPriceImpact::<Test>::get(Asset::Eth), Some(NEW_MAX_SWAP_PRICE_IMPACT));
+		assert_eq!(BrokerNetworkFeeDiscounts::<Test>::get(BROKER), NEW_BROKER_NETWORK_FEE_DISCOUNT);
+		assert!(AdaptiveDcaEnabled::<Test>::get());
+		assert_eq!(SwapVolumeLimits::<Test>::get(Asset::Btc), Some(NEW_SWAP_VOLUME_LIMIT));
+		assert_eq!(BrokerCommissionLimits::<Test>::get(), NEW_BROKER_COMMISSION_BOUNDS);
+		assert_eq!(
+			AssetBrokerCommissionLimits::<Test>::get(Asset::Btc),
+			Some(NEW_ASSET_BROKER_COMMISSION_BOUNDS)
+		);
+		assert_eq!(SwapDelayBlocks::<Test>::get(), NEW_SWAP_DELAY);
 
 		// Check that the PalletConfigUpdate event was emitted for each update
 		for update in updates {
@@ -298,6 +352,149 @@ fn broker_bps_is_limited() {
 	});
 }
 
+#[test]
+fn broker_commission_bounds_are_enforced() {
+	new_test_ext().execute_with(|| {
+		let open_channel = |source_asset: Asset, broker_commission: BasisPoints| {
+			Swapping::request_swap_deposit_address_with_affiliates(
+				RuntimeOrigin::signed(BROKER),
+				source_asset,
+				Asset::Usdc,
+				EncodedAddress::Eth(Default::default()),
+				broker_commission,
+				None,
+				0,
+				Default::default(),
+				REFUND_PARAMS,
+				None,
+			)
+		};
+
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![
+				PalletConfigUpdate::SetBrokerCommissionBounds {
+					bounds: BrokerCommissionBounds { min_bps: 10, max_bps: 100 },
+				},
+				PalletConfigUpdate::SetBrokerCommissionBoundsForAsset {
+					asset: Asset::Flip,
+					bounds: Some(BrokerCommissionBounds { min_bps: 0, max_bps: 50 }),
+				},
+			]
+			.try_into()
+			.unwrap()
+		));
+
+		// Global bounds apply to assets without an override:
+		assert_noop!(open_channel(Asset::Eth, 9), Error::<Test>::BrokerCommissionBpsTooLow);
+		assert_noop!(open_channel(Asset::Eth, 101), Error::<Test>::BrokerCommissionBpsTooHigh);
+		assert_ok!(open_channel(Asset::Eth, 10));
+		assert_ok!(open_channel(Asset::Eth, 100));
+
+		// The per-asset override takes precedence:
+		assert_noop!(open_channel(Asset::Flip, 51), Error::<Test>::BrokerCommissionBpsTooHigh);
+		assert_ok!(open_channel(Asset::Flip, 0));
+
+		// Removing the override restores the global bounds:
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetBrokerCommissionBoundsForAsset {
+				asset: Asset::Flip,
+				bounds: None,
+			}]
+			.try_into()
+			.unwrap()
+		));
+		assert_noop!(open_channel(Asset::Flip, 0), Error::<Test>::BrokerCommissionBpsTooLow);
+		assert_ok!(open_channel(Asset::Flip, 100));
+
+		// Invalid bounds are rejected:
+		for bounds in [
+			BrokerCommissionBounds { min_bps: 101, max_bps: 100 },
+			BrokerCommissionBounds { min_bps: 0, max_bps: MAX_BASIS_POINTS + 1 },
+		] {
+			assert_noop!(
+				Swapping::update_pallet_config(
+					OriginTrait::root(),
+					vec![PalletConfigUpdate::SetBrokerCommissionBounds { bounds }]
+						.try_into()
+						.unwrap()
+				),
+				Error::<Test>::InvalidBrokerCommissionBounds
+			);
+		}
+	});
+}
+
+#[test]
+fn swap_delay_and_retry_delay_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let update_config = |update: PalletConfigUpdate<Test>| {
+			Swapping::update_pallet_config(OriginTrait::root(), vec![update].try_into().unwrap())
+		};
+
+		for blocks in [0, MAX_SWAP_DELAY_BLOCKS + 1] {
+			assert_noop!(
+				update_config(PalletConfigUpdate::SetSwapDelay { blocks }),
+				Error::<Test>::InvalidSwapDelay
+			);
+		}
+		assert_noop!(
+			update_config(PalletConfigUpdate::SwapRetryDelay {
+				delay: (MAX_SWAP_RETRY_DELAY_BLOCKS + 1).into()
+			}),
+			Error::<Test>::SwapRetryDelayTooHigh
+		);
+		assert_ok!(update_config(PalletConfigUpdate::SwapRetryDelay {
+			delay: MAX_SWAP_RETRY_DELAY_BLOCKS.into()
+		}));
+
+		// Swaps are scheduled using the updated delay:
+		assert_ok!(update_config(PalletConfigUpdate::SetSwapDelay {
+			blocks: MAX_SWAP_DELAY_BLOCKS
+		}));
+		swap_with_custom_broker_fee(INPUT_ASSET, OUTPUT_ASSET, INPUT_AMOUNT, bounded_vec![]);
+		assert!(SwapQueue::<Test>::get(System::block_number() + u64::from(SWAP_DELAY_BLOCKS))
+			.is_empty());
+		assert_eq!(
+			SwapQueue::<Test>::get(System::block_number() + u64::from(MAX_SWAP_DELAY_BLOCKS)).len(),
+			1
+		);
+
+		// The minimum DCA chunk interval follows the swap delay:
+		assert_eq!(
+			<Swapping as cf_traits::SwapParameterValidation>::validate_dca_params(&DcaParameters {
+				number_of_chunks: 2,
+				chunk_interval: MAX_SWAP_DELAY_BLOCKS - 1,
+			}),
+			Err(Error::<Test>::ChunkIntervalTooLow.into())
+		);
+	});
+}
+
+#[test]
+fn max_swap_scheduling_horizon_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let update_config = |blocks| {
+			Swapping::update_pallet_config(
+				OriginTrait::root(),
+				vec![PalletConfigUpdate::SetMaxSwapSchedulingHorizon { blocks }]
+					.try_into()
+					.unwrap(),
+			)
+		};
+
+		for blocks in [0, MAX_SWAP_SCHEDULING_HORIZON_BLOCKS + 1] {
+			assert_noop!(update_config(blocks), Error::<Test>::InvalidMaxSwapSchedulingHorizon);
+		}
+		assert_ok!(update_config(MAX_SWAP_SCHEDULING_HORIZON_BLOCKS));
+		assert_eq!(
+			MaxSwapSchedulingHorizonBlocks::<Test>::get(),
+			MAX_SWAP_SCHEDULING_HORIZON_BLOCKS
+		);
+	});
+}
+
 #[test]
 fn cannot_swap_in_safe_mode() {
 	new_test_ext().execute_with(|| {
@@ -303,6 +303,7 @@ fn dca_with_fok_full_refund(is_ccm: bool) {
 				Test,
 				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
 					swap_request_id: SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::PriceLimitExceeded,
 					asset: INPUT_ASSET,
 					amount: INPUT_AMOUNT,
 					..
@@ -398,6 +399,7 @@ fn dca_with_fok_partial_refund(is_ccm: bool) {
 				Test,
 				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
 					swap_request_id: SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::PriceLimitExceeded,
 					asset: INPUT_ASSET,
 					amount: REFUNDED_AMOUNT,
 					..
@@ -803,3 +805,460 @@ fn test_dca_parameter_validation() {
 		);
 	});
 }
+
+#[test]
+fn broker_can_cancel_remaining_dca_chunks() {
+	const CHUNK_1_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+
+	const NUMBER_OF_CHUNKS: u32 = 4;
+	const CHUNK_AMOUNT: AssetAmount = INPUT_AMOUNT / NUMBER_OF_CHUNKS as u128;
+	const CHUNK_BROKER_FEE: AssetAmount = CHUNK_AMOUNT * BROKER_FEE_BPS as u128 / 10_000;
+	const CHUNK_OUTPUT: AssetAmount = (CHUNK_AMOUNT - CHUNK_BROKER_FEE) * DEFAULT_SWAP_RATE;
+
+	// Only the first (already scheduled) chunk is executed, the rest is refunded:
+	const REFUNDED_AMOUNT: AssetAmount = INPUT_AMOUNT - CHUNK_AMOUNT;
+
+	new_test_ext()
+		.execute_with(|| {
+			setup_dca_swap(
+				NUMBER_OF_CHUNKS,
+				CHUNK_INTERVAL,
+				Some(TestRefundParams {
+					retry_duration: DEFAULT_SWAP_RETRY_DELAY_BLOCKS,
+					min_output: 1,
+				}),
+				false,
+			);
+
+			assert_ok!(Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(BROKER),
+				SWAP_REQUEST_ID,
+				DcaUpdate::CancelRemainingChunks,
+				None,
+			));
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::Cancelled,
+					asset: INPUT_ASSET,
+					amount: REFUNDED_AMOUNT,
+					..
+				})
+			);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::DcaSwapRequestUpdated {
+					swap_request_id: SWAP_REQUEST_ID,
+					updated_by: BROKER,
+					update: DcaUpdate::CancelRemainingChunks,
+				})
+			);
+
+			assert_eq!(
+				get_dca_state(SWAP_REQUEST_ID),
+				DcaState {
+					status: DcaStatus::ChunkScheduled(1.into()),
+					remaining_input_amount: 0,
+					remaining_chunks: 0,
+					chunk_interval: CHUNK_INTERVAL,
+					accumulated_output_amount: 0,
+					network_fee_collected: 0,
+					accumulated_stable_amount: 0,
+				}
+			);
+
+			// Nothing left to cancel:
+			assert_noop!(
+				Swapping::update_dca_swap_request(
+					RuntimeOrigin::signed(BROKER),
+					SWAP_REQUEST_ID,
+					DcaUpdate::CancelRemainingChunks,
+					None,
+				),
+				Error::<Test>::NoRemainingDcaChunks
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_1_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(SwapRequests::<Test>::get(SWAP_REQUEST_ID), None);
+			assert_eq!(SwapRequestBrokers::<Test>::get(SWAP_REQUEST_ID), None);
+
+			assert_event_sequence!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(1),
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					asset: OUTPUT_ASSET,
+					amount: CHUNK_OUTPUT,
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
+					swap_request_id: SWAP_REQUEST_ID
+				}),
+			);
+		});
+}
+
+#[test]
+fn dca_chunk_interval_can_be_updated() {
+	const CHUNK_1_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const NEW_CHUNK_INTERVAL: u32 = CHUNK_INTERVAL + 5;
+
+	new_test_ext()
+		.execute_with(|| {
+			setup_dca_swap(4, CHUNK_INTERVAL, None, false);
+
+			assert_ok!(Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(BROKER),
+				SWAP_REQUEST_ID,
+				DcaUpdate::SetChunkInterval { chunk_interval: NEW_CHUNK_INTERVAL },
+				None,
+			));
+
+			assert_eq!(get_dca_state(SWAP_REQUEST_ID).chunk_interval, NEW_CHUNK_INTERVAL);
+
+			assert_noop!(
+				Swapping::update_dca_swap_request(
+					RuntimeOrigin::signed(BROKER),
+					SWAP_REQUEST_ID,
+					DcaUpdate::SetChunkInterval { chunk_interval: SWAP_DELAY_BLOCKS - 1 },
+					None,
+				),
+				Error::<Test>::ChunkIntervalTooLow
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_1_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(2),
+					execute_at,
+					..
+				}) if *execute_at == CHUNK_1_BLOCK + NEW_CHUNK_INTERVAL as u64
+			);
+		});
+}
+
+#[test]
+fn only_broker_or_refund_account_can_update_dca_swap() {
+	new_test_ext().execute_with(|| {
+		setup_dca_swap(4, CHUNK_INTERVAL, None, false);
+
+		assert_noop!(
+			Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(ALICE),
+				SWAP_REQUEST_ID,
+				DcaUpdate::CancelRemainingChunks,
+				None,
+			),
+			Error::<Test>::NotAuthorisedToUpdateSwapRequest
+		);
+
+		// Without an EVM refund address, there is nothing to check a signature against:
+		assert_noop!(
+			Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(ALICE),
+				SWAP_REQUEST_ID,
+				DcaUpdate::CancelRemainingChunks,
+				Some([0; 65]),
+			),
+			Error::<Test>::NotAuthorisedToUpdateSwapRequest
+		);
+
+		// The broker is authorised, but without refund parameters there is nowhere to refund to:
+		assert_noop!(
+			Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(BROKER),
+				SWAP_REQUEST_ID,
+				DcaUpdate::CancelRemainingChunks,
+				None,
+			),
+			Error::<Test>::MissingRefundParameters
+		);
+
+		assert_noop!(
+			Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(BROKER),
+				SwapRequestId(SWAP_REQUEST_ID.0 + 1),
+				DcaUpdate::CancelRemainingChunks,
+				None,
+			),
+			Error::<Test>::SwapRequestNotFound
+		);
+	});
+}
+
+#[test]
+fn evm_refund_address_can_update_dca_swap_with_signature() {
+	const NEW_CHUNK_INTERVAL: u32 = CHUNK_INTERVAL + 5;
+
+	let refund_key = ecdsa::Pair::from_seed(&[1; 32]);
+	let other_key = ecdsa::Pair::from_seed(&[2; 32]);
+
+	new_test_ext().execute_with(|| {
+		let mut swap = TestSwapParams::new(
+			Some(DcaParameters { number_of_chunks: 4, chunk_interval: CHUNK_INTERVAL }),
+			Some(TestRefundParams {
+				retry_duration: DEFAULT_SWAP_RETRY_DELAY_BLOCKS,
+				min_output: 1,
+			}),
+			false,
+		);
+		swap.refund_params.as_mut().unwrap().refund_destination =
+			AccountOrAddress::ExternalAddress(ForeignChainAddress::Eth(eth_address(&refund_key)));
+		insert_swaps(&[swap]);
+
+		let update = DcaUpdate::SetChunkInterval { chunk_interval: NEW_CHUNK_INTERVAL };
+		let payload = Swapping::dca_update_signing_payload(SWAP_REQUEST_ID, &update).unwrap();
+
+		assert_noop!(
+			Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(ALICE),
+				SWAP_REQUEST_ID,
+				update.clone(),
+				Some(sign(&other_key, &payload)),
+			),
+			Error::<Test>::InvalidDcaUpdateSignature
+		);
+
+		// Anyone can submit the update signed by the refund address:
+		assert_ok!(Swapping::update_dca_swap_request(
+			RuntimeOrigin::signed(ALICE),
+			SWAP_REQUEST_ID,
+			update.clone(),
+			Some(sign(&refund_key, &payload)),
+		));
+		assert_eq!(get_dca_state(SWAP_REQUEST_ID).chunk_interval, NEW_CHUNK_INTERVAL);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::DcaSwapRequestUpdated {
+				swap_request_id: SWAP_REQUEST_ID,
+				updated_by: ALICE,
+				..
+			})
+		);
+
+		// The signed update cannot be replayed:
+		assert_noop!(
+			Swapping::update_dca_swap_request(
+				RuntimeOrigin::signed(ALICE),
+				SWAP_REQUEST_ID,
+				update,
+				Some(sign(&refund_key, &payload)),
+			),
+			Error::<Test>::InvalidDcaUpdateSignature
+		);
+	});
+}
+
+#[test]
+fn swaps_are_split_to_respect_price_impact_limit() {
+	const MAX_IMPACT_BPS: BasisPoints = 100;
+
+	#[track_caller]
+	fn assert_requested_with_dca(swap_request_id: SwapRequestId, expected: Option<DcaParameters>) {
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapRequested { swap_request_id: id, dca_parameters, .. })
+				if *id == swap_request_id && *dca_parameters == expected
+		);
+	}
+
+	fn request_swap(dca_params: Option<DcaParameters>) -> SwapRequestId {
+		Swapping::init_swap_request(
+			INPUT_ASSET,
+			INPUT_AMOUNT,
+			OUTPUT_ASSET,
+			SwapRequestType::Regular {
+				output_action: SwapOutputAction::Egress {
+					output_address: (*EVM_OUTPUT_ADDRESS).clone(),
+					ccm_deposit_metadata: None,
+				},
+			},
+			Default::default(),
+			None,
+			dca_params,
+			SwapOrigin::Vault {
+				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+				broker_id: Some(BROKER),
+			},
+		)
+	}
+
+	new_test_ext().execute_with(|| {
+		// Swapping the full amount results in ~10% price impact:
+		PoolDepth::set(Some((INPUT_AMOUNT * 9) as f64));
+
+		let mandatory_weight = || {
+			System::block_weight()
+				.get(frame_support::dispatch::DispatchClass::Mandatory)
+				.ref_time()
+		};
+
+		// No limit set, so the swap is executed in one go:
+		let weight_before = mandatory_weight();
+		let id = request_swap(None);
+		assert_requested_with_dca(id, None);
+		assert_eq!(mandatory_weight(), weight_before);
+
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMaxSwapPriceImpact {
+				asset: OUTPUT_ASSET,
+				max_impact: Some(MAX_IMPACT_BPS),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		let id = request_swap(None);
+		assert_requested_with_dca(
+			id,
+			Some(DcaParameters { number_of_chunks: 10, chunk_interval: SWAP_DELAY_BLOCKS }),
+		);
+		// The swaps used to estimate the price impact are accounted for:
+		assert!(mandatory_weight() > weight_before);
+
+		// Existing DCA parameters are taken into account:
+		let id = request_swap(Some(DcaParameters {
+			number_of_chunks: 2,
+			chunk_interval: CHUNK_INTERVAL,
+		}));
+		assert_requested_with_dca(
+			id,
+			Some(DcaParameters { number_of_chunks: 10, chunk_interval: CHUNK_INTERVAL }),
+		);
+
+		// The number of chunks is capped by the max swap request duration:
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMaxSwapRequestDuration { blocks: CHUNK_INTERVAL * 3 }]
+				.try_into()
+				.unwrap()
+		));
+		let id = request_swap(Some(DcaParameters {
+			number_of_chunks: 2,
+			chunk_interval: CHUNK_INTERVAL,
+		}));
+		assert_requested_with_dca(
+			id,
+			Some(DcaParameters { number_of_chunks: 4, chunk_interval: CHUNK_INTERVAL }),
+		);
+
+		// No price impact limit for the stable asset:
+		assert_noop!(
+			Swapping::update_pallet_config(
+				OriginTrait::root(),
+				vec![PalletConfigUpdate::SetMaxSwapPriceImpact {
+					asset: STABLE_ASSET,
+					max_impact: Some(MAX_IMPACT_BPS),
+				}]
+				.try_into()
+				.unwrap()
+			),
+			Error::<Test>::InvalidPriceImpactLimit
+		);
+	});
+}
+
+#[test]
+fn adaptive_dca_finishes_early_when_liquidity_allows() {
+	const NUMBER_OF_CHUNKS: u32 = 4;
+	const CHUNK_1_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const CHUNK_2_BLOCK: u64 = CHUNK_1_BLOCK + CHUNK_INTERVAL as u64;
+
+	new_test_ext()
+		.execute_with(|| {
+			// Swapping the full amount results in ~10% price impact, so with a 6% limit the
+			// request can be completed in 2 chunks instead of 4:
+			PoolDepth::set(Some((INPUT_AMOUNT * 9) as f64));
+			assert_ok!(Swapping::update_pallet_config(
+				OriginTrait::root(),
+				vec![
+					PalletConfigUpdate::SetMaxSwapPriceImpact {
+						asset: OUTPUT_ASSET,
+						max_impact: Some(600),
+					},
+					PalletConfigUpdate::SetAdaptiveDca { enabled: true },
+				]
+				.try_into()
+				.unwrap()
+			));
+
+			insert_swaps(&[TestSwapParams::new(
+				Some(DcaParameters {
+					number_of_chunks: NUMBER_OF_CHUNKS,
+					chunk_interval: CHUNK_INTERVAL,
+				}),
+				None,
+				false,
+			)]);
+
+			// The first chunk is larger than a regular chunk, but doesn't include the full amount:
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::DcaChunkSized {
+					swap_request_id: SWAP_REQUEST_ID,
+					chunk_input_amount,
+					remaining_input_amount,
+					remaining_chunks: 3,
+				}) if *chunk_input_amount > INPUT_AMOUNT / NUMBER_OF_CHUNKS as u128
+					&& *remaining_input_amount == INPUT_AMOUNT - *chunk_input_amount
+					&& *remaining_input_amount > 0
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_1_BLOCK)
+		.then_execute_with(|_| {
+			// The rest of the input is swapped in the second chunk:
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::DcaChunkSized {
+					swap_request_id: SWAP_REQUEST_ID,
+					remaining_input_amount: 0,
+					remaining_chunks: 0,
+					..
+				})
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_2_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(SwapRequests::<Test>::get(SWAP_REQUEST_ID), None);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
+					swap_request_id: SWAP_REQUEST_ID
+				})
+			);
+		});
+}
+
+#[test]
+fn dca_chunks_are_not_adapted_unless_enabled() {
+	new_test_ext().execute_with(|| {
+		PoolDepth::set(Some((INPUT_AMOUNT * 9) as f64));
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMaxSwapPriceImpact {
+				asset: OUTPUT_ASSET,
+				max_impact: Some(600),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		setup_dca_swap(4, CHUNK_INTERVAL, None, false);
+
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Swapping(Event::DcaChunkSized { .. })
+		)));
+	});
+}
@@ -0,0 +1,74 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const INPUT_AMOUNT: AssetAmount = 1000;
+
+fn swap_btc_to_eth() -> AssetAmount {
+	Swapping::init_swap_request(
+		Asset::Btc,
+		INPUT_AMOUNT,
+		Asset::Eth,
+		SwapRequestType::Regular {
+			output_action: SwapOutputAction::Egress {
+				ccm_deposit_metadata: None,
+				output_address: ForeignChainAddress::Eth(H160::zero()),
+			},
+		},
+		Default::default(),
+		None,
+		None,
+		SwapOrigin::Vault {
+			tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+			broker_id: Some(BROKER),
+		},
+	);
+	Swapping::on_finalize(System::block_number() + SWAP_DELAY_BLOCKS as u64);
+
+	match &MockEgressHandler::<AnyChain>::get_scheduled_egresses()[..] {
+		[MockEgressParameter::Swap { asset: Asset::Eth, amount, .. }] => *amount,
+		egresses => panic!("Unexpected egresses: {egresses:?}"),
+	}
+}
+
+#[test]
+fn swaps_use_direct_pool_if_it_gives_a_better_output() {
+	new_test_ext().execute_with(|| {
+		NetworkFee::set(Permill::from_percent(1));
+		DirectPools::set(vec![(Asset::Btc, Asset::Eth)]);
+		// Better than the rate of swapping via the stable asset:
+		DirectSwapRate::set((DEFAULT_SWAP_RATE * DEFAULT_SWAP_RATE * 2) as f64);
+
+		// The stable value of the input is 2000, so the network fee is 20, covered by swapping
+		// 11 BTC (including a margin) via the stable asset. The remaining 2 USDC are swapped to
+		// ETH.
+		assert_eq!(swap_btc_to_eth(), 989 * 8 + 2 * DEFAULT_SWAP_RATE);
+		assert_eq!(CollectedNetworkFee::<Test>::get(), 20);
+	});
+}
+
+#[test]
+fn swaps_go_via_stable_asset_if_direct_pool_is_worse() {
+	new_test_ext().execute_with(|| {
+		NetworkFee::set(Permill::from_percent(1));
+		DirectPools::set(vec![(Asset::Btc, Asset::Eth)]);
+		DirectSwapRate::set((DEFAULT_SWAP_RATE * DEFAULT_SWAP_RATE - 1) as f64);
+
+		assert_eq!(swap_btc_to_eth(), (2000 - 20) * DEFAULT_SWAP_RATE);
+		assert_eq!(CollectedNetworkFee::<Test>::get(), 20);
+	});
+}
@@ -90,7 +90,7 @@ fn test_buy_back_flip() {
 		NetworkFee::set(NETWORK_FEE);
 
 		// Get some network fees, just like we did a swap.
-		let FeeTaken { remaining_amount, fee: network_fee } =
+		let FeeTaken { remaining_amount, fee: network_fee, .. } =
 			Swapping::take_network_fee(SWAP_AMOUNT, MinFeePolicy::NotEnforced);
 
 		// Sanity check the network fee.
@@ -185,7 +185,7 @@ fn test_network_fee_calculation() {
 			},
 		);
 
-		let FeeTaken { remaining_amount, fee } = Swapping::take_network_fee(
+		let FeeTaken { remaining_amount, fee, .. } = Swapping::take_network_fee(
 			chunk_amount,
 			MinFeePolicy::Enforced { swap_request_id: SWAP_REQUEST_ID },
 		);
@@ -577,6 +577,7 @@ fn input_amount_excludes_network_fee() {
 				input_asset: FROM_ASSET,
 				output_asset: TO_ASSET,
 				network_fee,
+				network_fee_discount: 0,
 				broker_fee: 0,
 				input_amount: expected_input_amount,
 				output_amount: expected_input_amount * DEFAULT_SWAP_RATE,
@@ -585,6 +586,80 @@ fn input_amount_excludes_network_fee() {
 		});
 }
 
+#[test]
+fn broker_network_fee_discount_is_applied() {
+	const AMOUNT: AssetAmount = 1_000;
+	const NETWORK_FEE: Permill = Permill::from_percent(1);
+	const DISCOUNT: Permill = Permill::from_percent(20);
+
+	NetworkFee::set(NETWORK_FEE);
+
+	new_test_ext()
+		.execute_with(|| {
+			assert_ok!(Swapping::update_pallet_config(
+				OriginTrait::root(),
+				bounded_vec![PalletConfigUpdate::SetBrokerNetworkFeeDiscount {
+					broker_id: BROKER,
+					discount: DISCOUNT,
+				}],
+			));
+
+			for broker_id in [Some(BROKER), None] {
+				Swapping::init_swap_request(
+					Asset::Usdc,
+					AMOUNT,
+					Asset::Flip,
+					SwapRequestType::Regular {
+						output_action: SwapOutputAction::Egress {
+							ccm_deposit_metadata: None,
+							output_address: ForeignChainAddress::Eth(Default::default()),
+						},
+					},
+					bounded_vec![],
+					None,
+					None,
+					SwapOrigin::Vault {
+						tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+						broker_id,
+					},
+				);
+			}
+		})
+		.then_process_blocks_until(|_| System::block_number() == 3)
+		.then_execute_with(|_| {
+			let full_fee = NETWORK_FEE * AMOUNT;
+			let discount = DISCOUNT * full_fee;
+
+			// The broker's swap only pays the discounted network fee:
+			System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::SwapExecuted {
+				swap_request_id: 1.into(),
+				swap_id: 1.into(),
+				input_asset: Asset::Usdc,
+				output_asset: Asset::Flip,
+				network_fee: full_fee - discount,
+				network_fee_discount: discount,
+				broker_fee: 0,
+				input_amount: AMOUNT - (full_fee - discount),
+				output_amount: (AMOUNT - (full_fee - discount)) * DEFAULT_SWAP_RATE,
+				intermediate_amount: None,
+			}));
+
+			// Swaps without a broker pay the full network fee:
+			System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::SwapExecuted {
+				swap_request_id: 2.into(),
+				swap_id: 2.into(),
+				input_asset: Asset::Usdc,
+				output_asset: Asset::Flip,
+				network_fee: full_fee,
+				network_fee_discount: 0,
+				broker_fee: 0,
+				input_amount: AMOUNT - full_fee,
+				output_amount: (AMOUNT - full_fee) * DEFAULT_SWAP_RATE,
+				intermediate_amount: None,
+			}));
+		});
+}
+
 #[test]
 fn withdraw_broker_fees() {
 	new_test_ext().execute_with(|| {
@@ -662,6 +737,7 @@ fn expect_earned_fees_to_be_recorded() {
 				swap_request_id: 1.into(),
 				swap_id: 1.into(),
 				network_fee: NETWORK_FEE_1,
+				network_fee_discount: 0,
 				broker_fee: ALICE_FEE_1,
 				input_amount: INPUT_AMOUNT,
 				input_asset: Asset::Flip,
@@ -687,6 +763,7 @@ fn expect_earned_fees_to_be_recorded() {
 				swap_request_id: 2.into(),
 				swap_id: 2.into(),
 				network_fee: NETWORK_FEE_2,
+				network_fee_discount: 0,
 				broker_fee: ALICE_FEE_2,
 				input_amount: AMOUNT_AFTER_FEES,
 				input_asset: Asset::Usdc,
@@ -718,6 +795,7 @@ fn expect_earned_fees_to_be_recorded() {
 				swap_request_id: 3.into(),
 				swap_id: 3.into(),
 				network_fee: NETWORK_FEE_3,
+				network_fee_discount: 0,
 				broker_fee: TOTAL_BROKER_FEES,
 				input_amount: INPUT_AMOUNT,
 				input_asset: Asset::ArbEth,
@@ -275,6 +275,7 @@ fn fok_swap_gets_refunded_due_to_price_limit(is_ccm: bool) {
 				Test,
 				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
 					swap_request_id: FOK_SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::PriceLimitExceeded,
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
@@ -420,6 +421,7 @@ fn fok_swap_gets_refunded_due_to_price_impact_protection(is_ccm: bool) {
 				RuntimeEvent::Swapping(Event::BatchSwapFailed { .. }),
 				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
 					swap_request_id: FOK_SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::DurationExpired,
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
@@ -465,6 +467,7 @@ fn fok_test_zero_refund_duration(is_ccm: bool) {
 				RuntimeEvent::Swapping(Event::BatchSwapFailed { .. }),
 				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
 					swap_request_id: SwapRequestId(1),
+					refund_reason: SwapRefundReason::DurationExpired,
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
@@ -0,0 +1,199 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use cf_test_utilities::assert_events_match;
+
+const REFERRAL_CODE: ReferralCode = *b"ALICE123";
+const REFERRER: u64 = ALICE;
+
+fn request_referred_channel(referral_code: ReferralCode) -> DispatchResult {
+	Swapping::request_referred_swap_deposit_address(
+		RuntimeOrigin::signed(BROKER),
+		INPUT_ASSET,
+		OUTPUT_ASSET,
+		EncodedAddress::Eth(Default::default()),
+		0,
+		None,
+		0,
+		Default::default(),
+		REFUND_PARAMS,
+		None,
+		referral_code,
+	)
+}
+
+fn swap_from_channel(channel_id: ChannelId) {
+	Swapping::init_swap_request(
+		INPUT_ASSET,
+		INPUT_AMOUNT,
+		OUTPUT_ASSET,
+		SwapRequestType::Regular {
+			output_action: SwapOutputAction::Egress {
+				ccm_deposit_metadata: None,
+				output_address: (*EVM_OUTPUT_ADDRESS).clone(),
+			},
+		},
+		Default::default(),
+		None,
+		None,
+		SwapOrigin::DepositChannel {
+			deposit_address: EncodedAddress::Eth(Default::default()),
+			channel_id,
+			deposit_block_height: 0,
+			broker_id: BROKER,
+		},
+	);
+}
+
+#[test]
+fn can_register_referral_code() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::register_referral_code(
+			RuntimeOrigin::signed(REFERRER),
+			REFERRAL_CODE
+		));
+
+		assert_eq!(ReferralCodes::<Test>::get(REFERRAL_CODE), Some(REFERRER));
+		assert_eq!(AccountReferralCodes::<Test>::get(REFERRER), Some(REFERRAL_CODE));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::ReferralCodeRegistered {
+				referral_code: REFERRAL_CODE,
+				account_id: REFERRER,
+			})
+		);
+
+		// Codes are unique, and each account can only register one:
+		assert_noop!(
+			Swapping::register_referral_code(RuntimeOrigin::signed(BROKER), REFERRAL_CODE),
+			Error::<Test>::ReferralCodeAlreadyRegistered
+		);
+		assert_noop!(
+			Swapping::register_referral_code(RuntimeOrigin::signed(REFERRER), *b"OTHERCOD"),
+			Error::<Test>::AccountAlreadyHasReferralCode
+		);
+	});
+}
+
+#[test]
+fn referred_channel_requires_known_referral_code() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(request_referred_channel(REFERRAL_CODE), Error::<Test>::UnknownReferralCode);
+
+		assert_ok!(Swapping::register_referral_code(RuntimeOrigin::signed(BROKER), REFERRAL_CODE));
+		assert_noop!(
+			request_referred_channel(REFERRAL_CODE),
+			Error::<Test>::SelfReferralNotAllowed
+		);
+	});
+}
+
+#[test]
+fn referrer_receives_share_of_network_fee() {
+	const NETWORK_FEE: Permill = Permill::from_percent(1);
+	const REFERRAL_SHARE: Permill = Permill::from_percent(25);
+	// The input asset is the stable asset, so the network fee is taken from the input amount:
+	const EXPECTED_NETWORK_FEE: AssetAmount = INPUT_AMOUNT / 100;
+	const EXPECTED_REWARD: AssetAmount = EXPECTED_NETWORK_FEE / 4;
+
+	new_test_ext()
+		.execute_with(|| {
+			NetworkFee::set(NETWORK_FEE);
+			assert_ok!(Swapping::update_pallet_config(
+				OriginTrait::root(),
+				vec![PalletConfigUpdate::SetReferralRewardShare { share: REFERRAL_SHARE }]
+					.try_into()
+					.unwrap()
+			));
+			assert_ok!(Swapping::register_referral_code(
+				RuntimeOrigin::signed(REFERRER),
+				REFERRAL_CODE
+			));
+			assert_ok!(request_referred_channel(REFERRAL_CODE));
+
+			let channel_id = assert_events_match!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapDepositChannelReferred {
+					channel_id,
+					referrer: REFERRER,
+					..
+				}) => channel_id
+			);
+
+			swap_from_channel(channel_id);
+			assert_eq!(SwapRequestReferrers::<Test>::get(SwapRequestId(1)), Some(REFERRER));
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					network_fee: EXPECTED_NETWORK_FEE,
+					..
+				})
+			);
+
+			// The user pays the regular network fee, part of which goes to the referrer:
+			assert_eq!(ReferralRewards::<Test>::get(REFERRER), EXPECTED_REWARD);
+			assert_eq!(CollectedNetworkFee::<Test>::get(), EXPECTED_NETWORK_FEE - EXPECTED_REWARD);
+			assert_eq!(SwapRequestReferrers::<Test>::get(SwapRequestId(1)), None);
+
+			assert_ok!(Swapping::claim_referral_rewards(RuntimeOrigin::signed(REFERRER)));
+			assert_eq!(get_broker_balance::<Test>(&REFERRER, STABLE_ASSET), EXPECTED_REWARD);
+			assert_eq!(ReferralRewards::<Test>::get(REFERRER), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::ReferralRewardsClaimed {
+					account_id: REFERRER,
+					amount: EXPECTED_REWARD,
+				})
+			);
+
+			assert_noop!(
+				Swapping::claim_referral_rewards(RuntimeOrigin::signed(REFERRER)),
+				Error::<Test>::NoReferralRewards
+			);
+		});
+}
+
+#[test]
+fn channel_referrer_expires() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::register_referral_code(
+			RuntimeOrigin::signed(REFERRER),
+			REFERRAL_CODE
+		));
+		assert_ok!(request_referred_channel(REFERRAL_CODE));
+
+		let channel_id = assert_events_match!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositChannelReferred {
+				channel_id,
+				..
+			}) => channel_id
+		);
+		let channel = (ForeignChain::from(INPUT_ASSET), channel_id);
+		let expires_at = System::block_number() + CHANNEL_PARAMETERS_RETENTION_BLOCKS as u64;
+
+		Swapping::on_initialize(expires_at - 1);
+		assert_eq!(ChannelReferrers::<Test>::get(channel), Some(REFERRER));
+
+		Swapping::on_initialize(expires_at);
+		assert_eq!(ChannelReferrers::<Test>::get(channel), None);
+		assert!(ChannelParameterExpiries::<Test>::get(expires_at).is_empty());
+	});
+}
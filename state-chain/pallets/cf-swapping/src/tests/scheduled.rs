@@ -0,0 +1,139 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use cf_test_utilities::assert_events_match;
+
+const EXECUTE_AT: u64 = INIT_BLOCK + 20;
+
+fn request_scheduled_channel(execute_at: u64) -> DispatchResult {
+	Swapping::request_scheduled_swap_deposit_address(
+		RuntimeOrigin::signed(BROKER),
+		INPUT_ASSET,
+		OUTPUT_ASSET,
+		EncodedAddress::Eth(Default::default()),
+		0,
+		None,
+		0,
+		Default::default(),
+		REFUND_PARAMS,
+		None,
+		execute_at,
+	)
+}
+
+fn swap_from_channel(channel_id: ChannelId) {
+	Swapping::init_swap_request(
+		INPUT_ASSET,
+		INPUT_AMOUNT,
+		OUTPUT_ASSET,
+		SwapRequestType::Regular {
+			output_action: SwapOutputAction::Egress {
+				ccm_deposit_metadata: None,
+				output_address: (*EVM_OUTPUT_ADDRESS).clone(),
+			},
+		},
+		Default::default(),
+		None,
+		None,
+		SwapOrigin::DepositChannel {
+			deposit_address: EncodedAddress::Eth(Default::default()),
+			channel_id,
+			deposit_block_height: 0,
+			broker_id: BROKER,
+		},
+	);
+}
+
+#[test]
+fn swaps_from_scheduled_channel_are_deferred() {
+	new_test_ext()
+		.execute_with(|| {
+			assert_ok!(request_scheduled_channel(EXECUTE_AT));
+
+			let channel_id = assert_events_match!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapDepositChannelScheduled {
+					channel_id,
+					execute_at: EXECUTE_AT,
+					..
+				}) => channel_id
+			);
+
+			swap_from_channel(channel_id);
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					execute_at: EXECUTE_AT,
+					..
+				})
+			);
+
+			channel_id
+		})
+		.then_process_blocks_until_block(EXECUTE_AT)
+		.then_execute_with(|channel_id| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					swap_request_id: SWAP_REQUEST_ID,
+					..
+				})
+			);
+
+			// Once the scheduled block is reached, new deposits are swapped as usual:
+			assert_eq!(
+				ScheduledChannelSwaps::<Test>::get((ForeignChain::from(INPUT_ASSET), channel_id)),
+				None
+			);
+			swap_from_channel(channel_id);
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapScheduled {
+					execute_at,
+					..
+				}) if *execute_at == EXECUTE_AT + SWAP_DELAY_BLOCKS as u64
+			);
+		});
+}
+
+#[test]
+fn scheduled_execution_block_must_be_within_horizon() {
+	new_test_ext().execute_with(|| {
+		let current_block = System::block_number();
+
+		assert_noop!(
+			request_scheduled_channel(current_block + SWAP_DELAY_BLOCKS as u64),
+			Error::<Test>::ScheduledExecutionTooSoon
+		);
+
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMaxSwapSchedulingHorizon { blocks: 100 }]
+				.try_into()
+				.unwrap()
+		));
+
+		assert_noop!(
+			request_scheduled_channel(current_block + 101),
+			Error::<Test>::ScheduledExecutionTooFarInFuture
+		);
+		assert_ok!(request_scheduled_channel(current_block + 100));
+	});
+}
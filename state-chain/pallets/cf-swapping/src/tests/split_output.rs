@@ -0,0 +1,158 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use cf_traits::mocks::deposit_handler::MockDepositHandler;
+use frame_support::dispatch::DispatchResult;
+
+fn request_split_channel(
+	destination_addresses: Vec<(EncodedAddress, BasisPoints)>,
+) -> DispatchResult {
+	Swapping::request_split_swap_deposit_address(
+		RuntimeOrigin::signed(BROKER),
+		Asset::Eth,
+		Asset::Usdc,
+		destination_addresses.try_into().unwrap(),
+		0,
+		0,
+		Default::default(),
+		REFUND_PARAMS,
+		None,
+	)
+}
+
+#[test]
+fn can_open_channel_with_split_output() {
+	new_test_ext().execute_with(|| {
+		let destination_addresses =
+			vec![(EncodedAddress::Eth([2; 20]), 7_000), (EncodedAddress::Eth([3; 20]), 3_000)];
+
+		assert_ok!(request_split_channel(destination_addresses.clone()));
+
+		let channels = MockDepositHandler::<AnyChain, Test>::get_swap_channels();
+		assert_eq!(channels.len(), 1);
+		assert_eq!(
+			channels[0].split_destinations,
+			vec![
+				(ForeignChainAddress::Eth([2; 20].into()), 7_000),
+				(ForeignChainAddress::Eth([3; 20].into()), 3_000),
+			]
+		);
+
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady { .. }),
+			RuntimeEvent::Swapping(Event::SwapDepositChannelOutputSplit {
+				source_chain: ForeignChain::Ethereum,
+				destination_addresses: ref addresses_in_event,
+				..
+			}) if *addresses_in_event == destination_addresses
+		);
+	});
+}
+
+#[test]
+fn rejects_invalid_output_split() {
+	new_test_ext().execute_with(|| {
+		for destination_addresses in [
+			// A single address is not a split:
+			vec![(EncodedAddress::Eth([2; 20]), 10_000)],
+			// Shares must add up to 100%:
+			vec![(EncodedAddress::Eth([2; 20]), 5_000), (EncodedAddress::Eth([3; 20]), 4_000)],
+			vec![(EncodedAddress::Eth([2; 20]), 6_000), (EncodedAddress::Eth([3; 20]), 5_000)],
+			// Every address must receive a share:
+			vec![
+				(EncodedAddress::Eth([2; 20]), 10_000),
+				(EncodedAddress::Eth([3; 20]), 0),
+				(EncodedAddress::Eth([4; 20]), 0),
+			],
+		] {
+			assert_noop!(
+				request_split_channel(destination_addresses),
+				Error::<Test>::InvalidOutputSplit
+			);
+		}
+
+		// Addresses must be valid for the destination asset:
+		assert_noop!(
+			request_split_channel(vec![
+				(EncodedAddress::Eth([2; 20]), 5_000),
+				(EncodedAddress::Dot([3; 32]), 5_000),
+			]),
+			Error::<Test>::IncompatibleAssetAndAddress
+		);
+	});
+}
+
+#[test]
+fn swap_output_is_split_between_destination_addresses() {
+	const SHARES: [BasisPoints; 3] = [3_333, 3_333, 3_334];
+	let addresses: Vec<ForeignChainAddress> =
+		(2u8..5).map(|i| ForeignChainAddress::Eth([i; 20].into())).collect();
+
+	new_test_ext()
+		.execute_with(|| {
+			Swapping::init_swap_request(
+				INPUT_ASSET,
+				INPUT_AMOUNT,
+				OUTPUT_ASSET,
+				SwapRequestType::Regular {
+					output_action: SwapOutputAction::SplitEgress {
+						output_addresses: addresses.iter().cloned().zip(SHARES).collect(),
+					},
+				},
+				Default::default(),
+				None,
+				None,
+				SwapOrigin::Vault {
+					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+					broker_id: Some(BROKER),
+				},
+			);
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			let egresses = MockEgressHandler::<AnyChain>::get_scheduled_egresses();
+			assert_eq!(egresses.len(), SHARES.len());
+
+			let total_output: AssetAmount = egresses.iter().map(|egress| egress.amount()).sum();
+			assert_eq!(total_output, INPUT_AMOUNT * DEFAULT_SWAP_RATE);
+
+			// Every address but the first receives its exact share, the first one
+			// also receives any rounding remainder:
+			for (egress, (address, share)) in
+				egresses.iter().zip(addresses.iter().zip(SHARES)).skip(1)
+			{
+				assert!(matches!(
+					egress,
+					MockEgressParameter::Swap { asset: OUTPUT_ASSET, destination_address, amount, .. }
+						if destination_address == address &&
+							*amount == Permill::from_parts(share as u32 * BASIS_POINTS_PER_MILLION) * total_output
+				));
+			}
+			assert!(matches!(
+				&egresses[0],
+				MockEgressParameter::Swap { destination_address, .. } if *destination_address == addresses[0]
+			));
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
+					swap_request_id: SwapRequestId(1)
+				})
+			);
+		});
+}
@@ -0,0 +1,114 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const CHUNK_INTERVAL: u32 = 3;
+const CHUNK_1_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+const CHUNK_2_BLOCK: u64 = CHUNK_1_BLOCK + CHUNK_INTERVAL as u64;
+
+const CHUNK_AMOUNT: AssetAmount = INPUT_AMOUNT / 2;
+const CHUNK_BROKER_FEE: AssetAmount = CHUNK_AMOUNT * BROKER_FEE_BPS as u128 / 10_000;
+const CHUNK_OUTPUT: AssetAmount = (CHUNK_AMOUNT - CHUNK_BROKER_FEE) * DEFAULT_SWAP_RATE;
+
+fn status_with(
+	status: SwapRequestStatus,
+	executed_chunks: u128,
+	output_amount: AssetAmount,
+) -> SwapStatus {
+	SwapStatus {
+		status,
+		input_asset: INPUT_ASSET,
+		output_asset: OUTPUT_ASSET,
+		input_amount: INPUT_AMOUNT,
+		swapped_input_amount: CHUNK_AMOUNT * executed_chunks,
+		intermediate_amount: 0,
+		output_amount,
+		network_fee: 0,
+		broker_fee: CHUNK_BROKER_FEE * executed_chunks,
+	}
+}
+
+#[test]
+fn swap_status_tracks_dca_progress() {
+	new_test_ext()
+		.execute_with(|| {
+			insert_swaps(&[TestSwapParams::new(
+				Some(DcaParameters { number_of_chunks: 2, chunk_interval: CHUNK_INTERVAL }),
+				None,
+				false,
+			)]);
+
+			assert_eq!(
+				Swapping::swap_status(SWAP_REQUEST_ID),
+				Some(status_with(SwapRequestStatus::Scheduled, 0, 0))
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_1_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(
+				Swapping::swap_status(SWAP_REQUEST_ID),
+				Some(status_with(
+					SwapRequestStatus::Executing { chunk: 2, total_chunks: 2 },
+					1,
+					CHUNK_OUTPUT
+				))
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_2_BLOCK)
+		.then_execute_with(|_| {
+			let status = Swapping::swap_status(SWAP_REQUEST_ID).unwrap();
+			assert!(matches!(
+				status.status,
+				SwapRequestStatus::AwaitingEgress { egress_id: Some(_) }
+			));
+			assert_eq!(status, status_with(status.status.clone(), 2, CHUNK_OUTPUT * 2));
+		})
+		.then_process_blocks_until_block(CHUNK_2_BLOCK + SWAP_STATUS_RETENTION_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_eq!(Swapping::swap_status(SWAP_REQUEST_ID), None);
+		});
+}
+
+#[test]
+fn swap_status_of_cancelled_swap_is_refunded() {
+	new_test_ext().execute_with(|| {
+		insert_swaps(&[TestSwapParams::new(
+			None,
+			Some(TestRefundParams {
+				retry_duration: DEFAULT_SWAP_RETRY_DELAY_BLOCKS,
+				min_output: INPUT_AMOUNT,
+			}),
+			false,
+		)]);
+
+		assert_ok!(Swapping::cancel_swap_request(RuntimeOrigin::signed(BROKER), SWAP_REQUEST_ID));
+
+		assert_eq!(
+			Swapping::swap_status(SWAP_REQUEST_ID),
+			Some(status_with(SwapRequestStatus::Refunded, 0, 0))
+		);
+	});
+}
+
+#[test]
+fn swap_status_is_not_tracked_for_network_fee_swaps() {
+	new_test_ext().execute_with(|| {
+		let swap_request_id = Swapping::init_network_fee_swap_request(Asset::Usdc, 1_000);
+
+		assert_eq!(Swapping::swap_status(swap_request_id), None);
+	});
+}
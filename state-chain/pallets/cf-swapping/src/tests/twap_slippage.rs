@@ -0,0 +1,144 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use cf_primitives::Tick;
+use cf_test_utilities::assert_events_match;
+
+const MAX_SLIPPAGE_BPS: BasisPoints = 100;
+// The mock swaps at DEFAULT_SWAP_RATE, which corresponds to a tick of ~6931.5.
+const TICK_AT_SWAP_RATE: Tick = 6932;
+// The output at this tick exceeds the actual output by more than MAX_SLIPPAGE_BPS.
+const TICK_ABOVE_SWAP_RATE: Tick = 7100;
+
+fn request_twap_protected_channel(max_slippage_bps_vs_twap: BasisPoints) -> DispatchResult {
+	Swapping::request_twap_protected_swap_deposit_address(
+		RuntimeOrigin::signed(BROKER),
+		INPUT_ASSET,
+		OUTPUT_ASSET,
+		EncodedAddress::Eth(Default::default()),
+		0,
+		None,
+		0,
+		Default::default(),
+		REFUND_PARAMS,
+		None,
+		max_slippage_bps_vs_twap,
+	)
+}
+
+fn swap_from_twap_protected_channel(output_asset_twap_tick: Option<Tick>) {
+	// Ticks are for the price of the output asset in the stable (input) asset:
+	MockPoolPriceApi::set_twap_tick(OUTPUT_ASSET, STABLE_ASSET, output_asset_twap_tick);
+
+	assert_ok!(request_twap_protected_channel(MAX_SLIPPAGE_BPS));
+	let channel_id = assert_events_match!(
+		Test,
+		RuntimeEvent::Swapping(Event::SwapDepositChannelTwapSlippageSet {
+			channel_id,
+			max_slippage_bps_vs_twap: MAX_SLIPPAGE_BPS,
+			..
+		}) => channel_id
+	);
+
+	Swapping::init_swap_request(
+		INPUT_ASSET,
+		INPUT_AMOUNT,
+		OUTPUT_ASSET,
+		SwapRequestType::Regular {
+			output_action: SwapOutputAction::Egress {
+				ccm_deposit_metadata: None,
+				output_address: (*EVM_OUTPUT_ADDRESS).clone(),
+			},
+		},
+		Default::default(),
+		Some(
+			TestRefundParams { retry_duration: 0, min_output: 0 }
+				.into_extended_params(INPUT_AMOUNT),
+		),
+		None,
+		SwapOrigin::DepositChannel {
+			deposit_address: EncodedAddress::Eth(Default::default()),
+			channel_id,
+			deposit_block_height: 0,
+			broker_id: BROKER,
+		},
+	);
+	assert_eq!(SwapRequestTwapSlippage::<Test>::get(SWAP_REQUEST_ID), Some(MAX_SLIPPAGE_BPS));
+}
+
+#[test]
+fn rejects_invalid_twap_slippage() {
+	new_test_ext().execute_with(|| {
+		for max_slippage in [0, MAX_BASIS_POINTS + 1] {
+			assert_noop!(
+				request_twap_protected_channel(max_slippage),
+				Error::<Test>::InvalidTwapSlippage
+			);
+		}
+	});
+}
+
+#[test]
+fn swap_within_twap_slippage_is_executed() {
+	new_test_ext()
+		.execute_with(|| swap_from_twap_protected_channel(Some(-TICK_AT_SWAP_RATE)))
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					swap_request_id: SWAP_REQUEST_ID,
+					output_amount,
+					..
+				}) if *output_amount == INPUT_AMOUNT * DEFAULT_SWAP_RATE
+			);
+			assert_eq!(SwapRequestTwapSlippage::<Test>::get(SWAP_REQUEST_ID), None);
+		});
+}
+
+#[test]
+fn swap_exceeding_twap_slippage_is_refunded() {
+	new_test_ext()
+		.execute_with(|| swap_from_twap_protected_channel(Some(-TICK_ABOVE_SWAP_RATE)))
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					refund_reason: SwapRefundReason::PriceLimitExceeded,
+					..
+				})
+			);
+		});
+}
+
+#[test]
+fn only_min_price_is_enforced_without_twap() {
+	new_test_ext()
+		.execute_with(|| swap_from_twap_protected_channel(None))
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					swap_request_id: SWAP_REQUEST_ID,
+					..
+				})
+			);
+		});
+}
@@ -0,0 +1,210 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+const WINDOW_BLOCKS: u32 = 10;
+
+fn set_volume_limit(on_exceeded: SwapVolumeLimitAction) {
+	assert_ok!(Swapping::update_pallet_config(
+		OriginTrait::root(),
+		vec![PalletConfigUpdate::SetSwapVolumeLimit {
+			asset: INPUT_ASSET,
+			limit: Some(SwapVolumeLimit {
+				// Enough room for one swap, but not two:
+				max_volume: INPUT_AMOUNT * 3 / 2,
+				window_blocks: WINDOW_BLOCKS,
+				on_exceeded,
+			}),
+		}]
+		.try_into()
+		.unwrap()
+	));
+}
+
+#[track_caller]
+fn assert_swap_scheduled_at(swap_request_id: SwapRequestId, block: u64) {
+	assert_has_matching_event!(
+		Test,
+		RuntimeEvent::Swapping(Event::SwapScheduled {
+			swap_request_id: id,
+			execute_at,
+			..
+		}) if *id == swap_request_id && *execute_at == block
+	);
+}
+
+#[test]
+fn swaps_exceeding_volume_limit_are_queued() {
+	new_test_ext().execute_with(|| {
+		set_volume_limit(SwapVolumeLimitAction::Queue);
+
+		insert_swaps(&[
+			TestSwapParams::new(None, None, false),
+			TestSwapParams::new(None, None, false),
+			TestSwapParams::new(None, None, false),
+		]);
+
+		// The first swap is within the limit:
+		assert_swap_scheduled_at(1.into(), INIT_BLOCK + SWAP_DELAY_BLOCKS as u64);
+
+		// The others have to wait for the previous swaps to leave the window:
+		for (id, execute_at) in [
+			(2.into(), INIT_BLOCK + WINDOW_BLOCKS as u64),
+			(3.into(), INIT_BLOCK + 2 * WINDOW_BLOCKS as u64),
+		] {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapVolumeLimitReached {
+					swap_request_id,
+					asset: INPUT_ASSET,
+					action: SwapVolumeLimitAction::Queue,
+				}) if *swap_request_id == id
+			);
+			assert_swap_scheduled_at(id, execute_at);
+		}
+
+		assert_eq!(
+			SwapVolumeHistory::<Test>::get(INPUT_ASSET).into_inner(),
+			vec![
+				(INIT_BLOCK, INPUT_AMOUNT),
+				(INIT_BLOCK + WINDOW_BLOCKS as u64, INPUT_AMOUNT),
+				(INIT_BLOCK + 2 * WINDOW_BLOCKS as u64, INPUT_AMOUNT),
+			]
+		);
+	});
+}
+
+#[test]
+fn swap_volume_history_is_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetSwapVolumeLimit {
+				asset: INPUT_ASSET,
+				limit: Some(SwapVolumeLimit {
+					max_volume: INPUT_AMOUNT,
+					window_blocks: 2 * MAX_SWAP_VOLUME_HISTORY_BLOCKS,
+					on_exceeded: SwapVolumeLimitAction::Queue,
+				}),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		let check_volume_at = |block: u64| {
+			System::set_block_number(block);
+			Swapping::check_swap_volume_limit(block.into(), INPUT_ASSET, 1, false)
+		};
+
+		// Volume within the same block is recorded together:
+		for _ in 0..2 {
+			assert!(matches!(check_volume_at(INIT_BLOCK), SwapVolumeCheck::Proceed { delay: 0 }));
+		}
+		assert_eq!(SwapVolumeHistory::<Test>::get(INPUT_ASSET).into_inner(), vec![(INIT_BLOCK, 2)]);
+
+		for block in 1..MAX_SWAP_VOLUME_HISTORY_BLOCKS as u64 {
+			check_volume_at(INIT_BLOCK + block);
+		}
+		assert!(SwapVolumeHistory::<Test>::get(INPUT_ASSET).is_full());
+
+		// Once full, the latest volume is moved forward to the current block:
+		let current_block = INIT_BLOCK + MAX_SWAP_VOLUME_HISTORY_BLOCKS as u64;
+		assert!(matches!(check_volume_at(current_block), SwapVolumeCheck::Proceed { delay: 0 }));
+		let history = SwapVolumeHistory::<Test>::get(INPUT_ASSET);
+		assert_eq!(history.len(), MAX_SWAP_VOLUME_HISTORY_BLOCKS as usize);
+		assert_eq!(history.last(), Some(&(current_block, 2)));
+	});
+}
+
+#[test]
+fn swaps_exceeding_volume_limit_are_refunded() {
+	new_test_ext().execute_with(|| {
+		set_volume_limit(SwapVolumeLimitAction::Refund);
+
+		let refund_params = TestRefundParams { retry_duration: 10, min_output: 0 };
+		insert_swaps(&[
+			TestSwapParams::new(None, Some(refund_params.clone()), false),
+			TestSwapParams::new(None, Some(refund_params), false),
+			// Swaps without refund parameters are queued instead:
+			TestSwapParams::new(None, None, false),
+		]);
+
+		assert!(SwapRequests::<Test>::get(SwapRequestId(1)).is_some());
+
+		assert_eq!(SwapRequests::<Test>::get(SwapRequestId(2)), None);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapVolumeLimitReached {
+				swap_request_id: SwapRequestId(2),
+				asset: INPUT_ASSET,
+				action: SwapVolumeLimitAction::Refund,
+			})
+		);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+				swap_request_id: SwapRequestId(2),
+				refund_reason: SwapRefundReason::VolumeLimitExceeded,
+				asset: INPUT_ASSET,
+				..
+			})
+		);
+		assert_eq!(Swapping::swap_status(2.into()).unwrap().status, SwapRequestStatus::Refunded);
+
+		// The refunded swap does not count towards the limit:
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapVolumeLimitReached {
+				swap_request_id: SwapRequestId(3),
+				asset: INPUT_ASSET,
+				action: SwapVolumeLimitAction::Queue,
+			})
+		);
+		assert_swap_scheduled_at(3.into(), INIT_BLOCK + WINDOW_BLOCKS as u64);
+	});
+}
+
+#[test]
+fn swap_volume_limit_must_be_valid() {
+	new_test_ext().execute_with(|| {
+		for limit in [
+			SwapVolumeLimit {
+				max_volume: 0,
+				window_blocks: WINDOW_BLOCKS,
+				on_exceeded: SwapVolumeLimitAction::Queue,
+			},
+			SwapVolumeLimit {
+				max_volume: INPUT_AMOUNT,
+				window_blocks: 0,
+				on_exceeded: SwapVolumeLimitAction::Queue,
+			},
+		] {
+			assert_noop!(
+				Swapping::update_pallet_config(
+					OriginTrait::root(),
+					vec![PalletConfigUpdate::SetSwapVolumeLimit {
+						asset: INPUT_ASSET,
+						limit: Some(limit)
+					}]
+					.try_into()
+					.unwrap()
+				),
+				Error::<Test>::InvalidSwapVolumeLimit
+			);
+		}
+	});
+}
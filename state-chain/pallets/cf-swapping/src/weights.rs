@@ -58,6 +58,21 @@ pub trait WeightInfo {
 	fn register_affiliate() -> Weight;
 	fn affiliate_withdrawal_request() -> Weight;
 	fn set_vault_swap_minimum_broker_fee() -> Weight;
+	fn update_dca_swap_request() -> Weight;
+	fn cancel_swap_request() -> Weight;
+	fn request_scheduled_swap_deposit_address() -> Weight;
+	fn update_affiliate_withdrawal_address() -> Weight;
+	fn signed_affiliate_withdrawal_request() -> Weight;
+	fn open_private_evm_channel() -> Weight;
+	fn close_private_evm_channel() -> Weight;
+	fn request_split_swap_deposit_address() -> Weight;
+	fn register_referral_code() -> Weight;
+	fn request_referred_swap_deposit_address() -> Weight;
+	fn claim_referral_rewards() -> Weight;
+	fn request_twap_protected_swap_deposit_address() -> Weight;
+	fn set_broker_channel_defaults() -> Weight;
+	fn request_swap_deposit_address_with_broker_defaults() -> Weight;
+	fn request_account_funding_deposit_address() -> Weight;
 }
 
 /// Weights for pallet_cf_swapping using the Substrate node and recommended hardware.
@@ -283,6 +298,81 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	fn update_dca_swap_request() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn cancel_swap_request() -> Weight {
+		Weight::from_parts(43_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn request_scheduled_swap_deposit_address() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn update_affiliate_withdrawal_address() -> Weight {
+		Weight::from_parts(73_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn signed_affiliate_withdrawal_request() -> Weight {
+		Weight::from_parts(110_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn open_private_evm_channel() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	fn close_private_evm_channel() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	fn request_split_swap_deposit_address() -> Weight {
+		Weight::from_parts(57_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn register_referral_code() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn request_referred_swap_deposit_address() -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn claim_referral_rewards() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn request_twap_protected_swap_deposit_address() -> Weight {
+		Weight::from_parts(57_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn set_broker_channel_defaults() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn request_swap_deposit_address_with_broker_defaults() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn request_account_funding_deposit_address() -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -507,4 +597,79 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(1_u64))
 			.saturating_add(ParityDbWeight::get().writes(1_u64))
 	}
+	fn update_dca_swap_request() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(6_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+	}
+	fn cancel_swap_request() -> Weight {
+		Weight::from_parts(43_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(7_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+	}
+	fn request_scheduled_swap_deposit_address() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(11_u64))
+			.saturating_add(ParityDbWeight::get().writes(6_u64))
+	}
+	fn update_affiliate_withdrawal_address() -> Weight {
+		Weight::from_parts(73_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(4_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	fn signed_affiliate_withdrawal_request() -> Weight {
+		Weight::from_parts(110_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(10_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+	}
+	fn open_private_evm_channel() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(8_u64))
+			.saturating_add(ParityDbWeight::get().writes(8_u64))
+	}
+	fn close_private_evm_channel() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(12_u64))
+			.saturating_add(ParityDbWeight::get().writes(8_u64))
+	}
+	fn request_split_swap_deposit_address() -> Weight {
+		Weight::from_parts(57_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(11_u64))
+			.saturating_add(ParityDbWeight::get().writes(4_u64))
+	}
+	fn register_referral_code() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	fn request_referred_swap_deposit_address() -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(13_u64))
+			.saturating_add(ParityDbWeight::get().writes(6_u64))
+	}
+	fn claim_referral_rewards() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	fn request_twap_protected_swap_deposit_address() -> Weight {
+		Weight::from_parts(57_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(12_u64))
+			.saturating_add(ParityDbWeight::get().writes(6_u64))
+	}
+	fn set_broker_channel_defaults() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(3_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	fn request_swap_deposit_address_with_broker_defaults() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(12_u64))
+			.saturating_add(ParityDbWeight::get().writes(4_u64))
+	}
+	fn request_account_funding_deposit_address() -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(ParityDbWeight::get().reads(11_u64))
+			.saturating_add(ParityDbWeight::get().writes(4_u64))
+	}
 }
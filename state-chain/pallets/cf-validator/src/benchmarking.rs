@@ -23,7 +23,7 @@ use pallet_cf_reputation::Config as ReputationConfig;
 use pallet_session::Config as SessionConfig;
 
 use cf_primitives::AccountRole;
-use cf_traits::{AccountRoleRegistry, KeyRotationStatusOuter, SafeMode, SetSafeMode};
+use cf_traits::{AccountRoleRegistry, Funding, KeyRotationStatusOuter, SafeMode, SetSafeMode};
 use cf_utilities::assert_matches;
 use frame_benchmarking::v2::*;
 use frame_support::{
@@ -59,6 +59,14 @@ pub fn bidder_set<T: Chainflip, Id: From<<T as frame_system::Config>::AccountId>
 		.map(move |i| account::<<T as frame_system::Config>::AccountId>("bidder", i, set_id).into())
 }
 
+fn new_operator<T: Config>() -> T::AccountId {
+	let operator = account::<T::AccountId>("operator", 0, 0);
+	frame_system::Pallet::<T>::inc_providers(&operator);
+	<T as frame_system::Config>::OnNewAccount::on_new_account(&operator);
+	assert_ok!(<T as Chainflip>::AccountRoleRegistry::register_as_operator(&operator));
+	operator
+}
+
 /// Initialises bidders for the auction by funding each one, registering session keys and peer ids
 /// and submitting heartbeats.
 pub fn init_bidders<T: RuntimeConfig>(n: u32, set_id: u32, flip_funded: u128) {
@@ -401,5 +409,188 @@ mod benchmarks {
 
 		assert!(Pallet::<T>::is_bidding(&caller));
 	}
+
+	#[benchmark]
+	fn set_delegation_commission() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Validator,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		set_delegation_commission(
+			RawOrigin::Signed(caller.clone()),
+			Some(Percent::from_percent(10)),
+		);
+
+		assert_eq!(DelegationCommission::<T>::get(&caller), Some(Percent::from_percent(10)));
+	}
+
+	#[benchmark]
+	fn delegate() {
+		let operator: T::AccountId = account("operator", 0, 0);
+		DelegationCommission::<T>::insert(&operator, Percent::from_percent(10));
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = T::Amount::from(DEFAULT_MINIMUM_DELEGATION);
+		MinimumDelegation::<T>::set(amount);
+		<T as FundingConfig>::Flip::credit_funds(&caller, amount);
+
+		#[extrinsic_call]
+		delegate(RawOrigin::Signed(caller.clone()), operator.clone(), amount);
+
+		assert_eq!(Delegations::<T>::get(&operator, &caller), amount);
+	}
+
+	#[benchmark]
+	fn undelegate() {
+		let operator: T::AccountId = account("operator", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = T::Amount::from(FLIPPERINOS_PER_FLIP);
+		Delegations::<T>::insert(&operator, &caller, amount);
+		DelegatorOperator::<T>::insert(&caller, &operator);
+		DelegatedStake::<T>::insert(&operator, amount);
+		DelegatorCount::<T>::insert(&operator, 1);
+		DelegationRewardPerStake::<T>::insert(&operator, FixedU128::one());
+
+		#[extrinsic_call]
+		undelegate(RawOrigin::Signed(caller.clone()), None);
+
+		assert!(PendingUndelegations::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn release_undelegated() {
+		let caller: T::AccountId = whitelisted_caller();
+		PendingUndelegations::<T>::insert(
+			&caller,
+			(T::Amount::from(FLIPPERINOS_PER_FLIP), BlockNumberFor::<T>::zero()),
+		);
+
+		#[extrinsic_call]
+		release_undelegated(RawOrigin::Signed(caller.clone()));
+
+		assert!(!PendingUndelegations::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn claim_delegation_rewards() {
+		let operator: T::AccountId = account("operator", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = T::Amount::from(FLIPPERINOS_PER_FLIP);
+		Delegations::<T>::insert(&operator, &caller, amount);
+		DelegatorOperator::<T>::insert(&caller, &operator);
+		DelegationRewardPerStake::<T>::insert(&operator, FixedU128::one());
+
+		#[extrinsic_call]
+		claim_delegation_rewards(RawOrigin::Signed(caller.clone()));
+
+		assert!(UnclaimedDelegationRewards::<T>::get(&caller).is_zero());
+	}
+
+	#[benchmark]
+	fn adjust_bid(a: Linear<3, 400>) {
+		// a = number of bidders.
+		init_bidders::<T>(a, 1, 100_000u128);
+		let caller: T::AccountId = ActiveBidder::<T>::get().into_iter().next().unwrap();
+		let bid_limit = T::Amount::from(200_000 * FLIPPERINOS_PER_FLIP);
+
+		#[extrinsic_call]
+		adjust_bid(RawOrigin::Signed(caller.clone()), Some(bid_limit));
+
+		assert_eq!(BidLimits::<T>::get(&caller), Some(bid_limit));
+	}
+
+	#[benchmark]
+	fn register_as_operator() {
+		let caller: T::AccountId = whitelisted_caller();
+		frame_system::Pallet::<T>::inc_providers(&caller);
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+
+		#[extrinsic_call]
+		register_as_operator(RawOrigin::Signed(caller.clone()));
+
+		assert_ok!(<T as Chainflip>::AccountRoleRegistry::ensure_operator(
+			RawOrigin::Signed(caller).into()
+		));
+	}
+
+	#[benchmark]
+	fn deregister_as_operator() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Operator,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		deregister_as_operator(RawOrigin::Signed(caller.clone()));
+
+		assert!(<T as Chainflip>::AccountRoleRegistry::is_unregistered(&caller));
+	}
+
+	#[benchmark]
+	fn authorise_operator() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Validator,
+		)
+		.unwrap();
+		let operator = new_operator::<T>();
+
+		#[extrinsic_call]
+		authorise_operator(RawOrigin::Signed(caller.clone()), Some(operator.clone()));
+
+		assert_eq!(ValidatorOperators::<T>::get(&caller), Some(operator));
+	}
+
+	#[benchmark]
+	fn operate() {
+		let validator = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Validator,
+		)
+		.unwrap();
+		let operator = new_operator::<T>();
+		ValidatorOperators::<T>::insert(&validator, &operator);
+		ActiveBidder::<T>::set(BTreeSet::from([validator.clone()]));
+		let call: <T as Config>::RuntimeCall = Call::<T>::stop_bidding {}.into();
+
+		#[extrinsic_call]
+		operate(RawOrigin::Signed(operator), validator.clone(), Box::new(call));
+
+		assert!(!Pallet::<T>::is_bidding(&validator));
+	}
+
+	#[benchmark]
+	fn set_reward_compounding() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Validator,
+		)
+		.unwrap();
+		PendingCompoundedRewards::<T>::insert(&caller, T::Amount::from(1_000u128));
+		CompoundedBonds::<T>::insert(&caller, T::Amount::from(100_000u128));
+
+		#[extrinsic_call]
+		set_reward_compounding(RawOrigin::Signed(caller.clone()), false);
+
+		assert!(!CompoundedBonds::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn cancel_stop_bidding() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Validator,
+		)
+		.unwrap();
+		PendingBidStops::<T>::insert(
+			&caller,
+			(
+				Some(T::Amount::from(1_000u128)),
+				frame_system::Pallet::<T>::block_number() + 10u32.into(),
+			),
+		);
+
+		#[extrinsic_call]
+		cancel_stop_bidding(RawOrigin::Signed(caller.clone()));
+
+		assert!(Pallet::<T>::is_bidding(&caller));
+	}
 	// NOTE: Test suite not included due to missing Funding and Reputation pallet in `mock::Test`.
 }
@@ -33,24 +33,25 @@ mod rotation_state;
 
 pub use auction_resolver::*;
 use cf_primitives::{
-	AuthorityCount, CfeCompatibility, Ed25519PublicKey, EpochIndex, Ipv6Addr, SemVer,
+	AccountRole, AuthorityCount, CfeCompatibility, Ed25519PublicKey, EpochIndex, Ipv6Addr, SemVer,
 	DEFAULT_MAX_AUTHORITY_SET_CONTRACTION, FLIPPERINOS_PER_FLIP,
 };
 use cf_traits::{
 	impl_pallet_safe_mode, offence_reporting::OffenceReporter, AccountInfo, AsyncResult,
-	AuthoritiesCfeVersions, Bid, Bonding, CfePeerRegistration, Chainflip, EpochInfo,
-	EpochTransitionHandler, ExecutionCondition, FundingInfo, HistoricalEpoch, KeyRotator,
-	MissedAuthorshipSlots, OnAccountFunded, QualifyNode, RedemptionCheck, ReputationResetter,
-	SetSafeMode,
+	AuthoritiesCfeVersions, Bid, Bonding, CfePeerRegistration, Chainflip, DelegationRewardsPayout,
+	EpochInfo, EpochTransitionHandler, ExecutionCondition, FundingInfo, HistoricalEpoch,
+	KeyRotator, MissedAuthorshipSlots, OnAccountFunded, QualifyNode, RedemptionCheck,
+	ReputationResetter, SetSafeMode,
 };
 use cf_utilities::Port;
 use frame_support::{
+	dispatch::GetDispatchInfo,
 	pallet_prelude::*,
 	sp_runtime::{
 		traits::{BlockNumberProvider, One, Saturating, UniqueSaturatedInto, Zero},
-		Percent, Permill,
+		FixedPointNumber, FixedU128, Percent, Permill, Perquintill,
 	},
-	traits::{EstimateNextSessionRotation, OnKilledAccount},
+	traits::{Contains, EstimateNextSessionRotation, OnKilledAccount, UnfilteredDispatchable},
 };
 use frame_system::pallet_prelude::*;
 use nanorand::{Rng, WyRand};
@@ -80,12 +81,25 @@ pub enum PalletConfigUpdate {
 	AuctionParameters { parameters: SetSizeParameters },
 	MinimumReportedCfeVersion { version: SemVer },
 	MaxAuthoritySetContractionPercentage { percentage: Percent },
+	UndelegationPeriod { blocks: u32 },
+	DeregistrationGracePeriod { blocks: u32 },
+	MinimumDelegation { amount: u128 },
 }
 
 type RuntimeRotationState<T> =
 	RotationState<<T as Chainflip>::ValidatorId, <T as Chainflip>::Amount>;
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(5);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(6);
+
+/// The maximum number of delegators an operator can accept. Keeps reward and slashing bookkeeping
+/// for each operator bounded.
+pub const MAX_DELEGATORS_PER_OPERATOR: u32 = 100;
+
+/// Undelegated stake remains bonded for 7 days by default.
+pub const DEFAULT_UNDELEGATION_PERIOD: u32 = 7 * 14_400;
+
+/// The smallest amount that can be delegated to an operator, by default.
+pub const DEFAULT_MINIMUM_DELEGATION: u128 = 1_000 * FLIPPERINOS_PER_FLIP;
 
 // Might be better to add the enum inside a struct rather than struct inside enum
 #[derive(Clone, PartialEq, Eq, Default, Encode, Decode, TypeInfo, RuntimeDebugNoBound)]
@@ -121,6 +135,42 @@ pub enum PalletOffence {
 	MissedAuthorshipSlot,
 }
 
+/// Block authorship statistics for an authority over the current epoch.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct AuthorshipStats {
+	/// The number of blocks authored.
+	pub authored: u32,
+	/// The number of authorship slots that were missed.
+	pub missed: u32,
+}
+
+impl AuthorshipStats {
+	/// The number of blocks the authority was expected to author.
+	pub fn expected(&self) -> u32 {
+		self.authored.saturating_add(self.missed)
+	}
+}
+
+/// The kinds of reward a validator can earn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum RewardKind {
+	/// Emissions paid to the author of each block.
+	Authorship,
+	/// Emissions paid to qualified backup nodes.
+	Backup,
+}
+
+/// The rewards earned by a validator over an epoch.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct RewardStatement<Amount> {
+	/// Rewards earned by authoring blocks.
+	pub authorship: Amount,
+	/// Rewards earned as a backup node.
+	pub backup: Amount,
+	/// The part of the rewards above that was paid out to the validator's delegators.
+	pub delegators_share: Amount,
+}
+
 impl_pallet_safe_mode!(PalletSafeMode; authority_rotation_enabled, start_bidding_enabled, stop_bidding_enabled);
 
 #[frame_support::pallet]
@@ -176,6 +226,22 @@ pub mod pallet {
 
 		type CfePeerRegistration: CfePeerRegistration<Self>;
 
+		/// The overarching call type, used by operators to act on behalf of validators.
+		type RuntimeCall: Member
+			+ Parameter
+			+ From<Call<Self>>
+			+ UnfilteredDispatchable<RuntimeOrigin = <Self as frame_system::Config>::RuntimeOrigin>
+			+ GetDispatchInfo;
+
+		/// The calls an operator may make on behalf of the validators it manages.
+		type OperatorCallFilter: Contains<<Self as Config>::RuntimeCall>;
+
+		/// Pays out the rewards earned by delegators.
+		type DelegationRewards: DelegationRewardsPayout<
+			AccountId = Self::AccountId,
+			Amount = Self::Amount,
+		>;
+
 		/// Benchmark weights.
 		type ValidatorWeightInfo: WeightInfo;
 	}
@@ -319,6 +385,123 @@ pub mod pallet {
 	#[pallet::getter(fn active_bidder)]
 	pub type ActiveBidder<T: Config> = StorageValue<_, BTreeSet<T::AccountId>, ValueQuery>;
 
+	/// Validators accepting delegated stake, along with the commission they take on the rewards
+	/// earned by that stake.
+	#[pallet::storage]
+	pub type DelegationCommission<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Percent, OptionQuery>;
+
+	/// Stake delegated to each operator, by delegator.
+	#[pallet::storage]
+	pub type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Amount,
+		ValueQuery,
+	>;
+
+	/// The operator each delegator is currently delegating to.
+	#[pallet::storage]
+	pub type DelegatorOperator<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Undelegated stake that remains bonded until the given block.
+	#[pallet::storage]
+	pub type PendingUndelegations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::Amount, BlockNumberFor<T>), OptionQuery>;
+
+	/// The number of blocks undelegated stake remains bonded before it can be released.
+	#[pallet::storage]
+	pub type UndelegationPeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// The smallest delegation an operator will accept.
+	#[pallet::storage]
+	pub type MinimumDelegation<T: Config> = StorageValue<_, T::Amount, ValueQuery>;
+
+	/// The total stake delegated to each operator.
+	#[pallet::storage]
+	pub type DelegatedStake<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Amount, ValueQuery>;
+
+	/// The number of delegators of each operator.
+	#[pallet::storage]
+	pub type DelegatorCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The cumulative reward earned by delegators of each operator, per unit of delegated stake.
+	#[pallet::storage]
+	pub type DelegationRewardPerStake<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, FixedU128, ValueQuery>;
+
+	/// The value of the operator's [DelegationRewardPerStake] up to which each delegator's rewards
+	/// have been settled.
+	#[pallet::storage]
+	pub type DelegatorRewardCheckpoint<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, FixedU128, ValueQuery>;
+
+	/// Settled delegation rewards that the delegator has not yet claimed.
+	#[pallet::storage]
+	pub type UnclaimedDelegationRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Amount, ValueQuery>;
+
+	/// Validators that have recently stopped bidding, with the bid limit they had set and the
+	/// block at which their grace period ends. Until then, they can cancel stopping to bid and
+	/// can't deregister or redeem.
+	#[pallet::storage]
+	pub type PendingBidStops<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		(Option<T::Amount>, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
+	/// The number of blocks after stopping to bid during which a validator can change its mind.
+	#[pallet::storage]
+	pub type DeregistrationGracePeriod<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// Block authorship statistics for each authority, reset at the start of every epoch.
+	#[pallet::storage]
+	pub type CurrentEpochAuthorshipStats<T: Config> =
+		StorageMap<_, Blake2_128Concat, ValidatorIdOf<T>, AuthorshipStats, ValueQuery>;
+
+	/// The rewards earned by each validator in each epoch, kept until the epoch expires.
+	#[pallet::storage]
+	pub type EpochRewardStatements<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EpochIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		RewardStatement<T::Amount>,
+		ValueQuery,
+	>;
+
+	/// The maximum amount a bidder is willing to bid. Bidders without a limit bid their full
+	/// balance, plus any stake delegated to them.
+	#[pallet::storage]
+	pub type BidLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Amount, OptionQuery>;
+
+	/// The operator each validator has authorised to act on its behalf.
+	#[pallet::storage]
+	pub type ValidatorOperators<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Validators that have opted in to compounding their rewards, with the rewards earned since
+	/// the last epoch boundary.
+	#[pallet::storage]
+	pub type PendingCompoundedRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Amount, OptionQuery>;
+
+	/// The total rewards each validator has compounded into its bond.
+	#[pallet::storage]
+	pub type CompoundedBonds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Amount, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -345,6 +528,35 @@ pub mod pallet {
 		/// The rotation transaction(s) for the previous rotation are still pending to be
 		/// succesfully broadcast, therefore, cannot start a new epoch rotation.
 		PreviousRotationStillPending,
+		/// A validator has updated the commission it takes on delegated stake. `None` means the
+		/// validator no longer accepts delegations.
+		DelegationCommissionUpdated { operator: T::AccountId, commission: Option<Percent> },
+		/// Stake has been delegated to an operator.
+		Delegated { delegator: T::AccountId, operator: T::AccountId, amount: T::Amount },
+		/// Stake has been undelegated and will be released after the undelegation period.
+		UndelegationRequested {
+			delegator: T::AccountId,
+			operator: T::AccountId,
+			amount: T::Amount,
+			unlocks_at: BlockNumberFor<T>,
+		},
+		/// Previously undelegated stake has been released.
+		UndelegatedFundsReleased { delegator: T::AccountId, amount: T::Amount },
+		/// A delegator has claimed the rewards earned by its delegated stake.
+		DelegationRewardsClaimed { delegator: T::AccountId, amount: T::Amount },
+		/// A bidder has adjusted the maximum amount it bids. `None` means the bidder bids its full
+		/// stake.
+		BidAdjusted { account_id: T::AccountId, bid_limit: Option<T::Amount> },
+		/// A validator has authorised an operator to act on its behalf. `None` means the
+		/// validator has revoked its operator's authorisation.
+		OperatorAuthorised { validator: T::AccountId, operator: Option<T::AccountId> },
+		/// A validator has opted in or out of compounding its rewards into its bond.
+		RewardCompoundingUpdated { account_id: T::AccountId, enabled: bool },
+		/// A validator's earned rewards have been rolled into its bond.
+		RewardsCompounded { account_id: T::AccountId, amount: T::Amount, total: T::Amount },
+		/// A validator has cancelled stopping to bid within the grace period, and is bidding
+		/// again.
+		StopBiddingCancelled { account_id: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -385,6 +597,45 @@ pub mod pallet {
 		AlreadyBidding,
 		/// We are in the auction phase
 		AuctionPhase,
+		/// The operator does not accept delegations.
+		NotAcceptingDelegations,
+		/// The operator still has stake delegated to it.
+		DelegationsOutstanding,
+		/// Validators cannot delegate their stake to other validators.
+		ValidatorsCannotDelegate,
+		/// The account is already delegating to a different operator.
+		AlreadyDelegating,
+		/// The account is not delegating to any operator.
+		NotDelegating,
+		/// The delegated amount must be non-zero and cannot exceed the current delegation.
+		InvalidDelegationAmount,
+		/// Accounts cannot register as validators while they have delegated stake.
+		StillDelegating,
+		/// The account has no undelegated stake awaiting release.
+		NoPendingUndelegation,
+		/// The undelegation period has not yet elapsed.
+		UndelegationStillLocked,
+		/// The operator has reached the maximum number of delegators.
+		TooManyDelegators,
+		/// Delegations, including what remains after a partial undelegation, may not fall below
+		/// the minimum delegation.
+		BelowMinimumDelegation,
+		/// The account has no delegation rewards to claim.
+		NoDelegationRewards,
+		/// The adjusted bid would fall below the current minimum active bid.
+		BidBelowMinimumActiveBid,
+		/// The account is not registered as an operator.
+		NotAnOperator,
+		/// The operator has not been authorised by the validator.
+		OperatorNotAuthorised,
+		/// Operators are not permitted to make this call on behalf of a validator.
+		CallNotPermittedForOperator,
+		/// Operators cannot deregister while validators have authorised them.
+		OperatorStillAuthorised,
+		/// The validator stopped bidding too recently to deregister or redeem.
+		DeregistrationGracePeriodNotElapsed,
+		/// The validator has not stopped bidding within the grace period.
+		NoPendingBidStop,
 	}
 
 	/// Pallet implements [`Hooks`] trait
@@ -567,6 +818,15 @@ pub mod pallet {
 				PalletConfigUpdate::MaxAuthoritySetContractionPercentage { percentage } => {
 					MaxAuthoritySetContractionPercentage::<T>::put(percentage);
 				},
+				PalletConfigUpdate::UndelegationPeriod { blocks } => {
+					UndelegationPeriod::<T>::put(BlockNumberFor::<T>::from(blocks));
+				},
+				PalletConfigUpdate::DeregistrationGracePeriod { blocks } => {
+					DeregistrationGracePeriod::<T>::put(BlockNumberFor::<T>::from(blocks));
+				},
+				PalletConfigUpdate::MinimumDelegation { amount } => {
+					MinimumDelegation::<T>::put(T::Amount::from(amount));
+				},
 			}
 
 			Self::deposit_event(Event::PalletConfigUpdated { update });
@@ -751,6 +1011,11 @@ pub mod pallet {
 		#[pallet::weight(T::ValidatorWeightInfo::register_as_validator())]
 		pub fn register_as_validator(origin: OriginFor<T>) -> DispatchResult {
 			let account_id: T::AccountId = ensure_signed(origin)?;
+			ensure!(
+				!DelegatorOperator::<T>::contains_key(&account_id) &&
+					!PendingUndelegations::<T>::contains_key(&account_id),
+				Error::<T>::StillDelegating
+			);
 			if Self::current_authority_count() >= AuctionParameters::<T>::get().max_size {
 				ensure!(
 					T::FundingInfo::total_balance_of(&account_id) >=
@@ -766,6 +1031,10 @@ pub mod pallet {
 		pub fn deregister_as_validator(origin: OriginFor<T>) -> DispatchResult {
 			let account_id = T::AccountRoleRegistry::ensure_validator(origin.clone())?;
 			ensure!(!Self::is_bidding(&account_id), Error::<T>::StillBidding);
+			ensure!(
+				!Self::in_deregistration_grace_period(&account_id),
+				Error::<T>::DeregistrationGracePeriodNotElapsed
+			);
 
 			let validator_id = <ValidatorIdOf<T> as IsType<
 				<T as frame_system::Config>::AccountId,
@@ -782,6 +1051,12 @@ pub mod pallet {
 				T::CfePeerRegistration::peer_deregistered(validator_id.clone(), peer_id);
 			}
 
+			ValidatorOperators::<T>::remove(&account_id);
+			PendingBidStops::<T>::remove(&account_id);
+			PendingCompoundedRewards::<T>::remove(&account_id);
+			if !CompoundedBonds::<T>::take(&account_id).is_zero() {
+				Self::update_validator_bond(validator_id);
+			}
 			T::AccountRoleRegistry::deregister_as_validator(&account_id)?;
 
 			Ok(())
@@ -803,6 +1078,7 @@ pub mod pallet {
 			ensure!(T::SafeMode::get().start_bidding_enabled, Error::<T>::StartBiddingDisabled);
 			let account_id = T::AccountRoleRegistry::ensure_validator(origin)?;
 			Self::activate_bidding(&account_id)?;
+			PendingBidStops::<T>::remove(&account_id);
 			Self::deposit_event(Event::StartedBidding { account_id });
 			Ok(())
 		}
@@ -811,6 +1087,10 @@ pub mod pallet {
 		/// from future auctions. Should only be called by accounts that are not already not
 		/// bidding.
 		///
+		/// If a [DeregistrationGracePeriod] is set, the node can't deregister or redeem until it
+		/// has elapsed, and can use [Pallet::cancel_stop_bidding] to resume bidding with its
+		/// previous bid limit in the meantime.
+		///
 		/// ## Events
 		///
 		/// - [StoppedBidding](Event::StoppedBidding)
@@ -831,9 +1111,428 @@ pub mod pallet {
 			ActiveBidder::<T>::try_mutate(|bidders| {
 				bidders.remove(&account_id).then_some(()).ok_or(Error::<T>::AlreadyNotBidding)
 			})?;
+			let bid_limit = BidLimits::<T>::take(&account_id);
+			let grace_period = DeregistrationGracePeriod::<T>::get();
+			if !grace_period.is_zero() {
+				PendingBidStops::<T>::insert(
+					&account_id,
+					(
+						bid_limit,
+						frame_system::Pallet::<T>::block_number().saturating_add(grace_period),
+					),
+				);
+			}
 			Self::deposit_event(Event::StoppedBidding { account_id });
 			Ok(())
 		}
+
+		/// Allows a validator to accept delegated stake, taking the given commission on the
+		/// rewards earned by that stake. Passing `None` stops the validator from accepting
+		/// delegations, which is only possible once all delegations have been withdrawn.
+		///
+		/// ## Events
+		///
+		/// - [DelegationCommissionUpdated](Event::DelegationCommissionUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [DelegationsOutstanding](Error::DelegationsOutstanding)
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::ValidatorWeightInfo::set_delegation_commission())]
+		pub fn set_delegation_commission(
+			origin: OriginFor<T>,
+			commission: Option<Percent>,
+		) -> DispatchResult {
+			let operator = T::AccountRoleRegistry::ensure_validator(origin)?;
+
+			if let Some(commission) = commission {
+				DelegationCommission::<T>::insert(&operator, commission);
+			} else {
+				ensure!(
+					Delegations::<T>::iter_prefix(&operator).next().is_none(),
+					Error::<T>::DelegationsOutstanding
+				);
+				DelegationCommission::<T>::remove(&operator);
+				DelegationRewardPerStake::<T>::remove(&operator);
+			}
+
+			Self::deposit_event(Event::DelegationCommissionUpdated { operator, commission });
+			Ok(())
+		}
+
+		/// Delegates stake to a validator that accepts delegations. The delegated funds are
+		/// bonded and count towards the operator's auction bid.
+		///
+		/// ## Events
+		///
+		/// - [Delegated](Event::Delegated)
+		///
+		/// ## Errors
+		///
+		/// - [ValidatorsCannotDelegate](Error::ValidatorsCannotDelegate)
+		/// - [NotAcceptingDelegations](Error::NotAcceptingDelegations)
+		/// - [AlreadyDelegating](Error::AlreadyDelegating)
+		/// - [InvalidDelegationAmount](Error::InvalidDelegationAmount)
+		/// - [BelowMinimumDelegation](Error::BelowMinimumDelegation)
+		/// - [TooManyDelegators](Error::TooManyDelegators)
+		/// - [NotEnoughFunds](Error::NotEnoughFunds)
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::ValidatorWeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			amount: T::Amount,
+		) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+
+			ensure!(
+				!T::AccountRoleRegistry::has_account_role(&delegator, AccountRole::Validator),
+				Error::<T>::ValidatorsCannotDelegate
+			);
+			ensure!(
+				DelegationCommission::<T>::contains_key(&operator),
+				Error::<T>::NotAcceptingDelegations
+			);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidDelegationAmount);
+			if let Some(current_operator) = DelegatorOperator::<T>::get(&delegator) {
+				ensure!(current_operator == operator, Error::<T>::AlreadyDelegating);
+			}
+
+			let current = Delegations::<T>::get(&operator, &delegator);
+			let delegated = current.saturating_add(amount);
+			ensure!(delegated >= MinimumDelegation::<T>::get(), Error::<T>::BelowMinimumDelegation);
+			ensure!(
+				T::FundingInfo::balance(&delegator) >=
+					delegated.saturating_add(Self::pending_undelegation(&delegator)),
+				Error::<T>::NotEnoughFunds
+			);
+			if current.is_zero() {
+				DelegatorCount::<T>::try_mutate(&operator, |count| {
+					ensure!(*count < MAX_DELEGATORS_PER_OPERATOR, Error::<T>::TooManyDelegators);
+					*count += 1;
+					Ok::<_, Error<T>>(())
+				})?;
+			}
+
+			Self::settle_delegation_rewards(&operator, &delegator);
+			Delegations::<T>::insert(&operator, &delegator, delegated);
+			DelegatorOperator::<T>::insert(&delegator, &operator);
+			DelegatedStake::<T>::mutate(&operator, |stake| stake.saturating_accrue(amount));
+			Self::update_delegator_bond(&delegator);
+
+			Self::deposit_event(Event::Delegated { delegator, operator, amount });
+			Ok(())
+		}
+
+		/// Withdraws some or all (if `amount` is `None`) of the caller's delegated stake. The
+		/// stake no longer counts towards the operator's bid, but remains bonded until the
+		/// undelegation period has elapsed.
+		///
+		/// ## Events
+		///
+		/// - [UndelegationRequested](Event::UndelegationRequested)
+		///
+		/// ## Errors
+		///
+		/// - [NotDelegating](Error::NotDelegating)
+		/// - [InvalidDelegationAmount](Error::InvalidDelegationAmount)
+		/// - [BelowMinimumDelegation](Error::BelowMinimumDelegation)
+		/// - [AuctionPhase](Error::AuctionPhase)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::ValidatorWeightInfo::undelegate())]
+		pub fn undelegate(origin: OriginFor<T>, amount: Option<T::Amount>) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+
+			ensure!(!Self::is_auction_phase(), Error::<T>::AuctionPhase);
+
+			let operator =
+				DelegatorOperator::<T>::get(&delegator).ok_or(Error::<T>::NotDelegating)?;
+			let delegated = Delegations::<T>::get(&operator, &delegator);
+			let amount = amount.unwrap_or(delegated);
+			ensure!(!amount.is_zero() && amount <= delegated, Error::<T>::InvalidDelegationAmount);
+
+			let remaining = delegated.saturating_sub(amount);
+			ensure!(
+				remaining.is_zero() || remaining >= MinimumDelegation::<T>::get(),
+				Error::<T>::BelowMinimumDelegation
+			);
+
+			Self::settle_delegation_rewards(&operator, &delegator);
+			if remaining.is_zero() {
+				Delegations::<T>::remove(&operator, &delegator);
+				DelegatorOperator::<T>::remove(&delegator);
+				DelegatorRewardCheckpoint::<T>::remove(&delegator);
+				DelegatorCount::<T>::mutate(&operator, |count| count.saturating_dec());
+			} else {
+				Delegations::<T>::insert(&operator, &delegator, remaining);
+			}
+			DelegatedStake::<T>::mutate(&operator, |stake| stake.saturating_reduce(amount));
+
+			let unlocks_at = frame_system::Pallet::<T>::current_block_number()
+				.saturating_add(UndelegationPeriod::<T>::get());
+			PendingUndelegations::<T>::insert(
+				&delegator,
+				(Self::pending_undelegation(&delegator).saturating_add(amount), unlocks_at),
+			);
+
+			Self::deposit_event(Event::UndelegationRequested {
+				delegator,
+				operator,
+				amount,
+				unlocks_at,
+			});
+			Ok(())
+		}
+
+		/// Releases the bond on undelegated stake once the undelegation period has elapsed.
+		///
+		/// ## Events
+		///
+		/// - [UndelegatedFundsReleased](Event::UndelegatedFundsReleased)
+		///
+		/// ## Errors
+		///
+		/// - [NoPendingUndelegation](Error::NoPendingUndelegation)
+		/// - [UndelegationStillLocked](Error::UndelegationStillLocked)
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::ValidatorWeightInfo::release_undelegated())]
+		pub fn release_undelegated(origin: OriginFor<T>) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+
+			let (amount, unlocks_at) = PendingUndelegations::<T>::get(&delegator)
+				.ok_or(Error::<T>::NoPendingUndelegation)?;
+			ensure!(
+				frame_system::Pallet::<T>::current_block_number() >= unlocks_at,
+				Error::<T>::UndelegationStillLocked
+			);
+
+			PendingUndelegations::<T>::remove(&delegator);
+			Self::update_delegator_bond(&delegator);
+
+			Self::deposit_event(Event::UndelegatedFundsReleased { delegator, amount });
+			Ok(())
+		}
+
+		/// Pays out the rewards the caller has earned on its delegated stake, including any
+		/// earned on stake it has since undelegated.
+		///
+		/// ## Events
+		///
+		/// - [DelegationRewardsClaimed](Event::DelegationRewardsClaimed)
+		///
+		/// ## Errors
+		///
+		/// - [NoDelegationRewards](Error::NoDelegationRewards)
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::ValidatorWeightInfo::claim_delegation_rewards())]
+		pub fn claim_delegation_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+
+			if let Some(operator) = DelegatorOperator::<T>::get(&delegator) {
+				Self::settle_delegation_rewards(&operator, &delegator);
+			}
+			let amount = UnclaimedDelegationRewards::<T>::take(&delegator);
+			ensure!(!amount.is_zero(), Error::<T>::NoDelegationRewards);
+
+			T::DelegationRewards::pay_out(&delegator, amount);
+
+			Self::deposit_event(Event::DelegationRewardsClaimed { delegator, amount });
+			Ok(())
+		}
+
+		/// Adjusts the amount a bidding node bids without it having to stop bidding. The bid is
+		/// capped at `bid_limit`, or uncapped if `None`. The adjusted bid may not fall below the
+		/// current minimum active bid, so that the node is not dropped from the auction.
+		///
+		/// ## Events
+		///
+		/// - [BidAdjusted](Event::BidAdjusted)
+		///
+		/// ## Errors
+		///
+		/// - [AlreadyNotBidding](Error::AlreadyNotBidding)
+		/// - [RotationInProgress](Error::RotationInProgress)
+		/// - [BidBelowMinimumActiveBid](Error::BidBelowMinimumActiveBid)
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::ValidatorWeightInfo::adjust_bid(
+			ActiveBidder::<T>::decode_len().unwrap_or_default() as u32,
+		))]
+		pub fn adjust_bid(origin: OriginFor<T>, bid_limit: Option<T::Amount>) -> DispatchResult {
+			let account_id = T::AccountRoleRegistry::ensure_validator(origin)?;
+
+			ensure!(Self::is_bidding(&account_id), Error::<T>::AlreadyNotBidding);
+			ensure!(
+				CurrentRotationPhase::<T>::get() == RotationPhase::Idle,
+				Error::<T>::RotationInProgress
+			);
+
+			if let Some(bid_limit) = bid_limit {
+				if let Some(min_active_bid) = Self::min_active_bid() {
+					ensure!(bid_limit >= min_active_bid, Error::<T>::BidBelowMinimumActiveBid);
+				}
+				BidLimits::<T>::insert(&account_id, bid_limit);
+			} else {
+				BidLimits::<T>::remove(&account_id);
+			}
+
+			Self::deposit_event(Event::BidAdjusted { account_id, bid_limit });
+			Ok(())
+		}
+
+		/// Registers the account as an operator, which validators can authorise to perform
+		/// non-custodial actions on their behalf.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::ValidatorWeightInfo::register_as_operator())]
+		pub fn register_as_operator(origin: OriginFor<T>) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			T::AccountRoleRegistry::register_as_operator(&account_id)
+		}
+
+		/// Deregisters an operator. Only possible once no validator has it authorised.
+		///
+		/// ## Errors
+		///
+		/// - [OperatorStillAuthorised](Error::OperatorStillAuthorised)
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::ValidatorWeightInfo::deregister_as_operator())]
+		pub fn deregister_as_operator(origin: OriginFor<T>) -> DispatchResult {
+			let operator = T::AccountRoleRegistry::ensure_operator(origin)?;
+			ensure!(
+				Self::operator_validators(&operator).is_empty(),
+				Error::<T>::OperatorStillAuthorised
+			);
+			T::AccountRoleRegistry::deregister_as_operator(&operator)
+		}
+
+		/// Authorises an operator to act on the validator's behalf, replacing any previously
+		/// authorised operator. Passing `None` revokes the current operator's authorisation.
+		///
+		/// ## Events
+		///
+		/// - [OperatorAuthorised](Event::OperatorAuthorised)
+		///
+		/// ## Errors
+		///
+		/// - [NotAnOperator](Error::NotAnOperator)
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::ValidatorWeightInfo::authorise_operator())]
+		pub fn authorise_operator(
+			origin: OriginFor<T>,
+			operator: Option<T::AccountId>,
+		) -> DispatchResult {
+			let validator = T::AccountRoleRegistry::ensure_validator(origin)?;
+
+			if let Some(operator) = &operator {
+				ensure!(
+					T::AccountRoleRegistry::has_account_role(operator, AccountRole::Operator),
+					Error::<T>::NotAnOperator
+				);
+				ValidatorOperators::<T>::insert(&validator, operator);
+			} else {
+				ValidatorOperators::<T>::remove(&validator);
+			}
+
+			Self::deposit_event(Event::OperatorAuthorised { validator, operator });
+			Ok(())
+		}
+
+		/// Dispatches a call on behalf of a validator that has authorised the calling operator.
+		/// Only calls permitted by [Config::OperatorCallFilter] can be made, so operators can
+		/// manage a validator without having custody of its funds.
+		///
+		/// ## Errors
+		///
+		/// - [OperatorNotAuthorised](Error::OperatorNotAuthorised)
+		/// - [CallNotPermittedForOperator](Error::CallNotPermittedForOperator)
+		#[pallet::call_index(18)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				T::ValidatorWeightInfo::operate().saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn operate(
+			origin: OriginFor<T>,
+			validator: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			let operator = T::AccountRoleRegistry::ensure_operator(origin)?;
+
+			ensure!(
+				ValidatorOperators::<T>::get(&validator).as_ref() == Some(&operator),
+				Error::<T>::OperatorNotAuthorised
+			);
+			ensure!(
+				T::OperatorCallFilter::contains(&call),
+				Error::<T>::CallNotPermittedForOperator
+			);
+
+			call.dispatch_bypass_filter(frame_system::RawOrigin::Signed(validator).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		/// Opts in to or out of compounding rewards. While opted in, the rewards a validator earns
+		/// are rolled into its bond (and its bid limit, if set) at each epoch boundary rather than
+		/// becoming redeemable. Opting out releases any previously compounded rewards.
+		///
+		/// ## Events
+		///
+		/// - [RewardCompoundingUpdated](Event::RewardCompoundingUpdated)
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::ValidatorWeightInfo::set_reward_compounding())]
+		pub fn set_reward_compounding(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			let account_id = T::AccountRoleRegistry::ensure_validator(origin)?;
+
+			if enabled {
+				PendingCompoundedRewards::<T>::mutate(&account_id, |pending| {
+					pending.get_or_insert_with(Zero::zero);
+				});
+			} else {
+				PendingCompoundedRewards::<T>::remove(&account_id);
+				if !CompoundedBonds::<T>::take(&account_id).is_zero() {
+					Self::update_validator_bond(
+						<ValidatorIdOf<T> as IsType<T::AccountId>>::from_ref(&account_id),
+					);
+				}
+			}
+
+			Self::deposit_event(Event::RewardCompoundingUpdated { account_id, enabled });
+			Ok(())
+		}
+
+		/// Cancels a recent [Pallet::stop_bidding] while still within the
+		/// [DeregistrationGracePeriod]. The node resumes bidding with the bid limit it had set.
+		///
+		/// ## Events
+		///
+		/// - [StopBiddingCancelled](Event::StopBiddingCancelled)
+		///
+		/// ## Errors
+		///
+		/// - [NoPendingBidStop](Error::NoPendingBidStop)
+		/// - [AlreadyBidding](Error::AlreadyBidding)
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::ValidatorWeightInfo::cancel_stop_bidding())]
+		pub fn cancel_stop_bidding(origin: OriginFor<T>) -> DispatchResult {
+			ensure!(T::SafeMode::get().start_bidding_enabled, Error::<T>::StartBiddingDisabled);
+			let account_id = T::AccountRoleRegistry::ensure_validator(origin)?;
+			ensure!(
+				Self::in_deregistration_grace_period(&account_id),
+				Error::<T>::NoPendingBidStop
+			);
+
+			let (bid_limit, _) =
+				PendingBidStops::<T>::take(&account_id).ok_or(Error::<T>::NoPendingBidStop)?;
+			Self::activate_bidding(&account_id)?;
+			if let Some(bid_limit) = bid_limit {
+				BidLimits::<T>::insert(&account_id, bid_limit);
+			}
+
+			Self::deposit_event(Event::StopBiddingCancelled { account_id });
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -848,6 +1547,8 @@ pub mod pallet {
 		pub auction_parameters: SetSizeParameters,
 		pub auction_bid_cutoff_percentage: Percent,
 		pub max_authority_set_contraction_percentage: Percent,
+		pub undelegation_period: BlockNumberFor<T>,
+		pub minimum_delegation: T::Amount,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
@@ -867,6 +1568,8 @@ pub mod pallet {
 				},
 				auction_bid_cutoff_percentage: Zero::zero(),
 				max_authority_set_contraction_percentage: DEFAULT_MAX_AUTHORITY_SET_CONTRACTION,
+				undelegation_period: DEFAULT_UNDELEGATION_PERIOD.into(),
+				minimum_delegation: DEFAULT_MINIMUM_DELEGATION.into(),
 			}
 		}
 	}
@@ -884,6 +1587,8 @@ pub mod pallet {
 			MaxAuthoritySetContractionPercentage::<T>::set(
 				self.max_authority_set_contraction_percentage,
 			);
+			UndelegationPeriod::<T>::set(self.undelegation_period);
+			MinimumDelegation::<T>::set(self.minimum_delegation);
 
 			CurrentEpoch::<T>::set(GENESIS_EPOCH);
 
@@ -1012,6 +1717,11 @@ impl<T: Config> Pallet<T> {
 			old_epoch,
 		);
 
+		// Bounded by the number of authorities in the previous epoch.
+		let _ = CurrentEpochAuthorshipStats::<T>::clear(u32::MAX, None);
+
+		Self::compound_rewards();
+
 		Self::initialise_new_epoch(
 			new_epoch,
 			&new_authorities,
@@ -1038,7 +1748,7 @@ impl<T: Config> Pallet<T> {
 			if EpochHistory::<T>::number_of_active_epochs_for_authority(authority) == 0 {
 				T::ReputationResetter::reset_reputation(authority);
 			}
-			T::Bonder::update_bond(authority, EpochHistory::<T>::active_bond(authority));
+			Self::update_validator_bond(authority);
 		}
 		T::EpochTransitionHandler::on_expired_epoch(epoch);
 
@@ -1047,6 +1757,7 @@ impl<T: Config> Pallet<T> {
 			AuthorityIndex::<T>::remove(epoch, validator);
 		}
 		HistoricalBonds::<T>::remove(epoch);
+		let _ = EpochRewardStatements::<T>::clear_prefix(epoch, u32::MAX, None);
 	}
 
 	fn expire_epochs_up_to(latest_epoch_to_expire: EpochIndex, remaining_weight: Weight) -> Weight {
@@ -1096,7 +1807,7 @@ impl<T: Config> Pallet<T> {
 		new_authorities.iter().enumerate().for_each(|(index, account_id)| {
 			AuthorityIndex::<T>::insert(new_epoch, account_id, index as AuthorityCount);
 			EpochHistory::<T>::activate_epoch(account_id, new_epoch);
-			T::Bonder::update_bond(account_id, EpochHistory::<T>::active_bond(account_id));
+			Self::update_validator_bond(account_id);
 		});
 
 		CurrentEpochStartedAt::<T>::set(frame_system::Pallet::<T>::current_block_number());
@@ -1290,11 +2001,24 @@ impl<T: Config> Pallet<T> {
 	fn punish_missed_authorship_slots() -> Weight {
 		let mut num_missed_slots = 0;
 		let session_validators = <pallet_session::Pallet<T>>::validators();
-		for slot in T::MissedAuthorshipSlots::missed_slots() {
+		// https://github.com/chainflip-io/substrate/blob/c172d0f683fab3792b90d876fd6ca27056af9fe9/frame/aura/src/lib.rs#L97
+		let slot_author =
+			|slot: u64| session_validators.get((slot % session_validators.len() as u64) as usize);
+		let missed_slots = T::MissedAuthorshipSlots::missed_slots();
+		// The end of the range is the slot of the current block, unless it could not be determined.
+		if missed_slots != Default::default() {
+			if let Some(id) = slot_author(missed_slots.end) {
+				CurrentEpochAuthorshipStats::<T>::mutate(id, |stats| {
+					stats.authored = stats.authored.saturating_add(1)
+				});
+			}
+		}
+		for slot in missed_slots {
 			num_missed_slots += 1;
-			// https://github.com/chainflip-io/substrate/blob/c172d0f683fab3792b90d876fd6ca27056af9fe9/frame/aura/src/lib.rs#L97
-			let authority_index = slot % session_validators.len() as u64;
-			if let Some(id) = session_validators.get(authority_index as usize) {
+			if let Some(id) = slot_author(slot) {
+				CurrentEpochAuthorshipStats::<T>::mutate(id, |stats| {
+					stats.missed = stats.missed.saturating_add(1)
+				});
 				T::OffenceReporter::report(PalletOffence::MissedAuthorshipSlot, id.clone());
 			} else {
 				log::error!(
@@ -1337,22 +2061,233 @@ impl<T: Config> Pallet<T> {
 	pub fn get_active_bids() -> Vec<Bid<ValidatorIdOf<T>, T::Amount>> {
 		ActiveBidder::<T>::get()
 			.into_iter()
-			.map(|bidder_id| Bid {
-				bidder_id: <ValidatorIdOf<T> as IsType<T::AccountId>>::from_ref(&bidder_id).clone(),
-				amount: T::FundingInfo::balance(&bidder_id),
+			.map(|bidder_id| {
+				let stake = T::FundingInfo::balance(&bidder_id)
+					.saturating_add(Self::delegated_stake(&bidder_id));
+				Bid {
+					bidder_id: <ValidatorIdOf<T> as IsType<T::AccountId>>::from_ref(&bidder_id)
+						.clone(),
+					amount: BidLimits::<T>::get(&bidder_id)
+						.map_or(stake, |limit| sp_std::cmp::min(stake, limit)),
+				}
+			})
+			.collect()
+	}
+
+	/// The outcome of resolving the auction with the current qualified bids, if the auction can
+	/// be resolved.
+	pub fn projected_auction_outcome() -> Option<AuctionOutcome<ValidatorIdOf<T>, T::Amount>> {
+		SetSizeMaximisingAuctionResolver::try_new(
+			T::EpochInfo::current_authority_count(),
+			AuctionParameters::<T>::get(),
+		)
+		.and_then(|resolver| {
+			resolver.resolve_auction(
+				Self::get_qualified_bidders::<T::KeygenQualification>(),
+				AuctionBidCutoffPercentage::<T>::get(),
+			)
+		})
+		.ok()
+	}
+
+	/// The bond that would result from resolving the auction with the current bids, if the
+	/// auction can be resolved.
+	pub fn min_active_bid() -> Option<T::Amount> {
+		Self::projected_auction_outcome().map(|auction_outcome| auction_outcome.bond)
+	}
+
+	/// The delegations to an operator, each capped at the delegator's current balance.
+	fn active_delegations(
+		operator: &T::AccountId,
+	) -> impl Iterator<Item = (T::AccountId, T::Amount)> {
+		Delegations::<T>::iter_prefix(operator).map(|(delegator, amount)| {
+			let amount = sp_std::cmp::min(amount, T::FundingInfo::balance(&delegator));
+			(delegator, amount)
+		})
+	}
+
+	/// The total stake delegated to an operator.
+	pub fn delegated_stake(operator: &T::AccountId) -> T::Amount {
+		Self::active_delegations(operator).map(|(_, amount)| amount).sum()
+	}
+
+	/// Splits `amount` between the delegators of `operator`, pro rata to each delegator's share
+	/// of the operator's total backing (its own balance plus all delegated stake). The remainder
+	/// is the operator's share.
+	pub fn delegator_shares(
+		operator: &T::AccountId,
+		amount: T::Amount,
+	) -> Vec<(T::AccountId, T::Amount)> {
+		let delegations = Self::active_delegations(operator).collect::<Vec<_>>();
+		let total_backing = T::FundingInfo::balance(operator)
+			.saturating_add(delegations.iter().map(|(_, stake)| *stake).sum());
+
+		delegations
+			.into_iter()
+			.map(|(delegator, stake)| {
+				(delegator, Perquintill::from_rational(stake, total_backing) * amount)
 			})
+			.filter(|(_, share)| !share.is_zero())
 			.collect()
 	}
 
+	/// Accrues the delegators' share of a reward earned by `operator`, pro rata to their share of
+	/// the operator's total backing and net of the operator's commission, without touching the
+	/// individual delegators. Each delegator's part is settled when its delegation next changes or
+	/// it claims its rewards. Returns the delegators' share of the `reward`.
+	pub fn accrue_delegation_rewards(operator: &T::AccountId, reward: T::Amount) -> T::Amount {
+		let delegated = DelegatedStake::<T>::get(operator);
+		if delegated.is_zero() {
+			return Zero::zero()
+		}
+		let total_backing = T::FundingInfo::balance(operator).saturating_add(delegated);
+		let commission = DelegationCommission::<T>::get(operator).unwrap_or_default();
+		let share = commission.left_from_one() *
+			(Perquintill::from_rational(delegated, total_backing) * reward);
+
+		DelegationRewardPerStake::<T>::mutate(operator, |reward_per_stake| {
+			*reward_per_stake = reward_per_stake
+				.saturating_add(FixedU128::saturating_from_rational(share, delegated))
+		});
+		share
+	}
+
+	/// The rewards a delegator has earned but not yet claimed.
+	pub fn pending_delegation_rewards(delegator: &T::AccountId) -> T::Amount {
+		let unsettled = DelegatorOperator::<T>::get(delegator)
+			.map(|operator| Self::unsettled_delegation_rewards(&operator, delegator).0)
+			.unwrap_or_default();
+		UnclaimedDelegationRewards::<T>::get(delegator).saturating_add(unsettled)
+	}
+
+	/// The rewards earned by `delegator` since it was last settled, along with the operator's
+	/// current reward per unit of delegated stake.
+	fn unsettled_delegation_rewards(
+		operator: &T::AccountId,
+		delegator: &T::AccountId,
+	) -> (T::Amount, FixedU128) {
+		let reward_per_stake = DelegationRewardPerStake::<T>::get(operator);
+		let earned = reward_per_stake
+			.saturating_sub(DelegatorRewardCheckpoint::<T>::get(delegator))
+			.saturating_mul_int(Delegations::<T>::get(operator, delegator));
+		(earned, reward_per_stake)
+	}
+
+	/// Moves the rewards earned by `delegator` on its current delegation into its unclaimed
+	/// rewards. Must be called before the delegation changes.
+	fn settle_delegation_rewards(operator: &T::AccountId, delegator: &T::AccountId) {
+		let (earned, reward_per_stake) = Self::unsettled_delegation_rewards(operator, delegator);
+		if !earned.is_zero() {
+			UnclaimedDelegationRewards::<T>::mutate(delegator, |unclaimed| {
+				unclaimed.saturating_accrue(earned)
+			});
+		}
+		DelegatorRewardCheckpoint::<T>::insert(delegator, reward_per_stake);
+	}
+
+	fn pending_undelegation(delegator: &T::AccountId) -> T::Amount {
+		PendingUndelegations::<T>::get(delegator)
+			.map(|(amount, _)| amount)
+			.unwrap_or_default()
+	}
+
+	/// A validator's bond covers the highest bond of its active epochs, plus any rewards it has
+	/// compounded.
+	fn update_validator_bond(validator_id: &ValidatorIdOf<T>) {
+		T::Bonder::update_bond(
+			validator_id,
+			EpochHistory::<T>::active_bond(validator_id)
+				.saturating_add(CompoundedBonds::<T>::get(validator_id.into_ref())),
+		);
+	}
+
+	/// Records a reward earned by a validator, to be compounded into its bond at the next epoch
+	/// boundary if it has opted in.
+	pub fn note_reward(account_id: &T::AccountId, amount: T::Amount) {
+		PendingCompoundedRewards::<T>::mutate_exists(account_id, |pending| {
+			if let Some(pending) = pending {
+				pending.saturating_accrue(amount);
+			}
+		});
+	}
+
+	/// Adds a reward to the validator's statement for the current epoch. `delegators_share` is the
+	/// part of the `reward` that was paid out to the validator's delegators.
+	pub fn note_epoch_reward(
+		account_id: &T::AccountId,
+		kind: RewardKind,
+		reward: T::Amount,
+		delegators_share: T::Amount,
+	) {
+		EpochRewardStatements::<T>::mutate(CurrentEpoch::<T>::get(), account_id, |statement| {
+			match kind {
+				RewardKind::Authorship => statement.authorship.saturating_accrue(reward),
+				RewardKind::Backup => statement.backup.saturating_accrue(reward),
+			}
+			statement.delegators_share.saturating_accrue(delegators_share);
+		});
+	}
+
+	/// Rolls the rewards earned by opted-in validators during the past epoch into their bonds and,
+	/// where set, their bid limits.
+	fn compound_rewards() {
+		for account_id in PendingCompoundedRewards::<T>::iter_keys().collect::<Vec<_>>() {
+			let amount = PendingCompoundedRewards::<T>::mutate(&account_id, |pending| {
+				pending.as_mut().map(sp_std::mem::take).unwrap_or_default()
+			});
+			if amount.is_zero() {
+				continue
+			}
+			let total = CompoundedBonds::<T>::mutate(&account_id, |total| {
+				total.saturating_accrue(amount);
+				*total
+			});
+			BidLimits::<T>::mutate_exists(&account_id, |limit| {
+				if let Some(limit) = limit {
+					limit.saturating_accrue(amount);
+				}
+			});
+			Self::update_validator_bond(<ValidatorIdOf<T> as IsType<T::AccountId>>::from_ref(
+				&account_id,
+			));
+			Self::deposit_event(Event::RewardsCompounded { account_id, amount, total });
+		}
+	}
+
+	/// A delegator's bond covers its active delegation as well as any stake awaiting release.
+	fn update_delegator_bond(delegator: &T::AccountId) {
+		let delegated = DelegatorOperator::<T>::get(delegator)
+			.map(|operator| Delegations::<T>::get(operator, delegator))
+			.unwrap_or_default();
+		T::Bonder::update_bond(
+			<ValidatorIdOf<T> as IsType<T::AccountId>>::from_ref(delegator),
+			delegated.saturating_add(Self::pending_undelegation(delegator)),
+		);
+	}
+
 	pub fn get_qualified_bidders<Q: QualifyNode<ValidatorIdOf<T>>>(
 	) -> Vec<Bid<ValidatorIdOf<T>, T::Amount>> {
 		Q::filter_qualified_by_key(Self::get_active_bids(), |Bid { ref bidder_id, .. }| bidder_id)
 	}
 
+	/// The validators that have authorised the given operator.
+	pub fn operator_validators(operator: &T::AccountId) -> Vec<T::AccountId> {
+		ValidatorOperators::<T>::iter()
+			.filter_map(|(validator, authorised)| (authorised == *operator).then_some(validator))
+			.collect()
+	}
+
 	pub fn is_bidding(account_id: &T::AccountId) -> bool {
 		ActiveBidder::<T>::get().contains(account_id)
 	}
 
+	/// Whether the account stopped bidding less than [DeregistrationGracePeriod] blocks ago.
+	pub fn in_deregistration_grace_period(account_id: &T::AccountId) -> bool {
+		PendingBidStops::<T>::get(account_id).is_some_and(|(_, grace_period_ends_at)| {
+			frame_system::Pallet::<T>::block_number() < grace_period_ends_at
+		})
+	}
+
 	pub fn is_auction_phase() -> bool {
 		if CurrentRotationPhase::<T>::get() != RotationPhase::Idle {
 			return true
@@ -1554,6 +2489,10 @@ pub struct RemoveVanityNames<T>(PhantomData<T>);
 impl<T: Config> OnKilledAccount<T::AccountId> for RemoveVanityNames<T> {
 	fn on_killed_account(who: &T::AccountId) {
 		ActiveBidder::<T>::mutate(|bidders| bidders.remove(who));
+		ValidatorOperators::<T>::remove(who);
+		PendingBidStops::<T>::remove(who);
+		PendingCompoundedRewards::<T>::remove(who);
+		CompoundedBonds::<T>::remove(who);
 	}
 }
 
@@ -1578,6 +2517,12 @@ impl<T: Config> QualifyNode<<T as Chainflip>::ValidatorId> for QualifyByCfeVersi
 impl<T: Config> RedemptionCheck for Pallet<T> {
 	type ValidatorId = ValidatorIdOf<T>;
 	fn ensure_can_redeem(validator_id: &Self::ValidatorId) -> DispatchResult {
+		ensure!(
+			!Self::in_deregistration_grace_period(
+				<ValidatorIdOf<T> as IsType<T::AccountId>>::into_ref(validator_id)
+			),
+			Error::<T>::DeregistrationGracePeriodNotElapsed
+		);
 		if Self::is_auction_phase() {
 			ensure!(
 				!ActiveBidder::<T>::get()
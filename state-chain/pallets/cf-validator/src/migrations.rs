@@ -16,4 +16,16 @@
 
 use crate::Pallet;
 use cf_runtime_utilities::PlaceholderMigration;
-pub type PalletMigration<T> = (PlaceholderMigration<5, Pallet<T>>,);
+use frame_support::migrations::VersionedMigration;
+mod delegation_bounds;
+
+pub type PalletMigration<T> = (
+	VersionedMigration<
+		5,
+		6,
+		delegation_bounds::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	PlaceholderMigration<6, Pallet<T>>,
+);
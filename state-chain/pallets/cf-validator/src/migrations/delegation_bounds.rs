@@ -0,0 +1,98 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+	Config, DelegatedStake, Delegations, DelegatorCount, MinimumDelegation, UndelegationPeriod,
+	DEFAULT_MINIMUM_DELEGATION, DEFAULT_UNDELEGATION_PERIOD,
+};
+use frame_support::{
+	sp_runtime::traits::{Saturating, Zero},
+	traits::{Get, UncheckedOnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::pallet_prelude::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Sets the undelegation period and minimum delegation, which were not initialised at genesis, to
+/// their defaults, and builds the per-operator delegation totals from the existing delegations.
+pub struct Migration<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if UndelegationPeriod::<T>::get().is_zero() {
+			UndelegationPeriod::<T>::set(DEFAULT_UNDELEGATION_PERIOD.into());
+		}
+		if MinimumDelegation::<T>::get().is_zero() {
+			MinimumDelegation::<T>::set(DEFAULT_MINIMUM_DELEGATION.into());
+		}
+
+		let mut delegations = 0u64;
+		for (operator, _delegator, amount) in Delegations::<T>::iter() {
+			delegations.saturating_inc();
+			DelegatedStake::<T>::mutate(&operator, |stake| stake.saturating_accrue(amount));
+			DelegatorCount::<T>::mutate(&operator, |count| count.saturating_inc());
+		}
+
+		T::DbWeight::get()
+			.reads_writes(2, 2)
+			.saturating_add(T::DbWeight::get().reads_writes(3 * delegations, 2 * delegations))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		frame_support::ensure!(
+			!UndelegationPeriod::<T>::get().is_zero(),
+			"The undelegation period is not set"
+		);
+		for (operator, count) in DelegatorCount::<T>::iter() {
+			frame_support::ensure!(
+				Delegations::<T>::iter_prefix(&operator).count() == count as usize,
+				"Delegator count does not match the delegations"
+			);
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+
+	#[test]
+	fn sets_defaults_and_totals() {
+		new_test_ext().execute_with(|| {
+			const OPERATOR: u64 = 100;
+			const ALICE: u64 = 200;
+			const BOB: u64 = 201;
+			UndelegationPeriod::<Test>::kill();
+			MinimumDelegation::<Test>::kill();
+			Delegations::<Test>::insert(OPERATOR, ALICE, 100);
+			Delegations::<Test>::insert(OPERATOR, BOB, 50);
+
+			Migration::<Test>::on_runtime_upgrade();
+
+			assert_eq!(UndelegationPeriod::<Test>::get(), DEFAULT_UNDELEGATION_PERIOD as u64);
+			assert_eq!(MinimumDelegation::<Test>::get(), DEFAULT_MINIMUM_DELEGATION);
+			assert_eq!(DelegatedStake::<Test>::get(OPERATOR), 150);
+			assert_eq!(DelegatorCount::<Test>::get(OPERATOR), 2);
+		});
+	}
+}
@@ -26,7 +26,7 @@ use cf_traits::{
 		cfe_interface_mock::MockCfeInterface, key_rotator::MockKeyRotatorA,
 		qualify_node::QualifyAll, reputation_resetter::MockReputationResetter,
 	},
-	AccountRoleRegistry, RotationBroadcastsPending,
+	AccountRoleRegistry, DelegationRewardsPayout, RotationBroadcastsPending,
 };
 use frame_support::{construct_runtime, derive_impl};
 use sp_runtime::{impl_opaque_keys, testing::UintAuthorityId, traits::ConvertInto};
@@ -155,6 +155,34 @@ impl Config for Test {
 	type SafeMode = MockRuntimeSafeMode;
 	type ValidatorWeightInfo = ();
 	type CfePeerRegistration = MockCfeInterface;
+	type RuntimeCall = RuntimeCall;
+	type OperatorCallFilter = MockOperatorCallFilter;
+	type DelegationRewards = MockDelegationRewards;
+}
+
+pub struct MockDelegationRewards;
+
+impl DelegationRewardsPayout for MockDelegationRewards {
+	type AccountId = ValidatorId;
+	type Amount = Amount;
+
+	fn pay_out(delegator: &ValidatorId, amount: Amount) {
+		MockFlip::credit_funds(delegator, amount);
+	}
+}
+
+pub struct MockOperatorCallFilter;
+
+impl Contains<RuntimeCall> for MockOperatorCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::ValidatorPallet(
+				pallet_cf_validator::Call::start_bidding { .. } |
+					pallet_cf_validator::Call::stop_bidding { .. }
+			)
+		)
+	}
 }
 
 /// Session pallet requires a set of validators at genesis.
@@ -162,6 +190,7 @@ pub const GENESIS_AUTHORITIES: [u64; 3] = [u64::MAX, u64::MAX - 1, u64::MAX - 2]
 pub const REDEMPTION_PERCENTAGE_AT_GENESIS: Percent = Percent::from_percent(50);
 pub const GENESIS_BOND: Amount = 100;
 pub const EPOCH_DURATION: u64 = 10;
+pub const MINIMUM_DELEGATION: Amount = 10;
 
 fn all_validators() -> Vec<ValidatorId> {
 	[
@@ -201,6 +230,8 @@ cf_test_utilities::impl_test_helpers! {
 			},
 			auction_bid_cutoff_percentage: Percent::from_percent(0),
 			max_authority_set_contraction_percentage: DEFAULT_MAX_AUTHORITY_SET_CONTRACTION,
+			undelegation_period: DEFAULT_UNDELEGATION_PERIOD.into(),
+			minimum_delegation: MINIMUM_DELEGATION,
 		},
 	},
 	||{
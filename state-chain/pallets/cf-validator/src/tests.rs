@@ -27,7 +27,7 @@ use cf_traits::{
 		key_rotator::MockKeyRotatorA,
 		reputation_resetter::MockReputationResetter,
 	},
-	AccountRoleRegistry, SafeMode, SetSafeMode,
+	AccountRoleRegistry, RedemptionCheck, SafeMode, SetSafeMode,
 };
 use cf_utilities::{assert_matches, success_threshold_from_share_count};
 use frame_support::{
@@ -601,6 +601,34 @@ fn test_missing_author_punishment() {
 		});
 }
 
+#[test]
+fn authorship_stats_are_recorded_for_authored_and_missed_slots() {
+	let (expected_authority_index, authored_authority_index) = (1usize, 3usize);
+	new_test_ext()
+		.then_execute_with_checks(|| {
+			let offset: u64 = GENESIS_AUTHORITIES.len() as u64 * 123456;
+			MockMissedAuthorshipSlots::set(
+				expected_authority_index as u64 + offset,
+				authored_authority_index as u64 + offset,
+			);
+		})
+		.then_advance_n_blocks_and_execute_with_checks(1, || {
+			let authorities =
+				ValidatorPallet::current_authorities().into_iter().collect::<Vec<_>>();
+			assert_eq!(
+				CurrentEpochAuthorshipStats::<Test>::get(authorities[authored_authority_index]),
+				AuthorshipStats { authored: 1, missed: 0 }
+			);
+			for missed_authority in &authorities[expected_authority_index..authored_authority_index]
+			{
+				assert_eq!(
+					CurrentEpochAuthorshipStats::<Test>::get(missed_authority).expected(),
+					1
+				);
+			}
+		});
+}
+
 #[test]
 fn no_validator_rotation_when_disabled_by_safe_mode() {
 	new_test_ext().then_execute_with_checks(|| {
@@ -1367,6 +1395,115 @@ fn test_start_and_stop_bidding() {
 	});
 }
 
+#[test]
+fn bidders_can_adjust_bids_in_place() {
+	new_test_ext().then_execute_with_checks(|| {
+		set_default_test_bids();
+		let bidder = WINNING_BIDS[0].bidder_id;
+		let bid_of = |bidder_id| {
+			ValidatorPallet::get_active_bids()
+				.into_iter()
+				.find(|bid| bid.bidder_id == bidder_id)
+				.map(|bid| bid.amount)
+		};
+		assert_eq!(ValidatorPallet::min_active_bid(), Some(EXPECTED_BOND));
+
+		// Can't lower the bid below the current minimum active bid.
+		assert_noop!(
+			ValidatorPallet::adjust_bid(RuntimeOrigin::signed(bidder), Some(EXPECTED_BOND - 1)),
+			Error::<Test>::BidBelowMinimumActiveBid
+		);
+
+		assert_ok!(ValidatorPallet::adjust_bid(RuntimeOrigin::signed(bidder), Some(EXPECTED_BOND)));
+		assert_eq!(bid_of(bidder), Some(EXPECTED_BOND));
+		assert!(ValidatorPallet::is_bidding(&bidder));
+
+		// A limit above the available stake has no effect on the bid.
+		assert_ok!(ValidatorPallet::adjust_bid(RuntimeOrigin::signed(bidder), Some(1_000)));
+		assert_eq!(bid_of(bidder), Some(WINNING_BIDS[0].amount));
+
+		assert_ok!(ValidatorPallet::adjust_bid(RuntimeOrigin::signed(bidder), None));
+		assert_eq!(BidLimits::<Test>::get(bidder), None);
+		assert_eq!(bid_of(bidder), Some(WINNING_BIDS[0].amount));
+
+		for bid_limit in [Some(EXPECTED_BOND), Some(1_000), None] {
+			System::assert_has_event(RuntimeEvent::ValidatorPallet(Event::BidAdjusted {
+				account_id: bidder,
+				bid_limit,
+			}));
+		}
+
+		// Stopping bidding clears the limit, and non-bidders can't adjust their bid.
+		assert_ok!(ValidatorPallet::adjust_bid(RuntimeOrigin::signed(bidder), Some(EXPECTED_BOND)));
+		remove_bids(vec![bidder]);
+		assert_eq!(BidLimits::<Test>::get(bidder), None);
+		assert_noop!(
+			ValidatorPallet::adjust_bid(RuntimeOrigin::signed(bidder), None),
+			Error::<Test>::AlreadyNotBidding
+		);
+
+		// Bids are fixed once the rotation has started.
+		CurrentRotationPhase::<Test>::set(RotationPhase::KeygensInProgress(Default::default()));
+		assert_noop!(
+			ValidatorPallet::adjust_bid(RuntimeOrigin::signed(WINNING_BIDS[1].bidder_id), None),
+			Error::<Test>::RotationInProgress
+		);
+	});
+}
+
+#[test]
+fn opted_in_validators_compound_rewards_into_their_bond() {
+	new_test_ext().execute_with(|| {
+		const BOND: u128 = 100;
+		const REWARD: u128 = 10;
+		const COMPOUNDING: u64 = 1;
+		const NOT_COMPOUNDING: u64 = 2;
+
+		assert_ok!(ValidatorPallet::set_reward_compounding(
+			RuntimeOrigin::signed(COMPOUNDING),
+			true
+		));
+		System::assert_last_event(RuntimeEvent::ValidatorPallet(Event::RewardCompoundingUpdated {
+			account_id: COMPOUNDING,
+			enabled: true,
+		}));
+		BidLimits::<Test>::insert(COMPOUNDING, BOND);
+
+		for account_id in [COMPOUNDING, NOT_COMPOUNDING] {
+			ValidatorPallet::note_reward(&account_id, REWARD);
+		}
+		assert_eq!(PendingCompoundedRewards::<Test>::get(COMPOUNDING), Some(REWARD));
+		assert_eq!(PendingCompoundedRewards::<Test>::get(NOT_COMPOUNDING), None);
+
+		// Rewards are rolled into the bond and bid limit at the epoch boundary.
+		ValidatorPallet::transition_to_next_epoch(vec![COMPOUNDING, NOT_COMPOUNDING], BOND);
+		System::assert_has_event(RuntimeEvent::ValidatorPallet(Event::RewardsCompounded {
+			account_id: COMPOUNDING,
+			amount: REWARD,
+			total: REWARD,
+		}));
+		assert_eq!(MockBonderFor::<Test>::get_bond(&COMPOUNDING), BOND + REWARD);
+		assert_eq!(MockBonderFor::<Test>::get_bond(&NOT_COMPOUNDING), BOND);
+		assert_eq!(BidLimits::<Test>::get(COMPOUNDING), Some(BOND + REWARD));
+		assert_eq!(PendingCompoundedRewards::<Test>::get(COMPOUNDING), Some(0));
+
+		// Compounded rewards accumulate across epochs.
+		ValidatorPallet::note_reward(&COMPOUNDING, REWARD);
+		ValidatorPallet::transition_to_next_epoch(vec![COMPOUNDING, NOT_COMPOUNDING], BOND);
+		assert_eq!(CompoundedBonds::<Test>::get(COMPOUNDING), 2 * REWARD);
+		assert_eq!(MockBonderFor::<Test>::get_bond(&COMPOUNDING), BOND + 2 * REWARD);
+
+		// Opting out releases the compounded rewards.
+		assert_ok!(ValidatorPallet::set_reward_compounding(
+			RuntimeOrigin::signed(COMPOUNDING),
+			false
+		));
+		assert_eq!(CompoundedBonds::<Test>::get(COMPOUNDING), 0);
+		assert_eq!(PendingCompoundedRewards::<Test>::get(COMPOUNDING), None);
+		assert_eq!(MockBonderFor::<Test>::get_bond(&COMPOUNDING), BOND);
+	});
+}
+
 #[test]
 fn can_determine_is_auction_phase() {
 	new_test_ext().execute_with(|| {
@@ -1449,6 +1586,62 @@ fn validator_set_change_propagates_to_session_pallet() {
 		.then_execute_with_checks(|| {});
 }
 
+#[test]
+fn stop_bidding_can_be_cancelled_within_grace_period() {
+	new_test_ext().execute_with(|| {
+		const GRACE_PERIOD: u32 = 10;
+		const BID_LIMIT: u128 = 50;
+
+		assert_ok!(ValidatorPallet::update_pallet_config(
+			OriginTrait::root(),
+			PalletConfigUpdate::DeregistrationGracePeriod { blocks: GRACE_PERIOD },
+		));
+		MockFlip::credit_funds(&ALICE, 100);
+		assert_ok!(<<Test as Chainflip>::AccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_validator(&ALICE));
+		assert_ok!(ValidatorPallet::start_bidding(RuntimeOrigin::signed(ALICE)));
+		BidLimits::<Test>::insert(ALICE, BID_LIMIT);
+
+		// Nothing to cancel yet.
+		assert_noop!(
+			ValidatorPallet::cancel_stop_bidding(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NoPendingBidStop
+		);
+
+		assert_ok!(ValidatorPallet::stop_bidding(RuntimeOrigin::signed(ALICE)));
+		assert!(!ValidatorPallet::is_bidding(&ALICE));
+		assert!(ValidatorPallet::in_deregistration_grace_period(&ALICE));
+
+		// Can't leave while the grace period is running.
+		assert_noop!(
+			ValidatorPallet::deregister_as_validator(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::DeregistrationGracePeriodNotElapsed
+		);
+		assert_noop!(
+			ValidatorPallet::ensure_can_redeem(&ALICE),
+			Error::<Test>::DeregistrationGracePeriodNotElapsed
+		);
+
+		// Cancelling resumes bidding with the previous bid limit.
+		assert_ok!(ValidatorPallet::cancel_stop_bidding(RuntimeOrigin::signed(ALICE)));
+		assert!(ValidatorPallet::is_bidding(&ALICE));
+		assert!(!ValidatorPallet::in_deregistration_grace_period(&ALICE));
+		assert_eq!(BidLimits::<Test>::get(ALICE), Some(BID_LIMIT));
+		System::assert_last_event(RuntimeEvent::ValidatorPallet(Event::StopBiddingCancelled {
+			account_id: ALICE,
+		}));
+
+		// Once the grace period has elapsed, the stop can no longer be cancelled.
+		assert_ok!(ValidatorPallet::stop_bidding(RuntimeOrigin::signed(ALICE)));
+		System::set_block_number(System::block_number() + GRACE_PERIOD as u64);
+		assert!(!ValidatorPallet::in_deregistration_grace_period(&ALICE));
+		assert_noop!(
+			ValidatorPallet::cancel_stop_bidding(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NoPendingBidStop
+		);
+		assert_ok!(ValidatorPallet::ensure_can_redeem(&ALICE));
+	});
+}
+
 #[test]
 fn can_update_all_config_items() {
 	new_test_ext().execute_with(|| {
@@ -1462,6 +1655,8 @@ fn can_update_all_config_items() {
 			SetSizeParameters { min_size: 3, max_size: 10, max_expansion: 10 };
 		const NEW_MINIMUM_REPORTED_CFE_VERSION: SemVer = SemVer { major: 1, minor: 0, patch: 0 };
 		const NEW_MAX_AUTHORITY_SET_CONTRACTION_PERCENTAGE: Percent = Percent::from_percent(10);
+		const NEW_UNDELEGATION_PERIOD: u32 = 100;
+		const NEW_DEREGISTRATION_GRACE_PERIOD: u32 = 50;
 
 		// Check that the default values are different from the new ones
 		assert_ne!(AuctionBidCutoffPercentage::<Test>::get(), NEW_AUCTION_BID_CUTOFF_PERCENTAGE);
@@ -1479,6 +1674,11 @@ fn can_update_all_config_items() {
 			MaxAuthoritySetContractionPercentage::<Test>::get(),
 			NEW_MAX_AUTHORITY_SET_CONTRACTION_PERCENTAGE
 		);
+		assert_ne!(UndelegationPeriod::<Test>::get(), NEW_UNDELEGATION_PERIOD as u64);
+		assert_ne!(
+			DeregistrationGracePeriod::<Test>::get(),
+			NEW_DEREGISTRATION_GRACE_PERIOD as u64
+		);
 
 		// Update all config items
 		let updates = vec![
@@ -1503,6 +1703,10 @@ fn can_update_all_config_items() {
 			PalletConfigUpdate::MaxAuthoritySetContractionPercentage {
 				percentage: NEW_MAX_AUTHORITY_SET_CONTRACTION_PERCENTAGE,
 			},
+			PalletConfigUpdate::UndelegationPeriod { blocks: NEW_UNDELEGATION_PERIOD },
+			PalletConfigUpdate::DeregistrationGracePeriod {
+				blocks: NEW_DEREGISTRATION_GRACE_PERIOD,
+			},
 		];
 		for update in updates {
 			assert_ok!(ValidatorPallet::update_pallet_config(OriginTrait::root(), update.clone()));
@@ -1528,6 +1732,11 @@ fn can_update_all_config_items() {
 			MaxAuthoritySetContractionPercentage::<Test>::get(),
 			NEW_MAX_AUTHORITY_SET_CONTRACTION_PERCENTAGE
 		);
+		assert_eq!(UndelegationPeriod::<Test>::get(), NEW_UNDELEGATION_PERIOD as u64);
+		assert_eq!(
+			DeregistrationGracePeriod::<Test>::get(),
+			NEW_DEREGISTRATION_GRACE_PERIOD as u64
+		);
 
 		// Make sure that only governance can update the config
 		assert_noop!(
@@ -1564,3 +1773,335 @@ fn should_expire_all_previous_epochs() {
 		assert_eq!(HistoricalActiveEpochs::<Test>::get(ID), vec![third_epoch]);
 	});
 }
+
+#[test]
+fn reward_statements_are_kept_per_epoch_until_expiry() {
+	new_test_ext().execute_with(|| {
+		const ID: u64 = 1;
+		const BOND: u128 = 100;
+		ValidatorPallet::transition_to_next_epoch(vec![ID], BOND);
+		let first_epoch = ValidatorPallet::current_epoch();
+
+		ValidatorPallet::note_epoch_reward(&ID, RewardKind::Authorship, 10, 0);
+		ValidatorPallet::note_epoch_reward(&ID, RewardKind::Authorship, 10, 2);
+		ValidatorPallet::note_epoch_reward(&ID, RewardKind::Backup, 5, 1);
+		assert_eq!(
+			EpochRewardStatements::<Test>::get(first_epoch, ID),
+			RewardStatement { authorship: 20, backup: 5, delegators_share: 3 }
+		);
+
+		ValidatorPallet::transition_to_next_epoch(vec![ID], BOND);
+		let second_epoch = ValidatorPallet::current_epoch();
+		ValidatorPallet::note_epoch_reward(&ID, RewardKind::Authorship, 7, 0);
+		assert_eq!(EpochRewardStatements::<Test>::get(first_epoch, ID).authorship, 20);
+		assert_eq!(EpochRewardStatements::<Test>::get(second_epoch, ID).authorship, 7);
+
+		ValidatorPallet::expire_epochs_up_to(first_epoch, Weight::from_all(u64::MAX));
+		assert!(!EpochRewardStatements::<Test>::contains_key(first_epoch, ID));
+		assert!(EpochRewardStatements::<Test>::contains_key(second_epoch, ID));
+	});
+}
+
+mod delegation {
+	use super::*;
+
+	const OPERATOR: u64 = WINNING_BIDS[0].bidder_id;
+	const COMMISSION: Percent = Percent::from_percent(10);
+	const UNDELEGATION_PERIOD: u32 = 20;
+
+	fn setup_operator() {
+		assert_ok!(ValidatorPallet::set_delegation_commission(
+			RuntimeOrigin::signed(OPERATOR),
+			Some(COMMISSION)
+		));
+		assert_ok!(ValidatorPallet::update_pallet_config(
+			OriginTrait::root(),
+			PalletConfigUpdate::UndelegationPeriod { blocks: UNDELEGATION_PERIOD },
+		));
+	}
+
+	#[test]
+	fn delegation_lifecycle() {
+		new_test_ext().execute_with(|| {
+			const AMOUNT: u128 = 100;
+			setup_operator();
+			add_bids(vec![WINNING_BIDS[0].clone()]);
+			MockFlip::credit_funds(&ALICE, AMOUNT);
+
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, AMOUNT));
+			assert_eq!(Delegations::<Test>::get(OPERATOR, ALICE), AMOUNT);
+			assert_eq!(MockBonderFor::<Test>::get_bond(&ALICE), AMOUNT);
+			assert!(ValidatorPallet::get_active_bids()
+				.contains(&Bid { bidder_id: OPERATOR, amount: WINNING_BIDS[0].amount + AMOUNT }));
+			System::assert_last_event(RuntimeEvent::ValidatorPallet(Event::Delegated {
+				delegator: ALICE,
+				operator: OPERATOR,
+				amount: AMOUNT,
+			}));
+
+			// Can't delegate more than the available balance.
+			assert_noop!(
+				ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 1),
+				Error::<Test>::NotEnoughFunds
+			);
+
+			// Undelegated stake no longer backs the operator but remains bonded.
+			assert_ok!(ValidatorPallet::undelegate(RuntimeOrigin::signed(ALICE), None));
+			let unlocks_at = System::block_number() + UNDELEGATION_PERIOD as u64;
+			assert!(!DelegatorOperator::<Test>::contains_key(ALICE));
+			assert_eq!(PendingUndelegations::<Test>::get(ALICE), Some((AMOUNT, unlocks_at)));
+			assert_eq!(ValidatorPallet::delegated_stake(&OPERATOR), 0);
+			assert_eq!(MockBonderFor::<Test>::get_bond(&ALICE), AMOUNT);
+
+			assert_noop!(
+				ValidatorPallet::release_undelegated(RuntimeOrigin::signed(ALICE)),
+				Error::<Test>::UndelegationStillLocked
+			);
+
+			System::set_block_number(unlocks_at);
+			assert_ok!(ValidatorPallet::release_undelegated(RuntimeOrigin::signed(ALICE)));
+			assert_eq!(MockBonderFor::<Test>::get_bond(&ALICE), 0);
+			System::assert_last_event(RuntimeEvent::ValidatorPallet(
+				Event::UndelegatedFundsReleased { delegator: ALICE, amount: AMOUNT },
+			));
+		});
+	}
+
+	#[test]
+	fn delegation_restrictions() {
+		new_test_ext().execute_with(|| {
+			const OTHER_OPERATOR: u64 = WINNING_BIDS[1].bidder_id;
+			MockFlip::credit_funds(&ALICE, 100);
+
+			assert_noop!(
+				ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 50),
+				Error::<Test>::NotAcceptingDelegations
+			);
+
+			setup_operator();
+			assert_ok!(ValidatorPallet::set_delegation_commission(
+				RuntimeOrigin::signed(OTHER_OPERATOR),
+				Some(COMMISSION)
+			));
+
+			assert_noop!(
+				ValidatorPallet::delegate(RuntimeOrigin::signed(OTHER_OPERATOR), OPERATOR, 50),
+				Error::<Test>::ValidatorsCannotDelegate
+			);
+			assert_noop!(
+				ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 0),
+				Error::<Test>::InvalidDelegationAmount
+			);
+
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 50));
+			assert_noop!(
+				ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OTHER_OPERATOR, 50),
+				Error::<Test>::AlreadyDelegating
+			);
+			assert_noop!(
+				ValidatorPallet::undelegate(RuntimeOrigin::signed(ALICE), Some(51)),
+				Error::<Test>::InvalidDelegationAmount
+			);
+			assert_noop!(
+				ValidatorPallet::undelegate(RuntimeOrigin::signed(BOB), None),
+				Error::<Test>::NotDelegating
+			);
+
+			// Delegators can't become validators, and operators can't stop accepting delegations
+			// while stake is delegated to them.
+			assert_noop!(
+				ValidatorPallet::register_as_validator(RuntimeOrigin::signed(ALICE)),
+				Error::<Test>::StillDelegating
+			);
+			assert_noop!(
+				ValidatorPallet::set_delegation_commission(RuntimeOrigin::signed(OPERATOR), None),
+				Error::<Test>::DelegationsOutstanding
+			);
+		});
+	}
+
+	#[test]
+	fn delegators_share_rewards_net_of_commission() {
+		new_test_ext().execute_with(|| {
+			const REWARD: u128 = 1_000;
+			setup_operator();
+			MockFlip::set_balances([(OPERATOR, 200), (ALICE, 100), (BOB, 100)]);
+
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 100));
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(BOB), OPERATOR, 100));
+
+			// Each delegator backs a quarter of the operator's total stake.
+			assert_eq!(
+				ValidatorPallet::delegator_shares(&OPERATOR, REWARD)
+					.into_iter()
+					.collect::<BTreeMap<_, _>>(),
+				BTreeMap::from([(ALICE, 250), (BOB, 250)])
+			);
+
+			// Rewards accrue to the delegators without being paid out.
+			assert_eq!(ValidatorPallet::accrue_delegation_rewards(&OPERATOR, REWARD), 450);
+			assert_eq!(ValidatorPallet::pending_delegation_rewards(&ALICE), 225);
+			assert_eq!(ValidatorPallet::pending_delegation_rewards(&BOB), 225);
+			assert_eq!(MockFlip::balance(&ALICE), 100);
+
+			// Rewards earned before a delegation changes are settled at the old stake.
+			MockFlip::credit_funds(&ALICE, 100);
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 100));
+			assert_eq!(UnclaimedDelegationRewards::<Test>::get(ALICE), 225);
+			assert_eq!(ValidatorPallet::pending_delegation_rewards(&ALICE), 225);
+
+			assert_ok!(ValidatorPallet::claim_delegation_rewards(RuntimeOrigin::signed(ALICE)));
+			assert_eq!(MockFlip::balance(&ALICE), 425);
+			System::assert_last_event(RuntimeEvent::ValidatorPallet(
+				Event::DelegationRewardsClaimed { delegator: ALICE, amount: 225 },
+			));
+			assert_noop!(
+				ValidatorPallet::claim_delegation_rewards(RuntimeOrigin::signed(ALICE)),
+				Error::<Test>::NoDelegationRewards
+			);
+
+			// Rewards earned on undelegated stake can still be claimed.
+			assert_ok!(ValidatorPallet::undelegate(RuntimeOrigin::signed(BOB), None));
+			assert_ok!(ValidatorPallet::claim_delegation_rewards(RuntimeOrigin::signed(BOB)));
+			assert_eq!(MockFlip::balance(&BOB), 325);
+		});
+	}
+
+	#[test]
+	fn delegations_are_bounded() {
+		new_test_ext().execute_with(|| {
+			setup_operator();
+			MockFlip::credit_funds(&ALICE, 100);
+
+			assert_noop!(
+				ValidatorPallet::delegate(
+					RuntimeOrigin::signed(ALICE),
+					OPERATOR,
+					MINIMUM_DELEGATION - 1
+				),
+				Error::<Test>::BelowMinimumDelegation
+			);
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 50));
+			assert_noop!(
+				ValidatorPallet::undelegate(
+					RuntimeOrigin::signed(ALICE),
+					Some(50 - MINIMUM_DELEGATION + 1)
+				),
+				Error::<Test>::BelowMinimumDelegation
+			);
+
+			DelegatorCount::<Test>::insert(OPERATOR, MAX_DELEGATORS_PER_OPERATOR);
+			// Existing delegators can still top up.
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(ALICE), OPERATOR, 50));
+			MockFlip::credit_funds(&BOB, 100);
+			assert_noop!(
+				ValidatorPallet::delegate(RuntimeOrigin::signed(BOB), OPERATOR, 50),
+				Error::<Test>::TooManyDelegators
+			);
+
+			assert_ok!(ValidatorPallet::undelegate(RuntimeOrigin::signed(ALICE), None));
+			assert_eq!(DelegatorCount::<Test>::get(OPERATOR), MAX_DELEGATORS_PER_OPERATOR - 1);
+			assert_ok!(ValidatorPallet::delegate(RuntimeOrigin::signed(BOB), OPERATOR, 50));
+		});
+	}
+}
+
+mod operators {
+	use super::*;
+
+	const OPERATOR: u64 = 200;
+	const VALIDATOR: u64 = WINNING_BIDS[0].bidder_id;
+
+	fn start_bidding_call() -> Box<RuntimeCall> {
+		Box::new(RuntimeCall::ValidatorPallet(Call::start_bidding {}))
+	}
+
+	#[test]
+	fn operators_can_act_on_behalf_of_authorising_validators() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				ValidatorPallet::authorise_operator(
+					RuntimeOrigin::signed(VALIDATOR),
+					Some(OPERATOR)
+				),
+				Error::<Test>::NotAnOperator
+			);
+			assert_ok!(ValidatorPallet::register_as_operator(RuntimeOrigin::signed(OPERATOR)));
+
+			assert_noop!(
+				ValidatorPallet::operate(
+					RuntimeOrigin::signed(OPERATOR),
+					VALIDATOR,
+					start_bidding_call()
+				),
+				Error::<Test>::OperatorNotAuthorised
+			);
+
+			assert_ok!(ValidatorPallet::authorise_operator(
+				RuntimeOrigin::signed(VALIDATOR),
+				Some(OPERATOR)
+			));
+			System::assert_last_event(RuntimeEvent::ValidatorPallet(Event::OperatorAuthorised {
+				validator: VALIDATOR,
+				operator: Some(OPERATOR),
+			}));
+			assert_eq!(ValidatorPallet::operator_validators(&OPERATOR), vec![VALIDATOR]);
+
+			assert_ok!(ValidatorPallet::operate(
+				RuntimeOrigin::signed(OPERATOR),
+				VALIDATOR,
+				start_bidding_call()
+			));
+			assert!(ValidatorPallet::is_bidding(&VALIDATOR));
+
+			// Only non-custodial calls can be made by the operator.
+			assert_noop!(
+				ValidatorPallet::operate(
+					RuntimeOrigin::signed(OPERATOR),
+					VALIDATOR,
+					Box::new(RuntimeCall::ValidatorPallet(Call::deregister_as_validator {}))
+				),
+				Error::<Test>::CallNotPermittedForOperator
+			);
+
+			// Only operators can operate validators.
+			assert_noop!(
+				ValidatorPallet::operate(
+					RuntimeOrigin::signed(VALIDATOR),
+					VALIDATOR,
+					start_bidding_call()
+				),
+				BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn operators_can_only_deregister_once_revoked() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(ValidatorPallet::register_as_operator(RuntimeOrigin::signed(OPERATOR)));
+			assert_ok!(ValidatorPallet::authorise_operator(
+				RuntimeOrigin::signed(VALIDATOR),
+				Some(OPERATOR)
+			));
+
+			assert_noop!(
+				ValidatorPallet::deregister_as_operator(RuntimeOrigin::signed(OPERATOR)),
+				Error::<Test>::OperatorStillAuthorised
+			);
+
+			assert_ok!(ValidatorPallet::authorise_operator(RuntimeOrigin::signed(VALIDATOR), None));
+			assert!(ValidatorOperators::<Test>::get(VALIDATOR).is_none());
+			assert_noop!(
+				ValidatorPallet::operate(
+					RuntimeOrigin::signed(OPERATOR),
+					VALIDATOR,
+					start_bidding_call()
+				),
+				Error::<Test>::OperatorNotAuthorised
+			);
+
+			assert_ok!(ValidatorPallet::deregister_as_operator(RuntimeOrigin::signed(OPERATOR)));
+		});
+	}
+}
@@ -41,10 +41,12 @@ use frame_support::{
 	dispatch::GetDispatchInfo,
 	ensure,
 	pallet_prelude::{DispatchResult, Member, RuntimeDebug},
+	sp_runtime::traits::{One, Saturating},
 	storage::with_storage_layer,
 	traits::{EnsureOrigin, Get, UnfilteredDispatchable},
 	Hashable,
 };
+use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
@@ -101,6 +103,30 @@ pub trait WitnessDataExtraction {
 	fn combine_and_inject(&mut self, data: &mut [Vec<u8>]);
 }
 
+/// The number of blocks for which witnessing failures are retained for reporting.
+pub const WITNESSING_FAILURES_RETENTION_BLOCKS: u32 = 14_400;
+
+/// The number of calls a validator failed to witness before their deadline, and the number of
+/// calls they went on to witness after the deadline had elapsed.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+pub struct WitnessingFailureCount {
+	pub missed: u32,
+	pub late: u32,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum PalletOffence {
 	FailedToWitnessInTime,
@@ -199,6 +225,44 @@ pub mod pallet {
 	pub type WitnessDeadline<T: Config> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<(EpochIndex, CallHash)>, ValueQuery>;
 
+	/// The number of calls per epoch whose witnessing deadline has elapsed.
+	#[pallet::storage]
+	pub type ElapsedWitnessDeadlines<T: Config> =
+		StorageMap<_, Twox64Concat, EpochIndex, u32, ValueQuery>;
+
+	/// The number of elapsed witnessing deadlines per epoch that each authority failed to meet.
+	#[pallet::storage]
+	pub type MissedWitnessDeadlines<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EpochIndex,
+		Blake2_128Concat,
+		<T as Chainflip>::ValidatorId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Calls whose witnessing deadline elapsed before every authority had witnessed them. Votes
+	/// cast for these calls are late.
+	#[pallet::storage]
+	pub type ElapsedDeadlineCalls<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EpochIndex, Identity, CallHash, ()>;
+
+	/// The witnessing failures of each validator, by the block in which they were recorded and the
+	/// epoch of the calls. Missed witnesses are recorded at the witnessing deadline, and late
+	/// witnesses when the vote is cast. Only the last [WITNESSING_FAILURES_RETENTION_BLOCKS]
+	/// blocks are retained.
+	#[pallet::storage]
+	pub type WitnessingFailures<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		(EpochIndex, <T as Chainflip>::ValidatorId),
+		WitnessingFailureCount,
+		ValueQuery,
+	>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_idle(_block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
@@ -257,8 +321,12 @@ pub mod pallet {
 			}
 
 			let mut deletions_count_remaining = max_deletions_count_remaining;
-			let (mut cleared_votes, mut cleared_extra_call_data, mut cleared_call_hash) =
-				(false, false, false);
+			let (
+				mut cleared_votes,
+				mut cleared_extra_call_data,
+				mut cleared_call_hash,
+				mut cleared_elapsed_deadlines,
+			) = (false, false, false, false);
 
 			// Cull the Votes storage
 			let remove_result =
@@ -291,6 +359,8 @@ pub mod pallet {
 					deletions_count_remaining as u32,
 					None,
 				);
+				deletions_count_remaining =
+					deletions_count_remaining.saturating_sub(remove_result.backend as u64);
 				used_weight
 					.saturating_accrue(T::WeightInfo::remove_storage_items(remove_result.backend));
 				if remove_result.maybe_cursor.is_none() {
@@ -298,8 +368,26 @@ pub mod pallet {
 				}
 			}
 
+			// Cull the `ElapsedDeadlineCalls` storage
+			if deletions_count_remaining > 0 {
+				let remove_result = ElapsedDeadlineCalls::<T>::clear_prefix(
+					epoch,
+					deletions_count_remaining as u32,
+					None,
+				);
+				used_weight
+					.saturating_accrue(T::WeightInfo::remove_storage_items(remove_result.backend));
+				if remove_result.maybe_cursor.is_none() {
+					cleared_elapsed_deadlines = true;
+				}
+			}
+
 			// If all storages have been cleared, update storage.
-			if cleared_votes && cleared_extra_call_data && cleared_call_hash {
+			if cleared_votes &&
+				cleared_extra_call_data &&
+				cleared_call_hash &&
+				cleared_elapsed_deadlines
+			{
 				EpochsToCull::<T>::put(epochs_to_cull);
 			}
 			used_weight
@@ -309,6 +397,12 @@ pub mod pallet {
 			// -- Punish nodes who haven't witnessed the call within the grace period. -- //
 			// Cache the authorities to avoid repeated storage lookups.
 			let mut authorities_cache = BTreeMap::new();
+			let retention = BlockNumberFor::<T>::from(WITNESSING_FAILURES_RETENTION_BLOCKS);
+			if n > retention {
+				// Bounded by the number of authorities that failed to witness in the block.
+				let _ = WitnessingFailures::<T>::clear_prefix(n - retention, u32::MAX, None);
+			}
+
 			for (epoch, call_hash) in WitnessDeadline::<T>::take(n) {
 				if let Some(votes) = Votes::<T>::get(epoch, call_hash) {
 					let authorities = authorities_cache
@@ -329,8 +423,21 @@ pub mod pallet {
 						.cloned()
 						.collect::<Vec<_>>();
 
+					ElapsedWitnessDeadlines::<T>::mutate(epoch, |count| {
+						*count = count.saturating_add(1)
+					});
+					for validator in &failed_witnessers {
+						MissedWitnessDeadlines::<T>::mutate(epoch, validator, |count| {
+							*count = count.saturating_add(1)
+						});
+						WitnessingFailures::<T>::mutate(n, (epoch, validator), |failures| {
+							failures.missed.saturating_accrue(1)
+						});
+					}
+
 					// Report these nodes for failed to witness in time.
 					if !failed_witnessers.is_empty() {
+						ElapsedDeadlineCalls::<T>::insert(epoch, call_hash, ());
 						T::OffenceReporter::report_many(
 							PalletOffence::FailedToWitnessInTime,
 							failed_witnessers.clone(),
@@ -442,7 +549,8 @@ pub mod pallet {
 			let num_authorities = T::EpochInfo::authority_count_at_epoch(epoch_index)
 				.ok_or(Error::<T>::InvalidEpoch)?;
 
-			let index = T::EpochInfo::authority_index(epoch_index, &who.into())
+			let validator_id: T::ValidatorId = who.into();
+			let index = T::EpochInfo::authority_index(epoch_index, &validator_id)
 				.ok_or(Error::<T>::UnauthorisedWitness)? as usize;
 
 			// Register the vote
@@ -488,6 +596,15 @@ pub mod pallet {
 				},
 			)?;
 
+			// Votes cast after the witnessing deadline has elapsed are recorded as late.
+			if ElapsedDeadlineCalls::<T>::contains_key(epoch_index, call_hash) {
+				WitnessingFailures::<T>::mutate(
+					frame_system::Pallet::<T>::block_number(),
+					(epoch_index, validator_id),
+					|failures| failures.late.saturating_accrue(1),
+				);
+			}
+
 			// Check if threshold is reached and, if so, apply the voted-on Call.
 			// At the epoch boundary, asynchronicity can cause validators to witness events at a
 			// earlier epoch than intended. We need to check that the same event has not already
@@ -646,6 +763,40 @@ impl<T: Config> Pallet<T> {
 				.collect(),
 		)
 	}
+
+	/// Returns the number of calls the validator witnessed in time during the given epoch, and the
+	/// number of calls whose witnessing deadline has elapsed in that epoch.
+	pub fn witness_participation(
+		epoch: EpochIndex,
+		validator: &<T as Chainflip>::ValidatorId,
+	) -> (u32, u32) {
+		let elapsed = ElapsedWitnessDeadlines::<T>::get(epoch);
+		(elapsed.saturating_sub(MissedWitnessDeadlines::<T>::get(epoch, validator)), elapsed)
+	}
+
+	/// Sums up the witnessing failures of each validator recorded between `from_block` and
+	/// `to_block`, inclusive, per epoch. Blocks whose failures are no longer retained are skipped.
+	pub fn witnessing_failures(
+		from_block: BlockNumberFor<T>,
+		to_block: BlockNumberFor<T>,
+	) -> BTreeMap<(EpochIndex, <T as Chainflip>::ValidatorId), WitnessingFailureCount> {
+		let current_block = frame_system::Pallet::<T>::block_number();
+		let mut block = from_block.max(
+			current_block
+				.saturating_sub(WITNESSING_FAILURES_RETENTION_BLOCKS.into())
+				.saturating_add(One::one()),
+		);
+		let mut report = BTreeMap::<_, WitnessingFailureCount>::new();
+		while block <= to_block.min(current_block) {
+			for (key, failures) in WitnessingFailures::<T>::iter_prefix(block) {
+				let total = report.entry(key).or_default();
+				total.missed.saturating_accrue(failures.missed);
+				total.late.saturating_accrue(failures.late);
+			}
+			block.saturating_inc();
+		}
+		report
+	}
 }
 
 impl<T: pallet::Config> cf_traits::EpochTransitionHandler for Pallet<T> {
@@ -653,6 +804,9 @@ impl<T: pallet::Config> cf_traits::EpochTransitionHandler for Pallet<T> {
 	/// growing indefinitely.
 	fn on_expired_epoch(expired: EpochIndex) {
 		EpochsToCull::<T>::append(expired);
+		ElapsedWitnessDeadlines::<T>::remove(expired);
+		// Bounded by the number of authorities in the expired epoch.
+		let _ = MissedWitnessDeadlines::<T>::clear_prefix(expired, u32::MAX, None);
 	}
 }
 
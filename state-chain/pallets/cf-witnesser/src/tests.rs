@@ -19,8 +19,9 @@
 use crate::{
 	mock::{dummy::pallet as pallet_dummy, *},
 	weights::WeightInfo,
-	CallHash, CallHashExecuted, Config, EpochsToCull, Error, Event, ExtraCallData, PalletOffence,
-	PalletSafeMode, VoteMask, Votes, WitnessDeadline, WitnessedCallsScheduledForDispatch,
+	CallHash, CallHashExecuted, Config, ElapsedDeadlineCalls, EpochsToCull, Error, Event,
+	ExtraCallData, PalletOffence, PalletSafeMode, VoteMask, Votes, WitnessDeadline,
+	WitnessedCallsScheduledForDispatch, WitnessingFailureCount,
 };
 use cf_test_utilities::assert_event_sequence;
 use cf_traits::{
@@ -32,7 +33,7 @@ use frame_support::{
 	traits::{Hooks, Len},
 	weights::Weight,
 };
-use sp_std::collections::btree_set::BTreeSet;
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 
 #[test]
 fn call_on_threshold() {
@@ -252,6 +253,7 @@ fn can_purge_stale_storage() {
 				ExtraCallData::<Test>::insert(e, call2, vec![vec![0], vec![e as u8]]);
 				CallHashExecuted::<Test>::insert(e, call1, ());
 				CallHashExecuted::<Test>::insert(e, call2, ());
+				ElapsedDeadlineCalls::<Test>::insert(e, call1, ());
 			}
 		})
 		// Commit Overlay changeset into the backend DB, to fully test clear_prefix logic.
@@ -319,6 +321,7 @@ fn can_purge_stale_storage() {
 			// Epoch 2's stale data should be fully cleaned.
 			assert_eq!(CallHashExecuted::<Test>::get(2u32, call1), None);
 			assert_eq!(CallHashExecuted::<Test>::get(2u32, call2), None);
+			assert_eq!(ElapsedDeadlineCalls::<Test>::get(2u32, call1), None);
 			assert!(EpochsToCull::<Test>::get().is_empty());
 
 			// Future epoch items are unaffected.
@@ -585,11 +588,66 @@ fn can_punish_failed_witnesser() {
 				accounts: (success_threshold..100u64).collect(),
 			}));
 
+			// Participation is recorded against the epoch.
+			let epoch = MockEpochInfo::epoch_index();
+			assert_eq!(Witnesser::witness_participation(epoch, &0u64), (1, 1));
+			assert_eq!(Witnesser::witness_participation(epoch, &99u64), (0, 1));
+
 			// storage is cleaned up.
 			assert_eq!(WitnessDeadline::<Test>::decode_len(target), None);
 		});
 }
 
+#[test]
+fn witnessing_failures_are_reported_over_block_range() {
+	let mut target = 0u64;
+	let success_threshold = cf_utilities::success_threshold_from_share_count(100u32) as u64;
+	new_test_ext()
+		.execute_with(|| {
+			let (call, _) = setup_witness_authorities(0u64..100u64);
+			let epoch = MockEpochInfo::epoch_index();
+			target = System::block_number() + GracePeriod::get();
+
+			for v in 0u64..=success_threshold {
+				assert_ok!(Witnesser::witness_at_epoch(
+					RuntimeOrigin::signed(v),
+					call.clone(),
+					epoch
+				));
+			}
+			// Votes cast after the call was dispatched but before the deadline are not late.
+			assert!(Witnesser::witnessing_failures(0, System::block_number()).is_empty());
+			(call, epoch)
+		})
+		.then_execute_at_block(target, |context| context)
+		.then_execute_with(|(call, epoch)| {
+			let deadline = System::block_number();
+			let failures = Witnesser::witnessing_failures(0, deadline);
+			assert_eq!(failures.len(), 99 - success_threshold as usize);
+			assert_eq!(
+				failures.get(&(epoch, 99)),
+				Some(&WitnessingFailureCount { missed: 1, late: 0 })
+			);
+			assert_eq!(failures.get(&(epoch, success_threshold)), None);
+
+			// Votes cast after the deadline are recorded as late, at the block they are cast.
+			System::set_block_number(deadline + 1);
+			assert_ok!(Witnesser::witness_at_epoch(RuntimeOrigin::signed(99), call, epoch));
+			assert_eq!(
+				Witnesser::witnessing_failures(0, deadline + 1).get(&(epoch, 99)),
+				Some(&WitnessingFailureCount { missed: 1, late: 1 })
+			);
+			assert_eq!(
+				Witnesser::witnessing_failures(deadline, deadline).len(),
+				99 - success_threshold as usize
+			);
+			assert_eq!(
+				Witnesser::witnessing_failures(deadline + 1, deadline + 1),
+				BTreeMap::from([((epoch, 99), WitnessingFailureCount { missed: 0, late: 1 })])
+			);
+		});
+}
+
 #[test]
 fn can_punish_failed_witnesser_after_forced_witness() {
 	let mut target = 0u64;
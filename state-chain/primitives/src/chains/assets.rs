@@ -809,6 +809,25 @@ assets!(
 	},
 );
 
+impl any::Asset {
+	/// The number of decimals of the asset, ie. the amount of its smallest unit per whole token
+	/// is `10^decimals`.
+	pub const fn decimals(self) -> u8 {
+		match self {
+			any::Asset::Eth | any::Asset::Flip | any::Asset::ArbEth => 18,
+			any::Asset::Dot | any::Asset::HubDot => 10,
+			any::Asset::Sol => 9,
+			any::Asset::Btc => 8,
+			any::Asset::Usdc |
+			any::Asset::Usdt |
+			any::Asset::ArbUsdc |
+			any::Asset::SolUsdc |
+			any::Asset::HubUsdt |
+			any::Asset::HubUsdc => 6,
+		}
+	}
+}
+
 #[cfg(test)]
 mod test_assets {
 	use super::*;
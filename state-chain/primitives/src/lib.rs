@@ -292,6 +292,9 @@ pub enum AccountRole {
 	LiquidityProvider,
 	/// Brokers submit swap deposit requests on behalf of users.
 	Broker,
+	/// Operators perform non-custodial actions on behalf of the validators that have authorised
+	/// them.
+	Operator,
 }
 
 pub type EgressBatch<Amount, EgressAddress> = Vec<(Amount, EgressAddress)>;
@@ -313,6 +316,30 @@ pub enum SwapLeg {
 	ToStable,
 }
 
+/// Why (the remaining part of) a swap request, or a deposit meant to be swapped, was refunded.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SwapRefundReason {
+	/// The swap output kept falling short of the minimum price until the retry duration expired.
+	PriceLimitExceeded,
+	/// The swap could not be executed (e.g. due to insufficient liquidity or price impact) before
+	/// the retry duration expired.
+	DurationExpired,
+	/// The swap request (or its remaining DCA chunks) was cancelled.
+	Cancelled,
+	/// Executing the swap would have exceeded the rolling volume limit for its input asset.
+	VolumeLimitExceeded,
+	/// The swap output could not be added to the funds of the account it was meant to fund.
+	AccountFundingFailed,
+	/// The CCM metadata was invalid or not supported by the destination chain.
+	CcmInvalid,
+	/// The deposit was rejected by the broker after screening.
+	Screened,
+	/// Some other swap parameter (broker fees, refund or DCA parameters, destination address)
+	/// was invalid.
+	InvalidParameters,
+}
+
 pub type TransactionHash = [u8; 32];
 
 #[derive(
@@ -425,6 +452,9 @@ fn is_more_recent_semver() {
 }
 
 pub const MAX_AFFILIATES: u32 = 5;
+
+/// The maximum number of addresses that the output of a swap can be split between.
+pub const MAX_SPLIT_OUTPUT_ADDRESSES: u32 = 5;
 // Beneficiaries can be 1 element larger since they include the primary broker:
 pub const MAX_BENEFICIARIES: u32 = MAX_AFFILIATES + 1;
 
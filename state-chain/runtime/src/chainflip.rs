@@ -34,9 +34,10 @@ use crate::{
 	ArbitrumIngressEgress, AssethubBroadcaster, AssethubChainTracking, AssethubIngressEgress,
 	Authorship, BitcoinChainTracking, BitcoinIngressEgress, BitcoinThresholdSigner, BlockNumber,
 	Emissions, Environment, EthereumBroadcaster, EthereumChainTracking, EthereumIngressEgress,
-	Flip, FlipBalance, Hash, PolkadotBroadcaster, PolkadotChainTracking, PolkadotIngressEgress,
-	PolkadotThresholdSigner, Runtime, RuntimeCall, SolanaBroadcaster, SolanaChainTrackingProvider,
-	SolanaIngressEgress, SolanaThresholdSigner, System, Validator, YEAR,
+	Flip, FlipBalance, Funding, Hash, LiquidityProvider, PolkadotBroadcaster,
+	PolkadotChainTracking, PolkadotIngressEgress, PolkadotThresholdSigner, Runtime, RuntimeCall,
+	RuntimeOrigin, SolanaBroadcaster, SolanaChainTrackingProvider, SolanaIngressEgress,
+	SolanaThresholdSigner, Swapping, System, Validator, YEAR,
 };
 use backup_node_rewards::calculate_backup_rewards;
 use cf_chains::{
@@ -87,28 +88,29 @@ use cf_primitives::{
 	DcaParameters,
 };
 use cf_traits::{
-	AccountInfo, AccountRoleRegistry, BackupRewardsNotifier, BlockEmissions,
-	BroadcastAnyChainGovKey, Broadcaster, Chainflip, CommKeyBroadcaster, DepositApi, EgressApi,
-	EpochInfo, FetchesTransfersLimitProvider, Heartbeat, IngressEgressFeeApi, Issuance,
-	KeyProvider, OnBroadcastReady, OnDeposit, QualifyNode, RewardsDistribution, RuntimeUpgrade,
-	ScheduledEgressDetails,
+	AccountInfo, AccountRoleRegistry, AccountRoleSwitch, BackupRewardsNotifier, BlockEmissions,
+	BroadcastAnyChainGovKey, Broadcaster, Chainflip, CommKeyBroadcaster, DelegationRewardsPayout,
+	DepositApi, EgressApi, EpochInfo, FetchesTransfersLimitProvider, FundAccountFromSwap,
+	Heartbeat, IngressEgressFeeApi, Issuance, KeyProvider, OnBroadcastReady, OnDeposit,
+	QualifyNode, RewardsDistribution, RuntimeUpgrade, ScheduledEgressDetails, Slashing,
 };
 
 use cf_chains::{btc::ScriptPubkey, instances::BitcoinInstance, sol::api::SolanaTransactionType};
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use eth::Address as EvmAddress;
 use frame_support::{
 	dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo},
-	pallet_prelude::DispatchError,
+	pallet_prelude::{DispatchError, DispatchResult},
 	sp_runtime::{
-		traits::{BlockNumberProvider, One, UniqueSaturatedFrom, UniqueSaturatedInto},
+		traits::{BlockNumberProvider, One, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto},
 		FixedPointNumber, FixedU64,
 	},
-	traits::{Defensive, Get},
+	traits::{Contains, Defensive, Get, Imbalance},
 };
 pub use missed_authorship_slots::MissedAuraSlots;
 pub use offences::*;
-use pallet_cf_flip::CallIndexer;
+use pallet_cf_flip::{CallIndexer, FlipSlasher, DELEGATION_REWARDS_POT};
+use pallet_cf_validator::RewardKind;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 pub use signer_nomination::RandomSignerNomination;
@@ -154,12 +156,126 @@ impl RewardsDistribution for BackupNodeEmissions {
 			Emissions::current_authority_emission_per_block(),
 			Self::Balance::unique_saturated_from(Validator::current_authority_count()),
 		) {
-			Flip::settle(&validator_id, Self::Issuance::mint(reward).into());
+			distribute_reward_with_delegators(&validator_id, reward, RewardKind::Backup);
 			<Emissions as BackupRewardsNotifier>::emit_event(&validator_id, reward);
 		}
 	}
 }
 
+/// Mints `reward` for `operator`, setting aside its delegators' share net of the operator's
+/// commission until they claim it, and records it in the operator's reward statement for the
+/// epoch.
+fn distribute_reward_with_delegators(operator: &AccountId, reward: FlipBalance, kind: RewardKind) {
+	type I = pallet_cf_flip::FlipIssuance<Runtime>;
+
+	let delegators_share = Validator::accrue_delegation_rewards(operator, reward);
+	if delegators_share > 0 {
+		let _ = I::mint(delegators_share)
+			.offset(Flip::deposit_reserves(DELEGATION_REWARDS_POT, delegators_share));
+	}
+	let operator_reward = reward.saturating_sub(delegators_share);
+	Flip::settle(operator, I::mint(operator_reward).into());
+	Validator::note_reward(operator, operator_reward);
+	Validator::note_epoch_reward(operator, kind, reward, delegators_share);
+}
+
+/// Pays delegation rewards out of the reserve they were minted into.
+pub struct DelegationRewardsPot;
+
+impl DelegationRewardsPayout for DelegationRewardsPot {
+	type AccountId = AccountId;
+	type Amount = FlipBalance;
+
+	fn pay_out(delegator: &AccountId, amount: FlipBalance) {
+		Flip::settle(delegator, Flip::withdraw_reserves(DELEGATION_REWARDS_POT, amount).into());
+	}
+}
+
+/// Switches accounts between the Broker and LiquidityProvider roles using the roles' own
+/// registration and deregistration calls, so that the same checks and cleanup apply.
+pub struct BrokerLiquidityProviderRoleSwitch;
+
+impl AccountRoleSwitch<AccountId> for BrokerLiquidityProviderRoleSwitch {
+	fn deregister(account_id: &AccountId, role: AccountRole) -> DispatchResult {
+		let origin = RuntimeOrigin::signed(account_id.clone());
+		match role {
+			AccountRole::Broker => Swapping::deregister_as_broker(origin),
+			AccountRole::LiquidityProvider => LiquidityProvider::deregister_lp_account(origin),
+			_ => Err(DispatchError::Other("Role switching is not supported for this role.")),
+		}
+	}
+
+	fn register(account_id: &AccountId, role: AccountRole) -> DispatchResult {
+		let origin = RuntimeOrigin::signed(account_id.clone());
+		match role {
+			AccountRole::Broker => Swapping::register_as_broker(origin),
+			AccountRole::LiquidityProvider => LiquidityProvider::register_lp_account(origin),
+			_ => Err(DispatchError::Other("Role switching is not supported for this role.")),
+		}
+	}
+}
+
+/// Sends FLIP bought by a swap from the Ethereum vault to the State Chain Gateway. The amount
+/// that arrives, net of the egress fee, is added to the account's funds once the transfer has been
+/// witnessed.
+pub struct GatewayAccountFunding;
+
+impl FundAccountFromSwap for GatewayAccountFunding {
+	type AccountId = AccountId;
+
+	fn fund_account(
+		account_id: &AccountId,
+		amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
+		let ScheduledEgressDetails { egress_id, egress_amount, .. } =
+			EthereumIngressEgress::schedule_egress(
+				assets::eth::Asset::Flip,
+				amount,
+				Environment::state_chain_gateway_address(),
+				None,
+			)
+			.map_err(Into::<DispatchError>::into)?;
+		Funding::fund_account_from_swap(egress_id, account_id, egress_amount);
+		Ok(egress_amount)
+	}
+}
+
+/// Slashes an operator together with the stake delegated to it, so that delegators share in
+/// the operator's penalties as well as its rewards.
+pub struct DelegationAwareSlasher;
+
+impl Slashing for DelegationAwareSlasher {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Balance = FlipBalance;
+
+	fn slash(validator_id: &Self::AccountId, blocks_offline: Self::BlockNumber) {
+		FlipSlasher::<Runtime>::slash(validator_id, blocks_offline);
+		// A delegator's bond is its delegated stake, so it is slashed at the operator's rate. The
+		// number of delegators is bounded by `MAX_DELEGATORS_PER_OPERATOR`.
+		for delegator in pallet_cf_validator::Delegations::<Runtime>::iter_key_prefix(validator_id)
+		{
+			FlipSlasher::<Runtime>::slash(&delegator, blocks_offline);
+		}
+	}
+
+	fn slash_balance(account_id: &Self::AccountId, slash_amount: FlipBalance) {
+		let mut operator_amount = slash_amount;
+		for (delegator, share) in Validator::delegator_shares(account_id, slash_amount) {
+			operator_amount.saturating_reduce(share);
+			FlipSlasher::<Runtime>::slash_balance(&delegator, share);
+		}
+		FlipSlasher::<Runtime>::slash_balance(account_id, operator_amount);
+	}
+
+	fn calculate_slash_amount(
+		account_id: &Self::AccountId,
+		blocks: Self::BlockNumber,
+	) -> Self::Balance {
+		FlipSlasher::<Runtime>::calculate_slash_amount(account_id, blocks)
+	}
+}
+
 pub struct ChainflipHeartbeat;
 
 impl Heartbeat for ChainflipHeartbeat {
@@ -172,6 +288,51 @@ impl Heartbeat for ChainflipHeartbeat {
 	}
 }
 
+/// The non-custodial calls an operator may make on behalf of the validators it manages.
+pub struct OperatorCallFilter;
+
+impl Contains<RuntimeCall> for OperatorCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Validator(
+				pallet_cf_validator::Call::start_bidding { .. } |
+					pallet_cf_validator::Call::stop_bidding { .. } |
+					pallet_cf_validator::Call::cancel_stop_bidding { .. } |
+					pallet_cf_validator::Call::adjust_bid { .. } |
+					pallet_cf_validator::Call::set_reward_compounding { .. } |
+					pallet_cf_validator::Call::set_keys { .. } |
+					pallet_cf_validator::Call::register_peer_id { .. }
+			) | RuntimeCall::AccountRoles(pallet_cf_account_roles::Call::set_vanity_name { .. })
+		)
+	}
+}
+
+/// Only allows deposit channels to be opened in batches, since batching any other calls would
+/// bypass fee scaling and the checks made on individual calls.
+pub struct BaseCallFilter;
+
+impl Contains<RuntimeCall> for BaseCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		match call {
+			RuntimeCall::Utility(pallet_utility::Call::batch_all { calls }) =>
+				calls.len() <= crate::constants::common::MAX_BATCHED_DEPOSIT_CHANNELS as usize &&
+					calls.iter().all(|call| {
+						matches!(
+							call,
+							RuntimeCall::Swapping(
+								pallet_cf_swapping::Call::request_swap_deposit_address_with_affiliates {
+									..
+								}
+							)
+						)
+					}),
+			RuntimeCall::Utility(_) => false,
+			_ => true,
+		}
+	}
+}
+
 /// Checks if the caller can execute free transactions
 pub struct WaivedFees;
 
@@ -453,7 +614,11 @@ impl RewardsDistribution for BlockAuthorRewardDistribution {
 		let reward_amount = Emissions::current_authority_emission_per_block();
 		if reward_amount != 0 {
 			if let Some(current_block_author) = Authorship::author() {
-				Flip::settle(&current_block_author, Self::Issuance::mint(reward_amount).into());
+				distribute_reward_with_delegators(
+					&current_block_author,
+					reward_amount,
+					RewardKind::Authorship,
+				);
 			} else {
 				log::warn!("No block author for block {}.", System::current_block_number());
 			}
@@ -810,6 +975,56 @@ macro_rules! impl_deposit_api_for_anychain {
 					)+
 				}
 			}
+
+			fn request_split_swap_deposit_address(
+				source_asset: Asset,
+				destination_asset: Asset,
+				destination_addresses: Vec<(ForeignChainAddress, BasisPoints)>,
+				broker_commission: Beneficiaries<Self::AccountId>,
+				broker_id: Self::AccountId,
+				boost_fee: BasisPoints,
+				refund_parameters: ChannelRefundParametersDecoded,
+				dca_parameters: Option<DcaParameters>,
+			) -> Result<(ChannelId, ForeignChainAddress, <AnyChain as cf_chains::Chain>::ChainBlockNumber, FlipBalance), DispatchError> {
+				match source_asset.into() {
+					$(
+						ForeignChainAndAsset::$chain(source_asset) => $pallet::request_split_swap_deposit_address(
+							source_asset,
+							destination_asset,
+							destination_addresses,
+							broker_commission,
+							broker_id,
+							boost_fee,
+							refund_parameters,
+							dca_parameters,
+						).map(|(channel, address, block_number, channel_opening_fee)| (channel, address, block_number.into(), channel_opening_fee)),
+					)+
+				}
+			}
+
+			fn request_account_funding_deposit_address(
+				source_asset: Asset,
+				account_id: Self::AccountId,
+				broker_commission: Beneficiaries<Self::AccountId>,
+				broker_id: Self::AccountId,
+				boost_fee: BasisPoints,
+				refund_parameters: ChannelRefundParametersDecoded,
+				dca_parameters: Option<DcaParameters>,
+			) -> Result<(ChannelId, ForeignChainAddress, <AnyChain as cf_chains::Chain>::ChainBlockNumber, FlipBalance), DispatchError> {
+				match source_asset.into() {
+					$(
+						ForeignChainAndAsset::$chain(source_asset) => $pallet::request_account_funding_deposit_address(
+							source_asset,
+							account_id,
+							broker_commission,
+							broker_id,
+							boost_fee,
+							refund_parameters,
+							dca_parameters,
+						).map(|(channel, address, block_number, channel_opening_fee)| (channel, address, block_number.into(), channel_opening_fee)),
+					)+
+				}
+			}
 		}
 	}
 }
@@ -1107,10 +1322,19 @@ impl cf_traits::MinimumDeposit for MinimumDepositProvider {
 	}
 }
 
-pub struct LpOrderCallIndexer;
-impl CallIndexer<RuntimeCall> for LpOrderCallIndexer {
-	/// Calls are indexed by the pool's base asset.
-	type CallIndex = Asset;
+/// Identifies a class of calls whose fees are scaled together.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeScalingCallIndex {
+	/// Pool order calls, indexed by the pool's base asset.
+	PoolOrder(Asset),
+	/// Any other call, identified by its pallet and call indices. These are only scaled if they
+	/// are explicitly listed in the fee scaled call classes.
+	Call { pallet_index: u8, call_index: u8 },
+}
+
+pub struct FeeScalingCallIndexer;
+impl CallIndexer<RuntimeCall> for FeeScalingCallIndexer {
+	type CallIndex = FeeScalingCallIndex;
 
 	fn call_index(call: &RuntimeCall) -> Option<Self::CallIndex> {
 		match call {
@@ -1122,6 +1346,10 @@ impl CallIndexer<RuntimeCall> for LpOrderCallIndexer {
 				base_asset,
 				..
 			}) |
+			RuntimeCall::LiquidityPools(pallet_cf_pools::Call::modify_limit_order {
+				base_asset,
+				..
+			}) |
 			RuntimeCall::LiquidityPools(pallet_cf_pools::Call::set_range_order {
 				base_asset,
 				..
@@ -1129,8 +1357,40 @@ impl CallIndexer<RuntimeCall> for LpOrderCallIndexer {
 			RuntimeCall::LiquidityPools(pallet_cf_pools::Call::update_range_order {
 				base_asset,
 				..
-			}) => Some(*base_asset),
-			_ => None,
+			}) |
+			RuntimeCall::LiquidityPools(pallet_cf_pools::Call::set_fee_tier_range_order {
+				base_asset,
+				..
+			}) |
+			RuntimeCall::LiquidityPools(
+				pallet_cf_pools::Call::set_auto_rebalancing_range_order { base_asset, .. },
+			) => Some(FeeScalingCallIndex::PoolOrder(*base_asset)),
+			// Batches are counted by weight against the pool of their first order.
+			RuntimeCall::LiquidityPools(pallet_cf_pools::Call::set_orders_batch {
+				cancel,
+				place,
+			}) => place
+				.first()
+				.map(|order| match order {
+					pallet_cf_pools::NewOrder::Limit { base_asset, .. } |
+					pallet_cf_pools::NewOrder::Range { base_asset, .. } => *base_asset,
+				})
+				.or_else(|| {
+					cancel.first().map(|order| match order {
+						pallet_cf_pools::CloseOrder::Limit { base_asset, .. } |
+						pallet_cf_pools::CloseOrder::Range { base_asset, .. } => *base_asset,
+					})
+				})
+				.map(FeeScalingCallIndex::PoolOrder),
+			_ => {
+				let call_index = call.using_encoded(|bytes| FeeScalingCallIndex::Call {
+					pallet_index: bytes[0],
+					call_index: bytes[1],
+				});
+				pallet_cf_flip::FeeScaledCallClasses::<Runtime>::get()
+					.is_some_and(|call_classes| call_classes.contains(&call_index))
+					.then_some(call_index)
+			},
 		}
 	}
 }
@@ -18,6 +18,7 @@ pub mod arb;
 pub mod btc;
 pub mod dot;
 pub mod eth;
+pub mod evm;
 pub mod hub;
 pub mod sol;
 pub struct AddressDerivation;
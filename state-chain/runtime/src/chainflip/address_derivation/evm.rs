@@ -0,0 +1,66 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::AddressDerivation;
+use crate::Environment;
+use cf_chains::{
+	address::{AddressDerivationApi, EncodedAddress},
+	Arbitrum, Ethereum, ForeignChain,
+};
+use cf_primitives::{
+	chains::assets::{arb, eth},
+	Asset, ChannelId,
+};
+use sp_std::vec::Vec;
+
+/// ONLY FOR USE IN RPC CALLS.
+///
+/// Derives the family of deposit addresses (one per asset) of a broker's private channel on an EVM
+/// chain. Returns an empty list for non-EVM chains. These are the addresses the ingress-egress
+/// pallet opens as deposit channels when the private channel is opened.
+pub fn derive_evm_private_deposit_addresses(
+	chain: ForeignChain,
+	private_channel_id: ChannelId,
+) -> Vec<(Asset, EncodedAddress)> {
+	let network = Environment::network_environment();
+	match chain {
+		ForeignChain::Ethereum => eth::Asset::all()
+			.filter_map(|asset| {
+				<AddressDerivation as AddressDerivationApi<Ethereum>>::generate_address(
+					asset,
+					private_channel_id,
+				)
+				.ok()
+				.map(|address| {
+					(asset.into(), EncodedAddress::from_chain_account::<Ethereum>(address, network))
+				})
+			})
+			.collect(),
+		ForeignChain::Arbitrum => arb::Asset::all()
+			.filter_map(|asset| {
+				<AddressDerivation as AddressDerivationApi<Arbitrum>>::generate_address(
+					asset,
+					private_channel_id,
+				)
+				.ok()
+				.map(|address| {
+					(asset.into(), EncodedAddress::from_chain_account::<Arbitrum>(address, network))
+				})
+			})
+			.collect(),
+		_ => Vec::new(),
+	}
+}
@@ -585,6 +585,7 @@ impl
 				dca_params: swap_details.dca_params,
 				refund_params: swap_details.refund_params,
 				boost_fee: swap_details.boost_fee.into(),
+				refund_ccm_metadata: None,
 			},
 		);
 	}
@@ -19,27 +19,41 @@ use crate::{
 		address_derivation::btc::derive_btc_vault_deposit_addresses, AddressConverter,
 		ChainAddressConverter, EvmEnvironment, SolEnvironment,
 	},
-	runtime_apis::{DispatchErrorWithMessage, EvmVaultSwapDetails, VaultSwapDetails},
+	runtime_apis::{
+		DecodedAffiliate, DecodedVaultSwap, DispatchErrorWithMessage, EvmVaultSwapDetails,
+		VaultSwapDetails,
+	},
 	AccountId, BlockNumber, Environment, Runtime, Swapping,
 };
 
 use cf_chains::{
-	address::EncodedAddress,
+	address::{EncodedAddress, IntoForeignChainAddress},
 	btc::vault_swap_encoding::{
 		encode_swap_params_in_nulldata_payload, BtcCfParameters, UtxoEncodedData,
 	},
-	cf_parameters::build_cf_parameters,
-	evm::api::{EvmCall, EvmEnvironmentProvider},
+	cf_parameters::{
+		build_cf_parameters, CfParameters, VaultSwapParameters, VersionedCcmCfParameters,
+		VersionedCfParameters,
+	},
+	evm::api::{decode_vault_swap_calldata, EvmCall, EvmEnvironmentProvider},
+	hub::vault_swap_encoding::{
+		decode_swap_params_from_remark, encode_swap_params_in_remark, vault_swap_call,
+		AssethubVaultSwapData,
+	},
 	sol::{
-		api::SolanaEnvironment, instruction_builder::SolanaInstructionBuilder,
-		sol_tx_core::address_derivation::derive_associated_token_account, SolAmount, SolPubkey,
+		api::SolanaEnvironment,
+		instruction_builder::{DecodedSolanaVaultSwap, SolanaInstructionBuilder},
+		sol_tx_core::address_derivation::derive_associated_token_account,
+		SolAmount, SolPubkey, SolVersionedMessage,
 	},
-	Arbitrum, CcmChannelMetadata, ChannelRefundParametersEncoded, Ethereum, ForeignChain, Solana,
+	Arbitrum, Assethub, CcmChannelMetadata, Chain, ChannelRefundParametersEncoded, Ethereum,
+	ForeignChain, Solana,
 };
 use cf_primitives::{
-	AffiliateAndFee, Affiliates, Asset, AssetAmount, BasisPoints, DcaParameters, SWAP_DELAY_BLOCKS,
+	AffiliateAndFee, Affiliates, Asset, AssetAmount, BasisPoints, DcaParameters, GasAmount,
 };
 use cf_traits::AffiliateRegistry;
+use codec::{Decode, Encode};
 use scale_info::prelude::string::String;
 use sp_core::U256;
 use sp_std::vec::Vec;
@@ -96,7 +110,7 @@ pub fn bitcoin_vault_swap(
 			chunk_interval: dca_parameters
 				.as_ref()
 				.map(|params| params.chunk_interval)
-				.unwrap_or(SWAP_DELAY_BLOCKS)
+				.unwrap_or_else(pallet_cf_swapping::SwapDelayBlocks::<Runtime>::get)
 				.try_into()
 				.map_err(|_| pallet_cf_swapping::Error::<Runtime>::InvalidDcaParameters)?,
 			boost_fee,
@@ -287,56 +301,357 @@ pub fn solana_vault_swap<A>(
 		processed_affiliate_fees,
 		channel_metadata.as_ref(),
 	);
+	let address_lookup_table_account = api_environment.address_lookup_table_account.clone();
 
-	Ok(VaultSwapDetails::Solana {
-		instruction: match source_asset {
-			Asset::Sol => SolanaInstructionBuilder::x_swap_native(
+	let instruction = match source_asset {
+		Asset::Sol => SolanaInstructionBuilder::x_swap_native(
+			api_environment,
+			swap_endpoint_native_vault.into(),
+			destination_asset,
+			destination_address,
+			from,
+			event_data_account,
+			input_amount,
+			cf_parameters,
+			channel_metadata,
+		),
+		Asset::SolUsdc => {
+			let token_supported_account =
+				cf_chains::sol::sol_tx_core::address_derivation::derive_token_supported_account(
+					api_environment.vault_program,
+					api_environment.usdc_token_mint_pubkey,
+				)
+				.map_err(|_| "Failed to derive supported token account")?;
+
+			let from_token_account = match from_token_account {
+				Some(token_account) => SolPubkey::try_from(token_account)
+					.map_err(|_| "Failed to decode the source token account")?,
+				// Defaulting to the user's associated token account
+				None => derive_associated_token_account(
+					from.into(),
+					api_environment.usdc_token_mint_pubkey,
+				)
+				.map_err(|_| "Failed to derive the associated token account")?
+				.address
+				.into(),
+			};
+
+			SolanaInstructionBuilder::x_swap_usdc(
 				api_environment,
-				swap_endpoint_native_vault.into(),
 				destination_asset,
 				destination_address,
 				from,
+				from_token_account,
 				event_data_account,
+				token_supported_account.address.into(),
 				input_amount,
 				cf_parameters,
 				channel_metadata,
+			)
+		},
+		_ => Err("Invalid source_asset: Not a Solana asset.")?,
+	};
+
+	let transaction_message = SolVersionedMessage::new(
+		&[instruction.clone()],
+		Some(from),
+		None,
+		&[address_lookup_table_account.clone()],
+	);
+	// The lookup table is only returned if the message actually references it.
+	let address_lookup_tables = transaction_message
+		.address_table_lookups()
+		.is_some_and(|lookups| {
+			lookups
+				.iter()
+				.any(|lookup| lookup.account_key == address_lookup_table_account.key)
+		})
+		.then(|| address_lookup_table_account.into())
+		.into_iter()
+		.collect();
+
+	Ok(VaultSwapDetails::Solana {
+		instruction: instruction.into(),
+		address_lookup_tables,
+		transaction_message: transaction_message.serialize(),
+	})
+}
+
+pub fn assethub_vault_swap<A>(
+	broker_id: AccountId,
+	source_asset: Asset,
+	amount: AssetAmount,
+	destination_asset: Asset,
+	destination_address: EncodedAddress,
+	broker_commission: BasisPoints,
+	refund_params: ChannelRefundParametersEncoded,
+	boost_fee: u8,
+	affiliate_fees: Affiliates<AccountId>,
+	dca_parameters: Option<DcaParameters>,
+) -> Result<VaultSwapDetails<A>, DispatchErrorWithMessage> {
+	let source_asset: cf_chains::assets::hub::Asset = source_asset
+		.try_into()
+		.map_err(|_| "Invalid source_asset: Not an Assethub asset.")?;
+	let vault_account =
+		Environment::assethub_vault_account().ok_or("Assethub vault account is not set")?;
+
+	let refund_params = refund_params.try_map_address(|addr| {
+		Ok::<_, DispatchErrorWithMessage>(
+			ChainAddressConverter::try_from_encoded_address(addr)
+				.and_then(|addr| addr.try_into().map_err(|_| ()))
+				.map_err(|_| "Invalid refund address")?,
+		)
+	})?;
+	let processed_affiliate_fees = to_affiliate_and_fees(&broker_id, affiliate_fees)?
+		.try_into()
+		.map_err(|_| "Too many affiliates.")?;
+
+	let remark = encode_swap_params_in_remark(AssethubVaultSwapData {
+		output_asset: destination_asset,
+		output_address: destination_address,
+		cf_parameters: build_cf_parameters::<Assethub>(
+			refund_params,
+			dca_parameters,
+			boost_fee,
+			broker_id,
+			broker_commission,
+			processed_affiliate_fees,
+			None,
+		),
+	});
+
+	Ok(VaultSwapDetails::Assethub {
+		call: vault_swap_call(source_asset, amount, vault_account, remark.clone()).encode(),
+		remark,
+		vault_account,
+	})
+}
+
+fn decoded_affiliates(
+	broker_id: Option<&AccountId>,
+	affiliates: impl IntoIterator<Item = AffiliateAndFee>,
+) -> Vec<DecodedAffiliate> {
+	affiliates
+		.into_iter()
+		.map(|AffiliateAndFee { affiliate, fee }| DecodedAffiliate {
+			short_id: affiliate,
+			account_id: broker_id.and_then(|broker_id| {
+				<Swapping as AffiliateRegistry>::get_account_id(broker_id, affiliate)
+			}),
+			fee: fee.into(),
+		})
+		.collect()
+}
+
+fn decode_destination(
+	dst_chain: u32,
+	dst_address: Vec<u8>,
+	dst_token: u32,
+) -> Result<(Asset, EncodedAddress), DispatchErrorWithMessage> {
+	Ok((
+		Asset::try_from(dst_token)?,
+		EncodedAddress::from_chain_bytes(ForeignChain::try_from(dst_chain)?, dst_address)?,
+	))
+}
+
+/// Decodes `cf_parameters` as built by `build_cf_parameters::<C>` and combines them with the
+/// parameters that are encoded elsewhere in the vault swap call.
+fn decode_with_cf_parameters<C: Chain>(
+	source_asset: Option<Asset>,
+	amount: Option<AssetAmount>,
+	destination_asset: Asset,
+	destination_address: EncodedAddress,
+	ccm: Option<(Vec<u8>, GasAmount)>,
+	cf_parameters: &[u8],
+) -> Result<DecodedVaultSwap, DispatchErrorWithMessage> {
+	let (
+		VaultSwapParameters { refund_params, dca_params, boost_fee, broker_fee, affiliate_fees },
+		channel_metadata,
+	) = match ccm {
+		Some((message, gas_budget)) => {
+			let VersionedCcmCfParameters::<C::ChainAccount>::V0(CfParameters {
+				ccm_additional_data,
+				vault_swap_parameters,
+			}) = VersionedCcmCfParameters::<C::ChainAccount>::decode(&mut &cf_parameters[..])
+				.map_err(|_| "Failed to decode cf_parameters")?;
+			(
+				vault_swap_parameters,
+				Some(CcmChannelMetadata {
+					message: message.try_into().map_err(|_| "CCM message is too long")?,
+					gas_budget,
+					ccm_additional_data,
+				}),
+			)
+		},
+		None => {
+			let VersionedCfParameters::<C::ChainAccount>::V0(CfParameters {
+				vault_swap_parameters,
+				..
+			}) = VersionedCfParameters::<C::ChainAccount>::decode(&mut &cf_parameters[..])
+				.map_err(|_| "Failed to decode cf_parameters")?;
+			(vault_swap_parameters, None)
+		},
+	};
+
+	Ok(DecodedVaultSwap {
+		source_asset,
+		amount,
+		destination_asset,
+		destination_address,
+		refund_address: Some(ChainAddressConverter::to_encoded_address(
+			<C::ChainAccount as IntoForeignChainAddress<C>>::into_foreign_chain_address(
+				refund_params.refund_address,
 			),
-			Asset::SolUsdc => {
-				let token_supported_account =
-						cf_chains::sol::sol_tx_core::address_derivation::derive_token_supported_account(
-							api_environment.vault_program,
-							api_environment.usdc_token_mint_pubkey,
-						)
-						.map_err(|_| "Failed to derive supported token account")?;
-
-				let from_token_account = match from_token_account {
-					Some(token_account) => SolPubkey::try_from(token_account)
-						.map_err(|_| "Failed to decode the source token account")?,
-					// Defaulting to the user's associated token account
-					None => derive_associated_token_account(
-						from.into(),
-						api_environment.usdc_token_mint_pubkey,
-					)
-					.map_err(|_| "Failed to derive the associated token account")?
-					.address
-					.into(),
+		)),
+		retry_duration: refund_params.retry_duration,
+		min_price: Some(refund_params.min_price),
+		min_output_amount: None,
+		dca_parameters: dca_params,
+		boost_fee,
+		affiliate_fees: decoded_affiliates(Some(&broker_fee.account), affiliate_fees),
+		broker: Some(broker_fee.account),
+		broker_commission: broker_fee.bps,
+		channel_metadata,
+	})
+}
+
+fn evm_token_asset(chain: ForeignChain, token: cf_chains::eth::Address) -> Option<Asset> {
+	match chain {
+		ForeignChain::Ethereum => cf_chains::assets::eth::Asset::all()
+			.find(|asset| {
+				<EvmEnvironment as EvmEnvironmentProvider<Ethereum>>::token_address(*asset) ==
+					Some(token)
+			})
+			.map(Into::into),
+		ForeignChain::Arbitrum => cf_chains::assets::arb::Asset::all()
+			.find(|asset| {
+				<EvmEnvironment as EvmEnvironmentProvider<Arbitrum>>::token_address(*asset) ==
+					Some(token)
+			})
+			.map(Into::into),
+		_ => None,
+	}
+}
+
+/// Decodes the raw encoding of a vault swap on the given chain: the nulldata payload for Bitcoin,
+/// the calldata for EVM chains, the instruction data for Solana and the remark for Assethub.
+pub fn decode_vault_swap(
+	chain: ForeignChain,
+	data: Vec<u8>,
+) -> Result<DecodedVaultSwap, DispatchErrorWithMessage> {
+	match chain {
+		ForeignChain::Bitcoin => {
+			let UtxoEncodedData { output_asset, output_address, parameters } =
+				UtxoEncodedData::decode(&mut &data[..])
+					.map_err(|_| "Failed to decode Bitcoin nulldata payload")?;
+			Ok(DecodedVaultSwap {
+				source_asset: Some(Asset::Btc),
+				amount: None,
+				destination_asset: output_asset,
+				destination_address: output_address,
+				refund_address: None,
+				retry_duration: parameters.retry_duration.into(),
+				min_price: None,
+				min_output_amount: Some(parameters.min_output_amount),
+				dca_parameters: Some(DcaParameters {
+					number_of_chunks: parameters.number_of_chunks.into(),
+					chunk_interval: parameters.chunk_interval.into(),
+				}),
+				boost_fee: parameters.boost_fee,
+				broker: None,
+				broker_commission: parameters.broker_fee.into(),
+				affiliate_fees: decoded_affiliates(None, parameters.affiliates),
+				channel_metadata: None,
+			})
+		},
+		ForeignChain::Ethereum | ForeignChain::Arbitrum => {
+			let call = decode_vault_swap_calldata(&data)
+				.ok_or("Calldata is not a supported vault swap call")?;
+			let (destination_asset, destination_address) =
+				decode_destination(call.dst_chain, call.dst_address, call.dst_token)?;
+			let (source_asset, amount) = match call.src_token {
+				Some((token, amount)) => (
+					evm_token_asset(chain, token),
+					Some(amount.try_into().map_err(|_| "Amount is too large")?),
+				),
+				None => (Some(chain.gas_asset()), None),
+			};
+			let ccm = call
+				.ccm
+				.map(|(message, gas_amount)| {
+					Ok::<_, DispatchErrorWithMessage>((
+						message,
+						gas_amount.try_into().map_err(|_| "Gas amount is too large")?,
+					))
+				})
+				.transpose()?;
+
+			let decode = if chain == ForeignChain::Ethereum {
+				decode_with_cf_parameters::<Ethereum>
+			} else {
+				decode_with_cf_parameters::<Arbitrum>
+			};
+			decode(
+				source_asset,
+				amount,
+				destination_asset,
+				destination_address,
+				ccm,
+				&call.cf_parameters,
+			)
+		},
+		ForeignChain::Solana => {
+			let (source_asset, amount, dst_chain, dst_address, dst_token, ccm, cf_parameters) =
+				match SolanaInstructionBuilder::decode_vault_swap_instruction_data(&data)
+					.ok_or("Instruction data is not a supported vault swap instruction")?
+				{
+					DecodedSolanaVaultSwap::Native(params) => (
+						Asset::Sol,
+						params.amount,
+						params.dst_chain,
+						params.dst_address,
+						params.dst_token,
+						params.ccm_parameters,
+						params.cf_parameters,
+					),
+					// The mint is not part of the instruction data, but USDC is the only token
+					// supported by the swap endpoint.
+					DecodedSolanaVaultSwap::Token(params) => (
+						Asset::SolUsdc,
+						params.amount,
+						params.dst_chain,
+						params.dst_address,
+						params.dst_token,
+						params.ccm_parameters,
+						params.cf_parameters,
+					),
 				};
+			let (destination_asset, destination_address) =
+				decode_destination(dst_chain, dst_address, dst_token)?;
 
-				SolanaInstructionBuilder::x_swap_usdc(
-					api_environment,
-					destination_asset,
-					destination_address,
-					from,
-					from_token_account,
-					event_data_account,
-					token_supported_account.address.into(),
-					input_amount,
-					cf_parameters,
-					channel_metadata,
-				)
-			},
-			_ => Err("Invalid source_asset: Not a Solana asset.")?,
-		}
-		.into(),
-	})
+			decode_with_cf_parameters::<Solana>(
+				Some(source_asset),
+				Some(amount.into()),
+				destination_asset,
+				destination_address,
+				ccm.map(|ccm| (ccm.message, ccm.gas_amount.into())),
+				&cf_parameters,
+			)
+		},
+		ForeignChain::Assethub => {
+			let AssethubVaultSwapData { output_asset, output_address, cf_parameters } =
+				decode_swap_params_from_remark(&data)
+					.ok_or("Remark is not a supported vault swap remark")?;
+
+			decode_with_cf_parameters::<Assethub>(
+				None,
+				None,
+				output_asset,
+				output_address,
+				None,
+				&cf_parameters,
+			)
+		},
+		ForeignChain::Polkadot => Err("Vault swaps are not supported for Polkadot".into()),
+	}
 }
@@ -116,4 +116,7 @@ pub mod common {
 	/// dispatched to the witnessing deadline. After the deadline is passed, any authorities failed
 	/// to witness the dispatched call are penalized.
 	pub const LATE_WITNESS_GRACE_PERIOD: BlockNumber = 10u32;
+
+	/// The maximum number of deposit channels that can be opened in a single batch.
+	pub const MAX_BATCHED_DEPOSIT_CHANNELS: u32 = 32;
 }
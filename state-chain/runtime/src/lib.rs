@@ -27,8 +27,9 @@ pub mod test_runner;
 mod weights;
 use crate::{
 	chainflip::{
-		address_derivation::btc::{
-			derive_btc_vault_deposit_addresses, BitcoinPrivateBrokerDepositAddresses,
+		address_derivation::{
+			btc::{derive_btc_vault_deposit_addresses, BitcoinPrivateBrokerDepositAddresses},
+			evm::derive_evm_private_deposit_addresses,
 		},
 		calculate_account_apy,
 		solana_elections::{
@@ -38,17 +39,27 @@ use crate::{
 		Offence,
 	},
 	monitoring_apis::{
-		ActivateKeysBroadcastIds, AuthoritiesInfo, BtcUtxos, EpochState, ExternalChainsBlockHeight,
-		FeeImbalance, FlipSupply, LastRuntimeUpgradeInfo, OpenDepositChannels, PendingBroadcasts,
-		PendingTssCeremonies, RedemptionsInfo, SolanaNonces,
+		ActivateKeysBroadcastIds, AuraKeyOwner, AuthoritiesInfo, BitcoinUtxoSummary, BtcUtxos,
+		EpochState, ExternalChainsBlockHeight, ExternalChainsWitnessingLag, FeeImbalance,
+		FlipSupply, LastRuntimeUpgradeInfo, OpenDepositChannels, PendingBroadcasts,
+		PendingRedemption, PendingTssCeremonies, RedemptionsInfo, SolanaNonceAccountStatus,
+		SolanaNonceAccountsStatus, SolanaNonces, WitnessingLag,
 	},
 	runtime_apis::{
-		runtime_decl_for_custom_runtime_api::CustomRuntimeApi, AuctionState, BoostPoolDepth,
-		BoostPoolDetails, BrokerInfo, CcmData, ChannelActionType, DispatchErrorWithMessage,
-		FailingWitnessValidators, FeeTypes, LiquidityProviderBoostPoolInfo, LiquidityProviderInfo,
-		RuntimeApiPenalty, SimulateSwapAdditionalOrder, SimulatedSwapInformation,
-		TradingStrategyInfo, TradingStrategyLimits, TransactionScreeningEvents, ValidatorInfo,
-		VaultAddresses, VaultSwapDetails,
+		runtime_decl_for_custom_runtime_api::CustomRuntimeApi, AccountEvent, AccountSummary,
+		AccountsPage, AffiliateInfo, AssetIngressEgressStatus, AuctionState, BoostPoolDepth,
+		BoostPoolDetails, BoostPoolDetailsPage, BoostQuote, BroadcastLifecycleEvent,
+		BroadcastState, BrokerInfo, CallFeeQuote, CcmData, CcmValidationError, ChannelActionType,
+		DecodedEvmCall, DecodedVaultSwap, DepositEvent, DepositStage, DispatchErrorWithMessage,
+		EgressBroadcastEvent, EpochRewardStatement, ExternalTransactionRef, FailedCallDetails,
+		FailedCallStatus, FailingWitnessValidators, FeeScalingInfo, FeeTypes,
+		LiquidityProviderBoostPoolInfo, LiquidityProviderInfo, LpEpochEarnedFees,
+		ProjectedAuctionOutcome, ProjectedBid, RotationBlocker, RotationStatus, RuntimeApiPenalty,
+		RuntimeApiRotationPhase, ScheduledEgress, SimulateSwapAdditionalOrder,
+		SimulatedSwapInformation, SimulatedSwapInformationWithFees, TradingStrategyInfo,
+		TradingStrategyLimits, TransactionScreeningEvents, ValidatorInfo, ValidatorPerformance,
+		ValidatorWitnessReport, VaultAddresses, VaultRotationProgress, VaultSwapDetails,
+		MAX_ACCOUNTS_PAGE_SIZE, MAX_BOOST_POOL_DETAILS_PAGE_SIZE,
 	},
 };
 use cf_amm::{
@@ -79,8 +90,9 @@ use cf_chains::{
 	Solana, TransactionBuilder, VaultSwapExtraParameters, VaultSwapExtraParametersEncoded,
 };
 use cf_primitives::{
-	Affiliates, BasisPoints, Beneficiary, BroadcastId, DcaParameters, EpochIndex,
-	NetworkEnvironment, STABLE_ASSET,
+	AffiliateShortId, Affiliates, BasisPoints, Beneficiary, BoostPoolTier, BroadcastId,
+	DcaParameters, EpochIndex, NetworkEnvironment, SwapRequestId, BASIS_POINTS_PER_MILLION,
+	STABLE_ASSET,
 };
 use cf_traits::{
 	AdjustedFeeEstimationApi, AssetConverter, BalanceApi, DummyEgressSuccessWitnesser,
@@ -89,17 +101,18 @@ use cf_traits::{
 };
 use codec::{alloc::string::ToString, Decode, Encode};
 use core::ops::Range;
-use frame_support::{derive_impl, instances::*};
+use frame_support::{derive_impl, dispatch::GetDispatchInfo, instances::*};
 pub use frame_system::Call as SystemCall;
 use monitoring_apis::MonitoringDataV2;
 use pallet_cf_governance::GovCallHash;
 use pallet_cf_ingress_egress::{IngressOrEgress, OwedAmount, TargetChainAsset};
 use pallet_cf_pools::{
 	AskBidMap, HistoricalEarnedFees, PoolLiquidity, PoolOrderbook, PoolPriceV1, PoolPriceV2,
-	UnidirectionalPoolDepth,
+	PoolStatistics, UnidirectionalPoolDepth,
 };
 use pallet_cf_swapping::{
-	AffiliateDetails, BatchExecutionError, BrokerPrivateBtcChannels, FeeType, Swap,
+	AffiliateAction, AffiliateDetails, BatchExecutionError, BrokerChannelDefaults,
+	BrokerPrivateBtcChannels, DcaUpdate, FeeType, Swap, SwapStatus,
 };
 use pallet_cf_trading_strategy::TradingStrategyDeregistrationCheck;
 use runtime_apis::ChainAccounts;
@@ -108,7 +121,6 @@ use crate::{chainflip::EvmLimit, runtime_apis::TransactionScreeningEvent};
 
 use pallet_cf_reputation::{ExclusionList, HeartbeatQualification, ReputationPointsQualification};
 use pallet_cf_swapping::SwapLegInfo;
-use pallet_cf_validator::SetSizeMaximisingAuctionResolver;
 use pallet_transaction_payment::{ConstFeeMultiplier, Multiplier};
 use scale_info::prelude::string::String;
 use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
@@ -130,7 +142,10 @@ pub use frame_support::{
 };
 use frame_system::{offchain::SendTransactionTypes, pallet_prelude::BlockNumberFor};
 use pallet_cf_funding::MinimumFunding;
-use pallet_cf_pools::{PoolInfo, PoolOrders};
+use pallet_cf_pools::{
+	PoolInfo, PoolOrders, PoolOrdersCursor, PoolOrdersPage, PoolOrdersValuation,
+	RangeOrderMintQuote, RangeOrderSize,
+};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use pallet_session::historical as session_historical;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -179,7 +194,7 @@ use chainflip::{
 use safe_mode::{RuntimeSafeMode, WitnesserCallPermission};
 
 use constants::common::*;
-use pallet_cf_flip::{Bonder, FlipSlasher};
+use pallet_cf_flip::Bonder;
 pub use pallet_transaction_payment::ChargeTransactionPayment;
 
 // Make the WASM binary available.
@@ -249,6 +264,7 @@ impl pallet_cf_validator::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Offence = chainflip::Offence;
 	type EpochTransitionHandler = ChainflipEpochTransitions;
+	type DelegationRewards = chainflip::DelegationRewardsPot;
 	type ValidatorWeightInfo = pallet_cf_validator::weights::PalletWeight<Runtime>;
 	type KeyRotator = cons_key_rotator!(
 		EvmThresholdSigner,
@@ -289,6 +305,8 @@ impl pallet_cf_validator::Config for Runtime {
 	type SafeMode = RuntimeSafeMode;
 	type ReputationResetter = Reputation;
 	type CfePeerRegistration = CfeInterface;
+	type RuntimeCall = RuntimeCall;
+	type OperatorCallFilter = chainflip::OperatorCallFilter;
 }
 
 parameter_types! {
@@ -340,7 +358,10 @@ impl pallet_cf_swapping::Config for Runtime {
 	type BalanceApi = AssetBalances;
 	type PoolPriceApi = LiquidityPools;
 	type ChannelIdAllocator = BitcoinIngressEgress;
+	type EthereumPrivateChannels = EthereumIngressEgress;
+	type ArbitrumPrivateChannels = ArbitrumIngressEgress;
 	type Bonder = Bonder<Runtime>;
+	type FundAccountFromSwap = chainflip::GatewayAccountFunding;
 }
 
 impl pallet_cf_vaults::Config<Instance1> for Runtime {
@@ -424,6 +445,7 @@ impl pallet_cf_ingress_egress::Config<Instance1> for Runtime {
 	type ChainApiCall = eth::api::EthereumApi<EvmEnvironment>;
 	type Broadcaster = EthereumBroadcaster;
 	type DepositHandler = chainflip::DepositHandler;
+	type EgressSuccessHandler = Funding;
 	type ChainTracking = EthereumChainTracking;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type NetworkEnvironment = Environment;
@@ -454,6 +476,7 @@ impl pallet_cf_ingress_egress::Config<Instance2> for Runtime {
 	type Broadcaster = PolkadotBroadcaster;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type DepositHandler = chainflip::DepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = PolkadotChainTracking;
 	type NetworkEnvironment = Environment;
 	type AssetConverter = Swapping;
@@ -483,6 +506,7 @@ impl pallet_cf_ingress_egress::Config<Instance3> for Runtime {
 	type Broadcaster = BitcoinBroadcaster;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type DepositHandler = chainflip::DepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = BitcoinChainTracking;
 	type NetworkEnvironment = Environment;
 	type AssetConverter = Swapping;
@@ -511,6 +535,7 @@ impl pallet_cf_ingress_egress::Config<Instance4> for Runtime {
 	type ChainApiCall = ArbitrumApi<EvmEnvironment>;
 	type Broadcaster = ArbitrumBroadcaster;
 	type DepositHandler = chainflip::DepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = ArbitrumChainTracking;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type NetworkEnvironment = Environment;
@@ -541,6 +566,7 @@ impl pallet_cf_ingress_egress::Config<Instance5> for Runtime {
 	type Broadcaster = SolanaBroadcaster;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type DepositHandler = chainflip::DepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = SolanaChainTrackingProvider;
 	type NetworkEnvironment = Environment;
 	type AssetConverter = Swapping;
@@ -570,6 +596,7 @@ impl pallet_cf_ingress_egress::Config<Instance6> for Runtime {
 	type Broadcaster = AssethubBroadcaster;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type DepositHandler = chainflip::DepositHandler;
+	type EgressSuccessHandler = ();
 	type ChainTracking = AssethubChainTracking;
 	type NetworkEnvironment = Environment;
 	type AssetConverter = Swapping;
@@ -614,6 +641,7 @@ impl pallet_cf_account_roles::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type EnsureGovernance = pallet_cf_governance::EnsureGovernance;
 	type DeregistrationCheck = (Bonder<Self>, TradingStrategyDeregistrationCheck<Self>);
+	type RoleSwitch = chainflip::BrokerLiquidityProviderRoleSwitch;
 	type WeightInfo = ();
 }
 
@@ -695,6 +723,8 @@ impl frame_system::Config for Runtime {
 
 	/// We don't use RuntimeTask.
 	type RuntimeTask = ();
+	/// Restricts the calls that can be batched.
+	type BaseCallFilter = chainflip::BaseCallFilter;
 }
 
 impl frame_system::offchain::SigningTypes for Runtime {
@@ -743,6 +773,13 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = weights::pallet_timestamp::SubstrateWeight<Runtime>;
 }
 
+impl pallet_utility::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_authorship::Config for Runtime {
 	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
 	type EventHandler = ();
@@ -755,7 +792,7 @@ impl pallet_cf_flip::Config for Runtime {
 	type OnAccountFunded = pallet_cf_validator::UpdateBackupMapping<Self>;
 	type WeightInfo = pallet_cf_flip::weights::PalletWeight<Runtime>;
 	type WaivedFees = chainflip::WaivedFees;
-	type CallIndexer = chainflip::LpOrderCallIndexer;
+	type CallIndexer = chainflip::FeeScalingCallIndexer;
 }
 
 impl pallet_cf_witnesser::Config for Runtime {
@@ -853,9 +890,10 @@ impl pallet_cf_reputation::Config for Runtime {
 	type Heartbeat = ChainflipHeartbeat;
 	type HeartbeatBlockInterval = ConstU32<HEARTBEAT_BLOCK_INTERVAL>;
 	type ReputationPointFloorAndCeiling = ReputationPointFloorAndCeiling;
-	type Slasher = FlipSlasher<Self>;
+	type Slasher = chainflip::DelegationAwareSlasher;
 	type WeightInfo = pallet_cf_reputation::weights::PalletWeight<Runtime>;
 	type MaximumAccruableReputation = MaximumAccruableReputation;
+	type MaximumSuspensionDuration = ConstU32<DAYS>;
 	type SafeMode = RuntimeSafeMode;
 }
 
@@ -870,7 +908,7 @@ impl pallet_cf_threshold_signature::Config<Instance16> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyRetryDelay = ConstU32<1>;
 	type SafeMode = RuntimeSafeMode;
-	type Slasher = FlipSlasher<Self>;
+	type Slasher = chainflip::DelegationAwareSlasher;
 	type CfeMultisigRequest = CfeInterface;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
@@ -886,7 +924,7 @@ impl pallet_cf_threshold_signature::Config<Instance15> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyRetryDelay = ConstU32<1>;
 	type SafeMode = RuntimeSafeMode;
-	type Slasher = FlipSlasher<Self>;
+	type Slasher = chainflip::DelegationAwareSlasher;
 	type CfeMultisigRequest = CfeInterface;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
@@ -902,7 +940,7 @@ impl pallet_cf_threshold_signature::Config<Instance3> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyRetryDelay = ConstU32<1>;
 	type SafeMode = RuntimeSafeMode;
-	type Slasher = FlipSlasher<Self>;
+	type Slasher = chainflip::DelegationAwareSlasher;
 	type CfeMultisigRequest = CfeInterface;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
@@ -918,7 +956,7 @@ impl pallet_cf_threshold_signature::Config<Instance5> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyRetryDelay = ConstU32<1>;
 	type SafeMode = RuntimeSafeMode;
-	type Slasher = FlipSlasher<Self>;
+	type Slasher = chainflip::DelegationAwareSlasher;
 	type CfeMultisigRequest = CfeInterface;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
@@ -1266,6 +1304,9 @@ mod runtime {
 
 	#[runtime::pallet_index(52)]
 	pub type TradingStrategy = pallet_cf_trading_strategy;
+
+	#[runtime::pallet_index(53)]
+	pub type Utility = pallet_utility;
 }
 
 /// The address format for describing accounts.
@@ -1368,6 +1409,7 @@ pub type PalletExecutionOrder = (
 	LiquidityPools,
 	// Miscellaneous
 	TradingStrategy,
+	Utility,
 );
 
 /// Contains:
@@ -1487,6 +1529,7 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_timestamp, Timestamp]
+		[pallet_utility, Utility]
 		[pallet_cf_environment, Environment]
 		[pallet_cf_flip, Flip]
 		[pallet_cf_emissions, Emissions]
@@ -1652,18 +1695,7 @@ impl_runtime_apis! {
 
 		fn cf_auction_state() -> AuctionState {
 			let auction_params = Validator::auction_parameters();
-			let min_active_bid = SetSizeMaximisingAuctionResolver::try_new(
-				<Runtime as Chainflip>::EpochInfo::current_authority_count(),
-				auction_params,
-			)
-			.and_then(|resolver| {
-				resolver.resolve_auction(
-					Validator::get_qualified_bidders::<<Runtime as pallet_cf_validator::Config>::KeygenQualification>(),
-					Validator::auction_bid_cutoff_percentage(),
-				)
-			})
-			.ok()
-			.map(|auction_outcome| auction_outcome.bond);
+			let min_active_bid = Validator::min_active_bid();
 			AuctionState {
 				epoch_duration: Validator::epoch_duration(),
 				current_epoch_started_at: Validator::current_epoch_started_at(),
@@ -1901,6 +1933,77 @@ impl_runtime_apis! {
 			})
 		}
 
+		/// Simulates a swap like `cf_pool_simulate_swap`, additionally accounting for affiliate
+		/// and boost fees, and returns the breakdown of all fees that would be paid.
+		///
+		/// Note: This function must only be called through RPC, because RPC has its own storage buffer
+		/// layer and would not affect on-chain storage.
+		fn cf_pool_simulate_swap_with_fees(
+			input_asset: Asset,
+			output_asset: Asset,
+			input_amount: AssetAmount,
+			broker_commission: BasisPoints,
+			affiliate_fees: Affiliates<AccountId>,
+			boost_fee: BasisPoints,
+			dca_parameters: Option<DcaParameters>,
+			ccm_data: Option<CcmData>,
+			exclude_fees: BTreeSet<FeeTypes>,
+			additional_orders: Option<Vec<SimulateSwapAdditionalOrder>>,
+		) -> Result<SimulatedSwapInformationWithFees, DispatchErrorWithMessage> {
+			// The boost fee is taken from the deposit before any other fees. The actual fee depends
+			// on which boost pools end up being used, so we assume the maximum fee here.
+			let boost_fee = Permill::from_parts(boost_fee as u32 * BASIS_POINTS_PER_MILLION) * input_amount;
+
+			// Affiliate fees are taken alongside the broker fee, so they can be simulated as a single
+			// broker commission that is split between the broker and its affiliates afterwards.
+			let total_commission = affiliate_fees
+				.iter()
+				.fold(broker_commission, |total, Beneficiary { bps, .. }| total.saturating_add(*bps));
+
+			let SimulatedSwapInformation {
+				intermediary,
+				output,
+				network_fee,
+				ingress_fee,
+				egress_fee,
+				broker_fee: total_commission_fee,
+			} = Self::cf_pool_simulate_swap(
+				input_asset,
+				output_asset,
+				input_amount.saturating_sub(boost_fee),
+				total_commission,
+				dca_parameters,
+				ccm_data,
+				exclude_fees,
+				additional_orders,
+			)?;
+
+			let affiliate_fees = affiliate_fees
+				.into_iter()
+				.map(|Beneficiary { account, bps }| {
+					(
+						account,
+						Permill::from_rational(bps as u32, total_commission as u32) *
+							total_commission_fee,
+					)
+				})
+				.collect::<Vec<_>>();
+			let broker_fee = affiliate_fees
+				.iter()
+				.fold(total_commission_fee, |remaining, (_, fee)| remaining.saturating_sub(*fee));
+
+			Ok(SimulatedSwapInformationWithFees {
+				intermediary,
+				output,
+				network_fee,
+				ingress_fee,
+				egress_fee,
+				broker_fee,
+				affiliate_fees,
+				boost_fee,
+			})
+		}
+
 		fn cf_pool_info(base_asset: Asset, quote_asset: Asset) -> Result<PoolInfo, DispatchErrorWithMessage> {
 			LiquidityPools::pool_info(base_asset, quote_asset).map_err(Into::into)
 		}
@@ -2106,8 +2209,11 @@ impl_runtime_apis! {
 			}
 		}
 
-		fn cf_account_role(account_id: AccountId) -> Option<AccountRole> {
-			pallet_cf_account_roles::AccountRoles::<Runtime>::get(account_id)
+		fn cf_account_role(account_id: AccountId) -> Vec<AccountRole> {
+			pallet_cf_account_roles::AccountRoles::<Runtime>::get(&account_id)
+				.into_iter()
+				.chain(pallet_cf_account_roles::SecondaryAccountRoles::<Runtime>::get(&account_id))
+				.collect()
 		}
 
 		fn cf_redemption_tax() -> AssetAmount {
@@ -2118,6 +2224,10 @@ impl_runtime_apis! {
 			pallet_cf_swapping::SwapRetryDelay::<Runtime>::get()
 		}
 
+		fn cf_swap_delay_blocks() -> u32 {
+			pallet_cf_swapping::SwapDelayBlocks::<Runtime>::get()
+		}
+
 		fn cf_swap_limits() -> SwapLimits {
 			pallet_cf_swapping::Pallet::<Runtime>::get_swap_limits()
 		}
@@ -2280,6 +2390,59 @@ impl_runtime_apis! {
 			pallet_cf_swapping::Pallet::<Runtime>::validate_refund_params(retry_duration).map_err(Into::into)
 		}
 
+		fn cf_validate_ccm_channel_metadata(
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			channel_metadata: CcmChannelMetadata,
+		) -> Result<(), CcmValidationError> {
+			if !ForeignChain::from(destination_asset).ccm_support() {
+				return Err(CcmValidationError::UnsupportedForTargetChain);
+			}
+
+			ChainAddressConverter::decode_and_validate_address_for_asset(
+				destination_address.clone(),
+				destination_asset,
+			)
+			.map_err(|_| CcmValidationError::InvalidDestinationAddress)?;
+
+			CcmValidityChecker::check_and_decode(&channel_metadata, destination_asset, destination_address)
+				.map_err(CcmValidationError::InvalidCcm)?;
+
+			// The gas limit of a CCM egress is capped, so any gas budget above what fits under the
+			// cap (after the overheads, which may depend on the chain's current fees) is lost.
+			let message_length = channel_metadata.message.len();
+			let max_gas_budget = match destination_asset.into() {
+				ForeignChainAndAsset::Ethereum(asset) => EthereumChainTracking::chain_state().map(|state| {
+					eth::fees::MAX_GAS_LIMIT.saturating_sub(state.tracked_data.calculate_ccm_gas_limit(
+						asset == cf_chains::assets::eth::Asset::Eth,
+						0,
+						message_length,
+					))
+				}),
+				// Arbitrum's overhead includes the L1 data cost, which is expressed in L2 gas units.
+				ForeignChainAndAsset::Arbitrum(asset) => ArbitrumChainTracking::chain_state().map(|state| {
+					state.tracked_data.max_ccm_gas_budget(
+						asset == cf_chains::assets::arb::Asset::ArbEth,
+						message_length,
+					)
+				}),
+				ForeignChainAndAsset::Solana(asset) => Some(
+					cf_chains::sol::compute_units_costs::MAX_COMPUTE_UNITS_PER_CCM_TRANSFER
+						.saturating_sub(cf_chains::sol::SolTrackedData::calculate_ccm_compute_limit(0, asset))
+						.into(),
+				),
+				_ => None,
+			};
+			if let Some(max_gas_budget) = max_gas_budget {
+				frame_support::ensure!(
+					channel_metadata.gas_budget <= max_gas_budget,
+					CcmValidationError::GasBudgetTooHigh { max_gas_budget }
+				);
+			}
+
+			Ok(())
+		}
+
 		fn cf_request_swap_parameter_encoding(
 			broker_id: AccountId,
 			source_asset: Asset,
@@ -2321,6 +2484,7 @@ impl_runtime_apis! {
 				broker_id.clone(),
 				broker_commission,
 				affiliate_fees.clone(),
+				source_asset,
 			)?;
 
 			// Validate refund duration.
@@ -2329,6 +2493,7 @@ impl_runtime_apis! {
 				VaultSwapExtraParametersEncoded::Ethereum(extra_params) => extra_params.refund_parameters.retry_duration,
 				VaultSwapExtraParametersEncoded::Arbitrum(extra_params) => extra_params.refund_parameters.retry_duration,
 				VaultSwapExtraParametersEncoded::Solana { refund_parameters, .. } => refund_parameters.retry_duration,
+				VaultSwapExtraParametersEncoded::Assethub { refund_parameters, .. } => refund_parameters.retry_duration,
 			})?;
 
 			// Validate CCM.
@@ -2336,6 +2501,9 @@ impl_runtime_apis! {
 				if source_chain == ForeignChain::Bitcoin {
 					return Err(DispatchErrorWithMessage::from("Vault swaps with CCM are not supported for the Bitcoin Chain"));
 				}
+				if source_chain == ForeignChain::Assethub {
+					return Err(DispatchErrorWithMessage::from("Vault swaps with CCM are not supported for the Assethub Chain"));
+				}
 				if !destination_chain.ccm_support() {
 					return Err(DispatchErrorWithMessage::from("Destination chain does not support CCM"));
 				}
@@ -2438,6 +2606,24 @@ impl_runtime_apis! {
 					event_data_account,
 					from_token_account,
 				),
+				(
+					ForeignChain::Assethub,
+					VaultSwapExtraParameters::Assethub {
+						input_amount,
+						refund_parameters,
+					}
+				) => crate::chainflip::vault_swaps::assethub_vault_swap(
+					broker_id,
+					source_asset,
+					input_amount,
+					destination_asset,
+					destination_address,
+					broker_commission,
+					refund_parameters,
+					boost_fee,
+					affiliate_fees,
+					dca_parameters,
+				),
 				_ => Err(DispatchErrorWithMessage::from(
 					"Incompatible or unsupported source_asset and extra_parameters"
 				)),
@@ -2524,7 +2710,7 @@ impl_runtime_apis! {
 						vec![TransactionScreeningEvent::TransactionRejectionRequestExpired { account_id, tx_id }],
 					pallet_cf_ingress_egress::Event::TransactionRejectionRequestReceived { account_id, tx_id, expires_at: _ } =>
 						vec![TransactionScreeningEvent::TransactionRejectionRequestReceived { account_id, tx_id }],
-					pallet_cf_ingress_egress::Event::TransactionRejectedByBroker { broadcast_id, tx_id } => tx_id
+					pallet_cf_ingress_egress::Event::TransactionRejectedByBroker { broadcast_id, tx_id, .. } => tx_id
 						.deposit_ids()
 						.into_iter()
 						.flat_map(IntoIterator::into_iter)
@@ -2626,6 +2812,915 @@ impl_runtime_apis! {
 					.map(|(asset, balance)| (asset, Some(balance)))),
 			}
 		}
+
+		fn cf_private_evm_deposit_addresses(
+			broker: AccountId,
+		) -> Vec<(ForeignChain, Vec<(Asset, EncodedAddress)>)> {
+			pallet_cf_swapping::BrokerPrivateEvmChannels::<Runtime>::iter_prefix(&broker)
+				.map(|(chain, channel_id)| {
+					(chain, derive_evm_private_deposit_addresses(chain, channel_id))
+				})
+				.collect()
+		}
+
+		fn cf_swap_status(swap_request_id: SwapRequestId) -> Option<SwapStatus> {
+			Swapping::swap_status(swap_request_id)
+		}
+
+		fn cf_pool_twap(
+			base_asset: Asset,
+			quote_asset: Asset,
+			window_blocks: u32,
+		) -> Option<PoolPriceV1> {
+			LiquidityPools::twap_price(base_asset, quote_asset, window_blocks)
+		}
+
+		fn cf_pool_statistics(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<Vec<(EpochIndex, PoolStatistics)>, DispatchErrorWithMessage> {
+			LiquidityPools::pool_statistics(base_asset, quote_asset).map_err(Into::into)
+		}
+
+		fn cf_pool_protocol_fee(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<Permill, DispatchErrorWithMessage> {
+			LiquidityPools::protocol_fee(base_asset, quote_asset).map_err(Into::into)
+		}
+
+		fn cf_fee_tier_pools(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<Vec<PoolInfo>, DispatchErrorWithMessage> {
+			LiquidityPools::fee_tier_pools(base_asset, quote_asset).map_err(Into::into)
+		}
+
+		fn cf_pool_orders_page(
+			base_asset: Asset,
+			quote_asset: Asset,
+			lp: Option<AccountId>,
+			filled_orders: bool,
+			after: Option<PoolOrdersCursor<AccountId>>,
+			limit: u32,
+		) -> Result<PoolOrdersPage<Runtime>, DispatchErrorWithMessage> {
+			LiquidityPools::pool_orders_page(base_asset, quote_asset, lp, filled_orders, after, limit)
+				.map_err(Into::into)
+		}
+
+		fn cf_lp_orders(
+			lp: AccountId,
+			filled_orders: bool,
+		) -> Result<Vec<(Asset, Asset, PoolOrders<Runtime>)>, DispatchErrorWithMessage> {
+			LiquidityPools::lp_orders(lp, filled_orders).map_err(Into::into)
+		}
+
+		fn cf_pool_range_order_mint_quote(
+			base_asset: Asset,
+			quote_asset: Asset,
+			tick_range: Range<Tick>,
+			size: RangeOrderSize,
+		) -> Result<RangeOrderMintQuote, DispatchErrorWithMessage> {
+			LiquidityPools::pool_range_order_mint_quote(base_asset, quote_asset, tick_range, size)
+				.map_err(Into::into)
+		}
+
+		fn cf_fee_scaling_multiplier(account_id: AccountId, base_asset: Asset) -> FeeScalingInfo {
+			// Limit order updates are representative of the calls that are scaled.
+			let call = RuntimeCall::LiquidityPools(pallet_cf_pools::Call::update_limit_order {
+				base_asset,
+				quote_asset: STABLE_ASSET,
+				side: cf_amm::common::Side::Buy,
+				id: 0,
+				option_tick: None,
+				amount_change: cf_traits::IncreaseOrDecrease::Increase(0),
+			});
+			let (call_count, multiplier) = Flip::fee_scaling_multiplier(
+				&account_id,
+				chainflip::FeeScalingCallIndex::PoolOrder(base_asset),
+				call.get_dispatch_info().weight,
+			);
+			let len = call.encoded_size() as u32;
+			let fee = TransactionPayment::query_call_info(call, len).partial_fee;
+			FeeScalingInfo {
+				call_count,
+				multiplier,
+				next_call_fee: fee.saturating_mul(multiplier.into()),
+			}
+		}
+
+		fn cf_call_fee_quote(
+			account_id: AccountId,
+			call: Vec<u8>,
+			len: u32,
+		) -> Result<CallFeeQuote, DispatchErrorWithMessage> {
+			let call = RuntimeCall::decode(&mut &call[..])
+				.map_err(|_| DispatchError::Other("Invalid call encoding"))?;
+			let Some(multiplier) = Flip::next_call_fee_multiplier(&account_id, &call) else {
+				return Ok(CallFeeQuote { base_fee: 0, multiplier: 0, fee: 0 })
+			};
+			let base_fee = TransactionPayment::query_call_info(call, len).partial_fee;
+			Ok(CallFeeQuote {
+				base_fee,
+				multiplier,
+				fee: base_fee.saturating_mul(multiplier.into()),
+			})
+		}
+
+		fn cf_validator_performance() -> Vec<ValidatorPerformance> {
+			let epoch = Validator::current_epoch();
+			pallet_cf_validator::CurrentAuthorities::<Runtime>::get()
+				.into_iter()
+				.map(|account_id| {
+					let authorship =
+						pallet_cf_validator::CurrentEpochAuthorshipStats::<Runtime>::get(&account_id);
+					let (witnessed_in_time, witness_deadlines_elapsed) =
+						Witnesser::witness_participation(epoch, &account_id);
+					ValidatorPerformance {
+						blocks_authored: authorship.authored,
+						blocks_expected: authorship.expected(),
+						witnessed_in_time,
+						witness_deadlines_elapsed,
+						witness_participation: if witness_deadlines_elapsed == 0 {
+							Permill::from_percent(100)
+						} else {
+							Permill::from_rational(witnessed_in_time, witness_deadlines_elapsed)
+						},
+						reputation_points: pallet_cf_reputation::Reputations::<Runtime>::get(&account_id).reputation_points,
+						last_heartbeat: pallet_cf_reputation::LastHeartbeat::<Runtime>::get(&account_id),
+						account_id,
+					}
+				})
+				.collect()
+		}
+
+		fn cf_operator_validators(operator: AccountId) -> Vec<AccountId> {
+			Validator::operator_validators(&operator)
+		}
+
+		fn cf_projected_auction_outcome() -> Option<ProjectedAuctionOutcome> {
+			let outcome = Validator::projected_auction_outcome()?;
+			let qualified_bidders = Validator::get_qualified_bidders::<<Runtime as pallet_cf_validator::Config>::KeygenQualification>()
+				.into_iter()
+				.map(|bid| bid.bidder_id)
+				.collect::<BTreeSet<_>>();
+			let mut bids = Validator::get_active_bids()
+				.into_iter()
+				.map(|bid| ProjectedBid {
+					qualified: qualified_bidders.contains(&bid.bidder_id),
+					winning: outcome.winners.contains(&bid.bidder_id),
+					account_id: bid.bidder_id,
+					amount: bid.amount,
+				})
+				.collect::<Vec<_>>();
+			bids.sort_by(|a, b| b.amount.cmp(&a.amount));
+			Some(ProjectedAuctionOutcome { authorities: outcome.winners, bond: outcome.bond, bids })
+		}
+
+		fn cf_accounts_by_vanity_name(name: Vec<u8>) -> Vec<AccountId> {
+			AccountRoles::accounts_by_vanity_name(&name)
+		}
+
+		fn cf_rotation_blockers(account_id: AccountId) -> Vec<RotationBlocker> {
+			if !chainflip::ValidatorRoleQualification::is_qualified(&account_id) {
+				return vec![RotationBlocker::NotRegisteredAsValidator]
+			}
+			let mut blockers = Vec::new();
+			if !Validator::is_bidding(&account_id) {
+				blockers.push(RotationBlocker::NotBidding);
+			}
+			if !SessionKeysRegistered::<Runtime, pallet_session::Pallet<Runtime>>::is_qualified(&account_id) {
+				blockers.push(RotationBlocker::SessionKeysNotRegistered);
+			}
+			if !pallet_cf_validator::PeerMapping::<Runtime>::is_qualified(&account_id) {
+				blockers.push(RotationBlocker::PeerIdNotRegistered);
+			}
+			if !HeartbeatQualification::<Runtime>::is_qualified(&account_id) {
+				blockers.push(RotationBlocker::Offline);
+			}
+			if !ExclusionList::<Runtime, chainflip::KeygenExclusionOffences>::is_qualified(&account_id) {
+				blockers.push(RotationBlocker::Suspended);
+			}
+			if !pallet_cf_validator::QualifyByCfeVersion::<Runtime>::is_qualified(&account_id) {
+				blockers.push(RotationBlocker::CfeVersionTooOld {
+					reported: Validator::node_cfe_version(&account_id),
+					minimum: Validator::minimum_reported_cfe_version(),
+				});
+			}
+			if !ReputationPointsQualification::<Runtime>::is_qualified(&account_id) {
+				blockers.push(RotationBlocker::InsufficientReputation);
+			}
+			// The bid only matters once the node is otherwise qualified to take part in the auction.
+			if blockers.is_empty() {
+				if let Some(outcome) = Validator::projected_auction_outcome() {
+					if !outcome.winners.contains(&account_id) {
+						let bid = Validator::get_active_bids()
+							.into_iter()
+							.find(|bid| bid.bidder_id == account_id)
+							.map(|bid| bid.amount)
+							.unwrap_or_default();
+						blockers.push(RotationBlocker::BidTooLow { bid, projected_bond: outcome.bond });
+					}
+				}
+			}
+			blockers
+		}
+
+		fn cf_epoch_reward_statements(epoch_index: EpochIndex) -> Vec<EpochRewardStatement> {
+			pallet_cf_validator::EpochRewardStatements::<Runtime>::iter_prefix(epoch_index)
+				.map(|(account_id, statement)| {
+					let (witnessed_in_time, witness_deadlines_elapsed) =
+						Witnesser::witness_participation(epoch_index, &account_id);
+					EpochRewardStatement {
+						authorship_rewards: statement.authorship,
+						backup_rewards: statement.backup,
+						delegators_share: statement.delegators_share,
+						witnessed_in_time,
+						witness_deadlines_elapsed,
+						account_id,
+					}
+				})
+				.collect()
+		}
+
+		fn cf_egress_broadcast_events() -> Vec<EgressBroadcastEvent> {
+			use pallet_cf_broadcast::Event as BroadcastEvent;
+			use pallet_cf_ingress_egress::Event as IngressEgressEvent;
+
+			macro_rules! broadcast_outcome {
+				($chain:ident, $event:expr) => {
+					match $event {
+						BroadcastEvent::BroadcastSuccess { broadcast_id, .. } =>
+							Some(EgressBroadcastEvent::BroadcastSucceeded {
+								chain: ForeignChain::$chain,
+								broadcast_id,
+							}),
+						BroadcastEvent::BroadcastAborted { broadcast_id } =>
+							Some(EgressBroadcastEvent::BroadcastAborted {
+								chain: ForeignChain::$chain,
+								broadcast_id,
+							}),
+						_ => None,
+					}
+				};
+			}
+
+			System::read_events_no_consensus()
+				.filter_map(|event_record| match event_record.event {
+					RuntimeEvent::EthereumIngressEgress(IngressEgressEvent::BatchBroadcastRequested { broadcast_id, egress_ids }) |
+					RuntimeEvent::PolkadotIngressEgress(IngressEgressEvent::BatchBroadcastRequested { broadcast_id, egress_ids }) |
+					RuntimeEvent::BitcoinIngressEgress(IngressEgressEvent::BatchBroadcastRequested { broadcast_id, egress_ids }) |
+					RuntimeEvent::ArbitrumIngressEgress(IngressEgressEvent::BatchBroadcastRequested { broadcast_id, egress_ids }) |
+					RuntimeEvent::SolanaIngressEgress(IngressEgressEvent::BatchBroadcastRequested { broadcast_id, egress_ids }) |
+					RuntimeEvent::AssethubIngressEgress(IngressEgressEvent::BatchBroadcastRequested { broadcast_id, egress_ids }) =>
+						Some(EgressBroadcastEvent::BroadcastRequested { broadcast_id, egress_ids }),
+					RuntimeEvent::EthereumIngressEgress(IngressEgressEvent::CcmBroadcastRequested { broadcast_id, egress_id }) |
+					RuntimeEvent::PolkadotIngressEgress(IngressEgressEvent::CcmBroadcastRequested { broadcast_id, egress_id }) |
+					RuntimeEvent::BitcoinIngressEgress(IngressEgressEvent::CcmBroadcastRequested { broadcast_id, egress_id }) |
+					RuntimeEvent::ArbitrumIngressEgress(IngressEgressEvent::CcmBroadcastRequested { broadcast_id, egress_id }) |
+					RuntimeEvent::SolanaIngressEgress(IngressEgressEvent::CcmBroadcastRequested { broadcast_id, egress_id }) |
+					RuntimeEvent::AssethubIngressEgress(IngressEgressEvent::CcmBroadcastRequested { broadcast_id, egress_id }) =>
+						Some(EgressBroadcastEvent::BroadcastRequested { broadcast_id, egress_ids: vec![egress_id] }),
+					RuntimeEvent::EthereumBroadcaster(event) => broadcast_outcome!(Ethereum, event),
+					RuntimeEvent::PolkadotBroadcaster(event) => broadcast_outcome!(Polkadot, event),
+					RuntimeEvent::BitcoinBroadcaster(event) => broadcast_outcome!(Bitcoin, event),
+					RuntimeEvent::ArbitrumBroadcaster(event) => broadcast_outcome!(Arbitrum, event),
+					RuntimeEvent::SolanaBroadcaster(event) => broadcast_outcome!(Solana, event),
+					RuntimeEvent::AssethubBroadcaster(event) => broadcast_outcome!(Assethub, event),
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn cf_deposit_events() -> Vec<DepositEvent> {
+			use pallet_cf_ingress_egress::Event as IngressEgressEvent;
+
+			fn deposit_event<I: 'static>(event: IngressEgressEvent<Runtime, I>) -> Option<DepositEvent>
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let (stage, deposit_address, asset, amount, block_height, channel_id) = match event {
+					IngressEgressEvent::DepositPrewitnessed { deposit_address, asset, amount, block_height, channel_id, .. } =>
+						(DepositStage::Prewitnessed, deposit_address, asset, amount, block_height, channel_id),
+					IngressEgressEvent::DepositBoosted { deposit_address, asset, amounts, block_height, channel_id, .. } =>
+						(
+							DepositStage::Boosted,
+							deposit_address,
+							asset,
+							amounts.into_values().fold(Default::default(), |total, amount| total.saturating_add(amount)),
+							block_height,
+							channel_id,
+						),
+					IngressEgressEvent::DepositFinalised { deposit_address, asset, amount, block_height, channel_id, .. } =>
+						(DepositStage::Finalised, deposit_address, asset, amount, block_height, channel_id),
+					_ => return None,
+				};
+				let deposit_address = deposit_address?;
+
+				Some(DepositEvent {
+					stage,
+					channel_owner: pallet_cf_ingress_egress::DepositChannelLookup::<Runtime, I>::get(&deposit_address)
+						.map(|channel_details| channel_details.owner),
+					deposit_address: deposit_address
+						.into_foreign_chain_address()
+						.to_encoded_address(Environment::network_environment()),
+					channel_id,
+					asset: asset.into(),
+					amount: amount.into(),
+					deposit_block_height: block_height.into(),
+				})
+			}
+
+			System::read_events_no_consensus()
+				.filter_map(|event_record| match event_record.event {
+					RuntimeEvent::EthereumIngressEgress(event) => deposit_event(event),
+					RuntimeEvent::PolkadotIngressEgress(event) => deposit_event(event),
+					RuntimeEvent::BitcoinIngressEgress(event) => deposit_event(event),
+					RuntimeEvent::ArbitrumIngressEgress(event) => deposit_event(event),
+					RuntimeEvent::SolanaIngressEgress(event) => deposit_event(event),
+					RuntimeEvent::AssethubIngressEgress(event) => deposit_event(event),
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn cf_boost_pool_details_page(
+			asset: Asset,
+			tier: BoostPoolTier,
+			booster: Option<AccountId>,
+			offset: u32,
+			limit: u32,
+		) -> Option<BoostPoolDetailsPage> {
+			Self::cf_boost_pool_details(asset).remove(&tier).map(|details| {
+				details.into_page(booster, offset, limit.min(MAX_BOOST_POOL_DETAILS_PAGE_SIZE))
+			})
+		}
+
+		fn cf_scheduled_egresses(chain: ForeignChain) -> Vec<ScheduledEgress> {
+			use pallet_cf_ingress_egress::FetchOrTransfer;
+
+			fn scheduled_egresses<I: 'static>() -> Vec<ScheduledEgress>
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let network_environment = Environment::network_environment();
+				pallet_cf_ingress_egress::ScheduledEgressFetchOrTransfer::<Runtime, I>::get()
+					.into_iter()
+					.map(|request| match request {
+						FetchOrTransfer::Fetch { asset, deposit_address, amount, .. } => ScheduledEgress::Fetch {
+							asset: asset.into(),
+							amount: amount.into(),
+							scheduled_at: pallet_cf_ingress_egress::ScheduledFetchBlocks::<Runtime, I>::get(&deposit_address),
+							deposit_address: deposit_address
+								.into_foreign_chain_address()
+								.to_encoded_address(network_environment),
+						},
+						FetchOrTransfer::Transfer { egress_id, asset, destination_address, amount } => ScheduledEgress::Transfer {
+							egress_id,
+							asset: asset.into(),
+							amount: amount.into(),
+							destination_address: destination_address
+								.into_foreign_chain_address()
+								.to_encoded_address(network_environment),
+							scheduled_at: pallet_cf_ingress_egress::ScheduledTransferBlocks::<Runtime, I>::get(egress_id),
+						},
+					})
+					.collect()
+			}
+
+			match chain {
+				ForeignChain::Ethereum => scheduled_egresses::<EthereumInstance>(),
+				ForeignChain::Polkadot => scheduled_egresses::<PolkadotInstance>(),
+				ForeignChain::Bitcoin => scheduled_egresses::<BitcoinInstance>(),
+				ForeignChain::Arbitrum => scheduled_egresses::<ArbitrumInstance>(),
+				ForeignChain::Solana => scheduled_egresses::<SolanaInstance>(),
+				ForeignChain::Assethub => scheduled_egresses::<AssethubInstance>(),
+			}
+		}
+
+		fn cf_lp_earned_fees_history(lp: AccountId) -> Vec<LpEpochEarnedFees> {
+			LiquidityPools::lp_earned_fees_history(&lp)
+				.into_iter()
+				.map(|(assets, epoch_index, fees)| LpEpochEarnedFees {
+					base_asset: assets.base,
+					quote_asset: assets.quote,
+					epoch_index,
+					fees,
+				})
+				.collect()
+		}
+
+		fn cf_failed_call_details(
+			chain: ForeignChain,
+			broadcast_id: BroadcastId,
+		) -> Option<FailedCallDetails> {
+			fn failed_call_status<I: 'static>(
+				broadcast_id: BroadcastId,
+			) -> Option<(EpochIndex, FailedCallStatus)>
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let current_epoch = Validator::current_epoch();
+				let find_call = |epoch| {
+					pallet_cf_ingress_egress::FailedForeignChainCalls::<Runtime, I>::get(epoch)
+						.into_iter()
+						.find(|call| call.broadcast_id == broadcast_id)
+				};
+				// Calls are re-signed once in the epoch after they failed, and moved to the list of
+				// the current epoch when they are.
+				find_call(current_epoch)
+					.map(|call| {
+						(
+							call.original_epoch,
+							FailedCallStatus::ReadyToBroadcast {
+								expires_after_epoch: call.original_epoch.saturating_add(1),
+							},
+						)
+					})
+					.or_else(|| {
+						find_call(current_epoch.saturating_sub(1))
+							.map(|call| (call.original_epoch, FailedCallStatus::AwaitingResigning))
+					})
+			}
+
+			let (original_epoch, status, call, transaction) = match chain {
+				ForeignChain::Ethereum => {
+					let (original_epoch, status) = failed_call_status::<EthereumInstance>(broadcast_id)?;
+					let api_call = EthereumBroadcaster::threshold_signature_data(broadcast_id)?;
+					let call = match &api_call {
+						EthereumApi::ExecutexSwapAndCall(tx) => DecodedEvmCall::from(&tx.call),
+						EthereumApi::TransferFallback(tx) => DecodedEvmCall::from(&tx.call),
+						_ => return None,
+					};
+					(original_epoch, status, call, chainflip::EthTransactionBuilder::build_transaction(&api_call))
+				},
+				ForeignChain::Arbitrum => {
+					let (original_epoch, status) = failed_call_status::<ArbitrumInstance>(broadcast_id)?;
+					let api_call = ArbitrumBroadcaster::threshold_signature_data(broadcast_id)?;
+					let call = match &api_call {
+						ArbitrumApi::ExecutexSwapAndCall(tx) => DecodedEvmCall::from(&tx.call),
+						ArbitrumApi::TransferFallback(tx) => DecodedEvmCall::from(&tx.call),
+						_ => return None,
+					};
+					(original_epoch, status, call, chainflip::ArbTransactionBuilder::build_transaction(&api_call))
+				},
+				_ => return None,
+			};
+
+			Some(FailedCallDetails {
+				broadcast_id,
+				original_epoch,
+				reason: call.reason(),
+				status,
+				call,
+				transaction,
+			})
+		}
+
+		fn cf_witness_report(from_block: BlockNumber, to_block: BlockNumber) -> Vec<ValidatorWitnessReport> {
+			let vanity_names = pallet_cf_account_roles::VanityNames::<Runtime>::get();
+			let mut report = Witnesser::witnessing_failures(from_block, to_block)
+				.into_iter()
+				.map(|((epoch_index, account_id), failures)| ValidatorWitnessReport {
+					epoch_index,
+					vanity_name: vanity_names
+						.get(&account_id)
+						.map(|name| String::from_utf8_lossy(name).into())
+						.unwrap_or_default(),
+					account_id,
+					missed: failures.missed,
+					late: failures.late,
+				})
+				.collect::<Vec<_>>();
+			// Within each epoch, the validators with the most failures come first.
+			report.sort_by(|a, b| {
+				(a.epoch_index, b.missed, b.late).cmp(&(b.epoch_index, a.missed, a.late))
+			});
+			report
+		}
+
+		fn cf_decode_vault_swap(
+			chain: ForeignChain,
+			data: Vec<u8>,
+		) -> Result<DecodedVaultSwap, DispatchErrorWithMessage> {
+			crate::chainflip::vault_swaps::decode_vault_swap(chain, data)
+		}
+
+		fn cf_accounts_page(
+			roles: Vec<AccountRole>,
+			after: Option<AccountId>,
+			limit: u32,
+			include_balances: bool,
+		) -> AccountsPage {
+			let limit = limit.clamp(1, MAX_ACCOUNTS_PAGE_SIZE) as usize;
+			// Account roles are keyed by the account id itself, so they are iterated in order.
+			let mut accounts = match after {
+				Some(after) => pallet_cf_account_roles::AccountRoles::<Runtime>::iter_from(
+					pallet_cf_account_roles::AccountRoles::<Runtime>::hashed_key_for(after),
+				),
+				None => pallet_cf_account_roles::AccountRoles::<Runtime>::iter(),
+			}
+			.filter(|(account_id, role)| {
+				roles.is_empty() ||
+					roles.contains(role) ||
+					pallet_cf_account_roles::SecondaryAccountRoles::<Runtime>::get(account_id)
+						.is_some_and(|role| roles.contains(&role))
+			})
+			.take(limit + 1)
+			.collect::<Vec<_>>();
+			let next = if accounts.len() > limit {
+				accounts.truncate(limit);
+				accounts.last().map(|(account_id, _)| account_id.clone())
+			} else {
+				None
+			};
+
+			let vanity_names = AccountRoles::vanity_names();
+			AccountsPage {
+				accounts: accounts
+					.into_iter()
+					.map(|(account_id, role)| AccountSummary {
+						vanity_name: vanity_names
+							.get(&account_id)
+							.cloned()
+							.unwrap_or_default()
+							.into(),
+						flip_balance: include_balances
+							.then(|| pallet_cf_flip::Account::<Runtime>::get(&account_id).total()),
+						asset_balances: (include_balances &&
+							matches!(role, AccountRole::LiquidityProvider | AccountRole::Broker))
+							.then(|| AssetBalances::free_balances(&account_id)),
+						account_id,
+						role,
+					})
+					.collect(),
+				next,
+			}
+		}
+
+		fn cf_estimate_egress_fee(
+			asset: Asset,
+			destination_chain: ForeignChain,
+			with_ccm_gas: Option<CcmData>,
+		) -> Result<AssetAmount, DispatchErrorWithMessage> {
+			frame_support::ensure!(
+				ForeignChain::from(asset) == destination_chain,
+				"Asset is not native to the destination chain."
+			);
+			let egress = match with_ccm_gas {
+				Some(CcmData { gas_budget, message_length }) => IngressOrEgress::EgressCcm {
+					gas_budget,
+					message_length: message_length as usize,
+				},
+				None => IngressOrEgress::Egress,
+			};
+			// Uses the same estimate as the fee that is withheld when the egress is scheduled.
+			match asset.into() {
+				ForeignChainAndAsset::Ethereum(asset) => pallet_cf_ingress_egress::Pallet::<Runtime, EthereumInstance>::estimate_ingress_or_egress_fee(egress, asset),
+				ForeignChainAndAsset::Polkadot(asset) => pallet_cf_ingress_egress::Pallet::<Runtime, PolkadotInstance>::estimate_ingress_or_egress_fee(egress, asset),
+				ForeignChainAndAsset::Bitcoin(asset) => pallet_cf_ingress_egress::Pallet::<Runtime, BitcoinInstance>::estimate_ingress_or_egress_fee(egress, asset).map(Into::into),
+				ForeignChainAndAsset::Arbitrum(asset) => pallet_cf_ingress_egress::Pallet::<Runtime, ArbitrumInstance>::estimate_ingress_or_egress_fee(egress, asset),
+				ForeignChainAndAsset::Solana(asset) => pallet_cf_ingress_egress::Pallet::<Runtime, SolanaInstance>::estimate_ingress_or_egress_fee(egress, asset).map(Into::into),
+				ForeignChainAndAsset::Assethub(asset) => pallet_cf_ingress_egress::Pallet::<Runtime, AssethubInstance>::estimate_ingress_or_egress_fee(egress, asset),
+			}
+			.ok_or_else(|| "Unable to convert the egress fee into the egress asset.".into())
+		}
+
+		fn cf_rotation_status() -> RotationStatus {
+			use pallet_cf_threshold_signature::KeyRotationStatus;
+			use pallet_cf_validator::RotationPhase;
+			use pallet_cf_vaults::VaultActivationStatus;
+
+			fn key_rotation_progress<I: 'static>() -> Option<VaultRotationProgress>
+			where
+				Runtime: pallet_cf_threshold_signature::Config<I>,
+			{
+				pallet_cf_threshold_signature::PendingKeyRotation::<Runtime, I>::get().map(
+					|status| match status {
+						KeyRotationStatus::AwaitingKeygen { .. } |
+						KeyRotationStatus::AwaitingKeygenVerification { .. } |
+						KeyRotationStatus::KeygenVerificationComplete { .. } =>
+							VaultRotationProgress::Keygen,
+						KeyRotationStatus::AwaitingKeyHandover { .. } |
+						KeyRotationStatus::AwaitingKeyHandoverVerification { .. } |
+						KeyRotationStatus::KeyHandoverComplete { .. } =>
+							VaultRotationProgress::KeyHandover,
+						KeyRotationStatus::AwaitingActivationSignatures { .. } =>
+							VaultRotationProgress::AwaitingActivationSignatures,
+						KeyRotationStatus::Complete => VaultRotationProgress::Complete,
+						KeyRotationStatus::Failed { .. } |
+						KeyRotationStatus::KeyHandoverFailed { .. } =>
+							VaultRotationProgress::Failed,
+					},
+				)
+			}
+
+			// The vault activation status is only updated once the key is being activated, so
+			// until then the progress is given by the rotation of the key that the vault uses.
+			fn vault_rotation_progress<K: 'static, V: 'static>() -> Option<VaultRotationProgress>
+			where
+				Runtime: pallet_cf_threshold_signature::Config<K> + pallet_cf_vaults::Config<V>,
+			{
+				match key_rotation_progress::<K>() {
+					Some(
+						VaultRotationProgress::AwaitingActivationSignatures |
+						VaultRotationProgress::Complete,
+					) => pallet_cf_vaults::PendingVaultActivation::<Runtime, V>::get().map(
+						|status| match status {
+							VaultActivationStatus::AwaitingActivation { .. } =>
+								VaultRotationProgress::AwaitingActivation,
+							VaultActivationStatus::Complete => VaultRotationProgress::Complete,
+							VaultActivationStatus::ActivationFailedAwaitingGovernance { .. } =>
+								VaultRotationProgress::ActivationFailedAwaitingGovernance,
+						},
+					),
+					progress => progress,
+				}
+			}
+
+			let (phase, rotation_state, candidates) =
+				match pallet_cf_validator::Pallet::<Runtime>::current_rotation_phase() {
+					RotationPhase::Idle => (RuntimeApiRotationPhase::Idle, None, Vec::new()),
+					RotationPhase::KeygensInProgress(rotation_state) =>
+						(RuntimeApiRotationPhase::KeygensInProgress, Some(rotation_state), Vec::new()),
+					RotationPhase::KeyHandoversInProgress(rotation_state) =>
+						(RuntimeApiRotationPhase::KeyHandoversInProgress, Some(rotation_state), Vec::new()),
+					RotationPhase::ActivatingKeys(rotation_state) =>
+						(RuntimeApiRotationPhase::ActivatingKeys, Some(rotation_state), Vec::new()),
+					RotationPhase::NewKeysActivated(rotation_state) =>
+						(RuntimeApiRotationPhase::NewKeysActivated, Some(rotation_state), Vec::new()),
+					RotationPhase::SessionRotating(authorities, _bond) =>
+						(RuntimeApiRotationPhase::SessionRotating, None, authorities),
+				};
+
+			match rotation_state {
+				Some(rotation_state) => RotationStatus {
+					phase,
+					new_epoch_index: Some(rotation_state.new_epoch_index),
+					candidates: rotation_state.authority_candidates().into_iter().collect(),
+					banned: rotation_state.banned.into_iter().collect(),
+					vaults: [
+						(ForeignChain::Ethereum, vault_rotation_progress::<EvmInstance, EthereumInstance>()),
+						(ForeignChain::Polkadot, vault_rotation_progress::<PolkadotCryptoInstance, PolkadotInstance>()),
+						(ForeignChain::Bitcoin, vault_rotation_progress::<BitcoinInstance, BitcoinInstance>()),
+						(ForeignChain::Arbitrum, vault_rotation_progress::<EvmInstance, ArbitrumInstance>()),
+						(ForeignChain::Solana, vault_rotation_progress::<SolanaInstance, SolanaInstance>()),
+						(ForeignChain::Assethub, vault_rotation_progress::<PolkadotCryptoInstance, AssethubInstance>()),
+					]
+					.into_iter()
+					.filter_map(|(chain, progress)| Some((chain, progress?)))
+					.collect(),
+				},
+				None => RotationStatus {
+					phase,
+					new_epoch_index: None,
+					candidates,
+					banned: Vec::new(),
+					vaults: Default::default(),
+				},
+			}
+		}
+
+		fn cf_lp_orders_valuation(
+			lp: AccountId,
+		) -> Result<Vec<PoolOrdersValuation<Runtime>>, DispatchErrorWithMessage> {
+			LiquidityPools::lp_orders_valuation(lp).map_err(Into::into)
+		}
+
+		fn cf_account_events() -> Vec<(AccountId, AccountEvent)> {
+			use pallet_cf_ingress_egress::{DepositAction, Event as IngressEgressEvent};
+
+			fn deposit_event<I: 'static>(event: IngressEgressEvent<Runtime, I>) -> Option<(AccountId, AccountEvent)>
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let IngressEgressEvent::DepositFinalised { deposit_address: Some(deposit_address), asset, amount, action, channel_id, .. } = event else {
+					return None;
+				};
+				let account_id = match action {
+					DepositAction::LiquidityProvision { lp_account } => lp_account,
+					DepositAction::PrivateBrokerDeposit { broker_id } => broker_id,
+					_ => pallet_cf_ingress_egress::DepositChannelLookup::<Runtime, I>::get(&deposit_address)?.owner,
+				};
+
+				Some((
+					account_id,
+					AccountEvent::DepositFinalised {
+						deposit_address: deposit_address
+							.into_foreign_chain_address()
+							.to_encoded_address(Environment::network_environment()),
+						channel_id,
+						asset: asset.into(),
+						amount: amount.into(),
+					},
+				))
+			}
+
+			System::read_events_no_consensus()
+				.flat_map(|event_record| -> Vec<(AccountId, AccountEvent)> {
+					match event_record.event {
+						RuntimeEvent::EthereumIngressEgress(event) => deposit_event(event).into_iter().collect(),
+						RuntimeEvent::PolkadotIngressEgress(event) => deposit_event(event).into_iter().collect(),
+						RuntimeEvent::BitcoinIngressEgress(event) => deposit_event(event).into_iter().collect(),
+						RuntimeEvent::ArbitrumIngressEgress(event) => deposit_event(event).into_iter().collect(),
+						RuntimeEvent::SolanaIngressEgress(event) => deposit_event(event).into_iter().collect(),
+						RuntimeEvent::AssethubIngressEgress(event) => deposit_event(event).into_iter().collect(),
+						RuntimeEvent::Swapping(pallet_cf_swapping::Event::SwapRequested {
+							swap_request_id, input_asset, input_amount, output_asset, origin, broker_fees, ..
+						}) => {
+							let accounts = match &origin {
+								cf_chains::SwapOrigin::OnChainAccount(account_id) => Some(account_id.clone()),
+								_ => origin.broker_id().cloned(),
+							}
+							.into_iter()
+							.chain(broker_fees.iter().map(|beneficiary| beneficiary.account.clone()))
+							.collect::<BTreeSet<_>>();
+							accounts
+								.into_iter()
+								.map(|account_id| {
+									(account_id, AccountEvent::SwapRequested { swap_request_id, input_asset, input_amount, output_asset })
+								})
+								.collect()
+						},
+						RuntimeEvent::Funding(pallet_cf_funding::Event::Funded { account_id, funds_added, .. }) |
+						RuntimeEvent::Funding(pallet_cf_funding::Event::FundedFromSwap { account_id, funds_added, .. }) =>
+							vec![(account_id, AccountEvent::Funded { amount: funds_added })],
+						RuntimeEvent::Funding(pallet_cf_funding::Event::RedemptionRequested { account_id, amount, broadcast_id, expiry_time }) =>
+							vec![(account_id, AccountEvent::RedemptionRequested { amount, broadcast_id, expiry_time })],
+						RuntimeEvent::Funding(pallet_cf_funding::Event::RedemptionSettled(account_id, amount)) =>
+							vec![(account_id, AccountEvent::RedemptionSettled { amount })],
+						RuntimeEvent::Funding(pallet_cf_funding::Event::RedemptionExpired { account_id }) =>
+							vec![(account_id, AccountEvent::RedemptionExpired)],
+						_ => Vec::new(),
+					}
+				})
+				.collect()
+		}
+
+		fn cf_asset_ingress_egress_status(generic_asset: Asset) -> AssetIngressEgressStatus {
+			fn status<I: 'static>(asset: pallet_cf_ingress_egress::TargetChainAsset<Runtime, I>) -> AssetIngressEgressStatus
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let safe_mode = <<Runtime as pallet_cf_ingress_egress::Config<I>>::SafeMode as Get<_>>::get();
+				AssetIngressEgressStatus {
+					deposits_enabled: safe_mode.deposits_enabled,
+					boost_deposits_enabled: safe_mode.boost_deposits_enabled,
+					egress_enabled: !pallet_cf_ingress_egress::DisabledEgressAssets::<Runtime, I>::contains_key(asset),
+				}
+			}
+
+			match generic_asset.into() {
+				ForeignChainAndAsset::Ethereum(asset) => status::<EthereumInstance>(asset),
+				ForeignChainAndAsset::Polkadot(asset) => status::<PolkadotInstance>(asset),
+				ForeignChainAndAsset::Bitcoin(asset) => status::<BitcoinInstance>(asset),
+				ForeignChainAndAsset::Arbitrum(asset) => status::<ArbitrumInstance>(asset),
+				ForeignChainAndAsset::Solana(asset) => status::<SolanaInstance>(asset),
+				ForeignChainAndAsset::Assethub(asset) => status::<AssethubInstance>(asset),
+			}
+		}
+
+		fn cf_boost_quote(generic_asset: Asset, amount: AssetAmount, max_boost_fee: BasisPoints) -> BoostQuote {
+			fn quote<I: 'static>(asset: pallet_cf_ingress_egress::TargetChainAsset<Runtime, I>, amount: AssetAmount, max_boost_fee: BasisPoints) -> BoostQuote
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let safe_mode = <<Runtime as pallet_cf_ingress_egress::Config<I>>::SafeMode as Get<_>>::get();
+				match safe_mode
+					.boost_deposits_enabled
+					.then(|| pallet_cf_ingress_egress::Pallet::<Runtime, I>::boost_quote(asset, amount.unique_saturated_into(), max_boost_fee))
+					.flatten()
+				{
+					Some(pallet_cf_ingress_egress::BoostOutput { used_pools, total_fee }) => {
+						let boost_fee: AssetAmount = total_fee.into();
+						BoostQuote {
+							fully_boostable: true,
+							boosted_amounts: used_pools.into_iter().map(|(tier, amount)| (tier, amount.into())).collect(),
+							boost_fee,
+							boost_fee_bps: (Permill::from_rational(boost_fee, amount).deconstruct() / BASIS_POINTS_PER_MILLION) as BasisPoints,
+							net_amount: amount.saturating_sub(boost_fee),
+						}
+					},
+					None => BoostQuote {
+						fully_boostable: false,
+						boosted_amounts: Default::default(),
+						boost_fee: 0,
+						boost_fee_bps: 0,
+						net_amount: amount,
+					},
+				}
+			}
+
+			match generic_asset.into() {
+				ForeignChainAndAsset::Ethereum(asset) => quote::<EthereumInstance>(asset, amount, max_boost_fee),
+				ForeignChainAndAsset::Polkadot(asset) => quote::<PolkadotInstance>(asset, amount, max_boost_fee),
+				ForeignChainAndAsset::Bitcoin(asset) => quote::<BitcoinInstance>(asset, amount, max_boost_fee),
+				ForeignChainAndAsset::Arbitrum(asset) => quote::<ArbitrumInstance>(asset, amount, max_boost_fee),
+				ForeignChainAndAsset::Solana(asset) => quote::<SolanaInstance>(asset, amount, max_boost_fee),
+				ForeignChainAndAsset::Assethub(asset) => quote::<AssethubInstance>(asset, amount, max_boost_fee),
+			}
+		}
+
+		fn cf_broadcast_events() -> Vec<(ForeignChain, BroadcastId, BroadcastLifecycleEvent)> {
+			fn broadcast_event<I: 'static>(
+				chain: ForeignChain,
+				event: pallet_cf_broadcast::Event<Runtime, I>,
+				transaction_ref: fn(pallet_cf_broadcast::TransactionRefFor<Runtime, I>) -> ExternalTransactionRef,
+			) -> Option<(ForeignChain, BroadcastId, BroadcastLifecycleEvent)>
+			where
+				Runtime: pallet_cf_broadcast::Config<I>,
+			{
+				use pallet_cf_broadcast::Event as BroadcastEvent;
+
+				let (broadcast_id, event) = match event {
+					BroadcastEvent::TransactionBroadcastRequest { broadcast_id, nominee, .. } =>
+						(broadcast_id, BroadcastLifecycleEvent::TransactionBroadcastRequested { nominee }),
+					BroadcastEvent::BroadcastRetryScheduled { broadcast_id, retry_block } =>
+						(broadcast_id, BroadcastLifecycleEvent::RetryScheduled { retry_block }),
+					BroadcastEvent::BroadcastTimeout { broadcast_id } =>
+						(broadcast_id, BroadcastLifecycleEvent::TimedOut),
+					BroadcastEvent::ThresholdSignatureInvalid { broadcast_id } =>
+						(broadcast_id, BroadcastLifecycleEvent::ThresholdSignatureInvalid),
+					BroadcastEvent::CallResigned { broadcast_id } =>
+						(broadcast_id, BroadcastLifecycleEvent::Resigned),
+					BroadcastEvent::BroadcastSuccess { broadcast_id, transaction_ref: tx_ref, .. } =>
+						(broadcast_id, BroadcastLifecycleEvent::Succeeded { transaction_ref: transaction_ref(tx_ref) }),
+					BroadcastEvent::BroadcastAborted { broadcast_id } =>
+						(broadcast_id, BroadcastLifecycleEvent::Aborted),
+					_ => return None,
+				};
+				Some((chain, broadcast_id, event))
+			}
+
+			System::read_events_no_consensus()
+				.filter_map(|event_record| match event_record.event {
+					RuntimeEvent::EthereumBroadcaster(event) => broadcast_event(ForeignChain::Ethereum, event, ExternalTransactionRef::Evm),
+					RuntimeEvent::PolkadotBroadcaster(event) => broadcast_event(ForeignChain::Polkadot, event, ExternalTransactionRef::Polkadot),
+					RuntimeEvent::BitcoinBroadcaster(event) => broadcast_event(ForeignChain::Bitcoin, event, ExternalTransactionRef::Bitcoin),
+					RuntimeEvent::ArbitrumBroadcaster(event) => broadcast_event(ForeignChain::Arbitrum, event, ExternalTransactionRef::Evm),
+					RuntimeEvent::SolanaBroadcaster(event) => broadcast_event(ForeignChain::Solana, event, ExternalTransactionRef::Solana),
+					RuntimeEvent::AssethubBroadcaster(event) => broadcast_event(ForeignChain::Assethub, event, ExternalTransactionRef::Polkadot),
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn cf_broadcast_state(chain: ForeignChain, broadcast_id: BroadcastId) -> Option<BroadcastState> {
+			fn state<I: 'static>(broadcast_id: BroadcastId) -> Option<BroadcastState>
+			where
+				Runtime: pallet_cf_broadcast::Config<I>,
+			{
+				if pallet_cf_broadcast::PendingBroadcasts::<Runtime, I>::get().contains(&broadcast_id) {
+					Some(BroadcastState::Pending)
+				} else if pallet_cf_broadcast::AbortedBroadcasts::<Runtime, I>::get().contains(&broadcast_id) {
+					Some(BroadcastState::Aborted)
+				} else if (1..=pallet_cf_broadcast::BroadcastIdCounter::<Runtime, I>::get()).contains(&broadcast_id) {
+					Some(BroadcastState::Completed)
+				} else {
+					None
+				}
+			}
+
+			match chain {
+				ForeignChain::Ethereum => state::<EthereumInstance>(broadcast_id),
+				ForeignChain::Polkadot => state::<PolkadotInstance>(broadcast_id),
+				ForeignChain::Bitcoin => state::<BitcoinInstance>(broadcast_id),
+				ForeignChain::Arbitrum => state::<ArbitrumInstance>(broadcast_id),
+				ForeignChain::Solana => state::<SolanaInstance>(broadcast_id),
+				ForeignChain::Assethub => state::<AssethubInstance>(broadcast_id),
+			}
+		}
+
+		fn cf_affiliates(broker: AccountId) -> Vec<AffiliateInfo> {
+			pallet_cf_swapping::AffiliateAccountDetails::<Runtime>::iter_prefix(&broker)
+				.map(|(account_id, AffiliateDetails { short_id, withdrawal_address })| AffiliateInfo {
+					earned_fees: AssetBalances::get_balance(&account_id, Asset::Usdc),
+					account_id,
+					short_id,
+					withdrawal_address,
+				})
+				.collect()
+		}
+
+		fn cf_affiliate_signing_payload(
+			broker: AccountId,
+			short_id: AffiliateShortId,
+			action: AffiliateAction,
+		) -> Option<[u8; 32]> {
+			pallet_cf_swapping::AffiliateIdMapping::<Runtime>::get(&broker, short_id)
+				.map(|affiliate_id| Swapping::affiliate_signing_payload(&affiliate_id, &action))
+		}
+
+		fn cf_broker_channel_defaults(broker: AccountId) -> Option<BrokerChannelDefaults> {
+			pallet_cf_swapping::BrokerChannelDefaultParameters::<Runtime>::get(&broker)
+		}
+
+		fn cf_dca_update_signing_payload(
+			swap_request_id: SwapRequestId,
+			update: DcaUpdate,
+		) -> Option<[u8; 32]> {
+			Swapping::dca_update_signing_payload(swap_request_id, &update)
+		}
 	}
 
 
@@ -2718,19 +3813,7 @@ impl_runtime_apis! {
 			}).collect()
 		}
 		fn cf_epoch_state() -> EpochState {
-			let auction_params = Validator::auction_parameters();
-			let min_active_bid = SetSizeMaximisingAuctionResolver::try_new(
-				<Runtime as Chainflip>::EpochInfo::current_authority_count(),
-				auction_params,
-			)
-			.and_then(|resolver| {
-				resolver.resolve_auction(
-					Validator::get_qualified_bidders::<<Runtime as pallet_cf_validator::Config>::KeygenQualification>(),
-					Validator::auction_bid_cutoff_percentage(),
-				)
-			})
-			.ok()
-			.map(|auction_outcome| auction_outcome.bond);
+			let min_active_bid = Validator::min_active_bid();
 			EpochState {
 				epoch_duration: Validator::epoch_duration(),
 				current_epoch_started_at: Validator::current_epoch_started_at(),
@@ -2857,6 +3940,91 @@ impl_runtime_apis! {
 				Self::cf_validator_info(account_id)
 			}).collect()
 		}
+
+		fn cf_external_chains_witnessing_lag() -> ExternalChainsWitnessingLag {
+			fn witnessing_lag<I: 'static>(consensus_block_height: Option<u64>) -> WitnessingLag
+			where
+				Runtime: pallet_cf_chain_tracking::Config<I>,
+			{
+				// safe to unwrap as stated on the storage item doc
+				let chain_tracking_block_height = pallet_cf_chain_tracking::CurrentChainState::<Runtime, I>::get()
+					.unwrap()
+					.block_height
+					.into();
+				WitnessingLag {
+					consensus_block_height: consensus_block_height.unwrap_or(chain_tracking_block_height),
+					chain_tracking_block_height,
+					blocks_since_last_witnessed: pallet_cf_chain_tracking::LastChainStateUpdate::<Runtime, I>::get()
+						.map(|updated_at| System::block_number().saturating_sub(updated_at)),
+				}
+			}
+
+			ExternalChainsWitnessingLag {
+				bitcoin: witnessing_lag::<BitcoinInstance>(None),
+				ethereum: witnessing_lag::<EthereumInstance>(None),
+				polkadot: witnessing_lag::<PolkadotInstance>(None),
+				// Solana block heights are agreed on through elections, and only forwarded to
+				// chain tracking once they have changed.
+				solana: witnessing_lag::<SolanaInstance>(Some(SolanaChainTrackingProvider::get_block_height())),
+				arbitrum: witnessing_lag::<ArbitrumInstance>(None),
+				assethub: witnessing_lag::<AssethubInstance>(None),
+			}
+		}
+
+		fn cf_pending_redemptions(
+			expires_after: Option<u64>,
+			expires_before: Option<u64>,
+		) -> Vec<PendingRedemption> {
+			pallet_cf_funding::PendingRedemptions::<Runtime>::iter()
+				.filter(|(_, redemption)| {
+					expires_after.is_none_or(|after| redemption.expiry_time >= after) &&
+						expires_before.is_none_or(|before| redemption.expiry_time < before)
+				})
+				.map(|(account_id, redemption)| PendingRedemption {
+					account_id,
+					amount: redemption.total,
+					restricted_amount: redemption.restricted,
+					redeem_address: redemption.redeem_address,
+					expiry_time: redemption.expiry_time,
+				})
+				.collect()
+		}
+
+		fn cf_aura_key_owner(aura_key: [u8; 32]) -> Option<AuraKeyOwner> {
+			let account_id = Session::key_owner(sp_core::crypto::key_types::AURA, &aura_key)?;
+			Some(AuraKeyOwner {
+				blocks_authored: pallet_cf_validator::CurrentEpochAuthorshipStats::<Runtime>::get(&account_id).authored,
+				cfe_version: pallet_cf_validator::NodeCFEVersion::<Runtime>::get(&account_id),
+				account_id,
+			})
+		}
+		fn cf_sol_nonce_accounts_status() -> SolanaNonceAccountsStatus {
+			let current_block = System::block_number();
+			let status = |(nonce_account, durable_nonce): cf_chains::sol::api::DurableNonceAndAccount, available: bool| {
+				let last_used_at = pallet_cf_environment::SolanaNonceAccountLastUsed::<Runtime>::get(nonce_account);
+				SolanaNonceAccountStatus {
+					nonce_account,
+					durable_nonce,
+					available,
+					last_used_at,
+					blocks_since_last_use: last_used_at.map(|last_used_at| current_block.saturating_sub(last_used_at)),
+				}
+			};
+			SolanaNonceAccountsStatus {
+				nonce_accounts: pallet_cf_environment::SolanaAvailableNonceAccounts::<Runtime>::get()
+					.into_iter()
+					.map(|nonce_and_account| status(nonce_and_account, true))
+					.chain(
+						pallet_cf_environment::SolanaUnavailableNonceAccounts::<Runtime>::iter()
+							.map(|nonce_and_account| status(nonce_and_account, false)),
+					)
+					.collect(),
+				reserved_for_critical_operations: cf_chains::sol::NONCE_NUMBER_CRITICAL_NONCES as u32,
+			}
+		}
+		fn cf_btc_utxo_summary() -> BitcoinUtxoSummary {
+			Environment::bitcoin_utxo_summary()
+		}
 	}
 
 	// END custom runtime APIs
@@ -79,6 +79,13 @@ impl OnRuntimeUpgrade for Migration {
 									OriginTrait::signed(account_id.clone()),
 								)
 							},
+							AccountRole::Operator => {
+								// To compensate for the consumer that we *should* have added.
+								frame_system::Consumer::<Runtime>::created(&account_id).expect("Can only fail if no providers or too many consumers, checked above.");
+								pallet_cf_validator::Pallet::<Runtime>::deregister_as_operator(
+									OriginTrait::signed(account_id.clone()),
+								)
+							},
 						}
 						.inspect_err(|e| {
 							log::error!(
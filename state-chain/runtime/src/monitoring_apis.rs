@@ -17,12 +17,14 @@
 use crate::{chainflip::Offence, ValidatorInfo};
 use cf_chains::{
 	dot::PolkadotAccountId,
-	sol::{api::DurableNonceAndAccount, SolAddress, SolSignature},
+	eth::Address as EthereumAddress,
+	sol::{api::DurableNonceAndAccount, SolAddress, SolHash, SolSignature},
 };
-use cf_primitives::AssetAmount;
+use cf_primitives::{AssetAmount, FlipBalance, SemVer};
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::AccountId32;
 use pallet_cf_asset_balances::VaultImbalance;
+pub use pallet_cf_environment::BitcoinUtxoSummary;
 pub use pallet_cf_ingress_egress::OwedAmount;
 use scale_info::{prelude::string::String, TypeInfo};
 use serde::{Deserialize, Serialize};
@@ -40,6 +42,24 @@ pub struct ExternalChainsBlockHeight {
 	pub assethub: u64,
 }
 #[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct WitnessingLag {
+	/// The latest external block height agreed on by the witnessing authorities.
+	pub consensus_block_height: u64,
+	/// The external block height recorded by the chain tracking pallet.
+	pub chain_tracking_block_height: u64,
+	/// The number of State Chain blocks since chain tracking was last updated, if it ever was.
+	pub blocks_since_last_witnessed: Option<u32>,
+}
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct ExternalChainsWitnessingLag {
+	pub bitcoin: WitnessingLag,
+	pub ethereum: WitnessingLag,
+	pub polkadot: WitnessingLag,
+	pub solana: WitnessingLag,
+	pub arbitrum: WitnessingLag,
+	pub assethub: WitnessingLag,
+}
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
 pub struct BtcUtxos {
 	pub total_balance: u64,
 	pub count: u32,
@@ -60,6 +80,15 @@ pub struct RedemptionsInfo {
 	pub count: u32,
 }
 #[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct PendingRedemption {
+	pub account_id: AccountId32,
+	pub amount: FlipBalance,
+	pub restricted_amount: FlipBalance,
+	pub redeem_address: EthereumAddress,
+	/// The unix time (in seconds) after which the redemption can no longer be executed.
+	pub expiry_time: u64,
+}
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
 pub struct PendingBroadcasts {
 	pub ethereum: u32,
 	pub bitcoin: u32,
@@ -135,6 +164,26 @@ pub struct SolanaNonces {
 	pub unavailable: Vec<SolAddress>,
 }
 
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct SolanaNonceAccountStatus {
+	pub nonce_account: SolAddress,
+	pub durable_nonce: SolHash,
+	/// Whether the nonce account can be used by a new transaction. Unavailable nonce accounts are
+	/// waiting for the nonce to be advanced by a broadcast transaction.
+	pub available: bool,
+	/// The State Chain block at which the nonce account was last used, if it was tracked.
+	pub last_used_at: Option<u32>,
+	pub blocks_since_last_use: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct SolanaNonceAccountsStatus {
+	pub nonce_accounts: Vec<SolanaNonceAccountStatus>,
+	/// The number of available nonce accounts that are reserved for critical operations such as
+	/// vault rotations.
+	pub reserved_for_critical_operations: u32,
+}
+
 #[derive(
 	Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone, Default,
 )]
@@ -169,6 +218,16 @@ pub struct MonitoringDataV2 {
 	pub activating_key_broadcast_ids: ActivateKeysBroadcastIds,
 }
 
+/// The validator that owns an Aura session key.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct AuraKeyOwner {
+	pub account_id: AccountId32,
+	/// The number of blocks authored by the validator in the current epoch.
+	pub blocks_authored: u32,
+	/// The engine version last reported by the validator.
+	pub cfe_version: SemVer,
+}
+
 decl_runtime_apis!(
 	#[api_version(2)]
 	pub trait MonitoringRuntimeApi {
@@ -193,5 +252,13 @@ decl_runtime_apis!(
 		fn cf_accounts_info(
 			accounts: BoundedVec<AccountId32, sp_core::ConstU32<10>>,
 		) -> Vec<ValidatorInfo>;
+		fn cf_external_chains_witnessing_lag() -> ExternalChainsWitnessingLag;
+		fn cf_pending_redemptions(
+			expires_after: Option<u64>,
+			expires_before: Option<u64>,
+		) -> Vec<PendingRedemption>;
+		fn cf_aura_key_owner(aura_key: [u8; 32]) -> Option<AuraKeyOwner>;
+		fn cf_sol_nonce_accounts_status() -> SolanaNonceAccountsStatus;
+		fn cf_btc_utxo_summary() -> BitcoinUtxoSummary;
 	}
 );
@@ -21,14 +21,20 @@ use cf_amm::{
 	range_orders::Liquidity,
 };
 use cf_chains::{
-	self, address::EncodedAddress, assets::any::AssetMap, eth::Address as EthereumAddress,
-	sol::SolInstructionRpc, CcmChannelMetadata, Chain, ChainCrypto, ForeignChainAddress,
-	VaultSwapExtraParametersEncoded,
+	self,
+	address::EncodedAddress,
+	assets::any::AssetMap,
+	ccm_checker::CcmValidityError,
+	dot::PolkadotAccountId,
+	eth::Address as EthereumAddress,
+	evm::api::{execute_x_swap_and_call::ExecutexSwapAndCall, transfer_fallback::TransferFallback},
+	sol::{rpc_types::SolAddressLookupTableRpc, SolInstructionRpc},
+	CcmChannelMetadata, Chain, ChainCrypto, ForeignChainAddress, VaultSwapExtraParametersEncoded,
 };
 use cf_primitives::{
-	AccountRole, Affiliates, Asset, AssetAmount, BasisPoints, BlockNumber, BroadcastId,
-	DcaParameters, EpochIndex, FlipBalance, ForeignChain, GasAmount, NetworkEnvironment,
-	PrewitnessedDepositId, SemVer,
+	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, BlockNumber,
+	BoostPoolTier, BroadcastId, ChannelId, DcaParameters, EgressId, EpochIndex, FlipBalance,
+	ForeignChain, GasAmount, NetworkEnvironment, PrewitnessedDepositId, SemVer, SwapRequestId,
 };
 use cf_traits::SwapLimits;
 use codec::{Decode, Encode};
@@ -37,16 +43,19 @@ use frame_support::sp_runtime::AccountId32;
 use pallet_cf_governance::GovCallHash;
 pub use pallet_cf_ingress_egress::{ChannelAction, OwedAmount};
 use pallet_cf_pools::{
-	AskBidMap, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders, PoolPriceV1, PoolPriceV2,
-	UnidirectionalPoolDepth,
+	AskBidMap, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders, PoolOrdersCursor,
+	PoolOrdersPage, PoolOrdersValuation, PoolPriceV1, PoolPriceV2, PoolStatistics,
+	RangeOrderMintQuote, RangeOrderSize, UnidirectionalPoolDepth,
+};
+use pallet_cf_swapping::{
+	AffiliateAction, AffiliateDetails, BrokerChannelDefaults, DcaUpdate, SwapLegInfo, SwapStatus,
 };
-use pallet_cf_swapping::{AffiliateDetails, SwapLegInfo};
 use pallet_cf_trading_strategy::TradingStrategy;
 use pallet_cf_witnesser::CallHash;
 use scale_info::{prelude::string::String, TypeInfo};
 use serde::{Deserialize, Serialize};
 use sp_api::decl_runtime_apis;
-use sp_runtime::{DispatchError, Percent};
+use sp_runtime::{DispatchError, Percent, Permill};
 use sp_std::{
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 	vec::Vec,
@@ -73,9 +82,52 @@ pub enum VaultSwapDetails<BtcAddress> {
 	Solana {
 		#[serde(flatten)]
 		instruction: SolInstructionRpc,
+		/// The address lookup tables referenced by `transaction_message`.
+		address_lookup_tables: Vec<SolAddressLookupTableRpc>,
+		/// The serialized v0 message of a transaction made of the instruction alone, with the
+		/// `from` account as the fee payer. The recent blockhash is left empty: wallets have to
+		/// set it (and may add compute budget instructions) before signing.
+		#[serde(with = "sp_core::bytes")]
+		transaction_message: Vec<u8>,
+	},
+	Assethub {
+		/// The encoded `utility.batch_all` call that transfers the input asset to the vault and
+		/// attaches the swap parameters as a remark.
+		#[serde(with = "sp_core::bytes")]
+		call: Vec<u8>,
+		/// The remark on its own, for callers that want to build the transfer themselves.
+		#[serde(with = "sp_core::bytes")]
+		remark: Vec<u8>,
+		vault_account: PolkadotAccountId,
 	},
 }
 
+/// The layout of [VaultSwapDetails] before Solana vault swap details included the address lookup
+/// tables and the transaction message.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+pub enum VaultSwapDetailsLegacy<BtcAddress> {
+	Bitcoin { nulldata_payload: Vec<u8>, deposit_address: BtcAddress },
+	Ethereum { details: EvmVaultSwapDetails },
+	Arbitrum { details: EvmVaultSwapDetails },
+	Solana { instruction: SolInstructionRpc },
+}
+
+impl<BtcAddress> From<VaultSwapDetailsLegacy<BtcAddress>> for VaultSwapDetails<BtcAddress> {
+	fn from(legacy: VaultSwapDetailsLegacy<BtcAddress>) -> Self {
+		match legacy {
+			VaultSwapDetailsLegacy::Bitcoin { nulldata_payload, deposit_address } =>
+				VaultSwapDetails::Bitcoin { nulldata_payload, deposit_address },
+			VaultSwapDetailsLegacy::Ethereum { details } => VaultSwapDetails::Ethereum { details },
+			VaultSwapDetailsLegacy::Arbitrum { details } => VaultSwapDetails::Arbitrum { details },
+			VaultSwapDetailsLegacy::Solana { instruction } => VaultSwapDetails::Solana {
+				instruction,
+				address_lookup_tables: Vec::new(),
+				transaction_message: Vec::new(),
+			},
+		}
+	}
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize)]
 pub struct EvmVaultSwapDetails {
 	#[serde(with = "sp_core::bytes")]
@@ -100,9 +152,16 @@ impl<BtcAddress> VaultSwapDetails<BtcAddress> {
 		match self {
 			VaultSwapDetails::Bitcoin { nulldata_payload, deposit_address } =>
 				VaultSwapDetails::Bitcoin { nulldata_payload, deposit_address: f(deposit_address) },
-			VaultSwapDetails::Solana { instruction } => VaultSwapDetails::Solana { instruction },
+			VaultSwapDetails::Solana {
+				instruction,
+				address_lookup_tables,
+				transaction_message,
+			} =>
+				VaultSwapDetails::Solana { instruction, address_lookup_tables, transaction_message },
 			VaultSwapDetails::Ethereum { details } => VaultSwapDetails::Ethereum { details },
 			VaultSwapDetails::Arbitrum { details } => VaultSwapDetails::Arbitrum { details },
+			VaultSwapDetails::Assethub { call, remark, vault_account } =>
+				VaultSwapDetails::Assethub { call, remark, vault_account },
 		}
 	}
 }
@@ -175,6 +234,135 @@ pub struct BoostPoolDetails {
 	pub network_fee_deduction_percent: Percent,
 }
 
+/// The maximum number of boosters returned in a single page of boost pool details.
+pub const MAX_BOOST_POOL_DETAILS_PAGE_SIZE: u32 = 100;
+
+impl BoostPoolDetails {
+	/// Restricts the details to the boosters on the requested page, ordered by account id. If a
+	/// `booster` is given, only that booster's details are kept.
+	pub fn into_page(
+		self,
+		booster: Option<AccountId32>,
+		offset: u32,
+		limit: u32,
+	) -> BoostPoolDetailsPage {
+		let boosters = self
+			.available_amounts
+			.keys()
+			.chain(self.pending_withdrawals.keys())
+			.chain(self.pending_boosts.values().flat_map(|owed_amounts| owed_amounts.keys()))
+			.filter(|account_id| booster.is_none() || booster.as_ref() == Some(*account_id))
+			.cloned()
+			.collect::<BTreeSet<_>>();
+		let page = boosters
+			.iter()
+			.skip(offset as usize)
+			.take(limit as usize)
+			.cloned()
+			.collect::<BTreeSet<_>>();
+		let next_offset = offset.saturating_add(limit);
+
+		BoostPoolDetailsPage {
+			details: BoostPoolDetails {
+				available_amounts: self
+					.available_amounts
+					.into_iter()
+					.filter(|(account_id, _)| page.contains(account_id))
+					.collect(),
+				pending_boosts: self
+					.pending_boosts
+					.into_iter()
+					.filter_map(|(deposit_id, owed_amounts)| {
+						let owed_amounts = owed_amounts
+							.into_iter()
+							.filter(|(account_id, _)| page.contains(account_id))
+							.collect::<BTreeMap<_, _>>();
+						(!owed_amounts.is_empty()).then_some((deposit_id, owed_amounts))
+					})
+					.collect(),
+				pending_withdrawals: self
+					.pending_withdrawals
+					.into_iter()
+					.filter(|(account_id, _)| page.contains(account_id))
+					.collect(),
+				network_fee_deduction_percent: self.network_fee_deduction_percent,
+			},
+			next_offset: ((next_offset as usize) < boosters.len()).then_some(next_offset),
+		}
+	}
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct BoostPoolDetailsPage {
+	pub details: BoostPoolDetails,
+	/// Pass this as the offset to get the next page. `None` if there are no more boosters.
+	pub next_offset: Option<u32>,
+}
+
+/// The maximum number of accounts returned in a single page of `cf_accounts_page`.
+pub const MAX_ACCOUNTS_PAGE_SIZE: u32 = 1_000;
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct AccountSummary {
+	pub account_id: AccountId32,
+	pub role: AccountRole,
+	pub vanity_name: VanityName,
+	/// Only included if balances were requested.
+	pub flip_balance: Option<FlipBalance>,
+	/// Only included if balances were requested, and only for liquidity providers and brokers.
+	pub asset_balances: Option<AssetMap<AssetAmount>>,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct AccountsPage {
+	pub accounts: Vec<AccountSummary>,
+	/// Pass this as `after` to get the next page. `None` if there are no more accounts.
+	pub next: Option<AccountId32>,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum RuntimeApiRotationPhase {
+	Idle,
+	KeygensInProgress,
+	KeyHandoversInProgress,
+	ActivatingKeys,
+	NewKeysActivated,
+	SessionRotating,
+}
+
+/// The progress of the rotation of a single chain's vault to the new key.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum VaultRotationProgress {
+	/// The new key is being generated and verified.
+	Keygen,
+	/// The current key is being handed over to the new key.
+	KeyHandover,
+	/// The transactions activating the new key are being signed.
+	AwaitingActivationSignatures,
+	/// The new key is waiting to be activated on the external chain.
+	AwaitingActivation,
+	/// The activation transaction could not be built. The rotation is paused until governance
+	/// intervenes.
+	ActivationFailedAwaitingGovernance,
+	/// The vault has been rotated to the new key.
+	Complete,
+	/// The key rotation failed and will be retried.
+	Failed,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Debug, Clone)]
+pub struct RotationStatus {
+	pub phase: RuntimeApiRotationPhase,
+	/// The epoch that is started by the rotation. `None` if no keys are being rotated.
+	pub new_epoch_index: Option<EpochIndex>,
+	/// The validators that will make up the new authority set if the rotation succeeds.
+	pub candidates: Vec<AccountId32>,
+	/// Candidates that were excluded from the rotation after failing a keygen or key handover.
+	pub banned: Vec<AccountId32>,
+	/// The rotation progress of each chain. Empty if no rotation is in progress.
+	pub vaults: BTreeMap<ForeignChain, VaultRotationProgress>,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
 pub struct RuntimeApiPenalty {
 	pub reputation_points: i32,
@@ -252,6 +440,38 @@ pub struct SimulatedSwapInformation {
 	pub broker_fee: AssetAmount,
 }
 
+/// Estimated output of a swap, with a breakdown of every fee paid by the user: ingress,
+/// boost, network, broker, affiliate and egress fees.
+#[derive(Encode, Decode, TypeInfo)]
+pub struct SimulatedSwapInformationWithFees {
+	pub intermediary: Option<AssetAmount>,
+	pub output: AssetAmount,
+	pub network_fee: AssetAmount,
+	pub ingress_fee: AssetAmount,
+	pub egress_fee: AssetAmount,
+	/// The part of the broker commission paid to the broker itself.
+	pub broker_fee: AssetAmount,
+	/// The part of the broker commission paid to each of the affiliates.
+	pub affiliate_fees: Vec<(AccountId32, AssetAmount)>,
+	/// The maximum fee paid to boosters, should the deposit be boosted.
+	pub boost_fee: AssetAmount,
+}
+
+/// Reasons for which a CCM would be rejected by the destination chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub enum CcmValidationError {
+	/// The destination chain does not support CCM.
+	UnsupportedForTargetChain,
+	/// The destination address is not valid for the destination asset.
+	InvalidDestinationAddress,
+	/// The gas budget exceeds what a single CCM can use on the destination chain, given the
+	/// message length and the chain's current fees.
+	GasBudgetTooHigh { max_gas_budget: GasAmount },
+	/// The message or the CCM additional data (cf_parameters) failed validation for the
+	/// destination chain.
+	InvalidCcm(CcmValidityError),
+}
+
 #[derive(Debug, Decode, Encode, TypeInfo)]
 pub enum DispatchErrorWithMessage {
 	Module(Vec<u8>),
@@ -293,6 +513,51 @@ pub struct FailingWitnessValidators {
 	pub validators: Vec<(cf_primitives::AccountId, String, bool)>,
 }
 
+/// The witnessing failures of a validator over a range of blocks, for calls witnessed in the given
+/// epoch.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct ValidatorWitnessReport {
+	pub epoch_index: EpochIndex,
+	pub account_id: AccountId32,
+	pub vanity_name: String,
+	/// Calls the validator did not witness before the witnessing deadline.
+	pub missed: u32,
+	/// Calls the validator witnessed only after the witnessing deadline had elapsed.
+	pub late: u32,
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct DecodedAffiliate {
+	pub short_id: AffiliateShortId,
+	/// `None` if the broker is unknown or has no affiliate registered under this short id.
+	pub account_id: Option<AccountId32>,
+	pub fee: BasisPoints,
+}
+
+/// The swap parameters encoded in vault swap calldata, nulldata or instruction data. Fields that
+/// are not part of the encoding for the given chain are `None`.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct DecodedVaultSwap {
+	/// The source asset, if it can be inferred from the encoded call.
+	pub source_asset: Option<Asset>,
+	/// The input amount, if it is part of the encoded call rather than the transaction value.
+	pub amount: Option<AssetAmount>,
+	pub destination_asset: Asset,
+	pub destination_address: EncodedAddress,
+	pub refund_address: Option<EncodedAddress>,
+	pub retry_duration: BlockNumber,
+	pub min_price: Option<sp_core::U256>,
+	/// Bitcoin vault swaps encode a minimum output amount instead of a minimum price.
+	pub min_output_amount: Option<AssetAmount>,
+	pub dca_parameters: Option<DcaParameters>,
+	pub boost_fee: u8,
+	/// Bitcoin vault swaps identify the broker by the deposit address, not the nulldata.
+	pub broker: Option<AccountId32>,
+	pub broker_commission: BasisPoints,
+	pub affiliate_fees: Vec<DecodedAffiliate>,
+	pub channel_metadata: Option<CcmChannelMetadata>,
+}
+
 #[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
 pub struct ChainAccounts {
 	pub chain_accounts: Vec<EncodedAddress>,
@@ -316,14 +581,18 @@ pub enum ChannelActionType {
 	Swap,
 	LiquidityProvision,
 	Refund,
+	PrivateBrokerDeposit,
 }
 
 impl<AccountId, C: Chain> From<ChannelAction<AccountId, C>> for ChannelActionType {
 	fn from(action: ChannelAction<AccountId, C>) -> Self {
 		match action {
-			ChannelAction::Swap { .. } => ChannelActionType::Swap,
+			ChannelAction::Swap { .. } |
+			ChannelAction::SplitSwap { .. } |
+			ChannelAction::FundAccount { .. } => ChannelActionType::Swap,
 			ChannelAction::LiquidityProvision { .. } => ChannelActionType::LiquidityProvision,
 			ChannelAction::Refund { .. } => ChannelActionType::Refund,
+			ChannelAction::PrivateBrokerDeposit { .. } => ChannelActionType::PrivateBrokerDeposit,
 		}
 	}
 }
@@ -377,6 +646,355 @@ pub struct TradingStrategyLimits {
 	pub minimum_added_funds_amount: AssetMap<Option<AssetAmount>>,
 }
 
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct FeeScalingInfo {
+	/// The number of recent calls counted against the account.
+	pub call_count: u16,
+	/// The multiplier that will be applied to the fee of the account's next call.
+	pub multiplier: u16,
+	/// The fee that would be charged for the account's next call.
+	pub next_call_fee: FlipBalance,
+}
+
+#[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq)]
+pub struct CallFeeQuote {
+	/// The fee for the call before any scaling is applied.
+	pub base_fee: FlipBalance,
+	/// The fee scaling multiplier that would apply to the call.
+	pub multiplier: u16,
+	/// The fee that would be charged for the call.
+	pub fee: FlipBalance,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct ValidatorPerformance {
+	pub account_id: AccountId32,
+	/// Blocks authored in the current epoch.
+	pub blocks_authored: u32,
+	/// Blocks the authority was expected to author in the current epoch.
+	pub blocks_expected: u32,
+	/// Witnessed calls in the current epoch whose deadline the authority met.
+	pub witnessed_in_time: u32,
+	/// Witnessed calls in the current epoch whose witnessing deadline has elapsed.
+	pub witness_deadlines_elapsed: u32,
+	pub witness_participation: Permill,
+	pub reputation_points: i32,
+	pub last_heartbeat: Option<u32>,
+}
+
+/// A step in the broadcast of egressed funds, as emitted in a block.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub enum EgressBroadcastEvent {
+	/// The egresses were included in a broadcast.
+	BroadcastRequested {
+		broadcast_id: BroadcastId,
+		egress_ids: Vec<EgressId>,
+	},
+	BroadcastSucceeded {
+		chain: ForeignChain,
+		broadcast_id: BroadcastId,
+	},
+	BroadcastAborted {
+		chain: ForeignChain,
+		broadcast_id: BroadcastId,
+	},
+}
+
+/// A reference to a transaction on an external chain.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalTransactionRef {
+	Evm(sp_core::H256),
+	Bitcoin(sp_core::H256),
+	Polkadot(cf_chains::dot::PolkadotTransactionId),
+	Solana(cf_chains::sol::SolSignature),
+}
+
+/// A step in the lifecycle of a broadcast, as emitted in a block.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BroadcastLifecycleEvent {
+	/// An authority was nominated to broadcast the transaction.
+	TransactionBroadcastRequested {
+		nominee: AccountId32,
+	},
+	/// The broadcast failed and will be retried by another authority.
+	RetryScheduled {
+		retry_block: BlockNumber,
+	},
+	TimedOut,
+	/// The threshold signature of the transaction was invalid, so it will be re-signed.
+	ThresholdSignatureInvalid,
+	Resigned,
+	Succeeded {
+		transaction_ref: ExternalTransactionRef,
+	},
+	Aborted,
+}
+
+/// Whether a broadcast is still in progress.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum BroadcastState {
+	Pending,
+	Aborted,
+	/// The broadcast succeeded.
+	Completed,
+}
+
+/// An affiliate registered by a broker, and the fees it has earned but not yet withdrawn.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Clone, Debug)]
+pub struct AffiliateInfo {
+	pub account_id: AccountId32,
+	pub short_id: AffiliateShortId,
+	pub withdrawal_address: EthereumAddress,
+	/// Affiliate fees are always earned in USDC.
+	pub earned_fees: AssetAmount,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum DepositStage {
+	Prewitnessed,
+	Boosted,
+	Finalised,
+}
+
+/// A deposit to a known address, as emitted in a block.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct DepositEvent {
+	pub stage: DepositStage,
+	pub deposit_address: EncodedAddress,
+	/// The owner of the deposit channel, if the deposit was made to a deposit channel that is
+	/// still open.
+	pub channel_owner: Option<AccountId32>,
+	pub channel_id: Option<ChannelId>,
+	pub asset: Asset,
+	pub amount: AssetAmount,
+	pub deposit_block_height: u64,
+}
+
+/// Whether the deposits, boosting and egresses of an asset are currently enabled.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct AssetIngressEgressStatus {
+	pub deposits_enabled: bool,
+	pub boost_deposits_enabled: bool,
+	pub egress_enabled: bool,
+}
+
+/// The outcome of boosting a deposit at the current liquidity of the boost pools.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Clone, Debug)]
+pub struct BoostQuote {
+	/// Whether the full amount could currently be boosted. If not, the deposit won't be boosted.
+	pub fully_boostable: bool,
+	/// The amount boosted by each of the pools used, by tier.
+	pub boosted_amounts: BTreeMap<BoostPoolTier, AssetAmount>,
+	pub boost_fee: AssetAmount,
+	/// The boost fee relative to the deposit amount, blended across the pools used.
+	pub boost_fee_bps: BasisPoints,
+	/// The deposit amount after the boost fee, but before the ingress fee.
+	pub net_amount: AssetAmount,
+}
+
+/// An event involving a specific account, as emitted in a block.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub enum AccountEvent {
+	/// A deposit to a channel owned by the account was finalised.
+	DepositFinalised {
+		deposit_address: EncodedAddress,
+		channel_id: Option<ChannelId>,
+		asset: Asset,
+		amount: AssetAmount,
+	},
+	/// A swap was requested by the account, through one of its channels, or with the account as
+	/// one of the fee beneficiaries.
+	SwapRequested {
+		swap_request_id: SwapRequestId,
+		input_asset: Asset,
+		input_amount: AssetAmount,
+		output_asset: Asset,
+	},
+	Funded {
+		amount: FlipBalance,
+	},
+	RedemptionRequested {
+		amount: FlipBalance,
+		broadcast_id: BroadcastId,
+		expiry_time: u64,
+	},
+	RedemptionSettled {
+		amount: FlipBalance,
+	},
+	RedemptionExpired,
+}
+
+/// A fetch or transfer waiting in a chain's egress queue. `scheduled_at` is `None` for requests
+/// that were scheduled before scheduling blocks were tracked.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub enum ScheduledEgress {
+	Fetch {
+		asset: Asset,
+		amount: AssetAmount,
+		deposit_address: EncodedAddress,
+		scheduled_at: Option<BlockNumber>,
+	},
+	Transfer {
+		egress_id: EgressId,
+		asset: Asset,
+		amount: AssetAmount,
+		destination_address: EncodedAddress,
+		scheduled_at: Option<BlockNumber>,
+	},
+}
+
+/// Why a call to an external chain failed and was stored for users to broadcast themselves.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum FailedCallReason {
+	/// Every authority reported that the CCM could not be broadcast.
+	CcmBroadcastAborted,
+	/// Witnesses observed the vault's transfer to the destination address failing, so the funds
+	/// are sent via the fallback instead.
+	VaultTransferFailed,
+}
+
+/// Whether the signed failed call can currently be broadcast.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum FailedCallStatus {
+	/// The call was signed in a previous epoch, and is waiting to be re-signed for the current
+	/// epoch.
+	AwaitingResigning,
+	/// The call is signed for the current epoch. It will be re-signed after the next rotation if
+	/// the current epoch is before `expires_after_epoch`, and removed otherwise.
+	ReadyToBroadcast { expires_after_epoch: EpochIndex },
+}
+
+/// The arguments of a failed EVM vault call, decoded from its ABI encoding.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub enum DecodedEvmCall {
+	ExecutexSwapAndCall {
+		asset: EthereumAddress,
+		destination_address: EthereumAddress,
+		amount: AssetAmount,
+		source_chain: Option<ForeignChain>,
+		source_address: Vec<u8>,
+		gas_budget: GasAmount,
+		message: Vec<u8>,
+	},
+	TransferFallback {
+		asset: EthereumAddress,
+		destination_address: EthereumAddress,
+		amount: AssetAmount,
+	},
+}
+
+impl DecodedEvmCall {
+	pub fn reason(&self) -> FailedCallReason {
+		match self {
+			DecodedEvmCall::ExecutexSwapAndCall { .. } => FailedCallReason::CcmBroadcastAborted,
+			DecodedEvmCall::TransferFallback { .. } => FailedCallReason::VaultTransferFailed,
+		}
+	}
+}
+
+impl From<&ExecutexSwapAndCall> for DecodedEvmCall {
+	fn from(call: &ExecutexSwapAndCall) -> Self {
+		DecodedEvmCall::ExecutexSwapAndCall {
+			asset: call.transfer_param.asset,
+			destination_address: call.transfer_param.to,
+			amount: call.transfer_param.amount,
+			source_chain: ForeignChain::try_from(call.source_chain).ok(),
+			source_address: call.source_address.clone(),
+			gas_budget: call.gas_budget,
+			message: call.message.clone(),
+		}
+	}
+}
+
+impl From<&TransferFallback> for DecodedEvmCall {
+	fn from(call: &TransferFallback) -> Self {
+		DecodedEvmCall::TransferFallback {
+			asset: call.transfer_param.asset,
+			destination_address: call.transfer_param.to,
+			amount: call.transfer_param.amount,
+		}
+	}
+}
+
+/// A failed CCM or vault transfer, along with the signed call that users can broadcast.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct FailedCallDetails {
+	pub broadcast_id: BroadcastId,
+	pub original_epoch: EpochIndex,
+	pub reason: FailedCallReason,
+	pub status: FailedCallStatus,
+	pub call: DecodedEvmCall,
+	pub transaction: cf_chains::evm::Transaction,
+}
+
+/// Fees paid to an LP from a pool over an epoch, in each of the pool's assets.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct LpEpochEarnedFees {
+	pub base_asset: Asset,
+	pub quote_asset: Asset,
+	pub epoch_index: EpochIndex,
+	pub fees: PoolPairsMap<AssetAmount>,
+}
+
+/// The rewards a validator earned over an epoch, alongside its witnessing record for the epoch.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct EpochRewardStatement {
+	pub account_id: AccountId32,
+	pub authorship_rewards: FlipBalance,
+	pub backup_rewards: FlipBalance,
+	/// The part of the rewards that was paid out to the validator's delegators.
+	pub delegators_share: FlipBalance,
+	/// Witnessed calls in the epoch whose deadline the validator met.
+	pub witnessed_in_time: u32,
+	/// Witnessed calls in the epoch whose witnessing deadline has elapsed.
+	pub witness_deadlines_elapsed: u32,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectedBid {
+	pub account_id: AccountId32,
+	pub amount: FlipBalance,
+	/// Whether the bidder meets the qualification criteria for the auction.
+	pub qualified: bool,
+	/// Whether the bidder would be in the authority set if the auction resolved now.
+	pub winning: bool,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectedAuctionOutcome {
+	/// The projected authority set, sorted by descending bid.
+	pub authorities: Vec<AccountId32>,
+	pub bond: FlipBalance,
+	/// All active bids, sorted by descending amount.
+	pub bids: Vec<ProjectedBid>,
+}
+
+/// A reason why a validator would not be included in the authority set if the auction were
+/// resolved now.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub enum RotationBlocker {
+	NotRegisteredAsValidator,
+	NotBidding,
+	SessionKeysNotRegistered,
+	PeerIdNotRegistered,
+	/// No heartbeat has been submitted recently.
+	Offline,
+	/// The validator is suspended for an offence that excludes it from keygen.
+	Suspended,
+	CfeVersionTooOld {
+		reported: SemVer,
+		minimum: SemVer,
+	},
+	InsufficientReputation,
+	/// The validator is qualified, but its bid is too low to win a seat.
+	BidTooLow {
+		bid: FlipBalance,
+		projected_bond: FlipBalance,
+	},
+}
+
 // READ THIS BEFORE UPDATING THIS TRAIT:
 //
 // ## When changing an existing method:
@@ -393,7 +1011,7 @@ pub struct TradingStrategyLimits {
 //  - Handle the dummy method gracefully in the custom rpc implementation using
 //    runtime_api().api_version().
 decl_runtime_apis!(
-	#[api_version(4)]
+	#[api_version(5)]
 	pub trait CustomRuntimeApi {
 		/// Returns true if the current phase is the auction phase.
 		fn cf_is_auction_phase() -> bool;
@@ -446,6 +1064,18 @@ decl_runtime_apis!(
 			exclude_fees: BTreeSet<FeeTypes>,
 			additional_limit_orders: Option<Vec<SimulateSwapAdditionalOrder>>,
 		) -> Result<SimulatedSwapInformation, DispatchErrorWithMessage>;
+		fn cf_pool_simulate_swap_with_fees(
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+			broker_commission: BasisPoints,
+			affiliate_fees: Affiliates<AccountId32>,
+			boost_fee: BasisPoints,
+			dca_parameters: Option<DcaParameters>,
+			ccm_data: Option<CcmData>,
+			exclude_fees: BTreeSet<FeeTypes>,
+			additional_limit_orders: Option<Vec<SimulateSwapAdditionalOrder>>,
+		) -> Result<SimulatedSwapInformationWithFees, DispatchErrorWithMessage>;
 		fn cf_pool_info(
 			base_asset: Asset,
 			quote_asset: Asset,
@@ -493,7 +1123,11 @@ decl_runtime_apis!(
 		#[changed_in(3)]
 		fn cf_broker_info(account_id: AccountId32) -> BrokerInfoLegacy;
 		fn cf_broker_info(account_id: AccountId32) -> BrokerInfo;
+		#[changed_in(5)]
 		fn cf_account_role(account_id: AccountId32) -> Option<AccountRole>;
+		/// The account's role, followed by the role it holds alongside it, if any. Empty if the
+		/// account doesn't exist.
+		fn cf_account_role(account_id: AccountId32) -> Vec<AccountRole>;
 		fn cf_free_balances(account_id: AccountId32) -> AssetMap<AssetAmount>;
 		fn cf_lp_total_balances(account_id: AccountId32) -> AssetMap<AssetAmount>;
 		fn cf_redemption_tax() -> AssetAmount;
@@ -517,6 +1151,7 @@ decl_runtime_apis!(
 		fn cf_safe_mode_statuses() -> RuntimeSafeMode;
 		fn cf_pools() -> Vec<PoolPairsMap<Asset>>;
 		fn cf_swap_retry_delay_blocks() -> u32;
+		fn cf_swap_delay_blocks() -> u32;
 		fn cf_swap_limits() -> SwapLimits;
 		fn cf_lp_events() -> Vec<pallet_cf_pools::Event<Runtime>>;
 		fn cf_minimum_chunk_size(asset: Asset) -> AssetAmount;
@@ -527,6 +1162,24 @@ decl_runtime_apis!(
 		fn cf_validate_refund_params(
 			retry_duration: BlockNumber,
 		) -> Result<(), DispatchErrorWithMessage>;
+		fn cf_validate_ccm_channel_metadata(
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			channel_metadata: CcmChannelMetadata,
+		) -> Result<(), CcmValidationError>;
+		#[changed_in(5)]
+		fn cf_request_swap_parameter_encoding(
+			broker: AccountId32,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			extra_parameters: VaultSwapExtraParametersEncoded,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<AccountId32>,
+			dca_parameters: Option<DcaParameters>,
+		) -> Result<VaultSwapDetailsLegacy<String>, DispatchErrorWithMessage>;
 		fn cf_request_swap_parameter_encoding(
 			broker: AccountId32,
 			source_asset: Asset,
@@ -551,6 +1204,118 @@ decl_runtime_apis!(
 			lp_id: Option<AccountId32>,
 		) -> Vec<TradingStrategyInfo<AssetAmount>>;
 		fn cf_trading_strategy_limits() -> TradingStrategyLimits;
+		fn cf_private_evm_deposit_addresses(
+			broker: AccountId32,
+		) -> Vec<(ForeignChain, Vec<(Asset, EncodedAddress)>)>;
+		fn cf_swap_status(swap_request_id: SwapRequestId) -> Option<SwapStatus>;
+		fn cf_pool_twap(
+			base_asset: Asset,
+			quote_asset: Asset,
+			window_blocks: u32,
+		) -> Option<PoolPriceV1>;
+		fn cf_pool_statistics(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<Vec<(EpochIndex, PoolStatistics)>, DispatchErrorWithMessage>;
+		fn cf_pool_protocol_fee(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<Permill, DispatchErrorWithMessage>;
+		fn cf_fee_tier_pools(
+			base_asset: Asset,
+			quote_asset: Asset,
+		) -> Result<Vec<PoolInfo>, DispatchErrorWithMessage>;
+		fn cf_pool_orders_page(
+			base_asset: Asset,
+			quote_asset: Asset,
+			lp: Option<AccountId32>,
+			filled_orders: bool,
+			after: Option<PoolOrdersCursor<AccountId32>>,
+			limit: u32,
+		) -> Result<PoolOrdersPage<Runtime>, DispatchErrorWithMessage>;
+		fn cf_lp_orders(
+			lp: AccountId32,
+			filled_orders: bool,
+		) -> Result<Vec<(Asset, Asset, PoolOrders<Runtime>)>, DispatchErrorWithMessage>;
+		fn cf_pool_range_order_mint_quote(
+			base_asset: Asset,
+			quote_asset: Asset,
+			tick_range: Range<Tick>,
+			size: RangeOrderSize,
+		) -> Result<RangeOrderMintQuote, DispatchErrorWithMessage>;
+		fn cf_fee_scaling_multiplier(account_id: AccountId32, base_asset: Asset) -> FeeScalingInfo;
+		fn cf_call_fee_quote(
+			account_id: AccountId32,
+			call: Vec<u8>,
+			len: u32,
+		) -> Result<CallFeeQuote, DispatchErrorWithMessage>;
+		fn cf_validator_performance() -> Vec<ValidatorPerformance>;
+		fn cf_operator_validators(operator: AccountId32) -> Vec<AccountId32>;
+		fn cf_projected_auction_outcome() -> Option<ProjectedAuctionOutcome>;
+		fn cf_accounts_by_vanity_name(name: VanityName) -> Vec<AccountId32>;
+		fn cf_rotation_blockers(account_id: AccountId32) -> Vec<RotationBlocker>;
+		fn cf_epoch_reward_statements(epoch_index: EpochIndex) -> Vec<EpochRewardStatement>;
+		fn cf_egress_broadcast_events() -> Vec<EgressBroadcastEvent>;
+		fn cf_deposit_events() -> Vec<DepositEvent>;
+		fn cf_boost_pool_details_page(
+			asset: Asset,
+			tier: BoostPoolTier,
+			booster: Option<AccountId32>,
+			offset: u32,
+			limit: u32,
+		) -> Option<BoostPoolDetailsPage>;
+		fn cf_scheduled_egresses(chain: ForeignChain) -> Vec<ScheduledEgress>;
+		fn cf_lp_earned_fees_history(lp: AccountId32) -> Vec<LpEpochEarnedFees>;
+		fn cf_failed_call_details(
+			chain: ForeignChain,
+			broadcast_id: BroadcastId,
+		) -> Option<FailedCallDetails>;
+		fn cf_witness_report(
+			from_block: BlockNumber,
+			to_block: BlockNumber,
+		) -> Vec<ValidatorWitnessReport>;
+		fn cf_decode_vault_swap(
+			chain: ForeignChain,
+			data: Vec<u8>,
+		) -> Result<DecodedVaultSwap, DispatchErrorWithMessage>;
+		fn cf_accounts_page(
+			roles: Vec<AccountRole>,
+			after: Option<AccountId32>,
+			limit: u32,
+			include_balances: bool,
+		) -> AccountsPage;
+		fn cf_estimate_egress_fee(
+			asset: Asset,
+			destination_chain: ForeignChain,
+			with_ccm_gas: Option<CcmData>,
+		) -> Result<AssetAmount, DispatchErrorWithMessage>;
+		fn cf_rotation_status() -> RotationStatus;
+		fn cf_lp_orders_valuation(
+			lp: AccountId32,
+		) -> Result<Vec<PoolOrdersValuation<Runtime>>, DispatchErrorWithMessage>;
+		fn cf_account_events() -> Vec<(AccountId32, AccountEvent)>;
+		fn cf_asset_ingress_egress_status(asset: Asset) -> AssetIngressEgressStatus;
+		fn cf_boost_quote(
+			asset: Asset,
+			amount: AssetAmount,
+			max_boost_fee: BasisPoints,
+		) -> BoostQuote;
+		fn cf_broadcast_events() -> Vec<(ForeignChain, BroadcastId, BroadcastLifecycleEvent)>;
+		fn cf_broadcast_state(
+			chain: ForeignChain,
+			broadcast_id: BroadcastId,
+		) -> Option<BroadcastState>;
+		fn cf_affiliates(broker: AccountId32) -> Vec<AffiliateInfo>;
+		fn cf_affiliate_signing_payload(
+			broker: AccountId32,
+			short_id: AffiliateShortId,
+			action: AffiliateAction,
+		) -> Option<[u8; 32]>;
+		fn cf_broker_channel_defaults(broker: AccountId32) -> Option<BrokerChannelDefaults>;
+		fn cf_dca_update_signing_payload(
+			swap_request_id: SwapRequestId,
+			update: DcaUpdate,
+		) -> Option<[u8; 32]>;
 	}
 );
 
@@ -47,7 +47,7 @@ use cf_primitives::{
 	AccountRole, AffiliateShortId, Asset, AssetAmount, AuthorityCount, BasisPoints, Beneficiaries,
 	BlockNumber, BroadcastId, ChannelId, DcaParameters, Ed25519PublicKey, EgressCounter, EgressId,
 	EpochIndex, FlipBalance, ForeignChain, GasAmount, Ipv6Addr, NetworkEnvironment, Price, SemVer,
-	ThresholdSignatureRequestId,
+	ThresholdSignatureRequestId, Tick,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
@@ -344,6 +344,15 @@ pub trait Issuance {
 	fn burn_offchain(amount: Self::Balance);
 }
 
+/// Pays out rewards that have been set aside for delegators as they were earned.
+pub trait DelegationRewardsPayout {
+	type AccountId;
+	type Amount;
+
+	/// Credits `amount` of previously set aside rewards to the delegator's account.
+	fn pay_out(delegator: &Self::AccountId, amount: Self::Amount);
+}
+
 /// Distribute rewards somehow.
 pub trait RewardsDistribution {
 	type Balance;
@@ -771,6 +780,31 @@ pub trait DepositApi<C: Chain> {
 		refund_params: ChannelRefundParametersDecoded,
 		dca_params: Option<DcaParameters>,
 	) -> Result<(ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount), DispatchError>;
+
+	/// Issues a channel id and deposit address for a new swap whose output is split between
+	/// several destination addresses, each receiving the given share (in basis points).
+	fn request_split_swap_deposit_address(
+		source_asset: C::ChainAsset,
+		destination_asset: Asset,
+		destination_addresses: Vec<(ForeignChainAddress, BasisPoints)>,
+		broker_commission: Beneficiaries<Self::AccountId>,
+		broker_id: Self::AccountId,
+		boost_fee: BasisPoints,
+		refund_params: ChannelRefundParametersDecoded,
+		dca_params: Option<DcaParameters>,
+	) -> Result<(ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount), DispatchError>;
+
+	/// Issues a channel id and deposit address for a new swap into FLIP whose output is added to
+	/// the funds of the given state chain account.
+	fn request_account_funding_deposit_address(
+		source_asset: C::ChainAsset,
+		account_id: Self::AccountId,
+		broker_commission: Beneficiaries<Self::AccountId>,
+		broker_id: Self::AccountId,
+		boost_fee: BasisPoints,
+		refund_params: ChannelRefundParametersDecoded,
+		dca_params: Option<DcaParameters>,
+	) -> Result<(ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount), DispatchError>;
 }
 
 pub trait AccountRoleRegistry<T: frame_system::Config> {
@@ -796,6 +830,10 @@ pub trait AccountRoleRegistry<T: frame_system::Config> {
 		Self::register_account_role(account_id, AccountRole::Validator)
 	}
 
+	fn register_as_operator(account_id: &T::AccountId) -> DispatchResult {
+		Self::register_account_role(account_id, AccountRole::Operator)
+	}
+
 	fn deregister_as_broker(account_id: &T::AccountId) -> DispatchResult {
 		Self::deregister_account_role(account_id, AccountRole::Broker)
 	}
@@ -808,6 +846,10 @@ pub trait AccountRoleRegistry<T: frame_system::Config> {
 		Self::deregister_account_role(account_id, AccountRole::Validator)
 	}
 
+	fn deregister_as_operator(account_id: &T::AccountId) -> DispatchResult {
+		Self::deregister_account_role(account_id, AccountRole::Operator)
+	}
+
 	fn ensure_account_role(
 		origin: T::RuntimeOrigin,
 		role: AccountRole,
@@ -825,6 +867,10 @@ pub trait AccountRoleRegistry<T: frame_system::Config> {
 		Self::ensure_account_role(origin, AccountRole::Validator)
 	}
 
+	fn ensure_operator(origin: T::RuntimeOrigin) -> Result<T::AccountId, BadOrigin> {
+		Self::ensure_account_role(origin, AccountRole::Operator)
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	fn whitelisted_caller_with_role(role: AccountRole) -> Result<T::AccountId, DispatchError> {
 		Self::generate_whitelisted_callers_with_role(role, 1u32).map(|r|
@@ -872,6 +918,16 @@ impl<A: DeregistrationCheck, B: DeregistrationCheck<AccountId = A::AccountId>> D
 	}
 }
 
+/// Moves an account between account roles, running the same checks and cleanup as each role's own
+/// registration and deregistration.
+pub trait AccountRoleSwitch<AccountId> {
+	/// Gives up the account's current role. Fails if the account still has state tied to it.
+	fn deregister(account_id: &AccountId, role: AccountRole) -> DispatchResult;
+
+	/// Registers the (now unregistered) account under its new role.
+	fn register(account_id: &AccountId, role: AccountRole) -> DispatchResult;
+}
+
 #[derive(
 	PartialEqNoBound, EqNoBound, CloneNoBound, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug,
 )]
@@ -941,11 +997,33 @@ pub trait FlipBurnInfo {
 	fn take_flip_to_burn() -> AssetAmount;
 }
 
+/// Adds FLIP that was bought by a swap to the funds of a state chain account.
+pub trait FundAccountFromSwap {
+	type AccountId;
+
+	/// Moves the FLIP to the State Chain Gateway. It is added to the account's funds once the
+	/// transfer has been witnessed. Returns the amount that will be added to the account, which
+	/// excludes any egress fee.
+	fn fund_account(
+		account_id: &Self::AccountId,
+		amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError>;
+}
+
 /// The trait implementation is intentionally no-op by default
 pub trait OnDeposit<C: Chain> {
 	fn on_deposit_made(_deposit_details: C::DepositDetails) {}
 }
 
+/// The trait implementation is intentionally no-op by default
+pub trait OnEgressSuccess {
+	/// Called once the transaction containing a scheduled transfer has been witnessed on the
+	/// target chain.
+	fn on_egress_success(_egress_id: EgressId) {}
+}
+
+impl OnEgressSuccess for () {}
+
 pub trait NetworkEnvironmentProvider {
 	fn get_network_environment() -> NetworkEnvironment;
 }
@@ -1060,6 +1138,7 @@ pub trait SwapParameterValidation {
 	fn validate_refund_params(retry_duration: BlockNumber) -> Result<(), DispatchError>;
 	fn validate_broker_fees(
 		broker_fees: &Beneficiaries<Self::AccountId>,
+		source_asset: Asset,
 	) -> Result<(), DispatchError>;
 	fn get_minimum_vault_swap_fee_for_broker(broker_id: &Self::AccountId) -> BasisPoints;
 }
@@ -1203,6 +1282,21 @@ pub trait ChannelIdAllocator {
 	fn allocate_private_channel_id() -> Result<ChannelId, DispatchError>;
 }
 
+/// Manages the deposit channels (one per asset of the chain) that back a broker's private channel.
+pub trait PrivateChannelManager {
+	type AccountId;
+
+	/// Opens the deposit channels of a new private channel for the broker and returns its channel
+	/// id. The channels stay open until the private channel is closed.
+	fn open_private_channel(
+		broker_id: &Self::AccountId,
+		refund_address: ForeignChainAddress,
+	) -> Result<ChannelId, DispatchError>;
+
+	/// Expires the deposit channels of the private channel so they can be recycled.
+	fn close_private_channel(channel_id: ChannelId);
+}
+
 pub trait AffiliateRegistry {
 	type AccountId;
 
@@ -1240,4 +1334,8 @@ pub struct PoolPrice {
 // Used to get price of a given pool
 pub trait PoolPriceProvider {
 	fn pool_price(base_asset: Asset, quote_asset: Asset) -> Result<PoolPrice, DispatchError>;
+
+	/// The time-weighted average tick of the pool's price over the last `window_blocks` blocks,
+	/// if the pool's price history covers the whole window.
+	fn twap_tick(base_asset: Asset, quote_asset: Asset, window_blocks: u32) -> Option<Tick>;
 }
@@ -16,9 +16,12 @@
 
 pub use cf_amm::common::{PoolPairsMap, Side};
 use cf_chains::assets::any::AssetMap;
-use cf_primitives::{Asset, AssetAmount, Tick};
+use cf_primitives::{Asset, AssetAmount, SwapId, Tick};
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::pallet_prelude::{DispatchError, DispatchResult};
+use frame_support::{
+	pallet_prelude::{DispatchError, DispatchResult},
+	weights::Weight,
+};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_std::vec::Vec;
@@ -163,12 +166,36 @@ pub trait PoolApi {
 }
 
 pub trait SwappingApi {
-	/// Process a single leg of a swap, into or from Stable asset. No network fee is taken.
+	/// Process a single leg of a swap, either into or from the Stable asset, or directly between
+	/// two assets that have their own pool (see [Self::pool_exists]). No network fee is taken.
 	fn swap_single_leg(
 		from: Asset,
 		to: Asset,
 		input_amount: AssetAmount,
 	) -> Result<AssetAmount, DispatchError>;
+
+	/// Same as [Self::swap_single_leg], for a leg executed on behalf of the given swaps, allowing
+	/// the liquidity consumed by the leg to be attributed to them.
+	fn swap_single_leg_for_swaps(
+		_swap_ids: &[SwapId],
+		from: Asset,
+		to: Asset,
+		input_amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
+		Self::swap_single_leg(from, to, input_amount)
+	}
+
+	/// The weight of a single call to [Self::swap_single_leg], for accounting for swaps that are
+	/// only simulated.
+	fn swap_single_leg_weight() -> Weight;
+
+	/// Whether there is a pool for swapping directly between the two assets.
+	fn pool_exists(_from: Asset, _to: Asset) -> bool {
+		false
+	}
+
+	/// Records the network fee taken from a swap through the pool for `asset`, for statistics.
+	fn record_network_fee(_asset: Asset, _fee: AssetAmount) {}
 }
 
 pub trait BoostApi {
@@ -41,6 +41,7 @@ pub mod eth_environment_provider;
 pub mod fee_payment;
 pub mod fetches_transfers_limit_provider;
 pub mod flip_burn_info;
+pub mod fund_account_from_swap;
 pub mod funding_info;
 pub mod ingress_egress_fee_handler;
 pub mod key_provider;
@@ -47,6 +47,8 @@ pub struct SwapChannel<C: Chain, T: Chainflip> {
 	pub broker_id: <T as frame_system::Config>::AccountId,
 	pub channel_metadata: Option<CcmChannelMetadata>,
 	pub boost_fee: BasisPoints,
+	pub split_destinations: Vec<(ForeignChainAddress, BasisPoints)>,
+	pub funded_account: Option<<T as frame_system::Config>::AccountId>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
@@ -166,6 +168,81 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 					broker_id,
 					channel_metadata,
 					boost_fee,
+					split_destinations: Default::default(),
+					funded_account: None,
+				});
+			};
+		});
+		Ok((channel_id, deposit_address, 0u32.into(), channel_opening_fee))
+	}
+
+	fn request_split_swap_deposit_address(
+		source_asset: <C as Chain>::ChainAsset,
+		destination_asset: cf_primitives::Asset,
+		destination_addresses: Vec<(ForeignChainAddress, BasisPoints)>,
+		broker_commission: Beneficiaries<Self::AccountId>,
+		broker_id: Self::AccountId,
+		boost_fee: BasisPoints,
+		_refund_params: ChannelRefundParametersDecoded,
+		_dca_params: Option<DcaParameters>,
+	) -> Result<
+		(cf_primitives::ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount),
+		DispatchError,
+	> {
+		let (channel_id, deposit_address, channel_opening_fee) =
+			Self::get_new_deposit_address(SwapOrLp::Swap, source_asset);
+		<Self as MockPalletStorage>::mutate_value(b"SWAP_INGRESS_CHANNELS", |swap_channels| {
+			if swap_channels.is_none() {
+				*swap_channels = Some(vec![]);
+			}
+			if let Some(inner) = swap_channels.as_mut() {
+				inner.push(SwapChannel::<C, T> {
+					deposit_address: deposit_address.clone(),
+					source_asset,
+					destination_asset,
+					destination_address: destination_addresses[0].0.clone(),
+					broker_commission,
+					broker_id,
+					channel_metadata: None,
+					boost_fee,
+					split_destinations: destination_addresses,
+					funded_account: None,
+				});
+			};
+		});
+		Ok((channel_id, deposit_address, 0u32.into(), channel_opening_fee))
+	}
+
+	fn request_account_funding_deposit_address(
+		source_asset: <C as Chain>::ChainAsset,
+		account_id: Self::AccountId,
+		broker_commission: Beneficiaries<Self::AccountId>,
+		broker_id: Self::AccountId,
+		boost_fee: BasisPoints,
+		_refund_params: ChannelRefundParametersDecoded,
+		_dca_params: Option<DcaParameters>,
+	) -> Result<
+		(cf_primitives::ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount),
+		DispatchError,
+	> {
+		let (channel_id, deposit_address, channel_opening_fee) =
+			Self::get_new_deposit_address(SwapOrLp::Swap, source_asset);
+		<Self as MockPalletStorage>::mutate_value(b"SWAP_INGRESS_CHANNELS", |swap_channels| {
+			if swap_channels.is_none() {
+				*swap_channels = Some(vec![]);
+			}
+			if let Some(inner) = swap_channels.as_mut() {
+				inner.push(SwapChannel::<C, T> {
+					deposit_address: deposit_address.clone(),
+					source_asset,
+					destination_asset: any::Asset::Flip,
+					destination_address: ForeignChainAddress::Eth(Default::default()),
+					broker_commission,
+					broker_id,
+					channel_metadata: None,
+					boost_fee,
+					split_destinations: Default::default(),
+					funded_account: Some(account_id),
 				});
 			};
 		});
@@ -0,0 +1,60 @@
+// Copyright 2025 Chainflip Labs GmbH
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MockPallet, MockPalletStorage};
+use crate::FundAccountFromSwap;
+use cf_primitives::AssetAmount;
+use codec::{Decode, Encode};
+use frame_support::sp_runtime::DispatchError;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+pub struct MockFundAccountFromSwap<AccountId>(PhantomData<AccountId>);
+
+impl<AccountId> MockPallet for MockFundAccountFromSwap<AccountId> {
+	const PREFIX: &'static [u8] = b"MockFundAccountFromSwap";
+}
+
+const FUNDED_ACCOUNTS: &[u8] = b"FUNDED_ACCOUNTS";
+const SHOULD_FAIL: &[u8] = b"SHOULD_FAIL";
+
+impl<AccountId: Encode + Decode> MockFundAccountFromSwap<AccountId> {
+	pub fn set_should_fail(should_fail: bool) {
+		Self::put_value(SHOULD_FAIL, should_fail);
+	}
+
+	pub fn funded_accounts() -> Vec<(AccountId, AssetAmount)> {
+		Self::get_value(FUNDED_ACCOUNTS).unwrap_or_default()
+	}
+}
+
+impl<AccountId: Encode + Decode + Clone> FundAccountFromSwap
+	for MockFundAccountFromSwap<AccountId>
+{
+	type AccountId = AccountId;
+
+	fn fund_account(
+		account_id: &Self::AccountId,
+		amount: AssetAmount,
+	) -> Result<AssetAmount, DispatchError> {
+		if Self::get_value(SHOULD_FAIL).unwrap_or_default() {
+			return Err(DispatchError::Other("Funding from swap failed"))
+		}
+		let mut funded_accounts = Self::funded_accounts();
+		funded_accounts.push((account_id.clone(), amount));
+		Self::put_value(FUNDED_ACCOUNTS, funded_accounts);
+		Ok(amount)
+	}
+}
@@ -1,4 +1,4 @@
-use cf_primitives::{Asset, Price};
+use cf_primitives::{Asset, Price, Tick};
 
 use crate::{PoolPrice, PoolPriceProvider};
 
@@ -13,11 +13,16 @@ impl MockPallet for MockPoolPriceApi {
 }
 
 const POOL_PRICES: &[u8] = b"POOL_PRICES";
+const TWAP_TICKS: &[u8] = b"TWAP_TICKS";
 
 impl MockPoolPriceApi {
 	pub fn set_pool_price(base_asset: Asset, quote_asset: Asset, price: Price) {
 		Self::put_storage::<_, Price>(POOL_PRICES, (base_asset, quote_asset), price)
 	}
+
+	pub fn set_twap_tick(base_asset: Asset, quote_asset: Asset, tick: Option<Tick>) {
+		Self::put_storage::<_, Option<Tick>>(TWAP_TICKS, (base_asset, quote_asset), tick)
+	}
 }
 
 impl PoolPriceProvider for MockPoolPriceApi {
@@ -26,4 +31,8 @@ impl PoolPriceProvider for MockPoolPriceApi {
 			.expect("price should have been set");
 		Ok(PoolPrice { sell: price, buy: price })
 	}
+
+	fn twap_tick(base_asset: Asset, quote_asset: Asset, _window_blocks: u32) -> Option<Tick> {
+		Self::get_storage::<_, Option<Tick>>(TWAP_TICKS, (base_asset, quote_asset)).flatten()
+	}
 }
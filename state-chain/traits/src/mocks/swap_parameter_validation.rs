@@ -87,6 +87,7 @@ impl SwapParameterValidation for MockSwapParameterValidation {
 
 	fn validate_broker_fees(
 		broker_fees: &cf_primitives::Beneficiaries<Self::AccountId>,
+		_source_asset: cf_primitives::Asset,
 	) -> Result<(), DispatchError> {
 		let total_bps = broker_fees.iter().fold(0u16, |total, fee| total.saturating_add(fee.bps));
 
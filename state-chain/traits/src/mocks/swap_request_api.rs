@@ -18,7 +18,7 @@ use crate::{
 	swapping::{SwapOutputAction, SwapRequestType},
 	EgressApi, SwapRequestHandler,
 };
-use cf_chains::{Chain, RefundParametersExtended, SwapOrigin};
+use cf_chains::{CcmDepositMetadata, Chain, RefundParametersExtended, SwapOrigin};
 use cf_primitives::{Asset, AssetAmount, Beneficiaries, DcaParameters, SwapRequestId};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -45,11 +45,16 @@ impl<T> MockPallet for MockSwapRequestHandler<T> {
 }
 
 const SWAP_REQUESTS: &[u8] = b"SWAP_REQUESTS";
+const REFUND_CCM_METADATA: &[u8] = b"REFUND_CCM_METADATA";
 
 impl<T> MockSwapRequestHandler<T> {
 	pub fn get_swap_requests() -> Vec<MockSwapRequest> {
 		Self::get_value(SWAP_REQUESTS).unwrap_or_default()
 	}
+
+	pub fn get_refund_ccm_metadata(swap_request_id: SwapRequestId) -> Option<CcmDepositMetadata> {
+		Self::get_storage(REFUND_CCM_METADATA, swap_request_id)
+	}
 }
 
 impl<C: Chain, E: EgressApi<C>> SwapRequestHandler for MockSwapRequestHandler<(C, E)>
@@ -97,7 +102,9 @@ where
 						ccm_deposit_metadata,
 					);
 				},
-				SwapOutputAction::CreditOnChain { .. } => {
+				SwapOutputAction::CreditOnChain { .. } |
+				SwapOutputAction::SplitEgress { .. } |
+				SwapOutputAction::FundAccount { .. } => {
 					// do nothing: this behaviour is tested by the swapping pallet's tests
 				},
 			},
@@ -106,4 +113,11 @@ where
 
 		(id as u64).into()
 	}
+
+	fn set_refund_ccm_metadata(
+		swap_request_id: SwapRequestId,
+		ccm_deposit_metadata: CcmDepositMetadata,
+	) {
+		Self::put_storage(REFUND_CCM_METADATA, swap_request_id, ccm_deposit_metadata);
+	}
 }
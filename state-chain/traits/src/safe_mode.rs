@@ -19,6 +19,39 @@ pub trait SafeMode {
 	const CODE_GREEN: Self;
 }
 
+/// Whether a safe mode has all of its features enabled (green), all of them disabled (red), or
+/// anything in between (amber).
+#[derive(
+	serde::Serialize,
+	serde::Deserialize,
+	codec::Encode,
+	codec::Decode,
+	scale_info::TypeInfo,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	frame_support::pallet_prelude::RuntimeDebug,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SafeModeCode {
+	Green,
+	Amber,
+	Red,
+}
+
+impl SafeModeCode {
+	pub fn of<S: SafeMode + PartialEq>(safe_mode: &S) -> Self {
+		if *safe_mode == S::CODE_GREEN {
+			SafeModeCode::Green
+		} else if *safe_mode == S::CODE_RED {
+			SafeModeCode::Red
+		} else {
+			SafeModeCode::Amber
+		}
+	}
+}
+
 /// Trait for setting the value of current runtime Safe Mode.
 pub trait SetSafeMode<SafeModeType: SafeMode> {
 	fn set_safe_mode(mode: SafeModeType);
@@ -94,6 +127,15 @@ macro_rules! impl_runtime_safe_mode {
 				};
 			}
 
+			impl $runtime_safe_mode {
+				/// The safe mode code of each of the constituent pallet safe modes, by name.
+				pub fn pallet_codes(&self) -> sp_std::vec::Vec<(&'static str, $crate::SafeModeCode)> {
+					sp_std::vec![
+						$( (stringify!($name), $crate::SafeModeCode::of(&self.$name)) ),*
+					]
+				}
+			}
+
 			impl SetSafeMode<$runtime_safe_mode> for $runtime_safe_mode {
 				fn set_safe_mode(mode: Self) {
 					<$root_storage as StorageValue<_>>::put(mode);
@@ -350,11 +392,27 @@ pub(crate) mod test {
 					TestPalletSafeMode2 { flag_1: false, flag_2: true },
 			);
 
+			let safe_mode = <TestRuntimeSafeMode as Get<TestRuntimeSafeMode>>::get();
+			assert_eq!(SafeModeCode::of(&safe_mode), SafeModeCode::Red);
+			assert_eq!(
+				safe_mode.pallet_codes(),
+				vec![
+					("example_a", SafeModeCode::Red),
+					("example_b", SafeModeCode::Red),
+					("pallet", SafeModeCode::Amber),
+					("pallet_2", SafeModeCode::Amber),
+				]
+			);
+
 			<TestRuntimeSafeMode as SetSafeMode<ExampleSafeModeA>>::set_code_green();
 			assert!(
 				<TestRuntimeSafeMode as Get<ExampleSafeModeA>>::get() ==
 					ExampleSafeModeA::CODE_GREEN,
 			);
+			assert_eq!(
+				SafeModeCode::of(&<TestRuntimeSafeMode as Get<TestRuntimeSafeMode>>::get()),
+				SafeModeCode::Amber
+			);
 		});
 	}
 }
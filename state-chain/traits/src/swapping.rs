@@ -16,14 +16,16 @@
 
 use cf_chains::{
 	address::{AddressConverter, EncodedAddress},
-	AccountOrAddress, CcmDepositMetadataGeneric, Chain, ForeignChainAddress,
+	AccountOrAddress, CcmDepositMetadata, CcmDepositMetadataGeneric, Chain, ForeignChainAddress,
 	RefundParametersExtended, SwapOrigin,
 };
 use cf_primitives::{
-	Asset, AssetAmount, Beneficiaries, BlockNumber, DcaParameters, Price, SwapRequestId,
+	Asset, AssetAmount, BasisPoints, Beneficiaries, BlockNumber, DcaParameters, Price,
+	SwapRequestId,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum SwapType {
@@ -41,6 +43,16 @@ pub enum SwapOutputActionGeneric<Address, AccountId> {
 	CreditOnChain {
 		account_id: AccountId,
 	},
+	/// Egress the output to several addresses on the same chain, each receiving the given share
+	/// (in basis points) of the output. The shares add up to 100%.
+	SplitEgress {
+		output_addresses: Vec<(Address, BasisPoints)>,
+	},
+	/// Add the output, which must be FLIP, to the funds of the given account, as if the account
+	/// had been funded via the State Chain Gateway.
+	FundAccount {
+		account_id: AccountId,
+	},
 }
 
 pub type SwapOutputAction<AccountId> = SwapOutputActionGeneric<ForeignChainAddress, AccountId>;
@@ -61,6 +73,17 @@ impl<AccountId> SwapRequestType<AccountId> {
 						},
 					SwapOutputAction::CreditOnChain { account_id } =>
 						SwapOutputActionEncoded::CreditOnChain { account_id },
+					SwapOutputAction::SplitEgress { output_addresses } =>
+						SwapOutputActionEncoded::SplitEgress {
+							output_addresses: output_addresses
+								.into_iter()
+								.map(|(address, share)| {
+									(Converter::to_encoded_address(address), share)
+								})
+								.collect(),
+						},
+					SwapOutputAction::FundAccount { account_id } =>
+						SwapOutputActionEncoded::FundAccount { account_id },
 				},
 			},
 		}
@@ -91,6 +114,13 @@ pub trait SwapRequestHandler {
 		origin: SwapOrigin<Self::AccountId>,
 	) -> SwapRequestId;
 
+	/// Attaches a CCM to any refund egress of the given swap request, so that the refund can be
+	/// handled programmatically by the receiving contract.
+	fn set_refund_ccm_metadata(
+		swap_request_id: SwapRequestId,
+		ccm_deposit_metadata: CcmDepositMetadata,
+	);
+
 	fn init_network_fee_swap_request(
 		input_asset: Asset,
 		input_amount: AssetAmount,